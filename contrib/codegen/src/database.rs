@@ -95,6 +95,13 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
                 <#pool>::get_one(&__rocket).await.map(Self)
             }
 
+            /// Returns a snapshot of the state of the associated connection
+            /// pool. Returns `Some` as long as `Self::fairing()` has been
+            /// attached.
+            pub fn pool_stats(__rocket: &::rocket::Rocket) -> Option<#databases::PoolStats> {
+                <#pool>::get_stats(&__rocket)
+            }
+
             /// Runs the provided closure on a thread from a threadpool. The
             /// closure will be passed an `&mut r2d2::PooledConnection`.
             /// `.await`ing the return value of this function yields the value
@@ -108,6 +115,12 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
             }
         }
 
+        impl #databases::Database for #guard_type {
+            const NAME: &'static str = #name;
+
+            type Connection = #conn_type;
+        }
+
         #[::rocket::async_trait]
         impl<'a, 'r> #request::FromRequest<'a, 'r> for #guard_type {
             type Error = ();
@@ -0,0 +1,497 @@
+//! Access logging for all requests in a standard log format.
+//!
+//! The [`AccessLog`] fairing records one line per request, after the response
+//! has been produced, in one of the following formats:
+//!
+//!   * [`LogFormat::Common`]: the [Common Log Format] used by Apache and nginx.
+//!   * [`LogFormat::Combined`]: the Common Log Format followed by the referer
+//!     and user agent.
+//!   * [`LogFormat::Json`]: one JSON object per line.
+//!
+//! In the `Common` and `Combined` formats, the request line contains the
+//! method and URI but not the HTTP protocol version, which Rocket does not
+//! expose. Each line is additionally suffixed with the request ID, the latency
+//! in milliseconds, and the name of the matched route, if any, in that order.
+//!
+//! [Common Log Format]: https://httpd.apache.org/docs/current/logs.html#common
+//!
+//! By default, the byte count is the size of the response body as produced,
+//! which may exceed what the client received if it disconnected mid-body.
+//! With [`AccessLog::on_completion()`], lines are instead written once the
+//! response has been written, with the number of body bytes actually sent;
+//! in the `Json` format, the error that cut the response short, if any, is
+//! recorded in an `error` field.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `access_log` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["access_log"]
+//! ```
+//!
+//! # Usage
+//!
+//! Attach an [`AccessLog`] to the application. By default, lines are written
+//! to standard output in the `Combined` format:
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::access_log::{AccessLog, LogFormat};
+//!
+//! # let _ = {
+//! rocket::ignite()
+//!     .attach(AccessLog::new(LogFormat::Json).to_writer(std::io::stderr()))
+//! # };
+//! ```
+//!
+//! Lines are handed to a dedicated writer thread, so a slow destination never
+//! blocks request handling.
+//!
+//! # Configuration
+//!
+//! Alternatively, attach the fairing returned by [`AccessLog::fairing()`],
+//! which is configured via the `access_log` configuration parameter, a
+//! dictionary with the following keys, all of which are optional:
+//!
+//! | key             | type            | default                             |
+//! |-----------------|-----------------|-------------------------------------|
+//! | `format`        | string          | `"combined"`                        |
+//! | `path`          | string          | none: lines go to standard output   |
+//! | `max_size`      | integer (bytes) | none: never rotate by size          |
+//! | `max_age`       | integer (secs)  | none: never rotate by age           |
+//! | `on_completion` | boolean         | `false`                             |
+//!
+//! The `format` is one of `"common"`, `"combined"`, or `"json"`. When `path`
+//! is set, lines are appended to the file at `path`. Once writing a line would
+//! grow the file beyond `max_size` bytes, or `max_age` seconds after the file
+//! was opened, the file is rotated: it is renamed to `path` suffixed with the
+//! current UTC time, as in `access.log.20201016120000`, and a new file is
+//! started. For example, in `Rocket.toml`:
+//!
+//! ```toml
+//! [default.access_log]
+//! format = "json"
+//! path = "logs/access.log"
+//! max_size = 10_485_760
+//! max_age = 86_400
+//! ```
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::access_log::AccessLog;
+//!
+//! # let _ = {
+//! rocket::ignite().attach(AccessLog::fairing())
+//! # };
+//! ```
+//!
+//! # Request IDs
+//!
+//! The request ID is taken from the incoming `X-Request-Id` header if present.
+//! Otherwise, a unique, process-local ID is generated. The ID is available to
+//! handlers via the [`RequestId`] request guard.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use rocket::{Rocket, Request, Response, Data};
+use rocket::http::Status;
+use rocket::response::Completion;
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::request::{self, FromRequest, Outcome};
+use rocket::futures::channel::mpsc;
+use rocket::futures::executor::block_on_stream;
+
+/// The format used by [`AccessLog`] to write log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Apache common log format.
+    Common,
+    /// Apache combined log format: common plus referer and user agent.
+    Combined,
+    /// One JSON object per line.
+    Json,
+}
+
+/// The ID of a request as seen by [`AccessLog`].
+///
+/// This is the value of the incoming `X-Request-Id` header, if there is one,
+/// or a unique, generated ID otherwise. As a request guard, it never fails.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    fn of(request: &Request<'_>) -> &RequestId {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        request.local_cache(|| match request.headers().get_one("X-Request-Id") {
+            Some(id) if !id.is_empty() => RequestId(id.to_string()),
+            _ => RequestId(format!("{:016x}", NEXT_ID.fetch_add(1, Ordering::Relaxed))),
+        })
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a RequestId {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(RequestId::of(request))
+    }
+}
+
+/// Request-local start time of a request.
+struct Start(Option<Instant>);
+
+/// Configuration for the fairing returned by [`AccessLog::fairing()`].
+///
+/// See the [module level documentation](crate::access_log#configuration) for
+/// details on each parameter.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct AccessLogConfig {
+    /// The format of log lines.
+    pub format: LogFormat,
+    /// The file to append lines to. Standard output when `None`.
+    pub path: Option<PathBuf>,
+    /// The size, in bytes, beyond which the file is rotated, if any.
+    pub max_size: Option<u64>,
+    /// The number of seconds after which the file is rotated, if any.
+    pub max_age: Option<u64>,
+    /// Whether lines are written once responses have been written.
+    pub on_completion: bool,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        AccessLogConfig {
+            format: LogFormat::Combined,
+            path: None,
+            max_size: None,
+            max_age: None,
+            on_completion: false,
+        }
+    }
+}
+
+/// The sending half of the channel to the thread writing log lines.
+struct Writer(mpsc::UnboundedSender<String>);
+
+impl Writer {
+    fn spawn<W: Write + Send + 'static>(mut writer: W) -> Writer {
+        let (tx, rx) = mpsc::unbounded::<String>();
+        thread::spawn(move || {
+            for line in block_on_stream(rx) {
+                if let Err(e) = writer.write_all(line.as_bytes()).and_then(|_| writer.flush()) {
+                    warn!("Access Log: failed to write log line: {}", e);
+                }
+            }
+        });
+
+        Writer(tx)
+    }
+}
+
+/// A log file that is rotated once it grows too large or too old.
+struct LogFile {
+    path: PathBuf,
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    file: File,
+    size: u64,
+    opened: Instant,
+}
+
+impl LogFile {
+    fn open(path: PathBuf, max_size: Option<u64>, max_age: Option<Duration>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(LogFile { path, max_size, max_age, file, size, opened: Instant::now() })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let stamp = time::OffsetDateTime::now_utc().format("%Y%m%d%H%M%S");
+        let mut rotated = PathBuf::from(format!("{}.{}", self.path.display(), stamp));
+        let mut n = 1;
+        while rotated.exists() {
+            rotated = PathBuf::from(format!("{}.{}.{}", self.path.display(), stamp, n));
+            n += 1;
+        }
+
+        self.file.flush()?;
+        fs::rename(&self.path, &rotated)?;
+        *self = LogFile::open(self.path.clone(), self.max_size, self.max_age)?;
+        Ok(())
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let too_large = self.max_size
+            .map_or(false, |max| self.size > 0 && self.size + buf.len() as u64 > max);
+
+        let too_old = self.max_age.map_or(false, |max| self.opened.elapsed() >= max);
+        if too_large || too_old {
+            self.rotate()?;
+        }
+
+        let n = self.file.write(buf)?;
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// A fairing that writes an access log line for every request.
+///
+/// See the [module level documentation](crate::access_log) for details.
+pub struct AccessLog {
+    format: LogFormat,
+    writer: Writer,
+    on_completion: bool,
+}
+
+impl Default for AccessLog {
+    /// Returns an `AccessLog` writing `Combined` lines to standard output.
+    fn default() -> Self {
+        AccessLog::new(LogFormat::Combined)
+    }
+}
+
+impl AccessLog {
+    /// Returns an `AccessLog` writing lines in `format` to standard output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::access_log::{AccessLog, LogFormat};
+    ///
+    /// let log = AccessLog::new(LogFormat::Common);
+    /// ```
+    pub fn new(format: LogFormat) -> Self {
+        AccessLog { format, writer: Writer::spawn(io::stdout()), on_completion: false }
+    }
+
+    /// Returns a fairing that attaches an `AccessLog` configured via the
+    /// `access_log` configuration parameter. See the [module level
+    /// documentation](crate::access_log#configuration) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::access_log::AccessLog;
+    ///
+    /// let rocket = rocket::ignite().attach(AccessLog::fairing());
+    /// ```
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_attach("Access Log", |rocket: Rocket| async move {
+            let config = match rocket.figment().extract_inner::<AccessLogConfig>("access_log") {
+                Ok(config) => config,
+                Err(e) if e.missing() => AccessLogConfig::default(),
+                Err(e) => {
+                    rocket::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            let mut log = AccessLog::new(config.format);
+            if let Some(ref path) = config.path {
+                let max_age = config.max_age.map(Duration::from_secs);
+                log = match log.to_file(path, config.max_size, max_age) {
+                    Ok(log) => log,
+                    Err(e) => {
+                        error!("Access Log: failed to open {}: {}", path.display(), e);
+                        return Err(rocket);
+                    }
+                };
+            }
+
+            if config.on_completion {
+                log = log.on_completion();
+            }
+
+            Ok(rocket.attach(log))
+        })
+    }
+
+    /// Sets the destination of log lines to `writer`. Lines are written from
+    /// a dedicated thread.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::access_log::{AccessLog, LogFormat};
+    ///
+    /// let log = AccessLog::new(LogFormat::Json).to_writer(std::io::stderr());
+    /// ```
+    pub fn to_writer<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        self.writer = Writer::spawn(writer);
+        self
+    }
+
+    /// Sets the destination of log lines to the file at `path`, creating it
+    /// if it doesn't exist and appending to it otherwise. The file is rotated
+    /// once writing a line would grow it beyond `max_size` bytes or once
+    /// `max_age` has passed since it was opened, if either is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use rocket_contrib::access_log::{AccessLog, LogFormat};
+    ///
+    /// let day = Duration::from_secs(24 * 60 * 60);
+    /// let log = AccessLog::new(LogFormat::Common)
+    ///     .to_file("access.log", Some(10 << 20), Some(day))
+    ///     .expect("access log file");
+    /// ```
+    pub fn to_file<P: AsRef<Path>>(
+        self,
+        path: P,
+        max_size: Option<u64>,
+        max_age: Option<Duration>,
+    ) -> io::Result<Self> {
+        let file = LogFile::open(path.as_ref().to_path_buf(), max_size, max_age)?;
+        Ok(self.to_writer(file))
+    }
+
+    /// Writes log lines once responses have been written to the client rather
+    /// than when they are produced, logging the number of body bytes actually
+    /// sent and, in the `Json` format, the error that ended writing early.
+    ///
+    /// Responses dispatched via a local client are never written to a
+    /// connection and so are not logged in this mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::access_log::{AccessLog, LogFormat};
+    ///
+    /// let log = AccessLog::new(LogFormat::Json).on_completion();
+    /// ```
+    pub fn on_completion(mut self) -> Self {
+        self.on_completion = true;
+        self
+    }
+
+    fn write(&self, mut line: String) {
+        line.push('\n');
+        if self.writer.0.unbounded_send(line).is_err() {
+            warn!("Access Log: log writer has stopped.");
+        }
+    }
+
+    fn line(
+        &self,
+        req: &Request<'_>,
+        status: Status,
+        bytes: Option<u64>,
+        error: Option<&io::Error>,
+    ) -> String {
+        let latency = req.local_cache(|| Start(None)).0
+            .map(|start| start.elapsed().as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+
+        let client = req.client_ip().map(|ip| ip.to_string());
+        let client = client.as_deref().unwrap_or("-");
+        let id = RequestId::of(req);
+        let route = req.route().and_then(|r| r.name);
+        let referer = req.headers().get_one("Referer");
+        let agent = req.headers().get_one("User-Agent");
+        let status = status.code;
+
+        if self.format == LogFormat::Json {
+            let time = time::OffsetDateTime::now_utc().format(time::Format::Rfc3339);
+            let mut value = serde_json::json!({
+                "time": time,
+                "client": client,
+                "method": req.method().as_str(),
+                "uri": req.uri().to_string(),
+                "status": status,
+                "bytes": bytes,
+                "latency_ms": latency,
+                "referer": referer,
+                "user_agent": agent,
+                "request_id": id.0,
+                "route": route,
+            });
+
+            if self.on_completion {
+                value["error"] = serde_json::json!(error.map(|e| e.to_string()));
+            }
+
+            return value.to_string();
+        }
+
+        let time = time::OffsetDateTime::now_utc().format("%d/%b/%Y:%H:%M:%S %z");
+        let bytes = bytes.map(|b| b.to_string());
+        let mut line = format!("{} - - [{}] \"{} {}\" {} {}",
+            client, time, req.method(), req.uri(), status,
+            bytes.as_deref().unwrap_or("-"));
+
+        if self.format == LogFormat::Combined {
+            line.push_str(&format!(" {:?} {:?}", referer.unwrap_or("-"), agent.unwrap_or("-")));
+        }
+
+        line.push_str(&format!(" {} {:.3}ms {}", id, latency, route.unwrap_or("-")));
+        line
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        let kind = match self.on_completion {
+            true => Kind::Request | Kind::Complete,
+            false => Kind::Request | Kind::Response,
+        };
+
+        Info { name: "Access Log", kind }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data) {
+        req.local_cache(|| Start(Some(Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let bytes = match res.body_mut() {
+            Some(body) => body.size().await.map(|size| size as u64),
+            None => Some(0),
+        };
+
+        self.write(self.line(req, res.status(), bytes, None));
+    }
+
+    async fn on_complete(&self, req: &Request<'_>, completion: &Completion) {
+        let bytes = Some(completion.bytes_written());
+        self.write(self.line(req, completion.status(), bytes, completion.error()));
+    }
+}
@@ -0,0 +1,157 @@
+//! Request and response body capture for debugging.
+//!
+//! The [`BodyCapture`] fairing logs a truncated copy of the request and
+//! response bodies of requests whose path begins with one of a set of
+//! prefixes. Bodies are recorded as they are read by data guards and written
+//! to the client via [`Data::capture()`] and [`Response::capture_body()`], so
+//! they are neither buffered nor read twice.
+//!
+//! Because bodies routinely contain credentials and personal data, the fairing
+//! is only active in the `debug` profile. In any other profile, it logs a
+//! warning at attach time and does nothing.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `body_capture` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["body_capture"]
+//! ```
+//!
+//! # Usage
+//!
+//! Attach a [`BodyCapture`] naming the path prefixes to capture. Each body is
+//! logged at the `info` level once it has been fully consumed, truncated to
+//! the configured limit, which defaults to 1KiB:
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket::data::ToByteUnit;
+//! use rocket_contrib::body_capture::BodyCapture;
+//!
+//! # let _ = {
+//! rocket::ignite()
+//!     .attach(BodyCapture::new(&["/api", "/webhooks"]).limit(4.kibibytes()))
+//! # };
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rocket::{Rocket, Request, Response, Data, Config};
+use rocket::data::{ByteUnit, Capture, ToByteUnit};
+use rocket::fairing::{Fairing, Info, Kind};
+
+/// A fairing that logs truncated request and response bodies.
+///
+/// See the [module level documentation](crate::body_capture) for details.
+#[derive(Debug)]
+pub struct BodyCapture {
+    prefixes: Vec<String>,
+    limit: ByteUnit,
+    enabled: AtomicBool,
+}
+
+/// Request-local capture of the request body.
+struct RequestCapture(Option<Capture>);
+
+impl BodyCapture {
+    /// Returns a `BodyCapture` for requests whose path begins with any of
+    /// `prefixes`. A prefix of `/` captures every request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::body_capture::BodyCapture;
+    ///
+    /// let capture = BodyCapture::new(&["/api"]);
+    /// ```
+    pub fn new<S: AsRef<str>>(prefixes: &[S]) -> Self {
+        BodyCapture {
+            prefixes: prefixes.iter().map(|s| s.as_ref().to_string()).collect(),
+            limit: 1.kibibytes(),
+            enabled: AtomicBool::new(true),
+        }
+    }
+
+    /// Sets the maximum number of bytes of each body to log to `limit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::ToByteUnit;
+    /// use rocket_contrib::body_capture::BodyCapture;
+    ///
+    /// let capture = BodyCapture::new(&["/"]).limit(256.bytes());
+    /// ```
+    pub fn limit(mut self, limit: ByteUnit) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn matches(&self, req: &Request<'_>) -> bool {
+        if !self.enabled.load(Ordering::Acquire) {
+            return false;
+        }
+
+        let path = req.uri().path();
+        self.prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            path.starts_with(prefix)
+                && (path.len() == prefix.len() || path[prefix.len()..].starts_with('/'))
+        })
+    }
+}
+
+fn log_body(kind: &str, request_line: &str, capture: &Capture) {
+    let bytes = capture.bytes();
+    let body = String::from_utf8_lossy(&bytes);
+    let truncated = match capture.is_truncated() {
+        true => format!(" [truncated, {} bytes total]", capture.total()),
+        false => String::new(),
+    };
+
+    info!("{} body for {}: {:?}{}", kind, request_line, body, truncated);
+}
+
+#[rocket::async_trait]
+impl Fairing for BodyCapture {
+    fn info(&self) -> Info {
+        Info { name: "Body Capture", kind: Kind::Attach | Kind::Request | Kind::Response }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        if rocket.config().profile != Config::DEBUG_PROFILE {
+            warn!("Body Capture: disabled outside of the `{}` profile.", Config::DEBUG_PROFILE);
+            self.enabled.store(false, Ordering::Release);
+        }
+
+        Ok(rocket)
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+        if self.matches(req) {
+            req.local_cache(|| RequestCapture(data.capture(self.limit)));
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if !self.matches(req) {
+            return;
+        }
+
+        let request_line = format!("{} {}", req.method(), req.uri());
+        if let Some(capture) = &req.local_cache(|| RequestCapture(None)).0 {
+            let line = request_line.clone();
+            capture.on_finish(move |capture| log_body("Request", &line, capture));
+        }
+
+        if let Some(capture) = res.capture_body(self.limit) {
+            capture.on_finish(move |capture| log_body("Response", &request_line, capture));
+        }
+    }
+}
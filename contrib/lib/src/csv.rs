@@ -0,0 +1,287 @@
+//! Automatic CSV (de)serialization support.
+//!
+//! See the [`Csv`](crate::csv::Csv) type for further details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `csv` feature is enabled. Enable it
+//! in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["csv"]
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use std::io::{self, Cursor, Write};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use rocket::request::Request;
+use rocket::outcome::Outcome::*;
+use rocket::data::{self, Data, ByteUnit, FromData};
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Response, Responder};
+use rocket::tokio::io::{AsyncRead, ReadBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+#[doc(inline)]
+pub use csv_crate::Error;
+
+/// The CSV type: implements [`FromData`] and [`Responder`], allowing you to
+/// easily consume and respond with CSV.
+///
+/// ## Receiving CSV
+///
+/// To receive CSV data, add a `data` parameter of type `Csv<Vec<T>>` to your
+/// route, where `T` implements [`DeserializeOwned`] from [`serde`]. The first
+/// row of the body is expected to be a header row naming the fields of `T`;
+/// every subsequent row is deserialized into a `T`.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type Sale = (String, usize);
+/// use rocket_contrib::csv::Csv;
+///
+/// #[post("/sales", format = "csv", data = "<sales>")]
+/// fn import(sales: Csv<Vec<Sale>>) -> String {
+///     format!("imported {} sales", sales.len())
+/// }
+/// ```
+///
+/// If the body fails to parse, the guard fails with a status of `422
+/// Unprocessable Entity` if a row doesn't match `T` and `400 Bad Request`
+/// otherwise.
+///
+/// ## Sending CSV
+///
+/// To respond with CSV data, return a `Csv<I>`, where `I` is an iterator, or
+/// any other `IntoIterator`, of records that implement [`Serialize`]. The
+/// response has a Content-Type of `text/csv` and a streamed body to which
+/// records are written as they are produced by the iterator. By default, the
+/// first row is a header row derived from the field names of the records, and
+/// fields are separated by commas; use [`Csv::headers()`] and
+/// [`Csv::delimiter()`] to change either.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::csv::Csv;
+///
+/// #[get("/squares")]
+/// fn squares() -> Csv<Vec<(usize, usize)>> {
+///     let squares = (1..=10).map(|n| (n, n * n)).collect();
+///     Csv::new(squares).headers(false).delimiter(b';')
+/// }
+/// ```
+///
+/// If a record fails to serialize, the error is logged and the response ends
+/// abruptly.
+///
+/// ## Incoming Data Limits
+///
+/// The default size limit for incoming CSV data is 1MiB. The limit can be
+/// changed by setting the `limits.csv` configuration parameter. For instance,
+/// to increase the CSV limit to 5MiB for all environments, you may add the
+/// following to your `Rocket.toml`:
+///
+/// ```toml
+/// [global.limits]
+/// csv = 5242880
+/// ```
+#[derive(Debug)]
+pub struct Csv<T> {
+    records: T,
+    delimiter: u8,
+    has_headers: bool,
+}
+
+/// An error returned by the [`Csv`] data guard when incoming data fails to
+/// deserialize as CSV.
+#[derive(Debug)]
+pub enum CsvError {
+    /// An I/O error occurred while reading the incoming request data.
+    Io(io::Error),
+
+    /// The client's data was received successfully but failed to parse as
+    /// valid CSV or as the requested type.
+    Parse(Error),
+}
+
+const DEFAULT_LIMIT: ByteUnit = ByteUnit::Mebibyte(1);
+
+impl<T> Csv<T> {
+    /// Wraps `records` with the default options: a header row and a
+    /// delimiter of `,`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket_contrib::csv::Csv;
+    /// let csv = Csv::new(vec![("a", 1), ("b", 2)]);
+    /// assert_eq!(csv.len(), 2);
+    /// ```
+    pub fn new(records: T) -> Self {
+        Csv { records, delimiter: b',', has_headers: true }
+    }
+
+    /// Sets the field delimiter of the response to `delimiter`.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the response begins with a header row. This is enabled by
+    /// default.
+    pub fn headers(mut self, enabled: bool) -> Self {
+        self.has_headers = enabled;
+        self
+    }
+
+    /// Consumes the CSV wrapper and returns the wrapped records.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket_contrib::csv::Csv;
+    /// let csv = Csv::new(vec![1, 2, 3]);
+    /// assert_eq!(csv.into_inner(), vec![1, 2, 3]);
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.records
+    }
+}
+
+#[rocket::async_trait]
+impl<T: DeserializeOwned + Send + 'static> FromData for Csv<Vec<T>> {
+    type Error = CsvError;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        let size_limit = req.limits().get("csv").unwrap_or(DEFAULT_LIMIT);
+        let bytes = match data.open(size_limit).stream_to_vec().await {
+            Ok(bytes) => bytes,
+            Err(e) => return Failure((Status::BadRequest, CsvError::Io(e))),
+        };
+
+        let mut reader = csv_crate::Reader::from_reader(&bytes[..]);
+        match reader.deserialize().collect::<Result<Vec<T>, _>>() {
+            Ok(records) => Success(Csv::new(records)),
+            Err(e) => {
+                error_!("Couldn't parse CSV body: {:?}", e);
+                match e.kind() {
+                    csv_crate::ErrorKind::Deserialize { .. } => {
+                        Failure((Status::UnprocessableEntity, CsvError::Parse(e)))
+                    }
+                    _ => Failure((Status::BadRequest, CsvError::Parse(e))),
+                }
+            }
+        }
+    }
+}
+
+/// A `Write`r whose contents can be taken while a `csv::Writer` owns it.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().expect("unpoisoned"))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().expect("unpoisoned").write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializes each record of an iterator as CSV, one record at a time.
+struct CsvReader<I> {
+    records: Box<I>,
+    writer: csv_crate::Writer<SharedBuffer>,
+    buffer: SharedBuffer,
+    pending: Cursor<Vec<u8>>,
+}
+
+impl<I: Iterator> AsyncRead for CsvReader<I> where I::Item: Serialize {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if self.pending.position() < self.pending.get_ref().len() as u64 {
+                return Pin::new(&mut self.pending).poll_read(cx, buf);
+            }
+
+            let record = match self.records.next() {
+                Some(record) => record,
+                None => return Poll::Ready(Ok(())),
+            };
+
+            let this = &mut *self;
+            this.writer.serialize(record)
+                .and_then(|_| this.writer.flush().map_err(Error::from))
+                .map_err(|e| {
+                    error_!("CSV record failed to serialize: {:?}", e);
+                    io::Error::new(io::ErrorKind::Other, e)
+                })?;
+
+            this.pending = Cursor::new(this.buffer.take());
+        }
+    }
+}
+
+/// Serializes the wrapped records into CSV. Returns a response with
+/// Content-Type CSV and a streamed body to which each record is written as it
+/// is produced.
+impl<'r, 'o: 'r, I> Responder<'r, 'o> for Csv<I>
+    where I: IntoIterator, I::IntoIter: Send + 'o, I::Item: Serialize
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let buffer = SharedBuffer::default();
+        let writer = csv_crate::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .from_writer(buffer.clone());
+
+        let reader = CsvReader {
+            records: Box::new(self.records.into_iter()),
+            writer,
+            buffer,
+            pending: Cursor::new(vec![]),
+        };
+
+        Response::build()
+            .header(ContentType::CSV)
+            .streamed_body(reader)
+            .ok()
+    }
+}
+
+impl<T> Deref for Csv<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.records
+    }
+}
+
+impl<T> DerefMut for Csv<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.records
+    }
+}
@@ -132,6 +132,11 @@
 //!
 //!   * `pool_size` - the size of the pool, i.e., the number of connections to
 //!     pool (defaults to the configured number of workers * 2)
+//!   * `timeout` - how long to wait, in seconds, for a connection before
+//!     failing (defaults to `5`)
+//!   * `health_check_interval` - how often, in seconds, to check that the
+//!     database is reachable; the result is reported by
+//!     [`PoolStats::healthy`] (defaults to `0`, disabled)
 //!
 //! Additional options may be required or supported by other adapters.
 //!
@@ -191,18 +196,26 @@
 //! retrieves a connection from the database pool or fails with a
 //! `Status::ServiceUnavailable` if connecting to the database times out.
 //!
-//! The macro will also generate two inherent methods on the decorated type:
+//! The macro will also generate three inherent methods on the decorated type:
 //!
 //!   * `fn fairing() -> impl Fairing`
 //!
 //!      Returns a fairing that initializes the associated database connection
-//!      pool.
+//!      pool. Ignition fails if the pool cannot be initialized or a
+//!      connection to the database cannot be established.
 //!
 //!   * `async fn get_one(&Rocket) -> Option<Self>`
 //!
 //!     Retrieves a connection wrapper from the configured pool. Returns `Some`
 //!     as long as `Self::fairing()` has been attached.
 //!
+//!   * `fn pool_stats(&Rocket) -> Option<PoolStats>`
+//!
+//!     Returns a snapshot of the state of the pool, including its size, its
+//!     number of idle connections, the average time spent waiting for a
+//!     connection, and its health. Returns `Some` as long as
+//!     `Self::fairing()` has been attached. See [`PoolStats`].
+//!
 //! The attribute can only be applied to unit-like structs with one type. The
 //! internal type of the structure must implement [`Poolable`].
 //!
@@ -306,6 +319,63 @@
 //! # }
 //! ```
 //!
+//! ## Transactions
+//!
+//! To run all of a request's database work in a single transaction, use the
+//! [`Transaction`] request guard in place of the database type. The
+//! transaction is committed if the response is not an error and rolled back
+//! otherwise:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_contrib;
+//! #
+//! # #[cfg(feature = "diesel_sqlite_pool")]
+//! # mod test {
+//! # use rocket_contrib::databases::diesel;
+//! use rocket_contrib::databases::Transaction;
+//!
+//! #[database("my_db")]
+//! struct MyDatabase(diesel::SqliteConnection);
+//!
+//! #[post("/")]
+//! async fn my_handler(tx: Transaction<MyDatabase>) {
+//!     tx.run(|c| { /* ... */ }).await
+//! }
+//! # }
+//! ```
+//!
+//! Transactions are available for database types whose connection type
+//! implements [`Transactional`].
+//!
+//! ## Migrations
+//!
+//! Schema migrations, `.sql` files in a directory, can be applied at ignition
+//! by attaching the [`Migrations`] fairing after the database's fairing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_contrib;
+//! #
+//! # #[cfg(feature = "sqlite_pool")]
+//! # mod test {
+//! use rocket_contrib::databases::{rusqlite, Migrations};
+//!
+//! #[database("my_db")]
+//! struct MyDatabase(rusqlite::Connection);
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .attach(MyDatabase::fairing())
+//!         .attach(Migrations::<MyDatabase>::fairing("migrations"))
+//! }
+//! # }
+//! ```
+//!
+//! Migrations are available for database types whose connection type
+//! implements [`Migratable`].
+//!
 //! # Database Support
 //!
 //! Built-in support is provided for many popular databases and drivers. Support
@@ -357,6 +427,12 @@
 //! [`FromRequest`]: rocket::request::FromRequest
 //! [request guards]: rocket::request::FromRequest
 //! [`Poolable`]: crate::databases::Poolable
+//! [`Transaction`]: crate::databases::Transaction
+//! [`Transactional`]: crate::databases::Transactional
+//! [`PoolStats`]: crate::databases::PoolStats
+//! [`Migrations`]: crate::databases::Migrations
+//! [`Migratable`]: crate::databases::Migratable
+//! [`PoolStats::healthy`]: crate::databases::PoolStats::healthy
 
 pub extern crate r2d2;
 
@@ -368,15 +444,18 @@ pub extern crate r2d2;
 pub extern crate diesel;
 
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use rocket::fairing::{AdHoc, Fairing};
+use rocket::{Rocket, Response};
+use rocket::fairing::{Fairing, Info, Kind};
 use rocket::request::{Request, Outcome, FromRequest};
-use rocket::outcome::IntoOutcome;
+use rocket::outcome::{IntoOutcome, try_outcome};
 use rocket::http::Status;
 
 use rocket::tokio::sync::{OwnedSemaphorePermit, Semaphore, Mutex};
-use rocket::tokio::time::timeout;
+use rocket::tokio::time::{interval, timeout};
 
 use self::r2d2::ManageConnection;
 
@@ -700,6 +779,267 @@ impl Poolable for memcache::Client {
     }
 }
 
+/// Trait implemented by database adapters that support transactions.
+///
+/// Implementing `Transactional` for a [`Poolable`] type allows its database
+/// types to be used with the [`Transaction`] request guard.
+///
+/// # Provided Implementations
+///
+/// Implementations of `Transactional` are provided for all of the provided
+/// [`Poolable`] types except `memcache::Client`.
+///
+/// # Example
+///
+/// An implementation for a hypothetical `foo::Connection` which executes SQL
+/// via `execute()`:
+///
+/// ```rust
+/// # mod foo {
+/// #     use std::fmt;
+/// #     use rocket_contrib::databases::{r2d2, Poolable, PoolResult};
+/// #     #[derive(Debug)] pub struct Error;
+/// #     impl fmt::Display for Error {
+/// #         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+/// #     }
+/// #
+/// #     pub struct Connection;
+/// #     pub struct ConnectionManager;
+/// #
+/// #     impl Connection {
+/// #         pub fn execute(&mut self, sql: &str) -> Result<(), Error> { Ok(()) }
+/// #     }
+/// #
+/// #     impl self::r2d2::ManageConnection for ConnectionManager {
+/// #          type Connection = Connection;
+/// #          type Error = Error;
+/// #          fn connect(&self) -> Result<Connection, Error> { panic!(()) }
+/// #          fn is_valid(&self, _: &mut Connection) -> Result<(), Error> { panic!() }
+/// #          fn has_broken(&self, _: &mut Connection) -> bool { panic!() }
+/// #     }
+/// #
+/// #     impl Poolable for Connection {
+/// #         type Manager = ConnectionManager;
+/// #         type Error = Error;
+/// #         fn pool(_: &str, _: &rocket::Rocket) -> PoolResult<Self> { panic!() }
+/// #     }
+/// # }
+/// use rocket_contrib::databases::Transactional;
+///
+/// impl Transactional for foo::Connection {
+///     type TransactionError = foo::Error;
+///
+///     fn begin(&mut self) -> Result<(), foo::Error> {
+///         self.execute("BEGIN")
+///     }
+///
+///     fn commit(&mut self) -> Result<(), foo::Error> {
+///         self.execute("COMMIT")
+///     }
+///
+///     fn rollback(&mut self) -> Result<(), foo::Error> {
+///         self.execute("ROLLBACK")
+///     }
+/// }
+/// ```
+pub trait Transactional: Poolable {
+    /// The error type returned when beginning or ending a transaction fails.
+    type TransactionError: std::fmt::Display;
+
+    /// Begins a transaction.
+    fn begin(&mut self) -> Result<(), Self::TransactionError>;
+
+    /// Commits the transaction begun by [`Transactional::begin()`].
+    fn commit(&mut self) -> Result<(), Self::TransactionError>;
+
+    /// Rolls back the transaction begun by [`Transactional::begin()`].
+    fn rollback(&mut self) -> Result<(), Self::TransactionError>;
+}
+
+macro_rules! impl_diesel_transactional {
+    ($feature:literal, $conn:ty) => (
+        #[cfg(feature = $feature)]
+        impl Transactional for $conn {
+            type TransactionError = diesel::result::Error;
+
+            fn begin(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                self.transaction_manager().begin_transaction(self)
+            }
+
+            fn commit(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                self.transaction_manager().commit_transaction(self)
+            }
+
+            fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+                use diesel::connection::{Connection, TransactionManager};
+                self.transaction_manager().rollback_transaction(self)
+            }
+        }
+    )
+}
+
+impl_diesel_transactional!("diesel_sqlite_pool", diesel::SqliteConnection);
+impl_diesel_transactional!("diesel_postgres_pool", diesel::PgConnection);
+impl_diesel_transactional!("diesel_mysql_pool", diesel::MysqlConnection);
+
+#[cfg(feature = "postgres_pool")]
+impl Transactional for postgres::Client {
+    type TransactionError = postgres::Error;
+
+    fn begin(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("BEGIN")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute("ROLLBACK")
+    }
+}
+
+#[cfg(feature = "mysql_pool")]
+impl Transactional for mysql::Conn {
+    type TransactionError = mysql::Error;
+
+    fn begin(&mut self) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, "START TRANSACTION")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, "COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, "ROLLBACK")
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+impl Transactional for rusqlite::Connection {
+    type TransactionError = rusqlite::Error;
+
+    fn begin(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("BEGIN")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::TransactionError> {
+        self.execute_batch("ROLLBACK")
+    }
+}
+
+/// Trait implemented by database adapters that can run schema migrations.
+///
+/// Implementing `Migratable` for a [`Transactional`] type allows its database
+/// types to be migrated by the [`Migrations`] fairing.
+///
+/// # Provided Implementations
+///
+/// Implementations of `Migratable` are provided for `postgres::Client`,
+/// `mysql::Conn`, and `rusqlite::Connection`. Note that MySQL implicitly
+/// commits DDL statements, so a failed migration's schema changes are not
+/// rolled back there.
+pub trait Migratable: Transactional {
+    /// Executes `sql`, which may contain several statements separated by `;`.
+    fn execute_batch(&mut self, sql: &str) -> Result<(), Self::TransactionError>;
+
+    /// Returns the values of the first column of the rows returned by the
+    /// query `sql`.
+    fn query_strings(&mut self, sql: &str) -> Result<Vec<String>, Self::TransactionError>;
+
+    /// Acquires a database-wide lock, blocking until it is available, so that
+    /// only one instance of an application migrates the database at once. The
+    /// default implementation does nothing.
+    fn lock(&mut self) -> Result<(), Self::TransactionError> {
+        Ok(())
+    }
+
+    /// Releases the lock acquired by [`Migratable::lock()`]. The default
+    /// implementation does nothing.
+    fn unlock(&mut self) -> Result<(), Self::TransactionError> {
+        Ok(())
+    }
+}
+
+// An arbitrary key identifying Rocket's migration lock.
+#[cfg(feature = "postgres_pool")]
+const PG_MIGRATION_LOCK: i64 = 0x526f636b6574;
+
+#[cfg(feature = "postgres_pool")]
+impl Migratable for postgres::Client {
+    fn execute_batch(&mut self, sql: &str) -> Result<(), Self::TransactionError> {
+        self.batch_execute(sql)
+    }
+
+    fn query_strings(&mut self, sql: &str) -> Result<Vec<String>, Self::TransactionError> {
+        Ok(self.query(sql, &[])?.iter().map(|row| row.get(0)).collect())
+    }
+
+    fn lock(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute(&format!("SELECT pg_advisory_lock({})", PG_MIGRATION_LOCK))
+    }
+
+    fn unlock(&mut self) -> Result<(), Self::TransactionError> {
+        self.batch_execute(&format!("SELECT pg_advisory_unlock({})", PG_MIGRATION_LOCK))
+    }
+}
+
+#[cfg(feature = "mysql_pool")]
+impl Migratable for mysql::Conn {
+    fn execute_batch(&mut self, sql: &str) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, sql)
+    }
+
+    fn query_strings(&mut self, sql: &str) -> Result<Vec<String>, Self::TransactionError> {
+        mysql::prelude::Queryable::query(self, sql)
+    }
+
+    fn lock(&mut self) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, "SELECT GET_LOCK('rocket_migrations', -1)")
+    }
+
+    fn unlock(&mut self) -> Result<(), Self::TransactionError> {
+        mysql::prelude::Queryable::query_drop(self, "SELECT RELEASE_LOCK('rocket_migrations')")
+    }
+}
+
+// SQLite locks the entire database for each write transaction, and each
+// migration is applied in one, so no additional lock is needed.
+#[cfg(feature = "sqlite_pool")]
+impl Migratable for rusqlite::Connection {
+    fn execute_batch(&mut self, sql: &str) -> Result<(), Self::TransactionError> {
+        rusqlite::Connection::execute_batch(self, sql)
+    }
+
+    fn query_strings(&mut self, sql: &str) -> Result<Vec<String>, Self::TransactionError> {
+        let mut stmt = self.prepare(sql)?;
+        let rows = stmt.query_map(rusqlite::NO_PARAMS, |row| row.get(0))?;
+        rows.collect()
+    }
+}
+
+/// Trait implemented by database types declared with the `#[database]`
+/// attribute.
+///
+/// This trait is implemented automatically by the `#[database]` attribute and
+/// should not be implemented manually. It ties a database type to its name and
+/// [`Poolable`] connection type for use with [`Transaction`] and
+/// [`Migrations`].
+pub trait Database: Send + Sync + 'static {
+    /// The name of the database in the `databases` configuration.
+    const NAME: &'static str;
+
+    /// The type of the database's connections.
+    type Connection: Poolable;
+}
+
 /// Unstable internal details of generated code for the #[database] attribute.
 ///
 /// This type is implemented here instead of in generated code to ensure all
@@ -708,17 +1048,64 @@ impl Poolable for memcache::Client {
 pub struct ConnectionPool<K, C: Poolable> {
     config: Config,
     // This is an 'Option' so that we can drop the pool in a 'spawn_blocking'.
-    pool: Option<r2d2::Pool<C::Manager>>,
+    pool: Option<Arc<r2d2::Pool<C::Manager>>>,
     semaphore: Arc<Semaphore>,
+    stats: Arc<Stats>,
     _marker: PhantomData<fn() -> K>,
 }
 
+/// A snapshot of the state of a database connection pool.
+///
+/// A snapshot is retrieved via the `pool_stats()` method generated by the
+/// `#[database]` attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket_contrib;
+/// # #[cfg(feature = "diesel_sqlite_pool")]
+/// # mod test {
+/// use rocket_contrib::databases::diesel;
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// fn log_stats(rocket: &rocket::Rocket) {
+///     if let Some(stats) = MyDatabase::pool_stats(rocket) {
+///         println!("{} of {} connections idle", stats.idle, stats.connections);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolStats {
+    /// The maximum number of connections in the pool.
+    pub max_size: u32,
+    /// The number of connections currently open, idle or in use.
+    pub connections: u32,
+    /// The number of idle connections.
+    pub idle: u32,
+    /// The number of connections that have been retrieved from the pool.
+    pub retrieved: u64,
+    /// The average time spent waiting to retrieve a connection.
+    pub average_wait: Duration,
+    /// Whether the database was reachable at the most recent health check, or
+    /// at ignition if health checks are disabled.
+    pub healthy: bool,
+}
+
+#[derive(Default)]
+struct Stats {
+    retrieved: AtomicU64,
+    wait_micros: AtomicU64,
+    healthy: AtomicBool,
+}
+
 impl<K, C: Poolable> Clone for ConnectionPool<K, C> {
     fn clone(&self) -> Self {
         ConnectionPool {
             config: self.config.clone(),
             pool: self.pool.clone(),
             semaphore: self.semaphore.clone(),
+            stats: self.stats.clone(),
             _marker: PhantomData
         }
     }
@@ -756,31 +1143,123 @@ macro_rules! dberr {
     });
 }
 
-impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
-    pub fn fairing(fairing_name: &'static str, db: &'static str) -> impl Fairing {
-        AdHoc::on_attach(fairing_name, move |rocket| async move {
-            let config = match Config::from(db, &rocket) {
-                Ok(config) => config,
-                Err(e) => dberr!("config", db, "{}", e, rocket),
+/// The fairing returned by `ConnectionPool::fairing()`. Initializes the pool
+/// and ends the request's transaction, if any, before the response is sent.
+struct PoolFairing<K, C> {
+    fairing_name: &'static str,
+    db: &'static str,
+    _marker: PhantomData<fn() -> (K, C)>,
+}
+
+#[rocket::async_trait]
+impl<K: 'static, C: Poolable> Fairing for PoolFairing<K, C> {
+    fn info(&self) -> Info {
+        Info { name: self.fairing_name, kind: Kind::Attach | Kind::Response }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let db = self.db;
+        let config = match Config::from(db, &rocket) {
+            Ok(config) => config,
+            Err(e) => dberr!("config", db, "{}", e, rocket),
+        };
+
+        let key = format!("databases.{}.health_check_interval", db);
+        let health_check_interval = match rocket.figment().extract_inner::<u64>(&key) {
+            Ok(secs) => Some(secs).filter(|&secs| secs > 0).map(Duration::from_secs),
+            Err(e) if e.missing() => None,
+            Err(e) => dberr!("config", db, "{}", e, rocket),
+        };
+
+        let pool = match C::pool(db, &rocket) {
+            Ok(pool) => Arc::new(pool),
+            Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
+            Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
+            Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
+        };
+
+        // Verify that the database is reachable before going any further.
+        let duration = Duration::from_secs(config.timeout as u64);
+        let check = pool.clone();
+        if let Err(e) = run_blocking(move || check.get_timeout(duration).map(|_| ())).await {
+            tokio::task::spawn_blocking(move || drop(pool));
+            dberr!("connection", db, "{}", e, rocket);
+        }
+
+        let stats = Arc::new(Stats::default());
+        stats.healthy.store(true, Ordering::Release);
+        if let Some(period) = health_check_interval {
+            spawn_health_check(db, period, duration, &pool, &stats);
+        }
+
+        let pool_size = config.pool_size;
+        Ok(rocket.manage(ConnectionPool::<K, C> {
+            config,
+            pool: Some(pool),
+            semaphore: Arc::new(Semaphore::new(pool_size as usize)),
+            stats,
+            _marker: PhantomData,
+        }))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let pending = match req.local_cache(TransactionSlot::<K, C>::default).take() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        let class = res.status().class();
+        let commit = !class.is_client_error() && !class.is_server_error();
+        if let Err(e) = pending.finish(commit).await {
+            let action = if commit { "commit" } else { "roll back" };
+            error_!("failed to {} database transaction: {}", action, e);
+            if commit {
+                *res = Response::new();
+                res.set_status(Status::InternalServerError);
+            }
+        }
+    }
+}
+
+// Periodically checks that a connection can be retrieved from `pool`, recording
+// the result in `stats`, until the pool is dropped.
+fn spawn_health_check<M: ManageConnection>(
+    db: &'static str,
+    period: Duration,
+    timeout: Duration,
+    pool: &Arc<r2d2::Pool<M>>,
+    stats: &Arc<Stats>,
+) {
+    let (pool, stats): (Weak<_>, Weak<_>) = (Arc::downgrade(pool), Arc::downgrade(stats));
+    tokio::spawn(async move {
+        let mut interval = interval(period);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let (pool, stats) = match (pool.upgrade(), stats.upgrade()) {
+                (Some(pool), Some(stats)) => (pool, stats),
+                _ => break,
             };
 
-            let pool_size = config.pool_size;
-            match C::pool(db, &rocket) {
-                Ok(pool) => Ok(rocket.manage(ConnectionPool::<K, C> {
-                    config,
-                    pool: Some(pool),
-                    semaphore: Arc::new(Semaphore::new(pool_size as usize)),
-                    _marker: PhantomData,
-                })),
-                Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
-                Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
-                Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
+            let healthy = run_blocking(move || pool.get_timeout(timeout).is_ok()).await;
+            if stats.healthy.swap(healthy, Ordering::AcqRel) != healthy {
+                match healthy {
+                    true => rocket::info!("database pool `{}` is healthy again", db),
+                    false => rocket::warn!("database pool `{}` failed its health check", db),
+                }
             }
-        })
+        }
+    });
+}
+
+impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
+    pub fn fairing(fairing_name: &'static str, db: &'static str) -> impl Fairing {
+        PoolFairing::<K, C> { fairing_name, db, _marker: PhantomData }
     }
 
     async fn get(&self) -> Result<Connection<K, C>, ()> {
-        let duration = std::time::Duration::from_secs(self.config.timeout as u64);
+        let start = Instant::now();
+        let duration = Duration::from_secs(self.config.timeout as u64);
         let permit = match timeout(duration, self.semaphore.clone().acquire_owned()).await {
             Ok(p) => p.expect("internal invariant broken: semaphore should not be closed"),
             Err(_) => {
@@ -793,11 +1272,16 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
             .expect("internal invariant broken: self.pool is Some");
 
         match run_blocking(move || pool.get_timeout(duration)).await {
-            Ok(c) => Ok(Connection {
-                connection: Arc::new(Mutex::new(Some(c))),
-                permit: Some(permit),
-                _marker: PhantomData,
-            }),
+            Ok(c) => {
+                let wait = start.elapsed().as_micros() as u64;
+                self.stats.retrieved.fetch_add(1, Ordering::AcqRel);
+                self.stats.wait_micros.fetch_add(wait, Ordering::AcqRel);
+                Ok(Connection {
+                    connection: Arc::new(Mutex::new(Some(c))),
+                    permit: Some(permit),
+                    _marker: PhantomData,
+                })
+            }
             Err(e) => {
                 error_!("failed to get a database connection: {}", e);
                 Err(())
@@ -817,6 +1301,21 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
     pub async fn get_pool(rocket: &rocket::Rocket) -> Option<Self> {
         rocket.state::<Self>().map(|pool| pool.clone())
     }
+
+    pub fn get_stats(rocket: &rocket::Rocket) -> Option<PoolStats> {
+        let this = rocket.state::<Self>()?;
+        let state = this.pool.as_ref()?.state();
+        let retrieved = this.stats.retrieved.load(Ordering::Acquire);
+        let wait_micros = this.stats.wait_micros.load(Ordering::Acquire);
+        Some(PoolStats {
+            max_size: this.config.pool_size,
+            connections: state.connections,
+            idle: state.idle_connections,
+            retrieved,
+            average_wait: Duration::from_micros(wait_micros.checked_div(retrieved).unwrap_or(0)),
+            healthy: this.stats.healthy.load(Ordering::Acquire),
+        })
+    }
 }
 
 impl<K: 'static, C: Poolable> Connection<K, C> {
@@ -875,3 +1374,347 @@ impl<'a, 'r, K: 'static, C: Poolable> FromRequest<'a, 'r> for Connection<K, C> {
         }
     }
 }
+
+/// A request guard that runs a request's database work in a transaction.
+///
+/// A `Transaction<Db>`, where `Db` is a type declared with `#[database]` whose
+/// connection type implements [`Transactional`], retrieves a connection from
+/// `Db`'s pool and begins a transaction on it. The transaction is ended by
+/// `Db`'s fairing after the handler returns, based on the status of the
+/// response:
+///
+///   * If the status is not an error (`4xx` or `5xx`), the transaction is
+///     committed. If committing fails, the response is replaced with an empty
+///     `500 Internal Server Error`.
+///   * Otherwise, the transaction is rolled back.
+///
+/// If the request is dropped before a response is produced, as when the
+/// handler panics, the transaction is rolled back.
+///
+/// Every `Transaction<Db>` guard in a single request refers to the same
+/// transaction. Retrieving the guard fails with `503 Service Unavailable` if a
+/// connection cannot be retrieved or a transaction cannot be begun.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_contrib;
+/// #
+/// # #[cfg(feature = "sqlite_pool")]
+/// # mod test {
+/// use rocket_contrib::databases::{rusqlite, Transaction};
+/// use rocket::response::Debug;
+///
+/// #[database("bank")]
+/// struct Bank(rusqlite::Connection);
+///
+/// #[post("/transfer/<from>/<to>/<amount>")]
+/// async fn transfer(
+///     tx: Transaction<Bank>,
+///     from: i64,
+///     to: i64,
+///     amount: i64
+/// ) -> Result<(), Debug<rusqlite::Error>> {
+///     // If either update fails, neither is committed.
+///     tx.run(move |c| {
+///         c.execute("UPDATE accounts SET balance = balance - ?1 WHERE id = ?2",
+///             rusqlite::params![amount, from])?;
+///         c.execute("UPDATE accounts SET balance = balance + ?1 WHERE id = ?2",
+///             rusqlite::params![amount, to])?;
+///         Ok(())
+///     }).await.map_err(Debug)
+/// }
+/// # }
+/// ```
+pub struct Transaction<D: Database> {
+    connection: Arc<Connection<D, D::Connection>>,
+}
+
+impl<D: Database> Transaction<D> {
+    /// Runs the provided closure on a thread from a threadpool, within the
+    /// transaction. The closure will be passed an `&mut` to the connection.
+    /// `.await`ing the return value of this function yields the value
+    /// returned by the closure.
+    #[inline]
+    pub async fn run<F, R>(&self, f: F) -> R
+        where F: FnOnce(&mut D::Connection) -> R + Send + 'static,
+              R: Send + 'static,
+    {
+        self.connection.run(f).await
+    }
+}
+
+// A transaction that has been begun but not yet ended.
+struct Pending<K, C: Poolable> {
+    connection: Arc<Connection<K, C>>,
+    end: fn(&mut C, bool) -> Result<(), String>,
+}
+
+fn end_transaction<C: Transactional>(conn: &mut C, commit: bool) -> Result<(), String> {
+    match commit {
+        true => conn.commit().map_err(|e| e.to_string()),
+        false => conn.rollback().map_err(|e| e.to_string()),
+    }
+}
+
+impl<K: 'static, C: Poolable> Pending<K, C> {
+    async fn finish(self, commit: bool) -> Result<(), String> {
+        let end = self.end;
+        self.connection.run(move |conn| end(conn, commit)).await
+    }
+}
+
+// The request-local transaction for the database `K`, if any.
+struct TransactionSlot<K: 'static, C: Poolable>(std::sync::Mutex<Option<Pending<K, C>>>);
+
+impl<K: 'static, C: Poolable> Default for TransactionSlot<K, C> {
+    fn default() -> Self {
+        TransactionSlot(std::sync::Mutex::new(None))
+    }
+}
+
+impl<K: 'static, C: Poolable> TransactionSlot<K, C> {
+    fn take(&self) -> Option<Pending<K, C>> {
+        self.0.lock().expect("transaction lock").take()
+    }
+}
+
+impl<K: 'static, C: Poolable> Drop for TransactionSlot<K, C> {
+    fn drop(&mut self) {
+        // The request was dropped without a response: roll back.
+        if let Some(pending) = self.0.get_mut().ok().and_then(|p| p.take()) {
+            tokio::spawn(async move {
+                if let Err(e) = pending.finish(false).await {
+                    error_!("failed to roll back database transaction: {}", e);
+                }
+            });
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r, D: Database> FromRequest<'a, 'r> for Transaction<D>
+    where D::Connection: Transactional
+{
+    type Error = ();
+
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, ()> {
+        let slot = request.local_cache(TransactionSlot::<D, D::Connection>::default);
+        let existing = slot.0.lock().expect("transaction lock")
+            .as_ref()
+            .map(|pending| pending.connection.clone());
+
+        if let Some(connection) = existing {
+            return Outcome::Success(Transaction { connection });
+        }
+
+        let connection = try_outcome!(Connection::<D, D::Connection>::from_request(request).await);
+        if let Err(e) = connection.run(|conn| conn.begin().map_err(|e| e.to_string())).await {
+            error_!("failed to begin database transaction: {}", e);
+            return Outcome::Failure((Status::ServiceUnavailable, ()));
+        }
+
+        let connection = Arc::new(connection);
+        let pending = Pending { connection: connection.clone(), end: end_transaction };
+        *slot.0.lock().expect("transaction lock") = Some(pending);
+        Outcome::Success(Transaction { connection })
+    }
+}
+
+/// A fairing that applies schema migrations to a database at ignition.
+///
+/// Migrations are `.sql` files in a directory, applied in lexicographic order
+/// of their file names, each in its own transaction. The name of each applied
+/// migration, its file name without the extension, is recorded in the
+/// `rocket_migrations` table, which is created if it does not exist; recorded
+/// migrations are not applied again. Migration names may only contain ASCII
+/// alphanumerics, `-`, `_`, and `.`, so migrations are typically named in the
+/// form `0001_create_users.sql`.
+///
+/// Migrations are applied while holding a database-wide lock, when the
+/// adapter supports one, so that replicas of an application that ignite
+/// concurrently do not migrate the database concurrently. See
+/// [`Migratable::lock()`].
+///
+/// Ignition fails if a migration fails, and the failed migration is not
+/// recorded. Its transaction is rolled back, but only to the extent the
+/// database supports: MySQL implicitly commits every DDL statement, such as
+/// `CREATE TABLE` or `ALTER TABLE`, so the schema changes a failed migration
+/// made before failing remain applied there. On MySQL, prefer migrations with
+/// a single DDL statement, or write them to be safely re-run. PostgreSQL and
+/// SQLite roll back DDL along with the rest of the transaction.
+///
+/// The database's fairing must be attached before the `Migrations` fairing.
+///
+/// # Configuration
+///
+/// The following keys in a database's configuration table are read:
+///
+///   * `migrations` - the directory containing the migrations, overriding the
+///     directory passed to [`Migrations::fairing()`]
+///   * `migrate` - whether to apply migrations at all (defaults to `true`)
+///
+/// ```toml
+/// [global.databases.my_db]
+/// url = "db.sqlite"
+/// migrations = "db/migrations"
+///
+/// [production.databases.my_db]
+/// migrate = false
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_contrib;
+/// #
+/// # #[cfg(feature = "sqlite_pool")]
+/// # mod test {
+/// use rocket_contrib::databases::{rusqlite, Migrations};
+///
+/// #[database("my_db")]
+/// struct MyDatabase(rusqlite::Connection);
+///
+/// #[launch]
+/// fn rocket() -> rocket::Rocket {
+///     rocket::ignite()
+///         .attach(MyDatabase::fairing())
+///         .attach(Migrations::<MyDatabase>::fairing("migrations"))
+/// }
+/// # }
+/// ```
+pub struct Migrations<D> {
+    path: std::path::PathBuf,
+    _marker: PhantomData<fn() -> D>,
+}
+
+impl<D: Database> Migrations<D> where D::Connection: Migratable {
+    /// Returns a fairing that applies the migrations in the directory at
+    /// `path`, relative to the current working directory, to the database
+    /// `D` at ignition.
+    pub fn fairing<P: AsRef<std::path::Path>>(path: P) -> Migrations<D> {
+        Migrations { path: path.as_ref().into(), _marker: PhantomData }
+    }
+}
+
+fn read_migrations(dir: &std::path::Path) -> std::io::Result<Vec<(String, String)>> {
+    let mut migrations = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |ext| ext != "sql") {
+            continue;
+        }
+
+        let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+        let valid = |c: char| c.is_ascii_alphanumeric() || "-_.".contains(c);
+        if name.is_empty() || !name.chars().all(valid) {
+            let msg = format!("invalid migration name: {}", path.display());
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg));
+        }
+
+        migrations.push((name.to_string(), std::fs::read_to_string(&path)?));
+    }
+
+    migrations.sort();
+    Ok(migrations)
+}
+
+// Applies the pending `migrations`, returning the names of those applied.
+fn migrate<C: Migratable>(
+    conn: &mut C,
+    migrations: Vec<(String, String)>
+) -> Result<Vec<String>, String> {
+    let e = |e: C::TransactionError| e.to_string();
+    conn.lock().map_err(e)?;
+    let result = (|| {
+        conn.execute_batch("CREATE TABLE IF NOT EXISTS rocket_migrations (\
+            name VARCHAR(255) PRIMARY KEY, \
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP)").map_err(e)?;
+
+        let applied = conn.query_strings("SELECT name FROM rocket_migrations").map_err(e)?;
+        let mut newly_applied = vec![];
+        for (name, sql) in migrations.into_iter().filter(|(n, _)| !applied.contains(n)) {
+            conn.begin().map_err(e)?;
+            let record = format!("INSERT INTO rocket_migrations (name) VALUES ('{}')", name);
+            let result = conn.execute_batch(&sql)
+                .and_then(|_| conn.execute_batch(&record))
+                .and_then(|_| conn.commit());
+
+            if let Err(error) = result {
+                let _ = conn.rollback();
+                return Err(format!("migration `{}` failed: {}", name, error));
+            }
+
+            newly_applied.push(name);
+        }
+
+        Ok(newly_applied)
+    })();
+
+    conn.unlock().map_err(e)?;
+    result
+}
+
+#[rocket::async_trait]
+impl<D: Database> Fairing for Migrations<D> where D::Connection: Migratable {
+    fn info(&self) -> Info {
+        Info { name: "Database Migrations", kind: Kind::Attach }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let key = |name: &str| format!("databases.{}.{}", D::NAME, name);
+        let figment = rocket.figment();
+        match figment.extract_inner::<bool>(&key("migrate")) {
+            Ok(false) => return Ok(rocket),
+            Ok(true) => {},
+            Err(e) if e.missing() => {},
+            Err(e) => dberr!("config", D::NAME, "{}", e, rocket),
+        }
+
+        let path = match figment.extract_inner::<std::path::PathBuf>(&key("migrations")) {
+            Ok(path) => path,
+            Err(e) if e.missing() => self.path.clone(),
+            Err(e) => dberr!("config", D::NAME, "{}", e, rocket),
+        };
+
+        let migrations = match read_migrations(&path) {
+            Ok(migrations) => migrations,
+            Err(e) => {
+                let error = format!("{}: {}", path.display(), e);
+                dberr!("migrations", D::NAME, "{}", error, rocket)
+            }
+        };
+
+        let pool = match rocket.state::<ConnectionPool<D, D::Connection>>() {
+            Some(pool) => pool,
+            None => {
+                rocket::error!("database pool for `{}` is not attached", D::NAME);
+                info_!("`{}::fairing()` must be attached before migrations",
+                    std::any::type_name::<D>());
+                return Err(rocket);
+            }
+        };
+
+        let conn = match pool.get().await {
+            Ok(conn) => conn,
+            Err(()) => dberr!("connection", D::NAME, "{}", "no connection available", rocket),
+        };
+
+        match conn.run(move |c| migrate(c, migrations)).await {
+            Ok(applied) if applied.is_empty() => {
+                rocket::info!("database `{}` is up to date", D::NAME);
+            }
+            Ok(applied) => {
+                rocket::info!("applied {} migrations to database `{}`", applied.len(), D::NAME);
+                for name in applied {
+                    info_!("{}", name);
+                }
+            }
+            Err(e) => dberr!("migration", D::NAME, "{}", e, rocket),
+        }
+
+        Ok(rocket)
+    }
+}
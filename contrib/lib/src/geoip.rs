@@ -0,0 +1,321 @@
+//! GeoIP lookups with a pluggable resolver.
+//!
+//! Attaching [`GeoIp::fairing()`] with a [`GeoResolver`] places a [`GeoIp`]
+//! registry in managed state. Handlers then receive the geographic
+//! information for the request's client IP via the [`GeoInfo`] request guard,
+//! without any custom middleware.
+//!
+//! The client IP is determined by [`Request::trusted_client_ip()`]: the
+//! `X-Real-IP` header is honored only for requests from proxies listed in the
+//! `trusted_proxies` configuration parameter.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `geoip` feature is enabled. The
+//! [`MaxMind`] resolver, which reads MaxMind's GeoIP2 and GeoLite2 City
+//! databases, additionally requires the `geoip_maxmind` feature:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["geoip_maxmind"]
+//! ```
+//!
+//! # Caching
+//!
+//! Lookups are cached twice: for the remainder of the request, so that
+//! multiple guards share one lookup, and by client address across requests,
+//! so that the many requests made over a client's connections incur a single
+//! lookup. The address cache holds up to 10,000 entries and is cleared when
+//! full. Negative results are cached as well.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use std::net::IpAddr;
+//!
+//! use rocket_contrib::geoip::{GeoIp, GeoInfo, GeoResolver};
+//!
+//! struct Everywhere;
+//!
+//! #[rocket::async_trait]
+//! impl GeoResolver for Everywhere {
+//!     async fn resolve(&self, _: IpAddr) -> Option<GeoInfo> {
+//!         Some(GeoInfo { country: Some("NZ".into()), ..Default::default() })
+//!     }
+//! }
+//!
+//! #[get("/", rank = 1)]
+//! fn local(geo: &GeoInfo) -> String {
+//!     format!("Shipping to {}", geo.country.as_deref().unwrap_or("your country"))
+//! }
+//!
+//! #[get("/", rank = 2)]
+//! fn unknown() -> &'static str {
+//!     "Shipping worldwide"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .attach(GeoIp::fairing(Everywhere))
+//!         .mount("/", routes![local, unknown])
+//! }
+//! ```
+//!
+//! [`Request::trusted_client_ip()`]: rocket::Request::trusted_client_ip()
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+use rocket::{Rocket, Request};
+use rocket::fairing::AdHoc;
+use rocket::request::{self, FromRequest};
+use rocket::outcome::Outcome;
+use rocket::http::Status;
+
+/// The maximum number of client addresses in the lookup cache.
+const CACHE_CAPACITY: usize = 10_000;
+
+/// Geographic information about an IP address.
+///
+/// Every field is optional: databases and services vary in what they know
+/// about a given address. As a request guard, `&GeoInfo` succeeds when the
+/// client's address resolves and forwards when it doesn't or when the client
+/// address is unknown. If [`GeoIp`] is not in managed state, the guard fails
+/// with a `500`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoInfo {
+    /// The two-letter continent code, such as `EU`.
+    pub continent: Option<String>,
+    /// The ISO 3166-1 alpha-2 country code, such as `DE`.
+    pub country: Option<String>,
+    /// The ISO 3166-2 code of the most general subdivision, such as `BE` for
+    /// Berlin, without the country prefix.
+    pub region: Option<String>,
+    /// The name of the city, in English.
+    pub city: Option<String>,
+    /// The approximate latitude of the address.
+    pub latitude: Option<f64>,
+    /// The approximate longitude of the address.
+    pub longitude: Option<f64>,
+    /// The IANA time zone of the address, such as `Europe/Berlin`.
+    pub time_zone: Option<String>,
+}
+
+impl GeoInfo {
+    /// Returns `true` if the country is `country`, compared
+    /// case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::geoip::GeoInfo;
+    ///
+    /// let info = GeoInfo { country: Some("DE".into()), ..Default::default() };
+    /// assert!(info.in_country("de"));
+    /// assert!(!info.in_country("AT"));
+    /// ```
+    pub fn in_country(&self, country: &str) -> bool {
+        self.country.as_deref().map_or(false, |c| c.eq_ignore_ascii_case(country))
+    }
+}
+
+/// A source of geographic information for IP addresses.
+///
+/// A resolver is registered via [`GeoIp::fairing()`]. Its results are cached
+/// by [`GeoIp`]; resolvers need not cache themselves.
+///
+/// # Example
+///
+/// A resolver for a single, internal network:
+///
+/// ```rust
+/// # extern crate rocket_contrib;
+/// use std::net::IpAddr;
+/// use rocket_contrib::geoip::{GeoInfo, GeoResolver};
+///
+/// struct Office;
+///
+/// #[rocket::async_trait]
+/// impl GeoResolver for Office {
+///     async fn resolve(&self, ip: IpAddr) -> Option<GeoInfo> {
+///         match ip {
+///             IpAddr::V4(v4) if v4.is_private() => Some(GeoInfo {
+///                 city: Some("Lisbon".into()),
+///                 country: Some("PT".into()),
+///                 ..Default::default()
+///             }),
+///             _ => None
+///         }
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait GeoResolver: Send + Sync + 'static {
+    /// Returns the geographic information for `ip`, or `None` if it is
+    /// unknown.
+    async fn resolve(&self, ip: IpAddr) -> Option<GeoInfo>;
+}
+
+/// The GeoIP registry, in managed state: the resolver and the lookup cache.
+///
+/// See the [module level documentation](crate::geoip) for details.
+pub struct GeoIp {
+    resolver: Box<dyn GeoResolver>,
+    cache: Mutex<HashMap<IpAddr, Option<Arc<GeoInfo>>>>,
+}
+
+/// The per-request lookup result.
+struct RequestGeo(Option<Arc<GeoInfo>>);
+
+impl GeoIp {
+    /// Returns a fairing that places a `GeoIp` registry, resolving addresses
+    /// with `resolver`, in managed state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use std::net::IpAddr;
+    /// use rocket_contrib::geoip::{GeoIp, GeoInfo, GeoResolver};
+    ///
+    /// struct Nowhere;
+    ///
+    /// #[rocket::async_trait]
+    /// impl GeoResolver for Nowhere {
+    ///     async fn resolve(&self, _: IpAddr) -> Option<GeoInfo> { None }
+    /// }
+    ///
+    /// let rocket = rocket::ignite().attach(GeoIp::fairing(Nowhere));
+    /// ```
+    pub fn fairing<R: GeoResolver>(resolver: R) -> AdHoc {
+        let resolver: Box<dyn GeoResolver> = Box::new(resolver);
+        AdHoc::on_attach("GeoIP", |rocket: Rocket| async move {
+            Ok(rocket.manage(GeoIp { resolver, cache: Mutex::new(HashMap::new()) }))
+        })
+    }
+
+    /// Returns the geographic information for `ip`, consulting the cache
+    /// before the resolver.
+    pub async fn lookup(&self, ip: IpAddr) -> Option<Arc<GeoInfo>> {
+        let cached = self.cache.lock().expect("geoip cache lock").get(&ip).cloned();
+        if let Some(info) = cached {
+            return info;
+        }
+
+        let info = self.resolver.resolve(ip).await.map(Arc::new);
+        let mut cache = self.cache.lock().expect("geoip cache lock");
+        if cache.len() >= CACHE_CAPACITY {
+            cache.clear();
+        }
+
+        cache.insert(ip, info.clone());
+        info
+    }
+
+    /// Removes all cached lookups, for instance after the resolver's
+    /// database has been updated.
+    pub fn clear_cache(&self) {
+        self.cache.lock().expect("geoip cache lock").clear();
+    }
+}
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a GeoInfo {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let geoip = match req.managed_state::<GeoIp>() {
+            Some(geoip) => geoip,
+            None => {
+                error_!("`GeoInfo` guard used without attaching `GeoIp`.");
+                return Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let cached = req.local_cache_async(async {
+            match req.trusted_client_ip() {
+                Some(ip) => RequestGeo(geoip.lookup(ip).await),
+                None => RequestGeo(None),
+            }
+        }).await;
+
+        match cached.0 {
+            Some(ref info) => Outcome::Success(&**info),
+            None => Outcome::Forward(()),
+        }
+    }
+}
+
+#[cfg(feature = "geoip_maxmind")]
+pub use self::maxmind::MaxMind;
+
+#[cfg(feature = "geoip_maxmind")]
+mod maxmind {
+    use std::net::IpAddr;
+    use std::path::Path;
+
+    use maxminddb::{geoip2, MaxMindDBError, Reader};
+
+    use super::{GeoInfo, GeoResolver};
+
+    /// A [`GeoResolver`] backed by a MaxMind GeoIP2 or GeoLite2 City
+    /// database.
+    ///
+    /// The database is read into memory when opened. Country databases are
+    /// supported as well; their lookups have no region, city, or location.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::geoip::{GeoIp, MaxMind};
+    ///
+    /// let maxmind = MaxMind::open("/usr/share/GeoIP/GeoLite2-City.mmdb")
+    ///     .expect("readable GeoIP database");
+    ///
+    /// let rocket = rocket::ignite().attach(GeoIp::fairing(maxmind));
+    /// ```
+    pub struct MaxMind {
+        reader: Reader<Vec<u8>>,
+    }
+
+    impl MaxMind {
+        /// Opens and reads the database at `path`.
+        pub fn open<P: AsRef<Path>>(path: P) -> Result<MaxMind, MaxMindDBError> {
+            Ok(MaxMind { reader: Reader::open_readfile(path)? })
+        }
+
+        fn lookup(&self, ip: IpAddr) -> Option<GeoInfo> {
+            let city: geoip2::City<'_> = self.reader.lookup(ip).ok()?;
+            let location = city.location.as_ref();
+            Some(GeoInfo {
+                continent: city.continent.and_then(|c| c.code).map(String::from),
+                country: city.country.and_then(|c| c.iso_code).map(String::from),
+                region: city.subdivisions.as_ref()
+                    .and_then(|s| s.first())
+                    .and_then(|s| s.iso_code)
+                    .map(String::from),
+                city: city.city
+                    .and_then(|c| c.names)
+                    .and_then(|names| names.get("en").map(|name| name.to_string())),
+                latitude: location.and_then(|l| l.latitude),
+                longitude: location.and_then(|l| l.longitude),
+                time_zone: location.and_then(|l| l.time_zone).map(String::from),
+            })
+        }
+    }
+
+    #[rocket::async_trait]
+    impl GeoResolver for MaxMind {
+        async fn resolve(&self, ip: IpAddr) -> Option<GeoInfo> {
+            self.lookup(ip)
+        }
+    }
+}
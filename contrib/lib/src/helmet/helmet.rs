@@ -167,7 +167,7 @@ impl SpaceHelmet {
 
     /// Sets all of the headers in `self.policies` in `response` as long as the
     /// header is not already in the response.
-    fn apply(&self, response: &mut Response<'_>) {
+    fn apply(&self, request: &Request<'_>, response: &mut Response<'_>) {
         for policy in self.policies.values() {
             let name = policy.name();
             if response.headers().contains(name.as_str()) {
@@ -177,7 +177,7 @@ impl SpaceHelmet {
             }
 
             // FIXME: Cache the rendered header.
-            response.set_header(policy.header());
+            response.set_header(policy.header_for(request));
         }
 
         if self.force_hsts.load(Ordering::Relaxed) {
@@ -197,8 +197,8 @@ impl Fairing for SpaceHelmet {
         }
     }
 
-    async fn on_response<'r>(&self, _: &'r Request<'_>, res: &mut Response<'r>) {
-        self.apply(res);
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        self.apply(req, res);
     }
 
     fn on_launch(&self, rocket: &Rocket) {
@@ -0,0 +1,200 @@
+use rocket::{Rocket, Request, Response, Data, Route};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::handler::{Handler, Outcome};
+use rocket::http::{Method, Status};
+use rocket::http::uri::{Absolute, Origin};
+use rocket::response::Redirect;
+
+use crate::helmet::{Hsts, Policy};
+
+/// The base of the internal routes plain HTTP requests are rerouted to.
+const REDIRECT_BASE: &str = "/__rocket_https_redirect";
+
+/// A [`Fairing`](../../rocket/fairing/trait.Fairing.html) that redirects plain
+/// HTTP requests to HTTPS and adds an HSTS header to HTTPS responses.
+///
+/// Whether a request arrived over HTTPS is determined by the scheme of
+/// [`Request::base_url()`](rocket::Request::base_url()). When Rocket is
+/// serving TLS itself, every request is secure. When Rocket is behind a TLS
+/// terminating proxy listed in the `trusted_proxies` configuration parameter,
+/// the proxy's `X-Forwarded-Proto` header decides. Requests that are not
+/// secure are answered with a `301 Moved Permanently` to the same host, path,
+/// and query over `https`; their handlers are never run.
+///
+/// Secure responses receive a `Strict-Transport-Security` header with the
+/// configured [`Hsts`] policy, [`Hsts::default()`] unless otherwise set, if
+/// they don't already have one. Per the HSTS specification, the header is
+/// never sent over plain HTTP.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::helmet::{HttpsRedirect, Hsts};
+/// use time::Duration;
+///
+/// # let _ = {
+/// rocket::ignite()
+///     .attach(HttpsRedirect::new().hsts(Hsts::Preload(Duration::weeks(104))))
+/// # };
+/// ```
+pub struct HttpsRedirect {
+    hsts: Option<Hsts>,
+    port: Option<u16>,
+}
+
+/// Request-local record of the transport a request arrived on.
+enum Transport {
+    Secure,
+    Insecure(Option<Absolute<'static>>),
+}
+
+#[derive(Clone)]
+struct RedirectHandler;
+
+impl HttpsRedirect {
+    /// Returns an `HttpsRedirect` that redirects to the default HTTPS port and
+    /// uses the default HSTS policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new();
+    /// ```
+    pub fn new() -> Self {
+        HttpsRedirect { hsts: Some(Hsts::default()), port: None }
+    }
+
+    /// Sets the HSTS policy sent with secure responses to `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::{HttpsRedirect, Hsts};
+    /// use time::Duration;
+    ///
+    /// let redirect = HttpsRedirect::new().hsts(Hsts::IncludeSubDomains(Duration::days(30)));
+    /// ```
+    pub fn hsts(mut self, policy: Hsts) -> Self {
+        self.hsts = Some(policy);
+        self
+    }
+
+    /// Disables the HSTS header. Plain HTTP requests are still redirected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new().without_hsts();
+    /// ```
+    pub fn without_hsts(mut self) -> Self {
+        self.hsts = None;
+        self
+    }
+
+    /// Sets the port redirects point to. By default, redirects omit the port,
+    /// implying the standard HTTPS port, `443`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::HttpsRedirect;
+    ///
+    /// let redirect = HttpsRedirect::new().port(8443);
+    /// ```
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    fn target(&self, req: &Request<'_>) -> Option<Absolute<'static>> {
+        let base = req.base_url();
+        let host = base.authority()?.host();
+        let host = match host.contains(':') {
+            true => format!("[{}]", host),
+            false => host.to_string(),
+        };
+
+        let port = match self.port {
+            Some(port) if port != 443 => format!(":{}", port),
+            _ => String::new(),
+        };
+
+        Absolute::parse_owned(format!("https://{}{}{}", host, port, req.uri())).ok()
+    }
+}
+
+impl Default for HttpsRedirect {
+    fn default() -> Self {
+        HttpsRedirect::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for RedirectHandler {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        match req.local_cache(|| Transport::Secure) {
+            Transport::Insecure(Some(uri)) => Outcome::from(req, Redirect::moved(uri.clone())),
+            Transport::Insecure(None) => Outcome::failure(Status::BadRequest),
+            Transport::Secure => Outcome::forward(data),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for HttpsRedirect {
+    fn info(&self) -> Info {
+        Info {
+            name: "HTTPS Redirect",
+            kind: Kind::Attach | Kind::Launch | Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        if let Some(Hsts::Preload(age)) = &self.hsts {
+            if *age < time::Duration::weeks(52) {
+                warn!("HTTPS Redirect: HSTS preload lists require a max-age of a year or more.");
+            }
+        }
+
+        let routes: Vec<_> = [
+            Method::Get, Method::Put, Method::Post, Method::Delete, Method::Options,
+            Method::Head, Method::Trace, Method::Connect, Method::Patch,
+        ].iter().map(|&m| Route::new(m, "/", RedirectHandler)).collect();
+
+        Ok(rocket.mount(REDIRECT_BASE, routes))
+    }
+
+    fn on_launch(&self, rocket: &Rocket) {
+        let config = rocket.config();
+        if config.tls_enabled() && config.trusted_proxies.is_empty() {
+            info_!("HTTPS Redirect: TLS is enabled; all requests are secure.");
+            info_!("Only HSTS headers will be sent. Redirects require a trusted proxy.");
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data) {
+        if req.base_url().scheme() == "https" {
+            req.local_cache(|| Transport::Secure);
+            return;
+        }
+
+        let target = self.target(req);
+        req.local_cache(|| Transport::Insecure(target));
+        req.set_uri(Origin::parse(REDIRECT_BASE).expect("valid redirect base"));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let transport = req.local_cache(|| Transport::Secure);
+        if let (Transport::Secure, Some(hsts)) = (transport, &self.hsts) {
+            if !res.headers().contains(Hsts::NAME) {
+                res.set_header(hsts);
+            }
+        }
+    }
+}
@@ -32,6 +32,7 @@
 //! | [Expect-CT]                 | Enables certificate transparency.      | [`ExpectCt`]  | ✗        |
 //! | [Referrer-Policy]           | Enables referrer policy.               | [`Referrer`]  | ✗        |
 //! | [X-DNS-Prefetch-Control]    | Controls browser DNS prefetching.      | [`Prefetch`]  | ✗        |
+//! | [Content-Security-Policy]   | Restricts sources of page content.     | [`Csp`]       | ✗        |
 //!
 //! <small>? If TLS is enabled when the application is launched, in a
 //! non-development environment (e.g., staging or production), HSTS is
@@ -45,6 +46,7 @@
 //! [Expect-CT]:  https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expect-CT
 //! [Referrer-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Referrer-Policy
 //! [X-DNS-Prefetch-Control]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-DNS-Prefetch-Control
+//! [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
 //! [clickjacking]: https://en.wikipedia.org/wiki/Clickjacking
 //!
 //! [`XssFilter`]: self::XssFilter
@@ -54,6 +56,7 @@
 //! [`ExpectCt`]: self::ExpectCt
 //! [`Referrer`]: self::Referrer
 //! [`Prefetch`]: self::Prefetch
+//! [`Csp`]: self::Csp
 //!
 //! # Usage
 //!
@@ -90,6 +93,52 @@
 //!     .disable::<NoSniff>();
 //! ```
 //!
+//! # Content Security Policy Nonces
+//!
+//! The [`Csp`] policy appends the request's CSP nonce, also available via the
+//! [`Nonce`] request guard and as `csp_nonce` in templates, to its
+//! `script-src` and `style-src` directives. Inline scripts and styles marked
+//! with the nonce are thus allowed while all others are refused:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket::request::Nonce;
+//! use rocket::response::content::Html;
+//! use rocket_contrib::helmet::{SpaceHelmet, Csp};
+//!
+//! #[get("/")]
+//! fn index(nonce: Nonce<'_>) -> Html<String> {
+//!     Html(format!(r#"<script nonce="{}">console.log("hi")</script>"#, nonce))
+//! }
+//!
+//! let rocket = rocket::ignite()
+//!     .mount("/", routes![index])
+//!     .attach(SpaceHelmet::default().enable(Csp::default()));
+//! ```
+//!
+//! [`Nonce`]: rocket::request::Nonce
+//!
+//! # Redirecting to HTTPS
+//!
+//! The [`HttpsRedirect`] fairing complements `SpaceHelmet` by redirecting
+//! plain HTTP requests to HTTPS and sending an HSTS header, with a
+//! configurable [`Hsts`] policy, on HTTPS responses only. Requests forwarded by
+//! a TLS terminating proxy are recognized via the `trusted_proxies`
+//! configuration parameter:
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::helmet::{SpaceHelmet, HttpsRedirect, Hsts};
+//!
+//! let rocket = rocket::ignite()
+//!     .attach(HttpsRedirect::new().hsts(Hsts::default()))
+//!     .attach(SpaceHelmet::default().disable::<Hsts>());
+//! ```
+//!
+//! [`HttpsRedirect`]: self::HttpsRedirect
+//!
 //! # FAQ
 //!
 //! * **Which policies should I choose?**
@@ -108,6 +157,8 @@
 
 mod helmet;
 mod policy;
+mod https;
 
 pub use self::helmet::SpaceHelmet;
+pub use self::https::HttpsRedirect;
 pub use self::policy::*;
@@ -2,6 +2,8 @@
 
 use std::borrow::Cow;
 
+use rocket::Request;
+use rocket::request::Nonce;
 use rocket::http::{Header, uri::Uri, uncased::UncasedStr};
 
 use time::Duration;
@@ -61,11 +63,20 @@ pub trait Policy: Default + Send + Sync + 'static {
     /// }
     /// ```
     fn header(&self) -> Header<'static>;
+
+    /// Returns the [`Header`](../../rocket/http/struct.Header.html) to attach
+    /// to the response to `request`. Defaults to [`Policy::header()`].
+    ///
+    /// Policies whose header varies per request, such as [`Csp`] with its
+    /// nonce, override this method.
+    fn header_for(&self, _request: &Request<'_>) -> Header<'static> {
+        self.header()
+    }
 }
 
 pub(crate) trait SubPolicy: Send + Sync {
     fn name(&self) -> &'static UncasedStr;
-    fn header(&self) -> Header<'static>;
+    fn header_for(&self, request: &Request<'_>) -> Header<'static>;
 }
 
 impl<P: Policy> SubPolicy for P {
@@ -73,8 +84,8 @@ impl<P: Policy> SubPolicy for P {
         P::NAME.into()
     }
 
-    fn header(&self) -> Header<'static> {
-        Policy::header(self)
+    fn header_for(&self, request: &Request<'_>) -> Header<'static> {
+        Policy::header_for(self, request)
     }
 }
 
@@ -99,6 +110,21 @@ impl_policy!(ExpectCt, "Expect-CT");
 impl_policy!(Referrer, "Referrer-Policy");
 impl_policy!(Prefetch, "X-DNS-Prefetch-Control");
 
+impl Policy for Csp {
+    const NAME: &'static str = "Content-Security-Policy";
+
+    fn header(&self) -> Header<'static> {
+        Header::new(Csp::NAME, self.render(None))
+    }
+
+    fn header_for(&self, request: &Request<'_>) -> Header<'static> {
+        match self.nonce {
+            true => Header::new(Csp::NAME, self.render(Some(Nonce::of(request)))),
+            false => self.header(),
+        }
+    }
+}
+
 /// The [Referrer-Policy] header: controls the value set by the browser for the
 /// [Referer] header.
 ///
@@ -283,7 +309,9 @@ pub enum Hsts {
     /// Google maintains an [HSTS preload service] that can be used to prevent
     /// the browser from ever connecting to your site over an insecure
     /// connection. Read more [here]. Don't enable this before you have
-    /// registered your site.
+    /// registered your site. Like [`Hsts::IncludeSubDomains`], this applies to
+    /// all subdomains, as the preload list requires. The list also requires a
+    /// [`Duration`] of at least one year.
     ///
     /// [HSTS preload service]: https://hstspreload.org/
     /// [here]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Strict-Transport-Security#Preloading_Strict_Transport_Security
@@ -304,7 +332,9 @@ impl Into<Header<'static>> for &Hsts {
             Hsts::IncludeSubDomains(age) => {
                 format!("max-age={}; includeSubDomains", age.whole_seconds())
             }
-            Hsts::Preload(age) => format!("max-age={}; preload", age.whole_seconds()),
+            Hsts::Preload(age) => {
+                format!("max-age={}; includeSubDomains; preload", age.whole_seconds())
+            }
         };
 
         Header::new(Hsts::NAME, policy_string)
@@ -434,3 +464,131 @@ impl Into<Header<'static>> for &Prefetch {
         Header::new(Prefetch::NAME, policy_string)
     }
 }
+
+/// The [Content-Security-Policy] header: restricts the sources of content the
+/// browser loads for a page.
+///
+/// A policy is a list of directives, each naming a kind of content and the
+/// sources it may be loaded from. The [default policy] restricts all content to
+/// the page's own origin and forbids plugins.
+///
+/// # Nonces
+///
+/// By default, the request's [`Nonce`] is appended to the `script-src` and
+/// `style-src` directives, if they are present, as `'nonce-<value>'`. Inline
+/// scripts and styles with a matching `nonce` attribute are then allowed
+/// without resorting to `'unsafe-inline'`. The same nonce is available to
+/// handlers via the [`Nonce`] request guard and to templates as `csp_nonce`:
+///
+/// ```html
+/// <script nonce="{{ csp_nonce }}">/* ... */</script>
+/// ```
+///
+/// Use [`Csp::nonce()`] to disable nonces.
+///
+/// [Content-Security-Policy]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy
+/// [default policy]: #impl-Default
+/// [`Nonce`]: rocket::request::Nonce
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_contrib::helmet::{SpaceHelmet, Csp};
+///
+/// let csp = Csp::default()
+///     .directive("img-src", "'self' https://images.example.com")
+///     .directive("report-uri", "/csp-reports");
+///
+/// let helmet = SpaceHelmet::default().enable(csp);
+/// ```
+pub struct Csp {
+    directives: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    nonce: bool,
+}
+
+impl Csp {
+    /// Returns a policy with no directives and nonces enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::Csp;
+    ///
+    /// let csp = Csp::new().directive("script-src", "'self'");
+    /// ```
+    pub fn new() -> Csp {
+        Csp { directives: vec![], nonce: true }
+    }
+
+    /// Sets the directive `name` to `value`, replacing any existing value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::Csp;
+    ///
+    /// let csp = Csp::default().directive("connect-src", "'self' wss:");
+    /// ```
+    pub fn directive<N, V>(mut self, name: N, value: V) -> Csp
+        where N: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>
+    {
+        let (name, value) = (name.into(), value.into());
+        match self.directives.iter_mut().find(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+            Some(existing) => existing.1 = value,
+            None => self.directives.push((name, value)),
+        }
+
+        self
+    }
+
+    /// Sets whether the request's nonce is appended to the `script-src` and
+    /// `style-src` directives. Nonces are enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::helmet::Csp;
+    ///
+    /// let csp = Csp::default().nonce(false);
+    /// ```
+    pub fn nonce(mut self, enabled: bool) -> Csp {
+        self.nonce = enabled;
+        self
+    }
+
+    fn render(&self, nonce: Option<Nonce<'_>>) -> String {
+        let directives = self.directives.iter().map(|(name, value)| {
+            let mut directive = name.to_string();
+            for source in value.split_whitespace() {
+                directive.push(' ');
+                directive.push_str(source);
+            }
+
+            if let Some(nonce) = nonce {
+                if NONCE_DIRECTIVES.iter().any(|d| name.eq_ignore_ascii_case(d)) {
+                    directive.push_str(&format!(" 'nonce-{}'", nonce));
+                }
+            }
+
+            directive
+        });
+
+        directives.collect::<Vec<_>>().join("; ")
+    }
+}
+
+/// The directives to which a [`Csp`] policy appends the request's nonce.
+const NONCE_DIRECTIVES: &[&str] = &["script-src", "style-src"];
+
+/// Defaults to `default-src 'self'; script-src 'self'; style-src 'self';
+/// object-src 'none'; base-uri 'self'` with nonces enabled.
+impl Default for Csp {
+    fn default() -> Csp {
+        Csp::new()
+            .directive("default-src", "'self'")
+            .directive("script-src", "'self'")
+            .directive("style-src", "'self'")
+            .directive("object-src", "'none'")
+            .directive("base-uri", "'self'")
+    }
+}
@@ -0,0 +1,483 @@
+//! IP allow and deny lists with CIDR support.
+//!
+//! The [`IpFilter`] fairing rejects requests from client IP addresses that are
+//! denied, or that are not allowed, with a `403 Forbidden` before routing; the
+//! handlers of rejected requests never run. Lists contain [`Cidr`] networks,
+//! such as `10.0.0.0/8` or `2001:db8::/32`, or single addresses.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `ip_filter` feature is enabled.
+//! Enable it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["ip_filter"]
+//! ```
+//!
+//! # Rules
+//!
+//! Rules apply either globally or to requests whose path is under a mount
+//! point. For every request, the global rules and the rules of every mount
+//! point that prefixes the request's path are consulted. A request is
+//! rejected if its client IP is:
+//!
+//!   * in any applicable deny list, or
+//!   * not in an applicable allow list that is non-empty.
+//!
+//! A request without a known client IP is rejected only if there is an
+//! applicable, non-empty allow list.
+//!
+//! The client IP is determined by [`Request::trusted_client_ip()`]: the
+//! `X-Real-IP` header is honored only for requests from proxies listed in the
+//! `trusted_proxies` configuration parameter, so clients cannot evade the
+//! lists by spoofing the header.
+//!
+//! # Runtime Updates
+//!
+//! On attach, `IpFilter` places a handle to itself in managed state. Handlers
+//! can retrieve it with `State<'_, IpFilter>` to update the global block list
+//! at runtime via [`IpFilter::block()`] and [`IpFilter::unblock()`]. Updates
+//! apply to subsequent requests.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket::State;
+//! use rocket_contrib::ip_filter::{IpFilter, Cidr};
+//!
+//! #[post("/admin/block/<net>")]
+//! fn block(filter: State<'_, IpFilter>, net: String) -> Option<()> {
+//!     filter.block(net.parse().ok()?);
+//!     Some(())
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     let filter = IpFilter::new()
+//!         .deny("203.0.113.0/24".parse().unwrap())
+//!         .allow_at("/admin", "10.0.0.0/8".parse().unwrap());
+//!
+//!     rocket::ignite()
+//!         .attach(filter)
+//!         .mount("/", routes![block])
+//! }
+//! ```
+//!
+//! [`Request::trusted_client_ip()`]: rocket::Request::trusted_client_ip()
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use rocket::{Rocket, Request, Data, Route};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::handler::{Handler, Outcome};
+use rocket::http::{Method, Status};
+use rocket::http::uri::{Origin, Segments};
+
+/// The base of the internal routes rejected requests are rerouted to.
+const REJECT_BASE: &str = "/__rocket_ip_filter";
+
+/// An IP network in CIDR notation, such as `192.168.0.0/16` or `fe80::/10`.
+///
+/// A `Cidr` parses from a network address and prefix length separated by a
+/// `/`, or from a single address, which is treated as a network containing
+/// only that address. Host bits in the address are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::ip_filter::Cidr;
+///
+/// let private: Cidr = "10.0.0.0/8".parse().unwrap();
+/// assert!(private.contains("10.1.2.3".parse().unwrap()));
+/// assert!(!private.contains("11.0.0.1".parse().unwrap()));
+///
+/// let host: Cidr = "::1".parse().unwrap();
+/// assert_eq!(host.prefix(), 128);
+/// assert!(host.contains("::1".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+/// An error parsing a [`Cidr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CidrParseError(String);
+
+impl fmt::Display for CidrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid CIDR network: {}", self.0)
+    }
+}
+
+impl std::error::Error for CidrParseError {}
+
+impl Cidr {
+    /// Returns the network of addresses sharing the first `prefix` bits of
+    /// `addr`, or `None` if `prefix` is longer than the address.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use std::net::Ipv4Addr;
+    /// use rocket_contrib::ip_filter::Cidr;
+    ///
+    /// let net = Cidr::new(Ipv4Addr::new(192, 168, 1, 77).into(), 24).unwrap();
+    /// assert_eq!(net.to_string(), "192.168.1.0/24");
+    /// assert!(Cidr::new(Ipv4Addr::LOCALHOST.into(), 33).is_none());
+    /// ```
+    pub fn new(addr: IpAddr, prefix: u8) -> Option<Cidr> {
+        let addr = match addr {
+            IpAddr::V4(v4) if prefix <= 32 => {
+                IpAddr::V4(Ipv4Addr::from(u32::from(v4) & v4_mask(prefix)))
+            }
+            IpAddr::V6(v6) if prefix <= 128 => {
+                IpAddr::V6(Ipv6Addr::from(u128::from(v6) & v6_mask(prefix)))
+            }
+            _ => return None,
+        };
+
+        Some(Cidr { addr, prefix })
+    }
+
+    /// Returns the network address.
+    #[inline(always)]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length.
+    #[inline(always)]
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns `true` if `ip` is in this network. IPv4-mapped IPv6 addresses,
+    /// such as `::ffff:10.0.0.1`, are matched as IPv4 addresses.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, canonical(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                u32::from(ip) & v4_mask(self.prefix) == u32::from(net)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                u128::from(ip) & v6_mask(self.prefix) == u128::from(net)
+            }
+            _ => false
+        }
+    }
+}
+
+fn v4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0)
+}
+
+fn v6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0)
+}
+
+/// Returns the IPv4 address an IPv4-mapped IPv6 address maps, or `ip`.
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, hi, lo] => {
+                IpAddr::V4(Ipv4Addr::from(((hi as u32) << 16) | lo as u32))
+            }
+            _ => ip
+        },
+        ip => ip
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = CidrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || CidrParseError(s.to_string());
+        let (addr, prefix) = match s.find('/') {
+            Some(i) => (&s[..i], Some(&s[(i + 1)..])),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr.trim().parse().map_err(|_| error())?;
+        let prefix = match prefix {
+            Some(prefix) => prefix.trim().parse().map_err(|_| error())?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        Cidr::new(addr, prefix).ok_or_else(error)
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// The rules for requests under one mount point.
+#[derive(Debug, Clone)]
+struct Scope {
+    base: String,
+    allow: Vec<Cidr>,
+    deny: Vec<Cidr>,
+}
+
+impl Scope {
+    /// Compares segments, skipping empty ones as the router does, so that
+    /// `//admin` and `/admin//x` fall under a scope based at `/admin`.
+    fn applies_to(&self, path: &str) -> bool {
+        let mut segments = Segments(path);
+        Segments(&self.base).all(|base| segments.next() == Some(base))
+    }
+
+    fn rejects(&self, ip: Option<IpAddr>) -> bool {
+        match ip {
+            Some(ip) => {
+                self.deny.iter().any(|net| net.contains(ip))
+                    || (!self.allow.is_empty() && !self.allow.iter().any(|net| net.contains(ip)))
+            }
+            None => !self.allow.is_empty()
+        }
+    }
+}
+
+/// Request-local record of whether a request was rejected.
+struct Rejected(bool);
+
+#[derive(Clone)]
+struct RejectHandler;
+
+/// A fairing that rejects requests by client IP with CIDR allow and deny
+/// lists.
+///
+/// Cloning an `IpFilter` yields a handle to the same lists. See the [module
+/// level documentation](crate::ip_filter) for details.
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    scopes: Arc<RwLock<Vec<Scope>>>,
+}
+
+impl IpFilter {
+    /// Returns an `IpFilter` without any rules, which allows every request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new();
+    /// ```
+    pub fn new() -> Self {
+        IpFilter::default()
+    }
+
+    fn add(&self, base: &str, net: Cidr, deny: bool) {
+        let mut scopes = self.scopes.write().expect("ip filter lock");
+        let base = match base.trim_end_matches('/') {
+            "" => "/",
+            base => base,
+        };
+
+        let i = match scopes.iter().position(|s| s.base == base) {
+            Some(i) => i,
+            None => {
+                scopes.push(Scope { base: base.into(), allow: vec![], deny: vec![] });
+                scopes.len() - 1
+            }
+        };
+
+        let list = match deny {
+            true => &mut scopes[i].deny,
+            false => &mut scopes[i].allow,
+        };
+
+        if !list.contains(&net) {
+            list.push(net);
+        }
+    }
+
+    /// Allows requests from `net` globally. Once any network is allowed
+    /// globally, requests from all other addresses are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new().allow("192.168.0.0/16".parse().unwrap());
+    /// ```
+    pub fn allow(self, net: Cidr) -> Self {
+        self.allow_at("/", net)
+    }
+
+    /// Denies requests from `net` globally.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new().deny("198.51.100.0/24".parse().unwrap());
+    /// ```
+    pub fn deny(self, net: Cidr) -> Self {
+        self.deny_at("/", net)
+    }
+
+    /// Allows requests from `net` to paths under `base`. Once any network is
+    /// allowed under `base`, requests from all other addresses to paths under
+    /// `base` are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new().allow_at("/admin", "10.0.0.0/8".parse().unwrap());
+    /// ```
+    pub fn allow_at(self, base: &str, net: Cidr) -> Self {
+        self.add(base, net, false);
+        self
+    }
+
+    /// Denies requests from `net` to paths under `base`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new().deny_at("/api", "203.0.113.7".parse().unwrap());
+    /// ```
+    pub fn deny_at(self, base: &str, net: Cidr) -> Self {
+        self.add(base, net, true);
+        self
+    }
+
+    /// Adds `net` to the global deny list at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket::State;
+    /// use rocket_contrib::ip_filter::{IpFilter, Cidr};
+    ///
+    /// fn ban(filter: State<'_, IpFilter>, net: Cidr) {
+    ///     filter.block(net);
+    /// }
+    /// ```
+    pub fn block(&self, net: Cidr) {
+        info!("IP Filter: blocking {}.", net);
+        self.add("/", net, true);
+    }
+
+    /// Removes `net` from the global deny list at runtime. Returns `true` if
+    /// it was in the list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket::State;
+    /// use rocket_contrib::ip_filter::{IpFilter, Cidr};
+    ///
+    /// fn unban(filter: State<'_, IpFilter>, net: Cidr) -> bool {
+    ///     filter.unblock(net)
+    /// }
+    /// ```
+    pub fn unblock(&self, net: Cidr) -> bool {
+        let mut scopes = self.scopes.write().expect("ip filter lock");
+        match scopes.iter_mut().find(|scope| scope.base == "/") {
+            Some(scope) => {
+                let len = scope.deny.len();
+                scope.deny.retain(|n| n != &net);
+                scope.deny.len() != len
+            }
+            None => false
+        }
+    }
+
+    /// Returns the networks in the global deny list.
+    pub fn blocked(&self) -> Vec<Cidr> {
+        let scopes = self.scopes.read().expect("ip filter lock");
+        scopes.iter()
+            .find(|scope| scope.base == "/")
+            .map(|scope| scope.deny.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if a request from `ip` to `path` is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::ip_filter::IpFilter;
+    ///
+    /// let filter = IpFilter::new().allow_at("/admin", "10.0.0.0/8".parse().unwrap());
+    /// assert!(!filter.rejects(Some("10.0.0.1".parse().unwrap()), "/admin/users"));
+    /// assert!(filter.rejects(Some("8.8.8.8".parse().unwrap()), "/admin"));
+    /// assert!(!filter.rejects(Some("8.8.8.8".parse().unwrap()), "/administrator"));
+    /// assert!(filter.rejects(Some("8.8.8.8".parse().unwrap()), "//admin/users"));
+    /// ```
+    pub fn rejects(&self, ip: Option<IpAddr>, path: &str) -> bool {
+        let scopes = self.scopes.read().expect("ip filter lock");
+        scopes.iter().any(|scope| scope.applies_to(path) && scope.rejects(ip))
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for RejectHandler {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        match req.local_cache(|| Rejected(false)) {
+            Rejected(true) => Outcome::failure(Status::Forbidden),
+            Rejected(false) => Outcome::forward(data),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for IpFilter {
+    fn info(&self) -> Info {
+        Info { name: "IP Filter", kind: Kind::Attach | Kind::Request }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let routes: Vec<_> = [
+            Method::Get, Method::Put, Method::Post, Method::Delete, Method::Options,
+            Method::Head, Method::Trace, Method::Connect, Method::Patch,
+        ].iter().map(|&m| Route::new(m, "/", RejectHandler)).collect();
+
+        Ok(rocket.manage(self.clone()).mount(REJECT_BASE, routes))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data) {
+        let ip = req.trusted_client_ip();
+        if self.rejects(ip, req.uri().path()) {
+            match ip {
+                Some(ip) => { info_!("IP Filter: rejecting request from {}.", ip); }
+                None => { info_!("IP Filter: rejecting request from unknown client."); }
+            }
+
+            req.local_cache(|| Rejected(true));
+            req.set_uri(Origin::parse(REJECT_BASE).expect("valid reject base"));
+        }
+    }
+}
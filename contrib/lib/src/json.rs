@@ -15,22 +15,29 @@
 //! ```
 
 use std::ops::{Deref, DerefMut};
-use std::io;
+use std::io::{self, Cursor};
 use std::iter::FromIterator;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 use rocket::request::Request;
 use rocket::outcome::Outcome::*;
-use rocket::data::{Data, ByteUnit, Transform::*, Transformed};
-use rocket::data::{FromTransformedData, TransformFuture, FromDataFuture};
-use rocket::http::Status;
-use rocket::response::{self, Responder, content};
+use rocket::data::{self, Data, ByteUnit, Transform::*, Transformed};
+use rocket::data::{FromData, FromTransformedData, TransformFuture, FromDataFuture};
+use rocket::futures::{ready, stream::{self, Stream}};
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Response, Responder, content};
+use rocket::tokio::io::{AsyncRead, AsyncBufReadExt, BufReader, ReadBuf};
 
 use serde::{Serialize, Serializer};
-use serde::de::{Deserialize, Deserializer};
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 
 #[doc(hidden)]
 pub use serde_json::{json_internal, json_internal_vec};
 
+#[doc(inline)]
+pub use serde_json::Value;
+
 /// The JSON type: implements [`FromTransformedData`] and [`Responder`], allowing you to
 /// easily consume and respond with JSON.
 ///
@@ -192,9 +199,10 @@ impl<T> DerefMut for Json<T> {
 
 /// An arbitrary JSON value.
 ///
-/// This structure wraps `serde`'s [`Value`] type. Importantly, unlike `Value`,
-/// this type implements [`Responder`], allowing a value of this type to be
-/// returned directly from a handler.
+/// This structure wraps `serde`'s [`Value`] type. Like `Value`, this type
+/// implements [`Responder`], allowing a value of this type to be returned
+/// directly from a handler. It is the type of value the [`json!`](json) macro
+/// produces.
 ///
 /// [`Value`]: serde_json::value
 /// [`Responder`]: rocket::response::Responder
@@ -224,6 +232,22 @@ impl<T> DerefMut for Json<T> {
 ///     })
 /// }
 /// ```
+///
+/// A `serde_json::Value`, such as one built from a `JsonValue` or returned by
+/// `serde_json::to_value()`, can be returned directly as well:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_contrib;
+/// use rocket_contrib::json::Value;
+///
+/// #[get("/json")]
+/// fn get_json() -> Value {
+///     let mut value: Value = json!({ "id": 83 }).into();
+///     value["values"] = (1..=4).collect();
+///     value
+/// }
+/// ```
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct JsonValue(pub serde_json::Value);
 
@@ -290,6 +314,166 @@ impl<'r> Responder<'r, 'static> for JsonValue {
     }
 }
 
+/// Newline-delimited JSON, also known as JSON Lines or NDJSON.
+///
+/// `JsonLines` is both a responder that streams a sequence of values as JSON
+/// lines and, as `JsonLines<JsonLinesStream<T>>`, a data guard that
+/// incrementally parses a request body of JSON lines. Neither holds more than
+/// one item in memory at a time, making `JsonLines` suitable for endpoints that
+/// ship logs or import records in bulk.
+///
+/// ## Sending JSON Lines
+///
+/// Return a `JsonLines<S>` where `S` is a [`Stream`] of items that implement
+/// [`Serialize`]. The response has a Content-Type of `application/x-ndjson`
+/// and a streamed body in which each item is serialized on its own line. Each
+/// line is sent to the client as soon as its item is produced.
+///
+/// If an item fails to serialize, the error is logged and the response ends
+/// abruptly.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket::futures::stream::{self, Stream};
+/// use rocket_contrib::json::JsonLines;
+///
+/// #[get("/numbers")]
+/// fn numbers() -> JsonLines<impl Stream<Item = usize>> {
+///     JsonLines(stream::iter(0..100))
+/// }
+/// ```
+///
+/// ## Receiving JSON Lines
+///
+/// Use `JsonLines<JsonLinesStream<T>>` as a data guard, where `T` implements
+/// [`DeserializeOwned`]. The wrapped [`JsonLinesStream`] yields each line of
+/// the body, ignoring blank lines, deserialized as a `T`, or the error that
+/// occurred while reading or deserializing it.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// # type Record = usize;
+/// # fn store(_: Record) { }
+/// use rocket::futures::stream::StreamExt;
+/// use rocket_contrib::json::{JsonLines, JsonLinesStream};
+///
+/// #[post("/import", data = "<records>")]
+/// async fn import(mut records: JsonLines<JsonLinesStream<Record>>) -> String {
+///     let mut count = 0;
+///     while let Some(record) = records.0.next().await {
+///         match record {
+///             Ok(record) => { store(record); count += 1; }
+///             Err(e) => return format!("{} records imported; line {}: {}", count, count + 1, e),
+///         }
+///     }
+///
+///     format!("{} records imported", count)
+/// }
+/// ```
+///
+/// ## Incoming Data Limits
+///
+/// The default size limit for an incoming JSON lines body is 16MiB. The limit
+/// can be changed by setting the `limits.json-lines` configuration parameter.
+/// A body that exceeds the limit is truncated; the stream ends early.
+#[derive(Debug)]
+pub struct JsonLines<S>(pub S);
+
+/// A stream of values deserialized from a request body of JSON lines.
+///
+/// See [`JsonLines`] for details.
+pub struct JsonLinesStream<T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, serde_json::Error>> + Send>>,
+}
+
+const DEFAULT_LINES_LIMIT: ByteUnit = ByteUnit::Mebibyte(16);
+
+impl<T> Stream for JsonLinesStream<T> {
+    type Item = Result<T, serde_json::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> std::fmt::Debug for JsonLinesStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesStream").finish()
+    }
+}
+
+#[rocket::async_trait]
+impl<T: DeserializeOwned + Send + 'static> FromData for JsonLines<JsonLinesStream<T>> {
+    type Error = std::convert::Infallible;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        let limit = req.limits().get("json-lines").unwrap_or(DEFAULT_LINES_LIMIT);
+        let lines = BufReader::new(data.open(limit)).lines();
+        let inner = stream::unfold(Some(lines), |lines| async move {
+            let mut lines = lines?;
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) if line.trim().is_empty() => continue,
+                    Ok(Some(line)) => return Some((serde_json::from_str(&line), Some(lines))),
+                    Ok(None) => return None,
+                    Err(e) => return Some((Err(serde_json::Error::io(e)), None)),
+                }
+            }
+        });
+
+        Success(JsonLines(JsonLinesStream { inner: Box::pin(inner) }))
+    }
+}
+
+/// Serializes each item of the stream into a line of JSON, one item at a time.
+struct JsonLinesReader<S> {
+    stream: Pin<Box<S>>,
+    line: Cursor<Vec<u8>>,
+}
+
+impl<S: Stream> AsyncRead for JsonLinesReader<S> where S::Item: Serialize {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if self.line.position() < self.line.get_ref().len() as u64 {
+                return Pin::new(&mut self.line).poll_read(cx, buf);
+            }
+
+            match ready!(self.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let mut line = serde_json::to_vec(&item).map_err(|e| {
+                        error_!("JSON line failed to serialize: {:?}", e);
+                        io::Error::new(io::ErrorKind::Other, e)
+                    })?;
+
+                    line.push(b'\n');
+                    self.line = Cursor::new(line);
+                }
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+/// Streams each item of the stream as a line of JSON. Returns a response with
+/// Content-Type `application/x-ndjson` and a streamed body.
+impl<'r, 'o: 'r, S> Responder<'r, 'o> for JsonLines<S>
+    where S: Stream + Send + 'o, S::Item: Serialize
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let reader = JsonLinesReader { stream: Box::pin(self.0), line: Cursor::new(vec![]) };
+        Response::build()
+            .header(ContentType::new("application", "x-ndjson"))
+            .streamed_body(reader)
+            .ok()
+    }
+}
+
 /// A macro to create ad-hoc JSON serializable values using JSON syntax.
 ///
 /// # Usage
@@ -18,12 +18,20 @@
 //!
 //! * [json*](type@json) - JSON (de)serialization
 //! * [serve*](serve) - Static File Serving
+//! * [serve_embedded](serve::MemoryServer::from_embedded()) - Static Files Embedded in the Binary
 //! * [msgpack](msgpack) - MessagePack (de)serialization
+//! * [csv](csv) - CSV (de)serialization
 //! * [handlebars_templates](templates) - Handlebars Templating
 //! * [tera_templates](templates) - Tera Templating
 //! * [uuid](uuid) - UUID (de)serialization
 //! * [${database}_pool](databases) - Database Configuration and Pooling
 //! * [helmet](helmet) - Fairing for Security and Privacy Headers
+//! * [access_log](access_log) - Fairing for Access Logging
+//! * [body_capture](body_capture) - Fairing for Debug Body Capture
+//! * [ip_filter](ip_filter) - Fairing for IP Allow and Deny Lists
+//! * [geoip](geoip) - GeoIP Lookup Request Guard
+//! * [geoip_maxmind](geoip::MaxMind) - MaxMind Database GeoIP Resolver
+//! * [otel](otel) - OpenTelemetry Trace and Metrics Export
 //!
 //! The recommend way to include features from this crate via Rocket in your
 //! project is by adding a `[dependencies.rocket_contrib]` section to your
@@ -46,10 +54,16 @@
 #[cfg(feature="json")] #[macro_use] pub mod json;
 #[cfg(feature="serve")] pub mod serve;
 #[cfg(feature="msgpack")] pub mod msgpack;
+#[cfg(feature="csv")] pub mod csv;
 #[cfg(feature="templates")] pub mod templates;
 #[cfg(feature="uuid")] pub mod uuid;
 #[cfg(feature="databases")] pub mod databases;
 #[cfg(feature = "helmet")] pub mod helmet;
+#[cfg(feature = "access_log")] pub mod access_log;
+#[cfg(feature = "body_capture")] pub mod body_capture;
+#[cfg(feature = "ip_filter")] pub mod ip_filter;
+#[cfg(feature = "geoip")] pub mod geoip;
+#[cfg(feature = "otel")] pub mod otel;
 // TODO.async: Migrate compression, reenable this, tests, and add to docs.
 //#[cfg(any(feature="brotli_compression", feature="gzip_compression"))] pub mod compression;
 
@@ -0,0 +1,453 @@
+//! Exporting request traces and metrics via OpenTelemetry.
+//!
+//! The [`OpenTelemetry`] fairing records a server span and duration metrics
+//! for every request and periodically exports them to an [OTLP] collector
+//! using OTLP/HTTP with JSON encoding. Spans are part of the distributed
+//! trace identified by the request's [`TraceContext`]: a span is recorded
+//! only if the trace is sampled, and its parent is the caller's span, if any.
+//! Metrics are exported as a cumulative `http.server.duration` histogram, in
+//! milliseconds, per method, route, and status.
+//!
+//! [OTLP]: https://opentelemetry.io/docs/specs/otlp/
+//!
+//! # Enabling
+//!
+//! This module is only available when the `otel` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["otel"]
+//! ```
+//!
+//! # Configuration
+//!
+//! The exporter is configured via the `otel` configuration parameter, a
+//! dictionary with the following keys, all of which are optional:
+//!
+//! | key             | type           | default                                 |
+//! |-----------------|----------------|-----------------------------------------|
+//! | `endpoint`      | string         | `"http://localhost:4318"`               |
+//! | `service_name`  | string         | `"rocket"`                              |
+//! | `environment`   | string         | the name of the selected profile        |
+//! | `interval`      | integer (secs) | `10`                                    |
+//! | `max_queue`     | integer        | `2048`                                  |
+//! | `attributes`    | dictionary     | `{}`                                    |
+//!
+//! The `service_name` and `environment` are exported as the `service.name` and
+//! `deployment.environment` resource attributes along with any additional
+//! string `attributes`. At most `max_queue` spans are buffered between
+//! exports; additional spans are dropped. For example, in `Rocket.toml`:
+//!
+//! ```toml
+//! [default.otel]
+//! endpoint = "http://collector.local:4318"
+//! service_name = "accounts"
+//! attributes = { "service.version" = "1.2.0" }
+//! ```
+//!
+//! The exporter sends data using [`HttpClient`], so only `http` endpoints are
+//! supported. Export failures are logged and the failed batch is discarded.
+//!
+//! # Usage
+//!
+//! Attach the fairing returned by [`OpenTelemetry::fairing()`]. Exports
+//! begin at launch. The managed [`Telemetry`] handle can be used to export
+//! buffered data immediately.
+//!
+//! ```rust
+//! # extern crate rocket;
+//! # extern crate rocket_contrib;
+//! use rocket_contrib::otel::OpenTelemetry;
+//!
+//! # let _ = {
+//! rocket::ignite().attach(OpenTelemetry::fairing())
+//! # };
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+
+use rocket::{Rocket, Request, Response, Data};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::client::{HttpClient, ClientConfig};
+use rocket::request::TraceContext;
+use rocket::http::hyper::{self, Body};
+
+/// The upper bounds, in milliseconds, of the duration histogram buckets.
+const BOUNDS: &[f64] = &[
+    5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0,
+    7500.0, 10000.0,
+];
+
+/// Configuration for the [`OpenTelemetry`] fairing.
+///
+/// See the [module level documentation](crate::otel#configuration) for
+/// details on each parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtelConfig {
+    /// The base URI of the OTLP/HTTP collector.
+    pub endpoint: String,
+    /// The value of the `service.name` resource attribute.
+    pub service_name: String,
+    /// The value of the `deployment.environment` resource attribute. The
+    /// name of the selected profile when `None`.
+    pub environment: Option<String>,
+    /// The number of seconds between exports.
+    pub interval: u64,
+    /// The maximum number of spans buffered between exports.
+    pub max_queue: usize,
+    /// Additional resource attributes.
+    pub attributes: HashMap<String, String>,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        OtelConfig {
+            endpoint: "http://localhost:4318".into(),
+            service_name: "rocket".into(),
+            environment: None,
+            interval: 10,
+            max_queue: 2048,
+            attributes: HashMap::new(),
+        }
+    }
+}
+
+/// The fairing that records and exports request telemetry.
+///
+/// See the [module level documentation](crate::otel) for details.
+pub struct OpenTelemetry {
+    _priv: (),
+}
+
+impl OpenTelemetry {
+    /// Returns a fairing that records telemetry for every request and exports
+    /// it to the collector configured by the `otel` configuration parameter.
+    /// Launch fails if the configuration is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::otel::OpenTelemetry;
+    ///
+    /// let rocket = rocket::ignite().attach(OpenTelemetry::fairing());
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        OpenTelemetry { _priv: () }
+    }
+}
+
+/// A handle to the telemetry recorded by the [`OpenTelemetry`] fairing.
+///
+/// A `Telemetry` is placed in managed state by the fairing and can be
+/// retrieved via [`State`](rocket::State) or
+/// [`Rocket::state()`](rocket::Rocket::state()).
+#[derive(Clone)]
+pub struct Telemetry {
+    inner: Arc<Exporter>,
+}
+
+/// Aggregated durations of requests with the same attributes.
+#[derive(Default)]
+struct Histogram {
+    count: u64,
+    sum: f64,
+    buckets: Vec<u64>,
+}
+
+struct Exporter {
+    config: OtelConfig,
+    resource: Value,
+    client: HttpClient,
+    start: SystemTime,
+    spans: Mutex<Vec<Value>>,
+    dropped: AtomicU64,
+    histograms: Mutex<HashMap<(String, String, u16), Histogram>>,
+}
+
+/// Request-local start time of a request.
+struct Start(SystemTime, Instant);
+
+fn unix_nanos(time: SystemTime) -> String {
+    let nanos = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    nanos.to_string()
+}
+
+fn attributes<'a, I>(attributes: I) -> Value
+    where I: IntoIterator<Item = (&'a str, Value)>
+{
+    attributes.into_iter()
+        .map(|(key, value)| json!({ "key": key, "value": value }))
+        .collect()
+}
+
+impl Telemetry {
+    /// Exports all buffered spans and the current metrics immediately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::State;
+    /// use rocket_contrib::otel::Telemetry;
+    ///
+    /// #[post("/flush")]
+    /// async fn flush(telemetry: State<'_, Telemetry>) {
+    ///     telemetry.flush().await;
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub async fn flush(&self) {
+        self.inner.flush().await
+    }
+
+    /// Returns the number of spans dropped because the queue was full.
+    pub fn dropped_spans(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Exporter {
+    fn scope() -> Value {
+        json!({ "name": "rocket_contrib", "version": env!("CARGO_PKG_VERSION") })
+    }
+
+    fn record(&self, req: &Request<'_>, res: &Response<'_>, trace: &TraceContext) {
+        let (start, instant) = match req.local_cache(|| None::<Start>) {
+            Some(Start(start, instant)) => (*start, *instant),
+            None => return,
+        };
+
+        let elapsed = instant.elapsed();
+        let end = start + elapsed;
+        let method = req.method().as_str();
+        let route = req.route().map(|r| r.uri.path().to_string());
+        let status = res.status().code;
+
+        let millis = elapsed.as_secs_f64() * 1000.0;
+        let key = (method.to_string(), route.clone().unwrap_or_default(), status);
+        if let Ok(mut histograms) = self.histograms.lock() {
+            let histogram = histograms.entry(key).or_default();
+            histogram.buckets.resize(BOUNDS.len() + 1, 0);
+            histogram.buckets[BOUNDS.iter().take_while(|&&b| millis > b).count()] += 1;
+            histogram.count += 1;
+            histogram.sum += millis;
+        }
+
+        if !trace.is_sampled() {
+            return;
+        }
+
+        let name = match route {
+            Some(ref route) => format!("{} {}", method, route),
+            None => method.to_string(),
+        };
+
+        let mut attrs = vec![
+            ("http.method", json!({ "stringValue": method })),
+            ("http.target", json!({ "stringValue": req.uri().to_string() })),
+            ("http.status_code", json!({ "intValue": status.to_string() })),
+        ];
+
+        if let Some(ref route) = route {
+            attrs.push(("http.route", json!({ "stringValue": route })));
+        }
+
+        let span = json!({
+            "traceId": trace.trace_id(),
+            "spanId": trace.span_id(),
+            "parentSpanId": trace.parent_id().unwrap_or_default(),
+            "name": name,
+            "kind": 2,
+            "startTimeUnixNano": unix_nanos(start),
+            "endTimeUnixNano": unix_nanos(end),
+            "attributes": attributes(attrs),
+            "status": { "code": if status >= 500 { 2 } else { 0 } },
+        });
+
+        if let Ok(mut spans) = self.spans.lock() {
+            if spans.len() < self.config.max_queue {
+                spans.push(span);
+            } else {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<Value> {
+        let histograms = self.histograms.lock().ok()?;
+        if histograms.is_empty() {
+            return None;
+        }
+
+        let (start, now) = (unix_nanos(self.start), unix_nanos(SystemTime::now()));
+        let points: Vec<Value> = histograms.iter()
+            .map(|((method, route, status), histogram)| {
+                let mut attrs = vec![
+                    ("http.method", json!({ "stringValue": method })),
+                    ("http.status_code", json!({ "intValue": status.to_string() })),
+                ];
+
+                if !route.is_empty() {
+                    attrs.push(("http.route", json!({ "stringValue": route })));
+                }
+
+                json!({
+                    "attributes": attributes(attrs),
+                    "startTimeUnixNano": start,
+                    "timeUnixNano": now,
+                    "count": histogram.count.to_string(),
+                    "sum": histogram.sum,
+                    "bucketCounts": histogram.buckets.iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>(),
+                    "explicitBounds": BOUNDS,
+                })
+            })
+            .collect();
+
+        Some(json!({
+            "resourceMetrics": [{
+                "resource": self.resource,
+                "scopeMetrics": [{
+                    "scope": Exporter::scope(),
+                    "metrics": [{
+                        "name": "http.server.duration",
+                        "unit": "ms",
+                        "histogram": { "aggregationTemporality": 2, "dataPoints": points },
+                    }],
+                }],
+            }],
+        }))
+    }
+
+    async fn send(&self, path: &str, payload: Value) {
+        let uri = format!("{}{}", self.config.endpoint.trim_end_matches('/'), path);
+        let request = hyper::Request::post(&uri)
+            .header("Content-Type", "application/json")
+            .body(Body::from(payload.to_string()));
+
+        let result = match request {
+            Ok(request) => self.client.send(request).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => warn!("OpenTelemetry: {} export failed: {}", path, response.status()),
+            Err(e) => warn!("OpenTelemetry: {} export failed: {}", path, e),
+        }
+    }
+
+    async fn flush(&self) {
+        let spans = match self.spans.lock() {
+            Ok(mut spans) => std::mem::take(&mut *spans),
+            Err(_) => vec![],
+        };
+
+        if !spans.is_empty() {
+            let payload = json!({
+                "resourceSpans": [{
+                    "resource": self.resource,
+                    "scopeSpans": [{ "scope": Exporter::scope(), "spans": spans }],
+                }],
+            });
+
+            self.send("/v1/traces", payload).await;
+        }
+
+        if let Some(payload) = self.metrics() {
+            self.send("/v1/metrics", payload).await;
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for OpenTelemetry {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenTelemetry",
+            kind: Kind::Attach | Kind::Launch | Kind::Request | Kind::Response
+        }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let config = match rocket.figment().extract_inner::<OtelConfig>("otel") {
+            Ok(config) => config,
+            Err(e) if e.missing() => OtelConfig::default(),
+            Err(e) => {
+                rocket::config::pretty_print_error(e);
+                return Err(rocket);
+            }
+        };
+
+        let client = match HttpClient::new(ClientConfig::default()) {
+            Ok(client) => client,
+            Err(e) => {
+                error!("OpenTelemetry: failed to create HTTP client: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        let profile = rocket.config().profile.to_string();
+        let environment = config.environment.clone().unwrap_or(profile);
+        let mut attrs = vec![
+            ("service.name", json!({ "stringValue": config.service_name })),
+            ("deployment.environment", json!({ "stringValue": environment })),
+            ("telemetry.sdk.name", json!({ "stringValue": "rocket" })),
+        ];
+
+        let mut extra: Vec<_> = config.attributes.iter().collect();
+        extra.sort();
+        attrs.extend(extra.into_iter().map(|(k, v)| (k.as_str(), json!({ "stringValue": v }))));
+        let resource = json!({ "attributes": attributes(attrs) });
+
+        let inner = Arc::new(Exporter {
+            config,
+            resource,
+            client,
+            start: SystemTime::now(),
+            spans: Mutex::new(vec![]),
+            dropped: AtomicU64::new(0),
+            histograms: Mutex::new(HashMap::new()),
+        });
+
+        Ok(rocket.manage(Telemetry { inner }))
+    }
+
+    fn on_launch(&self, rocket: &Rocket) {
+        let telemetry = match rocket.state::<Telemetry>() {
+            Some(telemetry) => telemetry.clone(),
+            None => return,
+        };
+
+        let interval = Duration::from_secs(telemetry.inner.config.interval.max(1));
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                telemetry.flush().await;
+            }
+        });
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data) {
+        req.local_cache(|| Some(Start(SystemTime::now(), Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if let Some(telemetry) = req.managed_state::<Telemetry>() {
+            if let rocket::outcome::Outcome::Success(trace) = req.guard::<&TraceContext>().await {
+                telemetry.inner.record(req, res, trace);
+            }
+        }
+    }
+}
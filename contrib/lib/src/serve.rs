@@ -1,6 +1,8 @@
-//! Custom handler and options for static file serving.
+//! Custom handlers and options for static file serving.
 //!
-//! See the [`StaticFiles`](crate::serve::StaticFiles) type for further details.
+//! See the [`StaticFiles`](crate::serve::StaticFiles) type for serving files
+//! from the file system and the [`MemoryServer`](crate::serve::MemoryServer)
+//! type for serving files compiled into the binary.
 //!
 //! # Enabling
 //!
@@ -13,13 +15,22 @@
 //! default-features = false
 //! features = ["serve"]
 //! ```
+//!
+//! To embed entire directories into the binary with
+//! [`MemoryServer::from_embedded()`], enable the `serve_embedded` feature
+//! instead.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Cursor;
 use std::path::{PathBuf, Path};
+use std::sync::Arc;
 
 use rocket::{Request, Data, Route};
-use rocket::http::{Method, uri::Segments, ext::IntoOwned};
+use rocket::http::{Method, ContentType, ETag, Status, uri::Segments, ext::IntoOwned};
 use rocket::handler::{Handler, Outcome};
-use rocket::response::{NamedFile, Redirect};
+use rocket::request::IfNoneMatch;
+use rocket::response::{self, NamedFile, Redirect, Responder, Response};
 
 /// Generates a crate-relative version of `$path`.
 ///
@@ -68,6 +79,12 @@ pub use crate_relative;
 ///   * [`Options::Index`] - Render `index.html` pages for directory requests.
 ///   * [`Options::NormalizeDirs`] - Redirect directories without a trailing
 ///     slash to ones with a trailing slash.
+///   * [`Options::Fingerprint`] - Additionally serve files under URLs
+///     containing a hash of their contents, with far-future caching.
+///   * [`Options::Precompressed`] - Serve precompressed `.br` and `.gz`
+///     siblings of files to clients that accept them.
+///   * [`Options::SpaFallback`] - Serve the root `index.html` for paths that
+///     don't correspond to a file, for single-page applications.
 ///
 /// `Options` structures can be `or`d together to select two or more options.
 /// For instance, to request that both dot files and index pages be returned,
@@ -104,6 +121,44 @@ impl Options {
     /// directory, rather than its parent. This is _not_ enabled by default.
     pub const NormalizeDirs: Options = Options(0b0100);
 
+    /// `Options` enabling serving files under _fingerprinted_ paths, which
+    /// contain a hash of the file's contents.
+    ///
+    /// When enabled, the [`StaticFiles`] handler hashes every file in its
+    /// directory when it is constructed and records the fingerprinted path of
+    /// each in a [`Manifest`], available via [`StaticFiles::manifest()`]. A
+    /// file `css/app.css` is additionally served as, for example,
+    /// `css/app.3fa9b2c1.css`. Responses for fingerprinted paths include a
+    /// `Cache-Control` header that allows clients to cache them indefinitely:
+    /// when the file changes, so does its path. Files changed after the
+    /// handler is constructed are not rehashed. This is _not_ enabled by
+    /// default.
+    pub const Fingerprint: Options = Options(0b1000);
+
+    /// `Options` enabling serving precompressed variants of files.
+    ///
+    /// When enabled, the [`StaticFiles`] handler responds to a request for a
+    /// file `app.js` with the sibling file `app.js.br` or `app.js.gz`, if it
+    /// exists and the client's `Accept-Encoding` header accepts `br` or `gzip`,
+    /// respectively, preferring `br`. The response has the Content-Type of
+    /// `app.js` and the appropriate `Content-Encoding`. Otherwise, `app.js`
+    /// itself is served. Responses for files with precompressed siblings
+    /// include a `Vary: Accept-Encoding` header. This is _not_ enabled by
+    /// default.
+    pub const Precompressed: Options = Options(0b1_0000);
+
+    /// `Options` enabling a fallback to the root `index.html` for single-page
+    /// applications.
+    ///
+    /// When enabled, the [`StaticFiles`] handler responds to a request for a
+    /// path that doesn't correspond to a file with `${root}/index.html` so that
+    /// routing can be handled client-side. Requests are still forwarded, and
+    /// thus typically answered with a `404 Not Found`, if the path's final
+    /// segment has an extension, like `app.js` or `logo.png`, or if the path
+    /// lies under a prefix excluded with [`StaticFiles::exclude()`], like an
+    /// API's. This is _not_ enabled by default.
+    pub const SpaFallback: Options = Options(0b10_0000);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the options in `other` are also in `self`.
     ///
@@ -202,11 +257,259 @@ impl std::ops::BitOr for Options {
 ///     rocket::ignite().mount("/", StaticFiles::from(crate_relative!("/static")))
 /// }
 /// ```
+///
+/// ## Fingerprinting
+///
+/// With [`Options::Fingerprint`], files are also served under paths that
+/// contain a hash of their contents, with headers allowing clients to cache
+/// them indefinitely. Use the [`Manifest`] returned by
+/// [`StaticFiles::manifest()`] to find the fingerprinted path of a file, for
+/// instance, from a template helper:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::serve::{StaticFiles, Options, Manifest, crate_relative};
+///
+/// #[launch]
+/// fn rocket() -> rocket::Rocket {
+///     let options = Options::Index | Options::Fingerprint;
+///     let assets = StaticFiles::new(crate_relative!("/assets"), options);
+///     let manifest: Manifest = assets.manifest();
+///     rocket::ignite()
+///         .mount("/assets", assets)
+///         .manage(manifest)
+/// }
+/// ```
+///
+/// ## Precompressed Files
+///
+/// With [`Options::Precompressed`], files compressed ahead of time, for
+/// instance by a build step that writes `app.js.br` and `app.js.gz` next to
+/// `app.js`, are served in place of the original to clients that accept
+/// their encoding. Files without precompressed siblings are served as-is;
+/// `StaticFiles` never compresses responses itself.
 #[derive(Clone)]
 pub struct StaticFiles {
     root: PathBuf,
     options: Options,
     rank: isize,
+    manifest: Manifest,
+    excluded: Vec<PathBuf>,
+}
+
+/// A mapping from the paths of files served by a [`StaticFiles`] handler to
+/// their fingerprinted paths.
+///
+/// A `Manifest` is returned by [`StaticFiles::manifest()`]. Paths are relative
+/// to the handler's directory and use `/` as a separator. A `Manifest` is
+/// cheap to clone and may be placed in managed state for use by handlers and
+/// template helpers.
+///
+/// The fingerprint is derived from an FNV-1a hash of a file's contents, so it
+/// is the same across restarts and across servers serving the same files.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    inner: Arc<ManifestInner>,
+}
+
+#[derive(Debug, Default)]
+struct ManifestInner {
+    /// Map from file path to fingerprinted path.
+    paths: HashMap<String, String>,
+    /// Map from fingerprinted path to file path.
+    files: HashMap<String, String>,
+}
+
+impl Manifest {
+    /// Returns the fingerprinted path of the file at `path`, relative to the
+    /// handler's directory, if the file was fingerprinted. A leading `/` in
+    /// `path` is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::{StaticFiles, Options};
+    ///
+    /// let manifest = StaticFiles::new("/www/public", Options::Fingerprint).manifest();
+    /// if let Some(path) = manifest.get("/css/app.css") {
+    ///     println!("<link rel=\"stylesheet\" href=\"/public/{}\">", path);
+    /// }
+    /// ```
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.inner.paths.get(path.trim_start_matches('/')).map(|s| s.as_str())
+    }
+
+    /// Returns an iterator over pairs of file paths and their fingerprinted
+    /// paths, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.inner.paths.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns `true` if no files were fingerprinted.
+    pub fn is_empty(&self) -> bool {
+        self.inner.paths.is_empty()
+    }
+
+    /// Hashes every file under `root`, skipping dotfiles unless `dotfiles`.
+    /// Symlinked directories are followed unless they lead back to a directory
+    /// being visited, which would otherwise recurse forever.
+    fn build(root: &Path, dotfiles: bool) -> Manifest {
+        fn visit(
+            dir: &Path,
+            prefix: &str,
+            dotfiles: bool,
+            ancestors: &mut Vec<PathBuf>,
+            inner: &mut ManifestInner,
+        ) {
+            let canonical = match dir.canonicalize() {
+                Ok(canonical) => canonical,
+                Err(e) => {
+                    warn_!("Failed to read '{}' for fingerprinting: {}", dir.display(), e);
+                    return;
+                }
+            };
+
+            if ancestors.contains(&canonical) {
+                warn_!("Not fingerprinting '{}': symlink cycle.", dir.display());
+                return;
+            }
+
+            let entries = match std::fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn_!("Failed to read '{}' for fingerprinting: {}", dir.display(), e);
+                    return;
+                }
+            };
+
+            for entry in entries.filter_map(Result::ok) {
+                let name = match entry.file_name().into_string() {
+                    Ok(name) if dotfiles || !name.starts_with('.') => name,
+                    _ => continue,
+                };
+
+                let path = format!("{}{}", prefix, name);
+                let file_path = entry.path();
+                if file_path.is_dir() {
+                    ancestors.push(canonical.clone());
+                    visit(&file_path, &format!("{}/", path), dotfiles, ancestors, inner);
+                    ancestors.pop();
+                    continue;
+                }
+
+                match std::fs::read(&file_path) {
+                    Ok(contents) => {
+                        let hashed = fingerprinted(&path, fnv1a(&contents));
+                        inner.files.insert(hashed.clone(), path.clone());
+                        inner.paths.insert(path, hashed);
+                    }
+                    Err(e) => warn_!("Failed to fingerprint '{}': {}", file_path.display(), e),
+                }
+            }
+        }
+
+        let mut inner = ManifestInner::default();
+        visit(root, "", dotfiles, &mut vec![], &mut inner);
+        Manifest { inner: Arc::new(inner) }
+    }
+}
+
+/// Returns `path` with `hash` inserted before the file name's extension:
+/// `css/app.css` becomes `css/app.{hash}.css`.
+fn fingerprinted(path: &str, hash: u64) -> String {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    match path[name_start..].rfind('.') {
+        Some(i) if i > 0 => {
+            let (stem, ext) = path.split_at(name_start + i);
+            format!("{}.{:08x}{}", stem, hash as u32, ext)
+        }
+        _ => format!("{}.{:08x}", path, hash as u32),
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// The precompressed sibling extensions, in order of preference, and the
+/// content codings they correspond to.
+const PRECOMPRESSED: &[(&str, &str)] = &[("br", "br"), ("gz", "gzip")];
+
+/// A file served by `StaticFiles`, possibly precompressed or fingerprinted.
+struct ServedFile {
+    file: NamedFile,
+    /// The content coding of `file` if it is a precompressed sibling, along
+    /// with the Content-Type of the file it is a variant of.
+    encoding: Option<(&'static str, Option<ContentType>)>,
+    /// Whether the file has precompressed siblings.
+    vary: bool,
+    /// Whether the file was requested via a fingerprinted path.
+    immutable: bool,
+}
+
+impl ServedFile {
+    /// Opens the file at `path` or, if `options` enables it and the client
+    /// accepts it, one of its precompressed siblings.
+    async fn open(options: Options, req: &Request<'_>, path: &Path, immutable: bool) -> Option<Self> {
+        if !path.is_file() {
+            return None;
+        }
+
+        let mut vary = false;
+        if options.contains(Options::Precompressed) {
+            for &(ext, encoding) in PRECOMPRESSED {
+                let mut sibling = path.as_os_str().to_owned();
+                sibling.push(".");
+                sibling.push(ext);
+                let sibling = PathBuf::from(sibling);
+                if !sibling.is_file() {
+                    continue;
+                }
+
+                vary = true;
+                if accepts_encoding(req, encoding) {
+                    if let Ok(file) = NamedFile::open(sibling).await {
+                        let content_type = path.extension()
+                            .and_then(|ext| ext.to_str())
+                            .and_then(ContentType::from_extension);
+
+                        let encoding = Some((encoding, content_type));
+                        return Some(ServedFile { file, encoding, vary, immutable });
+                    }
+                }
+            }
+        }
+
+        let file = NamedFile::open(path).await.ok()?;
+        Some(ServedFile { file, encoding: None, vary, immutable })
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ServedFile {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.file.respond_to(req)?;
+        if let Some((encoding, content_type)) = self.encoding {
+            response.remove_header("Content-Type");
+            if let Some(content_type) = content_type {
+                response.set_header(content_type);
+            }
+
+            response.set_raw_header("Content-Encoding", encoding);
+        }
+
+        if self.vary {
+            response.set_raw_header("Vary", "Accept-Encoding");
+        }
+
+        if self.immutable {
+            response.set_raw_header("Cache-Control", "public, max-age=31536000, immutable");
+        }
+
+        Ok(response)
+    }
 }
 
 impl StaticFiles {
@@ -293,7 +596,34 @@ impl StaticFiles {
             panic!("refusing to continue due to invalid static files path");
         }
 
-        StaticFiles { root: path.into(), options, rank: Self::DEFAULT_RANK }
+        let manifest = match options.contains(Options::Fingerprint) {
+            true => Manifest::build(path, options.contains(Options::DotFiles)),
+            false => Manifest::default(),
+        };
+
+        StaticFiles {
+            root: path.into(),
+            options,
+            rank: Self::DEFAULT_RANK,
+            manifest,
+            excluded: vec![],
+        }
+    }
+
+    /// Returns the [`Manifest`] of fingerprinted paths. The manifest is empty
+    /// unless [`Options::Fingerprint`] is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::{StaticFiles, Options};
+    ///
+    /// let files = StaticFiles::new("/www/public", Options::Fingerprint);
+    /// let app_css = files.manifest().get("app.css").map(|s| s.to_string());
+    /// ```
+    pub fn manifest(&self) -> Manifest {
+        self.manifest.clone()
     }
 
     /// Sets the rank for generated routes to `rank`.
@@ -314,6 +644,30 @@ impl StaticFiles {
         self.rank = rank;
         self
     }
+
+    /// Excludes paths under `prefix`, relative to the mount point, from the
+    /// [`Options::SpaFallback`] fallback. Requests for such paths that don't
+    /// correspond to a file are forwarded as usual. The prefix matches whole
+    /// segments: excluding `/api` excludes `/api` and `/api/users` but not
+    /// `/apiary`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::{StaticFiles, Options};
+    ///
+    /// #[launch]
+    /// fn rocket() -> rocket::Rocket {
+    ///     let app = StaticFiles::new("/www/app", Options::SpaFallback).exclude("/api");
+    ///     rocket::ignite().mount("/", app)
+    /// }
+    /// ```
+    pub fn exclude<P: AsRef<str>>(mut self, prefix: P) -> Self {
+        self.excluded.push(prefix.as_ref().trim_start_matches('/').into());
+        self
+    }
 }
 
 impl Into<Vec<Route>> for StaticFiles {
@@ -322,7 +676,11 @@ impl Into<Vec<Route>> for StaticFiles {
         // `Index` requires routing the index for obvious reasons.
         // `NormalizeDirs` requires routing the index so a `.mount("/foo")` with
         // a request `/foo`, can be redirected to `/foo/`.
-        if self.options.contains(Options::Index) || self.options.contains(Options::NormalizeDirs) {
+        // `SpaFallback` requires routing the index to serve `index.html` there.
+        if self.options.contains(Options::Index)
+            || self.options.contains(Options::NormalizeDirs)
+            || self.options.contains(Options::SpaFallback)
+        {
             let index = Route::ranked(self.rank, Method::Get, "/", self);
             vec![index, non_index]
         } else {
@@ -331,6 +689,43 @@ impl Into<Vec<Route>> for StaticFiles {
     }
 }
 
+impl StaticFiles {
+    /// Returns the path of the file whose fingerprinted path is `path`, if
+    /// there is one.
+    fn fingerprinted_file(&self, path: &Path) -> Option<&str> {
+        if self.manifest.is_empty() {
+            return None;
+        }
+
+        let path: Vec<_> = path.iter().map(|c| c.to_str()).collect::<Option<_>>()?;
+        self.manifest.inner.files.get(&path.join("/")).map(|s| s.as_str())
+    }
+
+    /// Returns `true` if a request for `path`, which doesn't correspond to a
+    /// file, should be answered with the root `index.html`.
+    fn falls_back(&self, path: &Path) -> bool {
+        self.options.contains(Options::SpaFallback)
+            && path.extension().is_none()
+            && !self.excluded.iter().any(|prefix| path.starts_with(prefix))
+    }
+
+    async fn handle_path<'r>(&self, req: &'r Request<'_>, data: Data, path: &Path) -> Outcome<'r> {
+        // Serve fingerprinted paths from the file they were derived from.
+        if let Some(file) = self.fingerprinted_file(path) {
+            let file = ServedFile::open(self.options, req, &self.root.join(file), true).await;
+            return Outcome::from_or_forward(req, data, file);
+        }
+
+        let path = self.root.join(path);
+        if path.is_dir() {
+            return handle_dir(self.options, req, data, path).await;
+        }
+
+        let file = ServedFile::open(self.options, req, &path, false).await;
+        Outcome::from_or_forward(req, data, file)
+    }
+}
+
 async fn handle_dir<'r, P>(opt: Options, r: &'r Request<'_>, d: Data, p: P) -> Outcome<'r>
     where P: AsRef<Path>
 {
@@ -346,7 +741,7 @@ async fn handle_dir<'r, P>(opt: Options, r: &'r Request<'_>, d: Data, p: P) -> O
         return Outcome::forward(d);
     }
 
-    let file = NamedFile::open(p.as_ref().join("index.html")).await.ok();
+    let file = ServedFile::open(opt, r, &p.as_ref().join("index.html"), false).await;
     Outcome::from_or_forward(r, d, file)
 }
 
@@ -357,22 +752,328 @@ impl Handler for StaticFiles {
         // requested a handling of index files.
         let current_route = req.route().expect("route while handling");
         let is_segments_route = current_route.uri.path().ends_with(">");
-        if !is_segments_route {
-            return handle_dir(self.options, req, data, &self.root).await;
+        let (path, outcome) = if !is_segments_route {
+            (PathBuf::new(), handle_dir(self.options, req, data, &self.root).await)
+        } else {
+            // Otherwise, we're handling segments. Get the segments as a
+            // `PathBuf`, only allowing dotfiles if the user allowed it.
+            let allow_dotfiles = self.options.contains(Options::DotFiles);
+            let path = req.get_segments::<Segments<'_>>(0)
+                .and_then(|res| res.ok())
+                .and_then(|segments| segments.into_path_buf(allow_dotfiles).ok());
+
+            match path {
+                Some(path) => {
+                    let outcome = self.handle_path(req, data, &path).await;
+                    (path, outcome)
+                }
+                None => return Outcome::forward(data),
+            }
+        };
+
+        // If nothing was found, let a single-page application handle the path.
+        match outcome {
+            Outcome::Forward(data) if self.falls_back(&path) => {
+                let index = self.root.join("index.html");
+                let file = ServedFile::open(self.options, req, &index, false).await;
+                Outcome::from_or_forward(req, data, file)
+            }
+            outcome => outcome,
+        }
+    }
+}
+
+#[cfg(feature = "serve_embedded")]
+#[doc(inline)]
+pub use include_dir::{include_dir, Dir};
+
+/// Custom handler for serving static files compiled into the binary.
+///
+/// A `MemoryServer` serves files from memory rather than from the file
+/// system, allowing an application to be deployed as a single binary. Files
+/// are added with [`MemoryServer::file()`], typically with contents from
+/// `include_bytes!()`, or, with the `serve_embedded` feature enabled, an
+/// entire directory is embedded with [`include_dir!`] and served with
+/// [`MemoryServer::from_embedded()`].
+///
+/// Like [`StaticFiles`], a `MemoryServer` is mounted at a desired path, where
+/// it generates routes with a default rank of `10` that forward when a file is
+/// not found. A request for a directory is answered with the directory's
+/// `index.html`, if there is one. Each response has:
+///
+///   * a Content-Type derived from the file's extension
+///   * a strong `ETag` derived from the file's contents, with a `-gzip`
+///     suffix for compressed responses; requests with a matching
+///     `If-None-Match` header receive a `304 Not Modified`
+///   * a gzip-compressed body when the client accepts `gzip` and a
+///     precompressed variant of the file, with the same path and an added
+///     `.gz` extension, was also added
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::serve::MemoryServer;
+///
+/// #[launch]
+/// fn rocket() -> rocket::Rocket {
+///     let assets = MemoryServer::new()
+///         .file("index.html", &b"<h1>Hello!</h1>"[..])
+///         .file("app.js", &b"console.log('hello')"[..]);
+///
+///     rocket::ignite().mount("/", assets)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct MemoryServer {
+    files: Arc<HashMap<String, MemoryFile>>,
+    rank: isize,
+}
+
+#[derive(Clone)]
+struct MemoryFile {
+    identity: Option<Contents>,
+    gzip: Option<Contents>,
+    etag: ETag,
+}
+
+/// The contents of a file in a `MemoryServer`: usually embedded, static data.
+#[derive(Clone)]
+enum Contents {
+    Static(&'static [u8]),
+    Owned(Arc<[u8]>),
+}
+
+impl AsRef<[u8]> for Contents {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Contents::Static(bytes) => bytes,
+            Contents::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl From<Cow<'static, [u8]>> for Contents {
+    fn from(bytes: Cow<'static, [u8]>) -> Self {
+        match bytes {
+            Cow::Borrowed(bytes) => Contents::Static(bytes),
+            Cow::Owned(bytes) => Contents::Owned(bytes.into()),
+        }
+    }
+}
+
+impl MemoryServer {
+    /// Constructs a new `MemoryServer` that serves no files. Add files with
+    /// [`MemoryServer::file()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::MemoryServer;
+    ///
+    /// let server = MemoryServer::new();
+    /// ```
+    pub fn new() -> Self {
+        MemoryServer { files: Arc::new(HashMap::new()), rank: StaticFiles::DEFAULT_RANK }
+    }
+
+    /// Constructs a new `MemoryServer` that serves the files in `dir`, as
+    /// embedded by [`include_dir!`], and its subdirectories. Dotfiles are
+    /// skipped. This method is only available when the `serve_embedded`
+    /// feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # #[macro_use] extern crate rocket;
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::{MemoryServer, Dir, include_dir};
+    ///
+    /// static ASSETS: Dir<'_> = include_dir!("assets");
+    ///
+    /// #[launch]
+    /// fn rocket() -> rocket::Rocket {
+    ///     rocket::ignite().mount("/", MemoryServer::from_embedded(&ASSETS))
+    /// }
+    /// ```
+    #[cfg(feature = "serve_embedded")]
+    pub fn from_embedded(dir: &Dir<'static>) -> Self {
+        fn add(mut server: MemoryServer, dir: &Dir<'static>) -> MemoryServer {
+            for file in dir.files() {
+                let path: Option<Vec<_>> = file.path().iter().map(|c| c.to_str()).collect();
+                match path {
+                    Some(path) if !path.iter().any(|c| c.starts_with('.')) => {
+                        server = server.file(path.join("/"), file.contents());
+                    }
+                    Some(_) => continue,
+                    None => warn_!("Skipping embedded file with non-UTF-8 path {:?}.", file.path()),
+                }
+            }
+
+            dir.dirs().iter().fold(server, add)
+        }
+
+        add(MemoryServer::new(), dir)
+    }
+
+    /// Adds a file at `path`, relative to the mount point, with contents
+    /// `contents`, replacing any existing file at `path`.
+    ///
+    /// If `path` ends in `.gz`, `contents` is instead used as the gzip
+    /// precompressed variant of the file without the `.gz` extension. The
+    /// `.gz` file itself is not served directly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::MemoryServer;
+    ///
+    /// let server = MemoryServer::new()
+    ///     .file("/css/app.css", &b"body { color: red; }"[..])
+    ///     .file("css/app.css.gz", vec![/* gzip-compressed contents */]);
+    /// ```
+    pub fn file<P, C>(mut self, path: P, contents: C) -> Self
+        where P: AsRef<str>, C: Into<Cow<'static, [u8]>>
+    {
+        let path = path.as_ref().trim_start_matches('/');
+        let contents = Contents::from(contents.into());
+        let files = Arc::make_mut(&mut self.files);
+        match path.strip_suffix(".gz") {
+            Some(original) => {
+                let etag = ETag::strong(format!("{:016x}", fnv1a(contents.as_ref())));
+                let file = files.entry(original.into())
+                    .or_insert(MemoryFile { identity: None, gzip: None, etag });
+                file.gzip = Some(contents);
+            }
+            None => {
+                let etag = ETag::strong(format!("{:016x}", fnv1a(contents.as_ref())));
+                let file = files.entry(path.into())
+                    .or_insert_with(|| MemoryFile { identity: None, gzip: None, etag: etag.clone() });
+                file.identity = Some(contents);
+                file.etag = etag;
+            }
+        }
+
+        self
+    }
+
+    /// Sets the rank for generated routes to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::serve::MemoryServer;
+    ///
+    /// let server = MemoryServer::new().rank(3);
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Returns the file for the request path `path`, or the `index.html` file
+    /// if `path` is a directory, along with its path.
+    fn lookup(&self, path: &str) -> Option<(&str, &MemoryFile)> {
+        let index = match path.is_empty() {
+            true => "index.html".to_string(),
+            false => format!("{}/index.html", path),
+        };
+
+        [path, &index].iter()
+            .filter_map(|path| self.files.get_key_value(*path))
+            .find(|(_, file)| file.identity.is_some())
+            .map(|(path, file)| (path.as_str(), file))
+    }
+}
+
+impl Default for MemoryServer {
+    fn default() -> Self {
+        MemoryServer::new()
+    }
+}
+
+impl Into<Vec<Route>> for MemoryServer {
+    fn into(self) -> Vec<Route> {
+        let index = Route::ranked(self.rank, Method::Get, "/", self.clone());
+        let non_index = Route::ranked(self.rank, Method::Get, "/<path..>", self);
+        vec![index, non_index]
+    }
+}
+
+/// Returns `true` if the `Accept-Encoding` header of `req` accepts the content
+/// coding `encoding`, either by name or, if it isn't named, via `*`.
+fn accepts_encoding(req: &Request<'_>, encoding: &str) -> bool {
+    let (mut named, mut wildcard) = (None, None);
+    let codings = req.headers().get("Accept-Encoding").flat_map(|value| value.split(','));
+    for coding in codings {
+        let mut params = coding.split(';').map(|s| s.trim());
+        let name = params.next().unwrap_or("");
+        let accepted = !params.any(|p| p.starts_with("q=") && p[2..].parse() == Ok(0.0f32));
+        if name.eq_ignore_ascii_case(encoding) {
+            named = Some(accepted);
+        } else if name == "*" {
+            wildcard = Some(accepted);
+        }
+    }
+
+    named.or(wildcard).unwrap_or(false)
+}
+
+#[rocket::async_trait]
+impl Handler for MemoryServer {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        let path = match req.get_segments::<Segments<'_>>(0) {
+            Some(segments) => segments.ok().and_then(|s| s.into_path_buf(false).ok()),
+            None => Some(PathBuf::new()),
+        };
+
+        let path: Option<Vec<_>> = path.as_ref()
+            .and_then(|path| path.iter().map(|c| c.to_str()).collect());
+
+        let (path, file) = match path.and_then(|path| self.lookup(&path.join("/"))) {
+            Some(found) => found,
+            None => return Outcome::forward(data),
+        };
+
+        let mut response = Response::build();
+        if let Some(content_type) = path.rsplit('/').next()
+            .and_then(|name| name.rsplit('.').next().filter(|ext| *ext != name))
+            .and_then(ContentType::from_extension)
+        {
+            response.header(content_type);
         }
 
-        // Otherwise, we're handling segments. Get the segments as a `PathBuf`,
-        // only allowing dotfiles if the user allowed it.
-        let allow_dotfiles = self.options.contains(Options::DotFiles);
-        let path = req.get_segments::<Segments<'_>>(0)
-            .and_then(|res| res.ok())
-            .and_then(|segments| segments.into_path_buf(allow_dotfiles).ok())
-            .map(|path| self.root.join(path));
-
-        match path {
-            Some(p) if p.is_dir() => handle_dir(self.options, req, data, p).await,
-            Some(p) => Outcome::from_or_forward(req, data, NamedFile::open(p).await.ok()),
-            None => Outcome::forward(data),
+        if file.gzip.is_some() {
+            response.raw_header("Vary", "Accept-Encoding");
         }
+
+        // Each encoding is a distinct representation with a distinct tag.
+        let (body, etag) = match (&file.gzip, &file.identity) {
+            (Some(gzip), _) if accepts_encoding(req, "gzip") => {
+                response.raw_header("Content-Encoding", "gzip");
+                (gzip.clone(), ETag::strong(format!("{}-gzip", file.etag.tag())))
+            }
+            (_, Some(identity)) => (identity.clone(), file.etag.clone()),
+            (_, None) => return Outcome::forward(data),
+        };
+
+        // A malformed `If-None-Match` is ignored: the full file is served.
+        let not_modified = match req.guard::<IfNoneMatch>().await {
+            rocket::outcome::Outcome::Success(if_none_match) => !if_none_match.matches(Some(&etag)),
+            _ => false,
+        };
+
+        response.header(etag);
+        if not_modified {
+            return Outcome::from(req, response.status(Status::NotModified).finalize());
+        }
+
+        let len = body.as_ref().len();
+        Outcome::from(req, response.sized_body(len, Cursor::new(body)).finalize())
     }
 }
+
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::io::Write;
 
 use serde::Serialize;
 
@@ -12,6 +13,7 @@ pub(crate) trait Engine: Send + Sync + 'static {
 
     fn init(templates: &[(&str, &TemplateInfo)]) -> Option<Self> where Self: Sized;
     fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String>;
+    fn render_to<C: Serialize, W: Write>(&self, name: &str, context: C, writer: W) -> bool;
 }
 
 /// A structure exposing access to templating engines.
@@ -117,4 +119,28 @@ impl Engines {
 
         None
     }
+
+    pub(crate) fn render_to<C: Serialize, W: Write>(
+        &self,
+        name: &str,
+        info: &TemplateInfo,
+        context: C,
+        writer: W
+    ) -> bool {
+        #[cfg(feature = "tera_templates")]
+        {
+            if info.extension == Tera::EXT {
+                return Engine::render_to(&self.tera, name, context, writer);
+            }
+        }
+
+        #[cfg(feature = "handlebars_templates")]
+        {
+            if info.extension == Handlebars::EXT {
+                return Engine::render_to(&self.handlebars, name, context, writer);
+            }
+        }
+
+        false
+    }
 }
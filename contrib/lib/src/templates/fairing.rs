@@ -11,20 +11,20 @@ type Callback = Box<dyn Fn(&mut Engines) -> Result<(), Box<dyn Error>>+ Send + S
 
 #[cfg(not(debug_assertions))]
 mod context {
-    use std::ops::Deref;
+    use std::sync::Arc;
     use crate::templates::Context;
 
     /// Wraps a Context. With `cfg(debug_assertions)` active, this structure
     /// additionally provides a method to reload the context at runtime.
-    pub(crate) struct ContextManager(Context);
+    pub(crate) struct ContextManager(Arc<Context>);
 
     impl ContextManager {
         pub fn new(ctxt: Context) -> ContextManager {
-            ContextManager(ctxt)
+            ContextManager(Arc::new(ctxt))
         }
 
-        pub fn context<'a>(&'a self) -> impl Deref<Target=Context> + 'a {
-            &self.0
+        pub fn context(&self) -> Arc<Context> {
+            self.0.clone()
         }
 
         pub fn is_reloading(&self) -> bool {
@@ -35,8 +35,7 @@ mod context {
 
 #[cfg(debug_assertions)]
 mod context {
-    use std::ops::{Deref, DerefMut};
-    use std::sync::{RwLock, Mutex};
+    use std::sync::{Arc, RwLock, Mutex};
     use std::sync::mpsc::{channel, Receiver};
 
     use notify::{raw_watcher, RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
@@ -47,7 +46,9 @@ mod context {
     /// additionally provides a method to reload the context at runtime.
     pub(crate) struct ContextManager {
         /// The current template context, inside an RwLock so it can be updated.
-        context: RwLock<Context>,
+        /// Renders in progress keep their own reference to the context they
+        /// started with.
+        context: RwLock<Arc<Context>>,
         /// A filesystem watcher and the receive queue for its events.
         watcher: Option<Mutex<(RecommendedWatcher, Receiver<RawEvent>)>>,
     }
@@ -72,22 +73,18 @@ mod context {
 
             ContextManager {
                 watcher,
-                context: RwLock::new(ctxt),
+                context: RwLock::new(Arc::new(ctxt)),
             }
         }
 
-        pub fn context(&self) -> impl Deref<Target=Context> + '_ {
-            self.context.read().unwrap()
+        pub fn context(&self) -> Arc<Context> {
+            self.context.read().unwrap().clone()
         }
 
         pub fn is_reloading(&self) -> bool {
             self.watcher.is_some()
         }
 
-        fn context_mut(&self) -> impl DerefMut<Target=Context> + '_ {
-            self.context.write().unwrap()
-        }
-
         /// Checks whether any template files have changed on disk. If there
         /// have been changes since the last reload, all templates are
         /// reinitialized from disk and the user's customization callback is run
@@ -102,10 +99,10 @@ mod context {
 
                 if changed {
                     info_!("Change detected: reloading templates.");
-                    let mut ctxt = self.context_mut();
+                    let mut ctxt = self.context.write().unwrap();
                     if let Some(mut new_ctxt) = Context::initialize(&ctxt.root) {
                         match callback(&mut new_ctxt.engines) {
-                            Ok(()) => *ctxt = new_ctxt,
+                            Ok(()) => *ctxt = Arc::new(new_ctxt),
                             Err(e) => {
                                 warn_!("The template customization callback returned an error:");
                                 warn_!("{}", e);
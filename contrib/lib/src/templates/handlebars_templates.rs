@@ -1,4 +1,5 @@
 use serde::Serialize;
+use std::io::Write;
 
 use crate::templates::{Engine, TemplateInfo};
 
@@ -36,4 +37,19 @@ impl Engine for Handlebars<'static> {
             }
         }
     }
+
+    fn render_to<C: Serialize, W: Write>(&self, name: &str, context: C, writer: W) -> bool {
+        if self.get_template(name).is_none() {
+            error_!("Handlebars template '{}' does not exist.", name);
+            return false;
+        }
+
+        match Handlebars::render_to_write(self, name, &context, writer) {
+            Ok(()) => true,
+            Err(e) => {
+                error_!("Error rendering Handlebars template '{}': {}", name, e);
+                false
+            }
+        }
+    }
 }
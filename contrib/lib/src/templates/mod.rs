@@ -136,11 +136,16 @@ use serde_json::{Value, to_value};
 use std::borrow::Cow;
 use std::path::PathBuf;
 use std::error::Error;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use tokio::io::AsyncWriteExt;
 
 use rocket::Rocket;
-use rocket::request::Request;
+use rocket::catcher::{ErrorPageProvider, description};
+use rocket::request::{Request, Nonce};
 use rocket::fairing::Fairing;
-use rocket::response::{self, Content, Responder};
+use rocket::response::{self, Content, Responder, Response};
 use rocket::http::{ContentType, Status};
 
 const DEFAULT_TEMPLATE_DIR: &str = "templates";
@@ -192,6 +197,30 @@ const DEFAULT_TEMPLATE_DIR: &str = "templates";
 /// }
 /// ```
 ///
+/// # Streaming
+///
+/// By default, a template is rendered into a `String` before the response is
+/// sent. Large templates can instead be rendered directly into the response
+/// body, in chunks, as rendering proceeds, by calling [`Template::streamed()`].
+///
+/// # Fragments
+///
+/// [`Template::fragment()`] renders a partial template on its own, as is
+/// common for endpoints that return HTML fragments to be swapped into an
+/// existing page.
+///
+/// # CSP Nonces
+///
+/// When a template is rendered as a response, its context, if it serializes to
+/// an object without a `csp_nonce` key, is extended with a `csp_nonce` key
+/// whose value is the request's [`Nonce`]. Inline scripts and styles can then
+/// be marked with the nonce permitted by a Content Security Policy, such as
+/// the one set by `rocket_contrib`'s `helmet` `Csp` policy:
+///
+/// ```html
+/// <script nonce="{{ csp_nonce }}">/* ... */</script>
+/// ```
+///
 /// # Helpers, Filters, and Customization
 ///
 /// You may use the [`Template::custom()`] method to construct a fairing with
@@ -200,7 +229,9 @@ const DEFAULT_TEMPLATE_DIR: &str = "templates";
 #[derive(Debug)]
 pub struct Template {
     name: Cow<'static, str>,
-    value: Option<Value>
+    value: Option<Value>,
+    fragment: bool,
+    streamed: bool,
 }
 
 #[derive(Debug)]
@@ -334,7 +365,75 @@ impl Template {
     pub fn render<S, C>(name: S, context: C) -> Template
         where S: Into<Cow<'static, str>>, C: Serialize
     {
-        Template { name: name.into(), value: to_value(context).ok() }
+        Template {
+            name: name.into(),
+            value: to_value(context).ok(),
+            fragment: false,
+            streamed: false,
+        }
+    }
+
+    /// Render the partial template named `name` on its own with the context
+    /// `context`, typically to respond to a request for an HTML fragment.
+    ///
+    /// Partials are ordinary templates that are also included by other
+    /// templates, via `{{> name}}` in Handlebars or `{% include "name" %}` in
+    /// Tera. By convention, their file names often begin with an underscore.
+    /// If no template named `name` exists, the template with the same name
+    /// but with an `_` prepended to its final path component is used instead.
+    /// That is, `Template::fragment("users/row", ..)` renders the template
+    /// named `users/row` or, if there is none, `users/_row`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # #[macro_use] extern crate rocket_contrib;
+    /// use rocket_contrib::templates::Template;
+    ///
+    /// // `templates/users/list.html.hbs` includes `{{> users/_row}}` for each
+    /// // user, while this endpoint returns a single row.
+    /// #[get("/users/<id>/row")]
+    /// fn user_row(id: usize) -> Template {
+    ///     # let context = ();
+    ///     Template::fragment("users/row", &context)
+    /// }
+    /// ```
+    #[inline]
+    pub fn fragment<S, C>(name: S, context: C) -> Template
+        where S: Into<Cow<'static, str>>, C: Serialize
+    {
+        Template { fragment: true, ..Template::render(name, context) }
+    }
+
+    /// Render the template into the response body as rendering proceeds
+    /// instead of rendering it into a `String` first.
+    ///
+    /// The template is rendered on a blocking thread while the response is
+    /// being sent, so the first bytes reach the client before the rest have
+    /// been rendered and the complete output is never held in memory. Because
+    /// the response's status and headers are sent before rendering completes,
+    /// an error that occurs while rendering cannot result in an error
+    /// response. Instead, the error is logged and the body is truncated. A
+    /// template that does not exist, however, still results in a `500`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # #[macro_use] extern crate rocket_contrib;
+    /// use rocket_contrib::templates::Template;
+    ///
+    /// #[get("/report")]
+    /// fn report() -> Template {
+    ///     # let rows: Vec<String> = vec![];
+    ///     Template::render("report", &rows).streamed()
+    /// }
+    /// ```
+    #[inline]
+    pub fn streamed(mut self) -> Self {
+        self.streamed = true;
+        self
     }
 
     /// Render the template named `name` with the context `context` into a
@@ -386,39 +485,161 @@ impl Template {
         Template::render(name, context).finalize(&ctxt).ok().map(|v| v.0)
     }
 
+    /// Returns an [`ErrorPageProvider`] that renders the HTML pages of
+    /// Rocket's built-in default catcher from templates, without registering
+    /// a catcher per status code.
+    ///
+    /// For an error with status code `code`, the template named
+    /// `errors/{code}`, such as `errors/404` for `errors/404.html.tera`, is
+    /// rendered if it exists. Otherwise, the template named `errors/default`
+    /// is rendered if it exists. Otherwise, or if rendering fails, Rocket's
+    /// built-in page is used. Templates are rendered with the following
+    /// context:
+    ///
+    ///   * `code`: the status code, such as `404`
+    ///   * `reason`: the status reason, such as `"Not Found"`
+    ///   * `description`: a description of the error
+    ///   * `csp_nonce`: the request's CSP nonce
+    ///
+    /// The template fairing must also be attached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::templates::Template;
+    ///
+    /// let rocket = rocket::ignite()
+    ///     .attach(Template::fairing())
+    ///     .error_pages(Template::error_pages());
+    /// ```
+    pub fn error_pages() -> impl ErrorPageProvider {
+        |status: Status, req: &Request<'_>| {
+            let ctxt = req.managed_state::<ContextManager>()?.context();
+            let name = [format!("errors/{}", status.code), "errors/default".into()].iter()
+                .find(|name| ctxt.templates.contains_key(*name))?
+                .clone();
+
+            let context = serde_json::json!({
+                "code": status.code,
+                "reason": status.reason,
+                "description": description(status),
+                "csp_nonce": Nonce::of(req).as_str(),
+            });
+
+            Template::render(name, context).finalize(&ctxt).ok().map(|(html, _)| html)
+        }
+    }
+
     /// Actually render this template given a template context. This method is
     /// called by the `Template` `Responder` implementation as well as
     /// `Template::show()`.
     #[inline(always)]
     fn finalize(self, ctxt: &Context) -> Result<(String, ContentType), Status> {
+        let (name, info) = self.lookup(ctxt)?;
+        let value = self.value.ok_or_else(|| {
+            error_!("The provided template context failed to serialize.");
+            Status::InternalServerError
+        })?;
+
+        let string = ctxt.engines.render(name, &info, value).ok_or_else(|| {
+            error_!("Template '{}' failed to render.", name);
+            Status::InternalServerError
+        })?;
+
+        Ok((string, info.data_type.clone()))
+    }
+
+    /// Returns the name and information of the template to render, resolving
+    /// fragment names as described in [`Template::fragment()`].
+    fn lookup<'c>(&self, ctxt: &'c Context) -> Result<(&'c str, &'c TemplateInfo), Status> {
         let name = &*self.name;
-        let info = ctxt.templates.get(name).ok_or_else(|| {
+        let partial = match self.fragment {
+            true => Some(match name.rfind('/') {
+                Some(i) => format!("{}/_{}", &name[..i], &name[i + 1..]),
+                None => format!("_{}", name),
+            }),
+            false => None,
+        };
+
+        let found = ctxt.templates.get_key_value(name)
+            .or_else(|| partial.and_then(|p| ctxt.templates.get_key_value(&p)));
+
+        found.map(|(name, info)| (name.as_str(), info)).ok_or_else(|| {
             let ts: Vec<_> = ctxt.templates.keys().map(|s| s.as_str()).collect();
             error_!("Template '{}' does not exist.", name);
             info_!("Known templates: {}", ts.join(","));
             info_!("Searched in '{:?}'.", ctxt.root);
             Status::InternalServerError
-        })?;
+        })
+    }
 
+    /// Returns a response whose body is rendered on a blocking thread as it
+    /// is read. See [`Template::streamed()`].
+    fn stream(self, ctxt: Arc<Context>) -> response::Result<'static> {
+        let (name, info) = self.lookup(&ctxt)?;
+        let (name, content_type) = (name.to_string(), info.data_type.clone());
         let value = self.value.ok_or_else(|| {
             error_!("The provided template context failed to serialize.");
             Status::InternalServerError
         })?;
 
-        let string = ctxt.engines.render(name, &info, value).ok_or_else(|| {
-            error_!("Template '{}' failed to render.", name);
-            Status::InternalServerError
-        })?;
+        let (reader, writer) = tokio::io::duplex(STREAM_BUFFER_SIZE);
+        let handle = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || {
+            let info = &ctxt.templates[&name];
+            let writer = BlockingWriter { handle, writer };
+            let mut writer = std::io::BufWriter::with_capacity(STREAM_BUFFER_SIZE, writer);
+            if !ctxt.engines.render_to(&name, info, value, &mut writer) {
+                error_!("Streamed template '{}' failed to render.", name);
+                warn_!("The response body was truncated.");
+            } else if let Err(e) = writer.flush() {
+                warn_!("Streamed template '{}' was not sent completely: {}", name, e);
+            }
+        });
 
-        Ok((string, info.data_type.clone()))
+        Response::build()
+            .header(content_type)
+            .streamed_body(reader)
+            .ok()
+    }
+}
+
+/// The capacity, in bytes, of the buffers between a streamed template's
+/// renderer and the response body.
+const STREAM_BUFFER_SIZE: usize = 4096;
+
+/// A synchronous `Write` into the write half of a streamed template's pipe,
+/// for use by template engines on a blocking thread.
+struct BlockingWriter {
+    handle: tokio::runtime::Handle,
+    writer: tokio::io::DuplexStream,
+}
+
+impl Write for BlockingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let writer = &mut self.writer;
+        self.handle.block_on(writer.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let writer = &mut self.writer;
+        self.handle.block_on(writer.flush())
     }
 }
 
 /// Returns a response with the Content-Type derived from the template's
-/// extension and a fixed-size body containing the rendered template. If
+/// extension and a fixed-size body containing the rendered template, or a
+/// streamed body if the template is [streamed](Template::streamed()). The
+/// request's CSP nonce is available to the template as `csp_nonce`. If
 /// rendering fails, an `Err` of `Status::InternalServerError` is returned.
 impl<'r> Responder<'r, 'static> for Template {
-    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        if let Some(Value::Object(map)) = &mut self.value {
+            if !map.contains_key("csp_nonce") {
+                map.insert("csp_nonce".into(), Nonce::of(req).as_str().into());
+            }
+        }
+
         let (render, content_type) = {
             let ctxt = req.managed_state::<ContextManager>().ok_or_else(|| {
                 error_!("Uninitialized template context: missing fairing.");
@@ -427,6 +648,10 @@ impl<'r> Responder<'r, 'static> for Template {
                 Status::InternalServerError
             })?.context();
 
+            if self.streamed {
+                return self.stream(ctxt);
+            }
+
             self.finalize(&ctxt)?
         };
 
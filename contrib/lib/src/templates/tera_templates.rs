@@ -1,5 +1,6 @@
 use serde::Serialize;
 use std::error::Error;
+use std::io::Write;
 
 use crate::templates::{Engine, TemplateInfo};
 
@@ -36,9 +37,24 @@ impl Engine for Tera {
     }
 
     fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String> {
+        let mut buffer = vec![];
+        if !Engine::render_to(self, name, context, &mut buffer) {
+            return None;
+        }
+
+        match String::from_utf8(buffer) {
+            Ok(string) => Some(string),
+            Err(_) => {
+                error_!("Tera template '{}' rendered invalid UTF-8.", name);
+                None
+            }
+        }
+    }
+
+    fn render_to<C: Serialize, W: Write>(&self, name: &str, context: C, writer: W) -> bool {
         if self.get_template(name).is_err() {
             error_!("Tera template '{}' does not exist.", name);
-            return None;
+            return false;
         };
 
         let tera_ctx = match Context::from_serialize(context) {
@@ -48,12 +64,12 @@ impl Engine for Tera {
                     "Error generating context when rendering Tera template '{}'.",
                     name
                 );
-                return None;
+                return false;
             }
         };
 
-        match Tera::render(self, name, &tera_ctx) {
-            Ok(string) => Some(string),
+        match Tera::render_to(self, name, &tera_ctx, writer) {
+            Ok(()) => true,
             Err(e) => {
                 error_!("Error rendering Tera template '{}'.", name);
 
@@ -63,7 +79,7 @@ impl Engine for Tera {
                     error = err.source();
                 }
 
-                None
+                false
             }
         }
     }
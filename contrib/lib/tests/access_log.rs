@@ -0,0 +1,149 @@
+#[macro_use]
+#[cfg(feature = "access_log")]
+extern crate rocket;
+
+#[cfg(feature = "access_log")]
+mod access_log_tests {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use rocket::figment::Figment;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+    use rocket_contrib::access_log::{AccessLog, LogFormat, RequestId};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<String> {
+            let bytes = self.0.lock().unwrap();
+            String::from_utf8_lossy(&bytes).lines().map(|s| s.to_string()).collect()
+        }
+
+        /// Waits for the writer thread to write `n` lines.
+        fn wait_for(&self, n: usize) -> Vec<String> {
+            for _ in 0..500 {
+                let lines = self.lines();
+                if lines.len() >= n {
+                    return lines;
+                }
+
+                std::thread::sleep(Duration::from_millis(10));
+            }
+
+            self.lines()
+        }
+    }
+
+    #[get("/")]
+    fn index(id: &RequestId) -> String {
+        id.to_string()
+    }
+
+    fn client(format: LogFormat, buffer: &Buffer) -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![index])
+            .attach(AccessLog::new(format).to_writer(buffer.clone()));
+
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn common_format() {
+        let buffer = Buffer::default();
+        let client = client(LogFormat::Common, &buffer);
+        client.get("/").header(Header::new("X-Request-Id", "abc123")).dispatch();
+        client.get("/missing").dispatch();
+
+        let lines = buffer.wait_for(2);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"GET /\" 200 6 abc123"), "{}", lines[0]);
+        assert!(lines[0].ends_with(" index"), "{}", lines[0]);
+        assert!(lines[1].contains("\"GET /missing\" 404 "), "{}", lines[1]);
+        assert!(lines[1].ends_with(" -"), "{}", lines[1]);
+    }
+
+    #[test]
+    fn combined_format() {
+        let buffer = Buffer::default();
+        let client = client(LogFormat::Combined, &buffer);
+        client.get("/")
+            .header(Header::new("User-Agent", "test-agent/1.0"))
+            .header(Header::new("Referer", "http://example.com"))
+            .dispatch();
+
+        let lines = buffer.wait_for(1);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("\"http://example.com\" \"test-agent/1.0\""), "{}", lines[0]);
+    }
+
+    #[test]
+    fn json_format() {
+        let buffer = Buffer::default();
+        let client = client(LogFormat::Json, &buffer);
+        let response = client.get("/").dispatch();
+        let id = response.into_string().unwrap();
+
+        let lines = buffer.wait_for(1);
+        assert_eq!(lines.len(), 1);
+
+        let value: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(value["status"], 200);
+        assert_eq!(value["method"], "GET");
+        assert_eq!(value["uri"], "/");
+        assert_eq!(value["route"], "index");
+        assert_eq!(value["request_id"], id.as_str());
+        assert!(value["latency_ms"].is_number());
+    }
+
+    #[test]
+    fn configured_file_rotates() {
+        let dir = std::env::temp_dir().join(format!("rocket-access-log-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("access.log");
+
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("access_log.format", "common"))
+            .merge(("access_log.path", path.to_str().unwrap()))
+            .merge(("access_log.max_size", 1));
+
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![index])
+            .attach(AccessLog::fairing());
+
+        let client = Client::tracked(rocket).unwrap();
+        client.get("/").dispatch();
+        client.get("/missing").dispatch();
+
+        let mut files = vec![];
+        for _ in 0..500 {
+            files = std::fs::read_dir(&dir).unwrap()
+                .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+                .collect();
+
+            if files.len() == 2 && files.iter().all(|f| f.ends_with('\n')) {
+                break;
+            }
+
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files.len(), 2, "{:?}", files);
+        assert!(files.iter().any(|f| f.contains("\"GET /\" 200 ")), "{:?}", files);
+        assert!(files.iter().any(|f| f.contains("\"GET /missing\" 404 ")), "{:?}", files);
+    }
+}
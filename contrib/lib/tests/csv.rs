@@ -0,0 +1,67 @@
+#[macro_use]
+#[cfg(feature = "csv")]
+extern crate rocket;
+
+#[cfg(feature = "csv")]
+mod csv_tests {
+    use std::collections::BTreeMap;
+
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket_contrib::csv::Csv;
+    use serde::{Serialize, Serializer, ser::SerializeStruct};
+
+    struct Square(usize);
+
+    impl Serialize for Square {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut record = serializer.serialize_struct("Square", 2)?;
+            record.serialize_field("n", &self.0)?;
+            record.serialize_field("square", &(self.0 * self.0))?;
+            record.end()
+        }
+    }
+
+    #[get("/squares?<n>&<headers>")]
+    fn squares(n: usize, headers: bool) -> Csv<impl Iterator<Item = Square>> {
+        Csv::new((1..=n).map(Square)).headers(headers).delimiter(b';')
+    }
+
+    #[post("/sum", data = "<rows>")]
+    fn sum(rows: Csv<Vec<BTreeMap<String, usize>>>) -> String {
+        rows.iter().map(|row| row["amount"]).sum::<usize>().to_string()
+    }
+
+    fn client() -> Client {
+        Client::tracked(rocket::ignite().mount("/", routes![squares, sum])).unwrap()
+    }
+
+    #[test]
+    fn responds_with_csv() {
+        let client = client();
+        let response = client.get("/squares?n=3&headers=true").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::CSV));
+        assert_eq!(response.into_string().unwrap(), "n;square\n1;1\n2;4\n3;9\n");
+
+        let response = client.get("/squares?n=2&headers=false").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1;1\n2;4\n");
+
+        let response = client.get("/squares?n=0&headers=true").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+
+    #[test]
+    fn parses_csv() {
+        let client = client();
+        let post = |body: &'static str| {
+            let response = client.post("/sum").header(ContentType::CSV).body(body).dispatch();
+            (response.status(), response.into_string())
+        };
+
+        assert_eq!(post("id,amount\n1,10\n2,32\n"), (Status::Ok, Some("42".into())));
+        assert_eq!(post("id,amount\n"), (Status::Ok, Some("0".into())));
+        assert_eq!(post("id,amount\n1,ten\n").0, Status::UnprocessableEntity);
+        assert_eq!(post("id,amount\n1,10,extra\n").0, Status::BadRequest);
+    }
+}
@@ -50,6 +50,173 @@ mod rusqlite_integration_test {
 
             tx.commit().expect("committed transaction");
         }).await;
+
+        let stats = SqliteDb::pool_stats(&rocket).expect("pool stats");
+        assert!(stats.healthy);
+        assert_eq!(stats.retrieved, 1);
+        assert_eq!(stats.max_size, rocket.config().workers as u32 * 2);
+        assert!(stats.connections >= 1 && stats.idle < stats.connections);
+
+        let stats = SqliteDb2::pool_stats(&rocket).expect("pool stats");
+        assert_eq!(stats.retrieved, 0);
+    }
+}
+
+#[cfg(all(feature = "databases", feature = "sqlite_pool"))]
+#[cfg(test)]
+mod transaction_test {
+    use rocket::{get, post, routes};
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+    use rocket_contrib::database;
+    use rocket_contrib::databases::{rusqlite, Transaction};
+
+    #[database("tx_db")]
+    struct TxDb(rusqlite::Connection);
+
+    #[post("/<value>/<status>")]
+    async fn insert(
+        tx: Transaction<TxDb>,
+        other: Transaction<TxDb>,
+        value: i64,
+        status: u16
+    ) -> Status {
+        tx.run(move |c| c.execute("INSERT INTO items VALUES (?1)", rusqlite::params![value]))
+            .await
+            .expect("inserted");
+
+        // Both guards refer to the same transaction.
+        other.run(move |c| c.execute("INSERT INTO items VALUES (?1)", rusqlite::params![-value]))
+            .await
+            .expect("inserted");
+
+        Status::from_code(status).unwrap()
+    }
+
+    #[get("/")]
+    async fn items(db: TxDb) -> String {
+        db.run(|c| {
+            let mut stmt = c.prepare("SELECT value FROM items ORDER BY value")?;
+            let values = stmt.query_map(rusqlite::params![], |row| row.get::<_, i64>(0))?;
+            values.map(|v| v.map(|v| v.to_string())).collect::<Result<Vec<_>, _>>()
+        }).await.expect("selected").join(",")
+    }
+
+    #[rocket::async_test]
+    async fn test_commit_and_rollback() {
+        use rocket::figment::{Figment, util::map};
+
+        // A single connection so that every request sees the same database.
+        let config = Figment::from(rocket::Config::default())
+            .merge(("databases", map!["tx_db" => map!["url" => ":memory:"]]))
+            .merge(("databases.tx_db.pool_size", 1));
+
+        let rocket = rocket::custom(config)
+            .attach(TxDb::fairing())
+            .mount("/", routes![insert, items]);
+
+        let conn = TxDb::get_one(&rocket).await.expect("connection");
+        conn.run(|c| c.execute_batch("CREATE TABLE items (value INTEGER)")).await.unwrap();
+        drop(conn);
+
+        let client = Client::tracked(rocket).await.unwrap();
+        let response = client.post("/1/200").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "-1,1");
+
+        let response = client.post("/2/304").dispatch().await;
+        assert_eq!(response.status(), Status::NotModified);
+        assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "-2,-1,1,2");
+
+        let response = client.post("/3/400").dispatch().await;
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "-2,-1,1,2");
+
+        let response = client.post("/4/500").dispatch().await;
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(client.get("/").dispatch().await.into_string().await.unwrap(), "-2,-1,1,2");
+    }
+}
+
+#[cfg(all(feature = "databases", feature = "sqlite_pool"))]
+#[cfg(test)]
+mod migrations_test {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use rocket::error::ErrorKind::FailedFairings;
+    use rocket::figment::Figment;
+    use rocket::local::asynchronous::Client;
+    use rocket_contrib::database;
+    use rocket_contrib::databases::{rusqlite, Migrations};
+
+    #[database("migrated_db")]
+    struct MigratedDb(rusqlite::Connection);
+
+    fn setup(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rocket-migrations-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("migrations")).unwrap();
+        dir
+    }
+
+    fn rocket(dir: &Path) -> rocket::Rocket {
+        let config = Figment::from(rocket::Config::default())
+            .merge(("databases.migrated_db.url", dir.join("db.sqlite")))
+            .merge(("databases.migrated_db.pool_size", 1));
+
+        rocket::custom(config)
+            .attach(MigratedDb::fairing())
+            .attach(Migrations::<MigratedDb>::fairing(dir.join("migrations")))
+    }
+
+    async fn names(client: &Client) -> Vec<String> {
+        let conn = MigratedDb::get_one(client.rocket()).await.unwrap();
+        conn.run(|c| {
+            let mut stmt = c.prepare("SELECT name FROM users ORDER BY name").unwrap();
+            let rows = stmt.query_map(rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+            rows.collect::<Result<Vec<String>, _>>().unwrap()
+        }).await
+    }
+
+    #[rocket::async_test]
+    async fn test_migrations_applied_once() {
+        let dir = setup("once");
+        let migrations = dir.join("migrations");
+        fs::write(migrations.join("0001_users.sql"), "CREATE TABLE users (name TEXT);").unwrap();
+        fs::write(migrations.join("0002_bob.sql"), "INSERT INTO users VALUES ('bob');").unwrap();
+        fs::write(migrations.join("README.md"), "Not a migration.").unwrap();
+
+        let client = Client::untracked(rocket(&dir)).await.unwrap();
+        assert_eq!(names(&client).await, vec!["bob"]);
+        drop(client);
+
+        // Only the new migration is applied on the next ignition.
+        fs::write(migrations.join("0003_alice.sql"), "INSERT INTO users VALUES ('alice');").unwrap();
+        let client = Client::untracked(rocket(&dir)).await.unwrap();
+        assert_eq!(names(&client).await, vec!["alice", "bob"]);
+    }
+
+    #[rocket::async_test]
+    async fn test_failed_migration_rolled_back() {
+        let dir = setup("failed");
+        let migrations = dir.join("migrations");
+        fs::write(migrations.join("0001_users.sql"), "CREATE TABLE users (name TEXT);").unwrap();
+        fs::write(migrations.join("0002_bad.sql"), "INSERT INTO users VALUES ('eve'); NOPE;")
+            .unwrap();
+
+        match Client::untracked(rocket(&dir)).await {
+            Err(e) => match e.kind() {
+                FailedFairings(failures) => assert_eq!(failures[0], "Database Migrations"),
+                _ => panic!("wrong kind of launch error"),
+            },
+            Ok(_) => panic!("ignition should fail"),
+        }
+
+        // The first migration was applied; the second was rolled back.
+        fs::remove_file(migrations.join("0002_bad.sql")).unwrap();
+        let client = Client::untracked(rocket(&dir)).await.unwrap();
+        assert!(names(&client).await.is_empty());
     }
 }
 
@@ -0,0 +1,106 @@
+#[macro_use]
+#[cfg(feature = "geoip")]
+extern crate rocket;
+
+#[cfg(feature = "geoip")]
+mod geoip_tests {
+    use std::net::{IpAddr, SocketAddr};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use rocket::Config;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::geoip::{GeoIp, GeoInfo, GeoResolver};
+
+    struct Fake(Arc<AtomicUsize>);
+
+    #[rocket::async_trait]
+    impl GeoResolver for Fake {
+        async fn resolve(&self, ip: IpAddr) -> Option<GeoInfo> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            match ip.to_string().as_str() {
+                "81.2.69.142" => Some(GeoInfo {
+                    continent: Some("EU".into()),
+                    country: Some("GB".into()),
+                    city: Some("London".into()),
+                    ..Default::default()
+                }),
+                _ => None
+            }
+        }
+    }
+
+    #[get("/", rank = 1)]
+    fn located(geo: &GeoInfo) -> String {
+        format!("{}/{}", geo.country.as_deref().unwrap(), geo.city.as_deref().unwrap())
+    }
+
+    #[get("/", rank = 2)]
+    fn unknown() -> &'static str { "unknown" }
+
+    #[get("/twice")]
+    fn twice(a: &GeoInfo, b: &GeoInfo) -> String {
+        assert_eq!(a, b);
+        a.in_country("gb").to_string()
+    }
+
+    fn addr(ip: &str) -> SocketAddr {
+        format!("{}:9999", ip).parse().unwrap()
+    }
+
+    fn client(lookups: Arc<AtomicUsize>) -> Client {
+        let figment = Config::figment().merge(("trusted_proxies", ["10.0.0.1"]));
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![located, unknown, twice])
+            .attach(GeoIp::fairing(Fake(lookups)));
+
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn resolves_client_address() {
+        let client = client(Arc::new(AtomicUsize::new(0)));
+        let response = client.get("/").remote(addr("81.2.69.142")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "GB/London");
+
+        let response = client.get("/").remote(addr("192.0.2.1")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "unknown");
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "unknown");
+
+        let response = client.get("/")
+            .remote(addr("10.0.0.1"))
+            .header(Header::new("X-Real-IP", "81.2.69.142"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "GB/London");
+    }
+
+    #[test]
+    fn lookups_are_cached() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let client = client(lookups.clone());
+
+        let response = client.get("/twice").remote(addr("81.2.69.142")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "true");
+        assert_eq!(lookups.load(Ordering::SeqCst), 1);
+
+        for _ in 0..3 {
+            client.get("/").remote(addr("81.2.69.142")).dispatch();
+            client.get("/").remote(addr("192.0.2.1")).dispatch();
+        }
+
+        assert_eq!(lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn guard_requires_fairing() {
+        let rocket = rocket::ignite().mount("/", routes![twice]);
+        let client = Client::tracked(rocket).unwrap();
+        let response = client.get("/twice").remote(addr("81.2.69.142")).dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+    }
+}
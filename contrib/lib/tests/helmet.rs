@@ -34,7 +34,7 @@ mod helmet_tests {
             let rocket = rocket::ignite().mount("/", routes![hello]).attach($helmet);
             let client = Client::tracked(rocket).unwrap();
             let response = client.get("/").dispatch();
-            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.status(), Status::NoContent);
             $closure(response)
         }}
     }
@@ -146,4 +146,88 @@ mod helmet_tests {
             assert_header!(response, "X-DNS-Prefetch-Control", "on");
         });
     }
+
+    fn redirect_client(redirect: HttpsRedirect) -> Client {
+        let figment = rocket::Config::figment().merge(("trusted_proxies", ["10.0.0.1"]));
+        let rocket = rocket::custom(figment).mount("/", routes![hello]).attach(redirect);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn https_redirect_test() {
+        use rocket::http::Header;
+
+        let client = redirect_client(HttpsRedirect::new());
+        let response = client.post("/a/b?c=d").header(Header::new("Host", "rocket.rs")).dispatch();
+        assert_eq!(response.status(), Status::MovedPermanently);
+        assert_header!(response, "Location", "https://rocket.rs/a/b?c=d");
+        assert_no_header!(response, "Strict-Transport-Security");
+
+        // Forwarded headers from an untrusted peer don't make a request secure.
+        let response = client.get("/")
+            .remote("10.0.0.2:5000".parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::MovedPermanently);
+        assert_header!(response, "Location", "https://127.0.0.1/");
+
+        let response = client.get("/")
+            .remote("10.0.0.1:5000".parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NoContent);
+        assert_header!(response, "Strict-Transport-Security",
+            format!("max-age={}", Duration::weeks(52).whole_seconds()));
+
+        let client = redirect_client(HttpsRedirect::new().port(8443).without_hsts());
+        let response = client.get("/").header(Header::new("Host", "rocket.rs:8000")).dispatch();
+        assert_header!(response, "Location", "https://rocket.rs:8443/");
+
+        let response = client.get("/")
+            .remote("10.0.0.1:5000".parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NoContent);
+        assert_no_header!(response, "Strict-Transport-Security");
+    }
+
+    #[test]
+    fn hsts_preload_test() {
+        let helmet = SpaceHelmet::default().enable(Hsts::Preload(Duration::weeks(104)));
+        dispatch!(helmet, |response: LocalResponse<'_>| {
+            assert_header!(response, "Strict-Transport-Security",
+                format!("max-age={}; includeSubDomains; preload",
+                    Duration::weeks(104).whole_seconds()));
+        });
+    }
+
+    #[get("/nonce")]
+    fn nonce(nonce: rocket::request::Nonce<'_>) -> String {
+        nonce.to_string()
+    }
+
+    #[test]
+    fn csp_nonce_test() {
+        let helmet = SpaceHelmet::default().enable(Csp::default());
+        let rocket = rocket::ignite().mount("/", routes![nonce]).attach(helmet);
+        let client = Client::tracked(rocket).unwrap();
+
+        let response = client.get("/nonce").dispatch();
+        let csp = response.headers().get_one("Content-Security-Policy").unwrap().to_string();
+        let nonce = response.into_string().unwrap();
+        assert_eq!(csp, format!("default-src 'self'; script-src 'self' 'nonce-{0}'; \
+            style-src 'self' 'nonce-{0}'; object-src 'none'; base-uri 'self'", nonce));
+
+        // Each request has its own nonce.
+        let response = client.get("/nonce").dispatch();
+        assert_ne!(response.into_string().unwrap(), nonce);
+
+        let csp = Csp::new().directive("script-src", "'self'").nonce(false);
+        dispatch!(SpaceHelmet::new().enable(csp), |response: LocalResponse<'_>| {
+            assert_header!(response, "Content-Security-Policy", "script-src 'self'");
+        });
+    }
 }
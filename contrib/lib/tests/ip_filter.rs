@@ -0,0 +1,127 @@
+#[macro_use]
+#[cfg(feature = "ip_filter")]
+extern crate rocket;
+
+#[cfg(feature = "ip_filter")]
+mod ip_filter_tests {
+    use std::net::SocketAddr;
+
+    use rocket::{Config, State};
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::ip_filter::{IpFilter, Cidr};
+
+    #[get("/")] fn index() -> &'static str { "index" }
+    #[get("/")] fn admin() -> &'static str { "admin" }
+
+    #[post("/block/<net>")]
+    fn block(filter: State<'_, IpFilter>, net: String) -> Option<()> {
+        filter.block(net.parse().ok()?);
+        Some(())
+    }
+
+    #[post("/unblock/<net>")]
+    fn unblock(filter: State<'_, IpFilter>, net: String) -> Option<String> {
+        Some(filter.unblock(net.parse().ok()?).to_string())
+    }
+
+    fn addr(ip: &str) -> SocketAddr {
+        format!("{}:9999", ip).parse().unwrap()
+    }
+
+    fn client(filter: IpFilter) -> Client {
+        let figment = Config::figment().merge(("trusted_proxies", ["10.0.0.1"]));
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![index, block, unblock])
+            .mount("/admin", routes![admin])
+            .attach(filter);
+
+        Client::tracked(rocket).unwrap()
+    }
+
+    macro_rules! status {
+        ($client:expr, $path:expr, $ip:expr) => {
+            $client.get($path).remote(addr($ip)).dispatch().status()
+        };
+    }
+
+    #[test]
+    fn cidr_parsing() {
+        let net: Cidr = "192.168.1.77/24".parse().unwrap();
+        assert_eq!(net.to_string(), "192.168.1.0/24");
+        assert!(net.contains("192.168.1.1".parse().unwrap()));
+        assert!(net.contains("::ffff:192.168.1.1".parse().unwrap()));
+        assert!(!net.contains("192.168.2.1".parse().unwrap()));
+
+        let any: Cidr = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains("8.8.8.8".parse().unwrap()));
+        assert!(!any.contains("::1".parse().unwrap()));
+
+        let v6: Cidr = "2001:db8::/32".parse().unwrap();
+        assert!(v6.contains("2001:db8:1::1".parse().unwrap()));
+        assert!(!v6.contains("2001:db9::1".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("10.0.0/8".parse::<Cidr>().is_err());
+        assert!("::/129".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn global_and_mount_rules() {
+        let filter = IpFilter::new()
+            .deny("203.0.113.0/24".parse().unwrap())
+            .allow_at("/admin", "192.168.0.0/16".parse().unwrap());
+
+        let client = client(filter);
+        assert_eq!(status!(client, "/", "8.8.8.8"), Status::Ok);
+        assert_eq!(status!(client, "/", "203.0.113.9"), Status::Forbidden);
+        assert_eq!(status!(client, "/admin", "192.168.4.2"), Status::Ok);
+        assert_eq!(status!(client, "/admin", "8.8.8.8"), Status::Forbidden);
+        assert_eq!(status!(client, "/admin", "203.0.113.9"), Status::Forbidden);
+
+        // Empty segments are skipped by the router and so by the filter, too.
+        assert_eq!(status!(client, "//admin", "8.8.8.8"), Status::Forbidden);
+        assert_eq!(status!(client, "/admin//x", "8.8.8.8"), Status::Forbidden);
+        assert_eq!(status!(client, "//admin", "192.168.4.2"), Status::Ok);
+
+        // Without a known client IP, only allow-listed mounts reject.
+        assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+        assert_eq!(client.get("/admin").dispatch().status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn real_ip_is_only_trusted_from_proxies() {
+        let filter = IpFilter::new().deny("203.0.113.9".parse().unwrap());
+        let client = client(filter);
+
+        let spoofed = client.get("/")
+            .remote(addr("203.0.113.9"))
+            .header(Header::new("X-Real-IP", "8.8.8.8"))
+            .dispatch();
+
+        assert_eq!(spoofed.status(), Status::Forbidden);
+
+        let proxied = client.get("/")
+            .remote(addr("10.0.0.1"))
+            .header(Header::new("X-Real-IP", "203.0.113.9"))
+            .dispatch();
+
+        assert_eq!(proxied.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn runtime_block_list_updates() {
+        let client = client(IpFilter::new());
+        assert_eq!(status!(client, "/", "198.51.100.3"), Status::Ok);
+
+        let response = client.post("/block/198.51.100.0%2F24").remote(addr("8.8.8.8")).dispatch();
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(status!(client, "/", "198.51.100.3"), Status::Forbidden);
+        assert_eq!(status!(client, "/", "8.8.8.8"), Status::Ok);
+
+        let response = client.post("/unblock/198.51.100.0%2F24").remote(addr("8.8.8.8")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "true");
+        assert_eq!(status!(client, "/", "198.51.100.3"), Status::Ok);
+    }
+}
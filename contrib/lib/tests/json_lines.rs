@@ -0,0 +1,60 @@
+#[macro_use]
+#[cfg(feature = "json")]
+extern crate rocket;
+
+#[cfg(feature = "json")]
+mod json_lines_tests {
+    use rocket::futures::stream::{self, Stream, StreamExt};
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket_contrib::json::{JsonLines, JsonLinesStream, Value};
+    use serde_json::json;
+
+    #[get("/events?<n>")]
+    fn events(n: usize) -> JsonLines<impl Stream<Item = Value>> {
+        JsonLines(stream::iter(0..n).map(|id| json!({ "id": id, "kind": "tick" })))
+    }
+
+    #[post("/import", data = "<events>")]
+    async fn import(mut events: JsonLines<JsonLinesStream<(usize, String)>>) -> String {
+        let mut lines = vec![];
+        while let Some(event) = events.0.next().await {
+            lines.push(match event {
+                Ok((id, kind)) => format!("{}:{}", id, kind),
+                Err(e) => format!("error: {:?}", e.classify()),
+            });
+        }
+
+        lines.join(",")
+    }
+
+    fn client() -> Client {
+        Client::tracked(rocket::ignite().mount("/", routes![events, import])).unwrap()
+    }
+
+    #[test]
+    fn streams_json_lines() {
+        let client = client();
+        let response = client.get("/events?n=3").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::new("application", "x-ndjson")));
+        assert_eq!(response.into_string().unwrap(), "\
+            {\"id\":0,\"kind\":\"tick\"}\n\
+            {\"id\":1,\"kind\":\"tick\"}\n\
+            {\"id\":2,\"kind\":\"tick\"}\n");
+
+        let response = client.get("/events?n=0").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+
+    #[test]
+    fn parses_json_lines() {
+        let client = client();
+        let body = "[1, \"a\"]\n\n[2, \"b\"]\r\n[3]\n[4, \"d\"\n[5, \"e\"]";
+        let response = client.post("/import").body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "1:a,2:b,error: Data,error: Eof,5:e");
+
+        let response = client.post("/import").body("").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(feature = "serve")]
+mod memory_server_tests {
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+    use rocket_contrib::serve::MemoryServer;
+
+    const INDEX: &[u8] = include_bytes!("static/index.html");
+    const GZIPPED: &[u8] = b"\x1f\x8b pretend this is gzip";
+
+    fn client() -> Client {
+        let server = MemoryServer::new()
+            .file("index.html", INDEX)
+            .file("/inner/index.html", &b"inner"[..])
+            .file("js/app.js.gz", GZIPPED)
+            .file("js/app.js", &b"console.log('hi')"[..])
+            .file("data", vec![1, 2, 3]);
+
+        Client::tracked(rocket::ignite().mount("/", server)).unwrap()
+    }
+
+    #[test]
+    fn serves_files_and_indexes() {
+        let client = client();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_bytes(), Some(INDEX.to_vec()));
+
+        let response = client.get("/inner").dispatch();
+        assert_eq!(response.into_string(), Some("inner".into()));
+
+        let response = client.get("/js/app.js").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::JavaScript));
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+        assert!(response.headers().get_one("Content-Encoding").is_none());
+        assert_eq!(response.into_string(), Some("console.log('hi')".into()));
+
+        let response = client.get("/data").dispatch();
+        assert_eq!(response.content_type(), None);
+        assert_eq!(response.into_bytes(), Some(vec![1, 2, 3]));
+
+        assert_eq!(client.get("/js/app.js.gz").dispatch().status(), Status::NotFound);
+        assert_eq!(client.get("/missing").dispatch().status(), Status::NotFound);
+        assert_eq!(client.get("/js").dispatch().status(), Status::NotFound);
+    }
+
+    #[test]
+    fn serves_gzip_variants() {
+        let client = client();
+        let get = |encoding: &'static str| client.get("/js/app.js")
+            .header(Header::new("Accept-Encoding", encoding))
+            .dispatch();
+
+        for encoding in &["gzip", "deflate, gzip;q=0.5", "*"] {
+            let response = get(*encoding);
+            assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+            assert_eq!(response.into_bytes(), Some(GZIPPED.to_vec()));
+        }
+
+        for encoding in &["deflate", "gzip;q=0", "br"] {
+            let response = get(*encoding);
+            assert!(response.headers().get_one("Content-Encoding").is_none());
+        }
+    }
+
+    #[test]
+    fn supports_conditional_requests() {
+        let client = client();
+        let response = client.get("/js/app.js").dispatch();
+        let etag = response.headers().get_one("ETag").unwrap().to_string();
+
+        let response = client.get("/js/app.js")
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        let gzip_etag = response.headers().get_one("ETag").unwrap().to_string();
+        assert_ne!(etag, gzip_etag);
+
+        let response = client.get("/js/app.js")
+            .header(Header::new("If-None-Match", etag.clone()))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotModified);
+        assert_eq!(response.headers().get_one("ETag"), Some(&*etag));
+
+        let response = client.get("/js/app.js")
+            .header(Header::new("If-None-Match", etag))
+            .header(Header::new("Accept-Encoding", "gzip"))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/js/app.js")
+            .header(Header::new("If-None-Match", "\"other\""))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
@@ -0,0 +1,112 @@
+#[macro_use]
+#[cfg(feature = "otel")]
+extern crate rocket;
+
+#[cfg(feature = "otel")]
+mod otel_tests {
+    use std::sync::{Arc, Mutex};
+
+    use rocket::{Config, State};
+    use rocket::fairing::AdHoc;
+    use rocket::figment::Figment;
+    use rocket::futures::channel::oneshot;
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket_contrib::otel::{OpenTelemetry, Telemetry};
+    use serde_json::Value;
+
+    #[derive(Clone, Default)]
+    struct Received(Arc<Mutex<Vec<(String, Value)>>>);
+
+    #[post("/v1/<kind>", data = "<body>")]
+    fn collect(kind: String, body: String, received: State<'_, Received>) {
+        let value = serde_json::from_str(&body).unwrap();
+        received.0.lock().unwrap().push((kind, value));
+    }
+
+    #[get("/hello/<name>")]
+    fn hello(name: String) -> String {
+        name
+    }
+
+    #[get("/fail")]
+    fn fail() -> Status {
+        Status::InternalServerError
+    }
+
+    async fn collector(received: Received) -> u16 {
+        let (tx, rx) = oneshot::channel();
+        let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+            .manage(received)
+            .mount("/", routes![collect])
+            .attach(AdHoc::on_launch("Send Port", move |rocket| {
+                tx.send(rocket.config().port).unwrap();
+            }));
+
+        rocket::tokio::spawn(rocket.launch());
+        rx.await.unwrap()
+    }
+
+    fn attribute<'a>(attributes: &'a Value, key: &str) -> &'a Value {
+        let attribute = attributes.as_array().unwrap().iter()
+            .find(|a| a["key"] == key)
+            .unwrap_or_else(|| panic!("missing attribute {}", key));
+
+        &attribute["value"]
+    }
+
+    #[rocket::async_test]
+    async fn exports_spans_and_metrics() {
+        let received = Received::default();
+        let port = collector(received.clone()).await;
+        let figment = Figment::from(Config::debug_default())
+            .merge(("otel.endpoint", format!("http://127.0.0.1:{}", port)))
+            .merge(("otel.service_name", "accounts"))
+            .merge(("otel.attributes", [("service.version", "1.2.0")].iter().cloned()
+                .collect::<std::collections::HashMap<_, _>>()));
+
+        let rocket = rocket::custom(figment)
+            .mount("/", routes![hello, fail])
+            .attach(OpenTelemetry::fairing());
+
+        let client = Client::untracked(rocket).await.unwrap();
+        let parent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+        client.get("/hello/bob").header(Header::new("traceparent", parent)).dispatch().await;
+        client.get("/fail").dispatch().await;
+        client.get("/fail").dispatch().await;
+
+        // Unsampled traces are measured but not traced.
+        let unsampled = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00";
+        client.get("/hello/eve").header(Header::new("traceparent", unsampled)).dispatch().await;
+
+        client.rocket().state::<Telemetry>().unwrap().flush().await;
+        let received = received.0.lock().unwrap();
+        assert_eq!(received.len(), 2);
+
+        let (kind, traces) = &received[0];
+        assert_eq!(kind, "traces");
+        let resource = &traces["resourceSpans"][0]["resource"]["attributes"];
+        assert_eq!(attribute(resource, "service.name")["stringValue"], "accounts");
+        assert_eq!(attribute(resource, "deployment.environment")["stringValue"], "debug");
+        assert_eq!(attribute(resource, "service.version")["stringValue"], "1.2.0");
+
+        let spans = traces["resourceSpans"][0]["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0]["name"], "GET /hello/<name>");
+        assert_eq!(spans[0]["traceId"], "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(spans[0]["parentSpanId"], "b7ad6b7169203331");
+        assert_eq!(spans[0]["status"]["code"], 0);
+        assert_eq!(attribute(&spans[0]["attributes"], "http.target")["stringValue"], "/hello/bob");
+        assert_eq!(spans[1]["parentSpanId"], "");
+        assert_eq!(spans[1]["status"]["code"], 2);
+
+        let (kind, metrics) = &received[1];
+        assert_eq!(kind, "metrics");
+        let metric = &metrics["resourceMetrics"][0]["scopeMetrics"][0]["metrics"][0];
+        assert_eq!(metric["name"], "http.server.duration");
+        let points = metric["histogram"]["dataPoints"].as_array().unwrap();
+        assert_eq!(points.len(), 2);
+        let counts: Vec<_> = points.iter().map(|p| p["count"].as_str().unwrap()).collect();
+        assert!(counts.iter().all(|&c| c == "2"));
+    }
+}
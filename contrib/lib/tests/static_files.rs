@@ -5,7 +5,7 @@ mod static_tests {
 
     use rocket::{self, Rocket, Route};
     use rocket_contrib::serve::{StaticFiles, Options, crate_relative};
-    use rocket::http::Status;
+    use rocket::http::{ContentType, Header, Status};
     use rocket::local::blocking::Client;
 
     fn static_root() -> &'static Path {
@@ -189,4 +189,138 @@ mod static_tests {
         assert_eq!(response.status(), Status::PermanentRedirect);
         assert_eq!(response.headers().get("Location").next(), Some("/redir_index/"));
     }
+
+    #[test]
+    fn test_fingerprinting() {
+        let files = StaticFiles::new(static_root(), Options::Fingerprint);
+        let manifest = files.manifest();
+        assert!(StaticFiles::from(static_root()).manifest().is_empty());
+        assert!(manifest.get("inner/.hideme").is_none());
+        assert_eq!(manifest.iter().count(), REGULAR_FILES.len());
+
+        let hello = manifest.get("/other/hello.txt").expect("fingerprinted").to_string();
+        assert!(hello.starts_with("other/hello.") && hello.ends_with(".txt"), "{}", hello);
+        assert_eq!(hello.len(), "other/hello..txt".len() + 8);
+
+        let goodbye = manifest.get("inner/goodbye").expect("fingerprinted").to_string();
+        assert!(goodbye.starts_with("inner/goodbye."), "{}", goodbye);
+
+        let client = Client::tracked(rocket::ignite().mount("/fp", files)).unwrap();
+        for (file, hashed) in &[("other/hello.txt", &hello), ("inner/goodbye", &goodbye)] {
+            let response = client.get(format!("/fp/{}", hashed)).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.headers().get_one("Cache-Control"),
+                Some("public, max-age=31536000, immutable"));
+
+            let expected = std::fs::read_to_string(static_root().join(file)).unwrap();
+            assert_eq!(response.into_string(), Some(expected));
+
+            let response = client.get(format!("/fp/{}", file)).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert!(response.headers().get_one("Cache-Control").is_none());
+        }
+
+        let response = client.get("/fp/other/hello.00000000.txt").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        // The fingerprint depends only on the file's contents.
+        let again = StaticFiles::new(static_root(), Options::Fingerprint | Options::Index);
+        assert_eq!(again.manifest().get("other/hello.txt"), Some(&*hello));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_fingerprinting_symlink_cycle() {
+        let root = std::env::temp_dir().join(format!("rocket-fp-cycle-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub/a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink(&root, root.join("sub/loop")).unwrap();
+
+        let manifest = StaticFiles::new(&root, Options::Fingerprint).manifest();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(manifest.iter().count(), 1);
+        assert!(manifest.get("sub/a.txt").is_some());
+    }
+
+    #[test]
+    fn test_precompressed() {
+        let root = Path::new(crate_relative!("/tests/precompressed"));
+        let rocket = rocket::ignite()
+            .mount("/pre", StaticFiles::new(root, Options::Precompressed))
+            .mount("/plain", StaticFiles::from(root));
+
+        let client = Client::tracked(rocket).unwrap();
+        let get = |path: &str, accept: Option<&'static str>| {
+            let mut request = client.get(path.to_string());
+            if let Some(accept) = accept {
+                request.add_header(Header::new("Accept-Encoding", accept));
+            }
+
+            let response = request.dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            let encoding = response.headers().get_one("Content-Encoding").map(String::from);
+            let vary = response.headers().get_one("Vary").map(String::from);
+            let content_type = response.content_type();
+            (content_type, encoding, vary, response.into_bytes().unwrap())
+        };
+
+        let read = |file: &str| std::fs::read(root.join(file)).unwrap();
+        let vary = Some("Accept-Encoding".to_string());
+        for accept in &[Some("gzip, deflate, br"), Some("br"), Some("br;q=1.0, *;q=0.5")] {
+            assert_eq!(get("/pre/app.js", *accept),
+                (Some(ContentType::JavaScript), Some("br".into()), vary.clone(), read("app.js.br")));
+        }
+
+        for accept in &[Some("gzip"), Some("deflate, gzip"), Some("br;q=0, *")] {
+            assert_eq!(get("/pre/app.js", *accept),
+                (Some(ContentType::JavaScript), Some("gzip".into()), vary.clone(), read("app.js.gz")));
+        }
+
+        for accept in &[None, Some("identity"), Some("gzip;q=0, br;q=0")] {
+            assert_eq!(get("/pre/app.js", *accept),
+                (Some(ContentType::JavaScript), None, vary.clone(), read("app.js")));
+        }
+
+        assert_eq!(get("/pre/style.css", Some("br, gzip")),
+            (Some(ContentType::CSS), Some("gzip".into()), vary.clone(), read("style.css.gz")));
+        assert_eq!(get("/pre/plain.txt", Some("br, gzip")),
+            (Some(ContentType::Plain), None, None, read("plain.txt")));
+
+        // Precompressed variants are only served when enabled.
+        assert_eq!(get("/plain/app.js", Some("br, gzip")),
+            (Some(ContentType::JavaScript), None, None, read("app.js")));
+    }
+
+    #[test]
+    fn test_spa_fallback() {
+        #[rocket::get("/api/status")]
+        fn status() -> &'static str { "up" }
+
+        let files = StaticFiles::new(static_root(), Options::SpaFallback).exclude("/api");
+        let rocket = rocket::ignite()
+            .mount("/", files)
+            .mount("/", rocket::routes![status]);
+
+        let client = Client::tracked(rocket).unwrap();
+        let index = std::fs::read_to_string(static_root().join("index.html")).unwrap();
+        for path in &["/", "/users", "/users/42/edit", "/inner", "/apiary"] {
+            let response = client.get(*path).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.content_type(), Some(ContentType::HTML));
+            assert_eq!(response.into_string().unwrap(), index, "{}", path);
+        }
+
+        for path in &["/missing.js", "/users/logo.png", "/api", "/api/users", "/.hidden"] {
+            let response = client.get(*path).dispatch();
+            assert_eq!(response.status(), Status::NotFound, "{}", path);
+        }
+
+        let expected = std::fs::read_to_string(static_root().join("other/hello.txt")).unwrap();
+        let response = client.get("/other/hello.txt").dispatch();
+        assert_eq!(response.into_string(), Some(expected));
+
+        let response = client.get("/api/status").dispatch();
+        assert_eq!(response.into_string().unwrap(), "up");
+    }
 }
@@ -77,15 +77,79 @@ mod templates_tests {
             assert_eq!(template, Some(ESCAPED_EXPECTED.into()));
         }
 
+        #[get("/tera/streamed")]
+        fn streamed() -> Template {
+            let mut map = HashMap::new();
+            map.insert("title", "_test_");
+            map.insert("content", "<script />");
+            Template::render("tera/html_test", &map).streamed()
+        }
+
+        #[test]
+        fn test_tera_streamed() {
+            let client = Client::tracked(rocket().mount("/", routes![streamed])).unwrap();
+            let response = client.get("/tera/streamed").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string(), Some(ESCAPED_EXPECTED.into()));
+        }
+
+        #[get("/tera/nonce?<fixed>")]
+        fn nonce(fixed: Option<String>) -> Template {
+            let mut map = HashMap::new();
+            if let Some(fixed) = fixed {
+                map.insert("csp_nonce", fixed);
+            }
+
+            Template::render("tera/nonce", &map)
+        }
+
+        #[test]
+        fn test_tera_csp_nonce() {
+            let client = Client::tracked(rocket().mount("/", routes![nonce])).unwrap();
+            let body = client.get("/tera/nonce").dispatch().into_string().unwrap();
+            let nonce = body.trim()
+                .strip_prefix("<script nonce=\"").unwrap()
+                .strip_suffix("\"></script>").unwrap();
+
+            assert!(!nonce.is_empty());
+            assert!(nonce.chars().all(|c| c.is_ascii_alphanumeric()));
+
+            // A `csp_nonce` in the context takes precedence.
+            let body = client.get("/tera/nonce?fixed=abc").dispatch().into_string().unwrap();
+            assert_eq!(body.trim(), "<script nonce=\"abc\"></script>");
+        }
+
+        #[get("/tera/bad")]
+        fn bad() -> Status {
+            Status::BadRequest
+        }
+
+        #[test]
+        fn test_tera_error_pages() {
+            let rocket = rocket().mount("/", routes![bad]).error_pages(Template::error_pages());
+            let client = Client::tracked(rocket).unwrap();
+            let response = client.get("/tera/missing/page").dispatch();
+            assert_eq!(response.status(), Status::NotFound);
+            assert_eq!(response.into_string().unwrap(),
+                "<h1>404: Not Found</h1>\n<p>The requested resource could not be found.</p>\n");
+
+            // Without an `errors/400` or `errors/default` template, the
+            // built-in page is used.
+            let response = client.get("/tera/bad").dispatch();
+            assert_eq!(response.status(), Status::BadRequest);
+            let body = response.into_string().unwrap();
+            assert!(body.contains("<h1>400: Bad Request</h1>"));
+        }
+
         #[test]
         fn test_template_metadata_with_tera() {
             let client = Client::tracked(rocket()).unwrap();
 
             let response = client.get("/tera/txt_test").dispatch();
-            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.status(), Status::NoContent);
 
             let response = client.get("/tera/html_test").dispatch();
-            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.status(), Status::NoContent);
 
             let response = client.get("/tera/not_existing").dispatch();
             assert_eq!(response.status(), Status::NotFound);
@@ -117,12 +181,55 @@ mod templates_tests {
             assert_eq!(template, Some(EXPECTED.into()));
         }
 
+        #[get("/hbs/streamed")]
+        fn streamed() -> Template {
+            let mut map = HashMap::new();
+            map.insert("title", "_test_");
+            map.insert("content", "<script /> hi");
+            Template::render("hbs/test", &map).streamed()
+        }
+
+        #[get("/hbs/fragment/<name>")]
+        fn fragment(name: String) -> Template {
+            let mut map = HashMap::new();
+            map.insert("content", "<b>");
+            map.insert("title", "frag");
+            Template::fragment(format!("hbs/{}", name), &map)
+        }
+
+        #[test]
+        fn test_handlebars_streamed_and_fragments() {
+            let rocket = rocket().mount("/", routes![streamed, fragment]);
+            let client = Client::tracked(rocket).unwrap();
+
+            let response = client.get("/hbs/streamed").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.content_type(), Some(rocket::http::ContentType::HTML));
+            assert_eq!(response.into_string(), Some(EXPECTED.into()));
+
+            let response = client.get("/hbs/fragment/item").dispatch();
+            assert_eq!(response.into_string(), Some("<li>&lt;b&gt;</li>\n".into()));
+
+            let response = client.get("/hbs/fragment/_item").dispatch();
+            assert_eq!(response.into_string(), Some("<li>&lt;b&gt;</li>\n".into()));
+
+            let response = client.get("/hbs/fragment/common%2Fheader").dispatch();
+            assert_eq!(response.into_string(), Some("Hello frag!\n".into()));
+
+            let response = client.get("/hbs/fragment/missing").dispatch();
+            assert_eq!(response.status(), Status::InternalServerError);
+
+            // Fragment resolution only applies to `Template::fragment()`.
+            let template = Template::show(client.rocket(), "hbs/item", ());
+            assert_eq!(template, None);
+        }
+
         #[test]
         fn test_template_metadata_with_handlebars() {
             let client = Client::tracked(rocket()).unwrap();
 
             let response = client.get("/hbs/test").dispatch();
-            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.status(), Status::NoContent);
 
             let response = client.get("/hbs/not_existing").dispatch();
             assert_eq!(response.status(), Status::NotFound);
@@ -158,7 +265,7 @@ mod templates_tests {
             // set up the client. if we can't reload templates, then just quit
             let client = Client::tracked(rocket()).unwrap();
             let res = client.get("/is_reloading").dispatch();
-            if res.status() != Status::Ok {
+            if res.status() != Status::NoContent {
                 return;
             }
 
@@ -1,13 +1,113 @@
-use devise::{syn, Diagnostic, Spanned, Result};
+use devise::{syn, Diagnostic, Spanned, SpanWrapped, Result, FromMeta};
 use devise::ext::SpanDiagnosticExt;
 use devise::proc_macro2::{TokenStream, Span};
 
+use self::syn::{Attribute, parse::Parser};
+
+/// The runtime configuration arguments to an entry attribute, as in
+/// `#[rocket::main(flavor = "current_thread")]`.
+#[derive(Default, FromMeta)]
+struct EntryArgs {
+    flavor: Option<SpanWrapped<String>>,
+    worker_threads: Option<SpanWrapped<usize>>,
+}
+
+impl EntryArgs {
+    fn parse(args: TokenStream) -> Result<Self> {
+        if args.is_empty() {
+            return Ok(EntryArgs::default());
+        }
+
+        let attrs = Attribute::parse_outer.parse2(quote!(#[entry(#args)]))?;
+        match EntryArgs::from_attrs("entry", &attrs) {
+            Some(result) => result.map_err(|diag| {
+                diag.help("expected `flavor = \"multi_thread\"` or `\"current_thread\"` \
+                    and/or `worker_threads = N`")
+            }),
+            None => Err(Span::call_site().error("internal error: bad attribute"))
+        }
+    }
+
+    /// Returns an expression that runs the future `fut` to completion on a
+    /// runtime configured by `self`.
+    fn block_on(&self, fut: TokenStream) -> Result<TokenStream> {
+        let multi_thread = match &self.flavor {
+            Some(flavor) if flavor.value == "multi_thread" => true,
+            Some(flavor) if flavor.value == "current_thread" => false,
+            Some(flavor) => return Err(flavor.span
+                .error("invalid runtime flavor")
+                .help("expected `\"multi_thread\"` or `\"current_thread\"`")),
+            None => true,
+        };
+
+        let worker_threads = match &self.worker_threads {
+            Some(n) if !multi_thread => return Err(n.span
+                .error("`worker_threads` requires the `multi_thread` flavor")),
+            Some(n) if n.value == 0 => return Err(n.span
+                .error("`worker_threads` must be greater than zero")),
+            Some(n) => { let n = n.value; quote!(::std::option::Option::Some(#n)) },
+            None => quote!(::std::option::Option::None),
+        };
+
+        Ok(quote!(::rocket::async_main_with(#fut, #multi_thread, #worker_threads)))
+    }
+}
+
+/// The arguments to `#[rocket::async_test]`: an optional `client = EXPR`
+/// fixture, where `EXPR` evaluates to the `Rocket` to build a client for.
+struct TestArgs {
+    client: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for TestArgs {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(TestArgs { client: None });
+        }
+
+        let key: syn::Ident = input.parse()?;
+        if key != "client" {
+            return Err(syn::Error::new(key.span(), "expected `client = expr`"));
+        }
+
+        input.parse::<syn::Token![=]>()?;
+        Ok(TestArgs { client: Some(input.parse()?) })
+    }
+}
+
+/// Whether `output` is a `Result`, or an alias like `io::Result`, by name.
+fn returns_result(output: &syn::ReturnType) -> bool {
+    match output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(path) => path.path.segments.last()
+                .map_or(false, |segment| segment.ident == "Result"),
+            _ => false
+        },
+        syn::ReturnType::Default => false
+    }
+}
+
+/// Returns an expression that evaluates to the `Ok` value of `result` or
+/// otherwise prints the `Err` value with `Display` and exits the process.
+fn unwrap_or_exit(result: TokenStream) -> TokenStream {
+    quote! {
+        match #result {
+            ::std::result::Result::Ok(___value) => ___value,
+            ::std::result::Result::Err(___error) => ::rocket::logger::exit_with_error(___error),
+        }
+    }
+}
+
 trait EntryAttr {
     /// Whether the attribute requires the attributed function to be `async`.
     const REQUIRES_ASYNC: bool;
 
+    /// Whether the attribute validates the function's arguments itself. If
+    /// not, the function must take no arguments.
+    const CHECKS_INPUTS: bool = false;
+
     /// Return a new or rewritten function, using block as the main execution.
-    fn function(f: &mut syn::ItemFn) -> Result<TokenStream>;
+    fn function(f: &mut syn::ItemFn, args: TokenStream) -> Result<TokenStream>;
 }
 
 struct Main;
@@ -15,7 +115,8 @@ struct Main;
 impl EntryAttr for Main {
     const REQUIRES_ASYNC: bool = true;
 
-    fn function(f: &mut syn::ItemFn) -> Result<TokenStream> {
+    fn function(f: &mut syn::ItemFn, args: TokenStream) -> Result<TokenStream> {
+        let args = EntryArgs::parse(args)?;
         let (attrs, vis, block, sig) = (&f.attrs, &f.vis, &f.block, &mut f.sig);
         if sig.ident != "main" {
             // FIXME(diag): warning!
@@ -26,8 +127,14 @@ impl EntryAttr for Main {
         }
 
         sig.asyncness = None;
+        let mut body = args.block_on(quote!(async move #block))?;
+        if returns_result(&sig.output) {
+            sig.output = syn::ReturnType::Default;
+            body = unwrap_or_exit(body);
+        }
+
         Ok(quote_spanned!(block.span().into() => #(#attrs)* #vis #sig {
-            ::rocket::async_main(async move #block)
+            #body
         }))
     }
 }
@@ -37,9 +144,41 @@ struct Test;
 impl EntryAttr for Test {
     const REQUIRES_ASYNC: bool = true;
 
-    fn function(f: &mut syn::ItemFn) -> Result<TokenStream> {
+    const CHECKS_INPUTS: bool = true;
+
+    fn function(f: &mut syn::ItemFn, args: TokenStream) -> Result<TokenStream> {
+        let args: TestArgs = syn::parse2(args).map_err(Diagnostic::from)?;
         let (attrs, vis, block, sig) = (&f.attrs, &f.vis, &f.block, &mut f.sig);
+        let fixture = match (args.client, sig.inputs.len()) {
+            (None, 0) => None,
+            (None, _) => return Err(Span::call_site()
+                .error("attribute can only be applied to functions without arguments")
+                .span_note(sig.span(), "this function must take no arguments")
+                .help("use `client = expr` to pass a `Client` as the only argument")),
+            (Some(rocket), 1) => match sig.inputs.first() {
+                Some(syn::FnArg::Typed(arg)) => Some((arg.clone(), rocket)),
+                _ => return Err(Span::call_site()
+                    .error("`client` fixture argument cannot be `self`")
+                    .span_note(sig.span(), "this function must take one `Client` argument")),
+            },
+            (Some(_), _) => return Err(Span::call_site()
+                .error("`client` fixture requires a function with exactly one argument")
+                .span_note(sig.span(), "this function must take one `Client` argument")),
+        };
+
+        let block = match fixture {
+            Some((syn::PatType { pat, ty, .. }, rocket)) => quote_spanned!(ty.span().into() => {
+                let #pat: #ty = ::rocket::local::asynchronous::Client::tracked(#rocket)
+                    .await
+                    .expect("valid `Rocket` for `client` fixture");
+
+                #block
+            }),
+            None => quote!(#block),
+        };
+
         sig.asyncness = None;
+        sig.inputs = Default::default();
         Ok(quote_spanned!(block.span().into() => #(#attrs)* #[test] #vis #sig {
             ::rocket::async_test(async move #block)
         }))
@@ -51,7 +190,8 @@ struct Launch;
 impl EntryAttr for Launch {
     const REQUIRES_ASYNC: bool = false;
 
-    fn function(f: &mut syn::ItemFn) -> Result<TokenStream> {
+    fn function(f: &mut syn::ItemFn, args: TokenStream) -> Result<TokenStream> {
+        let args = EntryArgs::parse(args)?;
         if f.sig.ident == "main" {
             return Err(Span::call_site()
                 .error("attribute cannot be applied to `main` function")
@@ -75,9 +215,15 @@ impl EntryAttr for Launch {
         };
 
         let block = &f.block;
+        let value = if returns_result(&f.sig.output) {
+            unwrap_or_exit(quote!(___rocket))
+        } else {
+            quote!(___rocket)
+        };
+
         let rocket = quote_spanned!(ty.span().into() => {
             let ___rocket: #ty = #block;
-            let ___rocket: ::rocket::Rocket = ___rocket;
+            let ___rocket: ::rocket::Rocket = #value;
             ___rocket
         });
 
@@ -86,11 +232,12 @@ impl EntryAttr for Launch {
         sig.output = syn::ReturnType::Default;
         sig.asyncness = None;
 
+        let body = args.block_on(quote!(async move { let _ = #rocket.launch().await; }))?;
         Ok(quote_spanned!(block.span().into() =>
             #[allow(dead_code)] #f
 
             #vis #sig {
-                ::rocket::async_main(async move { let _ = #rocket.launch().await; })
+                #body
             }
         ))
     }
@@ -107,7 +254,7 @@ fn parse_input<A: EntryAttr>(input: proc_macro::TokenStream) -> Result<syn::Item
             .span_note(function.sig.span(), "this function must be `async`"));
     }
 
-    if !function.sig.inputs.is_empty() {
+    if !A::CHECKS_INPUTS && !function.sig.inputs.is_empty() {
         return Err(Span::call_site()
             .error("attribute can only be applied to functions without arguments")
             .span_note(function.sig.span(), "this function must take no arguments"));
@@ -117,11 +264,11 @@ fn parse_input<A: EntryAttr>(input: proc_macro::TokenStream) -> Result<syn::Item
 }
 
 fn _async_entry<A: EntryAttr>(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream
 ) -> Result<TokenStream> {
     let mut function = parse_input::<A>(input)?;
-    A::function(&mut function).map(|t| t.into())
+    A::function(&mut function, args.into()).map(|t| t.into())
 }
 
 macro_rules! async_entry {
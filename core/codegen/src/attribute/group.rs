@@ -0,0 +1,188 @@
+use devise::{syn, Spanned, Result, Diagnostic};
+use devise::ext::SpanDiagnosticExt;
+
+use crate::http::uri::Origin;
+use crate::proc_macro2::{TokenStream, Span};
+use crate::syn::{Attribute, Lit, LitStr, Meta, NestedMeta, Token, Type};
+use crate::syn::parse::{self, Parse, ParseStream};
+
+/// The names of the route attributes whose routes a group rewrites.
+const ROUTE_ATTRS: &[&str] = &[
+    "route", "get", "put", "post", "delete", "head", "patch", "options"
+];
+
+/// The prefix of the idents of request guard parameters added to each route.
+const GUARD_PARAM_PREFIX: &str = "__rocket_group_guard_";
+
+/// The parsed `#[route_group(...)]` attribute.
+#[derive(Default)]
+struct GroupAttribute {
+    prefix: Option<LitStr>,
+    format: Option<LitStr>,
+    guards: Vec<Type>,
+}
+
+impl Parse for GroupAttribute {
+    fn parse(input: ParseStream<'_>) -> parse::Result<Self> {
+        let mut attr = GroupAttribute::default();
+        while !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            if key == "prefix" || key == "format" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<LitStr>()?;
+                let slot = if key == "prefix" { &mut attr.prefix } else { &mut attr.format };
+                if slot.replace(value).is_some() {
+                    return Err(parse::Error::new(key.span(), format!("duplicate `{}`", key)));
+                }
+            } else if key == "guards" {
+                let content;
+                syn::parenthesized!(content in input);
+                let guards = content.parse_terminated::<Type, Token![,]>(Type::parse)?;
+                attr.guards.extend(guards);
+            } else {
+                let msg = "expected one of `prefix`, `format`, or `guards`";
+                return Err(parse::Error::new(key.span(), msg));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(attr)
+    }
+}
+
+impl GroupAttribute {
+    /// Validates the prefix, which must be a static origin URI path.
+    fn check_prefix(&self) -> Result<()> {
+        let prefix = match self.prefix {
+            Some(ref prefix) => prefix,
+            None => return Ok(()),
+        };
+
+        let value = prefix.value();
+        let valid = Origin::parse(&value).map_or(false, |uri| uri.query().is_none());
+        if !valid || value.contains('<') {
+            return Err(prefix.span().error("invalid route group prefix")
+                .help("the prefix must be a static path without a query, e.g. \"/api/v1\""));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `path` with the group's prefix, if any, prepended.
+    fn prefixed(&self, path: &LitStr) -> LitStr {
+        let prefix = match self.prefix {
+            Some(ref prefix) => prefix.value(),
+            None => return path.clone(),
+        };
+
+        let (prefix, path_str) = (prefix.trim_end_matches('/'), path.value());
+        let full = match path_str.as_str() {
+            "/" if !prefix.is_empty() => prefix.to_string(),
+            _ => format!("{}{}", prefix, path_str),
+        };
+
+        LitStr::new(&full, path.span())
+    }
+
+    /// Rewrites `attr`, a route attribute, with the group's prefix and format.
+    fn rewrite(&self, attr: &mut Attribute) -> Result<()> {
+        let mut list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(meta.span().error("expected route attribute arguments")),
+        };
+
+        let path = list.nested.iter_mut()
+            .find_map(|meta| match meta {
+                NestedMeta::Lit(Lit::Str(path)) => Some(path),
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("path") => {
+                    match nv.lit {
+                        Lit::Str(ref mut path) => Some(path),
+                        _ => None
+                    }
+                }
+                _ => None
+            })
+            .ok_or_else(|| list.span().error("route attribute is missing a path"))?;
+
+        *path = self.prefixed(path);
+
+        let has_format = list.nested.iter().any(|meta| match meta {
+            NestedMeta::Meta(Meta::NameValue(nv)) => nv.path.is_ident("format"),
+            _ => false
+        });
+
+        if let (Some(format), false) = (&self.format, has_format) {
+            list.nested.push(syn::parse_quote!(format = #format));
+        }
+
+        let args = &list.nested;
+        attr.tokens = quote!((#args));
+        Ok(())
+    }
+}
+
+/// Returns `true` if `attr` is one of Rocket's route attributes.
+fn is_route_attr(attr: &Attribute) -> bool {
+    attr.path.segments.last()
+        .map_or(false, |segment| ROUTE_ATTRS.iter().any(|name| segment.ident == name))
+}
+
+fn group(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    let attr: GroupAttribute = syn::parse2(args)?;
+    attr.check_prefix()?;
+
+    let mut module: syn::ItemMod = syn::parse2(input)
+        .map_err(Diagnostic::from)
+        .map_err(|diag| diag.help("`#[route_group]` can only be used on modules"))?;
+
+    let items = match module.content {
+        Some((_, ref mut items)) => items,
+        None => return Err(module.span().error("`#[route_group]` requires an inline module")
+            .help("declare the module's routes inline: `mod api { .. }`")),
+    };
+
+    let mut route_names = vec![];
+    for item in items.iter_mut() {
+        let function = match item {
+            syn::Item::Fn(function) => function,
+            _ => continue,
+        };
+
+        let mut is_route = false;
+        for route_attr in function.attrs.iter_mut().filter(|a| is_route_attr(a)) {
+            attr.rewrite(route_attr)?;
+            is_route = true;
+        }
+
+        if !is_route {
+            continue;
+        }
+
+        for (i, guard) in attr.guards.iter().enumerate() {
+            let name = format!("{}{}", GUARD_PARAM_PREFIX, i);
+            let ident = syn::Ident::new(&name, Span::call_site());
+            function.sig.inputs.push(syn::parse_quote!(#ident: #guard));
+        }
+
+        route_names.push(function.sig.ident.clone());
+    }
+
+    items.push(syn::parse_quote! {
+        /// Returns all of the routes in this route group, ready to be mounted.
+        pub fn routes() -> ::std::vec::Vec<::rocket::Route> {
+            ::rocket::routes![#(#route_names),*]
+        }
+    });
+
+    Ok(quote!(#module))
+}
+
+pub fn route_group_attribute(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream
+) -> TokenStream {
+    group(args.into(), input.into()).unwrap_or_else(|diag| diag.emit_as_item_tokens())
+}
@@ -1,4 +1,5 @@
 pub mod async_entry;
 pub mod catch;
+pub mod group;
 pub mod route;
 pub mod segments;
@@ -4,6 +4,7 @@ use std::hash::{Hash, Hasher};
 
 use devise::{syn, Spanned, SpanWrapped, Result, FromMeta, Diagnostic};
 use devise::ext::{SpanDiagnosticExt, TypeExt};
+use quote::ToTokens;
 use indexmap::IndexSet;
 
 use crate::proc_macro_ext::{Diagnostics, StringLit};
@@ -24,6 +25,9 @@ struct RouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    version: Option<u32>,
+    extensions: Option<ExtensionsFn>,
+    blocking: Option<SpanWrapped<bool>>,
 }
 
 /// The raw, parsed `#[method]` (e.g, `get`, `put`, `post`, etc.) attribute.
@@ -34,6 +38,32 @@ struct MethodRouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    version: Option<u32>,
+    extensions: Option<ExtensionsFn>,
+    blocking: Option<SpanWrapped<bool>>,
+}
+
+/// The path to the function named by a route's `extensions` argument.
+#[derive(Debug)]
+struct ExtensionsFn(syn::Path);
+
+impl FromMeta for ExtensionsFn {
+    fn from_meta(meta: devise::MetaItem<'_>) -> Result<Self> {
+        let string = StringLit::from_meta(meta)?;
+        let path = syn::LitStr::new(&string, string.span())
+            .parse::<syn::Path>()
+            .map_err(|_| string.span().error("invalid extensions function path")
+                .help("expected the path to a `fn(&mut Extensions)`: \"route_extensions\""))?;
+
+        Ok(ExtensionsFn(path))
+    }
+}
+
+impl ToTokens for ExtensionsFn {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let path = &self.0;
+        tokens.extend(quote_spanned!(path.span() => #path as fn(&mut ::rocket::Extensions)));
+    }
 }
 
 /// This structure represents the parsed `route` attribute and associated items.
@@ -66,6 +96,15 @@ fn parse_route(attr: RouteAttribute, function: syn::ItemFn) -> Result<Route> {
         }
     }
 
+    // `blocking` handlers are run synchronously; they can't be `async`.
+    if let Some(ref blocking) = attr.blocking {
+        if let (true, Some(asyncness)) = (blocking.value, function.sig.asyncness) {
+            diags.push(blocking.span.error("`blocking` cannot be used with `async` handlers")
+                .span_note(asyncness.span(), "handler is declared `async` here")
+                .help("remove `async` from the handler or remove `blocking`"));
+        }
+    }
+
     // Collect non-wild dynamic segments in an `IndexSet`, checking for dups.
     let mut segments: IndexSet<Segment> = IndexSet::new();
     fn dup_check<'a, I>(set: &mut IndexSet<Segment>, iter: I, diags: &mut Diagnostics)
@@ -172,10 +211,17 @@ fn param_expr(seg: &Segment, ident: &syn::Ident, ty: &syn::Type) -> TokenStream
     }
 }
 
+/// The name of `ty` as recorded in request timings.
+fn timing_name(ty: &syn::Type) -> String {
+    quote!(#ty).to_string().replace(' ', "")
+}
+
 fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
-    define_vars_and_mods!(req, data, FromTransformedData, Outcome, Transform);
+    define_vars_and_mods!(req, data, request, FromTransformedData, Outcome, Transform);
     let span = ident.span().join(ty.span()).unwrap_or_else(|| ty.span());
+    let name = timing_name(ty);
     quote_spanned! { span =>
+        let __timer = #request::Timings::start(#req);
         let __transform = <#ty as #FromTransformedData>::transform(#req, #data).await;
 
         #[allow(unreachable_patterns, unreachable_code)]
@@ -192,8 +238,11 @@ fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
             #Transform::Owned(__o) => #Transform::Owned(__o),
         };
 
+        let __outcome = <#ty as #FromTransformedData>::from_data(#req, __outcome).await;
+        #request::Timings::stop(#req, __timer, #request::Phase::DataGuard, #name);
+
         #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
-        let #ident: #ty = match <#ty as #FromTransformedData>::from_data(#req, __outcome).await {
+        let #ident: #ty = match __outcome {
             #Outcome::Success(__d) => __d,
             #Outcome::Forward(__d) => return #Outcome::Forward(__d),
             #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
@@ -235,6 +284,17 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
         let matcher = match segment.kind {
             Kind::Single => quote_spanned! { span =>
                 (_, #name, __v) => {
+                    if #ident.is_some() {
+                        match __duplicates {
+                            #request::Duplicates::First => continue,
+                            #request::Duplicates::Last => { /* overwrite */ },
+                            #request::Duplicates::Reject => {
+                                #log::warn_(&format!("Duplicate query parameter '{}'.", #name));
+                                return #Outcome::Forward(#data);
+                            }
+                        }
+                    }
+
                     #[allow(unreachable_patterns, unreachable_code)]
                     let __v = match <#ty as #request::FromFormValue>::from_form_value(__v) {
                         #_Ok(__v) => __v,
@@ -268,7 +328,7 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
             },
             Kind::Multi => quote_spanned! { span =>
                 #[allow(non_snake_case)]
-                let #ident = match <#ty as #request::FromQuery>::from_query(#Query(&#trail)) {
+                let #ident = match <#ty as #request::FromQuery>::from_query(#Query(&#trail, __duplicates)) {
                     #_Ok(__v) => __v,
                     #_Err(__e) => {
                         #log::warn_(&format!("Failed to parse '{}': {:?}", #name, __e));
@@ -288,6 +348,8 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
     Some(quote! {
         #(#decls)*
 
+        #[allow(unused_variables)]
+        let __duplicates = #req.form_duplicates();
         if let #_Some(__items) = #req.raw_query_items() {
             for __i in __items {
                 match (__i.raw.as_str(), __i.key.as_str(), __i.value) {
@@ -309,9 +371,14 @@ fn query_exprs(route: &Route) -> Option<TokenStream> {
 fn request_guard_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
     define_vars_and_mods!(req, data, request, Outcome);
     let span = ident.span().join(ty.span()).unwrap_or_else(|| ty.span());
+    let name = timing_name(ty);
     quote_spanned! { span =>
+        let __timer = #request::Timings::start(#req);
+        let __outcome = <#ty as #request::FromRequest>::from_request(#req).await;
+        #request::Timings::stop(#req, __timer, #request::Phase::Guard, #name);
+
         #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
-        let #ident: #ty = match <#ty as #request::FromRequest>::from_request(#req).await {
+        let #ident: #ty = match __outcome {
             #Outcome::Success(__v) => __v,
             #Outcome::Forward(_) => return #Outcome::Forward(#data),
             #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
@@ -341,6 +408,8 @@ fn generate_internal_uri_macro(route: &Route) -> TokenStream {
     let generated_macro_name = route.function.sig.ident.prepend(URI_MACRO_PREFIX);
     let inner_generated_macro_name = generated_macro_name.append(&hasher.finish().to_string());
     let route_uri = route.attribute.path.origin.0.to_string();
+    let route_name = route.function.sig.ident.to_string();
+    let method = route.attribute.method;
 
     quote_spanned! { Span::call_site() =>
         #[doc(hidden)]
@@ -349,7 +418,8 @@ fn generate_internal_uri_macro(route: &Route) -> TokenStream {
             ($($token:tt)*) => {{
                 extern crate std;
                 extern crate rocket;
-                rocket::rocket_internal_uri!(#route_uri, (#(#dynamic_args),*), $($token)*)
+                rocket::rocket_internal_uri!(#route_name, #method, #route_uri,
+                    (#(#dynamic_args),*), $($token)*)
             }};
         }
 
@@ -364,20 +434,35 @@ fn generate_respond_expr(route: &Route) -> TokenStream {
         syn::ReturnType::Type(_, ref ty) => ty.span().into()
     };
 
-    define_vars_and_mods!(req);
+    define_vars_and_mods!(req, request);
     define_vars_and_mods!(ret_span => handler);
     let user_handler_fn_name = &route.function.sig.ident;
+    let name = user_handler_fn_name.to_string();
     let parameter_names = route.inputs.iter()
         .map(|(_, rocket_ident, _)| rocket_ident);
 
     let _await = route.function.sig.asyncness.map(|a| quote_spanned!(a.span().into() => .await));
-    let responder_stmt = quote_spanned! { ret_span =>
-        let ___responder = #user_handler_fn_name(#(#parameter_names),*) #_await;
+    let blocking = route.attribute.blocking.as_ref().map_or(false, |b| b.value);
+    let responder_stmt = match blocking {
+        true => quote_spanned! { ret_span =>
+            let ___responder = #handler::blocking(move || {
+                #user_handler_fn_name(#(#parameter_names),*)
+            }).await;
+        },
+        false => quote_spanned! { ret_span =>
+            let ___responder = #user_handler_fn_name(#(#parameter_names),*) #_await;
+        }
     };
 
     quote_spanned! { ret_span =>
+        let __timer = #request::Timings::start(#req);
         #responder_stmt
-        #handler::Outcome::from(#req, ___responder)
+        #request::Timings::stop(#req, __timer, #request::Phase::Handler, #name);
+
+        let __timer = #request::Timings::start(#req);
+        let __outcome = #handler::Outcome::from(#req, ___responder);
+        #request::Timings::stop(#req, __timer, #request::Phase::Responder, #name);
+        __outcome
     }
 }
 
@@ -419,6 +504,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let path = route.attribute.path.origin.0.to_string();
     let rank = Optional(route.attribute.rank);
     let format = Optional(route.attribute.format);
+    let version = Optional(route.attribute.version);
+    let extensions = Optional(route.attribute.extensions);
 
     Ok(quote! {
         #user_handler_fn
@@ -451,6 +538,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    version: #version,
+                    extensions: #extensions,
                 }
             }
         }
@@ -514,6 +603,9 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        version: method_attribute.version,
+        extensions: method_attribute.extensions,
+        blocking: method_attribute.blocking,
     };
 
     codegen_route(parse_route(attribute, function)?)
@@ -229,8 +229,38 @@ pub fn _uri_internal_macro(input: TokenStream) -> Result<TokenStream> {
     let path = explode_path(&uri, &mut bindings, path_params);
     let query = Optional(explode_query(&uri, &mut bindings, query_params));
 
-     Ok(quote!({
-         #(#bindings)*
-         #uri_mod::UriArguments { path: #path, query: #query, }.into_origin()
-     }))
+    let origin = quote!(#uri_mod::UriArguments { path: #path, query: #query, }.into_origin());
+    let uri = match internal.uri_params.base {
+        Some(ref base) => quote!(#uri_mod::UriBase::uri_base(&(#base)).join(&#origin)),
+        None => origin,
+    };
+
+    let target = register_target(&internal);
+    Ok(quote!({
+        #target
+        #(#bindings)*
+        #uri
+    }))
+}
+
+// Registers the invocation with the targeted route and mount point so that
+// `Rocket` can check that the route is mounted there at launch. Registration
+// only happens when `rocket`'s `uri-checks` feature is enabled.
+fn register_target(internal: &InternalUriParams) -> TokenStream {
+    let (name, method) = (&internal.route_name, &internal.route_method);
+    let path = internal.route_uri.to_string();
+    let mount_point = internal.uri_params.mount_point.as_ref()
+        .map(|origin| origin.path().to_string())
+        .unwrap_or_else(|| "/".into());
+
+    quote! {
+        rocket::__uri_target! {
+            name: #name,
+            method: #method,
+            path: #path,
+            mount_point: #mount_point,
+            file: std::file!(),
+            line: std::line!(),
+        }
+    }
 }
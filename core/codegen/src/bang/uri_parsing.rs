@@ -31,12 +31,13 @@ pub enum Args {
 }
 
 // For an invocation that looks like:
-//  uri!("/mount/point", this::route: e1, e2, e3);
-//       ^-------------| ^----------| ^---------|
-//           uri_params.mount_point |    uri_params.arguments
-//                      uri_params.route_path
+//  uri!(base = b, "/mount/point", this::route: e1, e2, e3);
+//              ^  ^-------------| ^----------| ^---------|
+//              |      uri_params.mount_point |    uri_params.arguments
+//   uri_params.base         uri_params.route_path
 #[derive(Debug)]
 pub struct UriParams {
+    pub base: Option<Expr>,
     pub mount_point: Option<Origin<'static>>,
     pub route_path: Path,
     pub arguments: Args,
@@ -73,6 +74,8 @@ pub enum Validation<'a> {
 //                      route_uri               fn_args          uri_params
 #[derive(Debug)]
 pub struct InternalUriParams {
+    pub route_name: LitStr,
+    pub route_method: Path,
     pub route_uri: Origin<'static>,
     pub fn_args: Vec<FnArg>,
     pub uri_params: UriParams,
@@ -114,6 +117,27 @@ impl Parse for UriParams {
             return Err(input.error("call to `uri!` cannot be empty"));
         }
 
+        // Parse the `base = expr` and suffixing ',', if any.
+        let base = if input.peek(Ident) && input.peek2(Token![=]) {
+            let ident = input.parse::<Ident>()?;
+            if ident != "base" {
+                return err(ident.span(), "unexpected named argument: \
+                    only `base` may precede the route path");
+            }
+
+            input.parse::<Token![=]>()?;
+            let expr = input.parse::<Expr>()?;
+            if input.cursor().eof() {
+                return err(expr.span(), "unexpected end of input: \
+                    expected ',' followed by route path");
+            }
+
+            input.parse::<Token![,]>()?;
+            Some(expr)
+        } else {
+            None
+        };
+
         // Parse the mount point and suffixing ',', if any.
         let mount_point = if input.peek(LitStr) {
             let string = input.parse::<LitStr>()?;
@@ -140,7 +164,7 @@ impl Parse for UriParams {
         // If there are no arguments, finish early.
         if !input.peek(Token![:]) && input.cursor().eof() {
             let arguments = Args::Unnamed(Punctuated::new());
-            return Ok(Self { mount_point, route_path, arguments });
+            return Ok(Self { base, mount_point, route_path, arguments });
         }
 
         // Parse arguments
@@ -171,7 +195,7 @@ impl Parse for UriParams {
             _ => Args::Unnamed(arguments)
         };
 
-        Ok(Self { mount_point, route_path, arguments })
+        Ok(Self { base, mount_point, route_path, arguments })
     }
 }
 
@@ -187,6 +211,11 @@ impl Parse for FnArg {
 
 impl Parse for InternalUriParams {
     fn parse(input: ParseStream<'_>) -> parse::Result<InternalUriParams> {
+        let route_name = input.parse::<LitStr>()?;
+        input.parse::<Token![,]>()?;
+        let route_method = input.parse::<Path>()?;
+        input.parse::<Token![,]>()?;
+
         let route_uri_str = input.parse::<LitStr>()?;
         input.parse::<Token![,]>()?;
 
@@ -203,7 +232,7 @@ impl Parse for InternalUriParams {
 
         input.parse::<Token![,]>()?;
         let uri_params = input.parse::<UriParams>()?;
-        Ok(InternalUriParams { route_uri, fn_args, uri_params })
+        Ok(InternalUriParams { route_name, route_method, route_uri, fn_args, uri_params })
     }
 }
 
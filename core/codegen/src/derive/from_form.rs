@@ -5,7 +5,74 @@ use crate::syn_ext::NameSource;
 
 #[derive(FromMeta)]
 pub struct Form {
-    pub field: FormField,
+    pub field: Option<FormField>,
+    pub nested: bool,
+    pub honeypot: bool,
+    pub max_index: Option<SpanWrapped<usize>>,
+    pub duplicates: Option<Duplicates>,
+}
+
+/// The form field name of a struct field and how it's parsed.
+struct FieldInfo {
+    span: Span,
+    name: NameSource,
+    nested: bool,
+    honeypot: bool,
+    max_index: Option<usize>,
+    duplicates: Option<Duplicates>,
+}
+
+impl FieldInfo {
+    fn from_field(field: &syn::Field) -> Result<Self> {
+        let id = field.ident.as_ref().expect("named field");
+        let form = match Form::from_attrs("form", &field.attrs) {
+            Some(result) => result?,
+            None => return Ok(FieldInfo {
+                span: Spanned::span(&id),
+                name: id.clone().into(),
+                nested: false,
+                honeypot: false,
+                max_index: None,
+                duplicates: None,
+            }),
+        };
+
+        if let (false, Some(max_index)) = (form.nested, &form.max_index) {
+            return Err(max_index.span.error("`max_index` requires `nested`"));
+        }
+
+        if form.nested && form.honeypot {
+            return Err(Spanned::span(&id).error("`honeypot` cannot be combined with `nested`"));
+        }
+
+        let (span, name) = match form.field {
+            Some(field) => (field.span, field.name),
+            None => (Spanned::span(&id), id.clone().into()),
+        };
+
+        let max_index = form.max_index.map(|m| m.value);
+        let duplicates = form.duplicates;
+        let (nested, honeypot) = (form.nested, form.honeypot);
+        Ok(FieldInfo { span, name, nested, honeypot, max_index, duplicates })
+    }
+}
+
+/// The names of the honeypot fields of the struct deriving `FromForm`.
+fn honeypot_names(input: &syn::DeriveInput) -> Result<Vec<String>> {
+    let fields = match input.data {
+        syn::Data::Struct(ref data) => &data.fields,
+        _ => return Ok(vec![]),
+    };
+
+    let mut names = vec![];
+    for field in fields.iter().filter(|field| field.ident.is_some()) {
+        let info = FieldInfo::from_field(field)?;
+        if info.honeypot {
+            names.push(info.name.name().to_string());
+        }
+    }
+
+    Ok(names)
 }
 
 pub struct FormField {
@@ -13,6 +80,23 @@ pub struct FormField {
     pub name: NameSource,
 }
 
+/// A `Duplicates` policy overriding the configured one for a single field.
+pub struct Duplicates(TokenStream);
+
+impl FromMeta for Duplicates {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let variant = match &*String::from_meta(meta)? {
+            "first" => quote!(First),
+            "last" => quote!(Last),
+            "reject" => quote!(Reject),
+            _ => return Err(meta.value_span().error("invalid duplicates policy")
+                .help("policy must be one of: `first`, `last`, `reject`")),
+        };
+
+        Ok(Duplicates(quote!(::rocket::request::Duplicates::#variant)))
+    }
+}
+
 fn is_valid_field_name(s: &str) -> bool {
     // The HTML5 spec (4.10.18.1) says 'isindex' is not allowed.
     if s == "isindex" || s.is_empty() {
@@ -42,12 +126,7 @@ fn validate_struct(_: &DeriveGenerator, data: Struct<'_>) -> Result<()> {
 
     let mut names = ::std::collections::HashMap::new();
     for field in data.fields().iter() {
-        let id = field.ident.as_ref().expect("named field");
-        let field = match Form::from_attrs("form", &field.attrs) {
-            Some(result) => result?.field,
-            None => FormField { span: Spanned::span(&id), name: id.clone().into() }
-        };
-
+        let field = FieldInfo::from_field(&field)?;
         if let Some(span) = names.get(&field.name) {
             return Err(field.span.error("duplicate field name")
                        .span_note(*span, "previous definition here"));
@@ -73,24 +152,70 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
             _ => Ok(())
         })
         .validate_struct(validate_struct)
-        .function(|_, inner| quote! {
-            type Error = ::rocket::request::FormParseError<'__f>;
-
-            fn from_form(
-                __items: &mut ::rocket::request::FormItems<'__f>,
-                __strict: bool,
-            ) -> ::std::result::Result<Self, Self::Error> {
-                #inner
+        .function(|gen, inner| {
+            // Errors in field attributes are reported by `validate_struct`.
+            let honeypots = honeypot_names(&gen.input).unwrap_or_default();
+            quote! {
+                type Error = ::rocket::request::FormParseError<'__f>;
+
+                const HONEYPOTS: &'static [&'static str] = &[#(#honeypots),*];
+
+                fn from_form(
+                    __items: &mut ::rocket::request::FormItems<'__f>,
+                    __strict: bool,
+                ) -> ::std::result::Result<Self, Self::Error> {
+                    Self::from_form_all(__items, __strict)
+                        .map_err(|mut __errors| __errors.remove(0))
+                }
+
+                fn from_form_all(
+                    __items: &mut ::rocket::request::FormItems<'__f>,
+                    __strict: bool,
+                ) -> ::std::result::Result<Self, ::std::vec::Vec<Self::Error>> {
+                    #inner
+                }
             }
         })
         .try_map_fields(move |_, fields| {
-            define_vars_and_mods!(_None, _Some, _Ok, _Err);
+            define_vars_and_mods!(_None, _Some, _Ok, _Err, _Vec, _Option);
             let (constructors, matchers, builders) = fields.iter().map(|field| {
                 let (ident, span) = (&field.ident, field.span());
-                let default_name = NameSource::from(ident.clone().expect("named"));
-                let name = Form::from_attrs("form", &field.attrs)
-                    .map(|result| result.map(|form| form.field.name))
-                    .unwrap_or_else(|| Ok(default_name))?;
+                let info = FieldInfo::from_field(&field)?;
+                let name = info.name.name();
+                let duplicates = match info.duplicates {
+                    Some(Duplicates(ref policy)) => policy.clone(),
+                    None => quote!(__duplicates),
+                };
+
+                if info.nested {
+                    let max_index = match info.max_index {
+                        Some(max_index) => quote!(#max_index),
+                        None => quote!(::rocket::request::IndexedItems::DEFAULT_MAX_INDEX),
+                    };
+
+                    let constructor = quote_spanned! { span =>
+                        let mut #ident = ::rocket::request::IndexedItems::new(#max_index)
+                            .with_duplicates(#duplicates);
+                    };
+
+                    let taker = quote_spanned! { span =>
+                        if #ident.push(#name, __item) {
+                            continue;
+                        }
+                    };
+
+                    let builder = quote_spanned! { span =>
+                        let #ident = match #ident.finish(#name, __strict) {
+                            #_Ok(__values) => #_Some(__values),
+                            #_Err(__nested_errors) => {
+                                __errors.extend(__nested_errors);
+                                #_None
+                            }
+                        };
+                    };
+
+                    return Ok((constructor, (quote!(), taker), builder));
+                }
 
                 let ty = field.ty.with_stripped_lifetimes();
                 let ty = quote_spanned! {
@@ -99,34 +224,87 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
 
                 let constructor = quote_spanned!(span => let mut #ident = #_None;);
 
-                let name = name.name();
+                // A honeypot must be submitted exactly once, with no value.
+                if info.honeypot {
+                    let matcher = quote_spanned! { span =>
+                        #name => match (#ident.is_some(), __v.as_str().is_empty()) {
+                            (false, true) => match #ty::from_form_value(__v) {
+                                #_Ok(__value) => #ident = #_Some(__value),
+                                #_Err(_) => __errors.push(#form_error::BadValue(__k, __v)),
+                            },
+                            _ => __errors.push(#form_error::Honeypot(#name.into())),
+                        },
+                    };
+
+                    let builder = quote_spanned! { span =>
+                        if #ident.is_none() && !__errors.iter().any(|__e| match __e {
+                            #form_error::BadValue(__k, _) => __k.as_str() == #name,
+                            #form_error::Honeypot(__k) => __k.as_str() == #name,
+                            _ => false,
+                        }) {
+                            __errors.push(#form_error::Honeypot(#name.into()));
+                        }
+                    };
+
+                    return Ok((constructor, (matcher, quote!()), builder));
+                }
+
                 let matcher = quote_spanned! { span =>
-                    #name => { #ident = #_Some(#ty::from_form_value(__v)
-                                .map_err(|_| #form_error::BadValue(__k, __v))?); },
+                    #name => match (#ident.is_some(), #duplicates) {
+                        (true, ::rocket::request::Duplicates::First) => { /* keep first */ },
+                        (true, ::rocket::request::Duplicates::Reject) => {
+                            __errors.push(#form_error::Duplicate(__k, __v));
+                        },
+                        _ => match #ty::from_form_value(__v) {
+                            #_Ok(__value) => #ident = #_Some(__value),
+                            #_Err(_) => __errors.push(#form_error::BadValue(__k, __v)),
+                        },
+                    },
                 };
 
+                // A field with an invalid value isn't also reported missing.
                 let builder = quote_spanned! { span =>
-                    #ident: #ident.or_else(#ty::default)
-                        .ok_or_else(|| #form_error::Missing(#name.into()))?,
+                    let #ident = #ident.or_else(#ty::default);
+                    if #ident.is_none() && !__errors.iter().any(|__e| match __e {
+                        #form_error::BadValue(__k, _) => __k.as_str() == #name,
+                        _ => false,
+                    }) {
+                        __errors.push(#form_error::Missing(#name.into()));
+                    }
                 };
 
-                Ok((constructor, matcher, builder))
+                Ok((constructor, (matcher, quote!()), builder))
             }).collect::<Result<Vec<_>>>()?.into_iter().split3();
 
+            let (matchers, takers): (Vec<_>, Vec<_>) = matchers.into_iter().unzip();
+            let idents = fields.iter().map(|field| &field.ident);
+            let idents2 = idents.clone();
             Ok(quote! {
+                let mut __errors = #_Vec::new();
+                #[allow(unused_variables)]
+                let __duplicates = __items.duplicates();
                 #(#constructors)*
 
-                for (__k, __v) in __items.map(|item| item.key_value()) {
+                for __item in __items {
+                    let (__k, __v) = __item.key_value();
+                    #(#takers)*
+
                     match __k.as_str() {
                         #(#matchers)*
                         _ if __strict && __k != "_method" => {
-                            return #_Err(#form_error::Unknown(__k, __v));
+                            __errors.push(#form_error::Unknown(__k, __v));
                         }
                         _ => { /* lenient or "method"; let it pass */ }
                     }
                 }
 
-                #_Ok(Self { #(#builders)* })
+                #(#builders)*
+
+                if !__errors.is_empty() {
+                    return #_Err(__errors);
+                }
+
+                #_Ok(Self { #(#idents: #_Option::unwrap(#idents2),)* })
             })
         })
         .to_tokens2()
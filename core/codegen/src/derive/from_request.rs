@@ -0,0 +1,108 @@
+use devise::ext::SpanDiagnosticExt;
+use devise::{syn, Spanned, SpanWrapped, Result, FromMeta, Diagnostic};
+
+use crate::proc_macro2::TokenStream;
+use crate::http_codegen::Status;
+
+#[derive(Default, FromMeta)]
+struct FieldAttr {
+    forward_on_error: bool,
+    status: Option<SpanWrapped<Status>>,
+}
+
+fn _derive_from_request(input: proc_macro::TokenStream) -> Result<TokenStream> {
+    define_vars_and_mods!(request, Outcome);
+
+    let input: syn::DeriveInput = syn::parse(input).map_err(Diagnostic::from)?;
+    let fields = match input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(ref f), .. }) => &f.named,
+        _ => return Err(input.span().error("only structs with named fields are supported")),
+    };
+
+    if fields.is_empty() {
+        return Err(input.span().error("at least one field is required"));
+    }
+
+    if let Some(param) = input.generics.type_params().next() {
+        return Err(param.span().error("type generics are not supported"));
+    }
+
+    if let Some(param) = input.generics.const_params().next() {
+        return Err(param.span().error("const generics are not supported"));
+    }
+
+    let lifetimes: Vec<_> = input.generics.lifetimes().map(|l| &l.lifetime).collect();
+    let (a, r) = match lifetimes.as_slice() {
+        [] => (quote!('__a), quote!('__r)),
+        [a] => (quote!(#a), quote!('__r)),
+        [a, r] => (quote!(#a), quote!(#r)),
+        [_, _, extra, ..] => return Err(extra.span()
+            .error("at most two lifetimes are supported")
+            .help("the first lifetime is the borrow of the request, `'a`, \
+                and the second is the request's lifetime, `'r`")),
+    };
+
+    let guards = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named field");
+        let (ty, span) = (&field.ty, field.span());
+        let name = ident.to_string();
+        let attr = FieldAttr::from_attrs("from_request", &field.attrs)
+            .unwrap_or_else(|| Ok(Default::default()))?;
+
+        if let (true, Some(status)) = (attr.forward_on_error, &attr.status) {
+            return Err(status.span.error("`status` has no effect with `forward_on_error`"));
+        }
+
+        let on_failure = match (attr.forward_on_error, attr.status) {
+            (true, _) => quote_spanned!(span => #Outcome::Failure(_) => {
+                return #Outcome::Forward(())
+            }),
+            (false, Some(status)) => quote_spanned!(span => #Outcome::Failure((_, __e)) => {
+                let __error = #request::GuardError::new(#name, &__e);
+                return #Outcome::Failure((#status, __error))
+            }),
+            (false, None) => quote_spanned!(span => #Outcome::Failure((__s, __e)) => {
+                let __error = #request::GuardError::new(#name, &__e);
+                return #Outcome::Failure((__s, __error))
+            }),
+        };
+
+        Ok(quote_spanned! { span =>
+            #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
+            let #ident = match <#ty as #request::FromRequest<#a, #r>>::from_request(__req).await {
+                #Outcome::Success(__v) => __v,
+                #Outcome::Forward(_) => return #Outcome::Forward(()),
+                #on_failure
+            };
+        })
+    }).collect::<Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let idents = fields.iter().map(|field| &field.ident);
+    let where_clause = &input.generics.where_clause;
+    let (impl_generics, ty_generics) = match lifetimes.len() {
+        0 => (quote!(<#a, #r>), quote!()),
+        1 => (quote!(<#a, #r>), quote!(<#a>)),
+        _ => (quote!(<#a, #r>), quote!(<#a, #r>)),
+    };
+
+    Ok(quote! {
+        #[::rocket::async_trait]
+        impl #impl_generics #request::FromRequest<#a, #r> for #name #ty_generics
+            #where_clause
+        {
+            type Error = #request::GuardError;
+
+            async fn from_request(
+                __req: &#a #request::Request<#r>
+            ) -> #request::Outcome<Self, Self::Error> {
+                #(#guards)*
+                #Outcome::Success(#name { #(#idents),* })
+            }
+        }
+    })
+}
+
+pub fn derive_from_request(input: proc_macro::TokenStream) -> TokenStream {
+    _derive_from_request(input).unwrap_or_else(|d| d.emit_as_item_tokens())
+}
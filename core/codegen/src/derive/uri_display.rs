@@ -57,12 +57,17 @@ pub fn derive_uri_display_query(input: proc_macro::TokenStream) -> TokenStream {
             let span = field.span().into();
             let accessor = field.accessor();
             let tokens = if let Some(ref ident) = field.ident {
-                let name_source = Form::from_attrs("form", &field.attrs)
-                    .map(|result| result.map(|form| form.field.name))
-                    .unwrap_or_else(|| Ok(ident.clone().into()))?;
+                let form = Form::from_attrs("form", &field.attrs).transpose()?;
+                let honeypot = form.as_ref().map_or(false, |form| form.honeypot);
+                let name_source = form.and_then(|form| form.field.map(|f| f.name))
+                    .unwrap_or_else(|| ident.clone().into());
 
+                // Honeypots are always emitted, and always empty.
                 let name = name_source.name();
-                quote_spanned!(span => f.write_named_value(#name, &#accessor)?;)
+                match honeypot {
+                    true => quote_spanned!(span => f.write_named_value(#name, "")?;),
+                    false => quote_spanned!(span => f.write_named_value(#name, &#accessor)?;),
+                }
             } else {
                 quote_spanned!(span => f.write_value(&#accessor)?;)
             };
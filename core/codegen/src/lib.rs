@@ -212,6 +212,9 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'version' '=' INTEGER
+        ///            | 'extensions' '=' '"' PATH '"'
+        ///            | 'blocking' ('=' BOOL)?
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// MULTI_PARAM := '<' IDENT '..>'
@@ -220,7 +223,9 @@ macro_rules! route_attribute {
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
         ///
         /// INTEGER := unsigned integer, as defined by Rust
+        /// BOOL := `true` or `false`
         /// IDENT := valid identifier, as defined by Rust, except `_`
+        /// PATH := path to a `fn(&mut Extensions)`, as defined by Rust
         /// ```
         ///
         /// The generic route attribute is defined as:
@@ -306,20 +311,32 @@ macro_rules! route_attribute {
         ///            `Failure`. See [`FromTransformedData` Outcomes] for further detail.
         ///
         ///      If all validation succeeds, the decorated function is called.
-        ///      The returned value is used to generate a [`Response`] via the
-        ///      type's [`Responder`] implementation.
+        ///      If the route is declared `blocking`, the function, which must
+        ///      not be `async`, is called on the blocking thread pool via
+        ///      [`handler::blocking()`] so that it does not stall the async
+        ///      workers. Its arguments and return type must thus be `Send` and
+        ///      `'static`, ruling out borrowing guards such as `&str` or
+        ///      `State`. The returned value is used to generate a [`Response`]
+        ///      via the type's [`Responder`] implementation.
         ///
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, and API version from the route attribute. The handler
+        ///      is set to the generated handler. A route with a `version` only
+        ///      matches requests for that version of the API; see
+        ///      [`ApiVersion`]. If an `extensions` function is named, it is
+        ///      called with the route's [`Extensions`] when the route is
+        ///      created.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
         ///
         /// [`Handler`]: ../rocket/trait.Handler.html
+        /// [`handler::blocking()`]: ../rocket/handler/fn.blocking.html
+        /// [`ApiVersion`]: ../rocket/request/struct.ApiVersion.html
+        /// [`Extensions`]: ../rocket/struct.Extensions.html
         /// [`routes!`]: macro.routes.html
         /// [`uri!`]: macro.uri.html
         /// [`Origin`]: ../rocket/http/uri/struct.Origin.html
@@ -343,6 +360,86 @@ route_attribute!(head => Method::Head);
 route_attribute!(patch => Method::Patch);
 route_attribute!(options => Method::Options);
 
+/// Attribute to apply a common prefix, format, and request guards to all of
+/// the routes in a module.
+///
+/// This attribute can only be applied to inline modules:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::request::{self, Request, FromRequest};
+/// # struct ApiKey;
+/// # #[rocket::async_trait]
+/// # impl<'a, 'r> FromRequest<'a, 'r> for ApiKey {
+/// #     type Error = ();
+/// #     async fn from_request(_: &'a Request<'r>) -> request::Outcome<Self, ()> {
+/// #         request::Outcome::Success(ApiKey)
+/// #     }
+/// # }
+/// #[route_group(prefix = "/api/v1", format = "json", guards(super::ApiKey))]
+/// mod api {
+///     #[get("/")]
+///     pub fn index() -> &'static str { "{ \"version\": 1 }" }
+///
+///     #[post("/items", data = "<item>")]
+///     pub fn create(item: String) -> String { item }
+///
+///     #[get("/items/<id>", format = "plain")]
+///     pub fn item(id: usize) -> String { id.to_string() }
+/// }
+///
+/// fn main() {
+///     let routes = api::routes();
+///     assert_eq!(routes[0].uri.path(), "/api/v1");
+///     assert_eq!(routes[1].uri.path(), "/api/v1/items");
+///     assert_eq!(routes[2].format, Some(rocket::http::MediaType::Plain));
+///
+///     let rocket = rocket::ignite().mount("/", routes);
+/// }
+/// ```
+///
+/// # Grammar
+///
+/// The grammar for the `#[route_group]` attribute is:
+///
+/// ```text
+/// route_group := group_param (',' group_param)*
+///
+/// group_param := 'prefix' '=' STRING
+///              | 'format' '=' STRING
+///              | 'guards' '(' TYPE (',' TYPE)* ')'
+///
+/// STRING := a static origin URI path, for `prefix`, or a media type, for
+///           `format`, as accepted by route attributes
+/// TYPE := a request guard type
+/// ```
+///
+/// # Semantics
+///
+/// The attribute rewrites the route attributes (`#[route]`, `#[get]`, and so
+/// on) on the functions in the module before they are expanded:
+///
+///   * The `prefix`, if any, is prepended to each route's path. A route path of
+///     `/` becomes the prefix itself, so `#[get("/")]` above is `GET /api/v1`.
+///
+///   * The `format`, if any, is set on each route that doesn't already declare
+///     a `format`.
+///
+///   * Each type in `guards`, if any, is added to each route's handler as an
+///     additional request guard parameter. The guards are thus evaluated for
+///     every route in the group, but their values are not accessible to the
+///     handlers. Note that this changes the handlers' signatures and that the
+///     types are resolved inside the module.
+///
+/// Additionally, the attribute generates a `pub fn routes() -> Vec<Route>` in
+/// the module returning every route in the group, ready to be mounted. Only
+/// routes declared directly in the module, not in nested modules, are part of
+/// the group.
+#[proc_macro_attribute]
+pub fn route_group(args: TokenStream, input: TokenStream) -> TokenStream {
+    emit!(attribute::group::route_group_attribute(args, input))
+}
+
 /// Attribute to generate a [`Catcher`] and associated metadata.
 ///
 /// This attribute can only be applied to free functions:
@@ -412,19 +509,107 @@ pub fn catch(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::catch::catch_attribute(args, input))
 }
 
-/// FIXME: Document.
+/// Generates a `#[test]` that runs the `async` function it's applied to on an
+/// async runtime.
+///
+/// ```rust
+/// #[rocket::async_test]
+/// async fn test_sleep() {
+///     rocket::tokio::task::yield_now().await;
+/// }
+/// ```
+///
+/// The attribute optionally accepts a `client = EXPR` argument, where `EXPR`
+/// evaluates to a [`Rocket`] instance. When present, the function must take
+/// exactly one argument of type [`local::asynchronous::Client`]. A tracked
+/// client is constructed from the result of `EXPR` for each test and passed as
+/// the argument; the test panics if the client can't be constructed:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::local::asynchronous::Client;
+///
+/// #[get("/")]
+/// fn index() -> &'static str { "Hello, world!" }
+///
+/// fn my_rocket() -> rocket::Rocket {
+///     rocket::ignite().mount("/", routes![index])
+/// }
+///
+/// #[rocket::async_test(client = my_rocket())]
+/// async fn test_index(client: Client) {
+///     let response = client.get("/").dispatch().await;
+///     assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+/// }
+/// ```
+///
+/// [`Rocket`]: ../rocket/struct.Rocket.html
+/// [`local::asynchronous::Client`]: ../rocket/local/asynchronous/struct.Client.html
 #[proc_macro_attribute]
 pub fn async_test(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::async_entry::async_test_attribute(args, input))
 }
 
-/// FIXME: Document.
+/// Sets up an async runtime and runs the `async` function it's applied to,
+/// typically `main`, to completion on it.
+///
+/// ```rust,no_run
+/// #[rocket::main]
+/// async fn main() {
+///     let _ = rocket::ignite().launch().await;
+/// }
+/// ```
+///
+/// By default, the runtime is multi-threaded with as many worker threads as
+/// Rocket's configured `workers`. The runtime can be configured with the
+/// following arguments:
+///
+///   * `flavor = "multi_thread"` or `flavor = "current_thread"`: selects a
+///     multi-threaded runtime, the default, or a single-threaded runtime.
+///   * `worker_threads = N`: sets the number of worker threads of a
+///     multi-threaded runtime to `N`, overriding the configured `workers`.
+///
+/// If the function returns a `Result`, or an alias of it such as `io::Result`,
+/// the generated function returns nothing. Instead, an `Err` is printed to
+/// `stderr` using its `Display` implementation, as in `Error: {}`, and the
+/// process exits with a status code of `1`:
+///
+/// ```rust,no_run
+/// #[rocket::main(flavor = "current_thread")]
+/// async fn main() -> Result<(), rocket::error::Error> {
+///     rocket::ignite().launch().await
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::async_entry::main_attribute(args, input))
 }
 
-/// FIXME: Document.
+/// Generates a `main` function that launches the `Rocket` instance returned by
+/// the function it's applied to.
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::ignite()
+/// }
+/// ```
+///
+/// The function may be `async`, and its return type may be `_`, which is
+/// inferred as `Rocket`. The attribute accepts the same runtime arguments as
+/// [`macro@main`]. The function may also return a `Result` whose `Ok` value is
+/// a `Rocket`; an `Err` is printed to `stderr` using its `Display`
+/// implementation and the process exits with a status code of `1`:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// #[launch(worker_threads = 4)]
+/// fn rocket() -> Result<rocket::Rocket, std::io::Error> {
+///     let _root = std::env::current_dir()?;
+///     Ok(rocket::ignite())
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn launch(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::async_entry::launch_attribute(args, input))
@@ -522,9 +707,17 @@ pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
 /// The derive accepts one field attribute: `form`, with the following syntax:
 ///
 /// ```text
-/// form := 'field' '=' '"' IDENT '"'
+/// form := param (',' param)*
+///
+/// param := 'field' '=' '"' IDENT '"'
+///        | 'nested'
+///        | 'max_index' '=' INTEGER
+///        | 'duplicates' '=' '"' POLICY '"'
+///        | 'honeypot'
 ///
 /// IDENT := valid identifier, as defined by Rust
+/// INTEGER := unsigned integer, as defined by Rust
+/// POLICY := 'first' | 'last' | 'reject'
 /// ```
 ///
 /// When applied, the attribute looks as follows:
@@ -546,15 +739,150 @@ pub fn derive_from_form_value(input: TokenStream) -> TokenStream {
 /// value of the `MyStruct::other` struct field will be parsed from the incoming
 /// form's `renamed_field` field.
 ///
+/// The `nested` parameter parses a field of type `Vec<T>`, where `T`
+/// implements `FromForm`, from indexed form fields such as
+/// `items[0].name=x&items[0].qty=2&items[1].name=y`. Indices may be sparse and
+/// out of order; values are ordered by index. Indices greater than `max_index`,
+/// which defaults to `1024`, are rejected. See [`IndexedItems`] for details.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromForm)]
+/// struct Item {
+///     name: String,
+///     qty: usize,
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Order {
+///     #[form(nested, max_index = 99)]
+///     items: Vec<Item>,
+/// }
+/// ```
+///
+/// The `duplicates` parameter overrides, for a single field, the configured
+/// [`Duplicates`] policy that determines which value is used, or whether an
+/// error is returned, when the field appears more than once in a form.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromForm)]
+/// struct Transfer {
+///     #[form(duplicates = "reject")]
+///     amount: usize,
+/// }
+/// ```
+///
+/// The `honeypot` parameter marks a field as a honeypot: a field that is
+/// hidden from humans, who leave it empty, but that spam bots fill in. Parsing
+/// fails with [`FormParseError::Honeypot`] unless the field is submitted
+/// exactly once and with an empty value, which is then parsed as usual. The
+/// field's name is listed in [`FromForm::HONEYPOTS`], which [`Contextual`]
+/// exposes to templates, and the [`UriDisplayQuery`] derive always emits the
+/// field with an empty value so that URIs built by `uri!` parse. A honeypot
+/// cannot be `nested`.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #
+/// #[derive(FromForm)]
+/// struct Comment {
+///     text: String,
+///     #[form(honeypot)]
+///     website: String,
+/// }
+/// ```
+///
 /// [`FromForm`]: ../rocket/request/trait.FromForm.html
+/// [`IndexedItems`]: ../rocket/request/struct.IndexedItems.html
+/// [`Duplicates`]: ../rocket/request/enum.Duplicates.html
 /// [`FromFormValue`]: ../rocket/request/trait.FromFormValue.html
 /// [`FormParseError`]: ../rocket/request/enum.FormParseError.html
+/// [`FormParseError::Honeypot`]: ../rocket/request/enum.FormParseError.html#variant.Honeypot
+/// [`FromForm::HONEYPOTS`]: ../rocket/request/trait.FromForm.html#associatedconstant.HONEYPOTS
+/// [`Contextual`]: ../rocket/request/struct.Contextual.html
+/// [`UriDisplayQuery`]: derive.UriDisplayQuery.html
 /// [`FromForm::Error`]: ../rocket/request/trait.FromForm.html#associatedtype.Error
 #[proc_macro_derive(FromForm, attributes(form))]
 pub fn derive_from_form(input: TokenStream) -> TokenStream {
     emit!(derive::from_form::derive_from_form(input))
 }
 
+/// Derive for the [`FromRequest`] trait.
+///
+/// The [`FromRequest`] derive can be applied to structures with named fields
+/// where the type of every field implements [`FromRequest`]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::Route;
+/// # use rocket::http::{Method, CookieJar};
+/// #
+/// #[derive(FromRequest)]
+/// struct Context<'a, 'r> {
+///     method: Method,
+///     route: &'r Route,
+///     cookies: &'a CookieJar<'r>,
+/// }
+/// ```
+///
+/// The generated implementation runs the guard of each field, in declaration
+/// order. If every guard succeeds, the structure is constructed from the
+/// values. Otherwise, the outcome of the first guard that doesn't succeed
+/// determines the outcome of the derived guard: a forward is forwarded, and a
+/// failure fails with the guard's status and a [`GuardError`] naming the field
+/// and carrying the `Debug` representation of the guard's error.
+///
+/// The structure may have at most two lifetime parameters and no type or const
+/// parameters. The first lifetime, if any, is the lifetime of the borrow of the
+/// request, `'a` in `FromRequest<'a, 'r>`, while the second is the lifetime of
+/// the request itself, `'r`.
+///
+/// Fields may be decorated with the `from_request` attribute, which has the
+/// following grammar:
+///
+/// ```text
+/// from_request := 'forward_on_error'
+///               | 'status' '=' STATUS
+///
+/// STATUS := unsigned integer >= 100 and < 600
+/// ```
+///
+/// When `forward_on_error` is set, a failure of the field's guard forwards
+/// instead of failing. When `status` is set, a failure of the field's guard
+/// fails with the given status instead of the guard's own:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # use rocket::http::CookieJar;
+/// # use rocket::request::{self, Request, FromRequest};
+/// # struct ApiKey;
+/// # #[rocket::async_trait]
+/// # impl<'a, 'r> FromRequest<'a, 'r> for ApiKey {
+/// #     type Error = ();
+/// #     async fn from_request(_: &'a Request<'r>) -> request::Outcome<Self, ()> {
+/// #         request::Outcome::Success(ApiKey)
+/// #     }
+/// # }
+/// #
+/// #[derive(FromRequest)]
+/// struct Admin<'a, 'r> {
+///     #[from_request(status = 401)]
+///     key: ApiKey,
+///     #[from_request(forward_on_error)]
+///     cookies: &'a CookieJar<'r>,
+/// }
+/// ```
+///
+/// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
+/// [`GuardError`]: ../rocket/request/struct.GuardError.html
+#[proc_macro_derive(FromRequest, attributes(from_request))]
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    emit!(derive::from_request::derive_from_request(input))
+}
+
 /// Derive for the [`Responder`] trait.
 ///
 /// The [`Responder`] derive can be applied to enums and structs with named
@@ -719,9 +1047,17 @@ pub fn derive_responder(input: TokenStream) -> TokenStream {
 /// The derive accepts one field attribute: `form`, with the following syntax:
 ///
 /// ```text
-/// form := 'field' '=' '"' IDENT '"'
+/// form := param (',' param)*
+///
+/// param := 'field' '=' '"' IDENT '"'
+///        | 'nested'
+///        | 'max_index' '=' INTEGER
+///        | 'duplicates' '=' '"' POLICY '"'
+///        | 'honeypot'
 ///
 /// IDENT := valid identifier, as defined by Rust
+/// INTEGER := unsigned integer, as defined by Rust
+/// POLICY := 'first' | 'last' | 'reject'
 /// ```
 ///
 /// When applied, the attribute looks as follows:
@@ -745,6 +1081,10 @@ pub fn derive_responder(input: TokenStream) -> TokenStream {
 /// the example above, the field `MyStruct::kind` is rendered with a name of
 /// `type`.
 ///
+/// A field marked `honeypot` is always rendered with an empty value,
+/// regardless of its actual value, so that the rendered query parses as the
+/// `FromForm` type it was derived for.
+///
 /// [`UriDisplay<Query>`]: ../rocket/http/uri/trait.UriDisplay.html
 /// [`Formatter::write_named_value()`]: ../rocket/http/uri/struct.Formatter.html#method.write_named_value
 /// [`Formatter::write_value()`]: ../rocket/http/uri/struct.Formatter.html#method.write_value
@@ -951,6 +1291,10 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// let option: Option<u8> = None;
 /// let mike = uri!(person: name = "Mike", age = option);
 /// assert_eq!(mike.to_string(), "/person/Mike");
+///
+/// // as an absolute URI with a base
+/// let mike = uri!(base = "https://rocket.rs", person: "Mike", _);
+/// assert_eq!(mike.to_string(), "https://rocket.rs/person/Mike");
 /// ```
 ///
 /// ## Grammar
@@ -958,7 +1302,7 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// The grammar for the `uri!` macro is:
 ///
 /// ```text
-/// uri := (mount ',')? PATH (':' params)?
+/// uri := ('base' '=' EXPR ',')? (mount ',')? PATH (':' params)?
 ///
 /// mount = STRING
 /// params := unnamed | named
@@ -991,6 +1335,17 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// If a mount-point is provided, the mount-point is prepended to the route's
 /// URI.
 ///
+/// If a `base` is provided, `uri!` instead returns an [`Absolute`] URI: the
+/// origin URI described above joined to the scheme, authority, and path, if
+/// any, of the base. The base may be any value that implements [`UriBase`],
+/// including a `&str` (which must be a valid absolute URI), an `Absolute`, a
+/// `Config`, or a `&Request`. In the latter case, the host is taken from the
+/// request's `Host` header, falling back to the configured address and port.
+/// The `X-Forwarded-Proto`, `X-Forwarded-Host`, and `X-Forwarded-Port` headers
+/// take precedence only when the request comes from one of the configured
+/// `trusted_proxies`; otherwise they are ignored. See
+/// [`Request::base_url()`].
+///
 /// ### Conversion
 ///
 /// The [`FromUriParam`] trait is used to typecheck and perform a conversion for
@@ -1012,8 +1367,22 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// parameters are not interpolated into the resulting `Origin`. Path parameters
 /// are not ignorable.
 ///
+/// ### Launch Checks
+///
+/// When `rocket`'s `uri-checks` feature is enabled, every `uri!` invocation
+/// records the route it targets and its mount-point, or `/` if none is given.
+/// Otherwise, nothing is recorded. When the `check_uris` configuration
+/// parameter is `true`, launch fails with an `ErrorKind::DanglingUris` error if
+/// any invocation targets a route that the application mounts, but not at the
+/// invocation's mount-point, such as after a route is moved. Invocations are
+/// collected from the entire binary, which may contain other applications, so
+/// those targeting routes the application doesn't mount at all are ignored.
+///
 /// [`Uri`]: ../rocket/http/uri/enum.Uri.html
 /// [`Origin`]: ../rocket/http/uri/struct.Origin.html
+/// [`Absolute`]: ../rocket/http/uri/struct.Absolute.html
+/// [`UriBase`]: ../rocket/http/uri/trait.UriBase.html
+/// [`Request::base_url()`]: ../rocket/struct.Request.html#method.base_url
 /// [`FromUriParam`]: ../rocket/http/uri/trait.FromUriParam.html
 /// [`UriDisplay`]: ../rocket/http/uri/trait.UriDisplay.html
 /// [`Ignorable`]: ../rocket/http/uri/trait.Ignorable.html
@@ -92,3 +92,57 @@ async fn main() -> Result<(), String> {
     let result = rocket::ignite().launch().await;
     result.map_err(|e| e.to_string())
 }
+
+mod h {
+    // main with a configured runtime.
+    #[rocket::main(flavor = "current_thread")]
+    async fn main() { }
+}
+
+mod i {
+    // main with a configured number of workers and an error return.
+    #[rocket::main(flavor = "multi_thread", worker_threads = 2)]
+    async fn main() -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn use_it() {
+        let () = main();
+    }
+}
+
+mod j {
+    // launch with a configured runtime and a fallible return.
+    #[rocket::launch(flavor = "current_thread")]
+    fn rocket() -> Result<rocket::Rocket, String> {
+        Ok(rocket::ignite())
+    }
+
+    fn use_it() {
+        let rocket: Result<rocket::Rocket, String> = rocket();
+    }
+}
+
+mod k {
+    use rocket::local::asynchronous::Client;
+
+    #[rocket::get("/")]
+    fn index() -> &'static str { "index" }
+
+    fn rocket() -> rocket::Rocket {
+        rocket::ignite().mount("/", rocket::routes![index])
+    }
+
+    // async test with a client fixture.
+    #[rocket::async_test(client = rocket())]
+    async fn test_client_fixture(client: Client) {
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "index");
+    }
+
+    // async test with a client fixture and a pattern.
+    #[rocket::async_test(client = rocket::ignite())]
+    async fn test_client_fixture_pattern(ref client: Client) {
+        assert!(client.get("/").dispatch().await.into_string().await.is_some());
+    }
+}
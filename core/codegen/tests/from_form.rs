@@ -1,6 +1,6 @@
 #[macro_use] extern crate rocket;
 
-use rocket::request::{FromForm, FormItems, FormParseError};
+use rocket::request::{FromForm, FormItems, FormParseError, Duplicates};
 use rocket::http::RawStr;
 
 fn parse<'f, T>(string: &'f str, strict: bool) -> Result<T, FormParseError<'f>>
@@ -318,6 +318,41 @@ fn form_errors() {
     assert_eq!(form, Err(FormParseError::Missing("other".into())));
 }
 
+#[derive(Debug, PartialEq, FromForm)]
+struct HoneypotForm {
+    text: String,
+    #[form(honeypot, field = "url")]
+    website: String,
+}
+
+#[test]
+fn honeypot_form() {
+    assert_eq!(HoneypotForm::HONEYPOTS, &["url"]);
+    assert_eq!(WhoopsForm::HONEYPOTS, &[] as &[&str]);
+
+    let form: Result<HoneypotForm, _> = strict("text=hi&url=");
+    assert_eq!(form, Ok(HoneypotForm { text: "hi".into(), website: "".into() }));
+
+    // The honeypot must be present, empty, and submitted once.
+    let form: Result<HoneypotForm, _> = strict("text=hi");
+    assert_eq!(form, Err(FormParseError::Honeypot("url".into())));
+
+    let form: Result<HoneypotForm, _> = strict("text=hi&url=spam.example");
+    assert_eq!(form, Err(FormParseError::Honeypot("url".into())));
+
+    let form: Result<HoneypotForm, _> = lenient("text=hi&url=&url=");
+    assert_eq!(form, Err(FormParseError::Honeypot("url".into())));
+
+    // A filled in honeypot is reported once, in incoming order.
+    let form: Result<HoneypotForm, _> = strict("url=x&text=hi&extra=1");
+    assert_eq!(form, Err(FormParseError::Honeypot("url".into())));
+    let errors = HoneypotForm::from_form_all(&mut FormItems::from("url=x"), true).unwrap_err();
+    assert_eq!(errors, vec![
+        FormParseError::Honeypot("url".into()),
+        FormParseError::Missing("text".into()),
+    ]);
+}
+
 #[derive(Debug, PartialEq, FromForm)]
 struct RawIdentForm {
     r#type: String,
@@ -328,3 +363,84 @@ fn raw_ident_form() {
     let form: Result<RawIdentForm, _> = strict("type=a");
     assert_eq!(form, Ok(RawIdentForm { r#type: "a".into() }));
 }
+
+#[derive(Debug, PartialEq, FromForm)]
+struct LineItem {
+    name: String,
+    qty: usize,
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Order {
+    customer: String,
+    #[form(nested, max_index = 9)]
+    items: Vec<LineItem>,
+    #[form(field = "notes", nested)]
+    remarks: Vec<LineItem>,
+}
+
+#[test]
+fn nested_indexed_fields() {
+    let item = |name: &str, qty| LineItem { name: name.into(), qty };
+
+    let form: Result<Order, _> = strict("customer=bob&items[0].name=x&items[0].qty=2&items[1].qty=1&items[1].name=y");
+    assert_eq!(form, Ok(Order {
+        customer: "bob".into(),
+        items: vec![item("x", 2), item("y", 1)],
+        remarks: vec![],
+    }));
+
+    // Sparse, out of order, and URL-encoded indices.
+    let form: Result<Order, _> = strict("items%5B7%5D.name=b&items[7].qty=7&customer=bob&items[3].qty=3&items[3].name=a&notes[0].name=c&notes[0].qty=0");
+    assert_eq!(form, Ok(Order {
+        customer: "bob".into(),
+        items: vec![item("a", 3), item("b", 7)],
+        remarks: vec![item("c", 0)],
+    }));
+
+    let form: Result<Order, _> = strict("customer=bob&items[10].name=x&items[10].qty=1");
+    assert_eq!(form, Err(FormParseError::BadValue("items[10].name".into(), "x".into())));
+
+    let form: Result<Order, _> = strict("customer=bob&items[a].name=x");
+    assert_eq!(form, Err(FormParseError::BadValue("items[a].name".into(), "x".into())));
+
+    let form: Result<Order, _> = strict("customer=bob&items[0].name=x&items[0].qty=many");
+    assert_eq!(form, Err(FormParseError::BadValue("items[0].qty".into(), "many".into())));
+
+    let form: Result<Order, _> = strict("customer=bob&items[0].name=x&items[0].qty=1&items[0].extra=1");
+    assert_eq!(form, Err(FormParseError::Unknown("items[0].extra".into(), "1".into())));
+
+    let form: Result<Order, _> = strict("customer=bob&items[0].name=x");
+    assert_eq!(form, Err(FormParseError::Missing("items".into())));
+
+    let form: Result<Order, _> = lenient("customer=bob&items[0].name=x&items[0].qty=1&items[0].extra=1");
+    assert_eq!(form.map(|order| order.items), Ok(vec![item("x", 1)]));
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Search {
+    query: String,
+    #[form(duplicates = "first")]
+    page: usize,
+    #[form(duplicates = "reject")]
+    sort: String,
+}
+
+#[test]
+fn duplicate_fields() {
+    let search: Result<Search, _> = strict("query=a&query=b&page=1&page=2&sort=asc");
+    assert_eq!(search, Ok(Search { query: "b".into(), page: 1, sort: "asc".into() }));
+
+    let search: Result<Search, _> = strict("query=a&page=1&sort=asc&sort=desc");
+    assert_eq!(search, Err(FormParseError::Duplicate("sort".into(), "desc".into())));
+
+    let mut items = FormItems::from("query=a&query=b&page=1&sort=asc")
+        .with_duplicates(Duplicates::First);
+    let search = Search::from_form(&mut items, true);
+    assert_eq!(search.map(|s| s.query), Ok("a".into()));
+
+    let mut items = FormItems::from("query=a&query=b&page=1&page=2&sort=asc")
+        .with_duplicates(Duplicates::Reject);
+    let search = Search::from_form(&mut items, true);
+    assert_eq!(search, Err(FormParseError::Duplicate("query".into(), "b".into())));
+}
@@ -0,0 +1,117 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Route;
+use rocket::local::asynchronous::Client;
+use rocket::request::{self, Request, FromRequest, GuardError};
+use rocket::http::{Method, Header, Status, CookieJar, uri::Origin};
+use rocket::outcome::Outcome::*;
+
+struct Key(String);
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Key {
+    type Error = &'static str;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match req.headers().get_one("X-Key") {
+            Some("forward") => Forward(()),
+            Some("bad") => Failure((Status::BadRequest, "bad key")),
+            Some(key) => Success(Key(key.into())),
+            None => Failure((Status::Forbidden, "missing key")),
+        }
+    }
+}
+
+#[derive(FromRequest)]
+struct Plain {
+    method: Method,
+    key: Key,
+}
+
+#[derive(FromRequest)]
+struct Borrowed<'a, 'r> {
+    route: Option<&'r Route>,
+    cookies: &'a CookieJar<'r>,
+    #[from_request(status = 401)]
+    key: Key,
+}
+
+#[derive(FromRequest)]
+struct OneLifetime<'a> {
+    method: Method,
+    #[from_request(forward_on_error)]
+    key: Key,
+    uri: &'a Origin<'a>,
+}
+
+#[rocket::async_test]
+async fn derived_guards_succeed() {
+    let client = Client::tracked(rocket::ignite()).await.unwrap();
+    let local = client.post("/").header(Header::new("X-Key", "secret"));
+    let req = local.inner();
+
+    let plain = Plain::from_request(req).await.unwrap();
+    assert_eq!(plain.method, Method::Post);
+    assert_eq!(plain.key.0, "secret");
+
+    let borrowed = Borrowed::from_request(req).await.unwrap();
+    assert!(borrowed.route.is_none());
+    assert_eq!(borrowed.cookies.iter().count(), 0);
+    assert_eq!(borrowed.key.0, "secret");
+
+    let one = OneLifetime::from_request(req).await.unwrap();
+    assert_eq!(one.method, Method::Post);
+    assert_eq!(one.key.0, "secret");
+    assert_eq!(one.uri.path(), "/");
+}
+
+#[rocket::async_test]
+async fn derived_guards_forward_and_fail() {
+    let client = Client::tracked(rocket::ignite()).await.unwrap();
+
+    let local = client.get("/").header(Header::new("X-Key", "forward"));
+    assert!(Plain::from_request(local.inner()).await.is_forward());
+    assert!(Borrowed::from_request(local.inner()).await.is_forward());
+
+    let local = client.get("/").header(Header::new("X-Key", "bad"));
+    let error = GuardError { field: "key", error: "\"bad key\"".into() };
+    match Plain::from_request(local.inner()).await {
+        Failure((status, e)) => assert_eq!((status, e), (Status::BadRequest, error.clone())),
+        _ => panic!("expected failure"),
+    }
+
+    match Borrowed::from_request(local.inner()).await {
+        Failure((status, e)) => assert_eq!((status, e), (Status::Unauthorized, error)),
+        _ => panic!("expected failure"),
+    }
+
+    assert!(OneLifetime::from_request(local.inner()).await.is_forward());
+
+    let local = client.get("/");
+    match Plain::from_request(local.inner()).await {
+        Failure((status, e)) => {
+            assert_eq!(status, Status::Forbidden);
+            assert_eq!(e.to_string(), "guard for `key` failed: \"missing key\"");
+        }
+        _ => panic!("expected failure"),
+    }
+}
+
+#[get("/")]
+fn index(plain: Plain) -> String {
+    format!("{} {}", plain.method, plain.key.0)
+}
+
+#[rocket::async_test]
+async fn derived_guard_in_route() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![index])).await.unwrap();
+
+    let response = client.get("/").header(Header::new("X-Key", "abc")).dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "GET abc");
+
+    let response = client.get("/").header(Header::new("X-Key", "forward")).dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Forbidden);
+}
@@ -0,0 +1,86 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::mpsc;
+
+use rocket::local::blocking::Client;
+
+#[get("/<name>", blocking)]
+fn hello(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[get("/sum?<a>&<b>", blocking = true)]
+fn sum(a: usize, b: usize) -> String {
+    (a + b).to_string()
+}
+
+#[post("/echo", data = "<body>", blocking)]
+fn echo(body: String) -> String {
+    body
+}
+
+#[get("/async")]
+async fn not_sync() -> &'static str {
+    "async"
+}
+
+#[get("/not/blocking", blocking = false)]
+fn not_blocking() -> &'static str {
+    "not blocking"
+}
+
+#[test]
+fn test_blocking_routes() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![hello, sum, echo, not_sync, not_blocking]);
+
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.get("/Bob").dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hello, Bob!");
+
+    let response = client.get("/sum?a=3&b=4").dispatch();
+    assert_eq!(response.into_string().unwrap(), "7");
+
+    let response = client.post("/echo").body("Hi there!").dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hi there!");
+
+    let response = client.get("/async").dispatch();
+    assert_eq!(response.into_string().unwrap(), "async");
+
+    let response = client.get("/not/blocking").dispatch();
+    assert_eq!(response.into_string().unwrap(), "not blocking");
+}
+
+#[rocket::async_test]
+async fn test_blocking_does_not_stall_worker() {
+    // The test runtime has a single worker. If `blocking` stalled it, the
+    // second task would never run and the first would never return.
+    let (tx, rx) = mpsc::channel();
+    let waiter = rocket::tokio::spawn(async move {
+        rocket::handler::blocking(move || rx.recv().unwrap()).await
+    });
+
+    rocket::tokio::spawn(async move { tx.send(42).unwrap() });
+    assert_eq!(waiter.await.unwrap(), 42);
+}
+
+#[test]
+fn test_blocking_under_current_thread_runtime() {
+    // A runtime Rocket didn't build: `blocking` still moves off the worker.
+    let runtime = rocket::tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let value = runtime.block_on(async move {
+        let waiter = rocket::tokio::spawn(async move {
+            rocket::handler::blocking(move || rx.recv().unwrap()).await
+        });
+
+        rocket::tokio::spawn(async move { tx.send(7).unwrap() });
+        waiter.await.unwrap()
+    });
+
+    assert_eq!(value, 7);
+}
@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{self, Request, FromRequest};
+use rocket::local::blocking::Client;
+use rocket::http::{Accept, ContentType, Header, MediaType, Method, Status};
+
+struct ApiKey;
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for ApiKey {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("X-Api-Key") {
+            Some("secret") => request::Outcome::Success(ApiKey),
+            _ => request::Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[route_group(prefix = "/api/v1/", format = "json", guards(super::ApiKey))]
+mod api {
+    #[get("/")]
+    pub fn index() -> &'static str { "index" }
+
+    #[post("/echo", data = "<body>")]
+    pub fn echo(body: String) -> String { body }
+
+    #[route(GET, path = "/items/<id>", format = "plain")]
+    pub fn item(id: usize) -> String { id.to_string() }
+
+    pub fn not_a_route() -> usize { 7 }
+}
+
+#[route_group(prefix = "/public")]
+mod public {
+    #[get("/hello?<name>")]
+    pub fn hello(name: Option<String>) -> String {
+        format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+    }
+}
+
+#[test]
+fn group_rewrites_routes() {
+    let routes = api::routes();
+    assert_eq!(routes.len(), 3);
+
+    assert_eq!(routes[0].method, Method::Get);
+    assert_eq!(routes[0].uri.path(), "/api/v1");
+    assert_eq!(routes[0].format, Some(MediaType::JSON));
+
+    assert_eq!(routes[1].method, Method::Post);
+    assert_eq!(routes[1].uri.path(), "/api/v1/echo");
+    assert_eq!(routes[1].format, Some(MediaType::JSON));
+
+    assert_eq!(routes[2].uri.path(), "/api/v1/items/<id>");
+    assert_eq!(routes[2].format, Some(MediaType::Plain));
+
+    assert_eq!(api::not_a_route(), 7);
+
+    let routes = public::routes();
+    assert_eq!(routes[0].uri.to_string(), "/public/hello?<name>");
+    assert_eq!(routes[0].format, None);
+}
+
+#[test]
+fn group_guards_apply_to_every_route() {
+    let rocket = rocket::ignite()
+        .mount("/", api::routes())
+        .mount("/", public::routes());
+
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.get("/api/v1").header(Accept::JSON).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.get("/api/v1")
+        .header(Accept::JSON)
+        .header(Header::new("X-Api-Key", "secret"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.post("/api/v1/echo")
+        .header(ContentType::JSON)
+        .header(Header::new("X-Api-Key", "secret"))
+        .body("{}")
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "{}");
+
+    let response = client.get("/api/v1/items/3").header(Accept::Plain).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.get("/public/hello?name=Bob").dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hello, Bob!");
+}
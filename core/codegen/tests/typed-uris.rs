@@ -314,6 +314,45 @@ fn check_location_promotion() {
     }
 }
 
+#[test]
+fn check_base() {
+    use rocket::http::uri::Absolute;
+
+    let string = String::from("https://rocket.rs/app/");
+    let absolute = Absolute::parse("http://localhost:8000").unwrap();
+    assert_eq!(uri!(base = "https://rocket.rs", simple: 100).to_string(), "https://rocket.rs/100");
+    assert_eq!(uri!(base = string, simple: id = 3).to_string(), "https://rocket.rs/app/3");
+    assert_eq!(uri!(base = &absolute, "/mount", simple2: 1, "a b").to_string(),
+        "http://localhost:8000/mount/1/a%20b");
+    assert_eq!(uri!(base = absolute, guard_1: 7).to_string(), "http://localhost:8000/7");
+}
+
+#[test]
+fn check_request_base() {
+    use rocket::local::blocking::Client;
+    use rocket::http::Header;
+
+    let client = Client::untracked(rocket::ignite()).unwrap();
+    let req = client.get("/");
+    assert_eq!(uri!(base = req.inner(), simple: 1).to_string(), "http://127.0.0.1:8000/1");
+
+    let req = client.get("/").header(Header::new("Host", "example.com:8080"));
+    assert_eq!(uri!(base = req.inner(), simple: 1).to_string(), "http://example.com:8080/1");
+
+    let config = rocket::Config {
+        trusted_proxies: vec!["10.0.0.1".parse().unwrap()],
+        ..rocket::Config::debug_default()
+    };
+
+    let client = Client::untracked(rocket::custom(config)).unwrap();
+    let req = client.get("/")
+        .remote("10.0.0.1:9000".parse().unwrap())
+        .header(Header::new("Host", "internal:8000"))
+        .header(Header::new("X-Forwarded-Host", "rocket.rs"))
+        .header(Header::new("X-Forwarded-Proto", "https"));
+    assert_eq!(uri!(base = req.inner(), simple: 1).to_string(), "https://rocket.rs/1");
+}
+
 #[test]
 fn check_scoped() {
     assert_uri_eq!{
@@ -113,6 +113,22 @@ fn uri_display_bam() {
     assert_uri_display_query!(bam, "foo=hi%20hi&baz=tony");
 }
 
+#[derive(UriDisplayQuery)]
+struct Comment<'a> {
+    text: &'a str,
+    #[form(honeypot)]
+    website: &'a str,
+}
+
+#[test]
+fn uri_display_honeypot() {
+    let comment = Comment { text: "hi there", website: "" };
+    assert_uri_display_query!(comment, "text=hi%20there&website=");
+
+    let comment = Comment { text: "hi", website: "spam.example" };
+    assert_uri_display_query!(comment, "text=hi&website=");
+}
+
 macro_rules! assert_uri_display_path {
     ($v:expr, $s:expr) => (
         let uri_string = format!("{}", &$v as &dyn UriDisplay<Path>);
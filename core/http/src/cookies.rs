@@ -187,6 +187,7 @@ mod key {
 pub struct CookieJar<'a> {
     jar: cookie::CookieJar,
     key: &'a Key,
+    policy: CookiePolicy,
     ops: Mutex<Vec<Op>>,
 }
 
@@ -195,17 +196,145 @@ impl<'a> Clone for CookieJar<'a> {
         CookieJar {
             jar: self.jar.clone(),
             key: self.key,
+            policy: self.policy,
             ops: Mutex::new(self.ops.lock().clone()),
         }
     }
 }
 
+/// Defaults applied to cookies added to a [`CookieJar`].
+///
+/// Rocket configures the policy via the `cookies` configuration parameter:
+///
+/// ```toml
+/// [release.cookies]
+/// same_site = "lax"
+/// secure = true
+/// ```
+///
+/// The `same_site` attribute is set on every added cookie that does not
+/// specify one. It is one of `"strict"`, `"lax"`, or `"none"`, and defaults to
+/// `"strict"`. When `secure` is `true`, the `Secure` attribute is set on every
+/// added cookie that does not specify one. It defaults to `false` when the
+/// selected profile is `debug` and `true` for every other profile,
+/// irrespective of the compilation profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_::Serialize, serde_::Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_"))]
+pub struct CookiePolicy {
+    /// The default `SameSite` attribute. **(default: `Strict`)**
+    #[cfg_attr(feature = "serde", serde(with = "same_site"))]
+    pub same_site: SameSite,
+    /// Whether cookies are `Secure` by default. **(default: `false`)**
+    pub secure: bool,
+}
+
+impl Default for CookiePolicy {
+    fn default() -> Self {
+        CookiePolicy { same_site: SameSite::Strict, secure: false }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod same_site {
+    use serde_::{Serializer, Deserializer, Deserialize, de::Error};
+    use super::SameSite;
+
+    pub fn serialize<S: Serializer>(same_site: &SameSite, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&same_site.to_string().to_ascii_lowercase())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<SameSite, D::Error> {
+        let string = String::deserialize(de)?;
+        match string.to_ascii_lowercase().as_str() {
+            "strict" => Ok(SameSite::Strict),
+            "lax" => Ok(SameSite::Lax),
+            "none" => Ok(SameSite::None),
+            _ => Err(D::Error::invalid_value(
+                serde_::de::Unexpected::Str(&string),
+                &"one of \"strict\", \"lax\", or \"none\"",
+            )),
+        }
+    }
+}
+
+/// The error returned by [`CookieJar::try_add()`] when a cookie violates the
+/// requirements of its name's prefix.
+///
+/// Browsers reject cookies whose names begin with `__Secure-` unless they are
+/// `Secure`, and those whose names begin with `__Host-` unless they are
+/// additionally set with a path of `/` and no domain. Rocket enforces the same
+/// rules when a cookie is added, after applying defaults, so that violations
+/// are caught on the server rather than silently dropped by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookiePrefixError {
+    /// A `__Secure-` or `__Host-` cookie is not `Secure`.
+    NotSecure(String),
+    /// A `__Host-` cookie has a path other than `/`.
+    HostPath(String),
+    /// A `__Host-` cookie has a `Domain` attribute.
+    HostDomain(String),
+}
+
+impl CookiePrefixError {
+    pub(crate) fn check(cookie: &Cookie<'_>) -> Result<(), CookiePrefixError> {
+        let name = cookie.name();
+        let is_host = name.starts_with("__Host-");
+        if !is_host && !name.starts_with("__Secure-") {
+            return Ok(());
+        }
+
+        if cookie.secure() != Some(true) {
+            return Err(CookiePrefixError::NotSecure(name.into()));
+        }
+
+        if is_host && cookie.path() != Some("/") {
+            return Err(CookiePrefixError::HostPath(name.into()));
+        }
+
+        if is_host && cookie.domain().is_some() {
+            return Err(CookiePrefixError::HostDomain(name.into()));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CookiePrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CookiePrefixError::NotSecure(name) => {
+                write!(f, "cookie `{}` has a secure name prefix but is not `Secure`", name)
+            }
+            CookiePrefixError::HostPath(name) => {
+                write!(f, "`__Host-` cookie `{}` must have a path of `/`", name)
+            }
+            CookiePrefixError::HostDomain(name) => {
+                write!(f, "`__Host-` cookie `{}` must not have a `Domain`", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CookiePrefixError {}
+
 #[derive(Clone)]
 enum Op {
     Add(Cookie<'static>, bool),
     Remove(Cookie<'static>, bool),
 }
 
+/// Returns the identity of `cookie` on a client: its name, path, and domain.
+/// A cookie replaces any other with the same identity. A missing path is
+/// taken to be `/`, the path Rocket sets by default, and domains are compared
+/// case-insensitively, without a leading `.`.
+#[doc(hidden)]
+pub fn identity(cookie: &Cookie<'_>) -> (String, String, Option<String>) {
+    let path = cookie.path().unwrap_or("/").to_string();
+    let domain = cookie.domain().map(|d| d.trim_start_matches('.').to_ascii_lowercase());
+    (cookie.name().to_string(), path, domain)
+}
+
 impl Op {
     fn cookie(&self) -> &Cookie<'static> {
         match self {
@@ -320,7 +449,12 @@ impl<'a> CookieJar<'a> {
     /// set on `cookie` before being added to `self`:
     ///
     ///    * `path`: `"/"`
-    ///    * `SameSite`: `Strict`
+    ///    * `SameSite`: the [`CookiePolicy`] default, `Strict` unless configured
+    ///    * `Secure`: `true` if the [`CookiePolicy`] requests it
+    ///
+    /// If, after applying defaults, the cookie violates the requirements of a
+    /// `__Secure-` or `__Host-` name prefix, it is not added and an error is
+    /// logged. Use [`CookieJar::try_add()`] to handle the error instead.
     ///
     /// # Example
     ///
@@ -340,9 +474,36 @@ impl<'a> CookieJar<'a> {
     ///     jar.add(cookie.finish());
     /// }
     /// ```
-    pub fn add(&self, mut cookie: Cookie<'static>) {
-        Self::set_defaults(&mut cookie);
+    pub fn add(&self, cookie: Cookie<'static>) {
+        if let Err(e) = self.try_add(cookie) {
+            log::error!("Refusing to add cookie: {}.", e);
+        }
+    }
+
+    /// Like [`CookieJar::add()`], but returns an error instead of logging one
+    /// when `cookie` violates the requirements of its name's prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar, CookiePrefixError};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     let cookie = Cookie::build("__Host-id", "1").secure(true).finish();
+    ///     assert!(jar.try_add(cookie).is_ok());
+    ///
+    ///     let cookie = Cookie::build("__Host-id", "1").secure(true).domain("rocket.rs");
+    ///     let error = jar.try_add(cookie.finish()).unwrap_err();
+    ///     assert_eq!(error, CookiePrefixError::HostDomain("__Host-id".into()));
+    /// }
+    /// ```
+    pub fn try_add(&self, mut cookie: Cookie<'static>) -> Result<(), CookiePrefixError> {
+        self.set_defaults(&mut cookie);
+        CookiePrefixError::check(&cookie)?;
         self.ops.lock().push(Op::Add(cookie, false));
+        Ok(())
     }
 
     /// Adds `cookie` to the collection. The cookie's value is encrypted with
@@ -355,12 +516,15 @@ impl<'a> CookieJar<'a> {
     /// set on `cookie` before being added to `self`:
     ///
     ///    * `path`: `"/"`
-    ///    * `SameSite`: `Strict`
+    ///    * `SameSite`: the [`CookiePolicy`] default, `Strict` unless configured
+    ///    * `Secure`: `true` if the [`CookiePolicy`] requests it
     ///    * `HttpOnly`: `true`
     ///    * `Expires`: 1 week from now
     ///
     /// These defaults ensure maximum usability and security. For additional
-    /// security, you may wish to set the `secure` flag.
+    /// security, you may wish to set the `secure` flag. As with
+    /// [`CookieJar::add()`], cookies violating their name's prefix requirements
+    /// are not added and an error is logged.
     ///
     /// # Example
     ///
@@ -375,9 +539,24 @@ impl<'a> CookieJar<'a> {
     /// ```
     #[cfg(feature = "private-cookies")]
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
-    pub fn add_private(&self, mut cookie: Cookie<'static>) {
-        Self::set_private_defaults(&mut cookie);
+    pub fn add_private(&self, cookie: Cookie<'static>) {
+        if let Err(e) = self.try_add_private(cookie) {
+            log::error!("Refusing to add private cookie: {}.", e);
+        }
+    }
+
+    /// Like [`CookieJar::add_private()`], but returns an error instead of
+    /// logging one when `cookie` violates the requirements of its name's
+    /// prefix.
+    #[cfg(feature = "private-cookies")]
+    pub(crate) fn try_add_private(
+        &self,
+        mut cookie: Cookie<'static>
+    ) -> Result<(), CookiePrefixError> {
+        self.set_private_defaults(&mut cookie);
+        CookiePrefixError::check(&cookie)?;
         self.ops.lock().push(Op::Add(cookie, true));
+        Ok(())
     }
 
     /// Removes `cookie` from this collection and generates a "removal" cookies
@@ -459,6 +638,44 @@ impl<'a> CookieJar<'a> {
     pub fn iter(&self) -> impl Iterator<Item=&Cookie<'static>> {
         self.jar.iter()
     }
+
+    /// Returns the cookies that will be sent to the client to reflect the
+    /// pending changes to this collection, in the order they will be sent.
+    ///
+    /// If a cookie with a given name is changed more than once, only the last
+    /// change is reflected. Additions are returned as-is, except that private
+    /// cookies are encrypted. Removals of cookies present in the request are
+    /// returned as "removal" cookies, while removals of cookies that were only
+    /// pending are not returned at all.
+    ///
+    /// Rocket emits these cookies as `Set-Cookie` headers after response
+    /// fairings have run, so a fairing may use this method to inspect the
+    /// changes made by a handler or catcher before they are emitted. Cookie
+    /// changes take precedence over any `Set-Cookie` headers with the same
+    /// name, path, and domain set directly on the response. Changes to cookies
+    /// with the same name but a different path or domain are all kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Cookie, CookieJar};
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     jar.add(Cookie::new("a", "1"));
+    ///     jar.add(Cookie::new("b", "2"));
+    ///     jar.add(Cookie::new("a", "3"));
+    ///
+    ///     let delta = jar.delta();
+    ///     assert_eq!(delta.len(), 2);
+    ///     assert_eq!((delta[0].name(), delta[0].value()), ("b", "2"));
+    ///     assert_eq!((delta[1].name(), delta[1].value()), ("a", "3"));
+    /// }
+    /// ```
+    pub fn delta(&self) -> Vec<Cookie<'static>> {
+        self.resolve(&self.ops.lock())
+    }
 }
 
 /// WARNING: These are unstable! Do not use outside of Rocket!
@@ -466,15 +683,18 @@ impl<'a> CookieJar<'a> {
 impl<'a> CookieJar<'a> {
     #[inline(always)]
     pub fn new(key: &'a Key) -> Self {
-        CookieJar {
-            jar: cookie::CookieJar::new(),
-            key, ops: Mutex::new(Vec::new()),
-        }
+        CookieJar::from(cookie::CookieJar::new(), key)
     }
 
     #[inline(always)]
     pub fn from(jar: cookie::CookieJar, key: &'a Key) -> CookieJar<'a> {
-        CookieJar { jar, key, ops: Mutex::new(Vec::new()) }
+        CookieJar { jar, key, policy: CookiePolicy::default(), ops: Mutex::new(Vec::new()) }
+    }
+
+    /// Sets the policy applied to cookies added from now on.
+    #[inline(always)]
+    pub fn set_policy(&mut self, policy: CookiePolicy) {
+        self.policy = policy;
     }
 
     /// Removes all delta cookies.
@@ -483,24 +703,36 @@ impl<'a> CookieJar<'a> {
         self.ops.lock().clear();
     }
 
-    /// TODO: This could be faster by just returning the cookies directly via
-    /// an ordered hash-set of sorts.
+    /// Removes all pending changes and returns the cookies that reflect them,
+    /// as in [`CookieJar::delta()`].
     #[inline(always)]
-    pub fn take_delta_jar(&self) -> cookie::CookieJar {
-        let ops = std::mem::replace(&mut *self.ops.lock(), Vec::new());
-        let mut jar = cookie::CookieJar::new();
+    pub fn take_delta(&self) -> Vec<Cookie<'static>> {
+        let ops = std::mem::take(&mut *self.ops.lock());
+        self.resolve(&ops)
+    }
 
+    /// Resolves `ops` into the cookies to send to the client: the last change
+    /// to each cookie, as identified by [`identity()`], wins, and cookies
+    /// appear in the order of their last change.
+    fn resolve(&self, ops: &[Op]) -> Vec<Cookie<'static>> {
+        let mut delta: Vec<Cookie<'static>> = Vec::with_capacity(ops.len());
         for op in ops {
+            let id = identity(op.cookie());
+            delta.retain(|c| identity(c) != id);
             match op {
-                Op::Add(c, false) => jar.add(c),
+                Op::Add(c, false) => delta.push(c.clone()),
                 #[cfg(feature = "private-cookies")]
-                Op::Add(c, true) => jar.private_mut(self.key).add(c),
-                Op::Remove(mut c, _) => {
+                Op::Add(c, true) => {
+                    let mut jar = cookie::CookieJar::new();
+                    jar.private_mut(self.key).add(c.clone());
+                    delta.extend(jar.get(c.name()).cloned());
+                }
+                Op::Remove(c, _) => {
+                    // Only cookies the client sent need removing on the client.
                     if self.jar.get(c.name()).is_some() {
+                        let mut c = c.clone();
                         c.make_removal();
-                        jar.add(c);
-                    } else {
-                        jar.remove(c);
+                        delta.push(c);
                     }
                 }
                 #[allow(unreachable_patterns)]
@@ -508,7 +740,7 @@ impl<'a> CookieJar<'a> {
             }
         }
 
-        jar
+        delta
     }
 
     /// Adds an original `cookie` to this collection.
@@ -530,15 +762,20 @@ impl<'a> CookieJar<'a> {
     /// values are:
     ///
     ///    * `path`: `"/"`
-    ///    * `SameSite`: `Strict`
+    ///    * `SameSite`: `self.policy.same_site`
+    ///    * `Secure`: `true` if `self.policy.secure`
     ///
-    fn set_defaults(cookie: &mut Cookie<'static>) {
+    fn set_defaults(&self, cookie: &mut Cookie<'static>) {
         if cookie.path().is_none() {
             cookie.set_path("/");
         }
 
         if cookie.same_site().is_none() {
-            cookie.set_same_site(SameSite::Strict);
+            cookie.set_same_site(self.policy.same_site);
+        }
+
+        if cookie.secure().is_none() && self.policy.secure {
+            cookie.set_secure(true);
         }
     }
 
@@ -547,20 +784,15 @@ impl<'a> CookieJar<'a> {
     /// values are:
     ///
     ///    * `path`: `"/"`
-    ///    * `SameSite`: `Strict`
+    ///    * `SameSite`: `self.policy.same_site`
+    ///    * `Secure`: `true` if `self.policy.secure`
     ///    * `HttpOnly`: `true`
     ///    * `Expires`: 1 week from now
     ///
     #[cfg(feature = "private-cookies")]
     #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
-    fn set_private_defaults(cookie: &mut Cookie<'static>) {
-        if cookie.path().is_none() {
-            cookie.set_path("/");
-        }
-
-        if cookie.same_site().is_none() {
-            cookie.set_same_site(SameSite::Strict);
-        }
+    fn set_private_defaults(&self, cookie: &mut Cookie<'static>) {
+        self.set_defaults(cookie);
 
         if cookie.http_only().is_none() {
             cookie.set_http_only(true);
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::header::Header;
+
+/// An HTTP entity tag, as used in the `ETag`, `If-Match`, and `If-None-Match`
+/// headers.
+///
+/// An entity tag is an opaque string identifying a version of a resource. A
+/// _strong_ tag, written `"tag"`, changes whenever the representation changes
+/// at all. A _weak_ tag, written `W/"tag"`, changes only when the resource
+/// changes meaningfully.
+///
+/// # Comparison
+///
+/// [RFC 7232] defines two comparison functions. [`ETag::strong_eq()`] is
+/// `true` only when both tags are strong and identical; [`ETag::weak_eq()`]
+/// ignores weakness. The derived `PartialEq` compares both the tag and its
+/// weakness.
+///
+/// [RFC 7232]: https://tools.ietf.org/html/rfc7232#section-2.3.2
+///
+/// # Header
+///
+/// `ETag` implements `Into<Header>`, producing an `ETag` header:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{ETag, Header};
+///
+/// let header: Header<'static> = ETag::strong("v1").into();
+/// assert_eq!(header.name(), "ETag");
+/// assert_eq!(header.value(), "\"v1\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// Returns a strong entity tag with the opaque string `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a character not allowed in an entity tag: a
+    /// double quote, a space, or a control character.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// let etag = ETag::strong("abc");
+    /// assert_eq!(etag.to_string(), "\"abc\"");
+    /// ```
+    pub fn strong<T: Into<String>>(tag: T) -> ETag {
+        ETag::new(tag.into(), false)
+    }
+
+    /// Returns a weak entity tag with the opaque string `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a character not allowed in an entity tag: a
+    /// double quote, a space, or a control character.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// let etag = ETag::weak("abc");
+    /// assert_eq!(etag.to_string(), "W/\"abc\"");
+    /// ```
+    pub fn weak<T: Into<String>>(tag: T) -> ETag {
+        ETag::new(tag.into(), true)
+    }
+
+    fn new(tag: String, weak: bool) -> ETag {
+        if let Some(c) = tag.bytes().find(|&b| !is_etagc(b)) {
+            panic!("invalid character {:?} in entity tag {:?}", c as char, tag);
+        }
+
+        ETag { tag, weak }
+    }
+
+    /// Parses a single entity tag such as `"abc"` or `W/"abc"`. Returns `None`
+    /// if `string` is not a valid entity tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// assert_eq!(ETag::parse("W/\"abc\""), Some(ETag::weak("abc")));
+    /// assert_eq!(ETag::parse("abc"), None);
+    /// ```
+    pub fn parse(string: &str) -> Option<ETag> {
+        match parse_one(string.trim()) {
+            Some((etag, "")) => Some(etag),
+            _ => None,
+        }
+    }
+
+    /// Parses a comma-separated list of entity tags, as found in `If-Match`
+    /// and `If-None-Match` headers. Returns `None` if any element is not a
+    /// valid entity tag. Empty elements are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// let tags = ETag::parse_list("\"a,b\", W/\"c\"").unwrap();
+    /// assert_eq!(tags, vec![ETag::strong("a,b"), ETag::weak("c")]);
+    /// assert!(ETag::parse_list("\"a\", b").is_none());
+    /// ```
+    pub fn parse_list(string: &str) -> Option<Vec<ETag>> {
+        let mut tags = vec![];
+        let mut rest = string.trim_start_matches(|c| c == ',' || c == ' ' || c == '\t');
+        while !rest.is_empty() {
+            let (etag, next) = parse_one(rest)?;
+            let next = next.trim_start_matches(|c| c == ' ' || c == '\t');
+            if !next.is_empty() && !next.starts_with(',') {
+                return None;
+            }
+
+            tags.push(etag);
+            rest = next.trim_start_matches(|c| c == ',' || c == ' ' || c == '\t');
+        }
+
+        Some(tags)
+    }
+
+    /// Returns the opaque string of `self`, without quotes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// assert_eq!(ETag::weak("abc").tag(), "abc");
+    /// ```
+    #[inline(always)]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` is a weak entity tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// assert!(ETag::weak("abc").is_weak());
+    /// assert!(!ETag::strong("abc").is_weak());
+    /// ```
+    #[inline(always)]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns `true` if `self` and `other` are both strong and have the same
+    /// opaque string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// assert!(ETag::strong("a").strong_eq(&ETag::strong("a")));
+    /// assert!(!ETag::strong("a").strong_eq(&ETag::weak("a")));
+    /// ```
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Returns `true` if `self` and `other` have the same opaque string,
+    /// regardless of weakness.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    ///
+    /// assert!(ETag::strong("a").weak_eq(&ETag::weak("a")));
+    /// assert!(!ETag::weak("a").weak_eq(&ETag::weak("b")));
+    /// ```
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+/// `etagc = %x21 / %x23-7E / obs-text`
+#[inline]
+fn is_etagc(b: u8) -> bool {
+    b == 0x21 || (b >= 0x23 && b != 0x7F)
+}
+
+/// Parses one entity tag at the start of `string`, returning it and the
+/// remainder of `string`.
+fn parse_one(string: &str) -> Option<(ETag, &str)> {
+    let (weak, rest) = match string.strip_prefix("W/") {
+        Some(rest) => (true, rest),
+        None => (false, string),
+    };
+
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    let (tag, rest) = (&rest[..end], &rest[end + 1..]);
+    if !tag.bytes().all(is_etagc) {
+        return None;
+    }
+
+    Some((ETag { tag: tag.to_string(), weak }, rest))
+}
+
+impl FromStr for ETag {
+    type Err = ();
+
+    #[inline]
+    fn from_str(string: &str) -> Result<ETag, ()> {
+        ETag::parse(string).ok_or(())
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+impl Into<Header<'static>> for ETag {
+    #[inline]
+    fn into(self) -> Header<'static> {
+        Header::new("ETag", self.to_string())
+    }
+}
+
+impl Into<Header<'static>> for &ETag {
+    #[inline]
+    fn into(self) -> Header<'static> {
+        Header::new("ETag", self.to_string())
+    }
+}
@@ -11,7 +11,13 @@ pub struct Header<'h> {
     /// The name of the header.
     pub name: Uncased<'h>,
     /// The value of the header.
+    ///
+    /// If the header was created from bytes that aren't valid UTF-8, this is
+    /// a lossy conversion of those bytes. The original bytes are available via
+    /// [`Header::value_bytes()`].
     pub value: Cow<'h, str>,
+    /// The original bytes of the value when they aren't valid UTF-8.
+    raw: Option<Cow<'h, [u8]>>,
 }
 
 impl<'h> Header<'h> {
@@ -50,10 +56,54 @@ impl<'h> Header<'h> {
     {
         Header {
             name: Uncased::new(name),
-            value: value.into()
+            value: value.into(),
+            raw: None,
         }
     }
 
+    /// Constructs a new header from a raw, possibly non-UTF-8 `value`.
+    ///
+    /// [`Header::value()`] returns a lossy UTF-8 conversion of `value` while
+    /// [`Header::value_bytes()`] returns `value` exactly. This is how Rocket
+    /// constructs the headers of incoming requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Header;
+    ///
+    /// let header = Header::from_bytes("X-Signature", &b"\xff\xfe!"[..]);
+    /// assert_eq!(header.value(), "\u{fffd}\u{fffd}!");
+    /// assert_eq!(header.value_bytes(), b"\xff\xfe!");
+    ///
+    /// let header = Header::from_bytes("X-Custom-Header", &b"custom value"[..]);
+    /// assert_eq!(header, Header::new("X-Custom-Header", "custom value"));
+    /// ```
+    pub fn from_bytes<'a: 'h, 'b: 'h, N, V>(name: N, value: V) -> Header<'h>
+        where N: Into<Cow<'a, str>>, V: Into<Cow<'b, [u8]>>
+    {
+        let (value, raw) = match value.into() {
+            Cow::Borrowed(bytes) => match std::str::from_utf8(bytes) {
+                Ok(string) => (Cow::Borrowed(string), None),
+                Err(_) => {
+                    let lossy = String::from_utf8_lossy(bytes).into_owned();
+                    (Cow::Owned(lossy), Some(Cow::Borrowed(bytes)))
+                }
+            },
+            Cow::Owned(bytes) => match String::from_utf8(bytes) {
+                Ok(string) => (Cow::Owned(string), None),
+                Err(e) => {
+                    let bytes = e.into_bytes();
+                    let lossy = String::from_utf8_lossy(&bytes).into_owned();
+                    (Cow::Owned(lossy), Some(Cow::Owned(bytes)))
+                }
+            },
+        };
+
+        Header { name: Uncased::new(name), value, raw }
+    }
+
     /// Returns the name of this header.
     ///
     /// # Example
@@ -102,6 +152,30 @@ impl<'h> Header<'h> {
     pub fn value(&self) -> &str {
         &self.value
     }
+
+    /// Returns the exact bytes of this header's value. These differ from the
+    /// bytes of [`Header::value()`] only when the header was created with
+    /// [`Header::from_bytes()`] from bytes that aren't valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Header;
+    ///
+    /// let header = Header::new("X-Custom-Header", "custom value");
+    /// assert_eq!(header.value_bytes(), b"custom value");
+    ///
+    /// let header = Header::from_bytes("X-Custom-Header", b"\xff".to_vec());
+    /// assert_eq!(header.value_bytes(), b"\xff");
+    /// ```
+    #[inline]
+    pub fn value_bytes(&self) -> &[u8] {
+        match self.raw {
+            Some(ref raw) => &**raw,
+            None => self.value.as_bytes(),
+        }
+    }
 }
 
 impl fmt::Display for Header<'_> {
@@ -121,7 +195,30 @@ impl fmt::Display for Header<'_> {
 /// returns values for headers of names "AbC", "ABC", "abc", and so on.
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct HeaderMap<'h> {
-    headers: IndexMap<Uncased<'h>, Vec<Cow<'h, str>>>
+    headers: IndexMap<Uncased<'h>, Vec<Value<'h>>>
+}
+
+/// A header value along with its original bytes when they aren't UTF-8.
+#[derive(Clone, PartialEq)]
+struct Value<'h> {
+    value: Cow<'h, str>,
+    raw: Option<Cow<'h, [u8]>>,
+}
+
+impl<'h> From<Cow<'h, str>> for Value<'h> {
+    #[inline(always)]
+    fn from(value: Cow<'h, str>) -> Self {
+        Value { value, raw: None }
+    }
+}
+
+impl fmt::Debug for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.raw {
+            Some(ref raw) => write!(f, "{:?}", raw),
+            None => write!(f, "{:?}", self.value),
+        }
+    }
 }
 
 impl<'h> HeaderMap<'h> {
@@ -226,7 +323,45 @@ impl<'h> HeaderMap<'h> {
         self.headers
             .get(UncasedStr::new(name))
             .into_iter()
-            .flat_map(|values| values.iter().map(|val| val.borrow()))
+            .flat_map(|values| values.iter().map(|val| val.value.borrow()))
+    }
+
+    /// Returns an iterator over the exact bytes of all of the values stored in
+    /// `self` for the header with name `name`. The headers are returned in FIFO
+    /// order.
+    ///
+    /// Unlike [`HeaderMap::get()`], which returns lossy UTF-8 conversions of
+    /// values that aren't valid UTF-8, the values are returned exactly as they
+    /// were received. This is useful for headers, such as signatures, that
+    /// carry binary data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{HeaderMap, Header};
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.add_raw("X-Custom", "value_1");
+    /// map.add(Header::from_bytes("X-Custom", b"\xc3(".to_vec()));
+    ///
+    /// let mut values = map.get_bytes("X-Custom");
+    /// assert_eq!(values.next(), Some(&b"value_1"[..]));
+    /// assert_eq!(values.next(), Some(&b"\xc3("[..]));
+    /// assert_eq!(values.next(), None);
+    ///
+    /// let values: Vec<_> = map.get("X-Custom").collect();
+    /// assert_eq!(values, vec!["value_1", "\u{fffd}("]);
+    /// ```
+    #[inline]
+    pub fn get_bytes(&self, name: &str) -> impl Iterator<Item=&[u8]> {
+        self.headers
+            .get(UncasedStr::new(name))
+            .into_iter()
+            .flat_map(|values| values.iter().map(|val| match val.raw {
+                Some(ref raw) => &**raw,
+                None => val.value.as_bytes(),
+            }))
     }
 
     /// Returns the _first_ value stored for the header with name `name` if
@@ -266,7 +401,7 @@ impl<'h> HeaderMap<'h> {
     pub fn get_one<'a>(&'a self, name: &str) -> Option<&'a str> {
         self.headers.get(UncasedStr::new(name))
             .and_then(|values| {
-                if !values.is_empty() { Some(values[0].borrow()) }
+                if !values.is_empty() { Some(values[0].value.borrow()) }
                 else { None }
             })
     }
@@ -324,7 +459,8 @@ impl<'h> HeaderMap<'h> {
     #[inline(always)]
     pub fn replace<'p: 'h, H: Into<Header<'p>>>(&mut self, header: H) -> bool {
         let header = header.into();
-        self.headers.insert(header.name, vec![header.value]).is_some()
+        let value = Value { value: header.value, raw: header.raw };
+        self.headers.insert(header.name, vec![value]).is_some()
     }
 
     /// A convenience method to replace a header using a raw name and value.
@@ -377,6 +513,7 @@ impl<'h> HeaderMap<'h> {
     pub fn replace_all<'n, 'v: 'h, H>(&mut self, name: H, values: Vec<Cow<'v, str>>)
         where 'n: 'h, H: Into<Cow<'n, str>>
     {
+        let values = values.into_iter().map(Value::from).collect();
         self.headers.insert(Uncased::new(name), values);
     }
 
@@ -398,7 +535,8 @@ impl<'h> HeaderMap<'h> {
     #[inline(always)]
     pub fn add<'p: 'h, H: Into<Header<'p>>>(&mut self, header: H) {
         let header = header.into();
-        self.headers.entry(header.name).or_insert(vec![]).push(header.value);
+        let value = Value { value: header.value, raw: header.raw };
+        self.headers.entry(header.name).or_insert(vec![]).push(value);
     }
 
     /// A convenience method to add a header using a raw name and value.
@@ -457,7 +595,7 @@ impl<'h> HeaderMap<'h> {
     {
         self.headers.entry(Uncased::new(name))
             .or_insert(vec![])
-            .append(values)
+            .extend(values.drain(..).map(Value::from))
     }
 
     /// Remove all of the values for header with name `name`.
@@ -562,8 +700,10 @@ impl<'h> HeaderMap<'h> {
     /// ```
     pub fn iter(&self) -> impl Iterator<Item=Header<'_>> {
         self.headers.iter().flat_map(|(key, values)| {
-            values.iter().map(move |val| {
-                Header::new(key.as_str(), &**val)
+            values.iter().map(move |val| Header {
+                name: Uncased::new(key.as_str()),
+                value: Cow::Borrowed(&*val.value),
+                raw: val.raw.as_ref().map(|raw| Cow::Borrowed(&**raw)),
             })
         })
     }
@@ -609,8 +749,8 @@ impl<'h> HeaderMap<'h> {
     #[inline(always)]
     pub fn into_iter(self) -> impl Iterator<Item=Header<'h>> {
         self.headers.into_iter().flat_map(|(name, value)| {
-            value.into_iter().map(move |value| {
-                Header { name: name.clone(), value }
+            value.into_iter().map(move |Value { value, raw }| {
+                Header { name: name.clone(), value, raw }
             })
         })
     }
@@ -624,12 +764,13 @@ impl<'h> HeaderMap<'h> {
     pub fn into_iter_raw(self)
             -> impl Iterator<Item=(Uncased<'h>, Vec<Cow<'h, str>>)> {
         self.headers.into_iter()
+            .map(|(name, values)| (name, values.into_iter().map(|v| v.value).collect()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::HeaderMap;
+    use super::{Header, HeaderMap};
 
     #[test]
     fn case_insensitive_add_get() {
@@ -653,4 +794,22 @@ mod tests {
         let vals: Vec<_> = map.get("x-CuStOm").collect();
         assert_eq!(vals, vec!["a", "b", "c"]);
     }
+
+    #[test]
+    fn raw_bytes_survive_map_operations() {
+        let mut map = HeaderMap::new();
+        map.add(Header::from_bytes("X-Raw", &b"\xffa"[..]));
+        map.add_raw("x-raw", "b");
+
+        let raw: Vec<_> = map.get_bytes("X-RAW").collect();
+        assert_eq!(raw, vec![&b"\xffa"[..], &b"b"[..]]);
+
+        let headers: Vec<_> = map.iter().collect();
+        assert_eq!(headers[0].value_bytes(), b"\xffa");
+        assert_eq!(headers[0].value(), "\u{fffd}a");
+
+        map.replace(Header::from_bytes("X-Raw", b"\xfe".to_vec()));
+        let raw: Vec<_> = map.clone().into_iter().map(|h| h.value_bytes().to_vec()).collect();
+        assert_eq!(raw, vec![b"\xfe".to_vec()]);
+    }
 }
@@ -5,11 +5,17 @@
 //! while necessary.
 
 #[doc(hidden)] pub use hyper::{Body, Error, Request, Response};
-#[doc(hidden)] pub use hyper::body::{Bytes, HttpBody, Sender as BodySender};
+#[doc(hidden)] pub use hyper::body::{to_bytes, Bytes, HttpBody, Sender as BodySender};
 #[doc(hidden)] pub use hyper::rt::Executor;
 #[doc(hidden)] pub use hyper::server::Server;
 #[doc(hidden)] pub use hyper::service::{make_service_fn, service_fn, Service};
 #[doc(hidden)] pub use hyper::upgrade::Upgraded;
+#[doc(hidden)] pub use hyper::ext::ReasonPhrase;
+
+#[cfg(feature = "client")]
+#[doc(hidden)] pub use hyper::client::{Client, HttpConnector, ResponseFuture};
+#[cfg(feature = "client")]
+#[doc(hidden)] pub use hyper::client::connect::{Connected, Connection};
 
 #[doc(hidden)] pub use http::header::HeaderMap;
 #[doc(hidden)] pub use http::header::HeaderName as HeaderName;
@@ -17,6 +23,7 @@
 #[doc(hidden)] pub use http::method::Method;
 #[doc(hidden)] pub use http::request::Parts as RequestParts;
 #[doc(hidden)] pub use http::response::Builder as ResponseBuilder;
+#[doc(hidden)] pub use http::response::Parts as ResponseParts;
 #[doc(hidden)] pub use http::status::StatusCode;
 #[doc(hidden)] pub use http::uri::{Uri, Parts as UriParts};
 
@@ -36,9 +36,12 @@ mod content_type;
 mod status;
 mod header;
 mod accept;
+mod etag;
 mod raw_str;
 mod parse;
 mod listener;
+#[cfg(feature = "serde")]
+mod typed_cookie;
 
 /// Case-preserving, ASCII case-insensitive string types.
 ///
@@ -57,7 +60,7 @@ pub mod private {
 
     pub mod cookie {
         pub use cookie::*;
-        pub use crate::cookies::Key;
+        pub use crate::cookies::{Key, identity};
     }
 
     pub use crate::listener::{Incoming, Listener, Connection, bind_tcp};
@@ -66,8 +69,11 @@ pub mod private {
 pub use crate::method::Method;
 pub use crate::content_type::ContentType;
 pub use crate::accept::{Accept, QMediaType};
+pub use crate::etag::ETag;
 pub use crate::status::{Status, StatusClass};
 pub use crate::header::{Header, HeaderMap};
 pub use crate::raw_str::RawStr;
 pub use crate::media_type::MediaType;
-pub use crate::cookies::{Cookie, CookieJar, SameSite};
+pub use crate::cookies::{Cookie, CookieJar, CookiePolicy, CookiePrefixError, SameSite};
+#[cfg(feature = "serde")]
+pub use crate::typed_cookie::TypedCookieError;
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::Range;
 use std::borrow::Cow;
 
 use pear::error::Expected;
@@ -9,19 +10,30 @@ use crate::ext::IntoOwned;
 /// Error emitted on URI parse failure.
 ///
 /// Internally, the type includes information about where the parse error
-/// occurred (the error's context) and information about what went wrong.
-/// Externally, this information can be retrieved (in textual form) through its
-/// `Display` implementation. In other words, by printing a value of this type.
+/// occurred (the error's context) and information about what went wrong. This
+/// information can be retrieved in textual form through its `Display`
+/// implementation, that is, by printing a value of this type, or piecewise
+/// through [`Error::span()`], [`Error::found()`], and [`Error::expected()`].
 #[derive(Debug)]
 pub struct Error<'a> {
     expected: Expected<u8, Cow<'a, [u8]>>,
     index: usize,
+    found: Option<char>,
 }
 
 impl<'a> From<ParseError<RawInput<'a>>> for Error<'a> {
     fn from(inner: ParseError<RawInput<'a>>) -> Self {
         let expected = inner.error.map(|t| t.into(), |v| v.values.into());
-        Error { expected, index: inner.info.context.start }
+        Error { expected, index: inner.info.context.start, found: None }
+    }
+}
+
+impl<'a> Error<'a> {
+    /// Creates an error from the parse error `inner` for the text `input`.
+    pub(crate) fn new(inner: ParseError<RawInput<'a>>, input: &str) -> Self {
+        let mut error = Error::from(inner);
+        error.found = input.get(error.index..).and_then(|rest| rest.chars().next());
+        error
     }
 }
 
@@ -41,6 +53,62 @@ impl Error<'_> {
     pub fn index(&self) -> usize {
         self.index
     }
+
+    /// Returns the byte range in the text of the character that caused the
+    /// error. The range is empty if the error occurred at the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let err = Origin::parse("/caf\u{e9}/\u{1f680}").unwrap_err();
+    /// assert_eq!(err.span(), 4..6);
+    ///
+    /// let err = Origin::parse("").unwrap_err();
+    /// assert_eq!(err.span(), 0..0);
+    /// ```
+    pub fn span(&self) -> Range<usize> {
+        self.index..(self.index + self.found.map_or(0, char::len_utf8))
+    }
+
+    /// Returns the character in the text that caused the error, or `None` if
+    /// the error occurred at the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let err = Origin::parse("/foo bar").unwrap_err();
+    /// assert_eq!(err.found(), Some(' '));
+    /// ```
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+
+    /// Returns a description of what the parser expected to find instead of
+    /// [`Error::found()`], if it is known.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let err = Origin::parse("foo").unwrap_err();
+    /// assert_eq!(err.expected(), Some("'/'"));
+    /// ```
+    pub fn expected(&self) -> Option<&str> {
+        match self.expected {
+            Expected::Token(Some(ref expected), _) => Some(expected),
+            Expected::Slice(Some(ref expected), _) => Some(expected),
+            Expected::Eof(_) => Some("EOF"),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error<'_> {
@@ -55,7 +123,8 @@ impl IntoOwned for Error<'_> {
     fn into_owned(self) -> Error<'static> {
         Error {
             expected: self.expected.map(|t| t, |s| s.into_owned().into()),
-            index: self.index
+            index: self.index,
+            found: self.found,
         }
     }
 }
@@ -77,4 +146,19 @@ mod tests {
         check_err!("?" => "expected token '/' but found '?' at index 0");
         check_err!("这" => "expected token '/' but found byte 232 at index 0");
     }
+
+    #[test]
+    fn check_spans() {
+        let e = origin_from_str("/a/这 b").unwrap_err();
+        assert_eq!((e.index(), e.span(), e.found()), (3, 3..6, Some('这')));
+
+        let e = origin_from_str("/a?b c").unwrap_err();
+        assert_eq!((e.span(), e.found()), (4..5, Some(' ')));
+
+        let e = origin_from_str("").unwrap_err();
+        assert_eq!((e.span(), e.found()), (0..0, None));
+
+        let e = origin_from_str("a").unwrap_err();
+        assert_eq!((e.found(), e.expected()), (Some('a'), Some("'/'")));
+    }
 }
@@ -4,8 +4,11 @@ pub(crate) mod tables;
 
 #[cfg(test)] mod tests;
 
+use std::borrow::Cow;
+
 use crate::uri::{Uri, Origin, Absolute, Authority};
 
+use self::tables::{is_pchar, is_qchar};
 use self::parser::{uri, origin, authority_only, absolute_only, rocket_route_origin};
 
 pub use self::error::Error;
@@ -14,25 +17,58 @@ type RawInput<'a> = pear::input::Pear<pear::input::Cursor<&'a [u8]>>;
 
 #[inline]
 pub fn from_str(s: &str) -> Result<Uri<'_>, Error<'_>> {
-    Ok(parse!(uri: RawInput::new(s.as_bytes()))?)
+    parse!(uri: RawInput::new(s.as_bytes())).map_err(|e| Error::new(e, s))
 }
 
 #[inline]
 pub fn origin_from_str(s: &str) -> Result<Origin<'_>, Error<'_>> {
-    Ok(parse!(origin: RawInput::new(s.as_bytes()))?)
+    parse!(origin: RawInput::new(s.as_bytes())).map_err(|e| Error::new(e, s))
 }
 
 #[inline]
 pub fn route_origin_from_str(s: &str) -> Result<Origin<'_>, Error<'_>> {
-    Ok(parse!(rocket_route_origin: RawInput::new(s.as_bytes()))?)
+    parse!(rocket_route_origin: RawInput::new(s.as_bytes())).map_err(|e| Error::new(e, s))
 }
 
 #[inline]
 pub fn authority_from_str(s: &str) -> Result<Authority<'_>, Error<'_>> {
-    Ok(parse!(authority_only: RawInput::new(s.as_bytes()))?)
+    parse!(authority_only: RawInput::new(s.as_bytes())).map_err(|e| Error::new(e, s))
 }
 
 #[inline]
 pub fn absolute_from_str(s: &str) -> Result<Absolute<'_>, Error<'_>> {
-    Ok(parse!(absolute_only: RawInput::new(s.as_bytes()))?)
+    parse!(absolute_only: RawInput::new(s.as_bytes())).map_err(|e| Error::new(e, s))
+}
+
+/// Percent-encodes the characters of `s` that are invalid in the path or query
+/// of an origin URI. Borrows `s` if it contains no such characters.
+pub fn encode_invalid_origin_chars(s: &str) -> Cow<'_, str> {
+    fn encode(string: &mut String, part: &str, is_valid: fn(&u8) -> bool) {
+        for byte in part.bytes() {
+            match is_valid(&byte) {
+                true => string.push(byte as char),
+                false => string.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+    }
+
+    let (path, query) = match s.find('?') {
+        Some(i) => (&s[..i], Some(&s[(i + 1)..])),
+        None => (s, None)
+    };
+
+    if path.bytes().all(|b| is_pchar(&b))
+        && query.map_or(true, |q| q.bytes().all(|b| is_qchar(&b)))
+    {
+        return Cow::Borrowed(s);
+    }
+
+    let mut string = String::with_capacity(s.len() + 16);
+    encode(&mut string, path, is_pchar);
+    if let Some(query) = query {
+        string.push('?');
+        encode(&mut string, query, is_qchar);
+    }
+
+    Cow::Owned(string)
 }
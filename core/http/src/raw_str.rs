@@ -161,11 +161,10 @@ impl RawStr {
     /// assert_eq!(decoded, Ok("Hello, world!".to_string()));
     /// ```
     pub fn url_decode(&self) -> Result<String, Utf8Error> {
-        // TODO: Make this more efficient!
-        let replaced = self.replace("+", " ");
-        RawStr::from_str(replaced.as_str())
-            .percent_decode()
-            .map(|cow| cow.into_owned())
+        match url_decode_bytes(self.as_bytes()) {
+            Cow::Borrowed(_) => Ok(self.as_str().to_string()),
+            Cow::Owned(bytes) => String::from_utf8(bytes).map_err(|e| e.utf8_error()),
+        }
     }
 
     /// Returns a URL-decoded version of the string.
@@ -200,10 +199,13 @@ impl RawStr {
     /// assert_eq!(bad_raw_str.url_decode_lossy(), "a b=�");
     /// ```
     pub fn url_decode_lossy(&self) -> String {
-        let replaced = self.replace("+", " ");
-        RawStr::from_str(replaced.as_str())
-            .percent_decode_lossy()
-            .into_owned()
+        match url_decode_bytes(self.as_bytes()) {
+            Cow::Borrowed(_) => self.as_str().to_string(),
+            Cow::Owned(bytes) => match String::from_utf8(bytes) {
+                Ok(string) => string,
+                Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+            }
+        }
     }
 
     /// Returns an HTML escaped version of `self`. Allocates only when
@@ -423,6 +425,61 @@ impl fmt::Display for RawStr {
     }
 }
 
+#[inline(always)]
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None
+    }
+}
+
+/// URL decodes `bytes`: `+` becomes a space and `%XX` sequences are
+/// percent-decoded. Invalid percent sequences are left as-is.
+///
+/// Instead of inspecting every byte, this skips over runs of bytes that need
+/// no decoding with `memchr2`, which uses vectorized search where available.
+/// Returns `Cow::Borrowed` when no decoding is needed at all.
+fn url_decode_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let mut i = match memchr::memchr2(b'%', b'+', bytes) {
+        Some(i) => i,
+        None => return Cow::Borrowed(bytes),
+    };
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    decoded.extend_from_slice(&bytes[..i]);
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            b'%' => {
+                let hi = bytes.get(i + 1).and_then(|&b| hex_value(b));
+                let lo = bytes.get(i + 2).and_then(|&b| hex_value(b));
+                match (hi, lo) {
+                    (Some(hi), Some(lo)) => {
+                        decoded.push(hi << 4 | lo);
+                        i += 3;
+                    }
+                    _ => {
+                        decoded.push(b'%');
+                        i += 1;
+                    }
+                }
+            }
+            _ => {
+                let run = memchr::memchr2(b'%', b'+', &bytes[i..]).unwrap_or(bytes.len() - i);
+                decoded.extend_from_slice(&bytes[i..(i + run)]);
+                i += run;
+            }
+        }
+    }
+
+    Cow::Owned(decoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::RawStr;
@@ -436,4 +493,31 @@ mod tests {
         assert_eq!(raw_str, "abc".to_string());
         assert_eq!("abc".to_string(), raw_str.as_str());
     }
+
+    #[test]
+    fn url_decode_matches_percent_decode() {
+        let cases = &[
+            "", "abc", "a+b", "+", "%", "%2", "%2G", "%%41", "a%2Cb%21", "%E2%9C%93",
+            "Hello%2C+world%21", "a+%2B+b", "trailing%", "trailing%4", "%4a%4A",
+            "lots+of+plus+signs+and+%25+percents%25",
+        ];
+
+        for case in cases {
+            let replaced = case.replace("+", " ");
+            let expected = percent_encoding::percent_decode(replaced.as_bytes())
+                .decode_utf8()
+                .map(|cow| cow.into_owned());
+
+            assert_eq!(RawStr::from_str(case).url_decode(), expected, "{:?}", case);
+            assert_eq!(RawStr::from_str(case).url_decode_lossy(),
+                expected.unwrap(), "{:?}", case);
+        }
+    }
+
+    #[test]
+    fn url_decode_invalid_utf8() {
+        let raw_str = RawStr::from_str("a+%FF");
+        assert!(raw_str.url_decode().is_err());
+        assert_eq!(raw_str.url_decode_lossy(), "a \u{FFFD}");
+    }
 }
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use parking_lot::{RwLock, const_rwlock};
+
+/// Reason phrases registered via [`Status::register_reason()`].
+static REASONS: RwLock<Vec<(u16, &'static str)>> = const_rwlock(Vec::new());
+
 /// Enumeration of HTTP status classes.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum StatusClass {
@@ -195,15 +200,93 @@ impl Status {
         }
     }
 
+    /// Registers `reason` as the reason phrase of the non-standard status code
+    /// `code`, replacing any previously registered phrase. Statuses Rocket
+    /// creates from `code`, such as those of responses from `http` services
+    /// and those in error catchers, then carry `reason`, which is also sent to
+    /// HTTP/1 clients. Registering a phrase for a standard code has no effect.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Status;
+    ///
+    /// Status::register_reason(499, "Client Closed Request");
+    /// assert_eq!(Status::registered(499), Some(Status::new(499, "Client Closed Request")));
+    ///
+    /// Status::register_reason(404, "Gone Fishing");
+    /// assert_eq!(Status::registered(404), Some(Status::NotFound));
+    /// ```
+    pub fn register_reason(code: u16, reason: &'static str) {
+        if Status::from_code(code).is_some() {
+            return;
+        }
+
+        let mut reasons = REASONS.write();
+        reasons.retain(|&(c, _)| c != code);
+        reasons.push((code, reason));
+    }
+
+    /// Returns the standard status for `code` or, if `code` is non-standard,
+    /// a status with the reason phrase registered via
+    /// [`Status::register_reason()`], if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Status;
+    ///
+    /// assert_eq!(Status::registered(404), Some(Status::NotFound));
+    /// assert_eq!(Status::registered(498), None);
+    ///
+    /// Status::register_reason(498, "Invalid Token");
+    /// assert_eq!(Status::registered(498), Some(Status::new(498, "Invalid Token")));
+    /// ```
+    pub fn registered(code: u16) -> Option<Status> {
+        Status::from_code(code).or_else(|| {
+            REASONS.read().iter()
+                .find(|&&(c, _)| c == code)
+                .map(|&(code, reason)| Status::new(code, reason))
+        })
+    }
+
+    /// Returns `true` if `self`'s reason phrase differs from that of the
+    /// standard status with its code, or if its code is non-standard and
+    /// `self` has a reason phrase.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Status;
+    ///
+    /// assert!(!Status::NotFound.has_custom_reason());
+    /// assert!(!Status::raw(599).has_custom_reason());
+    /// assert!(Status::new(404, "Gone Fishing").has_custom_reason());
+    /// assert!(Status::new(599, "Network Timeout").has_custom_reason());
+    /// ```
+    pub fn has_custom_reason(&self) -> bool {
+        match Status::from_code(self.code) {
+            Some(standard) => standard.reason != self.reason,
+            None => self.reason != Status::UNKNOWN_REASON,
+        }
+    }
+
+    /// The reason phrase of statuses with unknown, non-standard codes.
+    const UNKNOWN_REASON: &'static str = "<unknown code>";
+
     /// Returns a status from a given status code. If the status code is a
-    /// standard code, then the reason phrase is populated accordingly.
-    /// Otherwise the reason phrase is set to "<unknown code>".
+    /// standard code or has a registered reason phrase, then the reason phrase
+    /// is populated accordingly. Otherwise the reason phrase is set to
+    /// "<unknown code>".
     #[inline]
     #[doc(hidden)]
     pub fn raw(code: u16) -> Status {
-        match Status::from_code(code) {
+        match Status::registered(code) {
             Some(status) => status,
-            None => Status::new(code, "<unknown code>")
+            None => Status::new(code, Status::UNKNOWN_REASON)
         }
     }
 
@@ -0,0 +1,351 @@
+use std::fmt;
+
+use serde_::{Serialize, de::DeserializeOwned};
+
+use crate::cookies::{Cookie, CookieJar, CookiePrefixError};
+
+/// The maximum size, in bytes, of the encoded name and value of a cookie that
+/// browsers are required to accept.
+const MAX_COOKIE_SIZE: usize = 4096;
+
+/// The maximum number of chunks a typed value may be split into.
+const MAX_CHUNKS: usize = 8;
+
+/// The prefix of the value of a cookie whose typed value is chunked. Since no
+/// JSON document begins with `~`, the prefix can't be confused with a value.
+const CHUNKED_PREFIX: &str = "~chunks:";
+
+/// The error returned when setting or retrieving a typed cookie fails.
+///
+/// See [`CookieJar::get_typed()`] and [`CookieJar::add_typed()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypedCookieError {
+    /// The value could not be serialized. Contains the error message.
+    Serialize(String),
+    /// The cookie's value could not be deserialized into the requested type.
+    /// Contains the error message.
+    Deserialize(String),
+    /// The serialized value is `size` bytes, which exceeds the `max` bytes
+    /// that fit in all of the permitted chunks.
+    TooLarge { size: usize, max: usize },
+    /// The chunk cookie with the given name is missing or invalid.
+    MissingChunk(String),
+    /// A cookie violates the requirements of its name's prefix.
+    Prefix(CookiePrefixError),
+}
+
+impl fmt::Display for TypedCookieError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedCookieError::Serialize(e) => write!(f, "failed to serialize cookie: {}", e),
+            TypedCookieError::Deserialize(e) => write!(f, "failed to deserialize cookie: {}", e),
+            TypedCookieError::TooLarge { size, max } => {
+                write!(f, "cookie value of {} bytes exceeds the limit of {} bytes", size, max)
+            }
+            TypedCookieError::MissingChunk(name) => write!(f, "cookie chunk `{}` is missing", name),
+            TypedCookieError::Prefix(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for TypedCookieError {}
+
+impl From<CookiePrefixError> for TypedCookieError {
+    fn from(error: CookiePrefixError) -> Self {
+        TypedCookieError::Prefix(error)
+    }
+}
+
+/// Conservative upper bound on the number of bytes `byte` occupies in a
+/// percent-encoded cookie.
+fn encoded_len(byte: u8) -> usize {
+    match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => 1,
+        _ => 3
+    }
+}
+
+fn chunk_name(name: &str, i: usize) -> String {
+    format!("{}.{}", name, i)
+}
+
+/// Returns the maximum number of bytes of a (plaintext) value that fit into a
+/// single cookie named `name`.
+fn value_budget(name: &str, private: bool) -> usize {
+    let name_len: usize = name.bytes().map(encoded_len).sum();
+    let budget = MAX_COOKIE_SIZE.saturating_sub(name_len + 1);
+    if !private {
+        return budget;
+    }
+
+    // A private value is encrypted with a 12-byte nonce and 16-byte tag, then
+    // base64 encoded. Every base64 character may need percent-encoding.
+    ((budget / 3) / 4 * 3).saturating_sub(12 + 16)
+}
+
+/// Splits `value` into the fewest pieces that each fit into a single cookie
+/// named by the respective chunk name. Never splits a character.
+fn split(name: &str, value: &str, private: bool) -> Vec<String> {
+    let budget = value_budget(&chunk_name(name, MAX_CHUNKS), private);
+    let (mut chunks, mut current, mut size) = (vec![], String::new(), 0);
+    for ch in value.chars() {
+        let mut buf = [0; 4];
+        let len = match private {
+            true => ch.len_utf8(),
+            false => ch.encode_utf8(&mut buf).bytes().map(encoded_len).sum(),
+        };
+
+        if size + len > budget && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+            size = 0;
+        }
+
+        current.push(ch);
+        size += len;
+    }
+
+    chunks.push(current);
+    chunks
+}
+
+fn chunk_count(value: &str) -> Option<usize> {
+    value.strip_prefix(CHUNKED_PREFIX)?.parse().ok().filter(|n| *n <= MAX_CHUNKS)
+}
+
+impl CookieJar<'_> {
+    fn typed_value(&self, name: &str, private: bool) -> Option<String> {
+        if private {
+            #[cfg(feature = "private-cookies")]
+            return self.get_private(name).map(|c| c.value().to_string());
+        }
+
+        self.get(name).map(|c| c.value().to_string())
+    }
+
+    fn pending_chunks(&self, name: &str, private: bool) -> usize {
+        let cookie = match private {
+            #[cfg(feature = "private-cookies")]
+            true => self.get_private_pending(name),
+            _ => self.get_pending(name),
+        };
+
+        cookie.and_then(|c| chunk_count(c.value())).unwrap_or(0)
+    }
+
+    fn get_typed_inner<T>(&self, name: &str, private: bool)
+        -> Option<Result<T, TypedCookieError>>
+        where T: DeserializeOwned
+    {
+        let head = self.typed_value(name, private)?;
+        let value = match chunk_count(&head) {
+            None => head,
+            Some(n) => {
+                let mut value = String::new();
+                for i in 0..n {
+                    let name = chunk_name(name, i);
+                    match self.typed_value(&name, private) {
+                        Some(chunk) => value.push_str(&chunk),
+                        None => return Some(Err(TypedCookieError::MissingChunk(name))),
+                    }
+                }
+
+                value
+            }
+        };
+
+        Some(serde_json::from_str(&value)
+            .map_err(|e| TypedCookieError::Deserialize(e.to_string())))
+    }
+
+    fn add_typed_inner<T>(&self, name: &str, value: &T, private: bool)
+        -> Result<(), TypedCookieError>
+        where T: Serialize + ?Sized
+    {
+        let json = serde_json::to_string(value)
+            .map_err(|e| TypedCookieError::Serialize(e.to_string()))?;
+
+        let add = |name: String, value: String| -> Result<(), CookiePrefixError> {
+            let cookie = Cookie::new(name, value);
+            match private {
+                #[cfg(feature = "private-cookies")]
+                true => self.try_add_private(cookie),
+                _ => self.try_add(cookie),
+            }
+        };
+
+        let stale = self.pending_chunks(name, private);
+        let single = match private {
+            true => json.len(),
+            false => json.bytes().map(encoded_len).sum(),
+        };
+
+        let chunks = if single <= value_budget(name, private) {
+            add(name.into(), json)?;
+            0
+        } else {
+            let chunks = split(name, &json, private);
+            if chunks.len() > MAX_CHUNKS {
+                let max = value_budget(&chunk_name(name, MAX_CHUNKS), private) * MAX_CHUNKS;
+                return Err(TypedCookieError::TooLarge { size: single, max });
+            }
+
+            let n = chunks.len();
+            add(name.into(), format!("{}{}", CHUNKED_PREFIX, n))?;
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                add(chunk_name(name, i), chunk)?;
+            }
+
+            n
+        };
+
+        for i in chunks..stale {
+            self.remove(Cookie::named(chunk_name(name, i)));
+        }
+
+        Ok(())
+    }
+
+    fn remove_typed_inner(&self, name: &str, private: bool) {
+        for i in 0..self.pending_chunks(name, private) {
+            self.remove(Cookie::named(chunk_name(name, i)));
+        }
+
+        self.remove(Cookie::named(name.to_string()));
+    }
+
+    /// Retrieves the _original_ cookie named `name` and deserializes its JSON
+    /// value into a `T`. If there is no such cookie, returns `None`. If the
+    /// value fails to deserialize, returns `Some(Err)`.
+    ///
+    /// Values that were split into chunks by [`CookieJar::add_typed()`] are
+    /// reassembled before being deserialized.
+    ///
+    /// **Note:** Like [`CookieJar::get()`], this method _does not_ observe
+    /// changes made via additions and removals to the cookie jar.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::collections::HashMap;
+    /// use rocket::http::CookieJar;
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) -> Option<String> {
+    ///     let mut prefs = jar.get_typed::<HashMap<String, String>>("prefs")?.ok()?;
+    ///     prefs.remove("theme")
+    /// }
+    /// ```
+    pub fn get_typed<T>(&self, name: &str) -> Option<Result<T, TypedCookieError>>
+        where T: DeserializeOwned
+    {
+        self.get_typed_inner(name, false)
+    }
+
+    /// Like [`CookieJar::get_typed()`] but retrieves, authenticates, and
+    /// decrypts a private cookie set with [`CookieJar::add_private_typed()`].
+    #[cfg(feature = "private-cookies")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn get_private_typed<T>(&self, name: &str) -> Option<Result<T, TypedCookieError>>
+        where T: DeserializeOwned
+    {
+        self.get_typed_inner(name, true)
+    }
+
+    /// Serializes `value` to JSON and adds it to this collection as the value
+    /// of a cookie named `name`. The cookie receives the same defaults as
+    /// those applied by [`CookieJar::add()`].
+    ///
+    /// Browsers only accept cookies of up to 4096 bytes. If the encoded value
+    /// doesn't fit into a single cookie, it is split across up to 8 _chunk_
+    /// cookies named `name.0`, `name.1`, and so on, and transparently
+    /// reassembled by [`CookieJar::get_typed()`]. Stale chunks from a previous
+    /// value are removed. If the value doesn't fit into 8 chunks, or if it
+    /// fails to serialize, nothing is added and an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::collections::HashMap;
+    /// use rocket::http::CookieJar;
+    ///
+    /// #[get("/")]
+    /// fn handler(jar: &CookieJar<'_>) {
+    ///     let mut prefs = HashMap::new();
+    ///     prefs.insert("theme", "dark");
+    ///     jar.add_typed("prefs", &prefs).expect("small value");
+    /// }
+    /// ```
+    pub fn add_typed<T>(&self, name: &str, value: &T) -> Result<(), TypedCookieError>
+        where T: Serialize + ?Sized
+    {
+        self.add_typed_inner(name, value, false)
+    }
+
+    /// Like [`CookieJar::add_typed()`] but adds private cookies, encrypted as
+    /// with [`CookieJar::add_private()`] and receiving the same defaults.
+    /// Each chunk is encrypted separately.
+    #[cfg(feature = "private-cookies")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn add_private_typed<T>(&self, name: &str, value: &T) -> Result<(), TypedCookieError>
+        where T: Serialize + ?Sized
+    {
+        self.add_typed_inner(name, value, true)
+    }
+
+    /// Removes the typed cookie named `name` and any of its chunks. The
+    /// cookies are expected to have the default `"/"` path and no domain.
+    pub fn remove_typed(&self, name: &str) {
+        self.remove_typed_inner(name, false)
+    }
+
+    /// Removes the private typed cookie named `name` and any of its chunks.
+    /// The cookies are expected to have the default `"/"` path and no domain.
+    #[cfg(feature = "private-cookies")]
+    #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+    pub fn remove_private_typed(&self, name: &str) {
+        self.remove_typed_inner(name, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cookies::Key;
+
+    #[test]
+    fn split_respects_budget() {
+        let value = "a".repeat(10_000);
+        let chunks = split("name", &value, false);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.concat(), value);
+        assert!(chunks.iter().all(|c| c.len() <= value_budget("name.8", false)));
+
+        let value = "\u{e9}\"".repeat(2_000);
+        let chunks = split("name", &value, false);
+        assert_eq!(chunks.concat(), value);
+        assert!(chunks.iter().all(|c| c.bytes().map(encoded_len).sum::<usize>() <= 4090));
+    }
+
+    #[test]
+    fn typed_chunked_round_trip() {
+        let key = Key::generate();
+        let jar = CookieJar::new(&key);
+        let value: Vec<String> = (0..500).map(|i| format!("item-{}", i)).collect();
+        jar.add_typed("list", &value).unwrap();
+
+        let mut original = CookieJar::new(&key);
+        for cookie in jar.take_delta() {
+            original.add_original(cookie);
+        }
+
+        assert!(original.get("list.0").is_some());
+        assert_eq!(original.get_typed::<Vec<String>>("list"), Some(Ok(value)));
+        assert_eq!(original.get_typed::<u8>("missing"), None);
+        assert!(matches!(original.get_typed::<u8>("list"), Some(Err(_))));
+
+        let huge = "x".repeat(40_000);
+        assert!(matches!(jar.add_typed("huge", &huge), Err(TypedCookieError::TooLarge { .. })));
+    }
+}
@@ -89,6 +89,24 @@ impl<'a> Absolute<'a> {
         crate::parse::uri::absolute_from_str(string)
     }
 
+    /// Parses the string `string` into an `Absolute`, taking ownership of the
+    /// string. Returns an `Error` if `string` is not a valid absolute URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Absolute;
+    ///
+    /// let uri = Absolute::parse_owned("https://rocket.rs/guide".to_string());
+    /// assert_eq!(uri.unwrap().origin().unwrap().path(), "/guide");
+    /// ```
+    pub fn parse_owned(string: String) -> Result<Absolute<'static>, Error<'static>> {
+        Absolute::parse(&string)
+            .map(|uri| uri.into_owned())
+            .map_err(|e| e.into_owned())
+    }
+
     /// Returns the scheme part of the absolute URI.
     ///
     /// # Example
@@ -148,6 +166,81 @@ impl<'a> Absolute<'a> {
     pub fn origin(&self) -> Option<&Origin<'a>> {
         self.origin.as_ref()
     }
+
+    /// Returns a new `Absolute` URI with the scheme and authority of `self`
+    /// whose origin is the path of `self`, if any, followed by `origin`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::{Absolute, Origin};
+    ///
+    /// let base = Absolute::parse("https://rocket.rs").unwrap();
+    /// let uri = base.join(&Origin::parse("/guide?v=0.5").unwrap());
+    /// assert_eq!(uri.to_string(), "https://rocket.rs/guide?v=0.5");
+    ///
+    /// let base = Absolute::parse("https://rocket.rs/app/").unwrap();
+    /// let uri = base.join(&Origin::parse("/guide").unwrap());
+    /// assert_eq!(uri.to_string(), "https://rocket.rs/app/guide");
+    /// ```
+    pub fn join(&self, origin: &Origin<'_>) -> Absolute<'static> {
+        let prefix = self.origin()
+            .map(|o| o.path().trim_end_matches('/'))
+            .unwrap_or("");
+
+        let string = match self.authority() {
+            Some(authority) => format!("{}://{}{}{}", self.scheme(), authority, prefix, origin),
+            None => format!("{}:{}{}", self.scheme(), prefix, origin),
+        };
+
+        Absolute::parse_owned(string).expect("joining valid URIs yields a valid URI")
+    }
+}
+
+/// A source of the scheme, authority, and optional path prefix of absolute
+/// URIs generated with `uri!(base = ..., ...)`.
+///
+/// Implementations exist for `str`, `String`, and [`Absolute`] in this crate,
+/// and for Rocket's `Request` and `Config`. A base string must be a valid
+/// absolute URI; if it is not, [`UriBase::uri_base()`] panics.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::uri::{UriBase, Origin};
+///
+/// let base = "https://rocket.rs".uri_base();
+/// assert_eq!(base.join(&Origin::parse("/").unwrap()).to_string(), "https://rocket.rs/");
+/// ```
+pub trait UriBase {
+    /// Returns the base URI.
+    fn uri_base(&self) -> Absolute<'_>;
+}
+
+impl UriBase for str {
+    fn uri_base(&self) -> Absolute<'_> {
+        Absolute::parse(self).unwrap_or_else(|e| panic!("invalid URI base {:?}: {}", self, e))
+    }
+}
+
+impl UriBase for String {
+    fn uri_base(&self) -> Absolute<'_> {
+        self.as_str().uri_base()
+    }
+}
+
+impl UriBase for Absolute<'_> {
+    fn uri_base(&self) -> Absolute<'_> {
+        self.clone()
+    }
+}
+
+impl<T: UriBase + ?Sized> UriBase for &T {
+    fn uri_base(&self) -> Absolute<'_> {
+        (**self).uri_base()
+    }
 }
 
 impl<'b> PartialEq<Absolute<'b>> for Absolute<'_> {
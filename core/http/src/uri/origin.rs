@@ -227,6 +227,40 @@ impl<'a> Origin<'a> {
         Ok(uri)
     }
 
+    /// Parses the string `string` into an `Origin`, percent-encoding any
+    /// characters that are invalid in its path or query instead of failing.
+    /// Allocates only if there are such characters. Returns an `Error`, about
+    /// `string` as given, if `string` is invalid for any other reason, such as
+    /// not beginning with `/`.
+    ///
+    /// This method is intended for ingesting malformed URIs from legacy
+    /// clients. Prefer [`Origin::parse()`](crate::uri::Origin::parse()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let uri = Origin::parse_lenient("/a b/\"c\"?q=a b").expect("lenient URI");
+    /// assert_eq!(uri.path(), "/a%20b/%22c%22");
+    /// assert_eq!(uri.query(), Some("q=a%20b"));
+    ///
+    /// let err = Origin::parse_lenient("a b").unwrap_err();
+    /// assert_eq!(err.index(), 0);
+    /// ```
+    pub fn parse_lenient(string: &'a str) -> Result<Origin<'a>, Error<'a>> {
+        let error = match Origin::parse(string) {
+            Ok(origin) => return Ok(origin),
+            Err(error) => error,
+        };
+
+        match crate::parse::uri::encode_invalid_origin_chars(string) {
+            Cow::Owned(encoded) => Origin::parse_owned(encoded).map_err(|_| error),
+            Cow::Borrowed(_) => Err(error),
+        }
+    }
+
     /// Returns `true` if `self` is normalized. Otherwise, returns `false`.
     ///
     /// See [Normalization](#normalization) for more information on what it
@@ -640,4 +674,19 @@ mod tests {
         assert_eq!(uri_to_string("/a/b///c"), "/a/b/c".to_string());
         assert_eq!(uri_to_string("/a///b/c/d///"), "/a/b/c/d".to_string());
     }
+
+    #[test]
+    fn lenient() {
+        let lenient = |s| Origin::parse_lenient(s).map(|uri| uri.to_string());
+
+        assert_eq!(lenient("/a/b?c=d").unwrap(), "/a/b?c=d");
+        assert_eq!(lenient("/a b").unwrap(), "/a%20b");
+        assert_eq!(lenient("/{a}|b?{c}|d").unwrap(), "/%7Ba%7D%7Cb?{c}|d");
+        assert_eq!(lenient("/caf\u{e9}?q=\"x\"").unwrap(), "/caf%C3%A9?q=%22x%22");
+        assert_eq!(lenient("/a?b?c d#e").unwrap(), "/a?b?c%20d%23e");
+
+        let err = lenient("a b").unwrap_err();
+        assert_eq!((err.index(), err.found()), (0, Some('a')));
+        assert!(lenient("").is_err());
+    }
 }
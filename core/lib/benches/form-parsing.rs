@@ -0,0 +1,92 @@
+#[macro_use] extern crate rocket;
+#[macro_use] extern crate bencher;
+
+use bencher::Bencher;
+use rocket::request::{Form, FormItems};
+use rocket::http::{ContentType, RawStr};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Message {
+    author: String,
+    subject: String,
+    body: String,
+}
+
+#[post("/", data = "<form>")]
+fn post(form: Form<Message>) -> String { form.into_inner().body }
+
+#[post("/items", data = "<form>")]
+fn post_items(form: String) -> String {
+    FormItems::from(&*form).map(|item| item.key_value_decoded().1).collect()
+}
+
+fn rocket() -> rocket::Rocket {
+    let config = rocket::Config::figment()
+        .merge(("log_level", "off"))
+        .merge(("limits.forms", 8 * 1024 * 1024));
+
+    rocket::custom(config).mount("/", routes![post, post_items])
+}
+
+fn encoded(value: &str, repeat: usize) -> String {
+    value.repeat(repeat)
+}
+
+fn large_form(body: &str) -> String {
+    format!("author=Jane+Doe&subject=A+Very+Long+Message&body={}", body)
+}
+
+fn url_decode_plain(b: &mut Bencher) {
+    let string = encoded("abcdefghijklmnopqrstuvwxyz0123456789", 32 * 1024);
+    let raw = RawStr::from_str(&string);
+    b.bytes = string.len() as u64;
+    b.iter(|| raw.url_decode());
+}
+
+fn url_decode_sparse(b: &mut Bencher) {
+    let string = encoded("abcdefghijklmnopqrstuvwxyz+0123456789%21", 32 * 1024);
+    let raw = RawStr::from_str(&string);
+    b.bytes = string.len() as u64;
+    b.iter(|| raw.url_decode());
+}
+
+fn url_decode_dense(b: &mut Bencher) {
+    let string = encoded("%E2%9C%93+%2C%21", 32 * 1024);
+    let raw = RawStr::from_str(&string);
+    b.bytes = string.len() as u64;
+    b.iter(|| raw.url_decode());
+}
+
+fn form_items_many_fields(b: &mut Bencher) {
+    let string = encoded("key=value+with+spaces%21&", 16 * 1024);
+    b.bytes = string.len() as u64;
+    b.iter(|| FormItems::from(&*string).map(|i| i.key_value_decoded()).count());
+}
+
+fn post_large_form(b: &mut Bencher) {
+    let client = Client::tracked(rocket()).unwrap();
+    let form = large_form(&encoded("Hello%2C+world%21+", 64 * 1024));
+    let request = client.post("/").header(ContentType::Form).body(&form);
+    b.bytes = form.len() as u64;
+    b.iter(|| { request.clone().dispatch(); });
+}
+
+fn post_large_form_items(b: &mut Bencher) {
+    let client = Client::tracked(rocket()).unwrap();
+    let form = encoded("key=value+with+spaces%21&", 64 * 1024);
+    let request = client.post("/items").header(ContentType::Form).body(&form);
+    b.bytes = form.len() as u64;
+    b.iter(|| { request.clone().dispatch(); });
+}
+
+benchmark_main!(benches);
+benchmark_group! {
+    benches,
+    url_decode_plain,
+    url_decode_sparse,
+    url_decode_dense,
+    form_items_many_fields,
+    post_large_form,
+    post_large_form_items,
+}
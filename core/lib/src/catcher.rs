@@ -49,8 +49,30 @@ pub type ErrorHandlerFuture<'r> = BoxFuture<'r, Result<'r>>;
 /// # Built-In Default Catcher
 ///
 /// Rocket's built-in default catcher can handle all errors. It produces HTML or
-/// JSON, depending on the value of the `Accept` header. As such, catchers only
-/// need to be registered if an error needs to be handled in a custom fashion.
+/// JSON, depending on the value of the `Accept` header. Requests under one of
+/// the mount points in the `json_errors` configuration parameter always receive
+/// JSON, which makes the catcher suitable for API subtrees without registering
+/// any catchers. JSON errors include the status code, reason, and description,
+/// as well as the request's `X-Request-Id` header, if any:
+///
+/// ```json
+/// {
+///   "error": {
+///     "code": 404,
+///     "reason": "Not Found",
+///     "description": "The requested resource could not be found.",
+///     "request_id": "f058ebd6"
+///   }
+/// }
+/// ```
+///
+/// HTML error pages are footed with the server's configured
+/// [`ident`](crate::Config::ident), or have no footer if it is hidden. They
+/// can be replaced wholesale by registering an [`ErrorPageProvider`].
+///
+/// As such, catchers only need to be registered if an error needs to be
+/// handled in a custom fashion. Handlers can return typed application errors
+/// that render in the same way via [`Failure`](crate::response::Failure).
 ///
 /// # Code Generation
 ///
@@ -258,6 +280,64 @@ impl<F: Clone + Sync + Send + 'static> ErrorHandler for F
     }
 }
 
+/// A provider of the HTML pages of Rocket's built-in default catcher.
+///
+/// Error page providers are registered via [`Rocket::error_pages()`] and
+/// consulted, in the order they were registered, whenever the built-in default
+/// catcher responds with HTML. The first page returned is used. If every
+/// provider returns `None`, or none is registered, the built-in page is used.
+/// Providers thus customize the default error pages without registering a
+/// catcher per status code. They are not consulted for JSON errors nor when a
+/// registered catcher handles the error.
+///
+/// Any function or closure of type `Fn(Status, &Request<'_>) -> Option<String>`
+/// is an `ErrorPageProvider`.
+///
+/// [`Rocket::error_pages()`]: crate::Rocket::error_pages()
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::Request;
+/// use rocket::http::Status;
+/// use rocket::catcher::description;
+///
+/// let rocket = rocket::ignite().error_pages(|status: Status, _: &Request<'_>| {
+///     match status.code {
+///         404 => Some("<h1>Nothing to see here.</h1>".into()),
+///         _ => Some(format!("<h1>{}</h1><p>{}</p>", status, description(status))),
+///     }
+/// });
+/// ```
+pub trait ErrorPageProvider: Send + Sync + 'static {
+    /// Returns the HTML page for an error with `status` in response to `req`,
+    /// or `None` to defer to the next provider or the built-in page.
+    fn render(&self, status: Status, req: &Request<'_>) -> Option<String>;
+}
+
+impl<F: Send + Sync + 'static> ErrorPageProvider for F
+    where F: Fn(Status, &Request<'_>) -> Option<String>
+{
+    fn render(&self, status: Status, req: &Request<'_>) -> Option<String> {
+        self(status, req)
+    }
+}
+
+/// Returns the description of `status` used in the default catcher's error
+/// pages, such as "The requested resource could not be found." for `404`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::catcher::description;
+///
+/// assert_eq!(description(Status::NotFound), "The requested resource could not be found.");
+/// ```
+pub fn description(status: Status) -> &'static str {
+    describe(status).1
+}
+
 #[doc(hidden)]
 impl From<StaticCatcherInfo> for Catcher {
     #[inline]
@@ -283,8 +363,21 @@ impl fmt::Debug for Catcher {
     }
 }
 
+macro_rules! html_error_footer {
+    ($ident:expr) => (
+        concat!(r#"
+                <div role="contentinfo" align="center">
+                    <small>"#, $ident, r#"</small>
+                </div>"#
+        )
+    )
+}
+
 macro_rules! html_error_template {
     ($code:expr, $reason:expr, $description:expr) => (
+        html_error_template!($code, $reason, $description, html_error_footer!("Rocket"))
+    );
+    ($code:expr, $reason:expr, $description:expr, $footer:expr) => (
         concat!(r#"
             <!DOCTYPE html>
             <html lang="en">
@@ -297,10 +390,7 @@ macro_rules! html_error_template {
                     <h1>"#, $code, ": ", $reason, r#"</h1>
                     <p>"#, $description, r#"</p>
                     <hr />
-                </div>
-                <div role="contentinfo" align="center">
-                    <small>Rocket</small>
-                </div>
+                </div>"#, $footer, r#"
             </body>
             </html>
         "#
@@ -308,55 +398,100 @@ macro_rules! html_error_template {
     )
 }
 
-macro_rules! json_error_template {
-    ($code:expr, $reason:expr, $description:expr) => (
-        concat!(
-r#"{
-  "error": {
-    "code": "#, $code, r#",
-    "reason": ""#, $reason, r#"",
-    "description": ""#, $description, r#""
-  }
-}"#
-        )
-    )
+/// Returns `true` if the default catcher should respond to `req` with JSON:
+/// the request's path is under one of the configured `json_errors` mount
+/// points, or the request prefers JSON.
+pub(crate) fn wants_json(req: &Request<'_>) -> bool {
+    let path = req.uri().path();
+    let under_json_mount = req.state.config.json_errors.iter().any(|base| {
+        let base = base.trim_end_matches('/');
+        match path.strip_prefix(base) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    });
+
+    under_json_mount || req.accept().map(|a| a.preferred().is_json()).unwrap_or(false)
 }
 
-// This is unfortunate, but the `{`, `}` above make it unusable for `format!`.
-macro_rules! json_error_fmt_template {
-    ($code:expr, $reason:expr, $description:expr) => (
-        concat!(
-r#"{{
-  "error": {{
-    "code": "#, $code, r#",
-    "reason": ""#, $reason, r#"",
-    "description": ""#, $description, r#""
-  }}
-}}"#
-        )
-    )
+#[derive(serde::Serialize)]
+struct JsonError<'a> {
+    code: u16,
+    reason: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<serde_json::Value>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonErrorBody<'a> {
+    error: JsonError<'a>,
+}
+
+/// Responds with the default catcher's JSON error body for `status`, with
+/// `detail`, if any, as an additional `detail` member.
+pub(crate) fn json_error(
+    status: crate::http::Status,
+    req: &Request<'_>,
+    detail: Option<serde_json::Value>,
+) -> Result<'static> {
+    use crate::response::{content, status, Responder};
+
+    let (reason, description) = describe(status);
+    let request_id = req.headers().get_one("X-Request-Id");
+    let error = JsonError { code: status.code, reason, description, request_id, detail };
+    let json = serde_json::to_string_pretty(&JsonErrorBody { error })
+        .map_err(|_| crate::http::Status::InternalServerError)?;
+
+    status::Custom(status, content::Json(json)).respond_to(req)
 }
 
 macro_rules! default_catcher_fn {
     ($($code:expr, $reason:expr, $description:expr),+) => (
         use std::borrow::Cow;
-        use crate::http::Status;
+        use crate::http::{Status, RawStr};
         use crate::response::{content, status, Responder};
 
+        /// Returns the reason and description of `status` used in error bodies.
+        fn describe(status: Status) -> (&'static str, &'static str) {
+            match status.code {
+                $($code => ($reason, $description),)*
+                _ if status.has_custom_reason() => (status.reason, "An error has occurred."),
+                _ => ("Unknown Error", "An unknown error has occurred."),
+            }
+        }
+
         pub(crate) fn default<'r>(status: Status, req: &'r Request<'_>) -> Result<'r> {
-            if req.accept().map(|a| a.preferred().is_json()).unwrap_or(false) {
-                let json: Cow<'_, str> = match status.code {
-                    $($code => json_error_template!($code, $reason, $description).into(),)*
-                    code => format!(json_error_fmt_template!("{}", "Unknown Error",
-                            "An unknown error has occurred."), code).into()
+            if wants_json(req) {
+                json_error(status, req, None)
+            } else {
+                for provider in &req.state.rocket.error_pages {
+                    if let Some(html) = provider.render(status, req) {
+                        return status::Custom(status, content::Html(html)).respond_to(req);
+                    }
+                }
+
+                // Unless the identity is the default, show it, escaped, in the
+                // footer or, if it is hidden, show no footer at all.
+                let footer = match req.state.config.ident.as_str() {
+                    Some("Rocket") => None,
+                    Some(ident) => {
+                        let ident = RawStr::from_str(ident).html_escape();
+                        Some(format!(html_error_footer!("{}"), ident))
+                    }
+                    None => Some(String::new()),
                 };
 
-                status::Custom(status, content::Json(json)).respond_to(req)
-            } else {
-                let html: Cow<'_, str> = match status.code {
-                    $($code => html_error_template!($code, $reason, $description).into(),)*
-                    code => format!(html_error_template!("{}", "Unknown Error",
-                            "An unknown error has occurred."), code, code).into(),
+                let html: Cow<'_, str> = match (status.code, footer) {
+                    $(($code, None) => html_error_template!($code, $reason, $description).into(),)*
+                    (code, footer) => {
+                        let (reason, description) = describe(status);
+                        let footer = footer.as_deref().unwrap_or(html_error_footer!("Rocket"));
+                        format!(html_error_template!("{0}", "{1}", "{2}", "{3}"), code, reason,
+                            description, footer).into()
+                    }
                 };
 
                 status::Custom(status, content::Html(html)).respond_to(req)
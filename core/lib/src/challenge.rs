@@ -0,0 +1,334 @@
+//! Bot detection and request challenges.
+//!
+//! When a [`Challenges`] is in managed state, its [`ChallengePolicy`]
+//! classifies every request after request fairings have run and before it is
+//! routed. The policy returns a [`Verdict`]:
+//!
+//!   * [`Verdict::Pass`]: the request is routed as usual.
+//!   * [`Verdict::Tarpit`]: the request is delayed by the given duration and
+//!     then routed as usual, slowing down abusive clients without revealing
+//!     that they have been detected.
+//!   * [`Verdict::Challenge`]: the request is not routed. Instead, it is
+//!     answered with the challenge response configured via
+//!     [`Challenges::respond_with()`] or, if none was configured, by the `403
+//!     Forbidden` catcher.
+//!
+//! Policies classify requests by their headers, such as the
+//! [`UserAgent`](crate::request::UserAgent), and by [`Signals`]: the
+//! client's trusted IP address, the number of requests it has made in the
+//! current rate window, and the application's [`Load`]. Rocket counts requests
+//! per client IP for every request a policy sees, so policies can act on
+//! request rates without a separate rate limiter. Because policies run after
+//! request fairings, requests that fairings reroute, such as those rejected
+//! by `rocket_contrib`'s IP filter, are classified as rerouted. Policies may
+//! also consult such filters directly.
+//!
+//! # Example
+//!
+//! Challenge clients that claim to be bots, and tarpit clients that make more
+//! than 100 requests per minute:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::io::Cursor;
+//! use std::time::Duration;
+//!
+//! use rocket::{Request, Response};
+//! use rocket::challenge::{Challenges, Signals, Verdict};
+//! use rocket::http::{ContentType, Status};
+//! use rocket::request::UserAgent;
+//!
+//! const CHALLENGE: &str = "<p>Please enable JavaScript to continue.</p>";
+//!
+//! fn classify(req: &Request<'_>, signals: &Signals<'_>) -> Verdict {
+//!     if UserAgent::of(req).map_or(true, |ua| ua.is_bot()) {
+//!         Verdict::Challenge
+//!     } else if signals.requests() > 100 {
+//!         Verdict::Tarpit(Duration::from_secs(2))
+//!     } else {
+//!         Verdict::Pass
+//!     }
+//! }
+//!
+//! fn challenge<'r>(_: &'r Request<'_>) -> rocket::response::Result<'r> {
+//!     Response::build()
+//!         .status(Status::Forbidden)
+//!         .header(ContentType::HTML)
+//!         .sized_body(CHALLENGE.len(), Cursor::new(CHALLENGE))
+//!         .ok()
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     let challenges = Challenges::new(classify)
+//!         .window(Duration::from_secs(60))
+//!         .respond_with(challenge);
+//!
+//!     rocket::ignite().manage(challenges)
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::{Rocket, Request};
+use crate::load::Load;
+use crate::response::{self, Response};
+
+/// The default length of the window in which requests per client are counted.
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// The default maximum number of clients whose requests are counted at once.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// The classification of a request by a [`ChallengePolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Route the request as usual.
+    Pass,
+    /// Delay the request by the duration, then route it as usual.
+    Tarpit(Duration),
+    /// Answer the request with the challenge response instead of routing it.
+    Challenge,
+}
+
+/// Signals about a request's client available to a [`ChallengePolicy`].
+#[derive(Debug)]
+pub struct Signals<'a> {
+    client_ip: Option<IpAddr>,
+    requests: usize,
+    load: &'a Load,
+}
+
+impl<'a> Signals<'a> {
+    /// Returns the client's IP address as determined by
+    /// [`Request::trusted_client_ip()`], if it is known.
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    /// Returns the number of requests, including this one, the client has
+    /// made in the current rate window. Requests from clients without a known
+    /// IP address are counted together, as are requests from IPv6 addresses
+    /// in the same `/64` network, which is typically held by a single client.
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    /// Returns the application's current request load.
+    pub fn load(&self) -> &'a Load {
+        self.load
+    }
+}
+
+/// A policy classifying requests before they are routed.
+///
+/// A policy takes effect when wrapped in a [`Challenges`] placed in managed
+/// state. Any function or closure of type `Fn(&Request, &Signals) -> Verdict`
+/// is a `ChallengePolicy`.
+pub trait ChallengePolicy: Send + Sync + 'static {
+    /// Classifies `req` given the client's `signals`.
+    fn classify(&self, req: &Request<'_>, signals: &Signals<'_>) -> Verdict;
+}
+
+impl<F> ChallengePolicy for F
+    where F: Fn(&Request<'_>, &Signals<'_>) -> Verdict + Send + Sync + 'static
+{
+    fn classify(&self, req: &Request<'_>, signals: &Signals<'_>) -> Verdict {
+        self(req, signals)
+    }
+}
+
+/// A type-erased challenge response generator.
+type ChallengeResponder =
+    Box<dyn for<'r> Fn(&'r Request<'_>) -> response::Result<'r> + Send + Sync>;
+
+/// A client's rate window: when it started and how many requests it saw.
+struct Window {
+    start: Instant,
+    count: usize,
+}
+
+/// The rate windows of at most `capacity` clients.
+struct Clients {
+    windows: HashMap<Option<IpAddr>, Window>,
+    /// Clients in the order their windows started. An entry is stale if the
+    /// client's window has since been restarted or removed.
+    order: VecDeque<(Option<IpAddr>, Instant)>,
+    last_prune: Option<Instant>,
+}
+
+impl Clients {
+    fn is_current(&self, client: &Option<IpAddr>, start: Instant) -> bool {
+        self.windows.get(client).map_or(false, |w| w.start == start)
+    }
+}
+
+/// The challenge policy of an application, in managed state.
+///
+/// See the [module level documentation](crate::challenge) for details.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::Request;
+/// use rocket::challenge::{Challenges, Signals, Verdict};
+///
+/// fn require_user_agent(req: &Request<'_>, _: &Signals<'_>) -> Verdict {
+///     match req.headers().contains("User-Agent") {
+///         true => Verdict::Pass,
+///         false => Verdict::Challenge,
+///     }
+/// }
+///
+/// let rocket = rocket::ignite().manage(Challenges::new(require_user_agent));
+/// ```
+pub struct Challenges {
+    policy: Box<dyn ChallengePolicy>,
+    responder: Option<ChallengeResponder>,
+    window: Duration,
+    capacity: usize,
+    clients: Mutex<Clients>,
+}
+
+impl Challenges {
+    /// Wraps `policy` for placement in managed state. Requests are counted in
+    /// windows of 60 seconds, and challenges are answered by the `403`
+    /// catcher.
+    pub fn new<P: ChallengePolicy>(policy: P) -> Challenges {
+        Challenges {
+            policy: Box::new(policy),
+            responder: None,
+            window: DEFAULT_WINDOW,
+            capacity: DEFAULT_CAPACITY,
+            clients: Mutex::new(Clients {
+                windows: HashMap::new(),
+                order: VecDeque::new(),
+                last_prune: None,
+            }),
+        }
+    }
+
+    /// Sets the length of the window in which requests per client are
+    /// counted for [`Signals::requests()`].
+    pub fn window(mut self, window: Duration) -> Challenges {
+        self.window = window;
+        self
+    }
+
+    /// Sets the maximum number of clients whose requests are counted at once
+    /// to `capacity`. When a new client arrives at capacity, the client whose
+    /// window started earliest is forgotten. Defaults to `10,000`.
+    pub fn capacity(mut self, capacity: usize) -> Challenges {
+        self.capacity = std::cmp::max(capacity, 1);
+        self
+    }
+
+    /// Sets the function generating the response to challenged requests. If
+    /// the function fails, the request is answered by the catcher for the
+    /// returned status.
+    pub fn respond_with<F>(mut self, responder: F) -> Challenges
+        where F: for<'r> Fn(&'r Request<'_>) -> response::Result<'r> + Send + Sync + 'static
+    {
+        self.responder = Some(Box::new(responder));
+        self
+    }
+
+    /// Counts a request from `client` at `now` and returns the client's count
+    /// in the current window.
+    fn count(&self, client: Option<IpAddr>, now: Instant) -> usize {
+        let client = client.map(bucket);
+        let window = self.window;
+        let mut clients = self.clients.lock();
+
+        // Expired windows are pruned at most once per window length.
+        if clients.last_prune.map_or(true, |t| now.saturating_duration_since(t) >= window) {
+            clients.windows.retain(|_, w| now.saturating_duration_since(w.start) < window);
+            let order: VecDeque<_> = std::mem::take(&mut clients.order).into_iter()
+                .filter(|(client, start)| clients.is_current(client, *start))
+                .collect();
+
+            clients.order = order;
+
+            clients.last_prune = Some(now);
+        }
+
+        let expired = clients.windows.get(&client)
+            .map_or(true, |w| now.saturating_duration_since(w.start) >= window);
+
+        if expired {
+            if !clients.windows.contains_key(&client) {
+                while clients.windows.len() >= self.capacity {
+                    match clients.order.pop_front() {
+                        Some((oldest, start)) if clients.is_current(&oldest, start) => {
+                            clients.windows.remove(&oldest);
+                        }
+                        Some(_) => continue,
+                        None => break,
+                    }
+                }
+            }
+
+            clients.windows.insert(client, Window { start: now, count: 0 });
+            clients.order.push_back((client, now));
+        }
+
+        let entry = clients.windows.get_mut(&client).expect("client window");
+        entry.count += 1;
+        entry.count
+    }
+}
+
+/// Returns the key requests from `ip` are counted under: the `/64` network
+/// for IPv6 addresses and the address itself otherwise.
+fn bucket(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.to_ipv4() {
+            Some(v4) => IpAddr::V4(v4),
+            None => {
+                let s = v6.segments();
+                IpAddr::V6(Ipv6Addr::new(s[0], s[1], s[2], s[3], 0, 0, 0, 0))
+            }
+        },
+        ip => ip,
+    }
+}
+
+impl Rocket {
+    /// Classifies `req` with the managed challenge policy, if any, and
+    /// applies the verdict. Returns the challenge response if the request was
+    /// challenged and `None` if it should be routed.
+    pub(crate) async fn challenge<'s, 'r: 's>(
+        &'s self,
+        req: &'r Request<'s>
+    ) -> Option<Response<'r>> {
+        let challenges = self.state::<Challenges>()?;
+        let client_ip = req.trusted_client_ip();
+        let requests = challenges.count(client_ip, req.clock().now());
+        let signals = Signals { client_ip, requests, load: &self.load };
+
+        match challenges.policy.classify(req, &signals) {
+            Verdict::Pass => None,
+            Verdict::Tarpit(delay) => {
+                info_!("Tarpitting request for {:?}.", delay);
+                tokio::time::sleep(delay).await;
+                None
+            }
+            Verdict::Challenge => {
+                info_!("Challenging request.");
+                let response = match challenges.responder {
+                    Some(ref responder) => match responder(req) {
+                        Ok(response) => response,
+                        Err(status) => self.handle_error(status, req).await,
+                    },
+                    None => self.handle_error(crate::http::Status::Forbidden, req).await,
+                };
+
+                Some(response)
+            }
+        }
+    }
+}
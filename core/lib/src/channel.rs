@@ -0,0 +1,339 @@
+//! Broadcast channels for fanning messages out to long-lived responses.
+//!
+//! A [`Channel`] in managed state routes messages to subscribers by _topic_,
+//! an arbitrary string such as a chat room's name. Every message sent to a
+//! topic is delivered to every current subscriber of that topic. Topics are
+//! created when first subscribed to and discarded when they have no
+//! subscribers; messages sent to a topic without subscribers are dropped.
+//!
+//! A [`Subscription`] is a [`Stream`](futures::stream::Stream) of messages,
+//! suitable for forwarding to a WebSocket. Via [`Subscription::reader()`], it
+//! can also be converted into an `AsyncRead` for use with the
+//! [`Stream`](crate::response::Stream) responder, for instance to emit
+//! server-sent events.
+//!
+//! # Lag
+//!
+//! Each topic buffers at most a fixed number of messages, the channel's
+//! capacity. A subscriber that falls behind by more than the capacity _lags_:
+//! the oldest messages it has not yet received are dropped. What happens next
+//! is determined by the channel's [`Lag`] policy: by default, the subscriber
+//! skips the dropped messages and continues with the oldest retained message.
+//!
+//! # Example
+//!
+//! A chat room that streams messages to subscribers as server-sent events:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::State;
+//! use rocket::channel::Channel;
+//! use rocket::http::ContentType;
+//! use rocket::response::{Content, Stream};
+//! use rocket::tokio::io::AsyncRead;
+//!
+//! #[post("/room/<name>", data = "<message>")]
+//! fn send(room: State<'_, Channel<String>>, name: &str, message: String) {
+//!     room.send(name, message);
+//! }
+//!
+//! #[get("/room/<name>")]
+//! fn events(
+//!     room: State<'_, Channel<String>>,
+//!     name: &str
+//! ) -> Content<Stream<impl AsyncRead + Send>> {
+//!     let reader = room.subscribe(name).reader(|msg| format!("data: {}\n\n", msg).into());
+//!     let event_stream = ContentType::new("text", "event-stream");
+//!     Content(event_stream, Stream::from(reader))
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .mount("/", routes![send, events])
+//!         .manage(Channel::<String>::new(64))
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use parking_lot::Mutex;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// What a [`Subscription`] does when it lags behind its topic.
+///
+/// See the [module level documentation](crate::channel#lag) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lag {
+    /// Skip the dropped messages and continue with the oldest message still
+    /// buffered. This is the default.
+    Skip,
+    /// End the subscription. A client can then reconnect and resynchronize.
+    Close,
+}
+
+impl Default for Lag {
+    fn default() -> Self {
+        Lag::Skip
+    }
+}
+
+/// A broadcast channel with per-topic routing, in managed state.
+///
+/// See the [module level documentation](crate::channel) for details.
+pub struct Channel<T> {
+    capacity: usize,
+    lag: Lag,
+    topics: Mutex<HashMap<String, broadcast::Sender<T>>>,
+}
+
+impl<T: Clone + Send + 'static> Channel<T> {
+    /// Creates a channel that buffers up to `capacity` messages per topic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::channel::Channel;
+    ///
+    /// let rocket = rocket::ignite().manage(Channel::<String>::new(128));
+    /// ```
+    pub fn new(capacity: usize) -> Channel<T> {
+        assert!(capacity > 0, "channel capacity must be non-zero");
+        Channel { capacity, lag: Lag::default(), topics: Mutex::new(HashMap::new()) }
+    }
+
+    /// Sets the policy applied to lagging subscribers to `lag`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::channel::{Channel, Lag};
+    ///
+    /// let channel = Channel::<String>::new(128).on_lag(Lag::Close);
+    /// ```
+    pub fn on_lag(mut self, lag: Lag) -> Self {
+        self.lag = lag;
+        self
+    }
+
+    /// Sends `message` to every subscriber of `topic`, returning the number
+    /// of subscribers it was sent to. If `topic` has no subscribers, the
+    /// message is dropped and `0` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::channel::Channel;
+    ///
+    /// let channel = Channel::new(16);
+    /// assert_eq!(channel.send("news", "nobody is listening"), 0);
+    ///
+    /// let _subscription = channel.subscribe("news");
+    /// assert_eq!(channel.send("news", "somebody is listening"), 1);
+    /// ```
+    pub fn send(&self, topic: &str, message: T) -> usize {
+        let mut topics = self.topics.lock();
+        let sent = topics.get(topic).and_then(|sender| sender.send(message).ok());
+        if sent.is_none() {
+            topics.remove(topic);
+        }
+
+        sent.unwrap_or(0)
+    }
+
+    /// Subscribes to the messages sent to `topic` from now on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::channel::Channel;
+    ///
+    /// let channel = Channel::new(16);
+    /// let mut subscription = channel.subscribe("news");
+    /// channel.send("news", "extra! extra!");
+    /// assert_eq!(subscription.recv().await, Some("extra! extra!"));
+    /// # });
+    /// ```
+    pub fn subscribe(&self, topic: &str) -> Subscription<T> {
+        let mut topics = self.topics.lock();
+        topics.retain(|_, sender| sender.receiver_count() > 0);
+        let receiver = match topics.get(topic) {
+            Some(sender) => sender.subscribe(),
+            None => {
+                let (sender, receiver) = broadcast::channel(self.capacity);
+                topics.insert(topic.to_string(), sender);
+                receiver
+            }
+        };
+
+        let lag = self.lag;
+        let stream = stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message) => return Some((message, receiver)),
+                    Err(RecvError::Lagged(n)) if lag == Lag::Skip => {
+                        warn_!("Channel subscriber lagged; skipped {} messages.", n);
+                    }
+                    Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Subscription { stream: stream.boxed() }
+    }
+
+    /// Returns the number of current subscribers to `topic`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::channel::Channel;
+    ///
+    /// let channel = Channel::<String>::new(16);
+    /// let subscription = channel.subscribe("news");
+    /// assert_eq!(channel.subscribers("news"), 1);
+    ///
+    /// drop(subscription);
+    /// assert_eq!(channel.subscribers("news"), 0);
+    /// ```
+    pub fn subscribers(&self, topic: &str) -> usize {
+        self.topics.lock().get(topic).map_or(0, |sender| sender.receiver_count())
+    }
+}
+
+impl<T> fmt::Debug for Channel<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel")
+            .field("capacity", &self.capacity)
+            .field("lag", &self.lag)
+            .field("topics", &self.topics.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A stream of the messages sent to a topic of a [`Channel`].
+///
+/// The stream ends when the subscription lags behind and the channel's lag
+/// policy is [`Lag::Close`], or when the channel is dropped.
+pub struct Subscription<T> {
+    stream: BoxStream<'static, T>,
+}
+
+impl<T> Subscription<T> {
+    /// Waits for and returns the next message, or `None` if the subscription
+    /// has ended.
+    pub async fn recv(&mut self) -> Option<T> {
+        self.stream.next().await
+    }
+
+    /// Ends the subscription when `signal` resolves, typically a
+    /// [`ShutdownSignal`](crate::ShutdownSignal).
+    ///
+    /// See the [`ShutdownSignal`](crate::ShutdownSignal) documentation for an
+    /// example.
+    pub fn until<F>(self, signal: F) -> Subscription<T>
+        where F: Future + Send + 'static, T: Send + 'static
+    {
+        Subscription { stream: self.stream.take_until(signal).boxed() }
+    }
+
+    /// Emits `message` after the last message of the subscription, such as a
+    /// final event announcing that the stream has ended.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::channel::Channel;
+    ///
+    /// let channel = Channel::new(16);
+    /// let mut subscription = channel.subscribe("news").followed_by("goodbye");
+    /// channel.send("news", "hello");
+    /// drop(channel);
+    ///
+    /// assert_eq!(subscription.recv().await, Some("hello"));
+    /// assert_eq!(subscription.recv().await, Some("goodbye"));
+    /// assert_eq!(subscription.recv().await, None);
+    /// # });
+    /// ```
+    pub fn followed_by(self, message: T) -> Subscription<T>
+        where T: Send + 'static
+    {
+        Subscription { stream: self.stream.chain(stream::once(async { message })).boxed() }
+    }
+
+    /// Converts `self` into an `AsyncRead` that emits the bytes returned by
+    /// `encode` for each message. The reader reaches EOF when the
+    /// subscription ends.
+    ///
+    /// See the [module level documentation](crate::channel) for an example.
+    pub fn reader<F>(self, encode: F) -> SubscriptionReader<T, F>
+        where F: FnMut(T) -> Vec<u8> + Send + Unpin
+    {
+        SubscriptionReader { subscription: self, encode, buffer: vec![], position: 0 }
+    }
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription").finish()
+    }
+}
+
+/// An `AsyncRead` over the encoded messages of a [`Subscription`].
+///
+/// Returned by [`Subscription::reader()`].
+pub struct SubscriptionReader<T, F> {
+    subscription: Subscription<T>,
+    encode: F,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<T, F: FnMut(T) -> Vec<u8> + Unpin> AsyncRead for SubscriptionReader<T, F> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.position < this.buffer.len() {
+                let remaining = &this.buffer[this.position..];
+                let n = std::cmp::min(remaining.len(), buf.remaining());
+                buf.put_slice(&remaining[..n]);
+                this.position += n;
+                return Poll::Ready(Ok(()));
+            }
+
+            match Pin::new(&mut this.subscription).poll_next(cx) {
+                Poll::Ready(Some(message)) => {
+                    this.buffer = (this.encode)(message);
+                    this.position = 0;
+                }
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Built-in command-line interface for Rocket applications.
+//!
+//! An application that calls [`rocket::execute()`](crate::execute()) instead
+//! of [`Rocket::launch()`] receives the following subcommands for free:
+//!
+//!   * `serve` (default): launches the server, just like [`Rocket::launch()`].
+//!   * `routes`: prints the table of mounted routes and exits.
+//!   * `config`: prints the resolved configuration, with secrets redacted, and
+//!     exits.
+//!   * `check`: runs attach fairings and pre-launch checks, including route
+//!     collision detection, without binding to a socket, and exits.
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! #[get("/")]
+//! fn index() -> &'static str { "Hello, world!" }
+//!
+//! #[rocket::main]
+//! async fn main() {
+//!     let rocket = rocket::ignite().mount("/", routes![index]);
+//!     if let Err(e) = rocket::execute(rocket).await {
+//!         println!("Whoops! Rocket didn't launch!");
+//!         drop(e);
+//!     }
+//! }
+//! ```
+//!
+//! Running the binary as `./app routes` then prints the route table, while
+//! `./app` or `./app serve` launches the server.
+
+use std::fmt;
+
+use yansi::Paint;
+
+use crate::rocket::Rocket;
+use crate::error::{Error, ErrorKind};
+
+/// A command understood by [`rocket::execute()`](crate::execute()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    /// Launch the server. This is the default.
+    Serve,
+    /// Print the table of mounted routes.
+    Routes,
+    /// Print the resolved configuration with secrets redacted.
+    Config,
+    /// Run pre-launch checks without binding to a socket.
+    Check,
+    /// Print usage information.
+    Help,
+}
+
+impl Command {
+    /// The usage message printed for `help` and on invalid arguments.
+    pub const USAGE: &'static str = "\
+USAGE:
+    <app> [COMMAND]
+
+COMMANDS:
+    serve     Launch the server (default)
+    routes    Print the mounted routes
+    config    Print the resolved configuration, secrets redacted
+    check     Run pre-launch checks without binding to a socket
+    help      Print this message";
+
+    /// Parses a command from the arguments in `args`, excluding the program
+    /// name. No arguments selects [`Command::Serve`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cli::Command;
+    ///
+    /// assert_eq!(Command::parse(Vec::<String>::new()), Ok(Command::Serve));
+    /// assert_eq!(Command::parse(vec!["routes"]), Ok(Command::Routes));
+    /// assert_eq!(Command::parse(vec!["--help"]), Ok(Command::Help));
+    /// assert!(Command::parse(vec!["launch"]).is_err());
+    /// assert!(Command::parse(vec!["routes", "extra"]).is_err());
+    /// ```
+    pub fn parse<I, S>(args: I) -> Result<Command, String>
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let mut args = args.into_iter();
+        let command = match args.next() {
+            Some(arg) => match arg.as_ref() {
+                "serve" => Command::Serve,
+                "routes" => Command::Routes,
+                "config" => Command::Config,
+                "check" => Command::Check,
+                "help" | "-h" | "--help" => Command::Help,
+                other => return Err(format!("unknown command `{}`", other)),
+            },
+            None => Command::Serve,
+        };
+
+        match args.next() {
+            Some(extra) => Err(format!("unexpected argument `{}`", extra.as_ref())),
+            None => Ok(command),
+        }
+    }
+
+    /// Parses a command from the process's command-line arguments.
+    pub fn from_env() -> Result<Command, String> {
+        Command::parse(std::env::args().skip(1))
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Serve => "serve".fmt(f),
+            Command::Routes => "routes".fmt(f),
+            Command::Config => "config".fmt(f),
+            Command::Check => "check".fmt(f),
+            Command::Help => "help".fmt(f),
+        }
+    }
+}
+
+impl Rocket {
+    /// Runs `command` against `self`. See the [`cli`](crate::cli) module
+    /// documentation for details on each command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cli::Command;
+    ///
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::ignite();
+    /// assert!(rocket.execute_command(Command::Check).await.is_ok());
+    /// # });
+    /// ```
+    pub async fn execute_command(mut self, command: Command) -> Result<(), Error> {
+        match command {
+            Command::Serve => return self.launch().await,
+            Command::Help => println!("{}", Command::USAGE),
+            Command::Routes => {
+                let mut routes: Vec<_> = self.routes().collect();
+                routes.sort_by(|a, b| a.uri.path().cmp(b.uri.path())
+                    .then(a.method.as_str().cmp(b.method.as_str()))
+                    .then(a.rank.cmp(&b.rank)));
+
+                for route in routes {
+                    let format = route.format.as_ref().map(|f| f.to_string());
+                    println!("{:<7} {:<40} {:>4}  {:<20} {}",
+                        route.method, route.uri, route.rank,
+                        format.as_deref().unwrap_or("-"),
+                        route.name.unwrap_or("-"));
+                }
+            }
+            Command::Config => {
+                // Every serialized option is printed, so none can be missed.
+                // The key and TLS files are summarized instead of printed.
+                let config = &self.config;
+                let mut options = match serde_json::to_value(config) {
+                    Ok(serde_json::Value::Object(options)) => options,
+                    _ => serde_json::Map::new(),
+                };
+
+                options.remove("secret_key");
+                options.remove("tls");
+
+                println!("profile = {}", config.profile);
+                for (key, value) in &options {
+                    println!("{} = {}", key, value);
+                }
+
+                println!("tls = {}", if config.tls_enabled() { "enabled" } else { "disabled" });
+                let secret_key = if config.secret_key.is_zero() { "[zero]" } else { "[redacted]" };
+                println!("secret_key = {}", secret_key);
+
+                let known = |key: &str| {
+                    options.contains_key(key) || ["profile", "tls", "secret_key"].contains(&key)
+                };
+
+                let extras = self.figment.extract::<figment::value::Dict>()
+                    .map(|dict| dict.into_iter()
+                        .map(|(k, _)| k)
+                        .filter(|k| !known(k))
+                        .collect::<Vec<_>>())
+                    .unwrap_or_default();
+
+                if !extras.is_empty() {
+                    println!("# extra keys (values not shown): {}", extras.join(", "));
+                }
+            }
+            Command::Check => {
+                self.prelaunch_check().await?;
+                println!("{} {} routes, {} catchers: no problems found",
+                    Paint::green("ok:"), self.routes().count(), self.catchers().count());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses a [`Command`] from the process's command-line arguments and runs
+    /// it via [`Rocket::execute_command()`]. On invalid arguments, usage
+    /// information is printed and an error of kind [`ErrorKind::Cli`] is
+    /// returned.
+    pub async fn execute(self) -> Result<(), Error> {
+        match Command::from_env() {
+            Ok(command) => self.execute_command(command).await,
+            Err(e) => {
+                eprintln!("error: {}\n\n{}", e, Command::USAGE);
+                Err(Error::new(ErrorKind::Cli(e)))
+            }
+        }
+    }
+}
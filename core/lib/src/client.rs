@@ -0,0 +1,437 @@
+//! A shared HTTP client for making outbound requests.
+//!
+//! This module is available when the `client` feature is enabled. It provides
+//! [`HttpClient`], an asynchronous HTTP client with connection pooling that is
+//! configured from the application's configuration and placed in managed
+//! state by [`HttpClient::fairing()`], and [`Outbound`], a request guard that
+//! retrieves the client along with the headers of the incoming request that
+//! should be forwarded on outbound calls, such as trace context headers.
+//!
+//! Only `http` URIs are supported.
+//!
+//! # Configuration
+//!
+//! The client is configured via the `http_client` configuration parameter,
+//! a dictionary with the following keys, all of which are optional:
+//!
+//! | key                      | type            | default                         |
+//! |--------------------------|-----------------|---------------------------------|
+//! | `proxy`                  | string          | none                            |
+//! | `connect_timeout`        | integer (secs)  | `10`                            |
+//! | `pool_idle_timeout`      | integer (secs)  | `90`                            |
+//! | `pool_max_idle_per_host` | integer         | `32`                            |
+//! | `forward_headers`        | list of strings | `["traceparent", "tracestate"]` |
+//! | `credential_hosts`       | list of strings | `[]`                            |
+//!
+//! When `proxy` is set to an `http` URI, such as `http://proxy.local:3128`,
+//! all requests are sent via the HTTP proxy at that address. For example, in
+//! `Rocket.toml`:
+//!
+//! ```toml
+//! [default.http_client]
+//! proxy = "http://proxy.local:3128"
+//! pool_idle_timeout = 30
+//! forward_headers = ["traceparent", "tracestate", "x-request-id"]
+//! ```
+//!
+//! ## Credentials
+//!
+//! The credential headers `authorization`, `proxy-authorization`, and `cookie`
+//! are forwarded only if they are named in `forward_headers` _and_ the
+//! outbound request is to a host listed in `credential_hosts`. To forward the
+//! incoming `authorization` header to `api.internal` and nowhere else:
+//!
+//! ```toml
+//! [default.http_client]
+//! forward_headers = ["traceparent", "tracestate", "authorization"]
+//! credential_hosts = ["api.internal"]
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::client::{HttpClient, Outbound};
+//! # use rocket::http::hyper as hyper;
+//! use hyper::{Body, Request};
+//!
+//! #[get("/proxy")]
+//! async fn proxy(outbound: Outbound<'_>) -> Option<String> {
+//!     // The request carries the request's trace context.
+//!     let request = Request::get("http://upstream.local/").body(Body::empty()).ok()?;
+//!     let response = outbound.send(request).await.ok()?;
+//!     let body = hyper::to_bytes(response.into_body()).await.ok()?;
+//!     String::from_utf8(body.to_vec()).ok()
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .attach(HttpClient::fairing())
+//!         .mount("/", routes![proxy])
+//! }
+//! ```
+
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::time::Duration;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+use crate::Rocket;
+use crate::fairing::AdHoc;
+use crate::request::{self, FromRequest, Request, TraceContext};
+use crate::outcome::Outcome;
+use crate::http::{Header, Status};
+use crate::http::hyper::{self, Body, Service, Uri};
+use crate::http::hyper::{HttpConnector, Connected, Connection, HeaderName, HeaderValue};
+
+/// Configuration for [`HttpClient`].
+///
+/// See the [module level documentation](crate::client#configuration) for
+/// details on each parameter.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// The URI of an HTTP proxy to send all requests via. **(default:
+    /// `None`)**
+    pub proxy: Option<String>,
+    /// The number of seconds to wait for a connection to be established.
+    /// **(default: `10`)**
+    pub connect_timeout: u64,
+    /// The number of seconds an idle connection is kept in the pool.
+    /// **(default: `90`)**
+    pub pool_idle_timeout: u64,
+    /// The maximum number of idle connections kept per host. **(default:
+    /// `32`)**
+    pub pool_max_idle_per_host: usize,
+    /// The names of the incoming request headers [`Outbound`] forwards.
+    /// **(default: `["traceparent", "tracestate"]`)**
+    pub forward_headers: Vec<String>,
+    /// The hosts to which credential headers named in `forward_headers` are
+    /// forwarded. Credentials are forwarded to no other host. **(default:
+    /// `[]`)**
+    pub credential_hosts: Vec<String>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            proxy: None,
+            connect_timeout: 10,
+            pool_idle_timeout: 90,
+            pool_max_idle_per_host: 32,
+            forward_headers: vec!["traceparent".into(), "tracestate".into()],
+            credential_hosts: vec![],
+        }
+    }
+}
+
+/// The error returned by [`HttpClient::new()`] when the configured proxy is
+/// not a valid `http` URI. Contains the invalid proxy URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidProxy(pub String);
+
+impl fmt::Display for InvalidProxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid proxy URI '{}': expected `http://host[:port]`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidProxy { }
+
+/// A shared asynchronous HTTP client with connection pooling.
+///
+/// An `HttpClient` is typically placed in managed state by attaching
+/// [`HttpClient::fairing()`] and retrieved in handlers via the [`Outbound`]
+/// request guard or as [`State<HttpClient>`](crate::State). Clones of an
+/// `HttpClient` share the same connection pool.
+///
+/// See the [module level documentation](crate::client) for an example.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: hyper::Client<Connector>,
+    config: ClientConfig,
+}
+
+impl HttpClient {
+    /// Creates a new client configured by `config`. Returns an error if
+    /// `config.proxy` is set but is not a valid `http` URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::client::{HttpClient, ClientConfig};
+    ///
+    /// let client = HttpClient::new(ClientConfig::default()).unwrap();
+    ///
+    /// let config = ClientConfig {
+    ///     proxy: Some("socks5://proxy.local".into()),
+    ///     ..ClientConfig::default()
+    /// };
+    ///
+    /// assert!(HttpClient::new(config).is_err());
+    /// ```
+    pub fn new(config: ClientConfig) -> Result<HttpClient, InvalidProxy> {
+        let proxy = match config.proxy {
+            Some(ref proxy) => {
+                let uri = proxy.parse::<Uri>().map_err(|_| InvalidProxy(proxy.clone()))?;
+                if uri.scheme_str() != Some("http") || uri.authority().is_none() {
+                    return Err(InvalidProxy(proxy.clone()));
+                }
+
+                Some(uri)
+            }
+            None => None,
+        };
+
+        let mut http = HttpConnector::new();
+        http.set_connect_timeout(Some(Duration::from_secs(config.connect_timeout)));
+
+        let client = hyper::Client::builder()
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .build(Connector { http, proxy });
+
+        Ok(HttpClient { client, config })
+    }
+
+    /// Returns a fairing that creates an `HttpClient` configured by the
+    /// `http_client` configuration parameter and places it in managed state.
+    /// Launch fails if the configuration is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::client::HttpClient;
+    ///
+    /// let rocket = rocket::ignite().attach(HttpClient::fairing());
+    /// ```
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_attach("HTTP Client", |rocket: Rocket| async move {
+            let config = match rocket.figment().extract_inner::<ClientConfig>("http_client") {
+                Ok(config) => config,
+                Err(e) if e.missing() => ClientConfig::default(),
+                Err(e) => {
+                    crate::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            match HttpClient::new(config) {
+                Ok(client) => Ok(rocket.manage(client)),
+                Err(e) => {
+                    error!("Failed to initialize HTTP client.");
+                    info_!("{}", e);
+                    Err(rocket)
+                }
+            }
+        })
+    }
+
+    /// Returns the configuration of this client.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::client::{HttpClient, ClientConfig};
+    ///
+    /// let client = HttpClient::new(ClientConfig::default()).unwrap();
+    /// assert_eq!(client.config().pool_idle_timeout, 90);
+    /// ```
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Sends `request`, returning a future that resolves to the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::client::{HttpClient, ClientConfig};
+    /// # use rocket::http::hyper as hyper;
+    /// use hyper::{Body, Request};
+    ///
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = HttpClient::new(ClientConfig::default())?;
+    /// let request = Request::get("http://localhost:8000/").body(Body::empty())?;
+    /// let response = client.send(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send(&self, request: hyper::Request<Body>) -> hyper::ResponseFuture {
+        self.client.request(request)
+    }
+}
+
+/// A request guard that retrieves the managed [`HttpClient`] along with the
+/// incoming request headers to forward on outbound calls.
+///
+/// The headers forwarded are those named in the client's configured
+/// [`forward_headers`](ClientConfig::forward_headers). If `traceparent` or
+/// `tracestate` are among them, they are set from the request's
+/// [`TraceContext`] so that the receiving service observes its span as a child
+/// of the request's span, even if the incoming request carried neither.
+/// Credential headers are only forwarded to the configured
+/// [`credential_hosts`](ClientConfig::credential_hosts). Retrieving this guard
+/// fails with a `500 Internal Server Error` if no `HttpClient` is managed.
+///
+/// See the [module level documentation](crate::client) for an example.
+#[derive(Debug, Clone)]
+pub struct Outbound<'a> {
+    client: &'a HttpClient,
+    headers: Vec<Header<'a>>,
+}
+
+impl<'a> Outbound<'a> {
+    /// Returns the managed client.
+    pub fn client(&self) -> &'a HttpClient {
+        self.client
+    }
+
+    /// Returns the incoming request headers that are forwarded.
+    pub fn headers(&self) -> &[Header<'a>] {
+        &self.headers
+    }
+
+    /// Adds the forwarded headers to `request`. Headers `request` already
+    /// contains are not overwritten. Credential headers are only added if the
+    /// host of `request` is one of the configured `credential_hosts`.
+    pub fn forward<B>(&self, request: &mut hyper::Request<B>) {
+        let trusted = request.uri().host().map_or(false, |host| {
+            self.client.config.credential_hosts.iter().any(|h| h.eq_ignore_ascii_case(host))
+        });
+
+        for header in &self.headers {
+            if !trusted && is_credential(header.name().as_str()) {
+                continue;
+            }
+
+            let name = HeaderName::from_bytes(header.name().as_str().as_bytes());
+            let value = HeaderValue::from_bytes(header.value_bytes());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                if !request.headers().contains_key(&name) {
+                    request.headers_mut().append(name, value);
+                }
+            }
+        }
+    }
+
+    /// Adds the forwarded headers to `request` and sends it with the managed
+    /// client.
+    pub async fn send(
+        &self,
+        mut request: hyper::Request<Body>
+    ) -> Result<hyper::Response<Body>, hyper::Error> {
+        self.forward(&mut request);
+        self.client.send(request).await
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Outbound<'a> {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let client = match req.managed_state::<HttpClient>() {
+            Some(client) => client,
+            None => {
+                error_!("Attempted to use `Outbound` without an attached `HttpClient`.");
+                info_!("Attach `HttpClient::fairing()` to manage a client.");
+                return Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let forwarded = &client.config.forward_headers;
+        let is_forwarded = |name: &str| forwarded.iter().any(|n| n.eq_ignore_ascii_case(name));
+        let mut headers: Vec<Header<'a>> = req.headers().iter()
+            .filter(|h| is_forwarded(h.name().as_str()))
+            .filter(|h| h.name() != "traceparent" && h.name() != "tracestate")
+            .collect();
+
+        // Propagate the request's own span, not that of its caller.
+        if let Outcome::Success(trace) = req.guard::<&TraceContext>().await {
+            let trace_headers = trace.headers().into_iter()
+                .filter(|h| is_forwarded(h.name().as_str()));
+
+            headers.extend(trace_headers);
+        }
+
+        Outcome::Success(Outbound { client, headers })
+    }
+}
+
+/// Returns `true` if `name` is the name of a header carrying credentials.
+fn is_credential(name: &str) -> bool {
+    ["authorization", "proxy-authorization", "cookie"].iter()
+        .any(|credential| name.eq_ignore_ascii_case(credential))
+}
+
+/// Connects to the destination or, if one is configured, the proxy.
+#[derive(Debug, Clone)]
+struct Connector {
+    http: HttpConnector,
+    proxy: Option<Uri>,
+}
+
+impl Service<Uri> for Connector {
+    type Response = Stream;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = BoxFuture<'static, Result<Stream, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, destination: Uri) -> Self::Future {
+        let proxied = self.proxy.is_some();
+        let connecting = self.http.call(self.proxy.clone().unwrap_or(destination));
+        Box::pin(async move { Ok(Stream { inner: connecting.await?, proxied }) })
+    }
+}
+
+/// A connection that tells hyper whether it is to a proxy, in which case
+/// hyper sends requests in absolute-form.
+struct Stream {
+    inner: TcpStream,
+    proxied: bool,
+}
+
+impl Connection for Stream {
+    fn connected(&self) -> Connected {
+        self.inner.connected().proxy(self.proxied)
+    }
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
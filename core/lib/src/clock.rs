@@ -0,0 +1,199 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use state::Container;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// A source of the current time.
+///
+/// Time-dependent features, such as the [`Deadline`](crate::request::Deadline)
+/// guard and the expiration of cookies tracked by a local
+/// [`Client`](crate::local::blocking::Client), read the current time from the
+/// application's `Clock` instead of from the system. By default, the clock is
+/// the system clock. Managing a _fake_ clock replaces it:
+///
+/// ```rust
+/// use rocket::Clock;
+///
+/// let clock = Clock::fake();
+/// let rocket = rocket::ignite().manage(clock.clone());
+/// ```
+///
+/// A fake clock starts at the time it was created, or at the time it was given,
+/// and doesn't advance on its own. Instead, it advances only when
+/// [`Clock::advance()`] is called on it or any of its clones. This makes
+/// time-dependent behavior testable deterministically and without sleeping.
+/// Note that a fake clock doesn't affect timers in the async runtime: requests
+/// are still cancelled at their deadline in real time.
+///
+/// Handlers, guards, and fairings can retrieve the application's clock with
+/// [`Request::clock()`] or by using `&Clock` as a request guard, which never
+/// fails.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::Clock;
+/// use rocket::local::blocking::Client;
+///
+/// #[get("/")]
+/// fn now(clock: &Clock) -> String {
+///     clock.now_utc().timestamp().to_string()
+/// }
+///
+/// let clock = Clock::fake();
+/// let rocket = rocket::ignite().manage(clock.clone()).mount("/", routes![now]);
+/// let client = Client::tracked(rocket).unwrap();
+///
+/// let before: i64 = client.get("/").dispatch().into_string().unwrap().parse().unwrap();
+/// clock.advance(Duration::from_secs(60));
+/// let after: i64 = client.get("/").dispatch().into_string().unwrap().parse().unwrap();
+/// assert_eq!(after - before, 60);
+/// ```
+#[derive(Clone)]
+pub struct Clock(Option<Arc<Fake>>);
+
+struct Fake {
+    instant: Instant,
+    utc: time::OffsetDateTime,
+    elapsed: Mutex<Duration>,
+}
+
+static SYSTEM: Clock = Clock(None);
+
+impl Clock {
+    /// Returns the system clock.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Clock;
+    ///
+    /// let clock = Clock::system();
+    /// assert!(!clock.is_fake());
+    /// ```
+    pub const fn system() -> Clock {
+        Clock(None)
+    }
+
+    /// Returns a fake clock that starts at the current time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::Clock;
+    ///
+    /// let clock = Clock::fake();
+    /// let start = clock.now();
+    /// assert_eq!(clock.now(), start);
+    ///
+    /// clock.advance(Duration::from_secs(5));
+    /// assert_eq!(clock.now() - start, Duration::from_secs(5));
+    /// ```
+    pub fn fake() -> Clock {
+        Clock::fake_at(time::OffsetDateTime::now_utc())
+    }
+
+    /// Returns a fake clock whose wall-clock time starts at `utc`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Clock;
+    ///
+    /// let epoch = time::OffsetDateTime::unix_epoch();
+    /// let clock = Clock::fake_at(epoch);
+    /// assert_eq!(clock.now_utc(), epoch);
+    /// ```
+    pub fn fake_at(utc: time::OffsetDateTime) -> Clock {
+        let elapsed = Mutex::new(Duration::from_secs(0));
+        Clock(Some(Arc::new(Fake { instant: Instant::now(), utc, elapsed })))
+    }
+
+    /// Returns `true` if `self` is a fake clock.
+    pub fn is_fake(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Returns the current monotonic time.
+    pub fn now(&self) -> Instant {
+        match self.0 {
+            Some(ref fake) => fake.instant + *fake.elapsed.lock(),
+            None => Instant::now(),
+        }
+    }
+
+    /// Returns the current wall-clock time in UTC.
+    pub fn now_utc(&self) -> time::OffsetDateTime {
+        match self.0 {
+            Some(ref fake) => fake.utc + *fake.elapsed.lock(),
+            None => time::OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Advances the fake clock `self`, and all of its clones, by `duration`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is the system clock.
+    pub fn advance(&self, duration: Duration) {
+        match self.0 {
+            Some(ref fake) => *fake.elapsed.lock() += duration,
+            None => panic!("the system clock cannot be advanced"),
+        }
+    }
+
+    /// Returns the clock managed in `state`, or the system clock if there is
+    /// none.
+    pub(crate) fn from_state(state: &Container) -> &Clock {
+        state.try_get::<Clock>().unwrap_or(&SYSTEM)
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::system()
+    }
+}
+
+impl PartialEq for Clock {
+    fn eq(&self, other: &Clock) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Clock {  }
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(ref fake) => f.debug_struct("Clock")
+                .field("fake", &true)
+                .field("utc", &fake.utc)
+                .field("elapsed", &*fake.elapsed.lock())
+                .finish(),
+            None => f.debug_struct("Clock").field("fake", &false).finish(),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a Clock {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(request.clock())
+    }
+}
@@ -23,6 +23,10 @@ pub struct StaticRouteInfo {
     pub handler: StaticHandler,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The API version the route serves, if any.
+    pub version: Option<u32>,
+    /// The function populating the route's extensions, if any.
+    pub extensions: Option<fn(&mut crate::Extensions)>,
 }
 
 /// Information generated by the `catch` attribute during codegen.
@@ -32,3 +36,44 @@ pub struct StaticCatcherInfo {
     /// The catcher's handler, i.e, the annotated function.
     pub handler: StaticErrorHandler,
 }
+
+/// A `uri!` invocation, registered by the `uri!` macro during codegen so that
+/// it can be checked against the mounted routes at launch.
+pub struct UriTarget {
+    /// The targeted route's name, i.e, the name of the function.
+    pub name: &'static str,
+    /// The targeted route's method.
+    pub method: Method,
+    /// The targeted route's path, without the base mount point.
+    pub path: &'static str,
+    /// The mount point in the invocation, or `/` if there was none.
+    pub mount_point: &'static str,
+    /// The file containing the invocation.
+    pub file: &'static str,
+    /// The line of the invocation.
+    pub line: u32,
+}
+
+#[cfg(feature = "uri-checks")]
+inventory::collect!(UriTarget);
+
+/// Registers a [`UriTarget`] when the `uri-checks` feature is enabled and
+/// expands to nothing otherwise. Used by code generated for `uri!`.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "uri-checks")]
+macro_rules! __uri_target {
+    ($($field:tt)*) => {
+        rocket::inventory::submit! {
+            #![crate = rocket::inventory]
+            rocket::UriTarget { $($field)* }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "uri-checks"))]
+macro_rules! __uri_target {
+    ($($field:tt)*) => {};
+}
@@ -6,8 +6,11 @@ use figment::value::{Map, Dict};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{SecretKey, TlsConfig, LogLevel};
+use crate::config::{SecretKey, TlsConfig, Ident, LogLevel, LogFormat, LogDestination};
 use crate::data::Limits;
+use crate::http::CookiePolicy;
+use crate::request::{Duplicates, TimingsMode};
+use crate::http::uri::{Absolute, UriBase};
 
 /// Rocket server configuration.
 ///
@@ -53,6 +56,13 @@ use crate::data::Limits;
 ///     the "default" meta-profile.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Config {
+    /// The selected profile. **(default: _debug_ `debug` / _release_ `release`)**
+    ///
+    /// _**Note:** This field is never serialized nor deserialized. When a
+    /// `Config` is extracted via [`Config::from()`], it is set to the
+    /// extracting figment's selected profile._
+    #[serde(skip, default = "Config::default_profile")]
+    pub profile: Profile,
     /// IP address to serve on. **(default: `127.0.0.1`)**
     pub address: IpAddr,
     /// Port to serve on. **(default: `8000`)**
@@ -61,9 +71,30 @@ pub struct Config {
     pub workers: usize,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Time in seconds a request's handler may run before it is cancelled
+    /// and a `503` is returned; disabled when `0`. See
+    /// [`Deadline`](crate::request::Deadline). **(default: `0`)**
+    pub request_timeout: u32,
+    /// Time in seconds long-lived connections, such as event streams and
+    /// WebSockets, and [deferred work](crate::Request::defer()) are given to
+    /// finish after a shutdown is signalled; unlimited when `0`. See
+    /// [`ShutdownSignal`](crate::ShutdownSignal). **(default: `0`)**
+    pub shutdown_grace: u32,
+    /// Addresses of reverse proxies whose `X-Forwarded-Proto`,
+    /// `X-Forwarded-Host`, and `X-Forwarded-Port` headers are trusted. See
+    /// [`Request::base_url()`](crate::Request::base_url()). **(default: `[]`)**
+    pub trusted_proxies: Vec<IpAddr>,
     /// Max level to log. **(default: _debug_ `normal` / _release_ `critical`)**
     pub log_level: LogLevel,
-    /// Whether to use colors and emoji when logging. **(default: `true`)**
+    /// Format of log messages, `pretty`, `compact`, or `json`.
+    /// **(default: `pretty`)**
+    pub log_format: LogFormat,
+    /// Where log messages are written: `stdout`, `stderr`, or a file path.
+    /// **(default: `stdout`)**
+    pub log_destination: LogDestination,
+    /// Whether to use colors and emoji when logging. Even when `true`, colors
+    /// are only used when writing to a terminal and the `NO_COLOR` environment
+    /// variable is unset or empty. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
     /// The secret key for signing and encrypting. **(default: `0`)**
@@ -72,9 +103,61 @@ pub struct Config {
     pub tls: Option<TlsConfig>,
     /// Streaming read size limits. **(default: [`Limits::default()`])**
     pub limits: Limits,
+    /// Defaults for cookies added to a `CookieJar`. In [`Config::figment()`],
+    /// cookies are `Secure` by default in every profile but `debug`.
+    /// **(default: `SameSite` `strict`, _debug_ not `Secure` / _release_
+    /// `Secure`)**
+    pub cookies: CookiePolicy,
+    /// Mount points under which Rocket's default catcher always responds with
+    /// JSON rather than HTML, such as `"/api"`. Elsewhere, JSON is used only
+    /// when the request prefers it. **(default: `[]`)**
+    pub json_errors: Vec<String>,
+    /// Headers added to responses that don't already have them, keyed by the
+    /// mount point whose requests they apply to, with `"/"` applying to every
+    /// request. Where mount points nest, headers for the more specific mount
+    /// point take precedence. For instance:
+    ///
+    /// ```toml
+    /// [default.default_headers."/"]
+    /// X-Org = "Acme"
+    ///
+    /// [default.default_headers."/static"]
+    /// Cache-Control = "max-age=3600"
+    /// ```
+    ///
+    /// Defaults are added before response fairings run. **(default: `{}`)**
+    #[serde(deserialize_with = "validate_headers")]
+    pub default_headers: Map<String, Map<String, String>>,
+    /// How fields that appear more than once in forms and queries are
+    /// treated. See [`Duplicates`](crate::request::Duplicates). **(default:
+    /// `last`)**
+    pub form_duplicates: Duplicates,
+    /// The server's identity, sent in the `Server` header and shown in the
+    /// default HTML error pages, or `false` to reveal none. See
+    /// [`Ident`]. **(default: `"Rocket"`)**
+    pub ident: Ident,
+    /// Whether the guards, handler, and responder of routed requests are
+    /// timed, `off`, `record`, or `header`. See
+    /// [`Timings`](crate::request::Timings). **(default: `off`)**
+    pub timings: TimingsMode,
+    /// Whether characters that are invalid in the URI of an incoming request
+    /// are percent-encoded rather than the request being rejected, for clients
+    /// that send malformed URIs. See
+    /// [`Origin::parse_lenient()`](crate::http::uri::Origin::parse_lenient()).
+    /// **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub lenient_uris: bool,
     /// Whether `ctrl-c` initiates a server shutdown. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub ctrlc: bool,
+    /// Whether launch fails if a [`uri!`](crate::uri!) invocation anywhere in
+    /// the binary targets a route the application mounts, but not at the
+    /// invocation's mount point. Invocations targeting routes the application
+    /// doesn't mount at all are ignored. Requires the `uri-checks` feature;
+    /// without it, invocations aren't registered and nothing is checked.
+    /// **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub check_uris: bool,
 }
 
 impl Default for Config {
@@ -132,16 +215,30 @@ impl Config {
     /// ```
     pub fn debug_default() -> Config {
         Config {
+            profile: Self::DEBUG_PROFILE,
             address: Ipv4Addr::new(127, 0, 0, 1).into(),
             port: 8000,
             workers: num_cpus::get(),
             keep_alive: 5,
+            request_timeout: 0,
+            shutdown_grace: 0,
+            trusted_proxies: vec![],
             log_level: LogLevel::Normal,
+            log_format: LogFormat::Pretty,
+            log_destination: LogDestination::Stdout,
             cli_colors: true,
             secret_key: SecretKey::zero(),
             tls: None,
             limits: Limits::default(),
+            cookies: CookiePolicy::default(),
+            json_errors: vec![],
+            default_headers: Map::new(),
+            ident: Ident::default(),
+            timings: TimingsMode::Off,
+            lenient_uris: false,
+            form_duplicates: Duplicates::Last,
             ctrlc: true,
+            check_uris: false,
         }
     }
 
@@ -159,23 +256,58 @@ impl Config {
     /// ```
     pub fn release_default() -> Config {
         Config {
+            profile: Self::RELEASE_PROFILE,
             log_level: LogLevel::Critical,
+            cookies: CookiePolicy { secure: true, ..CookiePolicy::default() },
             ..Config::debug_default()
         }
     }
 
+    fn default_profile() -> Profile {
+        Self::DEFAULT_PROFILE
+    }
+
+    /// Returns the profile selected by the `ROCKET_PROFILE` environment
+    /// variable, or [`Config::DEFAULT_PROFILE`] if it is not set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Config;
+    ///
+    /// let profile = Config::selected_profile();
+    /// ```
+    pub fn selected_profile() -> Profile {
+        Profile::from_env_or("ROCKET_PROFILE", Self::DEFAULT_PROFILE)
+    }
+
     /// Returns the default provider figment used by [`rocket::ignite()`].
     ///
     /// The default figment reads from the following sources, in ascending
     /// priority order:
     ///
-    ///   1. [`Config::default()`] (see [Defaults](#Defaults))
+    ///   1. [`Config::default()`] (see [Defaults](#Defaults)), with
+    ///      `cookies.secure` set unless the selected profile is `debug`
     ///   2. `Rocket.toml` _or_ filename in `ROCKET_CONFIG` environment variable
     ///   3. `ROCKET_` prefixed environment variables
+    ///   4. `ROCKET_{PROFILE}_` prefixed environment variables, where
+    ///      `{PROFILE}` is the uppercased name of the selected profile with
+    ///      every `-` replaced by `_`
     ///
     /// The profile selected is the value set in the `ROCKET_PROFILE`
     /// environment variable. If it is not set, it defaults to `debug` when
-    /// compiled in debug mode and `release` when compiled in release mode.
+    /// compiled in debug mode and `release` when compiled in release mode. Any
+    /// profile name may be used. Values in the `default` profile are inherited
+    /// by every profile, while values in the selected profile override them.
+    ///
+    /// For example, with `ROCKET_PROFILE=staging`, the port is read from
+    /// `[default]` and then `[staging]` in `Rocket.toml`, then from
+    /// `ROCKET_PORT`, and finally from `ROCKET_STAGING_PORT`. Since `-` cannot
+    /// appear in an environment variable name, the port for a profile named
+    /// `my-profile` is read from `ROCKET_MY_PROFILE_PORT`. As a result,
+    /// profiles such as `my-profile` and `my_profile` would read the same
+    /// variables: if `Rocket.toml` contains another profile sharing the
+    /// selected profile's prefix, extracting from the figment fails.
     ///
     /// [`rocket::ignite()`]: crate::ignite()
     ///
@@ -193,9 +325,26 @@ impl Config {
     /// let my_config = Config::figment().extract::<MyConfig>();
     /// ```
     pub fn figment() -> Figment {
-        Figment::from(Config::default())
-            .merge(Toml::file(Env::var_or("ROCKET_CONFIG", "Rocket.toml")).nested())
+        let profile = Self::selected_profile();
+        let profile_prefix = env_prefix(&profile);
+        let secure_cookies = profile != Self::DEBUG_PROFILE;
+
+        let figment = Figment::from(Config::default())
+            .merge(Serialized::default("cookies.secure", secure_cookies))
+            .merge(Toml::file(Env::var_or("ROCKET_CONFIG", "Rocket.toml")).nested());
+
+        let collision = figment.profiles()
+            .find(|other| **other != profile && env_prefix(other) == profile_prefix)
+            .cloned();
+
+        let figment = figment
             .merge(Env::prefixed("ROCKET_").ignore(&["PROFILE"]).global())
+            .merge(Env::prefixed(&profile_prefix).global());
+
+        match collision {
+            Some(other) => figment.merge(AmbiguousProfile(profile, other)),
+            None => figment,
+        }
     }
 
     /// Attempts to extract a `Config` from `provider`.
@@ -227,6 +376,8 @@ impl Config {
             panic!("aborting due to configuration error(s)")
         });
 
+        config.profile = figment.profile().clone();
+
         #[cfg(all(feature = "secrets", not(test), not(rocket_unsafe_secret_key)))]
         if !config.secret_key.is_provided() {
             if figment.profile() != Self::DEBUG_PROFILE {
@@ -240,6 +391,15 @@ impl Config {
             config.secret_key = SecretKey::generate().unwrap_or(SecretKey::zero());
         }
 
+        #[cfg(all(feature = "secrets", not(rocket_unsafe_secret_key)))]
+        if config.secret_key.is_weak() && figment.profile() != Self::DEBUG_PROFILE {
+            crate::logger::try_init(LogLevel::Debug, true, false);
+            error!("secrets enabled in non-`debug` with a weak `secret_key`");
+            info_!("the configured key has insufficient entropy");
+            info_!("generate a key with `SecretKey::keygen()` or `openssl rand -base64 32`");
+            panic!("aborting due to configuration error(s)")
+        }
+
         config
     }
 
@@ -262,18 +422,49 @@ impl Config {
         cfg!(feature = "tls") && self.tls.is_some()
     }
 
-    pub(crate) fn pretty_print(&self, figment: &Figment) {
+    pub(crate) fn pretty_print(&self) {
         use crate::logger::PaintExt;
 
-        launch_info!("{}Configured for {}.", Paint::emoji("🔧 "), figment.profile());
+        launch_info!("{}Configured for {}.", Paint::emoji("🔧 "), self.profile);
 
         launch_info_!("address: {}", Paint::default(&self.address).bold());
         launch_info_!("port: {}", Paint::default(&self.port).bold());
         launch_info_!("workers: {}", Paint::default(self.workers).bold());
         launch_info_!("log level: {}", Paint::default(self.log_level).bold());
+        launch_info_!("log format: {}", Paint::default(self.log_format).bold());
+        launch_info_!("log destination: {}", Paint::default(&self.log_destination).bold());
         launch_info_!("secret key: {:?}", Paint::default(&self.secret_key).bold());
         launch_info_!("limits: {}", Paint::default(&self.limits).bold());
+        launch_info_!("cookies: {}", Paint::default(format!("same_site = {}, secure = {}",
+            self.cookies.same_site, self.cookies.secure)).bold());
+        if !self.json_errors.is_empty() {
+            launch_info_!("json errors: {}", Paint::default(self.json_errors.join(", ")).bold());
+        }
+
+        if !self.default_headers.is_empty() {
+            let mounts = self.default_headers.iter()
+                .map(|(base, headers)| format!("{} ({})", base, headers.len()))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            launch_info_!("default headers: {}", Paint::default(mounts).bold());
+        }
+
+        launch_info_!("form duplicates: {}", Paint::default(self.form_duplicates).bold());
+
+        if self.timings != TimingsMode::Off {
+            launch_info_!("timings: {}", Paint::default(self.timings).bold());
+        }
+
+        if self.lenient_uris {
+            launch_info_!("lenient uris: {}", Paint::default("enabled").bold());
+        }
+
+        launch_info_!("ident: {}", Paint::default(&self.ident).bold());
         launch_info_!("cli colors: {}", Paint::default(&self.cli_colors).bold());
+        if self.check_uris {
+            launch_info_!("uri checks: {}", Paint::default("enabled").bold());
+        }
 
         let ka = self.keep_alive;
         if ka > 0 {
@@ -282,11 +473,28 @@ impl Config {
             launch_info_!("keep-alive: {}", Paint::default("disabled").bold());
         }
 
+        match self.request_timeout {
+            0 => launch_info_!("request timeout: {}", Paint::default("disabled").bold()),
+            n => launch_info_!("request timeout: {}", Paint::default(format!("{}s", n)).bold()),
+        }
+
+        match self.shutdown_grace {
+            0 => launch_info_!("shutdown grace: {}", Paint::default("unlimited").bold()),
+            n => launch_info_!("shutdown grace: {}", Paint::default(format!("{}s", n)).bold()),
+        }
+
+        if !self.trusted_proxies.is_empty() {
+            let proxies: Vec<_> = self.trusted_proxies.iter().map(|ip| ip.to_string()).collect();
+            launch_info_!("trusted proxies: {}", Paint::default(proxies.join(", ")).bold());
+        }
+
         match self.tls_enabled() {
             true => launch_info_!("tls: {}", Paint::default("enabled").bold()),
             false => launch_info_!("tls: {}", Paint::default("disabled").bold()),
         }
+    }
 
+    pub(crate) fn print_warnings(&self, figment: &Figment) {
         #[cfg(all(feature = "secrets", not(test), not(rocket_unsafe_secret_key)))]
         if !self.secret_key.is_provided() {
             warn!("secrets enabled without a configured `secret_key`");
@@ -323,6 +531,67 @@ impl Config {
     }
 }
 
+/// The base of URIs generated with `uri!(base = config, ...)`:
+/// `http://{address}:{port}`, or `https://...` when TLS is enabled.
+impl UriBase for Config {
+    fn uri_base(&self) -> Absolute<'_> {
+        let scheme = if self.tls_enabled() { "https" } else { "http" };
+        let string = match self.address {
+            IpAddr::V4(ip) => format!("{}://{}:{}", scheme, ip, self.port),
+            IpAddr::V6(ip) => format!("{}://[{}]:{}", scheme, ip, self.port),
+        };
+
+        Absolute::parse_owned(string).expect("address and port form a valid URI")
+    }
+}
+
+/// Deserializes `default_headers`, rejecting invalid mount points, header
+/// names, and header values.
+fn validate_headers<'de, D: serde::Deserializer<'de>>(
+    de: D
+) -> std::result::Result<Map<String, Map<String, String>>, D::Error> {
+    use serde::de::Error;
+    use crate::http::hyper::{HeaderName, HeaderValue};
+
+    let mounts = Map::<String, Map<String, String>>::deserialize(de)?;
+    for (base, headers) in &mounts {
+        if !base.starts_with('/') {
+            return Err(D::Error::custom(format!("mount point `{}` must start with `/`", base)));
+        }
+
+        for (name, value) in headers {
+            if HeaderName::from_bytes(name.as_bytes()).is_err() {
+                return Err(D::Error::custom(format!("invalid header name `{}`", name)));
+            }
+
+            if HeaderValue::from_str(value).is_err() {
+                return Err(D::Error::custom(format!("invalid value for header `{}`", name)));
+            }
+        }
+    }
+
+    Ok(mounts)
+}
+
+/// Returns the prefix of environment variables for `profile`.
+fn env_prefix(profile: &Profile) -> String {
+    format!("ROCKET_{}_", profile.as_str().to_uppercase().replace('-', "_"))
+}
+
+/// A provider that fails because two profiles share an environment prefix.
+struct AmbiguousProfile(Profile, Profile);
+
+impl Provider for AmbiguousProfile {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("Rocket Profile Environment")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>> {
+        Err(format!("profiles `{}` and `{}` share the environment prefix `{}`",
+            self.0, self.1, env_prefix(&self.0)).into())
+    }
+}
+
 impl Provider for Config {
     fn metadata(&self) -> Metadata {
         Metadata::named("Rocket Config")
@@ -342,7 +611,7 @@ impl Provider for Config {
     }
 
     fn profile(&self) -> Option<Profile> {
-        Some(Profile::from_env_or("ROCKET_PROFILE", Self::DEFAULT_PROFILE))
+        Some(Self::selected_profile())
     }
 }
 
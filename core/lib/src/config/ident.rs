@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize, Deserializer, Serializer};
+use serde::de::{self, Visitor};
+
+use crate::http::hyper::HeaderValue;
+
+/// The identity a Rocket server reveals, or hiding it altogether.
+///
+/// Unless it is hidden, the identity is sent in the `Server` header of
+/// responses that don't already set it and shown at the foot of the default
+/// catcher's HTML error pages. A hidden identity is never sent nor shown.
+///
+/// # Configuration
+///
+/// In configuration sources, the identity is set as a string or disabled with
+/// `false`:
+///
+/// ```toml
+/// [default]
+/// ident = "Acme"
+///
+/// [release]
+/// ident = false
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, Ident};
+///
+/// let config = Config {
+///     ident: Ident::try_new("Acme").unwrap(),
+///     ..Config::default()
+/// };
+///
+/// assert_eq!(config.ident.as_str(), Some("Acme"));
+/// assert_eq!(Ident::none().as_str(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident(Option<String>);
+
+impl Ident {
+    /// Returns an identity of `ident`, or an error if `ident` is empty or
+    /// isn't a valid header value.
+    pub fn try_new<S: Into<String>>(ident: S) -> Result<Ident, String> {
+        let ident = ident.into();
+        if ident.is_empty() || HeaderValue::from_str(&ident).is_err() {
+            return Err(ident);
+        }
+
+        Ok(Ident(Some(ident)))
+    }
+
+    /// Returns a hidden identity.
+    pub fn none() -> Ident {
+        Ident(None)
+    }
+
+    /// Returns the identity, or `None` if it is hidden.
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+impl Default for Ident {
+    /// Returns the identity `Rocket`.
+    fn default() -> Self {
+        Ident(Some("Rocket".into()))
+    }
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_str() {
+            Some(ident) => ident.fmt(f),
+            None => "disabled".fmt(f),
+        }
+    }
+}
+
+impl Serialize for Ident {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(ident) => ser.serialize_str(ident),
+            None => ser.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct IdentVisitor;
+
+        impl<'de> Visitor<'de> for IdentVisitor {
+            type Value = Ident;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a server identity string or `false`")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+                match v {
+                    false => Ok(Ident::none()),
+                    true => Err(E::invalid_value(de::Unexpected::Bool(v), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ident::try_new(v).map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        de.deserialize_any(IdentVisitor)
+    }
+}
@@ -104,29 +104,65 @@
 //! }
 //! ```
 //!
+//! ## Layering Sources
+//!
+//! Sources can also be layered on top of an existing application's
+//! configuration via [`Rocket::reconfigure()`]. Each call merges a new
+//! provider whose values take precedence over all previously configured
+//! sources. The full precedence, from lowest to highest, is thus:
+//!
+//!   1. [`Config::default()`]
+//!   2. `Rocket.toml` or the file named by `ROCKET_CONFIG`
+//!   3. `ROCKET_` prefixed environment variables
+//!   4. `ROCKET_{PROFILE}_` prefixed environment variables
+//!   5. providers passed to [`Rocket::reconfigure()`], in call order
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use figment::providers::{Format, Json, Env};
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::ignite()
+//!         .reconfigure(Json::file("App.json").nested())
+//!         .reconfigure(Env::prefixed("APP_").global())
+//! }
+//! ```
+//!
+//! ## Library Sections
+//!
+//! Libraries that extend Rocket, such as database pools or template engines,
+//! should read their configuration from a named section of the merged figment
+//! returned by [`Rocket::figment()`], for example via `figment.focus("name")`
+//! or `figment.extract_inner("name")`. This ensures they observe every
+//! configured source with the same precedence as Rocket itself.
+//!
 //! [`rocket::custom()`]: crate::custom()
 //! [`rocket::ignite()`]: crate::ignite()
+//! [`Rocket::reconfigure()`]: crate::Rocket::reconfigure()
 //! [`Toml`]: figment::providers::Toml
 //! [`Env`]: figment::providers::Env
 
 mod secret_key;
 mod config;
 mod tls;
+mod ident;
 
 #[doc(hidden)] pub use config::pretty_print_error;
 
 pub use config::Config;
-pub use crate::logger::LogLevel;
+pub use crate::logger::{LogLevel, LogFormat, LogDestination, set_log_writer};
 pub use secret_key::SecretKey;
 pub use tls::TlsConfig;
+pub use ident::Ident;
 
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
     use figment::Figment;
 
-    use crate::config::{Config, TlsConfig};
-    use crate::logger::LogLevel;
+    use crate::config::{Config, TlsConfig, Ident};
+    use crate::logger::{LogLevel, LogFormat, LogDestination};
     use crate::data::{Limits, ToByteUnit};
 
     #[test]
@@ -303,6 +339,7 @@ mod tests {
             jail.set_env("ROCKET_PROFILE", "unknown");
             let config = Config::from(Config::figment());
             assert_eq!(config, Config {
+                profile: "unknown".into(),
                 limits: Limits::default()
                     .limit("stream", 50.kilobytes())
                     .limit("forms", 2.kilobytes()),
@@ -312,6 +349,7 @@ mod tests {
             jail.set_env("ROCKET_PROFILE", "debug");
             let config = Config::from(Config::figment());
             assert_eq!(config, Config {
+                profile: Config::DEBUG_PROFILE,
                 limits: Limits::default()
                     .limit("stream", 50.kilobytes())
                     .limit("forms", 2.kilobytes())
@@ -323,6 +361,361 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_custom_profiles_inherit() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                port = 1234
+                workers = 2
+
+                [staging]
+                port = 4321
+
+                [my-profile]
+                workers = 7
+            "#)?;
+
+            jail.set_env("ROCKET_PROFILE", "staging");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.profile, "staging");
+            assert_eq!(config.port, 4321);
+            assert_eq!(config.workers, 2);
+
+            jail.set_env("ROCKET_PROFILE", "my-profile");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.profile, "my-profile");
+            assert_eq!(config.port, 1234);
+            assert_eq!(config.workers, 7);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_ambiguous_profile_env_prefix() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("Rocket.toml", r#"
+                [my-app]
+                port = 1234
+
+                [my_app]
+                port = 4321
+
+                [other]
+                port = 5678
+            "#)?;
+
+            jail.set_env("ROCKET_PROFILE", "my-app");
+            let error = Config::figment().extract::<Config>().unwrap_err();
+            assert!(error.to_string().contains("ROCKET_MY_APP_"));
+
+            jail.set_env("ROCKET_PROFILE", "other");
+            assert_eq!(Config::from(Config::figment()).port, 5678);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_profile_env_vars() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("ROCKET_PORT", 1000);
+            jail.set_env("ROCKET_STAGING_PORT", 2000);
+            jail.set_env("ROCKET_RELEASE_PORT", 3000);
+
+            jail.set_env("ROCKET_PROFILE", "staging");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.port, 2000);
+
+            jail.set_env("ROCKET_PROFILE", "release");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.port, 3000);
+
+            jail.set_env("ROCKET_PROFILE", "other");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.port, 1000);
+
+            jail.set_env("ROCKET_MY_PROFILE_PORT", 4000);
+            jail.set_env("ROCKET_PROFILE", "my-profile");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.port, 4000);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_log_format() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_format, LogFormat::Pretty);
+
+            jail.set_env("ROCKET_LOG_FORMAT", "json");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_format, LogFormat::Json);
+
+            jail.set_env("ROCKET_LOG_FORMAT", "JSON");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_format, LogFormat::Json);
+
+            jail.set_env("ROCKET_LOG_FORMAT", "compact");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_format, LogFormat::Compact);
+
+            jail.set_env("ROCKET_LOG_FORMAT", "xml");
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_log_destination() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_destination, LogDestination::Stdout);
+
+            jail.set_env("ROCKET_LOG_DESTINATION", "stderr");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_destination, LogDestination::Stderr);
+
+            jail.set_env("ROCKET_LOG_DESTINATION", "logs/rocket.log");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.log_destination, LogDestination::File("logs/rocket.log".into()));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_cookie_policy() {
+        use crate::http::{CookiePolicy, SameSite};
+
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.cookies, Config::default().cookies);
+            assert_eq!(Config::debug_default().cookies, CookiePolicy::default());
+            assert!(Config::release_default().cookies.secure);
+
+            jail.set_env("ROCKET_PROFILE", "debug");
+            assert!(!Config::from(Config::figment()).cookies.secure);
+
+            jail.set_env("ROCKET_PROFILE", "release");
+            assert!(Config::from(Config::figment()).cookies.secure);
+
+            jail.set_env("ROCKET_PROFILE", "staging");
+            assert!(Config::from(Config::figment()).cookies.secure);
+
+            jail.set_env("ROCKET_PROFILE", "debug");
+
+            jail.create_file("Rocket.toml", r#"
+                [default.cookies]
+                same_site = "Lax"
+                secure = true
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.cookies, CookiePolicy { same_site: SameSite::Lax, secure: true });
+
+            jail.set_env("ROCKET_COOKIES", r#"{same_site="none"}"#);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.cookies.same_site, SameSite::None);
+
+            jail.set_env("ROCKET_COOKIES", r#"{same_site="sometimes"}"#);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_json_errors() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert!(config.json_errors.is_empty());
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                json_errors = ["/api", "/v2/"]
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.json_errors, vec!["/api", "/v2/"]);
+
+            jail.set_env("ROCKET_JSON_ERRORS", r#"["/rpc"]"#);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.json_errors, vec!["/rpc"]);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_default_headers() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert!(config.default_headers.is_empty());
+
+            jail.create_file("Rocket.toml", r#"
+                [default.default_headers."/"]
+                X-Org = "Acme"
+
+                [default.default_headers."/static"]
+                Cache-Control = "max-age=3600"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.default_headers.len(), 2);
+            assert_eq!(config.default_headers["/"]["X-Org"], "Acme");
+            assert_eq!(config.default_headers["/static"]["Cache-Control"], "max-age=3600");
+
+            jail.create_file("Rocket.toml", r#"
+                [default.default_headers."/"]
+                "Bad Name" = "value"
+            "#)?;
+
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            jail.create_file("Rocket.toml", r#"
+                [default.default_headers.static]
+                X-Org = "Acme"
+            "#)?;
+
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_ident() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.ident.as_str(), Some("Rocket"));
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                ident = "Acme"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.ident.as_str(), Some("Acme"));
+
+            jail.set_env("ROCKET_IDENT", false);
+            let config = Config::from(Config::figment());
+            assert_eq!(config.ident, Ident::none());
+
+            jail.set_env("ROCKET_IDENT", true);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            jail.set_env("ROCKET_IDENT", "\"\"");
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_form_duplicates() {
+        use crate::request::Duplicates;
+
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.form_duplicates, Duplicates::Last);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                form_duplicates = "first"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.form_duplicates, Duplicates::First);
+
+            jail.set_env("ROCKET_FORM_DUPLICATES", "Reject");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.form_duplicates, Duplicates::Reject);
+
+            jail.set_env("ROCKET_FORM_DUPLICATES", "all");
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_timings() {
+        use crate::request::TimingsMode;
+
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert_eq!(config.timings, TimingsMode::Off);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                timings = "record"
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.timings, TimingsMode::Record);
+
+            jail.set_env("ROCKET_TIMINGS", "Header");
+            let config = Config::from(Config::figment());
+            assert_eq!(config.timings, TimingsMode::Header);
+
+            jail.set_env("ROCKET_TIMINGS", "always");
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_lenient_uris() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert!(!config.lenient_uris);
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                lenient_uris = true
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert!(config.lenient_uris);
+
+            jail.set_env("ROCKET_LENIENT_URIS", 0);
+            let config = Config::from(Config::figment());
+            assert!(!config.lenient_uris);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_trusted_proxies() {
+        figment::Jail::expect_with(|jail| {
+            let config = Config::from(Config::figment());
+            assert!(config.trusted_proxies.is_empty());
+
+            jail.create_file("Rocket.toml", r#"
+                [default]
+                trusted_proxies = ["10.0.0.1", "::1"]
+            "#)?;
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.trusted_proxies, vec![
+                "10.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+                "::1".parse().unwrap(),
+            ]);
+
+            jail.set_env("ROCKET_TRUSTED_PROXIES", r#"["not an ip"]"#);
+            assert!(Config::figment().extract::<Config>().is_err());
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_env_vars_merge() {
         figment::Jail::expect_with(|jail| {
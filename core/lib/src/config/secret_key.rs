@@ -43,18 +43,81 @@ enum Kind {
 /// # */
 /// ```
 ///
+/// # External Sources
+///
+/// Instead of a string or bytes, the key can be configured as a table with
+/// one of the following keys:
+///
+///   * `file`: the path to a file containing a base64 or hex encoded key, or
+///     at least 32 raw bytes of key material.
+///   * `env`: the name of an environment variable containing a base64 or hex
+///     encoded key.
+///
+/// ```toml
+/// [release]
+/// secret_key = { file = "/run/secrets/rocket_key" }
+///
+/// [staging]
+/// secret_key = { env = "STAGING_SECRET_KEY" }
+/// ```
+///
+/// A fresh, encoded key suitable for configuration can be generated with
+/// [`SecretKey::keygen()`].
+///
+/// # Validation
+///
+/// Key material that is configured but has low entropy, such as a repeated
+/// pattern or a short alphabet, is considered _weak_. When the `secrets`
+/// feature is enabled, Rocket refuses to start with a weak key in any profile
+/// other than `debug`.
+///
+/// # Zeroization
+///
+/// The key material, and any intermediate buffers used while decoding it, are
+/// overwritten with zeroes when dropped.
+///
 /// [private cookies]: https://rocket.rs/master/guide/requests/#private-cookies
 /// [configuration guide]: https://rocket.rs/master/guide/configuration/#secret-key
 #[derive(Clone)]
 pub struct SecretKey {
     key: Key,
     provided: bool,
+    weak: bool,
+}
+
+/// Overwrites `bytes` with zeroes in a way that won't be optimized away.
+fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned, exclusive reference.
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Returns `true` if `material` appears to have insufficient entropy to be
+/// used as key material. This is a heuristic: it computes the Shannon entropy
+/// of the byte distribution and compares it against a threshold well below
+/// that of uniformly random 32-byte inputs (~4.8 bits per byte).
+fn is_weak(material: &[u8]) -> bool {
+    let mut counts = [0usize; 256];
+    for &byte in material {
+        counts[byte as usize] += 1;
+    }
+
+    let len = material.len() as f64;
+    let entropy: f64 = counts.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| { let p = c as f64 / len; -p * p.log2() })
+        .sum();
+
+    entropy < 3.5
 }
 
 impl SecretKey {
     /// Returns a secret key that is all zeroes.
     pub(crate) fn zero() -> SecretKey {
-        SecretKey { key: Key::from(&[0; 64]), provided: false }
+        SecretKey { key: Key::from(&[0; 64]), provided: false, weak: false }
     }
 
     /// Creates a `SecretKey` from a 512-bit `master` key. For security,
@@ -73,7 +136,7 @@ impl SecretKey {
     /// let key = SecretKey::from(&master);
     /// ```
     pub fn from(master: &[u8]) -> SecretKey {
-        SecretKey { key: Key::from(master), provided: true }
+        SecretKey { key: Key::from(master), provided: true, weak: is_weak(master) }
     }
 
     /// Derives a `SecretKey` from 256 bits of cryptographically random
@@ -92,7 +155,7 @@ impl SecretKey {
     /// let key = SecretKey::derive_from(&material);
     /// ```
     pub fn derive_from(material: &[u8]) -> SecretKey {
-        SecretKey { key: Key::derive_from(material), provided: true }
+        SecretKey { key: Key::derive_from(material), provided: true, weak: is_weak(material) }
     }
 
     /// Attempts to generate a `SecretKey` from randomness retrieved from the
@@ -106,7 +169,38 @@ impl SecretKey {
     /// let key = SecretKey::generate();
     /// ```
     pub fn generate() -> Option<SecretKey> {
-        Some(SecretKey { key: Key::try_generate()?, provided: false })
+        Some(SecretKey { key: Key::try_generate()?, provided: false, weak: false })
+    }
+
+    /// Generates a new 512-bit key from the OS's random source and returns it
+    /// as a base64-encoded string suitable for use as the `secret_key`
+    /// configuration parameter. Returns `None` if randomness from the OS isn't
+    /// available.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SecretKey;
+    /// use rocket::figment::Figment;
+    ///
+    /// let encoded = SecretKey::keygen().unwrap();
+    /// assert_eq!(encoded.len(), 88);
+    ///
+    /// let figment = Figment::from(("secret_key", &encoded));
+    /// let key: SecretKey = figment.extract_inner("secret_key").unwrap();
+    /// assert!(key.is_provided());
+    /// assert!(!key.is_weak());
+    /// ```
+    pub fn keygen() -> Option<String> {
+        let key = Key::try_generate()?;
+        let mut buf = [0u8; 96];
+        let encoded = binascii::b64encode(key.master(), &mut buf)
+            .ok()
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .map(|s| s.to_string());
+
+        zeroize(&mut buf);
+        encoded
     }
 
     /// Returns `true` if `self` is the `0`-key.
@@ -142,6 +236,34 @@ impl SecretKey {
     pub fn is_provided(&self) -> bool {
         self.provided && !self.is_zero()
     }
+
+    /// Returns `true` if `self` was provided from key material that appears to
+    /// have insufficient entropy. See [Validation](#validation).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::SecretKey;
+    ///
+    /// let key = SecretKey::from(&[1u8; 64]);
+    /// assert!(key.is_weak());
+    ///
+    /// let key = SecretKey::generate().unwrap();
+    /// assert!(!key.is_weak());
+    /// ```
+    pub fn is_weak(&self) -> bool {
+        self.provided && self.weak
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        // `Key` stores its material inline and has no drop glue, so writing a
+        // zero key in-place overwrites the material.
+        // SAFETY: `self.key` is a valid, aligned, exclusive reference.
+        unsafe { std::ptr::write_volatile(&mut self.key, Key::from(&[0; 64])) };
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
 }
 
 #[doc(hidden)]
@@ -186,7 +308,8 @@ impl<'de> Deserialize<'de> for SecretKey {
             type Value = SecretKey;
 
             fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str("256-bit base64 or hex string, or 32-byte slice")
+                f.write_str("256-bit base64 or hex string, 32-byte slice, \
+                    or table with `file` or `env` key")
             }
 
             fn visit_str<E: de::Error>(self, val: &str) -> Result<SecretKey, E> {
@@ -195,12 +318,14 @@ impl<'de> Deserialize<'de> for SecretKey {
                 // `binascii` requires a more space than actual output for padding
                 let mut buf = [0u8; 96];
                 let bytes = match val.len() {
-                    44 | 88 => b64decode(val.as_bytes(), &mut buf).map_err(|_| e(val))?,
-                    64 => hex2bin(val.as_bytes(), &mut buf).map_err(|_| e(val))?,
-                    n => Err(E::invalid_length(n, &"44 or 88 for base64, 64 for hex"))?
+                    44 | 88 => b64decode(val.as_bytes(), &mut buf).map_err(|_| e("<redacted>")),
+                    64 => hex2bin(val.as_bytes(), &mut buf).map_err(|_| e("<redacted>")),
+                    n => Err(E::invalid_length(n, &"44 or 88 for base64, 64 for hex"))
                 };
 
-                self.visit_bytes(bytes)
+                let key = bytes.and_then(|bytes| self.visit_bytes(bytes));
+                zeroize(&mut buf);
+                key
             }
 
             fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<SecretKey, E> {
@@ -223,7 +348,61 @@ impl<'de> Deserialize<'de> for SecretKey {
                     bytes.push(byte);
                 }
 
-                self.visit_bytes(&bytes)
+                let key = self.visit_bytes(&bytes);
+                zeroize(&mut bytes);
+                key
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where A: de::MapAccess<'de>
+            {
+                use de::Error;
+
+                let (kind, value) = match map.next_entry::<String, String>()? {
+                    Some(entry) => entry,
+                    None => return Err(A::Error::custom("expected a `file` or `env` key")),
+                };
+
+                if map.next_key::<String>()?.is_some() {
+                    return Err(A::Error::custom("expected exactly one of `file` or `env`"));
+                }
+
+                match kind.as_str() {
+                    "file" => {
+                        let mut bytes = std::fs::read(&value).map_err(|e| {
+                            A::Error::custom(format!("failed to read key file {:?}: {}", value, e))
+                        })?;
+
+                        let encoded = std::str::from_utf8(&bytes).ok()
+                            .map(|s| s.trim())
+                            .filter(|s| matches!(s.len(), 44 | 64 | 88))
+                            .map(|s| s.to_string());
+
+                        let key = match encoded {
+                            Some(mut string) => {
+                                let key = self.visit_str(&string);
+                                // SAFETY: zeroes are valid UTF-8.
+                                zeroize(unsafe { string.as_bytes_mut() });
+                                key
+                            }
+                            None => self.visit_bytes(&bytes),
+                        };
+
+                        zeroize(&mut bytes);
+                        key
+                    }
+                    "env" => {
+                        let mut string = std::env::var(&value).map_err(|e| {
+                            A::Error::custom(format!("failed to read env var {:?}: {}", value, e))
+                        })?;
+
+                        let key = self.visit_str(string.trim());
+                        // SAFETY: zeroes are valid UTF-8.
+                        zeroize(unsafe { string.as_bytes_mut() });
+                        key
+                    }
+                    _ => Err(A::Error::unknown_field(&kind, &["file", "env"])),
+                }
             }
         }
 
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use parking_lot::Mutex;
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+use crate::data::ByteUnit;
+
+/// A bounded copy of a body, recorded as the body is read.
+///
+/// A `Capture` is obtained via [`Data::capture()`](crate::data::Data::capture())
+/// for request bodies or via
+/// [`Response::capture_body()`](crate::response::Response::capture_body()) for
+/// response bodies. It records the first `limit` bytes of the body as they are
+/// consumed by data guards or written to the client, without buffering or
+/// re-reading the body, and counts all of the bytes seen, including those past
+/// the limit.
+///
+/// A capture is _finished_ once the underlying reader has been dropped, which
+/// occurs when the body has been fully consumed or abandoned. Callbacks
+/// registered with [`Capture::on_finish()`] are invoked at that point.
+///
+/// `Capture` is a cheap, shared handle: clones refer to the same recording.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::data::{Data, ToByteUnit};
+///
+/// async fn handler(mut data: Data) -> Vec<u8> {
+///     let capture = data.capture(64.bytes()).unwrap();
+///     let _ = data.open(1.kibibytes()).stream_to_vec().await;
+///     capture.bytes()
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Capture {
+    inner: Arc<Mutex<Recording>>,
+}
+
+struct Recording {
+    bytes: Vec<u8>,
+    limit: usize,
+    total: u64,
+    finished: bool,
+    callbacks: Vec<Box<dyn FnOnce(&Capture) + Send>>,
+}
+
+impl Capture {
+    pub(crate) fn new(limit: ByteUnit) -> Capture {
+        let limit = limit.as_u64().min(usize::max_value() as u64) as usize;
+        Capture {
+            inner: Arc::new(Mutex::new(Recording {
+                bytes: Vec::with_capacity(limit.min(1024)),
+                limit,
+                total: 0,
+                finished: false,
+                callbacks: vec![],
+            }))
+        }
+    }
+
+    /// Returns a copy of the recorded bytes: at most `limit` bytes from the
+    /// start of the body.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.inner.lock().bytes.clone()
+    }
+
+    /// Returns the total number of bytes seen so far, including those that
+    /// were not recorded because they exceeded the limit.
+    pub fn total(&self) -> u64 {
+        self.inner.lock().total
+    }
+
+    /// Returns `true` if more bytes were seen than were recorded.
+    pub fn is_truncated(&self) -> bool {
+        let recording = self.inner.lock();
+        recording.total > recording.bytes.len() as u64
+    }
+
+    /// Returns `true` if the captured body has been fully consumed or
+    /// abandoned, and no more bytes will be recorded.
+    pub fn is_finished(&self) -> bool {
+        self.inner.lock().finished
+    }
+
+    /// Registers `f` to be called once the capture is finished. If the
+    /// capture is already finished, `f` is called immediately.
+    pub fn on_finish<F: FnOnce(&Capture) + Send + 'static>(&self, f: F) {
+        let mut recording = self.inner.lock();
+        if recording.finished {
+            drop(recording);
+            f(self);
+        } else {
+            recording.callbacks.push(Box::new(f));
+        }
+    }
+
+    pub(crate) fn record(&self, bytes: &[u8]) {
+        let mut recording = self.inner.lock();
+        recording.total += bytes.len() as u64;
+        let remaining = recording.limit.saturating_sub(recording.bytes.len());
+        let n = std::cmp::min(remaining, bytes.len());
+        recording.bytes.extend_from_slice(&bytes[..n]);
+    }
+
+    fn finish(&self) {
+        let callbacks = {
+            let mut recording = self.inner.lock();
+            if recording.finished {
+                return;
+            }
+
+            recording.finished = true;
+            std::mem::replace(&mut recording.callbacks, vec![])
+        };
+
+        callbacks.into_iter().for_each(|f| f(self));
+    }
+}
+
+impl fmt::Debug for Capture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let recording = self.inner.lock();
+        f.debug_struct("Capture")
+            .field("recorded", &recording.bytes.len())
+            .field("limit", &recording.limit)
+            .field("total", &recording.total)
+            .field("finished", &recording.finished)
+            .finish()
+    }
+}
+
+/// The recording side of a [`Capture`]. Finishes the capture when dropped.
+struct CaptureSink(Capture);
+
+impl io::Write for CaptureSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.record(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for CaptureSink {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+/// An observer of a body stream: receives a copy of every byte read.
+pub(crate) struct Tee(Option<Box<dyn io::Write + Send>>);
+
+impl Tee {
+    pub(crate) fn new<W: io::Write + Send + 'static>(sink: W) -> Tee {
+        Tee(Some(Box::new(sink)))
+    }
+
+    pub(crate) fn capture(capture: Capture) -> Tee {
+        Tee::new(CaptureSink(capture))
+    }
+
+    /// Writes the bytes in `buf` after `start`, that is, those that were
+    /// filled by the most recent read, to the sink. If writing fails, the
+    /// sink is dropped and receives no further data; the read is unaffected.
+    #[inline]
+    pub(crate) fn record_since(&mut self, buf: &ReadBuf<'_>, start: usize) {
+        let filled = buf.filled();
+        if filled.len() > start {
+            if let Some(sink) = self.0.as_mut() {
+                if let Err(e) = sink.write_all(&filled[start..]) {
+                    warn_!("Tee sink failed: {}. Detaching.", e);
+                    self.0 = None;
+                }
+            }
+        }
+    }
+}
+
+/// A reader that records everything read from `R` into a [`Capture`].
+pub(crate) struct CaptureReader<R> {
+    reader: R,
+    tee: Tee,
+}
+
+impl<R> CaptureReader<R> {
+    pub(crate) fn new(reader: R, capture: Capture) -> Self {
+        CaptureReader { reader, tee: Tee::capture(capture) }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CaptureReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let start = buf.filled().len();
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            this.tee.record_since(buf, start);
+        }
+
+        poll
+    }
+}
+
+impl<R: AsyncSeek + Unpin> AsyncSeek for CaptureReader<R> {
+    fn start_seek(mut self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        Pin::new(&mut self.reader).start_seek(position)
+    }
+
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.reader).poll_complete(cx)
+    }
+}
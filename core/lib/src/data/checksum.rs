@@ -0,0 +1,231 @@
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::request::Request;
+
+/// A digest algorithm computed by [`Digesting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// SHA-256.
+    Sha256,
+    /// MD5.
+    Md5,
+    /// CRC-32 (IEEE).
+    Crc32,
+}
+
+/// Algorithms to compute for every [`TempFile`](crate::upload::TempFile), in
+/// managed state.
+///
+/// Digests requested by the request's `Content-MD5` or `Digest` headers are
+/// always computed, and verified. `Checksums` adds to those.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::data::{Algorithm, Checksums};
+///
+/// let rocket = rocket::ignite().manage(Checksums(vec![Algorithm::Sha256]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Checksums(pub Vec<Algorithm>);
+
+/// The digests of a stream, computed by [`Digesting`].
+///
+/// Only the digests of requested algorithms are present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Digests {
+    sha256: Option<[u8; 32]>,
+    md5: Option<[u8; 16]>,
+    crc32: Option<u32>,
+}
+
+/// A digest in a request header that doesn't match the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestMismatch {
+    /// The algorithm whose digest doesn't match.
+    pub algorithm: Algorithm,
+}
+
+/// An [`AsyncRead`] adapter computing digests of the bytes read through it.
+///
+/// Returned by [`DataStream::digesting()`](crate::data::DataStream::digesting()).
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::data::{Algorithm, Data, ToByteUnit};
+/// use rocket::tokio::io::AsyncReadExt;
+///
+/// async fn handler(data: Data) -> std::io::Result<String> {
+///     let mut stream = data.open(1.mebibytes()).digesting(&[Algorithm::Sha256]);
+///     let mut body = Vec::new();
+///     stream.read_to_end(&mut body).await?;
+///     let sha256 = stream.digests().sha256().unwrap();
+///     Ok(format!("{} bytes with SHA-256 {:x?}", body.len(), sha256))
+/// }
+/// ```
+pub struct Digesting<R> {
+    inner: R,
+    sha256: Option<Sha256>,
+    md5: Option<Md5>,
+    crc32: Option<crc32fast::Hasher>,
+}
+
+impl Digests {
+    /// Returns the SHA-256 digest, if it was computed.
+    pub fn sha256(&self) -> Option<&[u8]> {
+        self.sha256.as_ref().map(|d| &d[..])
+    }
+
+    /// Returns the MD5 digest, if it was computed.
+    pub fn md5(&self) -> Option<&[u8]> {
+        self.md5.as_ref().map(|d| &d[..])
+    }
+
+    /// Returns the CRC-32 checksum, if it was computed.
+    pub fn crc32(&self) -> Option<u32> {
+        self.crc32
+    }
+
+    /// Returns the digest computed with `algorithm`, big-endian for CRC-32,
+    /// if it was computed.
+    pub fn get(&self, algorithm: Algorithm) -> Option<Vec<u8>> {
+        match algorithm {
+            Algorithm::Sha256 => self.sha256().map(|d| d.to_vec()),
+            Algorithm::Md5 => self.md5().map(|d| d.to_vec()),
+            Algorithm::Crc32 => self.crc32.map(|c| c.to_be_bytes().to_vec()),
+        }
+    }
+
+    /// Checks the digests against those in `req`'s `Content-MD5` header and
+    /// `Digest` header's `sha-256` and `md5` entries. Digests in headers that
+    /// weren't computed, or that aren't valid base64, aren't checked.
+    pub fn verify(&self, req: &Request<'_>) -> Result<(), DigestMismatch> {
+        for (algorithm, expected) in requested(req) {
+            if let Some(actual) = self.get(algorithm) {
+                if actual != expected {
+                    return Err(DigestMismatch { algorithm });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the digests specified in `req`'s `Content-MD5` and `Digest`
+/// headers, decoded.
+fn requested(req: &Request<'_>) -> Vec<(Algorithm, Vec<u8>)> {
+    let mut requested = vec![];
+    if let Some(md5) = req.headers().get_one("Content-MD5") {
+        if let Ok(md5) = base64::decode(md5.trim()) {
+            requested.push((Algorithm::Md5, md5));
+        }
+    }
+
+    let entries = req.headers().get("Digest").flat_map(|v| v.split(','));
+    for entry in entries {
+        let mut parts = entry.trim().splitn(2, '=');
+        let (name, value) = match (parts.next(), parts.next()) {
+            (Some(name), Some(value)) => (name, value),
+            _ => continue,
+        };
+
+        let algorithm = match name {
+            n if n.eq_ignore_ascii_case("sha-256") => Algorithm::Sha256,
+            n if n.eq_ignore_ascii_case("md5") => Algorithm::Md5,
+            _ => continue,
+        };
+
+        if let Ok(digest) = base64::decode(value.trim()) {
+            requested.push((algorithm, digest));
+        }
+    }
+
+    requested
+}
+
+/// Returns the algorithms whose digests `req`'s headers specify.
+pub(crate) fn requested_algorithms(req: &Request<'_>) -> Vec<Algorithm> {
+    requested(req).into_iter().map(|(algorithm, _)| algorithm).collect()
+}
+
+impl<R> Digesting<R> {
+    /// Wraps `inner`, computing the digests of `algorithms`.
+    pub fn new(inner: R, algorithms: &[Algorithm]) -> Digesting<R> {
+        let has = |algorithm| algorithms.contains(&algorithm);
+        Digesting {
+            inner,
+            sha256: Some(Sha256::new()).filter(|_| has(Algorithm::Sha256)),
+            md5: Some(Md5::new()).filter(|_| has(Algorithm::Md5)),
+            crc32: Some(crc32fast::Hasher::new()).filter(|_| has(Algorithm::Crc32)),
+        }
+    }
+
+    /// Returns the digests of the bytes read so far.
+    pub fn digests(&self) -> Digests {
+        Digests {
+            sha256: self.sha256.clone().map(|h| h.finalize().into()),
+            md5: self.md5.clone().map(|h| h.finalize().into()),
+            crc32: self.crc32.clone().map(|h| h.finalize()),
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for Digesting<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let start = buf.filled().len();
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            let read = &buf.filled()[start..];
+            if let Some(ref mut sha256) = this.sha256 {
+                sha256.update(read);
+            }
+
+            if let Some(ref mut md5) = this.md5 {
+                md5.update(read);
+            }
+
+            if let Some(ref mut crc32) = this.crc32 {
+                crc32.update(read);
+            }
+        }
+
+        poll
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Algorithm::Sha256 => f.write_str("SHA-256"),
+            Algorithm::Md5 => f.write_str("MD5"),
+            Algorithm::Crc32 => f.write_str("CRC-32"),
+        }
+    }
+}
+
+impl fmt::Display for DigestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} digest of the body doesn't match the request's", self.algorithm)
+    }
+}
+
+impl std::error::Error for DigestMismatch {}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use crate::request::Request;
+use crate::outcome::Outcome::*;
+use crate::data::{self, Data, FromData, ByteUnit, ToByteUnit};
+use crate::http::Status;
+
+/// Data guard that reads the entire body, failing if it exceeds its limit.
+///
+/// Reading a body through [`Data::open()`] silently stops at the given limit,
+/// so a guard that reads a body into memory that way must take care to detect
+/// and reject truncated bodies itself. `Complete<T>` does so on its behalf: it
+/// reads the body into a `String` or `Vec<u8>` and fails with a status of `413
+/// Payload Too Large` and a [`CompleteError::TooLarge`], which carries the
+/// limit that applied, if the body is longer than the limit.
+///
+/// The limits are named `string` for `Complete<String>` and `bytes` for
+/// `Complete<Vec<u8>>` and default to 8KiB. A body that isn't valid UTF-8 when
+/// reading into a `String` fails with a status of `400 Bad Request` and a
+/// [`CompleteError::Io`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::Complete;
+///
+/// #[post("/notes", data = "<note>")]
+/// fn new_note(note: Complete<String>) -> String {
+///     format!("saved {} bytes", note.len())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Complete<T>(pub T);
+
+/// The error returned by the [`Complete`] data guard.
+#[derive(Debug)]
+pub enum CompleteError {
+    /// The body exceeded the contained limit.
+    TooLarge(ByteUnit),
+    /// An I/O error occurred while reading the body, or the body wasn't valid
+    /// UTF-8.
+    Io(io::Error),
+}
+
+const DEFAULT_LIMIT: ByteUnit = ByteUnit::Kibibyte(8);
+
+impl<T> Complete<T> {
+    /// Consumes `self` and returns the wrapped value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Complete;
+    ///
+    /// let complete = Complete(String::from("hi"));
+    /// assert_eq!(complete.into_inner(), "hi");
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Reads all of `data`, up to the limit named `name`.
+async fn read_complete(
+    req: &Request<'_>,
+    data: Data,
+    name: &str
+) -> data::Outcome<Vec<u8>, CompleteError> {
+    let limit = req.limits().get(name).unwrap_or(DEFAULT_LIMIT);
+    // Read one byte past the limit to determine whether there's more.
+    let bytes = match data.open(limit + 1.bytes()).stream_to_vec().await {
+        Ok(bytes) => bytes,
+        Err(e) => return Failure((Status::BadRequest, CompleteError::Io(e))),
+    };
+
+    if bytes.len() as u64 > limit.as_u64() {
+        return Failure((Status::PayloadTooLarge, CompleteError::TooLarge(limit)));
+    }
+
+    Success(bytes)
+}
+
+#[crate::async_trait]
+impl FromData for Complete<Vec<u8>> {
+    type Error = CompleteError;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        read_complete(req, data, "bytes").await.map(Complete)
+    }
+}
+
+#[crate::async_trait]
+impl FromData for Complete<String> {
+    type Error = CompleteError;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        let bytes = try_outcome!(read_complete(req, data, "string").await);
+        match String::from_utf8(bytes) {
+            Ok(string) => Success(Complete(string)),
+            Err(e) => {
+                let error = io::Error::new(io::ErrorKind::InvalidData, e);
+                Failure((Status::BadRequest, CompleteError::Io(error)))
+            }
+        }
+    }
+}
+
+impl<T> Deref for Complete<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Complete<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl fmt::Display for CompleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompleteError::TooLarge(limit) => write!(f, "body exceeds the limit of {}", limit),
+            CompleteError::Io(e) => write!(f, "failed to read body: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CompleteError {}
@@ -5,6 +5,7 @@ use crate::ext::AsyncReadBody;
 use crate::tokio::io::AsyncReadExt;
 use crate::data::data_stream::DataStream;
 use crate::data::ByteUnit;
+use crate::data::capture::{Capture, Tee};
 
 /// The number of bytes to read into the "peek" buffer.
 pub const PEEK_BYTES: usize = 512;
@@ -41,10 +42,20 @@ pub const PEEK_BYTES: usize = 512;
 /// The `peek` method returns a slice containing at most 512 bytes of buffered
 /// body data. This enables partially or fully reading from a `Data` object
 /// without consuming the `Data` object.
+///
+/// # Observing Data
+///
+/// The [`tee()`](Data::tee()) method registers a sink that receives a copy of
+/// the body as it is read by whichever guard eventually opens it, and
+/// [`capture()`](Data::capture()) registers a sink that records a bounded copy.
+/// This enables auditing, signature verification, and debugging in fairings,
+/// where the body cannot otherwise be read without consuming it. At most one
+/// observer can be registered.
 pub struct Data {
     buffer: Vec<u8>,
     is_complete: bool,
     stream: AsyncReadBody,
+    tee: Option<Tee>,
 }
 
 impl Data {
@@ -55,7 +66,7 @@ impl Data {
 
         let stream = AsyncReadBody::from(body);
         let buffer = Vec::with_capacity(PEEK_BYTES / 8);
-        Data { buffer, stream, is_complete: false }
+        Data { buffer, stream, is_complete: false, tee: None }
     }
 
     /// This creates a `data` object from a local data source `data`.
@@ -65,9 +76,29 @@ impl Data {
             buffer: data,
             stream: AsyncReadBody::empty(),
             is_complete: true,
+            tee: None,
         }
     }
 
+    /// Creates a `Data` whose body is `bytes`.
+    ///
+    /// This is useful for constructing the body of a request built with
+    /// [`Request::from_raw_parts()`](crate::Request::from_raw_parts()), for
+    /// instance, in fuzz targets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Data;
+    ///
+    /// let data = Data::from_bytes(b"hello".to_vec());
+    /// assert!(data.peek_complete());
+    /// ```
+    #[inline(always)]
+    pub fn from_bytes(bytes: Vec<u8>) -> Data {
+        Data::local(bytes)
+    }
+
     /// Returns the raw data stream, limited to `limit` bytes.
     ///
     /// The stream contains all of the data in the body of the request,
@@ -90,7 +121,86 @@ impl Data {
         let stream_limit = limit - buffer_limit;
         let buffer = Cursor::new(self.buffer).take(buffer_limit.into());
         let stream = self.stream.take(stream_limit.into());
-        DataStream { buffer, stream }
+        DataStream { buffer, stream, tee: self.tee }
+    }
+
+    /// Registers `sink` to receive a copy of every byte of the body as it is
+    /// read from the stream returned by [`open()`](Data::open()). Bytes that
+    /// were only peeked at are written when they are read from the opened
+    /// stream. The sink is dropped when the stream or `self` is dropped.
+    ///
+    /// At most one observer can be registered. If one already is, including
+    /// via [`capture()`](Data::capture()), returns `sink` as an `Err`. If
+    /// writing to `sink` fails, it is dropped and the error is logged; reading
+    /// the body is unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// async fn handler(mut data: Data) -> std::io::Result<String> {
+    ///     // Write a copy of the body to stderr as the body is read.
+    ///     assert!(data.tee(std::io::stderr()).is_ok());
+    ///     assert!(data.tee(std::io::stderr()).is_err());
+    ///     data.open(1.kibibytes()).stream_to_string().await
+    /// }
+    /// ```
+    pub fn tee<W: std::io::Write + Send + 'static>(&mut self, sink: W) -> Result<(), W> {
+        if self.tee.is_some() {
+            return Err(sink);
+        }
+
+        self.tee = Some(Tee::new(sink));
+        Ok(())
+    }
+
+    /// Returns `true` if an observer was registered via
+    /// [`tee()`](Data::tee()) or [`capture()`](Data::capture()).
+    #[inline(always)]
+    pub fn is_teed(&self) -> bool {
+        self.tee.is_some()
+    }
+
+    /// Begins recording the first `limit` bytes of the body as it is read
+    /// from the stream returned by [`open()`](Data::open()), returning a
+    /// handle to the recording. This is a [`tee()`](Data::tee()) into a
+    /// [`Capture`]: if an observer is already registered, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::{Request, Data};
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    ///
+    /// struct Capturer;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for Capturer {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Capturer", kind: Kind::Request }
+    ///     }
+    ///
+    ///     async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+    ///         let uri = req.uri().to_string();
+    ///         if let Some(capture) = data.capture(1.kibibytes()) {
+    ///             capture.on_finish(move |capture| {
+    ///                 let body = String::from_utf8_lossy(&capture.bytes()).into_owned();
+    ///                 println!("{}: {:?}", uri, body);
+    ///             });
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn capture(&mut self, limit: ByteUnit) -> Option<Capture> {
+        if self.tee.is_some() {
+            return None;
+        }
+
+        let capture = Capture::new(limit);
+        self.tee = Some(Tee::capture(capture.clone()));
+        Some(capture)
     }
 
     /// Retrieve at most `num` bytes from the `peek` buffer without consuming
@@ -6,6 +6,7 @@ use std::io::{self, Cursor};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncReadExt, ReadBuf, Take};
 
 use crate::ext::AsyncReadBody;
+use crate::data::capture::Tee;
 
 /// Raw data stream of a request body.
 ///
@@ -15,10 +16,31 @@ use crate::ext::AsyncReadBody;
 /// must be used as an opaque [`AsyncRead`] structure.
 pub struct DataStream {
     pub(crate) buffer: Take<Cursor<Vec<u8>>>,
-    pub(crate) stream: Take<AsyncReadBody>
+    pub(crate) stream: Take<AsyncReadBody>,
+    pub(crate) tee: Option<Tee>,
 }
 
 impl DataStream {
+    /// Returns a reader over `self` that computes the digests of `algorithms`
+    /// of the bytes read through it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Algorithm, Data, ToByteUnit};
+    ///
+    /// async fn handler(data: Data) -> std::io::Result<Option<u32>> {
+    ///     let mut stream = data.open(1.mebibytes()).digesting(&[Algorithm::Crc32]);
+    ///     rocket::tokio::io::copy(&mut stream, &mut rocket::tokio::io::sink()).await?;
+    ///     Ok(stream.digests().crc32())
+    /// }
+    /// ```
+    #[cfg(feature = "checksums")]
+    #[cfg_attr(nightly, doc(cfg(feature = "checksums")))]
+    pub fn digesting(self, algorithms: &[crate::data::Algorithm]) -> crate::data::Digesting<Self> {
+        crate::data::Digesting::new(self, algorithms)
+    }
+
     /// A helper method to write the body of the request to any `AsyncWrite`
     /// type.
     ///
@@ -117,6 +139,24 @@ impl AsyncRead for DataStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let start = buf.filled().len();
+        let this = &mut *self;
+        let poll = this.poll_read_inner(cx, buf);
+        if let (Poll::Ready(Ok(())), Some(tee)) = (&poll, &mut this.tee) {
+            tee.record_since(buf, start);
+        }
+
+        poll
+    }
+}
+
+impl DataStream {
+    #[inline(always)]
+    fn poll_read_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
         if self.buffer.limit() > 0 {
             trace_!("DataStream::buffer_read()");
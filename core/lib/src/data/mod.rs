@@ -4,10 +4,19 @@ mod data;
 mod data_stream;
 mod from_data;
 mod limits;
+mod complete;
+pub(crate) mod capture;
+#[cfg(feature = "checksums")]
+pub(crate) mod checksum;
 
 pub use self::data::Data;
 pub use self::data_stream::DataStream;
 pub use self::from_data::{FromData, Outcome, FromTransformedData, FromDataFuture};
 pub use self::from_data::{Transform, Transformed, TransformFuture};
 pub use self::limits::Limits;
+pub use self::complete::{Complete, CompleteError};
+pub use self::capture::Capture;
+#[cfg(feature = "checksums")]
+#[cfg_attr(nightly, doc(cfg(feature = "checksums")))]
+pub use self::checksum::{Algorithm, Checksums, Digests, Digesting, DigestMismatch};
 pub use ubyte::{ByteUnit, ToByteUnit};
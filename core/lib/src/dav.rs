@@ -0,0 +1,419 @@
+//! Helpers for building [WebDAV] endpoints.
+//!
+//! This module provides request guards for the WebDAV request headers
+//! [`Depth`], [`Destination`], and [`Overwrite`], and a [`MultiStatus`]
+//! responder for `207 Multi-Status` responses.
+//!
+//! Rocket does not yet route the WebDAV-specific methods such as `PROPFIND`,
+//! `MKCOL`, `COPY`, and `MOVE`, which [`Method`](crate::http::Method) cannot
+//! represent. Until it does, these helpers can be used with the standard
+//! methods WebDAV also relies on, such as `PUT` and `DELETE`.
+//!
+//! [WebDAV]: https://tools.ietf.org/html/rfc4918
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::path::PathBuf;
+//!
+//! use rocket::http::Status;
+//! use rocket::dav::{Depth, MultiStatus, DavResponse};
+//!
+//! # fn remove(_: &PathBuf) -> Result<(), Status> { Ok(()) }
+//! #[delete("/files/<path..>")]
+//! fn delete(path: PathBuf, depth: Option<Depth>) -> Result<MultiStatus, Status> {
+//!     // Collections may only be deleted in their entirety.
+//!     if depth.map_or(false, |d| d != Depth::Infinity) {
+//!         return Err(Status::BadRequest);
+//!     }
+//!
+//!     let href = format!("/files/{}", path.display());
+//!     let status = remove(&path).err().unwrap_or(Status::NoContent);
+//!     Ok(MultiStatus::new().response(DavResponse::new(href).status(status)))
+//! }
+//! ```
+
+use std::fmt::{self, Write};
+use std::io::Cursor;
+
+use crate::request::{self, FromRequest, Request};
+use crate::response::{self, Response, Responder};
+use crate::outcome::Outcome;
+use crate::http::{ContentType, Status};
+use crate::http::uri::{Absolute, Origin};
+
+/// Request guard for the `Depth` header.
+///
+/// Forwards if the request has no `Depth` header; the default depth depends on
+/// the method. Fails with a status of `400 Bad Request` and a [`DavError`] if
+/// the header is not one of `0`, `1`, or `infinity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Depth {
+    /// `Depth: 0`: the resource only.
+    Zero,
+    /// `Depth: 1`: the resource and its immediate members.
+    One,
+    /// `Depth: infinity`: the resource and all of its descendants.
+    Infinity,
+}
+
+/// Request guard for the `Destination` header of a `COPY` or `MOVE` request.
+///
+/// The header's value may be an absolute URI or an absolute path. Forwards if
+/// the request has no `Destination` header. Fails with a status of `400 Bad
+/// Request` and a [`DavError`] if the header is malformed.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::Request;
+/// # use rocket::http::{Header, Method};
+/// use rocket::dav::Destination;
+///
+/// # Request::example(Method::Put, "/a", |req| {
+/// # req.add_header(Header::new("Destination", "https://rocket.rs/b/c?d"));
+/// # let destination = rocket::async_test(req.guard::<Destination>()).unwrap();
+/// // Destination: https://rocket.rs/b/c?d
+/// assert_eq!(destination.origin().path(), "/b/c");
+/// assert_eq!(destination.authority(), Some("rocket.rs"));
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Destination {
+    origin: Origin<'static>,
+    authority: Option<String>,
+}
+
+/// Request guard for the `Overwrite` header.
+///
+/// Contains `true` if the header's value is `T` or the header is absent, which
+/// is equivalent, and `false` if it is `F`. Fails with a status of `400 Bad
+/// Request` and a [`DavError`] otherwise. This guard never forwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overwrite(pub bool);
+
+/// The error returned by the [`Depth`], [`Destination`], and [`Overwrite`]
+/// request guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DavError {
+    /// The named header's value is invalid.
+    Malformed(&'static str, String),
+}
+
+impl Destination {
+    /// Returns the path and query of the destination.
+    pub fn origin(&self) -> &Origin<'static> {
+        &self.origin
+    }
+
+    /// Returns the authority of the destination, if it is an absolute URI
+    /// with one.
+    pub fn authority(&self) -> Option<&str> {
+        self.authority.as_deref()
+    }
+
+    fn parse(value: &str) -> Option<Destination> {
+        if value.starts_with('/') {
+            let origin = Origin::parse_owned(value.to_string()).ok()?;
+            return Some(Destination { origin, authority: None });
+        }
+
+        let uri = Absolute::parse(value).ok()?;
+        let origin = match uri.origin() {
+            Some(origin) => Origin::parse_owned(origin.to_string()).ok()?,
+            None => Origin::dummy(),
+        };
+
+        let authority = uri.authority().map(|a| a.to_string());
+        Some(Destination { origin, authority })
+    }
+}
+
+/// Returns the value of the header `name`, if there is exactly one.
+fn header<'r>(req: &'r Request<'_>, name: &'static str) -> Result<Option<&'r str>, DavError> {
+    let mut values = req.headers().get(name);
+    match (values.next(), values.next()) {
+        (None, _) => Ok(None),
+        (Some(value), None) => Ok(Some(value.trim())),
+        (Some(_), Some(_)) => {
+            let all: Vec<_> = req.headers().get(name).collect();
+            Err(DavError::Malformed(name, all.join(", ")))
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Depth {
+    type Error = DavError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let depth = header(request, "Depth").and_then(|value| match value {
+            None => Ok(None),
+            Some("0") => Ok(Some(Depth::Zero)),
+            Some("1") => Ok(Some(Depth::One)),
+            Some(v) if v.eq_ignore_ascii_case("infinity") => Ok(Some(Depth::Infinity)),
+            Some(v) => Err(DavError::Malformed("Depth", v.into())),
+        });
+
+        match depth {
+            Ok(Some(depth)) => Outcome::Success(depth),
+            Ok(None) => Outcome::Forward(()),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Destination {
+    type Error = DavError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let destination = header(request, "Destination").and_then(|value| match value {
+            None => Ok(None),
+            Some(v) => Destination::parse(v)
+                .map(Some)
+                .ok_or_else(|| DavError::Malformed("Destination", v.into())),
+        });
+
+        match destination {
+            Ok(Some(destination)) => Outcome::Success(destination),
+            Ok(None) => Outcome::Forward(()),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Overwrite {
+    type Error = DavError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let overwrite = header(request, "Overwrite").and_then(|value| match value {
+            None | Some("T") => Ok(Overwrite(true)),
+            Some("F") => Ok(Overwrite(false)),
+            Some(v) => Err(DavError::Malformed("Overwrite", v.into())),
+        });
+
+        match overwrite {
+            Ok(overwrite) => Outcome::Success(overwrite),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+impl fmt::Display for Depth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Depth::Zero => write!(f, "0"),
+            Depth::One => write!(f, "1"),
+            Depth::Infinity => write!(f, "infinity"),
+        }
+    }
+}
+
+impl fmt::Display for DavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DavError::Malformed(name, value) => {
+                write!(f, "malformed `{}` header: {:?}", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DavError {}
+
+/// A `207 Multi-Status` response.
+///
+/// A `MultiStatus` responds with a status of `207 Multi-Status` and an XML
+/// `multistatus` body containing one `response` element for each
+/// [`DavResponse`] added with [`MultiStatus::response()`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::Status;
+/// use rocket::dav::{MultiStatus, DavResponse};
+///
+/// let multi = MultiStatus::new()
+///     .response(DavResponse::new("/files/a.txt").status(Status::NoContent))
+///     .response(DavResponse::new("/files/b.txt")
+///         .prop(Status::Ok, "getcontentlength", "42")
+///         .empty_prop(Status::NotFound, "getetag"));
+///
+/// assert!(multi.to_xml().contains("<D:href>/files/a.txt</D:href>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiStatus {
+    responses: Vec<DavResponse>,
+    description: Option<String>,
+}
+
+/// A single `response` element in a [`MultiStatus`].
+///
+/// A `DavResponse` reports either the status of an operation on the resource
+/// at its `href`, set with [`DavResponse::status()`], or the status of each of
+/// the resource's properties, added with [`DavResponse::prop()`] and
+/// [`DavResponse::empty_prop()`]. If neither
+/// is set, the response reports a status of `200 OK`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DavResponse {
+    href: String,
+    status: Option<Status>,
+    propstats: Vec<(Status, Vec<(String, Option<String>)>)>,
+    description: Option<String>,
+}
+
+impl MultiStatus {
+    /// Returns an empty `MultiStatus`.
+    pub fn new() -> MultiStatus {
+        MultiStatus::default()
+    }
+
+    /// Adds the response `response`.
+    pub fn response(mut self, response: DavResponse) -> Self {
+        self.responses.push(response);
+        self
+    }
+
+    /// Sets the `responsedescription` of the entire multi-status response.
+    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Returns the XML body of the response.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<D:multistatus xmlns:D=\"DAV:\">\n");
+        for response in &self.responses {
+            response.write_xml(&mut xml);
+        }
+
+        if let Some(description) = &self.description {
+            element(&mut xml, 1, "responsedescription", description);
+        }
+
+        xml.push_str("</D:multistatus>\n");
+        xml
+    }
+}
+
+impl DavResponse {
+    /// Returns a response for the resource at `href`.
+    pub fn new<S: Into<String>>(href: S) -> DavResponse {
+        DavResponse { href: href.into(), status: None, propstats: vec![], description: None }
+    }
+
+    /// Sets the status of the operation on the resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if properties were added: a response reports either a status or
+    /// property statuses.
+    pub fn status(mut self, status: Status) -> Self {
+        assert!(self.propstats.is_empty(), "a `DavResponse` with properties has no status");
+        self.status = Some(status);
+        self
+    }
+
+    /// Adds the `DAV:` property `name` with status `status` and text `value`.
+    /// Properties with the same status are grouped together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a status was set with [`DavResponse::status()`].
+    pub fn prop<N, V>(self, status: Status, name: N, value: V) -> Self
+        where N: Into<String>, V: Into<String>
+    {
+        self.add_prop(status, name.into(), Some(value.into()))
+    }
+
+    /// Adds the empty `DAV:` property `name` with status `status`, as is
+    /// done, for instance, to report a requested property as `404 Not Found`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a status was set with [`DavResponse::status()`].
+    pub fn empty_prop<N: Into<String>>(self, status: Status, name: N) -> Self {
+        self.add_prop(status, name.into(), None)
+    }
+
+    fn add_prop(mut self, status: Status, name: String, value: Option<String>) -> Self {
+        assert!(self.status.is_none(), "a `DavResponse` with a status has no properties");
+        match self.propstats.iter_mut().find(|(s, _)| *s == status) {
+            Some((_, props)) => props.push((name, value)),
+            None => self.propstats.push((status, vec![(name, value)])),
+        }
+
+        self
+    }
+
+    /// Sets the `responsedescription` of this response.
+    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    fn write_xml(&self, xml: &mut String) {
+        xml.push_str("  <D:response>\n");
+        element(xml, 2, "href", &self.href);
+        if self.propstats.is_empty() {
+            element(xml, 2, "status", &status_line(self.status.unwrap_or(Status::Ok)));
+        }
+
+        for (status, props) in &self.propstats {
+            xml.push_str("    <D:propstat>\n      <D:prop>\n");
+            for (name, value) in props {
+                match value {
+                    Some(value) => element(xml, 4, name, value),
+                    None => { let _ = writeln!(xml, "        <D:{}/>", name); }
+                }
+            }
+
+            xml.push_str("      </D:prop>\n");
+            element(xml, 3, "status", &status_line(*status));
+            xml.push_str("    </D:propstat>\n");
+        }
+
+        if let Some(description) = &self.description {
+            element(xml, 2, "responsedescription", description);
+        }
+
+        xml.push_str("  </D:response>\n");
+    }
+}
+
+fn status_line(status: Status) -> String {
+    format!("HTTP/1.1 {}", status)
+}
+
+/// Writes the `DAV:` element `name` containing the escaped text `text`.
+fn element(xml: &mut String, indent: usize, name: &str, text: &str) {
+    let _ = write!(xml, "{:1$}<D:{2}>", "", indent * 2, name);
+    for c in text.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            _ => xml.push(c),
+        }
+    }
+
+    let _ = writeln!(xml, "</D:{}>", name);
+}
+
+/// Responds with a status of `207 Multi-Status` and the XML body, with a
+/// Content-Type of `application/xml`.
+impl<'r> Responder<'r, 'static> for MultiStatus {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let xml = self.to_xml();
+        Response::build()
+            .status(Status::MultiStatus)
+            .header(ContentType::with_params("application", "xml", ("charset", "utf-8")))
+            .sized_body(xml.len(), Cursor::new(xml))
+            .ok()
+    }
+}
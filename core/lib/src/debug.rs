@@ -0,0 +1,274 @@
+//! Development-only endpoints for inspecting a running application.
+//!
+//! Attaching the [`DebugEndpoints`] fairing mounts a set of read-only
+//! endpoints at `/_rocket` that describe the application:
+//!
+//!   * `GET /_rocket`: an HTML page with all of the information below.
+//!   * `GET /_rocket/routes`: the route table as JSON.
+//!   * `GET /_rocket/config`: the configuration as JSON, with the secret key
+//!     redacted.
+//!   * `GET /_rocket/fairings`: the active fairings and their kinds as JSON.
+//!   * `GET /_rocket/limits`: the configured data limits as JSON.
+//!   * `GET /_rocket/requests`: the most recent requests as JSON, newest
+//!     first.
+//!
+//! The endpoints expose internals of the application and are intended for
+//! use during development only. They are **only** mounted when the debug
+//! profile is selected: in any other profile, attaching the fairing logs a
+//! warning and mounts nothing. Should the profile change after the fairing is
+//! attached, the endpoints forward. When the endpoints are mounted, a warning
+//! is logged at attach.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::debug::DebugEndpoints;
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite().attach(DebugEndpoints::fairing())
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde_json::{json, Value};
+
+use crate::{Rocket, Request, Response, Data, Route, Config};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::handler::{Handler, Outcome};
+use crate::response::content;
+use crate::http::Method;
+
+/// The base path at which the debug endpoints are mounted.
+pub const BASE: &str = "/_rocket";
+
+/// A fairing that mounts development-only inspection endpoints.
+///
+/// See the [module level documentation](crate::debug) for details.
+#[derive(Debug, Clone)]
+pub struct DebugEndpoints {
+    capacity: usize,
+}
+
+impl DebugEndpoints {
+    /// Returns a fairing that mounts the debug endpoints and records the most
+    /// recent 100 requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::debug::DebugEndpoints;
+    ///
+    /// let rocket = rocket::ignite().attach(DebugEndpoints::fairing());
+    /// ```
+    pub fn fairing() -> DebugEndpoints {
+        DebugEndpoints { capacity: 100 }
+    }
+
+    /// Sets the number of recent requests recorded to `capacity`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::debug::DebugEndpoints;
+    ///
+    /// let rocket = rocket::ignite().attach(DebugEndpoints::fairing().capacity(10));
+    /// ```
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+}
+
+/// The ring buffer of recent requests.
+struct RequestLog {
+    capacity: usize,
+    entries: Mutex<VecDeque<Value>>,
+}
+
+/// Request-local start time of a request.
+struct Start(Option<Instant>);
+
+/// The information served by a debug endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Index,
+    Routes,
+    Config,
+    Fairings,
+    Limits,
+    Requests,
+}
+
+impl Section {
+    const ALL: &'static [(Section, &'static str)] = &[
+        (Section::Routes, "routes"),
+        (Section::Config, "config"),
+        (Section::Fairings, "fairings"),
+        (Section::Limits, "limits"),
+        (Section::Requests, "requests"),
+    ];
+
+    fn json(self, rocket: &Rocket) -> Value {
+        match self {
+            Section::Index => {
+                Section::ALL.iter()
+                    .map(|&(section, name)| (name.to_string(), section.json(rocket)))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into()
+            }
+            Section::Routes => {
+                rocket.routes()
+                    .map(|route| json!({
+                        "name": route.name,
+                        "method": route.method.as_str(),
+                        "uri": route.uri.to_string(),
+                        "base": route.base(),
+                        "rank": route.rank,
+                        "format": route.format.as_ref().map(|f| f.to_string()),
+                    }))
+                    .collect()
+            }
+            Section::Config => {
+                let mut config = serde_json::to_value(rocket.config()).unwrap_or_default();
+                if let Some(map) = config.as_object_mut() {
+                    map.insert("profile".into(), json!(rocket.config().profile.to_string()));
+                    map.insert("secret_key".into(), json!("[redacted]"));
+                }
+
+                config
+            }
+            Section::Fairings => {
+                let kinds = [
+                    (Kind::Launch, "launch"),
+                    (Kind::Request, "request"),
+                    (Kind::Response, "response"),
+                    (Kind::Complete, "complete"),
+                ];
+
+                rocket.fairings.info()
+                    .map(|info| {
+                        let kinds: Vec<_> = kinds.iter()
+                            .filter(|(kind, _)| info.kind.is(*kind))
+                            .map(|(_, name)| *name)
+                            .collect();
+
+                        json!({ "name": info.name, "kinds": kinds })
+                    })
+                    .collect()
+            }
+            Section::Limits => {
+                serde_json::to_value(&rocket.config().limits).unwrap_or_default()
+            }
+            Section::Requests => {
+                rocket.state::<RequestLog>()
+                    .and_then(|log| log.entries.lock().ok())
+                    .map(|entries| entries.iter().cloned().collect())
+                    .unwrap_or_else(|| json!([]))
+            }
+        }
+    }
+}
+
+fn escape_html(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn html(rocket: &Rocket) -> String {
+    let mut page = String::from("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+        <title>Rocket Debug</title></head><body><h1>Rocket Debug</h1>");
+
+    for &(section, name) in Section::ALL {
+        let value = serde_json::to_string_pretty(&section.json(rocket)).unwrap_or_default();
+        page.push_str(&format!("<h2><a href=\"{}/{}\">{}</a></h2><pre>{}</pre>",
+            BASE, name, name, escape_html(&value)));
+    }
+
+    page.push_str("</body></html>");
+    page
+}
+
+#[crate::async_trait]
+impl Handler for Section {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        // Never serve outside of the debug profile, even if reconfigured.
+        let rocket = req.state.rocket;
+        if rocket.config().profile != Config::DEBUG_PROFILE {
+            return Outcome::forward(data);
+        }
+
+        match self {
+            Section::Index => Outcome::from(req, content::Html(html(rocket))),
+            section => Outcome::from(req, content::Json(section.json(rocket).to_string())),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for DebugEndpoints {
+    fn info(&self) -> Info {
+        Info { name: "Debug Endpoints", kind: Kind::Attach | Kind::Request | Kind::Response }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        if rocket.config().profile != Config::DEBUG_PROFILE {
+            warn!("Debug endpoints are only available in the debug profile.");
+            info_!("Not mounting debug endpoints in profile `{}`.", rocket.config().profile);
+            return Ok(rocket);
+        }
+
+        warn!("Debug endpoints are enabled at `{}`.", BASE);
+        info_!("These expose application internals and are for development only.");
+
+        let mut routes = vec![Route::new(Method::Get, "/", Section::Index)];
+        for &(section, name) in Section::ALL {
+            routes.push(Route::new(Method::Get, &format!("/{}", name), section));
+        }
+
+        let log = RequestLog { capacity: self.capacity, entries: Mutex::new(VecDeque::new()) };
+        Ok(rocket.manage(log).mount(BASE, routes))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data) {
+        req.local_cache(|| Start(Some(Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let log = match req.managed_state::<RequestLog>() {
+            Some(log) if !req.uri().path().starts_with(BASE) => log,
+            _ => return,
+        };
+
+        let latency = req.local_cache(|| Start(None)).0
+            .map(|start| start.elapsed().as_secs_f64() * 1000.0);
+
+        let entry = json!({
+            "method": req.method().as_str(),
+            "uri": req.uri().to_string(),
+            "status": res.status().code,
+            "route": req.route().map(|r| r.uri.to_string()),
+            "client_ip": req.client_ip().map(|ip| ip.to_string()),
+            "latency_ms": latency,
+        });
+
+        if let Ok(mut entries) = log.entries.lock() {
+            entries.push_front(entry);
+            entries.truncate(log.capacity);
+        }
+    }
+}
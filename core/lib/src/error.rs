@@ -82,6 +82,15 @@ pub enum ErrorKind {
     Collision(Vec<(Route, Route)>),
     /// A launch fairing reported an error.
     FailedFairings(Vec<&'static str>),
+    /// With [`Config::check_uris`](crate::Config::check_uris) enabled, `uri!`
+    /// invocations were found to target unmounted routes. Each entry is the
+    /// route as the invocation expected it to be mounted and the `file:line`
+    /// of the invocation.
+    DanglingUris(Vec<(Route, String)>),
+    /// Invalid command-line arguments were passed to [`Rocket::execute()`].
+    ///
+    /// [`Rocket::execute()`]: crate::Rocket::execute()
+    Cli(String),
 }
 
 impl From<ErrorKind> for Error {
@@ -139,7 +148,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::Io(e) => write!(f, "I/O error: {}", e),
             ErrorKind::Collision(_) => write!(f, "route collisions detected"),
             ErrorKind::FailedFairings(_) => write!(f, "a launch fairing failed"),
-            ErrorKind::Runtime(e) => write!(f, "runtime error: {}", e)
+            ErrorKind::DanglingUris(_) => write!(f, "`uri!` invocations target unmounted routes"),
+            ErrorKind::Runtime(e) => write!(f, "runtime error: {}", e),
+            ErrorKind::Cli(e) => write!(f, "invalid arguments: {}", e),
         }
     }
 }
@@ -194,11 +205,25 @@ impl Drop for Error {
 
                 panic!("aborting due to launch fairing failure");
             }
+            ErrorKind::DanglingUris(ref dangling) => {
+                error!("Rocket failed to launch due to `uri!`s targeting unmounted routes:");
+                for &(ref route, ref location) in dangling {
+                    info_!("{} {} {}", location, Paint::red("expects").italic(), route)
+                }
+
+                info_!("Note: The route may have been renamed or not mounted.");
+                panic!("dangling `uri!` invocations detected");
+            }
             ErrorKind::Runtime(ref err) => {
                 error!("An error occured in the runtime:");
                 info_!("{}", err);
                 panic!("aborting due to runtime failure");
             }
+            ErrorKind::Cli(ref e) => {
+                error!("Rocket failed to launch due to invalid arguments:");
+                info_!("{}", e);
+                panic!("aborting due to invalid arguments");
+            }
         }
     }
 }
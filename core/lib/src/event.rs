@@ -0,0 +1,308 @@
+//! A typed, in-process event bus.
+//!
+//! Every Rocket instance has an [`Events`] bus over which decoupled components
+//! exchange _events_: values of any type that is `Clone + Send + Sync +
+//! 'static`. Events are routed by type. An event emitted via
+//! [`Events::emit()`] or [`Rocket::emit()`] is delivered to every listener and
+//! every subscriber of its type; events of a type without listeners or
+//! subscribers are dropped.
+//!
+//!   * A _listener_, registered by attaching [`Events::fairing()`], is an
+//!     async function run in a new task for every event of its type. Listeners
+//!     suit cross-cutting concerns like audit logging or cache invalidation.
+//!
+//!   * A _subscriber_, obtained via [`Events::subscribe()`] or as the
+//!     [`Subscriber`] request guard, is a [`Stream`] of the events of its type
+//!     emitted after it subscribed. Subscribers suit long-lived responses
+//!     like event streams.
+//!
+//! Handlers emit events via the `&Events` request guard.
+//!
+//! # Example
+//!
+//! An audit log that records every user registration:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::event::Events;
+//!
+//! #[derive(Debug, Clone)]
+//! struct UserRegistered(String);
+//!
+//! #[post("/register/<name>")]
+//! fn register(events: &Events, name: String) {
+//!     events.emit(UserRegistered(name));
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .mount("/", routes![register])
+//!         .attach(Events::fairing("Audit Log", |event: UserRegistered| async move {
+//!             println!("audit: {:?}", event);
+//!         }))
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::{BoxFuture, Future, FutureExt};
+use futures::stream::Stream;
+use parking_lot::RwLock;
+
+use crate::{Rocket, Request};
+use crate::channel::{Channel, Subscription};
+use crate::fairing::AdHoc;
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+
+/// The number of events of each type buffered for subscribers. Subscribers
+/// that fall further behind skip the oldest events.
+const CAPACITY: usize = 256;
+
+type Listener<E> = Arc<dyn Fn(E) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// The listeners and subscribers of events of type `E`.
+struct Topic<E> {
+    channel: Channel<E>,
+    listeners: Vec<Listener<E>>,
+}
+
+/// The event bus of a Rocket instance.
+///
+/// See the [module level documentation](crate::event) for details.
+#[derive(Default)]
+pub struct Events {
+    topics: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl Events {
+    fn with_topic<E, R, F>(&self, f: F) -> R
+        where E: Clone + Send + Sync + 'static, F: FnOnce(&mut Topic<E>) -> R
+    {
+        let mut topics = self.topics.write();
+        let topic = topics.entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(Topic::<E> {
+                channel: Channel::new(CAPACITY),
+                listeners: vec![],
+            }));
+
+        f(topic.downcast_mut().expect("event topic type"))
+    }
+
+    /// Emits `event` to every listener and subscriber of events of type `E`,
+    /// returning the number of listeners and subscribers it was delivered to.
+    ///
+    /// Listeners are run in new tasks. If `emit` is called outside of an async
+    /// runtime, listeners are not run.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::event::Events;
+    ///
+    /// #[derive(Clone)]
+    /// struct CacheInvalidated(&'static str);
+    ///
+    /// let events = Events::default();
+    /// assert_eq!(events.emit(CacheInvalidated("users")), 0);
+    ///
+    /// let _subscriber = events.subscribe::<CacheInvalidated>();
+    /// assert_eq!(events.emit(CacheInvalidated("users")), 1);
+    /// ```
+    pub fn emit<E: Clone + Send + Sync + 'static>(&self, event: E) -> usize {
+        let (listeners, subscribers) = {
+            let topics = self.topics.read();
+            let topic = match topics.get(&TypeId::of::<E>()) {
+                Some(topic) => topic.downcast_ref::<Topic<E>>().expect("event topic type"),
+                None => return 0,
+            };
+
+            let subscribers = topic.channel.send("", event.clone());
+            (topic.listeners.clone(), subscribers)
+        };
+
+        if !listeners.is_empty() {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => for listener in &listeners {
+                    handle.spawn(listener(event.clone()));
+                },
+                Err(_) => {
+                    warn_!("Event emitted outside of a runtime: listeners were not run.");
+                    return subscribers;
+                }
+            }
+        }
+
+        listeners.len() + subscribers
+    }
+
+    /// Subscribes to the events of type `E` emitted from now on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use rocket::event::Events;
+    ///
+    /// let events = Events::default();
+    /// let mut subscriber = events.subscribe::<u32>();
+    /// events.emit(42u32);
+    /// assert_eq!(subscriber.recv().await, Some(42));
+    /// # });
+    /// ```
+    pub fn subscribe<E: Clone + Send + Sync + 'static>(&self) -> Subscriber<E> {
+        Subscriber(self.with_topic(|topic: &mut Topic<E>| topic.channel.subscribe("")))
+    }
+
+    /// Registers `listener` to be run for every event of type `E`.
+    fn listen<E, F, Fut>(&self, listener: F)
+        where E: Clone + Send + Sync + 'static,
+              F: Fn(E) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = ()> + Send + 'static
+    {
+        let listener: Listener<E> = Arc::new(move |event| listener(event).boxed());
+        self.with_topic(|topic: &mut Topic<E>| topic.listeners.push(listener));
+    }
+
+    /// Returns a fairing named `name` that registers `listener` to be run, in
+    /// a new task, for every event of type `E` emitted by the application.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::event::Events;
+    ///
+    /// #[derive(Clone)]
+    /// struct CacheInvalidated(&'static str);
+    ///
+    /// let fairing = Events::fairing("Cache", |event: CacheInvalidated| async move {
+    ///     println!("invalidating {}", event.0);
+    /// });
+    ///
+    /// let rocket = rocket::ignite().attach(fairing);
+    /// ```
+    pub fn fairing<E, F, Fut>(name: &'static str, listener: F) -> AdHoc
+        where E: Clone + Send + Sync + 'static,
+              F: Fn(E) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = ()> + Send + 'static
+    {
+        AdHoc::on_attach(name, move |rocket: Rocket| async move {
+            rocket.events.listen(listener);
+            Ok(rocket)
+        })
+    }
+}
+
+impl fmt::Debug for Events {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Events").field("types", &self.topics.read().len()).finish()
+    }
+}
+
+/// A stream of the events of type `E`, usable as a request guard.
+///
+/// As a request guard, a `Subscriber` subscribes to the events emitted after
+/// the request is received. The stream ends when the `Rocket` instance is
+/// dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::event::Subscriber;
+/// use rocket::response::Stream;
+/// use rocket::tokio::io::AsyncRead;
+///
+/// #[derive(Clone)]
+/// struct PriceChanged(u64);
+///
+/// #[get("/prices")]
+/// fn prices(prices: Subscriber<PriceChanged>) -> Stream<impl AsyncRead + Send> {
+///     let reader = prices.into_subscription()
+///         .reader(|price| format!("data: {}\n\n", price.0).into());
+///
+///     Stream::from(reader)
+/// }
+/// ```
+pub struct Subscriber<E>(Subscription<E>);
+
+impl<E> Subscriber<E> {
+    /// Waits for and returns the next event, or `None` if the stream has
+    /// ended.
+    pub async fn recv(&mut self) -> Option<E> {
+        self.0.recv().await
+    }
+
+    /// Returns the underlying [`Subscription`].
+    pub fn into_subscription(self) -> Subscription<E> {
+        self.0
+    }
+}
+
+impl<E> Stream for Subscriber<E> {
+    type Item = E;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<E>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+}
+
+impl<E> fmt::Debug for Subscriber<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Subscriber").field(&std::any::type_name::<E>()).finish()
+    }
+}
+
+impl Rocket {
+    /// Returns the event bus of this instance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let rocket = rocket::ignite();
+    /// let subscriber = rocket.events().subscribe::<u32>();
+    /// ```
+    pub fn events(&self) -> &Events {
+        &self.events
+    }
+
+    /// Emits `event` on the event bus of this instance. Equivalent to
+    /// `self.events().emit(event)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[derive(Clone)]
+    /// struct Launched;
+    ///
+    /// let rocket = rocket::ignite();
+    /// rocket.emit(Launched);
+    /// ```
+    pub fn emit<E: Clone + Send + Sync + 'static>(&self, event: E) -> usize {
+        self.events.emit(event)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a Events {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(req.state.rocket.events())
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r, E: Clone + Send + Sync + 'static> FromRequest<'a, 'r> for Subscriber<E> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(req.state.rocket.events().subscribe())
+    }
+}
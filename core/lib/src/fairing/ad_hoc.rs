@@ -3,6 +3,7 @@ use std::sync::Mutex;
 use futures::future::{Future, BoxFuture};
 
 use crate::{Rocket, Request, Response, Data};
+use crate::response::Completion;
 use crate::fairing::{Fairing, Kind, Info};
 
 /// A ad-hoc fairing that can be created from a function or closure.
@@ -13,9 +14,10 @@ use crate::fairing::{Fairing, Kind, Info};
 /// # Usage
 ///
 /// Use the [`on_attach`](#method.on_attach), [`on_launch`](#method.on_launch),
-/// [`on_request`](#method.on_request), or [`on_response`](#method.on_response)
-/// constructors to create an `AdHoc` structure from a function or closure.
-/// Then, simply attach the structure to the `Rocket` instance.
+/// [`on_request`](#method.on_request), [`on_response`](#method.on_response),
+/// or [`on_complete`](#method.on_complete) constructors to create an `AdHoc`
+/// structure from a function or closure. Then, simply attach the structure to
+/// the `Rocket` instance.
 ///
 /// # Example
 ///
@@ -77,6 +79,11 @@ enum AdHocKind {
     /// sent to a client.
     Response(Box<dyn for<'a> Fn(&'a Request<'_>, &'a mut Response<'_>)
         -> BoxFuture<'a, ()> + Send + Sync + 'static>),
+
+    /// An ad-hoc **complete** fairing. Called when a response has been written
+    /// to a client or writing it has failed.
+    Complete(Box<dyn for<'a> Fn(&'a Request<'_>, &'a Completion)
+        -> BoxFuture<'a, ()> + Send + Sync + 'static>),
 }
 
 impl AdHoc {
@@ -212,6 +219,30 @@ impl AdHoc {
     {
         AdHoc { name, kind: AdHocKind::Response(Box::new(f)) }
     }
+
+    /// Constructs an `AdHoc` complete fairing named `name`. The function `f`
+    /// will be called and the returned `Future` will be `await`ed by Rocket
+    /// once a response has been written to the client or writing it failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// // A fairing that reports responses cut short.
+    /// let fairing = AdHoc::on_complete("Cut Short", |req, completion| {
+    ///     Box::pin(async move {
+    ///         if !completion.is_complete() {
+    ///             println!("{}: only {} bytes sent", req.uri(), completion.bytes_written());
+    ///         }
+    ///     })
+    /// });
+    /// ```
+    pub fn on_complete<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
+        where F: for<'a> Fn(&'a Request<'_>, &'a Completion) -> BoxFuture<'a, ()>
+    {
+        AdHoc { name, kind: AdHocKind::Complete(Box::new(f)) }
+    }
 }
 
 #[crate::async_trait]
@@ -222,6 +253,7 @@ impl Fairing for AdHoc {
             AdHocKind::Launch(_) => Kind::Launch,
             AdHocKind::Request(_) => Kind::Request,
             AdHocKind::Response(_) => Kind::Response,
+            AdHocKind::Complete(_) => Kind::Complete,
         };
 
         Info { name: self.name, kind }
@@ -258,4 +290,10 @@ impl Fairing for AdHoc {
             callback(req, res).await;
         }
     }
+
+    async fn on_complete(&self, req: &Request<'_>, completion: &Completion) {
+        if let AdHocKind::Complete(ref callback) = self.kind {
+            callback(req, completion).await;
+        }
+    }
 }
@@ -1,8 +1,6 @@
 use crate::{Rocket, Request, Response, Data};
-use crate::fairing::{Fairing, Kind};
-use crate::logger::PaintExt;
-
-use yansi::Paint;
+use crate::response::Completion;
+use crate::fairing::{Fairing, Info, Kind};
 
 #[derive(Default)]
 pub struct Fairings {
@@ -12,6 +10,7 @@ pub struct Fairings {
     launch: Vec<usize>,
     request: Vec<usize>,
     response: Vec<usize>,
+    complete: Vec<usize>,
 }
 
 impl Fairings {
@@ -42,6 +41,7 @@ impl Fairings {
             if kind.is(Kind::Launch) { self.launch.push(index); }
             if kind.is(Kind::Request) { self.request.push(index); }
             if kind.is(Kind::Response) { self.response.push(index); }
+            if kind.is(Kind::Complete) { self.complete.push(index); }
         }
     }
 
@@ -72,32 +72,24 @@ impl Fairings {
         }
     }
 
-    pub fn failures(&self) -> Option<&[&'static str]> {
-        if self.attach_failures.is_empty() {
-            None
-        } else {
-            Some(&self.attach_failures)
+    #[inline(always)]
+    pub async fn handle_complete(&self, request: &Request<'_>, completion: &Completion) {
+        for &i in &self.complete {
+            self.all_fairings[i].on_complete(request, completion).await;
         }
     }
 
-    fn info_for(&self, kind: &str, fairings: &[usize]) {
-        if !fairings.is_empty() {
-            let num = fairings.len();
-            let names = fairings.iter().cloned()
-                .map(|i| self.all_fairings[i].info().name)
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            info_!("{} {}: {}", Paint::default(num).bold(), kind, Paint::default(names).bold());
-        }
+    /// The info of every fairing with a launch, request, response, or complete
+    /// callback.
+    pub fn info(&self) -> impl Iterator<Item = Info> + '_ {
+        self.all_fairings.iter().map(|fairing| fairing.info())
     }
 
-    pub fn pretty_print_counts(&self) {
-        if !self.all_fairings.is_empty() {
-            info!("{}{}:", Paint::emoji("📡 "), Paint::magenta("Fairings"));
-            self.info_for("launch", &self.launch);
-            self.info_for("request", &self.request);
-            self.info_for("response", &self.response);
+    pub fn failures(&self) -> Option<&[&'static str]> {
+        if self.attach_failures.is_empty() {
+            None
+        } else {
+            Some(&self.attach_failures)
         }
     }
 }
@@ -22,7 +22,7 @@ use std::ops::BitOr;
 /// }
 /// # ;
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Info {
     /// The name of the fairing.
     pub name: &'static str,
@@ -40,6 +40,7 @@ pub struct Info {
 ///   * Launch
 ///   * Request
 ///   * Response
+///   * Complete
 ///
 /// Two `Kind` structures can be `or`d together to represent a combination. For
 /// instance, to represent a fairing that is both a launch and request fairing,
@@ -58,6 +59,8 @@ impl Kind {
     pub const Request: Kind = Kind(0b0100);
     /// `Kind` flag representing a request for a 'response' callback.
     pub const Response: Kind = Kind(0b1000);
+    /// `Kind` flag representing a request for a 'complete' callback.
+    pub const Complete: Kind = Kind(0b10000);
 
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the kinds in `other` are also in `self`.
@@ -1,4 +1,5 @@
-//! Fairings: callbacks at attach, launch, request, and response time.
+//! Fairings: callbacks at attach, launch, request, response, and completion
+//! time.
 //!
 //! Fairings allow for structured interposition at various points in the
 //! application lifetime. Fairings can be seen as a restricted form of
@@ -48,6 +49,7 @@
 //! abundantly clear, a fairing should not rewrite every request.
 
 use crate::{Rocket, Request, Response, Data};
+use crate::response::Completion;
 
 mod fairings;
 mod ad_hoc;
@@ -94,12 +96,13 @@ pub use self::info_kind::{Info, Kind};
 ///
 /// ## Fairing Callbacks
 ///
-/// There are four kinds of fairing callbacks: attach, launch, request, and
-/// response. A fairing can request any combination of these callbacks through
-/// the `kind` field of the `Info` structure returned from the `info` method.
-/// Rocket will only invoke the callbacks set in the `kind` field.
+/// There are five kinds of fairing callbacks: attach, launch, request,
+/// response, and complete. A fairing can request any combination of these
+/// callbacks through the `kind` field of the `Info` structure returned from
+/// the `info` method. Rocket will only invoke the callbacks set in the `kind`
+/// field.
 ///
-/// The four callback kinds are as follows:
+/// The five callback kinds are as follows:
 ///
 ///   * **Attach (`on_attach`)**
 ///
@@ -150,15 +153,29 @@ pub use self::info_kind::{Info, Kind};
 ///     Apart from any change made by other fairings, Rocket sets the method for
 ///     `HEAD` requests to `GET` if there is no matching `HEAD` handler for that
 ///     request. Additionally, Rocket will automatically strip the body for
-///     `HEAD` requests _after_ response fairings have run.
+///     `HEAD` requests _after_ response fairings have run. Cookie changes made
+///     via [`Request::cookies()`] are emitted only after response fairings have
+///     run, so a response callback may both inspect them, via
+///     [`CookieJar::delta()`], and make its own.
+///
+///   * **Complete (`on_complete`)**
+///
+///     A complete callback, represented by the [`Fairing::on_complete()`]
+///     method, is called once the server has finished writing a response to
+///     the client, whether or not writing succeeded. It receives the request
+///     and a [`Completion`] recording the number of body bytes written and the
+///     error that ended writing early, if any, such as the client
+///     disconnecting mid-body. A complete callback can't modify the response.
+///     Responses dispatched via a [`local`](crate::local) client aren't
+///     written to a connection, so complete callbacks don't run for them.
 ///
 /// # Implementing
 ///
 /// A `Fairing` implementation has one required method: [`info`]. A `Fairing`
 /// can also implement any of the available callbacks: `on_attach`, `on_launch`,
-/// `on_request`, and `on_response`. A `Fairing` _must_ set the appropriate
-/// callback kind in the `kind` field of the returned `Info` structure from
-/// [`info`] for a callback to actually be called by Rocket.
+/// `on_request`, `on_response`, and `on_complete`. A `Fairing` _must_ set the
+/// appropriate callback kind in the `kind` field of the returned `Info`
+/// structure from [`info`] for a callback to actually be called by Rocket.
 ///
 /// ## Fairing `Info`
 ///
@@ -181,6 +198,8 @@ pub use self::info_kind::{Info, Kind};
 ///      return a `kind` field with the value `Kind::Launch | Kind::Response`.
 ///
 /// [`info`]: Fairing::info()
+/// [`Request::cookies()`]: crate::Request::cookies()
+/// [`CookieJar::delta()`]: crate::http::CookieJar::delta()
 ///
 /// ## Restrictions
 ///
@@ -454,6 +473,20 @@ pub trait Fairing: Send + Sync + 'static {
     /// The default implementation of this method does nothing.
     #[allow(unused_variables)]
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {}
+
+    /// The complete callback.
+    ///
+    /// This method is called once a response has been written to the client,
+    /// or writing it has failed, if `Kind::Complete` is in the `kind` field of
+    /// the `Info` structure for this fairing. The `&Request` parameter is the
+    /// request that was routed, and the `&Completion` parameter records how
+    /// writing the response ended.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    #[allow(unused_variables)]
+    async fn on_complete(&self, req: &Request<'_>, completion: &Completion) {}
 }
 
 #[crate::async_trait]
@@ -482,4 +515,9 @@ impl<T: Fairing> Fairing for std::sync::Arc<T> {
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         (self as &T).on_response(req, res).await;
     }
+
+    #[inline]
+    async fn on_complete(&self, req: &Request<'_>, completion: &Completion) {
+        (self as &T).on_complete(req, completion).await;
+    }
 }
@@ -0,0 +1,303 @@
+//! Feature flags: enabling and disabling routes at runtime.
+//!
+//! A feature flag is a named boolean that determines whether a feature of an
+//! application is available. Attaching [`Flags::fairing()`] places a [`Flags`]
+//! registry in managed state. Routes are gated on a flag with the
+//! [`FeatureFlag`] request guard, which forwards when the flag is disabled.
+//! When no other route matches, the request thus fails with a `404`.
+//!
+//! A flag's state is resolved, in order, from:
+//!
+//!   1. A runtime override, set via [`Flags::set()`] and removed via
+//!      [`Flags::clear()`].
+//!   2. The [`FlagProvider`] registered via [`Flags::fairing_with()`], if any.
+//!   3. The `flags` configuration parameter, a table of flag names to
+//!      booleans.
+//!
+//! A flag with no state is disabled.
+//!
+//! # Configuration
+//!
+//! ```toml
+//! [default.flags]
+//! new_checkout = false
+//! beta_search = true
+//! ```
+//!
+//! # Example
+//!
+//! Gate a route on the `new_checkout` flag and flip the flag at runtime from
+//! an administrative route. Since any client could otherwise flip flags, the
+//! route requires an `Admin` request guard that authenticates the caller:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::State;
+//! use rocket::flags::{Flag, FeatureFlag, Flags};
+//! # use rocket::request::{self, FromRequest, Request};
+//! # struct Admin;
+//! # #[rocket::async_trait]
+//! # impl<'a, 'r> FromRequest<'a, 'r> for Admin {
+//! #     type Error = ();
+//! #     async fn from_request(_: &'a Request<'r>) -> request::Outcome<Self, ()> {
+//! #         rocket::outcome::Outcome::Forward(())
+//! #     }
+//! # }
+//!
+//! struct NewCheckout;
+//!
+//! impl Flag for NewCheckout {
+//!     const NAME: &'static str = "new_checkout";
+//! }
+//!
+//! #[get("/checkout")]
+//! fn checkout(_flag: FeatureFlag<NewCheckout>) -> &'static str {
+//!     "the new checkout"
+//! }
+//!
+//! #[post("/admin/flags/<name>/<enabled>")]
+//! fn flip(_admin: Admin, flags: State<'_, Flags>, name: String, enabled: bool) {
+//!     flags.set(name, enabled);
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .attach(Flags::fairing())
+//!         .mount("/", routes![checkout, flip])
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use parking_lot::RwLock;
+
+use crate::{Rocket, Request};
+use crate::fairing::AdHoc;
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::Status;
+
+/// A compile-time name for a feature flag, used with [`FeatureFlag`].
+///
+/// See the [module level documentation](crate::flags) for an example.
+pub trait Flag: Send + Sync + 'static {
+    /// The name of the flag.
+    const NAME: &'static str;
+}
+
+/// A source of feature flag states, such as a remote flag service.
+///
+/// A provider is registered via [`Flags::fairing_with()`]. It is consulted for
+/// every flag without a runtime override; providers backed by remote services
+/// should thus cache states locally.
+///
+/// # Example
+///
+/// A provider that enables flags for requests with an `X-Beta` header:
+///
+/// ```rust
+/// use rocket::Request;
+/// use rocket::flags::{Flags, FlagProvider};
+///
+/// struct BetaTesters;
+///
+/// #[rocket::async_trait]
+/// impl FlagProvider for BetaTesters {
+///     async fn is_enabled(&self, _name: &str, req: &Request<'_>) -> Option<bool> {
+///         match req.headers().contains("X-Beta") {
+///             true => Some(true),
+///             false => None,
+///         }
+///     }
+/// }
+///
+/// let rocket = rocket::ignite().attach(Flags::fairing_with(BetaTesters));
+/// ```
+#[crate::async_trait]
+pub trait FlagProvider: Send + Sync + 'static {
+    /// Returns the state of the flag `name` for `req`, or `None` if this
+    /// provider does not know the flag.
+    async fn is_enabled(&self, name: &str, req: &Request<'_>) -> Option<bool>;
+}
+
+/// The registry of feature flag states, in managed state.
+///
+/// See the [module level documentation](crate::flags) for details.
+pub struct Flags {
+    overrides: RwLock<HashMap<String, bool>>,
+    provider: Option<Box<dyn FlagProvider>>,
+    config: HashMap<String, bool>,
+}
+
+impl Flags {
+    /// Returns a fairing that places a `Flags` registry, configured by the
+    /// `flags` configuration parameter, in managed state. Launch fails if the
+    /// configuration is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::flags::Flags;
+    ///
+    /// let rocket = rocket::ignite().attach(Flags::fairing());
+    /// ```
+    pub fn fairing() -> AdHoc {
+        Flags::attach(None)
+    }
+
+    /// Like [`Flags::fairing()`], but additionally consults `provider` for
+    /// flags without a runtime override before falling back to configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    /// use rocket::flags::{Flags, FlagProvider};
+    ///
+    /// struct AlwaysOn;
+    ///
+    /// #[rocket::async_trait]
+    /// impl FlagProvider for AlwaysOn {
+    ///     async fn is_enabled(&self, _: &str, _: &Request<'_>) -> Option<bool> {
+    ///         Some(true)
+    ///     }
+    /// }
+    ///
+    /// let rocket = rocket::ignite().attach(Flags::fairing_with(AlwaysOn));
+    /// ```
+    pub fn fairing_with<P: FlagProvider>(provider: P) -> AdHoc {
+        Flags::attach(Some(Box::new(provider)))
+    }
+
+    fn attach(provider: Option<Box<dyn FlagProvider>>) -> AdHoc {
+        AdHoc::on_attach("Feature Flags", |rocket: Rocket| async move {
+            let config = match rocket.figment().extract_inner::<HashMap<String, bool>>("flags") {
+                Ok(config) => config,
+                Err(e) if e.missing() => HashMap::new(),
+                Err(e) => {
+                    crate::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            let overrides = RwLock::new(HashMap::new());
+            Ok(rocket.manage(Flags { overrides, provider, config }))
+        })
+    }
+
+    /// Returns whether the flag `name` is enabled for `req`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    /// use rocket::flags::Flags;
+    ///
+    /// async fn beta_search(req: &Request<'_>) -> bool {
+    ///     match req.managed_state::<Flags>() {
+    ///         Some(flags) => flags.is_enabled("beta_search", req).await,
+    ///         None => false,
+    ///     }
+    /// }
+    /// ```
+    pub async fn is_enabled(&self, name: &str, req: &Request<'_>) -> bool {
+        let overridden = self.overrides.read().get(name).copied();
+        if let Some(enabled) = overridden {
+            return enabled;
+        }
+
+        if let Some(ref provider) = self.provider {
+            if let Some(enabled) = provider.is_enabled(name, req).await {
+                return enabled;
+            }
+        }
+
+        self.config.get(name).copied().unwrap_or(false)
+    }
+
+    /// Overrides the state of the flag `name` to `enabled` until the override
+    /// is replaced or [cleared](Flags::clear()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::State;
+    /// use rocket::flags::Flags;
+    ///
+    /// fn kill_switch(flags: State<'_, Flags>) {
+    ///     flags.set("new_checkout", false);
+    /// }
+    /// ```
+    pub fn set<N: Into<String>>(&self, name: N, enabled: bool) {
+        let name = name.into();
+        info!("Feature flag `{}` set to `{}`.", name, enabled);
+        self.overrides.write().insert(name, enabled);
+    }
+
+    /// Removes the runtime override of the flag `name`, if any, returning
+    /// the flag to its provided or configured state. Returns the removed
+    /// override.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::State;
+    /// use rocket::flags::Flags;
+    ///
+    /// fn reset(flags: State<'_, Flags>) {
+    ///     flags.clear("new_checkout");
+    /// }
+    /// ```
+    pub fn clear(&self, name: &str) -> Option<bool> {
+        self.overrides.write().remove(name)
+    }
+
+    /// Returns the runtime overrides currently in effect.
+    pub fn overrides(&self) -> HashMap<String, bool> {
+        self.overrides.read().clone()
+    }
+}
+
+/// Request guard that succeeds only when the feature flag `F` is enabled.
+///
+/// If the flag is disabled, the guard forwards. If no other route matches,
+/// the request fails with a `404`. If [`Flags`] is not in managed state, the
+/// guard fails with a `500`.
+///
+/// See the [module level documentation](crate::flags) for an example.
+pub struct FeatureFlag<F: Flag>(PhantomData<F>);
+
+impl<F: Flag> FeatureFlag<F> {
+    /// Returns the name of the flag.
+    pub fn name(&self) -> &'static str {
+        F::NAME
+    }
+}
+
+impl<F: Flag> std::fmt::Debug for FeatureFlag<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FeatureFlag").field(&F::NAME).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r, F: Flag> FromRequest<'a, 'r> for FeatureFlag<F> {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let flags = match req.managed_state::<Flags>() {
+            Some(flags) => flags,
+            None => {
+                error_!("Feature flag `{}` used without attaching `Flags`.", F::NAME);
+                return Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        match flags.is_enabled(F::NAME, req).await {
+            true => Outcome::Success(FeatureFlag(PhantomData)),
+            false => Outcome::Forward(()),
+        }
+    }
+}
@@ -161,6 +161,41 @@ impl<F: Clone + Sync + Send + 'static> Handler for F
     }
 }
 
+/// Runs the synchronous, potentially blocking function `f` on the blocking
+/// thread pool of the current runtime without stalling the async workers,
+/// returning its result.
+///
+/// This is the function invoked by routes declared `blocking`, as in
+/// `#[get("/report", blocking)]`, and can be used directly in `async` handlers
+/// to run CPU-heavy code or synchronous libraries. Since `f` is run on another
+/// thread, it and its return value must be `Send` and `'static`.
+///
+/// # Panics
+///
+/// If `f` panics, the panic is resumed in the calling task.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::handler::blocking;
+///
+/// # fn render_report(id: usize) -> String { id.to_string() }
+/// #[get("/report/<id>")]
+/// async fn report(id: usize) -> String {
+///     blocking(move || render_report(id)).await
+/// }
+/// ```
+pub async fn blocking<F, R>(f: F) -> R
+    where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => panic!("blocking task failed to complete: {}", e),
+    }
+}
+
 // A handler to use when one is needed temporarily. Don't use outside of Rocket!
 #[doc(hidden)]
 pub fn dummy<'r>(r: &'r Request<'_>, _: Data) -> HandlerFuture<'r> {
@@ -0,0 +1,323 @@
+//! Mounting services written against the `http` and `tower` ecosystem.
+//!
+//! [`HttpService`] adapts any [`tower::Service`] that maps an
+//! [`http::Request`] to an [`http::Response`], including services built with
+//! `hyper::service::service_fn` and services wrapped in `tower` layers, into a
+//! set of Rocket routes. This allows services written for other frameworks to
+//! be mounted into a Rocket application and migrated incrementally.
+//!
+//! The service must be written against `http` 0.2, the version used by
+//! Rocket's HTTP stack, and `tower-service` 0.3.
+//!
+//! [`tower::Service`]: https://docs.rs/tower-service/0.3/tower_service/trait.Service.html
+//! [`http::Request`]: https://docs.rs/http/0.2/http/request/struct.Request.html
+//! [`http::Response`]: https://docs.rs/http/0.2/http/response/struct.Response.html
+//!
+//! Types from the `http` crate can also be used directly in Rocket routes:
+//! `http::request::Parts` is a request guard, and `http::Response<T>` is a
+//! responder for any responder `T`. Both are re-exported from
+//! [`rocket::http::hyper`](crate::http::hyper).
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::convert::Infallible;
+//!
+//! use rocket::interop::HttpService;
+//! # use rocket::http::hyper as hyper;
+//! use hyper::{service_fn, Body, Request, Response};
+//!
+//! async fn legacy(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+//!     let message = format!("legacy handler for {}", req.uri().path());
+//!     Ok(Response::new(Body::from(message)))
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite().mount("/legacy", HttpService::new(service_fn(legacy)))
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::io::Cursor;
+
+use futures::future::poll_fn;
+
+use crate::{Request, Data, Route};
+use crate::handler::{Handler, Outcome};
+use crate::data::{ByteUnit, ToByteUnit};
+use crate::outcome::Outcome::{Success, Failure};
+use crate::request::{self, FromRequest};
+use crate::response::{self, Response, Responder};
+use crate::http::{Method, Status, Header};
+use crate::http::hyper::{self, HttpBody, Service};
+
+/// A [`Handler`] that dispatches requests to an `http` service.
+///
+/// When mounted, an `HttpService` routes requests of every method to any path
+/// at or below the mount point, at a rank of [`HttpService::DEFAULT_RANK`]
+/// unless configured otherwise via [`HttpService::rank()`]. Each request is
+/// converted to an `http::Request` with the request's method, full URI, and
+/// headers. The remote address of the client, if known, is available as a
+/// `SocketAddr` in the request's extensions. The request body is read into
+/// memory, up to 1MiB unless configured otherwise via
+/// [`HttpService::limit()`]; a larger body results in a `413 Payload Too
+/// Large` error.
+///
+/// The service's response is converted into a Rocket [`Response`] with the
+/// same status, headers, and body, where the body is read into memory.
+/// Response fairings apply to the response as they do to any other. If the
+/// service fails to become ready or fails to produce a response, the error is
+/// logged and the request fails with a `500 Internal Server Error`.
+///
+/// See the [module level documentation](crate::interop) for an example.
+#[derive(Debug, Clone)]
+pub struct HttpService<S> {
+    service: S,
+    rank: isize,
+    limit: ByteUnit,
+}
+
+impl<S> HttpService<S> {
+    /// The default rank of routes to an `HttpService`: `10`.
+    pub const DEFAULT_RANK: isize = 10;
+
+    /// Creates a new handler dispatching to `service`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::convert::Infallible;
+    /// use rocket::interop::HttpService;
+    /// # use rocket::http::hyper as hyper;
+    /// use hyper::{service_fn, Body, Request, Response};
+    ///
+    /// let service = service_fn(|_: Request<Body>| async {
+    ///     Ok::<_, Infallible>(Response::new(Body::from("hi")))
+    /// });
+    ///
+    /// let handler = HttpService::new(service);
+    /// ```
+    pub fn new(service: S) -> Self {
+        HttpService { service, rank: Self::DEFAULT_RANK, limit: 1.mebibytes() }
+    }
+
+    /// Sets the rank of the routes to this service to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::convert::Infallible;
+    /// use rocket::interop::HttpService;
+    /// # use rocket::http::hyper as hyper;
+    /// use hyper::{service_fn, Body, Request, Response};
+    ///
+    /// let service = service_fn(|_: Request<Body>| async {
+    ///     Ok::<_, Infallible>(Response::new(Body::empty()))
+    /// });
+    ///
+    /// // Try other routes at the same mount point first.
+    /// let handler = HttpService::new(service).rank(20);
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Sets the maximum size of request bodies forwarded to the service to
+    /// `limit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::convert::Infallible;
+    /// use rocket::interop::HttpService;
+    /// use rocket::data::ToByteUnit;
+    /// # use rocket::http::hyper as hyper;
+    /// use hyper::{service_fn, Body, Request, Response};
+    ///
+    /// let service = service_fn(|_: Request<Body>| async {
+    ///     Ok::<_, Infallible>(Response::new(Body::empty()))
+    /// });
+    ///
+    /// let handler = HttpService::new(service).limit(8.mebibytes());
+    /// ```
+    pub fn limit(mut self, limit: ByteUnit) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// Converts `req`'s method, URI, headers, and remote address into the parts
+/// of an `http::Request`.
+fn request_parts(req: &Request<'_>) -> Result<hyper::RequestParts, String> {
+    let mut builder = hyper::Request::builder()
+        .method(req.method().as_str())
+        .uri(req.uri().to_string());
+
+    for header in req.headers().iter() {
+        builder = builder.header(header.name().as_str(), header.value_bytes());
+    }
+
+    let (mut parts, ()) = builder.body(()).map_err(|e| e.to_string())?.into_parts();
+    if let Some(remote) = req.remote() {
+        parts.extensions.insert(remote);
+    }
+
+    Ok(parts)
+}
+
+/// Converts `req` with the body `body` into an `http::Request`.
+fn to_http(req: &Request<'_>, body: Vec<u8>) -> Result<hyper::Request<hyper::Body>, String> {
+    Ok(hyper::Request::from_parts(request_parts(req)?, hyper::Body::from(body)))
+}
+
+/// Sets the status of `response` to that of `parts`, and replaces its headers
+/// with those in `parts`.
+fn apply_parts(response: &mut Response<'_>, parts: &hyper::ResponseParts) {
+    response.set_status(Status::raw(parts.status.as_u16()));
+    for name in parts.headers.keys() {
+        response.remove_header(name.as_str());
+    }
+
+    for (name, value) in parts.headers.iter() {
+        let header = Header::from_bytes(name.as_str().to_string(), value.as_bytes().to_vec());
+        response.adjoin_header(header);
+    }
+}
+
+/// Converts the `http::Response` `response` into a Rocket `Response`.
+async fn from_http<'r, B>(response: hyper::Response<B>) -> Result<Response<'r>, String>
+    where B: HttpBody, B::Error: Display
+{
+    let (parts, body) = response.into_parts();
+    let body = hyper::to_bytes(body).await.map_err(|e| e.to_string())?;
+
+    let mut response = Response::build().sized_body(body.len(), Cursor::new(body)).finalize();
+    apply_parts(&mut response, &parts);
+    Ok(response)
+}
+
+/// Request guard for the parts of the request as an `http::Request`: its
+/// method, URI, and headers, along with the remote address of the client, if
+/// known, as a `SocketAddr` extension.
+///
+/// Fails with a status of `400 Bad Request` if the request can't be
+/// represented as an `http::Request`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::hyper::RequestParts;
+///
+/// #[get("/")]
+/// fn index(parts: RequestParts) -> String {
+///     format!("{} {}", parts.method, parts.uri)
+/// }
+/// ```
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for hyper::RequestParts {
+    type Error = String;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match request_parts(req) {
+            Ok(parts) => Success(parts),
+            Err(e) => Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+/// Responds with the body's response, its status and headers replaced with
+/// those of the `http::Response`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::hyper::Response;
+///
+/// #[get("/")]
+/// fn index() -> Response<&'static str> {
+///     Response::builder()
+///         .status(202)
+///         .header("X-Queued", "true")
+///         .body("queued")
+///         .unwrap()
+/// }
+/// ```
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for hyper::Response<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let (parts, body) = self.into_parts();
+        let mut response = body.respond_to(req)?;
+        apply_parts(&mut response, &parts);
+        Ok(response)
+    }
+}
+
+#[crate::async_trait]
+impl<S, B> Handler for HttpService<S>
+    where S: Service<hyper::Request<hyper::Body>, Response = hyper::Response<B>>,
+          S: Clone + Send + Sync + 'static,
+          S::Future: Send,
+          S::Error: Display,
+          B: HttpBody + Send + 'static,
+          B::Data: Send,
+          B::Error: Display,
+{
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        // Read one byte past the limit to detect bodies exceeding it.
+        let body = match data.open((self.limit.as_u64() + 1).bytes()).stream_to_vec().await {
+            Ok(body) if body.len() as u64 > self.limit.as_u64() => {
+                return Outcome::failure(Status::PayloadTooLarge);
+            }
+            Ok(body) => body,
+            Err(e) => {
+                error_!("Failed to read request body: {}", e);
+                return Outcome::failure(Status::BadRequest);
+            }
+        };
+
+        let request = match to_http(req, body) {
+            Ok(request) => request,
+            Err(e) => {
+                error_!("Failed to convert request for service: {}", e);
+                return Outcome::failure(Status::BadRequest);
+            }
+        };
+
+        let mut service = self.service.clone();
+        if let Err(e) = poll_fn(|cx| service.poll_ready(cx)).await {
+            error_!("Service failed to become ready: {}", e);
+            return Outcome::failure(Status::InternalServerError);
+        }
+
+        let response = match service.call(request).await {
+            Ok(response) => from_http(response).await,
+            Err(e) => Err(e.to_string()),
+        };
+
+        match response {
+            Ok(response) => Outcome::Success(response),
+            Err(e) => {
+                error_!("Service failed to produce a response: {}", e);
+                Outcome::failure(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+impl<S> Into<Vec<Route>> for HttpService<S> where HttpService<S>: Handler + Clone {
+    fn into(self) -> Vec<Route> {
+        use Method::*;
+
+        let methods = [Get, Put, Post, Delete, Options, Head, Trace, Connect, Patch];
+        methods.iter()
+            .flat_map(|&method| vec![
+                Route::ranked(self.rank, method, "/", self.clone()),
+                Route::ranked(self.rank, method, "/<path..>", self.clone()),
+            ])
+            .collect()
+    }
+}
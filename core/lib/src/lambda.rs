@@ -0,0 +1,399 @@
+//! Serving a Rocket application from AWS Lambda.
+//!
+//! This module is available when the `lambda` feature is enabled. It converts
+//! the JSON events AWS Lambda delivers for HTTP requests into Rocket requests,
+//! dispatches them to the application without a network stack via
+//! [local dispatch](crate::local), and converts the responses into the JSON
+//! Lambda expects in return. The following event formats are supported:
+//!
+//!   * API Gateway REST APIs (payload format 1.0)
+//!   * API Gateway HTTP APIs (payload format 2.0)
+//!   * Application Load Balancer target groups, with or without multi-value
+//!     headers
+//!
+//! Rocket does not depend on a Lambda runtime. Instead, [`Lambda::handle()`]
+//! accepts and returns JSON values, so it can be called from any runtime's
+//! handler function. Request fairings, response fairings, and catchers run as
+//! usual, though the application is never "launched": no server is bound and
+//! launch fairings are not run.
+//!
+//! # Example
+//!
+//! With the `lambda_runtime` crate, a Lambda function serving a Rocket
+//! application might look as follows:
+//!
+//! ```rust,ignore
+//! use rocket::lambda::Lambda;
+//! use lambda_runtime::{handler_fn, Context, Error};
+//! use serde_json::Value;
+//!
+//! #[get("/")]
+//! fn hello() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let rocket = rocket::ignite().mount("/", routes![hello]);
+//!     let lambda: &'static Lambda = Box::leak(Box::new(Lambda::new(rocket).await?));
+//!     lambda_runtime::run(handler_fn(move |event: Value, _: Context| async move {
+//!         lambda.handle(event).await
+//!     })).await
+//! }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::Rocket;
+use crate::error::Error;
+use crate::local::asynchronous::Client;
+use crate::http::{Header, Method, uri::Uri};
+
+/// A Rocket application that handles AWS Lambda HTTP events.
+///
+/// See the [module level documentation](crate::lambda) for details.
+pub struct Lambda {
+    client: Client,
+}
+
+/// The error returned by [`Lambda::handle()`] when an event is not a
+/// supported HTTP event.
+#[derive(Debug)]
+pub enum LambdaError {
+    /// The event could not be parsed. Contains the error message.
+    Event(String),
+    /// The event's method is not supported by Rocket.
+    Method(String),
+    /// The event's body is marked as base64 encoded but isn't.
+    Body(base64::DecodeError),
+}
+
+impl fmt::Display for LambdaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LambdaError::Event(e) => write!(f, "invalid Lambda event: {}", e),
+            LambdaError::Method(m) => write!(f, "unsupported method: {}", m),
+            LambdaError::Body(e) => write!(f, "invalid base64 body: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LambdaError {}
+
+/// The union of the fields Rocket uses from each supported event format.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Event {
+    version: Option<String>,
+    // Payload format 1.0 and ALB.
+    http_method: Option<String>,
+    path: Option<String>,
+    query_string_parameters: Option<HashMap<String, String>>,
+    multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    // Payload format 2.0.
+    raw_path: Option<String>,
+    raw_query_string: Option<String>,
+    cookies: Option<Vec<String>>,
+    // All formats.
+    headers: Option<HashMap<String, String>>,
+    request_context: Option<RequestContext>,
+    body: Option<String>,
+    is_base64_encoded: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct RequestContext {
+    elb: Option<Value>,
+    http: Option<HttpContext>,
+    identity: Option<Identity>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct HttpContext {
+    method: Option<String>,
+    source_ip: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct Identity {
+    source_ip: Option<String>,
+}
+
+/// The format of an event, which determines the format of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// API Gateway payload format 1.0.
+    Rest,
+    /// API Gateway payload format 2.0.
+    Http,
+    /// An ALB event. `true` if multi-value headers are enabled.
+    Alb(bool),
+}
+
+impl Event {
+    fn format(&self) -> Format {
+        let context = self.request_context.as_ref();
+        if self.version.as_deref() == Some("2.0") {
+            Format::Http
+        } else if context.map_or(false, |c| c.elb.is_some()) {
+            Format::Alb(self.multi_value_headers.is_some())
+        } else {
+            Format::Rest
+        }
+    }
+
+    fn method(&self) -> Option<&str> {
+        self.http_method.as_deref().or_else(|| {
+            self.request_context.as_ref()?.http.as_ref()?.method.as_deref()
+        })
+    }
+
+    fn source_ip(&self) -> Option<IpAddr> {
+        let context = self.request_context.as_ref()?;
+        let http_ip = context.http.as_ref().and_then(|h| h.source_ip.as_ref());
+        let rest_ip = context.identity.as_ref().and_then(|i| i.source_ip.as_ref());
+        http_ip.or(rest_ip)?.parse().ok()
+    }
+
+    /// The origin-form URI of the request.
+    fn uri(&self, format: Format) -> String {
+        if format == Format::Http {
+            let path = self.raw_path.as_deref().unwrap_or("/");
+            return match self.raw_query_string.as_deref() {
+                Some(query) if !query.is_empty() => format!("{}?{}", path, query),
+                _ => path.to_string(),
+            };
+        }
+
+        // ALB passes query parameters as they were received; API Gateway
+        // passes them decoded.
+        let encode = |s: &str| match format {
+            Format::Alb(_) => s.to_string(),
+            _ => Uri::percent_encode(s).into_owned(),
+        };
+
+        let mut params: Vec<(&str, &str)> = vec![];
+        if let Some(ref multi) = self.multi_value_query_string_parameters {
+            for (key, values) in multi {
+                params.extend(values.iter().map(|v| (key.as_str(), v.as_str())));
+            }
+        } else if let Some(ref single) = self.query_string_parameters {
+            params.extend(single.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        }
+
+        let path = self.path.as_deref().unwrap_or("/");
+        if params.is_empty() {
+            return encode_path(path, format);
+        }
+
+        // Keep the order of values for a given key for duplicate handling.
+        params.sort_by_key(|(key, _)| *key);
+        let query: Vec<_> = params.into_iter()
+            .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+            .collect();
+
+        format!("{}?{}", encode_path(path, format), query.join("&"))
+    }
+
+    /// The headers of the request as name-value pairs.
+    fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![];
+        if let Some(ref multi) = self.multi_value_headers {
+            for (name, values) in multi {
+                headers.extend(values.iter().map(|v| (name.clone(), v.clone())));
+            }
+        } else if let Some(ref single) = self.headers {
+            headers.extend(single.iter().map(|(n, v)| (n.clone(), v.clone())));
+        }
+
+        if let Some(ref cookies) = self.cookies {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Cookie"));
+            if !cookies.is_empty() {
+                headers.push(("Cookie".into(), cookies.join("; ")));
+            }
+        }
+
+        headers
+    }
+}
+
+/// API Gateway REST APIs pass the path decoded; re-encode it.
+fn encode_path(path: &str, format: Format) -> String {
+    match format {
+        Format::Rest => path.split('/')
+            .map(|segment| Uri::percent_encode(segment))
+            .collect::<Vec<_>>()
+            .join("/"),
+        _ => path.to_string(),
+    }
+}
+
+impl Lambda {
+    /// Creates a `Lambda` handler for `rocket`. Returns an error if `rocket`
+    /// fails to launch, such as when routes collide.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::lambda::Lambda;
+    ///
+    /// # rocket::async_test(async {
+    /// let lambda = Lambda::new(rocket::ignite()).await.expect("valid rocket");
+    /// # });
+    /// ```
+    pub async fn new(rocket: Rocket) -> Result<Lambda, Error> {
+        Ok(Lambda { client: Client::untracked(rocket).await? })
+    }
+
+    /// Handles the Lambda event `event`, returning the JSON response to
+    /// return to Lambda. Returns an error if `event` is not a supported HTTP
+    /// event.
+    ///
+    /// Bodies that are not valid UTF-8 are base64 encoded in the response.
+    /// `Set-Cookie` headers are returned in `cookies` for API Gateway HTTP
+    /// APIs and as headers otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::lambda::Lambda;
+    /// use serde_json::json;
+    ///
+    /// #[get("/hello?<name>")]
+    /// fn hello(name: String) -> String {
+    ///     format!("Hello, {}!", name)
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::ignite().mount("/", routes![hello]);
+    /// let lambda = Lambda::new(rocket).await.unwrap();
+    /// let response = lambda.handle(json!({
+    ///     "version": "2.0",
+    ///     "rawPath": "/hello",
+    ///     "rawQueryString": "name=Lambda",
+    ///     "requestContext": { "http": { "method": "GET" } },
+    ///     "isBase64Encoded": false
+    /// })).await.unwrap();
+    ///
+    /// assert_eq!(response["statusCode"], 200);
+    /// assert_eq!(response["body"], "Hello, Lambda!");
+    /// # });
+    /// ```
+    pub async fn handle(&self, event: Value) -> Result<Value, LambdaError> {
+        let event: Event = serde_json::from_value(event)
+            .map_err(|e| LambdaError::Event(e.to_string()))?;
+
+        let format = event.format();
+        let method_str = event.method()
+            .ok_or_else(|| LambdaError::Event("missing HTTP method".into()))?;
+
+        let method = Method::from_str(method_str)
+            .map_err(|_| LambdaError::Method(method_str.into()))?;
+
+        let body = match event.body {
+            Some(ref body) if event.is_base64_encoded => {
+                base64::decode(body).map_err(LambdaError::Body)?
+            }
+            Some(ref body) => body.clone().into_bytes(),
+            None => vec![],
+        };
+
+        let mut request = self.client.req(method, event.uri(format)).body(body);
+        for (name, value) in event.headers() {
+            request.add_header(Header::new(name, value));
+        }
+
+        if let Some(ip) = event.source_ip() {
+            request = request.remote(SocketAddr::new(ip, 0));
+        }
+
+        let response = request.dispatch().await;
+        let status = response.status();
+        let mut cookies = vec![];
+        let mut headers: Vec<(String, String)> = vec![];
+        for header in response.headers().iter() {
+            let (name, value) = (header.name().to_string(), header.value().to_string());
+            let is_cookie = header.name().as_str().eq_ignore_ascii_case("Set-Cookie");
+            if format == Format::Http && is_cookie {
+                cookies.push(value);
+            } else {
+                headers.push((name, value));
+            }
+        }
+
+        let body = response.into_bytes().await.unwrap_or_default();
+        let (body, is_base64_encoded) = match String::from_utf8(body) {
+            Ok(string) => (string, false),
+            Err(e) => (base64::encode(e.as_bytes()), true),
+        };
+
+        let mut json = Map::new();
+        json.insert("statusCode".into(), json!(status.code));
+        json.insert("body".into(), json!(body));
+        json.insert("isBase64Encoded".into(), json!(is_base64_encoded));
+        match format {
+            Format::Http => {
+                json.insert("headers".into(), joined_headers(headers));
+                json.insert("cookies".into(), json!(cookies));
+            }
+            Format::Alb(multi) => {
+                json.insert("statusDescription".into(), json!(status.to_string()));
+                match multi {
+                    true => json.insert("multiValueHeaders".into(), multi_headers(headers)),
+                    false => json.insert("headers".into(), last_headers(headers)),
+                };
+            }
+            Format::Rest => {
+                json.insert("multiValueHeaders".into(), multi_headers(headers));
+            }
+        }
+
+        Ok(Value::Object(json))
+    }
+}
+
+/// Headers with one value per name, later values replacing earlier ones.
+fn last_headers(headers: Vec<(String, String)>) -> Value {
+    let map: Map<String, Value> = headers.into_iter()
+        .map(|(name, value)| (name, json!(value)))
+        .collect();
+
+    Value::Object(map)
+}
+
+/// Headers with one value per name, multiple values joined by `, `.
+fn joined_headers(headers: Vec<(String, String)>) -> Value {
+    let mut map = Map::new();
+    for (name, value) in headers {
+        match map.get_mut(&name) {
+            Some(Value::String(existing)) => *existing = format!("{}, {}", existing, value),
+            _ => { map.insert(name, json!(value)); }
+        }
+    }
+
+    Value::Object(map)
+}
+
+/// Headers with a list of values per name.
+fn multi_headers(headers: Vec<(String, String)>) -> Value {
+    let mut map: Map<String, Value> = Map::new();
+    for (name, value) in headers {
+        match map.get_mut(&name) {
+            Some(Value::Array(values)) => values.push(json!(value)),
+            _ => { map.insert(name, json!([value])); }
+        }
+    }
+
+    Value::Object(map)
+}
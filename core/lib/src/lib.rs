@@ -62,10 +62,20 @@
 //!
 //! ## Features
 //!
-//! There are two optional, disabled-by-default features:
+//! There are ten optional, disabled-by-default features:
 //!
 //!   * **secrets:** Enables support for [private cookies].
 //!   * **tls:** Enables support for [TLS].
+//!   * **tungstenite:** Enables WebSocket support via [`tungstenite`].
+//!   * **lambda:** Enables serving from AWS Lambda via [`lambda`].
+//!   * **client:** Enables a managed outbound HTTP [`client`].
+//!   * **checksums:** Enables computing and verifying upload digests via
+//!     [`Digesting`](data::Digesting) and [`TempFile`](upload::TempFile).
+//!   * **images:** Enables the `Image` upload guard in [`upload`].
+//!   * **uri-checks:** Enables checking [`uri!`] invocations against mounted
+//!     routes at launch via the `check_uris` configuration parameter.
+//!   * **anyhow:** Implements [`Responder`] for `anyhow::Error`.
+//!   * **eyre:** Implements [`Responder`] for `eyre::Report`.
 //!
 //! The features can be enabled in `Rocket.toml`:
 //!
@@ -75,6 +85,7 @@
 //! ```
 //!
 //! [private cookies]: https://rocket.rs/master/guide/requests/#private-cookies
+//! [`Responder`]: crate::response::Responder
 //! [TLS]: https://rocket.rs/master/guide/configuration/#tls
 //!
 //! ## Configuration
@@ -120,8 +131,28 @@ pub mod handler;
 pub mod fairing;
 pub mod error;
 pub mod catcher;
+pub mod cli;
+pub mod dav;
+pub mod upload;
+pub mod interop;
+pub mod scgi;
+pub mod debug;
+pub mod record;
+pub mod report;
+pub mod flags;
+pub mod multitenant;
+pub mod load;
+pub mod challenge;
+pub mod channel;
+pub mod event;
 #[cfg(feature = "tungstenite")]
 pub mod tungstenite;
+#[cfg(feature = "lambda")]
+#[cfg_attr(nightly, doc(cfg(feature = "lambda")))]
+pub mod lambda;
+#[cfg(feature = "client")]
+#[cfg_attr(nightly, doc(cfg(feature = "client")))]
+pub mod client;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -135,6 +166,7 @@ pub mod http {
 }
 
 mod shutdown;
+mod clock;
 mod router;
 mod rocket;
 mod server;
@@ -143,14 +175,18 @@ mod ext;
 
 #[doc(hidden)] pub use log::{info, warn, error, debug};
 #[doc(inline)] pub use crate::response::Response;
-#[doc(hidden)] pub use crate::codegen::{StaticRouteInfo, StaticCatcherInfo};
+#[doc(hidden)] pub use crate::codegen::{StaticRouteInfo, StaticCatcherInfo, UriTarget};
+#[cfg(feature = "uri-checks")]
+#[doc(hidden)] pub use inventory;
 #[doc(inline)] pub use crate::data::Data;
 #[doc(inline)] pub use crate::config::Config;
 #[doc(inline)] pub use crate::catcher::Catcher;
-pub use crate::router::Route;
+pub use crate::router::{Route, Extensions};
 pub use crate::request::{Request, State};
 pub use crate::rocket::Rocket;
-pub use crate::shutdown::Shutdown;
+pub use crate::shutdown::{Shutdown, ShutdownSignal};
+pub use crate::clock::Clock;
+pub use either::Either;
 
 /// Alias to [`Rocket::ignite()`] Creates a new instance of `Rocket`.
 pub fn ignite() -> Rocket {
@@ -163,6 +199,12 @@ pub fn custom<T: figment::Provider>(provider: T) -> Rocket {
     Rocket::custom(provider)
 }
 
+/// Alias to [`Rocket::execute()`]. Parses the command-line arguments and runs
+/// the selected [`cli`] command against `rocket`.
+pub async fn execute(rocket: Rocket) -> Result<(), error::Error> {
+    rocket.execute().await
+}
+
 /// WARNING: This is unstable! Do not use this method outside of Rocket!
 #[doc(hidden)]
 pub fn async_test<R>(fut: impl std::future::Future<Output = R> + Send) -> R {
@@ -178,12 +220,31 @@ pub fn async_test<R>(fut: impl std::future::Future<Output = R> + Send) -> R {
 /// WARNING: This is unstable! Do not use this method outside of Rocket!
 #[doc(hidden)]
 pub fn async_main<R>(fut: impl std::future::Future<Output = R> + Send) -> R {
-    // FIXME: The `workers` value won't reflect swaps of `Rocket` in attach
-    // fairings with different config values, or values from non-Rocket configs.
-    // See tokio-rs/tokio#3329 for a necessary solution in `tokio`.
-    tokio::runtime::Builder::new_multi_thread()
-        .worker_threads(Config::from(Config::figment()).workers)
-        .thread_name("rocket-worker-thread")
+    async_main_with(fut, true, None)
+}
+
+/// WARNING: This is unstable! Do not use this method outside of Rocket!
+#[doc(hidden)]
+pub fn async_main_with<R>(
+    fut: impl std::future::Future<Output = R> + Send,
+    multi_thread: bool,
+    worker_threads: Option<usize>,
+) -> R {
+    let mut builder = if multi_thread {
+        // FIXME: The `workers` value won't reflect swaps of `Rocket` in attach
+        // fairings with different config values, or values from non-Rocket
+        // configs. See tokio-rs/tokio#3329 for a necessary solution in `tokio`.
+        let workers = worker_threads
+            .unwrap_or_else(|| Config::from(Config::figment()).workers);
+
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.worker_threads(workers);
+        builder
+    } else {
+        tokio::runtime::Builder::new_current_thread()
+    };
+
+    builder.thread_name("rocket-worker-thread")
         .enable_all()
         .build()
         .expect("create tokio runtime")
@@ -0,0 +1,205 @@
+//! Request load tracking and load shedding.
+//!
+//! Rocket tracks the number of requests it is currently serving in a
+//! [`Load`], available via [`Rocket::load()`] or as a request guard. A request
+//! is _queued_ from the moment it is received until it is dispatched, while it
+//! waits for a worker, and _in flight_ from then until its response has been
+//! written.
+//!
+//! When a [`LoadShed`] is in managed state, its [`LoadShedPolicy`] is
+//! consulted for every incoming request before the request's body is read or
+//! any request fairings run. If the policy decides to shed the request, Rocket
+//! responds immediately via the `503 Service Unavailable` catcher. The request
+//! being considered is counted as queued.
+//!
+//! # Example
+//!
+//! Shed requests when 256 or more are in flight, or more than 64 are queued:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::Request;
+//! use rocket::load::{Load, LoadShed, MaxInFlight};
+//!
+//! #[get("/load")]
+//! fn load(load: &Load) -> String {
+//!     format!("{} in flight, {} queued", load.in_flight(), load.queued())
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .mount("/", routes![load])
+//!         .manage(LoadShed::new(|load: &Load, _: &Request<'_>| {
+//!             load.in_flight() >= 256 || load.queued() > 64
+//!         }))
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Rocket, Request};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+
+/// The current request load of an application.
+///
+/// See the [module level documentation](crate::load) for details.
+#[derive(Default)]
+pub struct Load {
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+}
+
+impl Load {
+    /// Returns the number of requests currently being dispatched or
+    /// responded to.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of requests that have been received but not yet
+    /// dispatched.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Acquire)
+    }
+
+    /// Counts a newly received request as queued until the returned ticket is
+    /// started or dropped.
+    pub(crate) fn enqueue(self: &Arc<Self>) -> Ticket {
+        self.queued.fetch_add(1, Ordering::AcqRel);
+        Ticket { load: self.clone(), started: false }
+    }
+}
+
+impl fmt::Debug for Load {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Load")
+            .field("in_flight", &self.in_flight())
+            .field("queued", &self.queued())
+            .finish()
+    }
+}
+
+/// A request's place in the [`Load`]: queued until started, then in flight
+/// until dropped.
+pub(crate) struct Ticket {
+    load: Arc<Load>,
+    started: bool,
+}
+
+impl Ticket {
+    /// Moves the request from the queue to in flight.
+    pub(crate) fn start(&mut self) {
+        if !self.started {
+            self.started = true;
+            self.load.queued.fetch_sub(1, Ordering::AcqRel);
+            self.load.in_flight.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        match self.started {
+            true => self.load.in_flight.fetch_sub(1, Ordering::AcqRel),
+            false => self.load.queued.fetch_sub(1, Ordering::AcqRel),
+        };
+    }
+}
+
+/// A policy deciding whether to shed an incoming request.
+///
+/// A policy takes effect when wrapped in a [`LoadShed`] placed in managed
+/// state. Any function or closure of type `Fn(&Load, &Request) -> bool` is a
+/// `LoadShedPolicy`.
+pub trait LoadShedPolicy: Send + Sync + 'static {
+    /// Returns `true` if `req` should be rejected with a `503` given the
+    /// current `load`.
+    fn should_shed(&self, load: &Load, req: &Request<'_>) -> bool;
+}
+
+impl<F> LoadShedPolicy for F
+    where F: Fn(&Load, &Request<'_>) -> bool + Send + Sync + 'static
+{
+    fn should_shed(&self, load: &Load, req: &Request<'_>) -> bool {
+        self(load, req)
+    }
+}
+
+/// A [`LoadShedPolicy`] that sheds requests when at least `.0` requests are
+/// in flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxInFlight(pub usize);
+
+impl LoadShedPolicy for MaxInFlight {
+    fn should_shed(&self, load: &Load, _: &Request<'_>) -> bool {
+        load.in_flight() >= self.0
+    }
+}
+
+/// A [`LoadShedPolicy`] that sheds requests when more than `.0` requests,
+/// including the one being considered, are queued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxQueued(pub usize);
+
+impl LoadShedPolicy for MaxQueued {
+    fn should_shed(&self, load: &Load, _: &Request<'_>) -> bool {
+        load.queued() > self.0
+    }
+}
+
+/// The load shedding policy of an application, in managed state.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::load::{LoadShed, MaxInFlight};
+///
+/// let rocket = rocket::ignite().manage(LoadShed::new(MaxInFlight(128)));
+/// ```
+pub struct LoadShed(Box<dyn LoadShedPolicy>);
+
+impl LoadShed {
+    /// Wraps `policy` for placement in managed state.
+    pub fn new<P: LoadShedPolicy>(policy: P) -> LoadShed {
+        LoadShed(Box::new(policy))
+    }
+}
+
+impl Rocket {
+    /// Returns the current request load of this instance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let rocket = rocket::ignite();
+    /// assert_eq!(rocket.load().in_flight(), 0);
+    /// ```
+    pub fn load(&self) -> &Load {
+        &self.load
+    }
+
+    /// Returns `true` if the managed load shedding policy, if any, decides
+    /// to shed `req`.
+    pub(crate) fn should_shed(&self, req: &Request<'_>) -> bool {
+        match self.state::<LoadShed>() {
+            Some(shed) if shed.0.should_shed(&self.load, req) => {
+                warn_!("Shedding request under load: {:?}.", self.load);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a Load {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(req.state.rocket.load())
+    }
+}
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use crate::{Request, Data};
+use crate::{Request, Data, Clock};
 use crate::http::{Status, Method, uri::Origin, ext::IntoOwned};
 
 use super::{Client, LocalResponse};
@@ -44,13 +44,19 @@ impl<'c> LocalRequest<'c> {
     ) -> LocalRequest<'c> {
         // We try to validate the URI now so that the inner `Request` contains a
         // valid URI. If it doesn't, we set a dummy one.
-        let origin = Origin::parse(&uri).unwrap_or_else(|_| Origin::dummy());
+        let origin = Request::parse_uri(client.rocket(), &uri)
+            .unwrap_or_else(|_| Origin::dummy());
         let mut request = Request::new(client.rocket(), method, origin.into_owned());
 
-        // Add any cookies we know about.
+        // Add any cookies we know about that haven't expired.
         if client.tracked {
+            let current_time = Clock::from_state(&client.rocket().managed_state).now_utc();
             client._with_raw_cookies(|jar| {
                 for cookie in jar.iter() {
+                    if cookie.expires().map_or(false, |expires| expires <= current_time) {
+                        continue;
+                    }
+
                     request.cookies_mut().add_original(cookie.clone());
                 }
             })
@@ -77,7 +83,7 @@ impl<'c> LocalRequest<'c> {
         // from an error catcher) immediately if it's invalid. If it's valid,
         // then `request` already contains the correct URI.
         let rocket = self.client.rocket();
-        if let Err(_) = Origin::parse(&self.uri) {
+        if let Err(_) = Request::parse_uri(rocket, &self.uri) {
             error!("Malformed request URI: {}", self.uri);
             return LocalResponse::new(self.request, move |req| {
                 rocket.handle_error(Status::BadRequest, req)
@@ -87,15 +93,19 @@ impl<'c> LocalRequest<'c> {
         // Actually dispatch the request.
         let mut data = Data::local(self.data);
         let token = rocket.preprocess_request(&mut self.request, &mut data).await;
+        let deferred = self.request.state.deferred.clone();
         let response = LocalResponse::new(self.request, move |req| {
             rocket.dispatch(token, req, data)
         }).await;
 
+        // The response is complete: run any work deferred until after it.
+        rocket.run_deferred(&deferred);
+
         // If the client is tracking cookies, updates the internal cookie jar
         // with the changes reflected by `response`.
         if self.client.tracked {
             self.client._with_raw_cookies_mut(|jar| {
-                let current_time = time::OffsetDateTime::now_utc();
+                let current_time = Clock::from_state(&rocket.managed_state).now_utc();
                 for cookie in response.cookies().iter() {
                     if let Some(expires) = cookie.expires() {
                         if expires <= current_time {
@@ -88,7 +88,16 @@ impl<'c> LocalResponse<'c> {
         let request: &'c Request<'c> = unsafe { &*(&*boxed_req as *const _) };
 
         async move {
-            let response: Response<'c> = f(request).await;
+            let mut response: Response<'c> = f(request).await;
+            let after_sent = response.take_after_sent();
+            if !after_sent.is_empty() {
+                request.state.deferred.push(Box::pin(async move {
+                    for cleanup in after_sent {
+                        cleanup.await;
+                    }
+                }));
+            }
+
             let mut cookies = CookieJar::new(&request.state.config.secret_key);
             for cookie in response.cookies() {
                 cookies.add_original(cookie.into_owned());
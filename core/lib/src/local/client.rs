@@ -146,6 +146,53 @@ macro_rules! pub_client_impl {
         crate::http::CookieJar::from(jar, key)
     }
 
+    /// Reads the requests recorded by a
+    /// [`Recorder`](crate::record::Recorder) to the file at `path` and returns
+    /// an equivalent local request for each, in recorded order. Each request
+    /// has the recorded method, URI, remote address, headers, and body.
+    ///
+    /// The requests are not dispatched automatically. To replay them, call
+    /// [`LocalRequest::dispatch()`] on each returned request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recording cannot be read or is invalid. See
+    /// [`Recorded::load()`](crate::record::Recorded::load()).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    #[doc = $import]
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// let requests = client.replay("requests.jsonl").expect("valid recording");
+    /// # });
+    /// ```
+    pub fn replay<P>(&self, path: P) -> std::io::Result<Vec<LocalRequest<'_>>>
+        where P: AsRef<std::path::Path>
+    {
+        let records = crate::record::Recorded::load(path)?;
+        let requests = records.into_iter().map(|record| {
+            let mut request = self.req(record.method(), record.uri().to_string());
+            for (name, value) in record.headers() {
+                request.add_header(crate::http::Header::new(name.to_string(), value.to_string()));
+            }
+
+            if let Some(remote) = record.remote() {
+                request = request.remote(remote);
+            }
+
+            if let Some(body) = record.body() {
+                request.set_body(body);
+            }
+
+            request
+        });
+
+        Ok(requests.collect())
+    }
+
     req_method!($import, "GET", get, Method::Get);
     req_method!($import, "PUT", put, Method::Put);
     req_method!($import, "POST", post, Method::Post);
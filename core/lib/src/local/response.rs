@@ -1,3 +1,35 @@
+use std::path::Path;
+
+/// Pretty-prints `string` if it's valid JSON and returns it as-is otherwise.
+pub(crate) fn pretty(string: String) -> String {
+    match serde_json::from_str::<serde_json::Value>(&string) {
+        Ok(value) => serde_json::to_string_pretty(&value).unwrap_or(string),
+        Err(_) => string,
+    }
+}
+
+/// Asserts that `body` matches the snapshot at `path`, instead writing it if
+/// `ROCKET_UPDATE_SNAPSHOTS` is set.
+pub(crate) fn check_snapshot(path: &Path, body: &str) {
+    if std::env::var_os("ROCKET_UPDATE_SNAPSHOTS").is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create snapshot directory");
+        }
+
+        std::fs::write(path, body).expect("write snapshot");
+        return;
+    }
+
+    assert!(path.exists(),
+        "snapshot `{}` doesn't exist; set `ROCKET_UPDATE_SNAPSHOTS` to create it",
+        path.display());
+
+    let snapshot = std::fs::read_to_string(path).expect("read snapshot");
+    assert!(snapshot == body,
+        "response body doesn't match snapshot `{}`; set `ROCKET_UPDATE_SNAPSHOTS` \
+        to update it\n--- snapshot\n{}\n--- body\n{}", path.display(), snapshot, body);
+}
+
 macro_rules! getter_method {
     ($doc_prelude:literal, $desc:literal, $f:ident -> $r:ty) => (
         getter_method!(@$doc_prelude, $f, $desc, $r,
@@ -94,6 +126,133 @@ macro_rules! pub_response_impl {
         self._into_bytes() $(.$suffix)?
     }
 
+    /// Consumes `self` and deserializes the entirety of its body as JSON into
+    /// a `T`. If `self` doesn't have a body, reading fails, or deserialization
+    /// fails, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// let numbers = response.into_json::<Vec<usize>>();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub $($prefix)? fn into_json<T: serde::de::DeserializeOwned>(self) -> Option<T> {
+        let string = self.into_string() $(.$suffix)?;
+        serde_json::from_str(&string?).ok()
+    }
+
+    /// Consumes `self` and reads the entirety of its body into a string,
+    /// pretty-printing it if it's valid JSON. If `self` doesn't have a body,
+    /// reading fails, or string conversion (for non-UTF-8 bodies) fails,
+    /// returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// let string = response.into_string_pretty();
+    /// # });
+    /// ```
+    pub $($prefix)? fn into_string_pretty(self) -> Option<String> {
+        let string = self.into_string() $(.$suffix)?;
+        string.map(crate::local::response::pretty)
+    }
+
+    /// Asserts that the HTTP status of `self` is `status`, returning `self`
+    /// to allow chaining assertions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the status of `self` isn't `status`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use rocket::http::Status;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response.assert_status(Status::NotFound);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn assert_status(&self, status: crate::http::Status) -> &Self {
+        assert_eq!(self.status(), status, "unexpected response status");
+        self
+    }
+
+    /// Asserts that `self` has a header named `name` with the value `value`,
+    /// returning `self` to allow chaining assertions. If there are several
+    /// headers named `name`, any may have the value `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` has no header named `name` with the value `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use rocket::http::Status;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response.assert_status(Status::NotFound)
+    ///     .assert_header("Content-Type", "text/html; charset=utf-8");
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+        let values: Vec<_> = self.headers().get(name).collect();
+        assert!(values.contains(&value),
+            "expected header `{}: {}`; found values {:?}", name, value, values);
+
+        self
+    }
+
+    /// Consumes `self` and asserts that its body, pretty-printed as by
+    /// [`into_string_pretty()`](Self::into_string_pretty()), matches the
+    /// snapshot stored in the file at `path`.
+    ///
+    /// If the `ROCKET_UPDATE_SNAPSHOTS` environment variable is set, the file
+    /// is instead written with the body, creating any missing parent
+    /// directories, and the assertion succeeds. A missing snapshot is never
+    /// created implicitly, so a test can't pass just because its snapshot
+    /// wasn't committed. Snapshots should be reviewed and committed alongside
+    /// the tests that use them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` doesn't have a UTF-8 body, if the snapshot doesn't
+    /// exist and `ROCKET_UPDATE_SNAPSHOTS` isn't set, if the snapshot can't be
+    /// read or written, or if the body doesn't match the snapshot.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response.assert_snapshot("tests/snapshots/index.txt");
+    /// # });
+    /// ```
+    pub $($prefix)? fn assert_snapshot<P: AsRef<std::path::Path>>(self, path: P) {
+        let body = self.into_string_pretty() $(.$suffix)?
+            .expect("response has a UTF-8 body");
+
+        crate::local::response::check_snapshot(path.as_ref(), &body);
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn _ensure_impls_exist() {
@@ -1,14 +1,32 @@
 //! Rocket's logging infrastructure.
 
-use std::fmt;
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use log;
 use yansi::Paint;
+use parking_lot::Mutex;
 use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
 
 #[derive(Debug)]
-struct RocketLogger(LogLevel);
+struct RocketLogger(LogLevel, LogFormat);
+
+/// The destination of log messages set via `set_log_writer()`, if any.
+static WRITER: Mutex<Option<Box<dyn Write + Send>>> = parking_lot::const_mutex(None);
+
+/// The configured destination of log messages when no writer is set.
+static DESTINATION: Mutex<Destination> = parking_lot::const_mutex(Destination::Stdout);
+
+/// An opened [`LogDestination`].
+enum Destination {
+    Stdout,
+    Stderr,
+    File(File),
+}
 
 /// Defines the maximum level of log messages to show.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -44,6 +62,194 @@ impl LogLevel {
     }
 }
 
+/// Defines the format of log messages.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LogFormat {
+    /// Human-readable, optionally colored, indented output: `"pretty"`.
+    Pretty,
+    /// One uncolored line per message, prefixed with its level, without
+    /// indentation: `"compact"`.
+    Compact,
+    /// One JSON object per message with `level`, `target`, and `message`
+    /// fields: `"json"`. Colors are always disabled.
+    Json,
+}
+
+impl LogFormat {
+    fn as_str(&self) -> &str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format = match &*s.to_ascii_lowercase() {
+            "pretty" => LogFormat::Pretty,
+            "compact" => LogFormat::Compact,
+            "json" => LogFormat::Json,
+            _ => return Err("a log format (pretty, compact, json)")
+        };
+
+        Ok(format)
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for LogFormat {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for LogFormat {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        LogFormat::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["pretty", "compact", "json"])
+        ))
+    }
+}
+
+/// Where log messages are written.
+///
+/// Configured via the `log_destination` configuration parameter: `"stdout"`,
+/// `"stderr"`, or the path to a file to append messages to. A writer set via
+/// [`set_log_writer()`] takes precedence over the configured destination.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum LogDestination {
+    /// Standard output: `"stdout"`.
+    Stdout,
+    /// Standard error: `"stderr"`.
+    Stderr,
+    /// The file at the given path, created if it doesn't exist.
+    File(PathBuf),
+}
+
+impl LogDestination {
+    fn open(&self) -> io::Result<Destination> {
+        Ok(match self {
+            LogDestination::Stdout => Destination::Stdout,
+            LogDestination::Stderr => Destination::Stderr,
+            LogDestination::File(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Destination::File(file)
+            }
+        })
+    }
+}
+
+impl Default for LogDestination {
+    fn default() -> Self {
+        LogDestination::Stdout
+    }
+}
+
+impl FromStr for LogDestination {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let destination = match s {
+            "stdout" => LogDestination::Stdout,
+            "stderr" => LogDestination::Stderr,
+            path => LogDestination::File(path.into()),
+        };
+
+        Ok(destination)
+    }
+}
+
+impl fmt::Display for LogDestination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogDestination::Stdout => write!(f, "stdout"),
+            LogDestination::Stderr => write!(f, "stderr"),
+            LogDestination::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Serialize for LogDestination {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LogDestination {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        match LogDestination::from_str(&string) {
+            Ok(destination) => Ok(destination),
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Sets the destination of Rocket's log messages to `writer`. By default,
+/// messages are written to standard output. Messages written to `writer` are
+/// never colored.
+///
+/// This can be called at any time, including before the application is
+/// created, and replaces any previously set writer.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::set_log_writer;
+///
+/// set_log_writer(std::io::stderr());
+/// ```
+pub fn set_log_writer<W: Write + Send + 'static>(writer: W) {
+    *WRITER.lock() = Some(Box::new(writer));
+    restyle();
+}
+
+/// Writes `string` to the writer set via `set_log_writer()`, if any, or the
+/// configured destination otherwise.
+fn emit(string: &str) {
+    let result = match WRITER.lock().as_mut() {
+        Some(writer) => writer.write_all(string.as_bytes()),
+        None => match &mut *DESTINATION.lock() {
+            Destination::Stdout => io::stdout().write_all(string.as_bytes()),
+            Destination::Stderr => io::stderr().write_all(string.as_bytes()),
+            Destination::File(file) => file.write_all(string.as_bytes()),
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Rocket failed to write log message: {}", e);
+    }
+}
+
+/// Writes `string` to `out` as a JSON string, quotes included.
+fn json_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(out, "\\u{:04x}", c as u32); }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
 impl FromStr for LogLevel {
     type Err = &'static str;
 
@@ -122,60 +328,93 @@ impl log::Log for RocketLogger {
             return;
         }
 
+        if self.1 == LogFormat::Json {
+            let level = record.level().to_string().to_ascii_lowercase();
+            let target = record.target().trim_end_matches('_');
+            let mut line = String::with_capacity(64);
+            line.push_str("{\"level\":");
+            json_string(&mut line, &level);
+            line.push_str(",\"target\":");
+            json_string(&mut line, target);
+            line.push_str(",\"message\":");
+            json_string(&mut line, &record.args().to_string());
+            line.push_str("}\n");
+            return emit(&line);
+        }
+
+        if self.1 == LogFormat::Compact {
+            let message = record.args().to_string();
+            let message = message.trim().replace(|c: char| c == '\r' || c == '\n', " ");
+            return emit(&format!("{:<5} {}\n", record.level(), message));
+        }
+
         // In Rocket, we abuse targets with suffix "_" to indicate indentation.
+        let mut out = String::new();
         let is_launch = record.target().starts_with("launch");
         if record.target().ends_with('_') {
             if configged_level != LogLevel::Critical || is_launch {
-                print!("    {} ", Paint::default("=>").bold());
+                let _ = write!(out, "    {} ", Paint::default("=>").bold());
             }
         }
 
-        match record.level() {
-            log::Level::Info => println!("{}", Paint::blue(record.args()).wrap()),
-            log::Level::Trace => println!("{}", Paint::magenta(record.args()).wrap()),
+        let _ = match record.level() {
+            log::Level::Info => writeln!(out, "{}", Paint::blue(record.args()).wrap()),
+            log::Level::Trace => writeln!(out, "{}", Paint::magenta(record.args()).wrap()),
             log::Level::Error => {
-                println!("{} {}",
+                writeln!(out, "{} {}",
                          Paint::red("Error:").bold(),
                          Paint::red(record.args()).wrap())
             }
             log::Level::Warn => {
-                println!("{} {}",
+                writeln!(out, "{} {}",
                          Paint::yellow("Warning:").bold(),
                          Paint::yellow(record.args()).wrap())
             }
             log::Level::Debug => {
-                print!("\n{} ", Paint::blue("-->").bold());
+                let _ = write!(out, "\n{} ", Paint::blue("-->").bold());
                 if let Some(file) = record.file() {
-                    print!("{}", Paint::blue(file));
+                    let _ = write!(out, "{}", Paint::blue(file));
                 }
 
                 if let Some(line) = record.line() {
-                    println!(":{}", Paint::blue(line));
+                    let _ = writeln!(out, ":{}", Paint::blue(line));
                 }
 
-                println!("{}", record.args());
+                writeln!(out, "{}", record.args())
             }
-        }
+        };
+
+        emit(&out);
     }
 
     fn flush(&self) {
-        // NOOP: We don't buffer any records.
+        let _ = match WRITER.lock().as_mut() {
+            Some(writer) => writer.flush(),
+            None => match &mut *DESTINATION.lock() {
+                Destination::Stdout => io::stdout().flush(),
+                Destination::Stderr => io::stderr().flush(),
+                Destination::File(file) => file.flush(),
+            }
+        };
     }
 }
 
 pub(crate) fn try_init(level: LogLevel, colors: bool, verbose: bool) -> bool {
+    try_init_with(level, LogFormat::Pretty, &LogDestination::Stdout, colors, verbose)
+}
+
+pub(crate) fn try_init_with(
+    level: LogLevel,
+    format: LogFormat,
+    destination: &LogDestination,
+    colors: bool,
+    verbose: bool
+) -> bool {
     if level == LogLevel::Off {
         return false;
     }
 
-    if !atty::is(atty::Stream::Stdout)
-        || (cfg!(windows) && !Paint::enable_windows_ascii())
-        || !colors
-    {
-        Paint::disable();
-    }
-
-    if let Err(e) = log::set_boxed_logger(Box::new(RocketLogger(level))) {
+    if let Err(e) = log::set_boxed_logger(Box::new(RocketLogger(level, format))) {
         if verbose {
             eprintln!("Logger failed to initialize: {}", e);
         }
@@ -183,10 +422,78 @@ pub(crate) fn try_init(level: LogLevel, colors: bool, verbose: bool) -> bool {
         return false;
     }
 
+    // Only the call that installs the logger configures its output, so that
+    // later calls, like those from `pretty_print_error()`, don't undo it.
+    match destination.open() {
+        Ok(destination) => *DESTINATION.lock() = destination,
+        Err(e) => eprintln!("Failed to open log destination {}: {}", destination, e),
+    }
+
+    configure_style(colors && format == LogFormat::Pretty);
     log::set_max_level(level.to_level_filter());
     true
 }
 
+/// Whether colors were enabled by the application, i.e, via `cli_colors`.
+static CLI_COLORS: AtomicBool = AtomicBool::new(true);
+
+/// Returns `true` if colored output is enabled for `stream`: if colors are
+/// enabled by the application, the `NO_COLOR` environment variable is unset or
+/// empty, and `stream` is a terminal that supports colors.
+///
+/// Log messages are only written to a terminal when no custom writer was set
+/// via [`set_log_writer()`] and the destination is standard output or error.
+pub(crate) fn colors_enabled_for(stream: atty::Stream) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty());
+    let is_log_stream = stream == log_stream();
+
+    CLI_COLORS.load(Ordering::Acquire)
+        && !no_color
+        && atty::is(stream)
+        && !(is_log_stream && (WRITER.lock().is_some() || is_file_destination()))
+        && (!cfg!(windows) || Paint::enable_windows_ascii())
+}
+
+/// The standard stream log messages are written to, if any.
+fn log_stream() -> atty::Stream {
+    match *DESTINATION.lock() {
+        Destination::Stderr => atty::Stream::Stderr,
+        _ => atty::Stream::Stdout,
+    }
+}
+
+fn is_file_destination() -> bool {
+    matches!(*DESTINATION.lock(), Destination::File(_))
+}
+
+/// Enables or disables colors and emoji in all of Rocket's log output. Colors
+/// are enabled only if `colors` is `true` and colors are enabled for the log
+/// stream as determined by [`colors_enabled_for()`].
+pub(crate) fn configure_style(colors: bool) {
+    CLI_COLORS.store(colors, Ordering::Release);
+    restyle();
+}
+
+/// Re-evaluates whether colors are enabled for the log stream.
+fn restyle() {
+    match colors_enabled_for(log_stream()) {
+        true => Paint::enable(),
+        false => Paint::disable(),
+    }
+}
+
+/// Prints `error` to standard error, styled as configured, and exits the
+/// process with a non-zero status. Used by code generated for entry points.
+#[doc(hidden)]
+pub fn exit_with_error<E: fmt::Display>(error: E) -> ! {
+    match colors_enabled_for(atty::Stream::Stderr) {
+        true => eprintln!("{} {}", Paint::red("Error:").bold(), error),
+        false => eprintln!("Error: {}", error),
+    }
+
+    std::process::exit(1)
+}
+
 pub trait PaintExt {
     fn emoji(item: &str) -> Paint<&str>;
 }
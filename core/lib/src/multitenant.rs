@@ -0,0 +1,508 @@
+//! Multi-tenancy: resolving, configuring, and isolating tenants.
+//!
+//! A tenant is a customer, organization, or workspace served by a shared
+//! application, identified by a string key. Attaching [`Tenants::fairing()`]
+//! with a [`TenantResolver`] places a [`Tenants`] registry in managed state.
+//! Handlers then receive the request's tenant via the [`Tenant`] request
+//! guard, which forwards when no tenant can be resolved.
+//!
+//! Rocket provides resolvers that read the tenant key from a subdomain
+//! ([`Subdomain`]), a header ([`FromHeader`]), or the first path segment
+//! ([`PathPrefix`]). Any `Fn(&Request<'_>) -> Option<String>` is also a
+//! resolver, and custom resolvers, such as those looking up a custom domain
+//! in a database, can implement [`TenantResolver`] directly.
+//!
+//! Tenant keys consist only of lowercase ASCII letters, digits, and `-`. A
+//! resolved key containing any other character, which may well come from an
+//! untrusted `Host` header or path, is treated as if no tenant was resolved.
+//!
+//! # Configuration
+//!
+//! Per-tenant configuration is read from the `tenants` configuration
+//! parameter, a table of tenant keys to tables of arbitrary values, and is
+//! retrieved, typed, with [`Tenant::config()`]. When the table is non-empty,
+//! it also serves as an allow-list: the `Tenant` guard fails with a `404` for
+//! tenants it doesn't contain.
+//!
+//! ```toml
+//! [default.tenants.acme]
+//! plan = "enterprise"
+//! seats = 250
+//!
+//! [default.tenants.globex]
+//! plan = "free"
+//! seats = 5
+//! ```
+//!
+//! # Isolation
+//!
+//! State that must not leak between tenants, such as caches, connection pools,
+//! or rate limiters, is kept in a [`TenantState`], which lazily creates a
+//! separate value for every tenant. Because tenant keys may come from
+//! untrusted input, a `TenantState` keeps values for a bounded number of
+//! tenants; configure an allow-list to reject unknown tenants outright. Keys
+//! in shared stores, such as session cookie names or rate-limit buckets,
+//! should be namespaced with [`Tenant::scoped()`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! use rocket::State;
+//! use rocket::multitenant::{Tenant, Tenants, TenantState, Subdomain};
+//!
+//! #[get("/")]
+//! fn index(tenant: Tenant<'_>, hits: State<'_, TenantState<AtomicUsize>>) -> String {
+//!     let hits = hits.get(&tenant).fetch_add(1, Ordering::Relaxed) + 1;
+//!     format!("{} has {} hits", tenant, hits)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite()
+//!         .attach(Tenants::fairing(Subdomain::of("example.com")))
+//!         .manage(TenantState::new(|_| AtomicUsize::new(0)))
+//!         .mount("/", routes![index])
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+
+use crate::{Rocket, Request};
+use crate::fairing::AdHoc;
+use crate::figment::value::Value;
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+use crate::http::Status;
+
+/// A strategy for resolving the tenant a request is for.
+///
+/// A resolver is registered via [`Tenants::fairing()`] and is invoked at most
+/// once per request, the first time the tenant is requested. Closures of the
+/// form `Fn(&Request<'_>) -> Option<String>` implement this trait.
+///
+/// # Example
+///
+/// A resolver that looks up the tenant of a custom domain:
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use rocket::Request;
+/// use rocket::multitenant::{Tenants, TenantResolver};
+///
+/// struct CustomDomains(HashMap<String, String>);
+///
+/// #[rocket::async_trait]
+/// impl TenantResolver for CustomDomains {
+///     async fn resolve(&self, req: &Request<'_>) -> Option<String> {
+///         let host = req.headers().get_one("Host")?;
+///         self.0.get(host).cloned()
+///     }
+/// }
+///
+/// let domains = CustomDomains(HashMap::new());
+/// let rocket = rocket::ignite().attach(Tenants::fairing(domains));
+/// ```
+#[crate::async_trait]
+pub trait TenantResolver: Send + Sync + 'static {
+    /// Returns the key of the tenant `req` is for, or `None` if there is none.
+    /// Keys that aren't made up of lowercase ASCII letters, digits, and `-`
+    /// are ignored.
+    async fn resolve(&self, req: &Request<'_>) -> Option<String>;
+}
+
+#[crate::async_trait]
+impl<F> TenantResolver for F
+    where F: Fn(&Request<'_>) -> Option<String> + Send + Sync + 'static
+{
+    async fn resolve(&self, req: &Request<'_>) -> Option<String> {
+        self(req)
+    }
+}
+
+/// Resolves the tenant from the subdomain of the request's `Host`.
+///
+/// With a base domain of `example.com`, a request to `acme.example.com`
+/// resolves to `acme`. Requests to the base domain itself, to nested
+/// subdomains such as `a.b.example.com`, or to other domains resolve to no
+/// tenant. Any port in the `Host` header is ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::multitenant::{Tenants, Subdomain};
+///
+/// let rocket = rocket::ignite().attach(Tenants::fairing(Subdomain::of("example.com")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Subdomain {
+    base: String,
+}
+
+impl Subdomain {
+    /// Returns a resolver for subdomains of `base`.
+    pub fn of<S: Into<String>>(base: S) -> Subdomain {
+        let base = base.into().trim_matches('.').to_ascii_lowercase();
+        Subdomain { base }
+    }
+}
+
+#[crate::async_trait]
+impl TenantResolver for Subdomain {
+    async fn resolve(&self, req: &Request<'_>) -> Option<String> {
+        let host = req.headers().get_one("Host")?;
+        let host = host.rsplitn(2, ':').last()?.to_ascii_lowercase();
+        let subdomain = host.strip_suffix(&*self.base)?.strip_suffix('.')?;
+        match subdomain.is_empty() || subdomain.contains('.') {
+            true => None,
+            false => Some(subdomain.to_string())
+        }
+    }
+}
+
+/// Resolves the tenant from the value of a request header.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::multitenant::{Tenants, FromHeader};
+///
+/// let rocket = rocket::ignite().attach(Tenants::fairing(FromHeader("X-Tenant")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FromHeader(pub &'static str);
+
+#[crate::async_trait]
+impl TenantResolver for FromHeader {
+    async fn resolve(&self, req: &Request<'_>) -> Option<String> {
+        let value = req.headers().get_one(self.0)?.trim();
+        match value.is_empty() {
+            true => None,
+            false => Some(value.to_string())
+        }
+    }
+}
+
+/// Resolves the tenant from the first segment of the request's path.
+///
+/// A request to `/acme/projects` resolves to `acme`. The segment is _not_
+/// removed from the path: routes must match it, for instance with a leading
+/// `<_>` or `<tenant>` dynamic segment.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::multitenant::{Tenants, PathPrefix};
+///
+/// let rocket = rocket::ignite().attach(Tenants::fairing(PathPrefix));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PathPrefix;
+
+#[crate::async_trait]
+impl TenantResolver for PathPrefix {
+    async fn resolve(&self, req: &Request<'_>) -> Option<String> {
+        req.uri().segments().next().map(|segment| segment.to_string())
+    }
+}
+
+/// The tenant registry, in managed state: the resolver and per-tenant
+/// configuration.
+///
+/// See the [module level documentation](crate::multitenant) for details.
+pub struct Tenants {
+    resolver: Box<dyn TenantResolver>,
+    config: HashMap<String, Value>,
+}
+
+impl Tenants {
+    /// Returns a fairing that places a `Tenants` registry, resolving tenants
+    /// with `resolver` and configured by the `tenants` configuration
+    /// parameter, in managed state. Launch fails if the configuration is
+    /// invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::multitenant::{Tenants, FromHeader};
+    ///
+    /// let rocket = rocket::ignite().attach(Tenants::fairing(FromHeader("X-Tenant")));
+    /// ```
+    pub fn fairing<R: TenantResolver>(resolver: R) -> AdHoc {
+        let resolver: Box<dyn TenantResolver> = Box::new(resolver);
+        AdHoc::on_attach("Tenants", |rocket: Rocket| async move {
+            let config = match rocket.figment().extract_inner::<HashMap<String, Value>>("tenants") {
+                Ok(config) => config,
+                Err(e) if e.missing() => HashMap::new(),
+                Err(e) => {
+                    crate::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            Ok(rocket.manage(Tenants { resolver, config }))
+        })
+    }
+
+    /// Returns `true` if `key` is an allowed tenant: either no tenants are
+    /// configured, or `key` is one of them.
+    pub fn is_allowed(&self, key: &str) -> bool {
+        self.config.is_empty() || self.config.contains_key(key)
+    }
+
+    /// Returns the keys of the configured tenants.
+    pub fn configured(&self) -> impl Iterator<Item = &str> + '_ {
+        self.config.keys().map(|key| key.as_str())
+    }
+}
+
+/// The per-request value cached by [`Tenant`].
+struct RequestTenant(Option<String>);
+
+/// Request guard for the tenant a request is for.
+///
+/// The tenant is resolved by the [`TenantResolver`] registered with
+/// [`Tenants::fairing()`] the first time it is requested and cached for the
+/// remainder of the request. The guard:
+///
+///   * succeeds with the tenant if one is resolved and allowed,
+///   * forwards if no tenant is resolved,
+///   * fails with a `404` if the tenant is not in a non-empty `tenants`
+///     configuration table,
+///   * fails with a `500` if [`Tenants`] is not in managed state.
+///
+/// See the [module level documentation](crate::multitenant) for an example.
+#[derive(Debug, Clone, Copy)]
+pub struct Tenant<'r> {
+    key: &'r str,
+    config: Option<&'r Value>,
+}
+
+impl<'r> Tenant<'r> {
+    /// Returns the tenant's key.
+    #[inline(always)]
+    pub fn key(&self) -> &'r str {
+        self.key
+    }
+
+    /// Deserializes the tenant's configuration from the `tenants`
+    /// configuration table into a `T`. Returns `None` if the tenant has no
+    /// configuration or if it fails to deserialize.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::multitenant::Tenant;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Plan {
+    ///     plan: String,
+    ///     seats: usize,
+    /// }
+    ///
+    /// #[get("/plan")]
+    /// fn plan(tenant: Tenant<'_>) -> Option<String> {
+    ///     let plan = tenant.config::<Plan>()?;
+    ///     Some(format!("{}: {} ({} seats)", tenant, plan.plan, plan.seats))
+    /// }
+    /// ```
+    pub fn config<T: DeserializeOwned>(&self) -> Option<T> {
+        let value = self.config?;
+        value.deserialize().map_err(|e| {
+            warn_!("Configuration for tenant `{}` is invalid: {}", self.key, e);
+        }).ok()
+    }
+
+    /// Returns `key` namespaced to this tenant as `{tenant}_{key}`, for use as
+    /// a key into stores shared by all tenants such as cookie jars, caches,
+    /// and rate limiters. Unlike `:`, `_` is valid in cookie names, and it
+    /// never appears in a tenant key, so keys scoped to different tenants
+    /// never collide.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::multitenant::Tenant;
+    /// use rocket::http::CookieJar;
+    ///
+    /// #[get("/session")]
+    /// fn session(tenant: Tenant<'_>, cookies: &CookieJar<'_>) -> Option<String> {
+    ///     let session = cookies.get(&tenant.scoped("session"))?;
+    ///     Some(session.value().to_string())
+    /// }
+    /// ```
+    pub fn scoped(&self, key: &str) -> String {
+        format!("{}_{}", self.key, key)
+    }
+}
+
+impl fmt::Display for Tenant<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Tenant<'a> {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let tenants = match req.managed_state::<Tenants>() {
+            Some(tenants) => tenants,
+            None => {
+                error_!("`Tenant` guard used without attaching `Tenants`.");
+                return Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let cached = req.local_cache_async(async {
+            let key = tenants.resolver.resolve(req).await;
+            RequestTenant(key.filter(|key| is_valid_key(key)))
+        }).await;
+
+        let key = match cached.0 {
+            Some(ref key) => key.as_str(),
+            None => return Outcome::Forward(()),
+        };
+
+        if !tenants.is_allowed(key) {
+            info_!("Tenant `{}` is not configured.", key);
+            return Outcome::Failure((Status::NotFound, ()));
+        }
+
+        Outcome::Success(Tenant { key, config: tenants.config.get(key) })
+    }
+}
+
+/// Returns `true` if `key` is non-empty and consists only of lowercase ASCII
+/// letters, digits, and `-`.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.bytes().all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-'))
+}
+
+/// Per-tenant state, in managed state.
+///
+/// A `TenantState<T>` holds a separate `T` for every tenant, created by the
+/// initializer passed to [`TenantState::new()`] the first time the tenant's
+/// value is requested. Values are never shared between tenants, making
+/// `TenantState` the natural home for per-tenant caches, connection pools, and
+/// rate limiters.
+///
+/// Values are kept for at most [`capacity`](TenantState::capacity()) tenants,
+/// [`TenantState::DEFAULT_CAPACITY`] by default. Initializing a value beyond
+/// that drops the value initialized least recently, which is reinitialized on
+/// its tenant's next use. Without an allow-list, any resolved key is a tenant,
+/// so the bound keeps clients from growing the state without limit.
+///
+/// See the [module level documentation](crate::multitenant) for an example.
+pub struct TenantState<T: Send + Sync + 'static> {
+    values: RwLock<Values<T>>,
+    capacity: usize,
+    init: Box<dyn Fn(&str) -> T + Send + Sync>,
+}
+
+/// The values in a `TenantState` and the order they were initialized in.
+struct Values<T> {
+    map: HashMap<String, Arc<T>>,
+    order: VecDeque<String>,
+}
+
+impl<T: Send + Sync + 'static> TenantState<T> {
+    /// The default maximum number of tenants values are kept for.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Returns a new `TenantState` that initializes the value for a tenant
+    /// by calling `init` with the tenant's key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::Mutex;
+    /// use rocket::multitenant::TenantState;
+    ///
+    /// let carts = TenantState::new(|_tenant| Mutex::new(Vec::<String>::new()));
+    /// let rocket = rocket::ignite().manage(carts);
+    /// ```
+    pub fn new<F>(init: F) -> TenantState<T>
+        where F: Fn(&str) -> T + Send + Sync + 'static
+    {
+        let values = Values { map: HashMap::new(), order: VecDeque::new() };
+        TenantState {
+            values: RwLock::new(values),
+            capacity: Self::DEFAULT_CAPACITY,
+            init: Box::new(init),
+        }
+    }
+
+    /// Sets the maximum number of tenants values are kept for to `capacity`,
+    /// which must be non-zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::AtomicUsize;
+    /// use rocket::multitenant::TenantState;
+    ///
+    /// let hits = TenantState::new(|_| AtomicUsize::new(0)).capacity(100);
+    /// ```
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "`TenantState` capacity must be non-zero");
+        self.capacity = capacity;
+        self
+    }
+
+    /// Returns the value for `tenant`, initializing it if this is its first
+    /// use.
+    pub fn get(&self, tenant: &Tenant<'_>) -> Arc<T> {
+        self.get_key(tenant.key())
+    }
+
+    /// Returns the value for the tenant with key `key`, initializing it if
+    /// this is its first use.
+    pub fn get_key(&self, key: &str) -> Arc<T> {
+        if let Some(value) = self.values.read().map.get(key) {
+            return value.clone();
+        }
+
+        let mut values = self.values.write();
+        if let Some(value) = values.map.get(key) {
+            return value.clone();
+        }
+
+        while values.map.len() >= self.capacity {
+            match values.order.pop_front() {
+                Some(oldest) => { values.map.remove(&oldest); }
+                None => break,
+            }
+        }
+
+        let value = Arc::new((self.init)(key));
+        values.map.insert(key.to_string(), value.clone());
+        values.order.push_back(key.to_string());
+        value
+    }
+
+    /// Removes and returns the value for the tenant with key `key`, if it has
+    /// been initialized. The next use reinitializes it.
+    pub fn remove(&self, key: &str) -> Option<Arc<T>> {
+        let mut values = self.values.write();
+        let value = values.map.remove(key)?;
+        values.order.retain(|k| k != key);
+        Some(value)
+    }
+}
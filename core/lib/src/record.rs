@@ -0,0 +1,314 @@
+//! Recording incoming requests and replaying them locally.
+//!
+//! Attaching the [`Recorder`] fairing writes every incoming request, its
+//! method, URI, remote address, headers, and body up to a limit, to a file.
+//! The file can later be read with [`Recorded::load()`] or replayed against a
+//! local instance of an application with
+//! [`Client::replay()`](crate::local::blocking::Client::replay()), making it
+//! easy to reproduce a bug observed with real traffic.
+//!
+//! Recordings contain headers, including cookies and credentials, verbatim. As
+//! such, the recorder is intended for use during development only: it is
+//! **only** enabled when the debug profile is selected. In any other profile,
+//! attaching the fairing logs a warning and records nothing.
+//!
+//! # Format
+//!
+//! A recording is a file of [JSON Lines]: each line is a JSON object
+//! describing one request, appended by a background thread as the request
+//! completes. A line may thus appear shortly after its response is sent.
+//!
+//! ```json
+//! {"method":"POST","uri":"/echo?x=1","remote":"127.0.0.1:8000","headers":[["Content-Type","text/plain"]],"body":"Hello!","truncated":false}
+//! ```
+//!
+//! A body that is valid UTF-8 is written as a string; any other body is
+//! written as an array of bytes. The body is recorded as it is read by the
+//! application, so only the portion of the body that was read is recorded.
+//! A `truncated` body exceeded the recorder's limit.
+//!
+//! [JSON Lines]: https://jsonlines.org/
+//!
+//! # Example
+//!
+//! Record requests to `requests.jsonl`, keeping at most 1MiB of each body:
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! use rocket::record::Recorder;
+//! use rocket::data::ToByteUnit;
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite().attach(Recorder::fairing("requests.jsonl").limit(1.mebibytes()))
+//! }
+//! ```
+//!
+//! Then, replay them in a test:
+//!
+//! ```rust,no_run
+//! use rocket::local::blocking::Client;
+//!
+//! let client = Client::tracked(rocket::ignite()).unwrap();
+//! for request in client.replay("requests.jsonl").unwrap() {
+//!     let response = request.dispatch();
+//!     println!("{}", response.status());
+//! }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+use futures::channel::mpsc;
+use futures::executor::block_on_stream;
+use parking_lot::Mutex;
+use serde::{Serialize, Deserialize};
+
+use crate::{Rocket, Request, Data, Config};
+use crate::data::{ByteUnit, ToByteUnit};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::Method;
+
+/// A request read from a recording.
+///
+/// See the [module level documentation](crate::record) for details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recorded {
+    method: String,
+    uri: String,
+    remote: Option<SocketAddr>,
+    headers: Vec<(String, String)>,
+    body: Option<Body>,
+    truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Recorded {
+    /// Reads all of the requests in the recording at `path`, in the order
+    /// they were recorded. Returns an error if the file cannot be read or
+    /// contains an invalid record.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket::record::Recorded;
+    ///
+    /// for request in Recorded::load("requests.jsonl").unwrap() {
+    ///     println!("{} {}", request.method(), request.uri());
+    /// }
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<Recorded>> {
+        let invalid = |line: usize, e: &dyn std::fmt::Display| io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid record on line {}: {}", line, e)
+        );
+
+        let mut records = vec![];
+        let reader = BufReader::new(File::open(path)?);
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: Recorded = serde_json::from_str(&line).map_err(|e| invalid(i + 1, &e))?;
+            if record.method.parse::<Method>().is_err() {
+                return Err(invalid(i + 1, &format!("unknown method `{}`", record.method)));
+            }
+
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    /// The method of the request.
+    pub fn method(&self) -> Method {
+        self.method.parse().expect("method validated on load")
+    }
+
+    /// The URI of the request, in origin form.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The remote address of the request, if it was known.
+    pub fn remote(&self) -> Option<SocketAddr> {
+        self.remote
+    }
+
+    /// The headers of the request as name-value pairs, in the order they were
+    /// received.
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// The recorded body of the request, if any of it was read.
+    pub fn body(&self) -> Option<&[u8]> {
+        match self.body.as_ref()? {
+            Body::Text(string) => Some(string.as_bytes()),
+            Body::Bytes(bytes) => Some(bytes),
+        }
+    }
+
+    /// Returns `true` if the body exceeded the recorder's limit and was
+    /// truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// A fairing that records incoming requests to a file in the debug profile.
+///
+/// See the [module level documentation](crate::record) for details.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    path: PathBuf,
+    limit: ByteUnit,
+    writer: Arc<Mutex<Option<mpsc::UnboundedSender<Vec<u8>>>>>,
+}
+
+impl Recorder {
+    /// Returns a fairing that appends requests to the file at `path`,
+    /// creating it if necessary, and records at most 64KiB of each body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::record::Recorder;
+    ///
+    /// let rocket = rocket::ignite().attach(Recorder::fairing("requests.jsonl"));
+    /// ```
+    pub fn fairing<P: AsRef<Path>>(path: P) -> Recorder {
+        Recorder {
+            path: path.as_ref().to_path_buf(),
+            limit: 64.kibibytes(),
+            writer: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sets the maximum number of bytes of each body that are recorded to
+    /// `limit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::record::Recorder;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// let recorder = Recorder::fairing("requests.jsonl").limit(1.mebibytes());
+    /// ```
+    pub fn limit(mut self, limit: ByteUnit) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// Spawns a thread that appends every line sent to the returned channel to
+/// `file`, keeping blocking file I/O off of the async runtime.
+fn spawn_writer(mut file: File) -> mpsc::UnboundedSender<Vec<u8>> {
+    let (tx, rx) = mpsc::unbounded::<Vec<u8>>();
+    thread::spawn(move || {
+        for line in block_on_stream(rx) {
+            if let Err(e) = file.write_all(&line) {
+                error_!("Failed to write recorded request: {}", e);
+            }
+        }
+    });
+
+    tx
+}
+
+fn write(writer: &Mutex<Option<mpsc::UnboundedSender<Vec<u8>>>>, record: &Recorded) {
+    let mut line = match serde_json::to_vec(record) {
+        Ok(line) => line,
+        Err(e) => {
+            error_!("Failed to serialize recorded request: {}", e);
+            return;
+        }
+    };
+
+    line.push(b'\n');
+    if let Some(writer) = writer.lock().as_ref() {
+        if writer.unbounded_send(line).is_err() {
+            error_!("Failed to write recorded request: the writer has stopped.");
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Recorder {
+    fn info(&self) -> Info {
+        Info { name: "Request Recorder", kind: Kind::Attach | Kind::Request }
+    }
+
+    async fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        if rocket.config().profile != Config::DEBUG_PROFILE {
+            warn!("Request recording is only available in the debug profile.");
+            info_!("Not recording requests in profile `{}`.", rocket.config().profile);
+            return Ok(rocket);
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&self.path);
+        match file {
+            Ok(file) => {
+                warn!("Recording requests to `{}`.", self.path.display());
+                info_!("Recordings contain request headers and bodies verbatim.");
+                *self.writer.lock() = Some(spawn_writer(file));
+                Ok(rocket)
+            }
+            Err(e) => {
+                error!("Failed to open request recording `{}`.", self.path.display());
+                info_!("Error: {}", e);
+                Err(rocket)
+            }
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+        if self.writer.lock().is_none() {
+            return;
+        }
+
+        let mut record = Recorded {
+            method: req.method().as_str().to_string(),
+            uri: req.uri().to_string(),
+            remote: req.remote(),
+            headers: req.headers().iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect(),
+            body: None,
+            truncated: false,
+        };
+
+        let writer = self.writer.clone();
+        match data.capture(self.limit) {
+            Some(capture) => capture.on_finish(move |capture| {
+                let bytes = capture.bytes();
+                if capture.total() > 0 {
+                    record.body = Some(match String::from_utf8(bytes) {
+                        Ok(string) => Body::Text(string),
+                        Err(e) => Body::Bytes(e.into_bytes()),
+                    });
+                }
+
+                record.truncated = capture.is_truncated();
+                write(&writer, &record);
+            }),
+            None => {
+                warn_!("Request body is already observed; recording without body.");
+                write(&writer, &record);
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Structured launch reports and customizable launch output.
+//!
+//! When an application launches, Rocket collects a description of it, its
+//! configuration, routes, catchers, and fairings, and the address it is
+//! serving on, into a [`LaunchReport`]. The report is handed to the
+//! application's [`Reporter`], which is responsible for emitting it. The
+//! default reporter, [`DefaultReporter`], logs the familiar launch output.
+//!
+//! A custom reporter can be registered via [`Rocket::reporter()`] to silence,
+//! restructure, or redirect the launch output. Any function or closure of type
+//! `Fn(&LaunchReport)` is a `Reporter`.
+//!
+//! # Example
+//!
+//! Log a one-line summary of the launched application instead of the default
+//! output:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::report::LaunchReport;
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     rocket::ignite().reporter(|report: &LaunchReport| {
+//!         println!("serving {} routes from {}", report.routes.len(), report.endpoint);
+//!     })
+//! }
+//! ```
+//!
+//! Silence the launch output entirely:
+//!
+//! ```rust
+//! use rocket::report::LaunchReport;
+//!
+//! let rocket = rocket::ignite().reporter(|_: &LaunchReport| ());
+//! ```
+
+use std::fmt;
+
+use yansi::Paint;
+
+use crate::{Rocket, Route, Config};
+use crate::fairing::{Info, Kind};
+use crate::http::{Method, MediaType};
+use crate::logger::PaintExt;
+
+/// A structured description of an application at launch.
+///
+/// See the [module level documentation](crate::report) for details.
+#[derive(Debug, Clone)]
+pub struct LaunchReport {
+    /// The application's configuration.
+    pub config: Config,
+    /// The endpoint the application is serving on, such as
+    /// `http://127.0.0.1:8000`.
+    pub endpoint: String,
+    /// The mounted routes, sorted by mount point, URI, method, and rank.
+    pub routes: Vec<RouteReport>,
+    /// The status codes of the registered catchers, in ascending order, with
+    /// `None` for the default catcher.
+    pub catchers: Vec<Option<u16>>,
+    /// The active launch, request, and response fairings, in the order they
+    /// were attached.
+    pub fairings: Vec<Info>,
+}
+
+/// A structured description of a mounted route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteReport {
+    /// The name of the route, if it has one.
+    pub name: Option<&'static str>,
+    /// The method the route matches against.
+    pub method: Method,
+    /// The mount point of the route.
+    pub base: String,
+    /// The complete URI the route matches against, including the mount point.
+    pub uri: String,
+    /// The rank of the route.
+    pub rank: isize,
+    /// The media type the route matches against, if any.
+    pub format: Option<MediaType>,
+}
+
+impl From<&Route> for RouteReport {
+    fn from(route: &Route) -> RouteReport {
+        RouteReport {
+            name: route.name,
+            method: route.method,
+            base: route.base.to_string(),
+            uri: route.uri.to_string(),
+            rank: route.rank,
+            format: route.format.clone(),
+        }
+    }
+}
+
+impl fmt::Display for RouteReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", Paint::green(&self.method))?;
+        match self.uri.strip_prefix(&self.base) {
+            Some(path) if self.base != "/" => {
+                let path = if path.is_empty() { "/" } else { path };
+                write!(f, "{}{}", Paint::blue(&self.base).underline(), Paint::blue(path))?;
+            }
+            _ => write!(f, "{}", Paint::blue(&self.uri))?,
+        }
+
+        if self.rank > 1 {
+            write!(f, " [{}]", Paint::default(&self.rank).bold())?;
+        }
+
+        if let Some(ref format) = self.format {
+            write!(f, " {}", Paint::yellow(format))?;
+        }
+
+        if let Some(name) = self.name {
+            write!(f, " {}{}{}", Paint::cyan("("), Paint::magenta(name), Paint::cyan(")"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A consumer of launch reports.
+///
+/// A `Reporter` is invoked exactly once, when the application launches, after
+/// launch fairings have run. It is registered via [`Rocket::reporter()`]; if
+/// none is registered, [`DefaultReporter`] is used. Any function or closure of
+/// type `Fn(&LaunchReport)` is a `Reporter`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::report::{LaunchReport, Reporter};
+///
+/// struct Summary;
+///
+/// impl Reporter for Summary {
+///     fn report(&self, report: &LaunchReport) {
+///         for route in &report.routes {
+///             println!("{} {} (rank {})", route.method, route.uri, route.rank);
+///         }
+///     }
+/// }
+///
+/// let rocket = rocket::ignite().reporter(Summary);
+/// ```
+pub trait Reporter: Send + Sync + 'static {
+    /// Emits `report`.
+    fn report(&self, report: &LaunchReport);
+}
+
+impl<F: Fn(&LaunchReport) + Send + Sync + 'static> Reporter for F {
+    fn report(&self, report: &LaunchReport) {
+        self(report)
+    }
+}
+
+/// The default [`Reporter`]: logs the configuration, routes, catchers,
+/// fairings, and launch endpoint of the application.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultReporter;
+
+impl DefaultReporter {
+    fn fairings_for(kind: Kind, name: &str, fairings: &[Info]) {
+        let names: Vec<_> = fairings.iter()
+            .filter(|info| info.kind.is(kind))
+            .map(|info| info.name)
+            .collect();
+
+        if !names.is_empty() {
+            info_!("{} {}: {}",
+                Paint::default(names.len()).bold(),
+                name,
+                Paint::default(names.join(", ")).bold());
+        }
+    }
+}
+
+impl Reporter for DefaultReporter {
+    fn report(&self, report: &LaunchReport) {
+        report.config.pretty_print();
+
+        let mut bases: Vec<&str> = vec![];
+        for route in &report.routes {
+            if !bases.contains(&route.base.as_str()) {
+                bases.push(&route.base);
+            }
+        }
+
+        for base in bases {
+            info!("{}{} {}{}",
+                  Paint::emoji("🛰  "),
+                  Paint::magenta("Mounting"),
+                  Paint::blue(base),
+                  Paint::magenta(":"));
+
+            for route in report.routes.iter().filter(|r| r.base == base) {
+                info_!("{}", route);
+            }
+        }
+
+        if !report.catchers.is_empty() {
+            info!("{}{}", Paint::emoji("👾 "), Paint::magenta("Catchers:"));
+            for code in &report.catchers {
+                match code {
+                    Some(code) => info_!("{}", Paint::blue(code)),
+                    None => info_!("{}", Paint::blue("default")),
+                }
+            }
+        }
+
+        if !report.fairings.is_empty() {
+            info!("{}{}:", Paint::emoji("📡 "), Paint::magenta("Fairings"));
+            Self::fairings_for(Kind::Launch, "launch", &report.fairings);
+            Self::fairings_for(Kind::Request, "request", &report.fairings);
+            Self::fairings_for(Kind::Response, "response", &report.fairings);
+            Self::fairings_for(Kind::Complete, "complete", &report.fairings);
+        }
+
+        launch_info!("{}{} {}",
+                     Paint::emoji("🚀 "),
+                     Paint::default("Rocket has launched from").bold(),
+                     Paint::default(&report.endpoint).bold().underline());
+    }
+}
+
+impl Rocket {
+    /// Returns a report describing `self` as launched on `endpoint`.
+    pub(crate) fn launch_report(&self, endpoint: String) -> LaunchReport {
+        let mut catchers: Vec<_> = self.catchers.keys().map(|&code| Some(code)).collect();
+        catchers.sort();
+        if self.default_catcher.is_some() {
+            catchers.push(None);
+        }
+
+        let mut routes: Vec<_> = self.routes().map(RouteReport::from).collect();
+        routes.sort_by(|a, b| (&a.base, &a.uri, a.method.as_str(), a.rank)
+            .cmp(&(&b.base, &b.uri, b.method.as_str(), b.rank)));
+
+        LaunchReport {
+            config: self.config.clone(),
+            endpoint,
+            routes,
+            catchers,
+            fairings: self.fairings.info().collect(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// Request guard that runs the guard `T` at most once per request.
+///
+/// The first time a `CachedGuard<T>` is requested during the handling of a
+/// request, the guard `T` is executed and its outcome, be it a success, a
+/// failure, or a forward, is stored in the [request-local cache]. Every later
+/// request for `CachedGuard<T>`, whether by another route the request is
+/// forwarded to or by another guard via [`Request::guard()`], replays the
+/// stored outcome without executing `T` again. This makes `CachedGuard`
+/// suitable for expensive guards, such as those that look up a user in a
+/// database, that are used by several routes or guards.
+///
+/// On success, a `CachedGuard<T>` dereferences to the `T` stored in the
+/// cache. On failure, the guard fails with the same status as `T` and a
+/// reference to `T`'s cached error.
+///
+/// Only requests for `CachedGuard<T>` are cached: requesting `T` directly
+/// always executes `T`'s guard.
+///
+/// [request-local cache]: crate::Request::local_cache()
+/// [`Request::guard()`]: crate::Request::guard()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest, CachedGuard};
+///
+/// struct User { is_admin: bool }
+///
+/// #[rocket::async_trait]
+/// impl<'a, 'r> FromRequest<'a, 'r> for User {
+///     type Error = ();
+///
+///     async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+///         // An expensive lookup, executed at most once per request.
+///         # request::Outcome::Success(User { is_admin: false })
+///     }
+/// }
+///
+/// struct Admin;
+///
+/// #[rocket::async_trait]
+/// impl<'a, 'r> FromRequest<'a, 'r> for Admin {
+///     type Error = ();
+///
+///     async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+///         // Executes the `User` guard only if it hasn't been executed yet.
+///         let user = req.guard::<CachedGuard<User>>().await;
+///         let user = try_outcome!(user.map_failure(|(s, _)| (s, ())));
+///         if user.is_admin {
+///             request::Outcome::Success(Admin)
+///         } else {
+///             request::Outcome::Forward(())
+///         }
+///     }
+/// }
+///
+/// #[get("/dashboard")]
+/// fn admin_dashboard(admin: Admin) { /* .. */ }
+///
+/// // If `admin_dashboard` forwards, the cached `User` outcome is reused.
+/// #[get("/dashboard", rank = 2)]
+/// fn user_dashboard(user: CachedGuard<User>) { /* .. */ }
+/// # fn main() {}
+/// ```
+pub struct CachedGuard<'a, T>(&'a T);
+
+/// The outcome of the guard `T`, keyed distinctly from any `T` in the cache.
+struct CachedOutcome<T, E>(request::Outcome<T, E>);
+
+impl<'a, T> CachedGuard<'a, T> {
+    /// Returns a reference to the cached value with the lifetime of the
+    /// request borrow.
+    #[inline(always)]
+    pub fn into_inner(self) -> &'a T {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r, T> FromRequest<'a, 'r> for CachedGuard<'a, T>
+    where T: FromRequest<'a, 'r> + Send + Sync + 'static,
+          T::Error: Send + Sync + 'static
+{
+    type Error = &'a T::Error;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let cached = request.local_cache_async(async {
+            CachedOutcome(T::from_request(request).await)
+        }).await;
+
+        match cached.0 {
+            Outcome::Success(ref value) => Outcome::Success(CachedGuard(value)),
+            Outcome::Failure((status, ref error)) => Outcome::Failure((status, error)),
+            Outcome::Forward(()) => Outcome::Forward(()),
+        }
+    }
+}
+
+impl<T> Deref for CachedGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<T> Clone for CachedGuard<'_, T> {
+    fn clone(&self) -> Self {
+        CachedGuard(self.0)
+    }
+}
+
+impl<T> Copy for CachedGuard<'_, T> { }
+
+impl<T: fmt::Debug> fmt::Debug for CachedGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CachedGuard").field(self.0).finish()
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+use crate::http::{ETag, Method, Status};
+
+/// Request guard for the `If-Match` header.
+///
+/// Forwards if the request has no `If-Match` header. Fails with a status of
+/// `400 Bad Request` and a [`PreconditionError`] if the header is malformed.
+/// Most applications should use [`Preconditions`] instead, which evaluates
+/// `If-Match` and `If-None-Match` together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    /// `If-Match: *`: the resource must exist.
+    Any,
+    /// The resource's current entity tag must strongly match one of these.
+    Tags(Vec<ETag>),
+}
+
+/// Request guard for the `If-None-Match` header.
+///
+/// Forwards if the request has no `If-None-Match` header. Fails with a status
+/// of `400 Bad Request` and a [`PreconditionError`] if the header is
+/// malformed. Most applications should use [`Preconditions`] instead, which
+/// evaluates `If-Match` and `If-None-Match` together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// `If-None-Match: *`: the resource must not exist.
+    Any,
+    /// The resource's current entity tag must not weakly match any of these.
+    Tags(Vec<ETag>),
+}
+
+/// Request guard that evaluates conditional request headers against the
+/// current state of a resource.
+///
+/// `Preconditions` captures the request's `If-Match` and `If-None-Match`
+/// headers, either of which may be absent. [`Preconditions::check()`] compares
+/// them against the resource's current [`ETag`] following the rules of [RFC
+/// 7232], returning the status the request should be short-circuited with, if
+/// any:
+///
+///   * `412 Precondition Failed` when `If-Match` doesn't match, or when
+///     `If-None-Match` matches a request that isn't a `GET` or `HEAD`
+///   * `304 Not Modified` when `If-None-Match` matches a `GET` or `HEAD`
+///
+/// Handlers call `check()` after loading the resource but before acting on
+/// the request. This standardizes optimistic concurrency control: clients
+/// send `If-Match` with the tag they last saw when updating a resource, and
+/// the update is rejected if the resource has changed in the meantime.
+///
+/// The guard fails with a status of `400 Bad Request` and a
+/// [`PreconditionError`] if either header is malformed. It never forwards.
+///
+/// [RFC 7232]: https://tools.ietf.org/html/rfc7232#section-6
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{ETag, Status};
+/// use rocket::request::Preconditions;
+///
+/// struct Document { body: String, version: u64 }
+///
+/// # fn load() -> Document { Document { body: String::new(), version: 1 } }
+/// # fn store(_: &Document) { }
+/// #[put("/doc", data = "<body>")]
+/// fn update(pre: Preconditions, body: String) -> Result<(), Status> {
+///     let mut doc = load();
+///     pre.check(Some(&ETag::strong(doc.version.to_string())))?;
+///     doc.body = body;
+///     doc.version += 1;
+///     store(&doc);
+///     Ok(())
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preconditions {
+    method: Method,
+    if_match: Option<IfMatch>,
+    if_none_match: Option<IfNoneMatch>,
+}
+
+/// The error returned by the [`IfMatch`], [`IfNoneMatch`], and
+/// [`Preconditions`] request guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreconditionError {
+    /// The named header's value is not `*` or a list of entity tags.
+    Malformed(&'static str, String),
+}
+
+impl IfMatch {
+    /// Returns `true` if the precondition holds for a resource whose current
+    /// entity tag is `current`, or which doesn't exist if `current` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    /// use rocket::request::IfMatch;
+    ///
+    /// let if_match = IfMatch::Tags(vec![ETag::strong("a")]);
+    /// assert!(if_match.matches(Some(&ETag::strong("a"))));
+    /// assert!(!if_match.matches(Some(&ETag::weak("a"))));
+    /// assert!(!IfMatch::Any.matches(None));
+    /// ```
+    pub fn matches(&self, current: Option<&ETag>) -> bool {
+        match (self, current) {
+            (_, None) => false,
+            (IfMatch::Any, Some(_)) => true,
+            (IfMatch::Tags(tags), Some(current)) => tags.iter().any(|t| t.strong_eq(current)),
+        }
+    }
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if the precondition holds for a resource whose current
+    /// entity tag is `current`, or which doesn't exist if `current` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ETag;
+    /// use rocket::request::IfNoneMatch;
+    ///
+    /// let if_none_match = IfNoneMatch::Tags(vec![ETag::weak("a")]);
+    /// assert!(!if_none_match.matches(Some(&ETag::strong("a"))));
+    /// assert!(if_none_match.matches(Some(&ETag::strong("b"))));
+    /// assert!(IfNoneMatch::Any.matches(None));
+    /// ```
+    pub fn matches(&self, current: Option<&ETag>) -> bool {
+        match (self, current) {
+            (_, None) => true,
+            (IfNoneMatch::Any, Some(_)) => false,
+            (IfNoneMatch::Tags(tags), Some(current)) => !tags.iter().any(|t| t.weak_eq(current)),
+        }
+    }
+}
+
+impl Preconditions {
+    /// Returns the `If-Match` precondition, if there is one.
+    pub fn if_match(&self) -> Option<&IfMatch> {
+        self.if_match.as_ref()
+    }
+
+    /// Returns the `If-None-Match` precondition, if there is one.
+    pub fn if_none_match(&self) -> Option<&IfNoneMatch> {
+        self.if_none_match.as_ref()
+    }
+
+    /// Returns `true` if the request has neither an `If-Match` nor an
+    /// `If-None-Match` header.
+    ///
+    /// APIs that require optimistic concurrency control can use this to
+    /// reject unconditional updates, typically with `428 Precondition
+    /// Required`.
+    pub fn is_empty(&self) -> bool {
+        self.if_match.is_none() && self.if_none_match.is_none()
+    }
+
+    /// Evaluates the preconditions against a resource whose current entity
+    /// tag is `current`, or which doesn't exist if `current` is `None`.
+    ///
+    /// Returns `Ok(())` if the request should proceed. Otherwise returns
+    /// `Err` with `412 Precondition Failed` or, for a `GET` or `HEAD` request
+    /// whose `If-None-Match` matched, `304 Not Modified`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{ETag, Header, Method, Status};
+    /// use rocket::request::Preconditions;
+    ///
+    /// # Request::example(Method::Get, "/doc", |req| {
+    /// # req.add_header(Header::new("If-None-Match", "W/\"1\""));
+    /// # let pre = rocket::async_test(req.guard::<Preconditions>()).unwrap();
+    /// // GET /doc with `If-None-Match: W/"1"`
+    /// assert_eq!(pre.check(Some(&ETag::strong("1"))), Err(Status::NotModified));
+    /// assert_eq!(pre.check(Some(&ETag::strong("2"))), Ok(()));
+    /// # });
+    /// ```
+    pub fn check(&self, current: Option<&ETag>) -> Result<(), Status> {
+        if let Some(if_match) = &self.if_match {
+            if !if_match.matches(current) {
+                return Err(Status::PreconditionFailed);
+            }
+        }
+
+        if let Some(if_none_match) = &self.if_none_match {
+            if !if_none_match.matches(current) {
+                return match self.method {
+                    Method::Get | Method::Head => Err(Status::NotModified),
+                    _ => Err(Status::PreconditionFailed),
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the header `name` into `*` (`Ok(Some(None))`) or a list of tags.
+fn parse_header(
+    request: &Request<'_>,
+    name: &'static str
+) -> Result<Option<Option<Vec<ETag>>>, PreconditionError> {
+    let values: Vec<_> = request.headers().get(name).collect();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let value = values.join(", ");
+    if value.trim() == "*" {
+        return Ok(Some(None));
+    }
+
+    match ETag::parse_list(&value) {
+        Some(tags) if !tags.is_empty() => Ok(Some(Some(tags))),
+        _ => Err(PreconditionError::Malformed(name, value)),
+    }
+}
+
+fn if_match(request: &Request<'_>) -> Result<Option<IfMatch>, PreconditionError> {
+    Ok(parse_header(request, "If-Match")?.map(|tags| match tags {
+        Some(tags) => IfMatch::Tags(tags),
+        None => IfMatch::Any,
+    }))
+}
+
+fn if_none_match(request: &Request<'_>) -> Result<Option<IfNoneMatch>, PreconditionError> {
+    Ok(parse_header(request, "If-None-Match")?.map(|tags| match tags {
+        Some(tags) => IfNoneMatch::Tags(tags),
+        None => IfNoneMatch::Any,
+    }))
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for IfMatch {
+    type Error = PreconditionError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match if_match(request) {
+            Ok(Some(if_match)) => Outcome::Success(if_match),
+            Ok(None) => Outcome::Forward(()),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for IfNoneMatch {
+    type Error = PreconditionError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match if_none_match(request) {
+            Ok(Some(if_none_match)) => Outcome::Success(if_none_match),
+            Ok(None) => Outcome::Forward(()),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Preconditions {
+    type Error = PreconditionError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let preconditions = if_match(request).and_then(|if_match| {
+            let if_none_match = if_none_match(request)?;
+            Ok(Preconditions { method: request.method(), if_match, if_none_match })
+        });
+
+        match preconditions {
+            Ok(preconditions) => Outcome::Success(preconditions),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+impl fmt::Display for PreconditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreconditionError::Malformed(name, value) => {
+                write!(f, "malformed `{}` header: {:?}", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PreconditionError {}
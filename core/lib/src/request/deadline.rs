@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+use crate::Clock;
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// Request guard for the time remaining to handle a request.
+///
+/// When [`Config::request_timeout`](crate::Config::request_timeout) is
+/// non-zero, every request is given a deadline of `request_timeout` seconds
+/// after it is dispatched, once request fairings have run. A handler that has
+/// not produced a response by the deadline is cancelled and a `503 Service
+/// Unavailable` error is returned instead. `Deadline` exposes the remaining
+/// budget so that handlers can scale their work, for instance by passing a
+/// timeout to an upstream service.
+///
+/// When `request_timeout` is `0`, the default, requests have no deadline. As
+/// a request guard, `Deadline` never fails.
+///
+/// Time is measured by the application's [`Clock`]. When it's a fake clock,
+/// the remaining time is that remaining when the guard was retrieved less the
+/// time the fake clock has since been advanced by; the handler is nevertheless
+/// cancelled at its deadline in real time.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::request::Deadline;
+///
+/// #[get("/")]
+/// fn index(deadline: Deadline) -> String {
+///     match deadline.remaining() {
+///         Some(left) if left < Duration::from_millis(100) => "hurry!".into(),
+///         Some(left) => format!("{}ms to spare", left.as_millis()),
+///         None => "no deadline".into(),
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deadline(Option<Instant>, Clock);
+
+impl Deadline {
+    /// Returns the instant, as measured by the application's [`Clock`], at
+    /// which the request's handler is cancelled, if there is a deadline.
+    #[inline(always)]
+    pub fn instant(&self) -> Option<Instant> {
+        self.0
+    }
+
+    /// Returns the time remaining until the deadline, if there is one. Returns
+    /// `Some(Duration::from_secs(0))` if the deadline has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0.map(|deadline| deadline.saturating_duration_since(self.1.now()))
+    }
+
+    /// Returns `true` if there is a deadline and it has passed.
+    pub fn is_expired(&self) -> bool {
+        self.0.map_or(false, |deadline| self.1.now() >= deadline)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Deadline {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        // The deadline is a real instant; translate it to the clock's timeline.
+        let clock = request.clock().clone();
+        let deadline = request.state.deadline.try_get()
+            .map(|d| clock.now() + d.saturating_duration_since(Instant::now()));
+
+        Outcome::Success(Deadline(deadline, clock))
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use crate::{Rocket, Request};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome;
+
+/// The work deferred by a request via [`Request::defer()`].
+///
+/// [`Request::defer()`]: crate::Request::defer()
+#[derive(Default)]
+pub(crate) struct Deferred(Mutex<Vec<BoxFuture<'static, ()>>>);
+
+impl Deferred {
+    pub(crate) fn push(&self, work: BoxFuture<'static, ()>) {
+        self.0.lock().push(work);
+    }
+}
+
+/// Request guard for deferring work until after the response is sent.
+///
+/// See [`Request::defer()`] for details. As a request guard, `Defer` never
+/// fails.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Defer;
+///
+/// async fn notify_subscribers(post: usize) {
+///     /* deliver webhooks */
+/// }
+///
+/// #[post("/publish/<post>")]
+/// fn publish(post: usize, defer: Defer) -> &'static str {
+///     defer.after_response(notify_subscribers(post));
+///     "Published."
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Defer(Arc<Deferred>);
+
+impl Defer {
+    /// Runs `work` after the response to the request has been sent.
+    /// Equivalent to [`Request::defer()`].
+    pub fn after_response<F>(&self, work: F)
+        where F: std::future::Future<Output = ()> + Send + 'static
+    {
+        self.0.push(Box::pin(work));
+    }
+}
+
+impl std::fmt::Debug for Defer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Defer").field("pending", &(self.0).0.lock().len()).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Defer {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(Defer(req.state.deferred.clone()))
+    }
+}
+
+/// Deferred work that is running, tracked so that shutdown can wait for it.
+#[derive(Default)]
+pub(crate) struct DeferredTasks {
+    running: AtomicUsize,
+    idle: Notify,
+}
+
+impl DeferredTasks {
+    /// Waits until no deferred work is running.
+    pub(crate) async fn idle(&self) {
+        loop {
+            let idle = self.idle.notified();
+            if self.running.load(Ordering::Acquire) == 0 {
+                return;
+            }
+
+            idle.await;
+        }
+    }
+}
+
+/// Marks a deferred task as finished when dropped, even if it panicked.
+struct Running(Arc<DeferredTasks>);
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        if self.0.running.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl Rocket {
+    /// Spawns the work deferred by a request, now that its response has been
+    /// sent.
+    pub(crate) fn run_deferred(&self, deferred: &Deferred) {
+        let work = std::mem::take(&mut *deferred.0.lock());
+        if !work.is_empty() {
+            info_!("Running {} deferred task(s).", work.len());
+        }
+
+        for work in work {
+            self.spawn_tracked(work);
+        }
+    }
+
+    /// Spawns the cleanup futures of a response, in order, now that its body
+    /// has been written out and dropped.
+    pub(crate) fn run_after_sent(&self, cleanup: Vec<BoxFuture<'static, ()>>) {
+        if !cleanup.is_empty() {
+            self.spawn_tracked(Box::pin(async move {
+                for cleanup in cleanup {
+                    cleanup.await;
+                }
+            }));
+        }
+    }
+
+    /// Spawns `work`, tracked so that shutdown waits for it.
+    fn spawn_tracked(&self, work: BoxFuture<'static, ()>) {
+        self.deferred.running.fetch_add(1, Ordering::AcqRel);
+        let running = Running(self.deferred.clone());
+        tokio::spawn(async move {
+            let _running = running;
+            work.await;
+        });
+    }
+}
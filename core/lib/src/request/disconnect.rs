@@ -0,0 +1,124 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Waker};
+
+use parking_lot::Mutex;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// The state of the connection a request was received on.
+#[derive(Default)]
+pub(crate) struct Connection {
+    disconnected: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Connection {
+    #[inline]
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Acquire)
+    }
+
+    /// Marks the connection as disconnected and wakes all pending
+    /// [`Disconnect`] futures.
+    pub(crate) fn disconnect(&self) {
+        if !self.disconnected.swap(true, Ordering::AcqRel) {
+            self.wakers.lock().drain(..).for_each(|w| w.wake());
+        }
+    }
+}
+
+/// Request guard and future that resolves when the client disconnects.
+///
+/// Long-lived handlers, such as those that stream responses or server-sent
+/// events, can await a `Disconnect` to stop producing data as soon as the peer
+/// goes away instead of writing into a closed socket. Disconnects are detected
+/// via the underlying HTTP connection: both while the handler runs and while
+/// the response body is being streamed. A `Disconnect` obtained from a
+/// locally dispatched request never resolves.
+///
+/// As a request guard, `Disconnect` never fails.
+///
+/// # Example
+///
+/// Stream an event every second until the client disconnects:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+///
+/// use rocket::request::Disconnect;
+/// use rocket::response::Stream;
+/// use rocket::tokio::time::interval;
+/// use rocket::tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+///
+/// #[get("/events")]
+/// fn events(disconnect: Disconnect) -> Stream<DuplexStream> {
+///     let (mut tx, rx) = duplex(64);
+///     rocket::tokio::spawn(async move {
+///         let mut ticks = interval(Duration::from_secs(1));
+///         rocket::tokio::select! {
+///             _ = disconnect => { /* client went away: clean up */ },
+///             _ = async {
+///                 loop {
+///                     ticks.tick().await;
+///                     if tx.write_all(b"data: tick\n\n").await.is_err() { break }
+///                 }
+///             } => {}
+///         }
+///     });
+///
+///     Stream::from(rx)
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct Disconnect(pub(crate) Arc<Connection>);
+
+impl Disconnect {
+    /// Returns `true` if the client has disconnected.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.0.is_disconnected()
+    }
+}
+
+impl Future for Disconnect {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0.is_disconnected() {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = self.0.wakers.lock();
+        if self.0.is_disconnected() {
+            return Poll::Ready(());
+        }
+
+        if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl std::fmt::Debug for Disconnect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Disconnect").field(&self.is_disconnected()).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Disconnect {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(Disconnect(request.state.connection.clone()))
+    }
+}
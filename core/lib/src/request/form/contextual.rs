@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::request::{Request, form::{Form, FormItems, FormDataError, FormParseError, FromForm}};
+use crate::request::form::Duplicates;
+use crate::data::{Data, Transformed, FromTransformedData, TransformFuture, FromDataFuture};
+
+/// A data guard for parsing [`FromForm`] types that preserves the submitted
+/// form for redisplay.
+///
+/// Unlike [`Form`], which fails when the form doesn't parse as a `T`, a
+/// `Contextual<T>` always succeeds once the form data is read. It carries the
+/// parsed `value`, if parsing succeeded, along with a [`Context`] containing
+/// the submitted, URL-decoded values of every field and, if parsing failed, an
+/// error message for every invalid field. Server-rendered applications can use
+/// the context to redisplay a form with the user's input and the errors
+/// alongside the fields they refer to; `Context` implements `Serialize` so
+/// that it can be passed directly to a template.
+///
+/// Parsing is strict, as with [`Form`]. Like `Form`, the guard forwards if the
+/// request's Content-Type isn't `application/x-www-form-urlencoded` and fails
+/// if the data can't be read. Only types whose `FromForm` error type is
+/// [`FormParseError`], including all derived implementations, can be parsed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Contextual;
+///
+/// #[derive(FromForm)]
+/// struct Signup {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// # fn render(_: &rocket::request::Context) -> String { String::new() }
+/// #[post("/signup", data = "<form>")]
+/// fn signup(form: Contextual<Signup>) -> String {
+///     match form.value {
+///         Some(signup) => format!("Welcome, {}!", signup.name),
+///         // Re-render the form with the submitted values and errors.
+///         None => render(&form.context),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Contextual<T> {
+    /// The parsed value, if the form parsed successfully.
+    pub value: Option<T>,
+    /// The submitted values and any errors.
+    pub context: Context,
+}
+
+/// The submitted values and errors of a form parsed by [`Contextual`].
+///
+/// When serialized, a `Context` is a map with four entries: `values`, mapping
+/// each field name to the list of values submitted for it; `errors`, mapping
+/// each invalid field name to the list of its error messages; `form_errors`,
+/// the list of error messages that don't refer to a field; and `honeypots`,
+/// the names of the form's honeypot fields, which a template should render as
+/// hidden, empty inputs. Values submitted for honeypots are never recorded so
+/// that they are redisplayed empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Context {
+    values: BTreeMap<String, Vec<String>>,
+    errors: BTreeMap<String, Vec<String>>,
+    form_errors: Vec<String>,
+    honeypots: Vec<String>,
+}
+
+impl Context {
+    /// Returns the first value submitted for the field `name`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Contextual;
+    ///
+    /// #[derive(FromForm)]
+    /// struct Signup { age: u8 }
+    ///
+    /// let form = Contextual::<Signup>::parse("age=ten&age=11");
+    /// assert_eq!(form.context.value("age"), Some("ten"));
+    /// assert_eq!(form.context.value("name"), None);
+    /// ```
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).next()
+    }
+
+    /// Returns every value submitted for the field `name`, in order.
+    pub fn values<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> + 'a {
+        self.values.get(name).into_iter().flatten().map(|s| s.as_str())
+    }
+
+    /// Returns the error messages for the field `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Contextual;
+    ///
+    /// #[derive(FromForm)]
+    /// struct Signup { name: String, age: u8 }
+    ///
+    /// let form = Contextual::<Signup>::parse("age=ten");
+    /// assert_eq!(form.context.errors("age").collect::<Vec<_>>(), ["invalid value"]);
+    /// assert_eq!(form.context.errors("name").collect::<Vec<_>>(), ["missing field"]);
+    /// ```
+    pub fn errors<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> + 'a {
+        self.errors.get(name).into_iter().flatten().map(|s| s.as_str())
+    }
+
+    /// Returns the error messages that don't refer to a field, such as for a
+    /// malformed form.
+    pub fn form_errors(&self) -> impl Iterator<Item = &str> {
+        self.form_errors.iter().map(|s| s.as_str())
+    }
+
+    /// Returns the names of the form's honeypot fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::Contextual;
+    ///
+    /// #[derive(FromForm)]
+    /// struct Comment {
+    ///     text: String,
+    ///     #[form(honeypot)]
+    ///     website: String,
+    /// }
+    ///
+    /// let form = Contextual::<Comment>::parse("text=hi&website=spam.example");
+    /// assert_eq!(form.context.honeypots().collect::<Vec<_>>(), ["website"]);
+    /// assert_eq!(form.context.value("website"), None);
+    /// assert!(form.value.is_none());
+    /// ```
+    pub fn honeypots(&self) -> impl Iterator<Item = &str> {
+        self.honeypots.iter().map(|s| s.as_str())
+    }
+
+    /// Returns `true` if there are any errors, for a field or otherwise.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty() || !self.form_errors.is_empty()
+    }
+
+    fn push_error(&mut self, name: &str, message: &str) {
+        self.errors.entry(name.into()).or_default().push(message.into());
+    }
+}
+
+impl<'f, T: FromForm<'f, Error = FormParseError<'f>>> Contextual<T> {
+    /// Strictly parses a `T` from the form string `form_str`, recording the
+    /// submitted values and any errors.
+    pub fn parse(form_str: &'f str) -> Contextual<T> {
+        Contextual::parse_with(form_str, Duplicates::default())
+    }
+
+    /// Like [`Contextual::parse()`] but applies the `duplicates` policy.
+    pub(crate) fn parse_with(form_str: &'f str, duplicates: Duplicates) -> Contextual<T> {
+        let mut context = Context::default();
+        context.honeypots = T::HONEYPOTS.iter().map(|&name| name.into()).collect();
+
+        let mut items = FormItems::from(form_str);
+        for item in items.by_ref() {
+            let (key, value) = item.key_value_decoded();
+            if !context.honeypots.contains(&key) {
+                context.values.entry(key).or_default().push(value);
+            }
+        }
+
+        if !items.exhaust() {
+            context.form_errors.push("malformed form".into());
+            return Contextual { value: None, context };
+        }
+
+        let mut items = FormItems::from(form_str).with_duplicates(duplicates);
+        let value = match T::from_form_all(&mut items, true) {
+            Ok(value) => Some(value),
+            Err(errors) => {
+                for error in errors {
+                    match error {
+                        FormParseError::BadValue(k, _) => {
+                            context.push_error(&k.url_decode_lossy(), "invalid value");
+                        }
+                        FormParseError::Unknown(k, _) => {
+                            context.push_error(&k.url_decode_lossy(), "unexpected field");
+                        }
+                        FormParseError::Missing(k) => {
+                            context.push_error(&k.url_decode_lossy(), "missing field");
+                        }
+                        FormParseError::Duplicate(k, _) => {
+                            context.push_error(&k.url_decode_lossy(), "duplicate field");
+                        }
+                        FormParseError::Honeypot(_) => {
+                            context.form_errors.push("rejected submission".into());
+                        }
+                    }
+                }
+
+                None
+            }
+        };
+
+        Contextual { value, context }
+    }
+}
+
+impl<'r, T> FromTransformedData<'r> for Contextual<T>
+    where T: FromForm<'r, Error = FormParseError<'r>> + Send + 'r
+{
+    type Error = FormDataError<'r, FormParseError<'r>>;
+    type Owned = String;
+    type Borrowed = str;
+
+    fn transform(
+        request: &'r Request<'_>,
+        data: Data
+    ) -> TransformFuture<'r, Self::Owned, Self::Error> {
+        <Form<T>>::transform(request, data)
+    }
+
+    fn from_data(
+        request: &'r Request<'_>,
+        o: Transformed<'r, Self>
+    ) -> FromDataFuture<'r, Self, Self::Error> {
+        let duplicates = request.form_duplicates();
+        Box::pin(async move {
+            o.borrowed().map(|form_str| Contextual::parse_with(form_str, duplicates))
+        })
+    }
+}
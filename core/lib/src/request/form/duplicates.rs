@@ -0,0 +1,93 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
+
+/// How fields that appear more than once in a form or query string are
+/// treated when parsing a single value.
+///
+/// The policy applies to fields of structures deriving
+/// [`FromForm`](crate::request::FromForm), to single-valued query parameters
+/// in routes, and to [`Request::get_query_value()`]. It is configured
+/// application-wide via the `form_duplicates` configuration parameter, which
+/// defaults to `"last"`, and can be overridden for a single field of a
+/// `FromForm` structure with `#[form(duplicates = "...")]`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromForm)]
+/// struct Search {
+///     #[form(duplicates = "reject")]
+///     query: String,
+///     #[form(duplicates = "first")]
+///     page: usize,
+/// }
+/// ```
+///
+/// [`Request::get_query_value()`]: crate::Request::get_query_value()
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Duplicates {
+    /// The first value wins; later values are ignored: `"first"`.
+    First,
+    /// The last value wins; earlier values are ignored: `"last"`.
+    Last,
+    /// Duplicates are an error: `"reject"`. Derived `FromForm`
+    /// implementations fail with [`FormParseError::Duplicate`] while routes
+    /// forward.
+    ///
+    /// [`FormParseError::Duplicate`]: crate::request::FormParseError::Duplicate
+    Reject,
+}
+
+impl Duplicates {
+    fn as_str(&self) -> &str {
+        match self {
+            Duplicates::First => "first",
+            Duplicates::Last => "last",
+            Duplicates::Reject => "reject",
+        }
+    }
+}
+
+impl Default for Duplicates {
+    fn default() -> Self {
+        Duplicates::Last
+    }
+}
+
+impl FromStr for Duplicates {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let policy = match &*s.to_ascii_lowercase() {
+            "first" => Duplicates::First,
+            "last" => Duplicates::Last,
+            "reject" => Duplicates::Reject,
+            _ => return Err("a duplicates policy (first, last, reject)")
+        };
+
+        Ok(policy)
+    }
+}
+
+impl fmt::Display for Duplicates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for Duplicates {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Duplicates {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        Duplicates::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["first", "last", "reject"])
+        ))
+    }
+}
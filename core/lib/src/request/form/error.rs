@@ -7,8 +7,9 @@ use crate::http::RawStr;
 /// If multiple errors occur while parsing a form, the first error in the
 /// following precedence, from highest to lowest, is returned:
 ///
-///   * `BadValue` or `Unknown` in incoming form string field order
-///   * `Missing` in lexical field order
+///   * `BadValue`, `Unknown`, `Duplicate`, or `Honeypot` for a filled in
+///     honeypot in incoming form string field order
+///   * `Missing` or `Honeypot` for a missing honeypot in lexical field order
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FormParseError<'f> {
     /// The field named `.0` with value `.1` failed to parse or validate.
@@ -20,6 +21,16 @@ pub enum FormParseError<'f> {
     Unknown(&'f RawStr, &'f RawStr),
     /// The field named `.0` was expected but is missing in the incoming form.
     Missing(&'f RawStr),
+    /// The field named `.0` appeared again, with value `.1`, in the incoming
+    /// form string, and duplicates are rejected.
+    ///
+    /// This error only occurs when the [`Duplicates`] policy is `Reject`.
+    ///
+    /// [`Duplicates`]: crate::request::Duplicates
+    Duplicate(&'f RawStr, &'f RawStr),
+    /// The honeypot field named `.0`, marked `#[form(honeypot)]`, was missing,
+    /// repeated, or filled in. The submission is likely from a spam bot.
+    Honeypot(&'f RawStr),
 }
 
 /// Error returned by the [`FromTransformedData`](crate::data::FromTransformedData) implementations of
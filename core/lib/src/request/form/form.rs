@@ -1,7 +1,8 @@
+use std::io;
 use std::ops::{Deref, DerefMut};
 
 use crate::outcome::Outcome::*;
-use crate::request::{Request, form::{FromForm, FormItems, FormDataError}};
+use crate::request::{Request, form::{FromForm, FormItems, FormDataError, Duplicates, FormStream}};
 use crate::data::{Data, Outcome, Transform, Transformed, ToByteUnit};
 use crate::data::{TransformFuture, FromTransformedData, FromDataFuture};
 use crate::http::{Status, uri::{Query, FromUriParam}};
@@ -109,6 +110,12 @@ use crate::http::{Status, uri::{Query, FromUriParam}};
 /// [global.limits]
 /// forms = 524288
 /// ```
+///
+/// The form is read incrementally, field by field. A malformed field is
+/// rejected as soon as it has been read, without reading the rest of the form.
+/// The size of any one field can additionally be limited with the
+/// `limits.form-field` parameter, which defaults to the forms limit; a form
+/// with a larger field fails with a status of `413 Payload Too Large`.
 #[derive(Debug)]
 pub struct Form<T>(pub T);
 
@@ -155,11 +162,12 @@ impl<T> DerefMut for Form<T> {
 impl<'f, T: FromForm<'f>> Form<T> {
     pub(crate) fn from_data(
         form_str: &'f str,
-        strict: bool
+        strict: bool,
+        duplicates: Duplicates,
     ) -> Outcome<T, FormDataError<'f, T::Error>> {
         use self::FormDataError::*;
 
-        let mut items = FormItems::from(form_str);
+        let mut items = FormItems::from(form_str).with_duplicates(duplicates);
         let result = T::from_form(&mut items, strict);
         if !items.exhaust() {
             error_!("The request's form string was malformed.");
@@ -204,23 +212,57 @@ impl<'r, T: FromForm<'r> + Send + 'r> FromTransformedData<'r> for Form<T> {
                 return Transform::Borrowed(Forward(data));
             }
 
+            // Read the form field by field, stopping at the first malformed
+            // field: `from_data` reports it without the rest being read.
             let limit = request.limits().get("forms").unwrap_or(32.kibibytes());
-            match data.open(limit).stream_to_string().await {
-                Ok(form_string) => Transform::Borrowed(Success(form_string)),
-                Err(e) => {
-                    let err = (Status::InternalServerError, FormDataError::Io(e));
-                    Transform::Borrowed(Failure(err))
+            let field_limit = request.limits().get("form-field").unwrap_or(limit);
+            let mut stream = FormStream::new(data.open(limit), field_limit);
+            let mut form_string = String::new();
+            loop {
+                let field = match stream.next_raw().await {
+                    Ok(Some(field)) if field.is_empty() => continue,
+                    Ok(Some(field)) => field,
+                    Ok(None) => break,
+                    Err(e) => {
+                        let status = match e.kind() {
+                            io::ErrorKind::InvalidData => Status::PayloadTooLarge,
+                            _ => Status::InternalServerError,
+                        };
+
+                        return Transform::Borrowed(Failure((status, FormDataError::Io(e))));
+                    }
+                };
+
+                let field = match String::from_utf8(field) {
+                    Ok(field) => field,
+                    Err(e) => {
+                        let e = io::Error::new(io::ErrorKind::InvalidData, e.utf8_error());
+                        let err = (Status::InternalServerError, FormDataError::Io(e));
+                        return Transform::Borrowed(Failure(err));
+                    }
+                };
+
+                if !form_string.is_empty() {
+                    form_string.push('&');
+                }
+
+                form_string.push_str(&field);
+                if field.matches('=').count() > 1 {
+                    break;
                 }
             }
+
+            Transform::Borrowed(Success(form_string))
         })
     }
 
     fn from_data(
-        _: &'r Request<'_>,
+        request: &'r Request<'_>,
         o: Transformed<'r, Self>
     ) -> FromDataFuture<'r, Self, Self::Error> {
+        let duplicates = request.form_duplicates();
         Box::pin(async move {
-            o.borrowed().and_then(|data| <Form<T>>::from_data(data, true).map(Form))
+            o.borrowed().and_then(|data| <Form<T>>::from_data(data, true, duplicates).map(Form))
         })
     }
 }
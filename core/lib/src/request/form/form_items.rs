@@ -1,6 +1,7 @@
 use memchr::memchr2;
 
 use crate::http::RawStr;
+use crate::request::form::Duplicates;
 
 /// Iterator over the key/value pairs of a given HTTP form string.
 ///
@@ -107,12 +108,14 @@ pub enum FormItems<'f> {
     #[doc(hidden)]
     Raw {
         string: &'f RawStr,
-        next_index: usize
+        next_index: usize,
+        duplicates: Duplicates,
     },
     #[doc(hidden)]
     Cooked {
         items: &'f [FormItem<'f>],
-        next_index: usize
+        next_index: usize,
+        duplicates: Duplicates,
     }
 }
 
@@ -212,6 +215,41 @@ impl<'f> FormItem<'f> {
 }
 
 impl FormItems<'_> {
+    /// Returns the [`Duplicates`] policy that `FromForm` implementations
+    /// should apply to fields that appear more than once in `self`. The
+    /// default is [`Duplicates::Last`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{FormItems, Duplicates};
+    ///
+    /// let items = FormItems::from("a=b&a=c");
+    /// assert_eq!(items.duplicates(), Duplicates::Last);
+    ///
+    /// let items = items.with_duplicates(Duplicates::Reject);
+    /// assert_eq!(items.duplicates(), Duplicates::Reject);
+    /// ```
+    #[inline]
+    pub fn duplicates(&self) -> Duplicates {
+        match self {
+            FormItems::Raw { duplicates, .. } => *duplicates,
+            FormItems::Cooked { duplicates, .. } => *duplicates,
+        }
+    }
+
+    /// Sets the [`Duplicates`] policy of `self` to `policy`. See
+    /// [`FormItems::duplicates()`].
+    #[inline]
+    pub fn with_duplicates(mut self, policy: Duplicates) -> Self {
+        match self {
+            FormItems::Raw { ref mut duplicates, .. } => *duplicates = policy,
+            FormItems::Cooked { ref mut duplicates, .. } => *duplicates = policy,
+        }
+
+        self
+    }
+
     /// Returns `true` if the form string was parsed to completion. Returns
     /// `false` otherwise. All valid form strings will parse to completion,
     /// while invalid form strings will not.
@@ -244,8 +282,8 @@ impl FormItems<'_> {
     #[inline]
     pub fn completed(&self) -> bool {
         match self {
-            FormItems::Raw { string, next_index } => *next_index >= string.len(),
-            FormItems::Cooked { items, next_index } => *next_index >= items.len(),
+            FormItems::Raw { string, next_index, .. } => *next_index >= string.len(),
+            FormItems::Cooked { items, next_index, .. } => *next_index >= items.len(),
         }
     }
 
@@ -291,8 +329,8 @@ impl FormItems<'_> {
     #[doc(hidden)]
     pub fn mark_complete(&mut self) {
         match self {
-            FormItems::Raw { string, ref mut next_index } => *next_index = string.len(),
-            FormItems::Cooked { items, ref mut next_index } => *next_index = items.len(),
+            FormItems::Raw { string, ref mut next_index, .. } => *next_index = string.len(),
+            FormItems::Cooked { items, ref mut next_index, .. } => *next_index = items.len(),
         }
     }
 }
@@ -300,7 +338,7 @@ impl FormItems<'_> {
 impl<'f> From<&'f RawStr> for FormItems<'f> {
     #[inline(always)]
     fn from(string: &'f RawStr) -> FormItems<'f> {
-        FormItems::Raw { string, next_index: 0 }
+        FormItems::Raw { string, next_index: 0, duplicates: Duplicates::default() }
     }
 }
 
@@ -314,7 +352,7 @@ impl<'f> From<&'f str> for FormItems<'f> {
 impl<'f> From<&'f [FormItem<'f>]> for FormItems<'f> {
     #[inline(always)]
     fn from(items: &'f [FormItem<'f>]) -> FormItems<'f> {
-        FormItems::Cooked { items, next_index: 0 }
+        FormItems::Cooked { items, next_index: 0, duplicates: Duplicates::default() }
     }
 }
 
@@ -356,10 +394,10 @@ impl<'f> Iterator for FormItems<'f> {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self {
-            FormItems::Raw { ref mut string, ref mut next_index } => {
+            FormItems::Raw { ref mut string, ref mut next_index, .. } => {
                 raw(string, next_index)
             }
-            FormItems::Cooked { items, ref mut next_index } => {
+            FormItems::Cooked { items, ref mut next_index, .. } => {
                 if *next_index < items.len() {
                     let item = items[*next_index];
                     *next_index += 1;
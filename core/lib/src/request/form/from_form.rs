@@ -1,3 +1,7 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
 use crate::request::FormItems;
 
 /// Trait to create an instance of some type from an HTTP form.
@@ -93,6 +97,14 @@ pub trait FromForm<'f>: Sized {
     /// The associated error to be returned when parsing fails.
     type Error: Send;
 
+    /// The names of the form's honeypot fields: fields that must be submitted
+    /// with an empty value and that humans, unlike spam bots, leave alone.
+    ///
+    /// Derived implementations list the fields marked `#[form(honeypot)]`.
+    /// [`Contextual`](crate::request::Contextual) exposes the names so that
+    /// templates can render the fields. The default is empty.
+    const HONEYPOTS: &'static [&'static str] = &[];
+
     /// Parses an instance of `Self` from the iterator of form items `it`.
     ///
     /// Extra form field are allowed when `strict` is `false` and disallowed
@@ -106,11 +118,26 @@ pub trait FromForm<'f>: Sized {
     /// When `strict` is `true` and unexpected, extra fields are present in
     /// `it`, an instance of `Self::Error` will be returned.
     fn from_form(it: &mut FormItems<'f>, strict: bool) -> Result<Self, Self::Error>;
+
+    /// Parses an instance of `Self` from the iterator of form items `it`,
+    /// returning every error encountered instead of only the first.
+    ///
+    /// This method is used by [`Contextual`](crate::request::Contextual) to
+    /// report an error for every invalid field. The default implementation
+    /// returns the single error from [`FromForm::from_form()`]. Derived
+    /// implementations return all errors, in the order described by
+    /// [`FormParseError`](crate::request::FormParseError); `from_form()`
+    /// returns the first of them.
+    fn from_form_all(it: &mut FormItems<'f>, strict: bool) -> Result<Self, Vec<Self::Error>> {
+        Self::from_form(it, strict).map_err(|e| vec![e])
+    }
 }
 
 impl<'f, T: FromForm<'f>> FromForm<'f> for Option<T> {
     type Error = std::convert::Infallible;
 
+    const HONEYPOTS: &'static [&'static str] = T::HONEYPOTS;
+
     #[inline]
     fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Option<T>, Self::Error> {
         Ok(T::from_form(items, strict).ok())
@@ -120,8 +147,58 @@ impl<'f, T: FromForm<'f>> FromForm<'f> for Option<T> {
 impl<'f, T: FromForm<'f>> FromForm<'f> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
 
+    const HONEYPOTS: &'static [&'static str] = T::HONEYPOTS;
+
     #[inline]
     fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Self, Self::Error> {
         Ok(T::from_form(items, strict))
     }
 }
+
+macro_rules! impl_for_pointer {
+    ($($P:ident),+) => ($(
+        /// Parses the inner `T` and wraps it.
+        impl<'f, T: FromForm<'f>> FromForm<'f> for $P<T> {
+            type Error = T::Error;
+
+            const HONEYPOTS: &'static [&'static str] = T::HONEYPOTS;
+
+            #[inline]
+            fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Self, Self::Error> {
+                T::from_form(items, strict).map($P::new)
+            }
+
+            #[inline]
+            fn from_form_all(
+                items: &mut FormItems<'f>,
+                strict: bool
+            ) -> Result<Self, Vec<Self::Error>> {
+                T::from_form_all(items, strict).map($P::new)
+            }
+        }
+    )+)
+}
+
+impl_for_pointer!(Box, Rc, Arc);
+
+/// Parses the owned form of `T` and wraps it in `Cow::Owned`.
+impl<'f, 'a, T: ?Sized + ToOwned> FromForm<'f> for Cow<'a, T>
+    where T::Owned: FromForm<'f>
+{
+    type Error = <T::Owned as FromForm<'f>>::Error;
+
+    const HONEYPOTS: &'static [&'static str] = T::Owned::HONEYPOTS;
+
+    #[inline]
+    fn from_form(items: &mut FormItems<'f>, strict: bool) -> Result<Self, Self::Error> {
+        T::Owned::from_form(items, strict).map(Cow::Owned)
+    }
+
+    #[inline]
+    fn from_form_all(
+        items: &mut FormItems<'f>,
+        strict: bool
+    ) -> Result<Self, Vec<Self::Error>> {
+        T::Owned::from_form_all(items, strict).map(Cow::Owned)
+    }
+}
@@ -1,4 +1,7 @@
 use std::str::FromStr;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr};
 use std::num::{
     NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
@@ -280,6 +283,44 @@ impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for Option<T> {
     }
 }
 
+macro_rules! impl_for_pointer {
+    ($($P:ident),+) => ($(
+        /// Parses the inner `T` and wraps it.
+        impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for $P<T> {
+            type Error = T::Error;
+
+            #[inline(always)]
+            fn from_form_value(v: &'v RawStr) -> Result<Self, Self::Error> {
+                T::from_form_value(v).map($P::new)
+            }
+
+            #[inline(always)]
+            fn default() -> Option<Self> {
+                T::default().map($P::new)
+            }
+        }
+    )+)
+}
+
+impl_for_pointer!(Box, Rc, Arc);
+
+/// Parses the owned form of `T` and wraps it in `Cow::Owned`.
+impl<'v, 'a, T: ?Sized + ToOwned> FromFormValue<'v> for Cow<'a, T>
+    where T::Owned: FromFormValue<'v>
+{
+    type Error = <T::Owned as FromFormValue<'v>>::Error;
+
+    #[inline(always)]
+    fn from_form_value(v: &'v RawStr) -> Result<Self, Self::Error> {
+        T::Owned::from_form_value(v).map(Cow::Owned)
+    }
+
+    #[inline(always)]
+    fn default() -> Option<Self> {
+        T::Owned::default().map(Cow::Owned)
+    }
+}
+
 // // TODO: Add more useful implementations (range, regex, etc.).
 impl<'v, T: FromFormValue<'v>> FromFormValue<'v> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
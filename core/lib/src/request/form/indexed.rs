@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use crate::http::RawStr;
+use crate::request::form::{FormItem, FormItems, FormParseError, FromForm, Duplicates};
+
+/// Collects the items of an indexed form field and parses them into a `Vec`.
+///
+/// An indexed field named `name` is made up of items whose keys are of the
+/// form `name[i].rest`, where `i` is a decimal index. The items sharing an
+/// index, with keys stripped to `rest`, are parsed as a single `T` using `T`'s
+/// [`FromForm`] implementation. This is the format produced by front-end form
+/// builders that add and remove rows dynamically:
+///
+/// ```text
+/// items[0].name=apple&items[0].qty=2&items[1].name=pear
+/// ```
+///
+/// Indices need not be contiguous nor in order: the parsed values are ordered
+/// by index, so `items[7]` followed by `items[2]` parses as a `Vec` of two
+/// values, the one at index `2` first. Indices greater than the configured
+/// maximum are rejected with a [`FormParseError::BadValue`] to bound the
+/// values a client can make the server parse. Keys may be URL-encoded, as
+/// browsers do for `[` and `]`.
+///
+/// This type is used by the `FromForm` derive for fields marked with
+/// `#[form(nested)]`, which may also set `max_index`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromForm)]
+/// struct Item {
+///     name: String,
+///     qty: usize,
+/// }
+///
+/// #[derive(FromForm)]
+/// struct Order {
+///     customer: String,
+///     #[form(nested, max_index = 99)]
+///     items: Vec<Item>,
+/// }
+/// ```
+///
+/// Because each index is parsed from keys that don't appear verbatim in the
+/// form string, `T` must not borrow from the form; it must implement
+/// `FromForm<'a>` for every lifetime `'a`, as derived implementations for
+/// types without a lifetime parameter do.
+#[derive(Debug)]
+pub struct IndexedItems<'f> {
+    max_index: usize,
+    duplicates: Duplicates,
+    items: BTreeMap<usize, Vec<(String, FormItem<'f>)>>,
+    errors: Vec<FormParseError<'f>>,
+}
+
+impl<'f> IndexedItems<'f> {
+    /// The maximum index used when one isn't configured.
+    pub const DEFAULT_MAX_INDEX: usize = 1024;
+
+    /// Returns an empty collection that rejects indices greater than
+    /// `max_index`.
+    pub fn new(max_index: usize) -> Self {
+        let duplicates = Duplicates::default();
+        IndexedItems { max_index, duplicates, items: BTreeMap::new(), errors: vec![] }
+    }
+
+    /// Sets the [`Duplicates`] policy used when parsing each index to
+    /// `duplicates`. The default is [`Duplicates::Last`].
+    pub fn with_duplicates(mut self, duplicates: Duplicates) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Adds `item` to the collection if it belongs to the field `name`,
+    /// returning `true` if it does. Items with a malformed or excessive index
+    /// belong to the field but are recorded as errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{FormItems, IndexedItems};
+    ///
+    /// let mut indexed = IndexedItems::new(10);
+    /// for item in FormItems::from("items[0].a=1&other=2&items%5B1%5D.a=3") {
+    ///     let taken = indexed.push("items", item);
+    ///     assert_eq!(taken, item.key != "other");
+    /// }
+    /// ```
+    pub fn push(&mut self, name: &str, item: FormItem<'f>) -> bool {
+        let key = item.key.url_decode_lossy();
+        let rest = match key.strip_prefix(name).and_then(|k| k.strip_prefix('[')) {
+            Some(rest) => rest,
+            None => return false,
+        };
+
+        let parsed = rest.find(']').and_then(|i| {
+            let (index, rest) = (&rest[..i], &rest[(i + 1)..]);
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let index = index.parse::<usize>().ok().filter(|&i| i <= self.max_index)?;
+            match rest.strip_prefix('.') {
+                Some(rest) => Some((index, rest.to_string())),
+                None if rest.is_empty() => Some((index, String::new())),
+                None => None,
+            }
+        });
+
+        match parsed {
+            Some((index, rest)) => self.items.entry(index).or_default().push((rest, item)),
+            None => self.errors.push(FormParseError::BadValue(item.key, item.value)),
+        }
+
+        true
+    }
+
+    /// Parses the collected items, in index order, into a `Vec<T>`.
+    ///
+    /// Errors from parsing an index as a `T` are reported against the
+    /// original, unstripped keys; a missing sub-field is reported as missing
+    /// `name`. If the field had no items, the `Vec` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::{FormItems, IndexedItems};
+    ///
+    /// #[derive(FromForm, Debug, PartialEq)]
+    /// struct Item { name: String }
+    ///
+    /// let mut indexed = IndexedItems::new(10);
+    /// for item in FormItems::from("items[7].name=b&items[2].name=a") {
+    ///     indexed.push("items", item);
+    /// }
+    ///
+    /// let items: Vec<Item> = indexed.finish("items", true).unwrap();
+    /// assert_eq!(items, vec![Item { name: "a".into() }, Item { name: "b".into() }]);
+    /// ```
+    pub fn finish<T>(
+        self,
+        name: &'static str,
+        strict: bool
+    ) -> Result<Vec<T>, Vec<FormParseError<'f>>>
+        where T: for<'a> FromForm<'a, Error = FormParseError<'a>>
+    {
+        let mut errors = self.errors;
+        let mut values = Vec::with_capacity(self.items.len());
+        for entries in self.items.values() {
+            let items: Vec<FormItem<'_>> = entries.iter()
+                .map(|(rest, item)| FormItem { key: rest.as_str().into(), ..*item })
+                .collect();
+
+            let original = |key: &RawStr| entries.iter()
+                .find(|(rest, _)| rest == key.as_str())
+                .map(|(_, item)| *item)
+                .expect("key of stripped item");
+
+            let mut items = FormItems::from(&items[..]).with_duplicates(self.duplicates);
+            match T::from_form_all(&mut items, strict) {
+                Ok(value) => values.push(value),
+                Err(element_errors) => errors.extend(element_errors.into_iter().map(|e| match e {
+                    FormParseError::BadValue(k, _) => {
+                        let item = original(k);
+                        FormParseError::BadValue(item.key, item.value)
+                    }
+                    FormParseError::Unknown(k, _) => {
+                        let item = original(k);
+                        FormParseError::Unknown(item.key, item.value)
+                    }
+                    FormParseError::Missing(_) => FormParseError::Missing(name.into()),
+                    FormParseError::Honeypot(_) => FormParseError::Honeypot(name.into()),
+                    FormParseError::Duplicate(k, _) => {
+                        let item = original(k);
+                        FormParseError::Duplicate(item.key, item.value)
+                    }
+                })),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(values)
+    }
+}
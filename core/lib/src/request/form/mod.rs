@@ -6,10 +6,18 @@ mod from_form_value;
 mod lenient;
 mod error;
 mod form;
+mod stream;
+mod contextual;
+mod indexed;
+mod duplicates;
 
 pub use self::form_items::{FormItems, FormItem};
 pub use self::from_form::FromForm;
 pub use self::from_form_value::FromFormValue;
 pub use self::form::Form;
 pub use self::lenient::LenientForm;
+pub use self::stream::{FormStream, FormField};
+pub use self::contextual::{Contextual, Context};
+pub use self::indexed::IndexedItems;
+pub use self::duplicates::Duplicates;
 pub use self::error::{FormError, FormParseError, FormDataError};
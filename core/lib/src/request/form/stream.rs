@@ -0,0 +1,195 @@
+use std::io;
+
+use memchr::memchr;
+use tokio::io::AsyncReadExt;
+
+use crate::outcome::Outcome::*;
+use crate::request::Request;
+use crate::data::{Data, DataStream, FromData, Outcome, ByteUnit, ToByteUnit};
+use crate::http::RawStr;
+
+/// The size of the chunks read from the underlying data stream.
+const CHUNK_SIZE: usize = 4096;
+
+/// A single, URL-decoded field emitted by a [`FormStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    /// The URL-decoded key of the field.
+    pub key: String,
+    /// The URL-decoded value of the field.
+    pub value: String,
+}
+
+/// Incremental parser for `application/x-www-form-urlencoded` data.
+///
+/// A `FormStream` emits each [`FormField`] as soon as its terminating `&` (or
+/// the end of the stream) has been read. As a result, memory usage is bounded
+/// by the size of the largest field, not by the size of the body. The parsing
+/// rules are identical to those of [`FormItems`](crate::request::FormItems):
+/// consecutive and trailing `&` characters are allowed, items with both an
+/// empty key and an empty value are skipped, and an item containing more than
+/// one `=` is malformed.
+///
+/// [`Form`] reads its data through a `FormStream` as well, rejecting malformed
+/// fields and fields over the field limit as soon as they arrive. Since the
+/// values of a [`FromForm`] type may borrow from the form string, however, it
+/// retains the raw fields until the entire form has been read.
+///
+/// [`Form`]: crate::request::Form
+/// [`FromForm`]: crate::request::FromForm
+///
+/// # Limits
+///
+/// When used as a data guard, the total number of bytes read is limited by the
+/// `"forms"` limit, and each individual field is limited by the `"form-field"`
+/// limit, which defaults to the `"forms"` limit. The total limit silently
+/// truncates the stream, just like it does for `Form`, while exceeding the
+/// per-field limit results in an error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::FormStream;
+/// use rocket::response::Debug;
+///
+/// #[post("/count", data = "<form>")]
+/// async fn count(mut form: FormStream) -> Result<String, Debug<std::io::Error>> {
+///     let mut count = 0;
+///     while let Some(field) = form.next().await? {
+///         if field.key == "item" {
+///             count += 1;
+///         }
+///     }
+///
+///     Ok(count.to_string())
+/// }
+/// # fn main() {}
+/// ```
+pub struct FormStream {
+    stream: DataStream,
+    buffer: Vec<u8>,
+    field_limit: usize,
+    eof: bool,
+}
+
+impl FormStream {
+    /// Creates a new `FormStream` reading from `stream` which errors if any
+    /// single field exceeds `field_limit` bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    /// use rocket::request::FormStream;
+    ///
+    /// fn stream(data: Data) -> FormStream {
+    ///     FormStream::new(data.open(1.mebibytes()), 4.kibibytes())
+    /// }
+    /// ```
+    pub fn new(stream: DataStream, field_limit: ByteUnit) -> FormStream {
+        let field_limit = field_limit.as_u64().min(usize::max_value() as u64) as usize;
+        FormStream { stream, buffer: Vec::new(), field_limit, eof: false }
+    }
+
+    /// Returns the next field in the stream, `Ok(None)` if the stream has been
+    /// exhausted, or an `Err` if reading from the stream failed, the field was
+    /// malformed, the field contained invalid UTF-8, or the field exceeded the
+    /// field limit.
+    pub async fn next(&mut self) -> io::Result<Option<FormField>> {
+        loop {
+            let raw = match self.next_raw().await? {
+                Some(raw) => raw,
+                None => return Ok(None),
+            };
+
+            let string = std::str::from_utf8(&raw)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let (key, value) = match memchr(b'=', raw.as_slice()) {
+                Some(i) => (&string[..i], &string[(i + 1)..]),
+                None => (string, ""),
+            };
+
+            if value.contains('=') {
+                let msg = format!("malformed form field: {:?}", string);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+
+            if key.is_empty() && value.is_empty() {
+                continue;
+            }
+
+            let decode = |s: &str| RawStr::from_str(s).url_decode()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+
+            return Ok(Some(FormField { key: decode(key)?, value: decode(value)? }));
+        }
+    }
+
+    /// Reads until a complete, raw field is available and returns it. Returns
+    /// an error of kind `InvalidData` if the field exceeds the field limit.
+    pub(crate) async fn next_raw(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut searched = 0;
+        loop {
+            if let Some(i) = memchr(b'&', &self.buffer[searched..]) {
+                let end = searched + i;
+                if end > self.field_limit {
+                    return Err(self.limit_exceeded());
+                }
+
+                let field = self.buffer[..end].to_vec();
+                self.buffer.drain(..=end);
+                return Ok(Some(field));
+            }
+
+            if self.buffer.len() > self.field_limit {
+                return Err(self.limit_exceeded());
+            }
+
+            if self.eof {
+                return match self.buffer.is_empty() {
+                    true => Ok(None),
+                    false => Ok(Some(std::mem::replace(&mut self.buffer, Vec::new()))),
+                };
+            }
+
+            searched = self.buffer.len();
+            let mut chunk = [0u8; CHUNK_SIZE];
+            let n = self.stream.read(&mut chunk).await?;
+            self.eof = n == 0;
+            self.buffer.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    fn limit_exceeded(&self) -> io::Error {
+        let msg = format!("form field exceeded limit of {} bytes", self.field_limit);
+        io::Error::new(io::ErrorKind::InvalidData, msg)
+    }
+}
+
+#[crate::async_trait]
+impl FromData for FormStream {
+    type Error = std::convert::Infallible;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> Outcome<Self, Self::Error> {
+        if !req.content_type().map_or(false, |ct| ct.is_form()) {
+            warn_!("Form data does not have form content type.");
+            return Forward(data);
+        }
+
+        let limit = req.limits().get("forms").unwrap_or(32.kibibytes());
+        let field_limit = req.limits().get("form-field").unwrap_or(limit);
+        Success(FormStream::new(data.open(limit), field_limit))
+    }
+}
+
+impl std::fmt::Debug for FormStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormStream")
+            .field("buffered", &self.buffer.len())
+            .field("field_limit", &self.field_limit)
+            .field("eof", &self.eof)
+            .finish()
+    }
+}
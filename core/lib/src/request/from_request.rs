@@ -1,7 +1,9 @@
 use std::fmt::Debug;
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 
 use futures::future::BoxFuture;
+use either::Either;
 
 use crate::router::Route;
 use crate::request::Request;
@@ -235,6 +237,40 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 /// # fn main() { }
 /// ```
 ///
+/// # Deriving
+///
+/// A structure whose fields are all request guards can derive `FromRequest`.
+/// The derived implementation runs each field's guard in declaration order and
+/// succeeds if all of them do. The first guard to forward forwards the request,
+/// and the first guard to fail fails it with that guard's status and a
+/// [`GuardError`]. A field marked `#[from_request(forward_on_error)]` forwards
+/// instead of failing, while one marked `#[from_request(status = 401)]` fails
+/// with the given status instead of its guard's:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # type ApiKey = rocket::http::Method;
+/// use rocket::Route;
+/// use rocket::http::CookieJar;
+///
+/// #[derive(FromRequest)]
+/// struct Context<'a, 'r> {
+///     route: &'r Route,
+///     #[from_request(status = 401)]
+///     key: ApiKey,
+///     #[from_request(forward_on_error)]
+///     cookies: &'a CookieJar<'r>,
+/// }
+///
+/// #[get("/")]
+/// fn index(context: Context<'_, '_>) { /* ... */ }
+/// # fn main() { }
+/// ```
+///
+/// A structure may have at most two lifetime parameters. The first is the
+/// lifetime `'a` of the borrow of the request, and the second is the request's
+/// lifetime `'r`.
+///
 /// # Request-Local State
 ///
 /// Request guards that perform expensive operations, such as those that query a
@@ -369,6 +405,11 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 /// Notice that these request guards provide access to *borrowed* data (`&'a
 /// User` and `Admin<'a>`) as the data is now owned by the request's cache.
 ///
+/// When a guard's outcome can be cached wholesale, the [`CachedGuard`] wrapper
+/// removes the need for such an implementation: requesting
+/// `CachedGuard<User>` executes the `User` guard at most once per request.
+///
+/// [`CachedGuard`]: crate::request::CachedGuard
 /// [request-local state]: https://rocket.rs/master/guide/state/#request-local-state
 #[crate::async_trait]
 pub trait FromRequest<'a, 'r>: Sized {
@@ -501,3 +542,80 @@ impl<'a, 'r, T: FromRequest<'a, 'r> + 'a> FromRequest<'a, 'r> for Option<T> {
         }).boxed()
     }
 }
+
+impl<'a, 'r, T: FromRequest<'a, 'r> + 'a> FromRequest<'a, 'r> for Box<T> {
+    type Error = T::Error;
+
+    fn from_request<'y>(request: &'a Request<'r>) -> BoxFuture<'y, Outcome<Self, Self::Error>>
+        where 'a: 'y, 'r: 'y
+    {
+        // TODO: FutureExt::map is a workaround (see rust-lang/rust#60658)
+        use futures::future::FutureExt;
+        T::from_request(request).map(|x| x.map(Box::new)).boxed()
+    }
+}
+
+impl<'a, 'r, T: FromRequest<'a, 'r> + 'a> FromRequest<'a, 'r> for Arc<T> {
+    type Error = T::Error;
+
+    fn from_request<'y>(request: &'a Request<'r>) -> BoxFuture<'y, Outcome<Self, Self::Error>>
+        where 'a: 'y, 'r: 'y
+    {
+        // TODO: FutureExt::map is a workaround (see rust-lang/rust#60658)
+        use futures::future::FutureExt;
+        T::from_request(request).map(|x| x.map(Arc::new)).boxed()
+    }
+}
+
+/// Succeeds with `Left` if the `A` guard succeeds and otherwise with `Right` if
+/// the `B` guard succeeds. If neither succeeds, fails with the error of `B` if
+/// it failed, otherwise with the error of `A` if it failed, and otherwise
+/// forwards. `B` is only run if `A` doesn't succeed.
+#[crate::async_trait]
+impl<'a, 'r, A, B> FromRequest<'a, 'r> for Either<A, B>
+    where A: FromRequest<'a, 'r> + 'a, A::Error: Send, B: FromRequest<'a, 'r> + 'a
+{
+    type Error = Either<A::Error, B::Error>;
+
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        let a_failure = match A::from_request(request).await {
+            Success(a) => return Success(Either::Left(a)),
+            Failure((status, e)) => Some((status, Either::Left(e))),
+            Forward(_) => None,
+        };
+
+        match B::from_request(request).await {
+            Success(b) => Success(Either::Right(b)),
+            Failure((status, e)) => Failure((status, Either::Right(e))),
+            Forward(_) => match a_failure {
+                Some(failure) => Failure(failure),
+                None => Forward(()),
+            },
+        }
+    }
+}
+
+/// The error returned by [derived](FromRequest#deriving) `FromRequest`
+/// implementations when a field's guard fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardError {
+    /// The name of the field whose guard failed.
+    pub field: &'static str,
+    /// The `Debug` representation of the error returned by the guard.
+    pub error: String,
+}
+
+impl GuardError {
+    #[doc(hidden)]
+    pub fn new<E: Debug>(field: &'static str, error: &E) -> GuardError {
+        GuardError { field, error: format!("{:?}", error) }
+    }
+}
+
+impl std::fmt::Display for GuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "guard for `{}` failed: {}", self.field, self.error)
+    }
+}
+
+impl std::error::Error for GuardError { }
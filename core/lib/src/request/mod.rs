@@ -5,21 +5,49 @@ mod param;
 mod form;
 mod from_request;
 mod state;
+mod cached;
 mod query;
+mod deadline;
+mod page;
+mod spec;
+mod conditional;
+mod trace_context;
+mod nonce;
+mod version;
+mod user_agent;
+mod timings;
+mod server_timing;
+pub(crate) mod disconnect;
+pub(crate) mod defer;
 
 #[cfg(test)]
 mod tests;
 
-#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue};
+#[doc(hidden)] pub use rocket_codegen::{FromForm, FromFormValue, FromRequest};
 
 pub use self::request::Request;
-pub use self::from_request::{FromRequest, Outcome};
+pub use self::from_request::{FromRequest, Outcome, GuardError};
 pub use self::param::{FromParam, FromSegments};
 pub use self::form::{FromForm, FromFormValue};
-pub use self::form::{Form, LenientForm, FormItems, FormItem};
+pub use self::form::{Form, LenientForm, FormItems, FormItem, IndexedItems, Duplicates};
+pub use self::form::{FormStream, FormField};
+pub use self::form::{Contextual, Context};
 pub use self::form::{FormError, FormParseError, FormDataError};
 pub use self::state::State;
+pub use self::cached::CachedGuard;
 pub use self::query::{Query, FromQuery};
+pub use self::deadline::Deadline;
+pub use self::page::{Page, PageLimits, PageError};
+pub use self::spec::{SortSpec, SortKey, Order, FilterSpec, Filter, FilterOp, SpecError};
+pub use self::conditional::{IfMatch, IfNoneMatch, Preconditions, PreconditionError};
+pub use self::disconnect::Disconnect;
+pub use self::defer::Defer;
+pub use self::trace_context::TraceContext;
+pub use self::nonce::Nonce;
+pub use self::version::ApiVersion;
+pub use self::user_agent::{UserAgent, Device};
+pub use self::timings::{Timings, Timing, Phase, TimingsMode};
+pub use self::server_timing::{ServerTiming, Metric};
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
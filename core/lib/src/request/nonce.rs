@@ -0,0 +1,99 @@
+use std::fmt;
+
+use rand::{distributions::Alphanumeric, Rng};
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// The number of random characters in a nonce: over 128 bits of entropy.
+const NONCE_LEN: usize = 24;
+
+/// The per-request value cached by [`Nonce::of()`].
+struct RequestNonce(String);
+
+/// Request guard for the request's Content Security Policy nonce.
+///
+/// A nonce is a random, single-use token that permits inline `<script>` and
+/// `<style>` elements carrying a matching `nonce` attribute under a [CSP]
+/// policy that otherwise forbids inline content. Every request has exactly one
+/// nonce, generated the first time it is requested and shared by all
+/// subsequent uses, so the guard, templates, and the CSP header of a given
+/// response always agree.
+///
+/// When the `helmet` feature of `rocket_contrib` is enabled, its `Csp` policy
+/// adds the nonce to the `Content-Security-Policy` header automatically, and
+/// templates can access it as `csp_nonce`. As a request guard, `Nonce` never
+/// fails.
+///
+/// The nonce consists of alphanumeric characters only and is thus safe to
+/// embed in HTML attributes and headers without escaping.
+///
+/// [CSP]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::Nonce;
+/// use rocket::response::content::Html;
+///
+/// #[get("/")]
+/// fn index(nonce: Nonce<'_>) -> Html<String> {
+///     Html(format!(r#"<script nonce="{}">alert("hi")</script>"#, nonce))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nonce<'r>(&'r str);
+
+impl<'r> Nonce<'r> {
+    /// Returns the nonce for `request`, generating it if this is its first
+    /// use.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Nonce;
+    ///
+    /// # let client = rocket::local::blocking::Client::tracked(rocket::ignite()).unwrap();
+    /// # let request = client.get("/");
+    /// # let request = request.inner();
+    /// let nonce = Nonce::of(request);
+    /// assert_eq!(nonce, Nonce::of(request));
+    /// assert!(nonce.as_str().chars().all(|c| c.is_ascii_alphanumeric()));
+    /// ```
+    pub fn of(request: &'r Request<'_>) -> Nonce<'r> {
+        let nonce = request.local_cache(|| {
+            let nonce = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(NONCE_LEN)
+                .map(char::from)
+                .collect();
+
+            RequestNonce(nonce)
+        });
+
+        Nonce(&nonce.0)
+    }
+
+    /// Returns the nonce as a string.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+impl fmt::Display for Nonce<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Nonce<'a> {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(Nonce::of(request))
+    }
+}
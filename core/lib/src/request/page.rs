@@ -0,0 +1,313 @@
+use std::fmt;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+use crate::http::{Header, Status, uri::{Origin, Uri}};
+
+/// Limits applied by the [`Page`] request guard.
+///
+/// To change the limits from their defaults, place a `PageLimits` in managed
+/// state:
+///
+/// ```rust
+/// use rocket::request::PageLimits;
+///
+/// # let _ = {
+/// rocket::ignite().manage(PageLimits { default_per_page: 50, max_per_page: 500 })
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimits {
+    /// The page size when none is requested. **(default: `20`)**
+    pub default_per_page: u64,
+    /// The largest page size that may be requested. **(default: `100`)**
+    pub max_per_page: u64,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        PageLimits { default_per_page: 20, max_per_page: 100 }
+    }
+}
+
+/// Request guard for pagination query parameters.
+///
+/// `Page` parses the following query parameters, all of which are optional:
+///
+///   * `page`: the 1-based page number
+///   * `per_page` or its alias `limit`: the number of items per page
+///   * `offset`: the 0-based index of the first item
+///   * `cursor`: an opaque cursor, for cursor-based pagination
+///
+/// At most one of `page`, `offset`, and `cursor` may be present. When `page`
+/// is present, the offset is computed from it. The page size defaults to and
+/// is limited by the [`PageLimits`] in managed state, or
+/// [`PageLimits::default()`] if there are none.
+///
+/// If any parameter is invalid, the guard fails with a status of
+/// `400 Bad Request` and a [`PageError`] describing the problem.
+///
+/// # Link Headers
+///
+/// [`Page::links()`] and [`Page::next_cursor_link()`] generate [`Link`]
+/// headers for the adjacent pages, preserving any other query parameters in
+/// the request URI.
+///
+/// [`Link`]: https://tools.ietf.org/html/rfc8288
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::request::Page;
+///
+/// #[derive(Responder)]
+/// struct Listing {
+///     items: String,
+///     link: rocket::http::Header<'static>,
+/// }
+///
+/// #[get("/items")]
+/// fn items(page: Page, request: &Request<'_>) -> Listing {
+///     let total = 1000;
+///     let items = format!("items {} to {}", page.offset(), page.offset() + page.per_page());
+///     Listing { items, link: page.links(request.uri(), Some(total)) }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    per_page: u64,
+    offset: u64,
+    cursor: Option<String>,
+    by_offset: bool,
+    size_key: &'static str,
+}
+
+/// The error returned by the [`Page`] request guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageError {
+    /// The named parameter's value is not a valid number.
+    Invalid(&'static str, String),
+    /// The named parameter's value is `0`, which is not allowed.
+    Zero(&'static str),
+    /// The requested page size is larger than the maximum.
+    TooLarge {
+        /// The requested page size.
+        requested: u64,
+        /// The maximum page size.
+        max: u64,
+    },
+    /// Two mutually exclusive parameters were both present.
+    Conflict(&'static str, &'static str),
+}
+
+const KEYS: &[&str] = &["page", "per_page", "limit", "offset", "cursor"];
+
+impl Page {
+    /// Returns the 1-based page number.
+    ///
+    /// When the request specified an offset that is not a multiple of the
+    /// page size, this is the page containing the first item.
+    pub fn page(&self) -> u64 {
+        self.offset / self.per_page + 1
+    }
+
+    /// Returns the number of items per page.
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+    }
+
+    /// Returns the 0-based index of the first item on the page.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the cursor, if one was requested.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Returns a `Link` header with `first`, `prev`, `next`, and `last`
+    /// relations to the pages adjacent to `self`, with the same page size.
+    ///
+    /// `uri` is the URI of the paginated resource, typically the request's
+    /// URI. Its pagination parameters are replaced; all others are kept. If
+    /// the request used `offset`, links use `offset` as well, otherwise they
+    /// use `page`. `prev` is omitted on the first page. When `total`, the
+    /// total number of items, is known, `last` is included and `next` is
+    /// omitted on the last page.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::request::Page;
+    ///
+    /// # Request::example(Method::Get, "/items?page=2&per_page=10&sort=name", |req| {
+    /// # let page = rocket::async_test(req.guard::<Page>()).unwrap();
+    /// let link = page.links(req.uri(), Some(35));
+    /// assert_eq!(link.value(), "</items?sort=name&page=1&per_page=10>; rel=\"first\", \
+    ///     </items?sort=name&page=1&per_page=10>; rel=\"prev\", \
+    ///     </items?sort=name&page=3&per_page=10>; rel=\"next\", \
+    ///     </items?sort=name&page=4&per_page=10>; rel=\"last\"");
+    /// # });
+    /// ```
+    pub fn links(&self, uri: &Origin<'_>, total: Option<u64>) -> Header<'static> {
+        let mut links = vec![(0, "first")];
+        if self.offset > 0 {
+            links.push((self.offset.saturating_sub(self.per_page), "prev"));
+        }
+
+        let next = self.offset.saturating_add(self.per_page);
+        if total.map_or(true, |total| next < total) {
+            links.push((next, "next"));
+        }
+
+        if let Some(total) = total {
+            let last = total.saturating_sub(1) / self.per_page * self.per_page;
+            links.push((last, "last"));
+        }
+
+        let value = links.into_iter()
+            .map(|(offset, rel)| {
+                let param = match self.by_offset {
+                    true => format!("offset={}", offset),
+                    false => format!("page={}", offset / self.per_page + 1),
+                };
+
+                format!("<{}>; rel=\"{}\"", self.uri_with(uri, &param), rel)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Header::new("Link", value)
+    }
+
+    /// Returns a `Link` header with a `next` relation to the page after
+    /// `self` in cursor-based pagination, where `cursor` identifies the next
+    /// page. See [`Page::links()`] for the meaning of `uri`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::request::Page;
+    ///
+    /// # Request::example(Method::Get, "/items?cursor=abc&limit=5", |req| {
+    /// # let page = rocket::async_test(req.guard::<Page>()).unwrap();
+    /// assert_eq!(page.cursor(), Some("abc"));
+    /// let link = page.next_cursor_link(req.uri(), "d e");
+    /// assert_eq!(link.value(), "</items?cursor=d%20e&limit=5>; rel=\"next\"");
+    /// # });
+    /// ```
+    pub fn next_cursor_link(&self, uri: &Origin<'_>, cursor: &str) -> Header<'static> {
+        let param = format!("cursor={}", Uri::percent_encode(cursor));
+        Header::new("Link", format!("<{}>; rel=\"next\"", self.uri_with(uri, &param)))
+    }
+
+    /// Returns `uri` with its pagination parameters replaced by `param` and
+    /// the page size.
+    fn uri_with(&self, uri: &Origin<'_>, param: &str) -> String {
+        let mut query: Vec<&str> = uri.query()
+            .map(|query| query.split('&')
+                .filter(|item| !item.is_empty())
+                .filter(|item| !KEYS.contains(&item.split('=').next().unwrap_or("")))
+                .collect())
+            .unwrap_or_default();
+
+        let size = format!("{}={}", self.size_key, self.per_page);
+        query.push(param);
+        query.push(&size);
+        format!("{}?{}", uri.path(), query.join("&"))
+    }
+}
+
+fn number(name: &'static str, value: Option<String>) -> Result<Option<u64>, PageError> {
+    match value {
+        Some(v) => v.parse().map(Some).map_err(|_| PageError::Invalid(name, v)),
+        None => Ok(None),
+    }
+}
+
+impl Page {
+    fn parse(request: &Request<'_>, limits: &PageLimits) -> Result<Page, PageError> {
+        let (mut page, mut per_page, mut limit, mut offset, mut cursor) = Default::default();
+        for item in request.raw_query_items().into_iter().flatten() {
+            let (key, value) = item.key_value_decoded();
+            match key.as_str() {
+                "page" => page = Some(value),
+                "per_page" => per_page = Some(value),
+                "limit" => limit = Some(value),
+                "offset" => offset = Some(value),
+                "cursor" => cursor = Some(value),
+                _ => continue,
+            }
+        }
+
+        let size_key = match (&per_page, &limit) {
+            (Some(_), Some(_)) => return Err(PageError::Conflict("per_page", "limit")),
+            (None, Some(_)) => "limit",
+            _ => "per_page",
+        };
+
+        let per_page = match number(size_key, per_page.or(limit))? {
+            Some(0) => return Err(PageError::Zero(size_key)),
+            Some(n) if n > limits.max_per_page => {
+                return Err(PageError::TooLarge { requested: n, max: limits.max_per_page });
+            }
+            Some(n) => n,
+            None => limits.default_per_page.max(1),
+        };
+
+        match (&page, &offset, &cursor) {
+            (Some(_), Some(_), _) => return Err(PageError::Conflict("page", "offset")),
+            (Some(_), _, Some(_)) => return Err(PageError::Conflict("page", "cursor")),
+            (_, Some(_), Some(_)) => return Err(PageError::Conflict("offset", "cursor")),
+            _ => {}
+        }
+
+        let by_offset = offset.is_some();
+        let offset = match (number("page", page)?, number("offset", offset)?) {
+            (Some(0), _) => return Err(PageError::Zero("page")),
+            (Some(page), _) => (page - 1).checked_mul(per_page)
+                .ok_or_else(|| PageError::Invalid("page", page.to_string()))?,
+            (None, Some(offset)) => offset,
+            (None, None) => 0,
+        };
+
+        Ok(Page { per_page, offset, cursor, by_offset, size_key })
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Page {
+    type Error = PageError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let default = PageLimits::default();
+        let limits = request.managed_state::<PageLimits>().unwrap_or(&default);
+        match Page::parse(request, limits) {
+            Ok(page) => Outcome::Success(page),
+            Err(e) => Outcome::Failure((Status::BadRequest, e)),
+        }
+    }
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::Invalid(name, value) => write!(f, "invalid `{}`: {:?}", name, value),
+            PageError::Zero(name) => write!(f, "`{}` must be greater than 0", name),
+            PageError::TooLarge { requested, max } => {
+                write!(f, "page size {} exceeds the maximum of {}", requested, max)
+            }
+            PageError::Conflict(a, b) => write!(f, "`{}` and `{}` cannot both be used", a, b),
+        }
+    }
+}
+
+impl std::error::Error for PageError {}
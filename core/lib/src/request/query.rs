@@ -1,4 +1,4 @@
-use crate::request::{FormItems, FormItem, Form, LenientForm, FromForm};
+use crate::request::{FormItems, FormItem, Form, LenientForm, FromForm, Duplicates};
 
 /// Iterator over form items in a query string.
 ///
@@ -49,7 +49,19 @@ use crate::request::{FormItems, FormItem, Form, LenientForm, FromForm};
 /// # }
 /// ```
 #[derive(Debug, Clone)]
-pub struct Query<'q>(#[doc(hidden)] pub &'q [FormItem<'q>]);
+pub struct Query<'q>(
+    #[doc(hidden)] pub &'q [FormItem<'q>],
+    #[doc(hidden)] pub Duplicates,
+);
+
+impl Query<'_> {
+    /// Returns the [`Duplicates`] policy configured for the application,
+    /// which `FromQuery` implementations should apply to repeated fields.
+    #[inline(always)]
+    pub fn duplicates(&self) -> Duplicates {
+        self.1
+    }
+}
 
 impl<'q> Iterator for Query<'q> {
     type Item = FormItem<'q>;
@@ -203,7 +215,7 @@ impl<'q, T: FromForm<'q>> FromQuery<'q> for Form<T> {
 
     #[inline]
     fn from_query(q: Query<'q>) -> Result<Self, Self::Error> {
-        T::from_form(&mut FormItems::from(q.0), true).map(Form)
+        T::from_form(&mut FormItems::from(q.0).with_duplicates(q.1), true).map(Form)
     }
 }
 
@@ -212,7 +224,7 @@ impl<'q, T: FromForm<'q>> FromQuery<'q> for LenientForm<T> {
 
     #[inline]
     fn from_query(q: Query<'q>) -> Result<Self, Self::Error> {
-        T::from_form(&mut FormItems::from(q.0), false).map(LenientForm)
+        T::from_form(&mut FormItems::from(q.0).with_duplicates(q.1), false).map(LenientForm)
     }
 }
 
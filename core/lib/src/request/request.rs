@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 use std::net::{IpAddr, SocketAddr};
 use std::future::Future;
 use std::fmt;
@@ -10,11 +11,13 @@ use futures::future::BoxFuture;
 use atomic::{Atomic, Ordering};
 
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
-use crate::request::{FromFormValue, FormItems, FormItem};
+use crate::request::{FromFormValue, FormItems, FormItem, Duplicates};
+use crate::request::disconnect::Connection;
+use crate::request::defer::Deferred;
 
-use crate::{Rocket, Config, Shutdown, Route};
-use crate::http::{hyper, uri::{Origin, Segments}};
-use crate::http::{Method, Header, HeaderMap, uncased::UncasedStr};
+use crate::{Rocket, Config, Shutdown, Route, Clock};
+use crate::http::{hyper, uri::{Origin, Segments, Absolute, Authority, UriBase}};
+use crate::http::{Method, Header, HeaderMap, uncased::{self, UncasedStr}};
 use crate::http::{RawStr, ContentType, Accept, MediaType, CookieJar, Cookie};
 use crate::http::private::{Indexed, SmallVec};
 use crate::data::Limits;
@@ -34,6 +37,7 @@ pub struct Request<'r> {
 }
 
 pub(crate) struct RequestState<'r> {
+    pub rocket: &'r Rocket,
     pub config: &'r Config,
     pub managed: &'r Container,
     pub shutdown: &'r Shutdown,
@@ -44,6 +48,9 @@ pub(crate) struct RequestState<'r> {
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
     pub cache: Arc<Container>,
+    pub deadline: Storage<Instant>,
+    pub connection: Arc<Connection>,
+    pub deferred: Arc<Deferred>,
 }
 
 impl Request<'_> {
@@ -61,6 +68,7 @@ impl Request<'_> {
 impl RequestState<'_> {
     fn clone(&self) -> Self {
         RequestState {
+            rocket: self.rocket,
             config: self.config,
             managed: self.managed,
             shutdown: self.shutdown,
@@ -71,6 +79,9 @@ impl RequestState<'_> {
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
             cache: self.cache.clone(),
+            deadline: self.deadline.clone(),
+            connection: self.connection.clone(),
+            deferred: self.deferred.clone(),
         }
     }
 }
@@ -91,6 +102,7 @@ impl<'r> Request<'r> {
             state: RequestState {
                 path_segments: SmallVec::new(),
                 query_items: None,
+                rocket,
                 config: &rocket.config,
                 managed: &rocket.managed_state,
                 shutdown: &rocket.shutdown_handle,
@@ -99,13 +111,71 @@ impl<'r> Request<'r> {
                 accept: Storage::new(),
                 content_type: Storage::new(),
                 cache: Arc::new(Container::new()),
+                deadline: Storage::new(),
+                connection: Arc::new(Connection::default()),
+                deferred: Arc::new(Deferred::default()),
             }
         };
 
+        request.state.cookies.set_policy(rocket.config.cookies);
         request.update_cached_uri_info();
         request
     }
 
+    /// Creates a new `Request` for `rocket` from raw parts: a `method` string,
+    /// `uri` bytes, and `headers` as pairs of names and raw values.
+    ///
+    /// This method performs the same validation as Rocket's server does on
+    /// incoming requests and never panics: an error is returned if `method` is
+    /// not a known method or `uri` is not a valid origin URI. `Cookie` headers
+    /// populate the request's cookie jar, and header values needn't be UTF-8:
+    /// see [`Header::from_bytes()`].
+    ///
+    /// The `rocket` instance needn't be launched, making this method suitable
+    /// for fuzz targets and property tests that exercise URI parsing, form
+    /// parsing, and, via [`Rocket::matching_routes()`], routing directly. A
+    /// request body can be constructed with
+    /// [`Data::from_bytes()`](crate::Data::from_bytes()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    /// use rocket::http::Method;
+    ///
+    /// let rocket = rocket::ignite();
+    /// let headers = vec![("Content-Type", &b"text/plain"[..]), ("Cookie", &b"a=b"[..])];
+    /// let request = Request::from_raw_parts(&rocket, "POST", b"/a?b=c", headers).unwrap();
+    /// assert_eq!(request.method(), Method::Post);
+    /// assert_eq!(request.uri().path(), "/a");
+    /// assert_eq!(request.content_type(), Some(&rocket::http::ContentType::Plain));
+    /// assert_eq!(request.cookies().get("a").map(|c| c.value()), Some("b"));
+    ///
+    /// let none: [(&str, &[u8]); 0] = [];
+    /// assert!(Request::from_raw_parts(&rocket, "BREW", b"/", none).is_err());
+    /// assert!(Request::from_raw_parts(&rocket, "GET", b"a b", none).is_err());
+    /// assert!(Request::from_raw_parts(&rocket, "GET", b"/\xff", none).is_err());
+    /// ```
+    pub fn from_raw_parts<'s: 'r, H, N, V>(
+        rocket: &'r Rocket,
+        method: &str,
+        uri: &'s [u8],
+        headers: H,
+    ) -> Result<Request<'r>, String>
+        where H: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<[u8]>
+    {
+        let method = method.parse::<Method>()
+            .map_err(|_| format!("Unknown or invalid method: {}", method))?;
+
+        let uri = str::from_utf8(uri).map_err(|e| format!("Bad URI: {}", e))?;
+        let uri = Request::parse_uri(rocket, uri)?;
+
+        let headers: Vec<(N, V)> = headers.into_iter().collect();
+        let mut request = Request::new(rocket, method, uri);
+        request.add_raw_headers(headers.iter().map(|(k, v)| (k.as_ref(), v.as_ref())));
+        Ok(request)
+    }
+
     /// Retrieve the method from `self`.
     ///
     /// # Example
@@ -289,6 +359,111 @@ impl<'r> Request<'r> {
         self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
     }
 
+    /// Returns the client's IP address, honoring the "X-Real-IP" header only
+    /// if the request was received from one of the configured
+    /// [`trusted_proxies`](Config::trusted_proxies).
+    ///
+    /// Unlike [`Request::client_ip()`], which trusts the header from any peer,
+    /// this method is suitable for access control and rate limiting: a client
+    /// connecting directly cannot spoof its address. If the remote address is
+    /// a trusted proxy that didn't send a valid "X-Real-IP" header, or if the
+    /// remote address is not a trusted proxy, the remote address is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{Header, Method};
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.set_remote("10.0.0.1:8000".parse().unwrap());
+    /// request.add_header(Header::new("X-Real-IP", "8.8.8.8"));
+    ///
+    /// // `10.0.0.1` is not a trusted proxy, so the header is ignored
+    /// assert_eq!(request.trusted_client_ip(), Some("10.0.0.1".parse().unwrap()));
+    /// # });
+    /// ```
+    pub fn trusted_client_ip(&self) -> Option<IpAddr> {
+        let remote = self.remote()?.ip();
+        match self.state.config.trusted_proxies.contains(&remote) {
+            true => Some(self.real_ip().unwrap_or(remote)),
+            false => Some(remote),
+        }
+    }
+
+    /// Returns the externally visible base URL of the server as seen by the
+    /// client: `scheme://host[:port]`.
+    ///
+    /// The scheme is `https` if TLS is enabled and `http` otherwise, and the
+    /// host is taken from the `Host` header, falling back to the configured
+    /// address and port if the header is missing or invalid. If the request
+    /// was received from one of the configured
+    /// [`trusted_proxies`](Config::trusted_proxies), the `X-Forwarded-Proto`,
+    /// `X-Forwarded-Host`, and `X-Forwarded-Port` headers, when present and
+    /// valid, take precedence. Forwarded headers from any other peer are
+    /// ignored, as they are trivially spoofed.
+    ///
+    /// This is the base used for absolute URIs generated with
+    /// `uri!(base = request, ...)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{Header, Method};
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// // without a `Host` header, the configured address is used
+    /// assert_eq!(request.base_url().to_string(), "http://127.0.0.1:8000");
+    ///
+    /// request.add_header(Header::new("Host", "rocket.rs"));
+    /// assert_eq!(request.base_url().to_string(), "http://rocket.rs");
+    ///
+    /// // the remote is not a trusted proxy, so this header is ignored
+    /// request.add_header(Header::new("X-Forwarded-Proto", "https"));
+    /// assert_eq!(request.base_url().to_string(), "http://rocket.rs");
+    /// # });
+    /// ```
+    pub fn base_url(&self) -> Absolute<'static> {
+        let config = self.state.config;
+        let trusted = self.remote()
+            .map_or(false, |remote| config.trusted_proxies.contains(&remote.ip()));
+
+        // Proxies may append to these headers; the first value is the client's.
+        let forwarded = |name: &'static str| match trusted {
+            true => self.headers().get_one(name).and_then(|v| v.split(',').next()).map(str::trim),
+            false => None,
+        };
+
+        let scheme = match forwarded("X-Forwarded-Proto") {
+            Some(proto) if proto.eq_ignore_ascii_case("https") => "https",
+            Some(proto) if proto.eq_ignore_ascii_case("http") => "http",
+            _ if config.tls_enabled() => "https",
+            _ => "http",
+        };
+
+        let authority = forwarded("X-Forwarded-Host")
+            .or_else(|| self.headers().get_one("Host"))
+            .and_then(|host| Authority::parse(host).ok())
+            .filter(|authority| authority.user_info().is_none());
+
+        let host = match authority {
+            Some(authority) if authority.port().is_some() => authority.to_string(),
+            Some(authority) => match forwarded("X-Forwarded-Port").map(|p| p.parse::<u16>()) {
+                Some(Ok(80)) if scheme == "http" => authority.to_string(),
+                Some(Ok(443)) if scheme == "https" => authority.to_string(),
+                Some(Ok(port)) => format!("{}:{}", authority, port),
+                _ => authority.to_string(),
+            },
+            None => match config.address {
+                IpAddr::V4(ip) => format!("{}:{}", ip, config.port),
+                IpAddr::V6(ip) => format!("[{}]:{}", ip, config.port),
+            }
+        };
+
+        Absolute::parse_owned(format!("{}://{}", scheme, host))
+            .expect("scheme and authority form a valid URI")
+    }
+
     /// Returns a wrapped borrow to the cookies in `self`.
     ///
     /// [`CookieJar`] implements internal mutability, so this method allows you
@@ -485,6 +660,51 @@ impl<'r> Request<'r> {
         &self.state.config.limits
     }
 
+    /// Returns the [`Duplicates`] policy configured via `form_duplicates`,
+    /// which determines how fields that appear more than once in forms and
+    /// queries are treated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::Method;
+    /// # use rocket::Request;
+    /// use rocket::request::Duplicates;
+    ///
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert_eq!(request.form_duplicates(), Duplicates::Last);
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn form_duplicates(&self) -> Duplicates {
+        self.state.config.form_duplicates
+    }
+
+    /// Returns `true` if the client that sent this request is known to have
+    /// disconnected before a response was sent.
+    ///
+    /// When a client disconnects, Rocket cancels the request's handler at its
+    /// next `.await` point. Handlers that perform long-running work without
+    /// yielding, for instance in a blocking task, can check this method to
+    /// stop early; asynchronous handlers can instead await a
+    /// [`Disconnect`](crate::request::Disconnect). Disconnects are detected via
+    /// the underlying HTTP connection; locally dispatched requests are never
+    /// disconnected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// assert!(!request.is_client_disconnected());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn is_client_disconnected(&self) -> bool {
+        self.state.connection.is_disconnected()
+    }
+
     /// Get the presently matched route, if any.
     ///
     /// This method returns `Some` any time a handler or its guards are being
@@ -537,6 +757,23 @@ impl<'r> Request<'r> {
         T::from_request(self)
     }
 
+    /// Returns the application's [`Clock`]: the clock in managed state, if
+    /// there is one, and otherwise the system clock.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::Method;
+    /// # use rocket::Request;
+    /// # Request::example(Method::Get, "/uri", |request| {
+    /// let now = request.clock().now_utc();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn clock(&self) -> &'r Clock {
+        Clock::from_state(self.state.managed)
+    }
+
     #[inline(always)]
     pub fn managed_state<T>(&self) -> Option<&'r T>
         where T: Send + Sync + 'static
@@ -607,6 +844,41 @@ impl<'r> Request<'r> {
         }
     }
 
+    /// Defers `work` until after the response to this request has been sent.
+    ///
+    /// Deferred work runs in new tasks once the response has been written, or
+    /// has failed to be written, whether the request succeeded or failed. It
+    /// is suited to work the client needn't wait for, such as analytics,
+    /// webhooks, and cleanup. Unlike tasks spawned directly, deferred work is
+    /// tracked: when the server shuts down, it waits for deferred work to
+    /// finish, up to the [`shutdown_grace`](crate::Config::shutdown_grace)
+    /// period. Long-running work can select on a
+    /// [`ShutdownSignal`](crate::ShutdownSignal) to finish early.
+    ///
+    /// Handlers defer work via the [`Defer`](crate::request::Defer) request
+    /// guard.
+    ///
+    /// # Example
+    ///
+    /// A fairing that records every visit after responding:
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// async fn record_visit(path: String) {
+    ///     /* send `path` to an analytics service */
+    /// }
+    ///
+    /// let fairing = AdHoc::on_request("Analytics", |req, _| Box::pin(async move {
+    ///     req.defer(record_visit(req.uri().path().to_string()));
+    /// }));
+    /// ```
+    pub fn defer<F>(&self, work: F)
+        where F: Future<Output = ()> + Send + 'static
+    {
+        self.state.deferred.push(Box::pin(work));
+    }
+
     /// Retrieves and parses into `T` the 0-indexed `n`th segment from the
     /// request. Returns `None` if `n` is greater than the number of segments.
     /// Returns `Some(Err(T::Error))` if the parameter type `T` failed to be
@@ -692,15 +964,17 @@ impl<'r> Request<'r> {
     /// Retrieves and parses into `T` the query value with key `key`. `T` must
     /// implement [`FromFormValue`], which is used to parse the query's value.
     /// Key matching is performed case-sensitively. If there are multiple pairs
-    /// with key `key`, the _last_ one is returned.
+    /// with key `key`, the configured [`Duplicates`] policy applies: by
+    /// default, the _last_ one is returned.
     ///
     /// This method exists only to be used by manual routing. To retrieve
     /// query values from a request, use Rocket's code generation facilities.
     ///
     /// # Error
     ///
-    /// If a query segment with key `key` isn't present, returns `None`. If
-    /// parsing the value fails, returns `Some(Err(T:Error))`.
+    /// If a query segment with key `key` isn't present, or if there are
+    /// multiple and duplicates are rejected, returns `None`. If parsing the
+    /// value fails, returns `Some(Err(T:Error))`.
     ///
     /// # Example
     ///
@@ -730,10 +1004,17 @@ impl<'r> Request<'r> {
     pub fn get_query_value<'a, T>(&'a self, key: &str) -> Option<Result<T, T::Error>>
         where T: FromFormValue<'a>
     {
-        self.raw_query_items()?
-            .rev()
-            .find(|item| item.key.as_str() == key)
-            .map(|item| T::from_form_value(item.value))
+        let mut items = self.raw_query_items()?.filter(|item| item.key.as_str() == key);
+        let item = match self.form_duplicates() {
+            Duplicates::First => items.next(),
+            Duplicates::Last => items.next_back(),
+            Duplicates::Reject => match (items.next(), items.next()) {
+                (Some(item), None) => Some(item),
+                _ => None,
+            }
+        };
+
+        item.map(|item| T::from_form_value(item.value))
     }
 }
 
@@ -864,35 +1145,57 @@ impl<'r> Request<'r> {
         };
 
         // We need to re-parse the URI since we don't trust Hyper... :(
-        let uri = Origin::parse(uri).map_err(|e| e.to_string())?;
+        let uri = Request::parse_uri(rocket, uri)?;
 
         // Construct the request object.
         let mut request = Request::new(rocket, method, uri);
         request.set_remote(h_addr);
+        request.add_raw_headers(h_headers.iter().map(|(k, v)| (k.as_str(), v.as_bytes())));
+        Ok(request)
+    }
 
+    // Parses the URI of an incoming request, leniently if so configured.
+    pub(crate) fn parse_uri<'s>(rocket: &Rocket, uri: &'s str) -> Result<Origin<'s>, String> {
+        let result = match rocket.config.lenient_uris {
+            true => Origin::parse_lenient(uri),
+            false => Origin::parse(uri),
+        };
+
+        result.map_err(|e| e.to_string())
+    }
+
+    // Adds the cookies in `Cookie` headers to the cookie jar and then all of
+    // the headers to the header map.
+    pub(crate) fn add_raw_headers<'h, I>(&mut self, headers: I)
+        where I: Iterator<Item = (&'h str, &'h [u8])> + Clone
+    {
         // Set the request cookies, if they exist.
-        for header in h_headers.get_all("Cookie") {
-            let raw_str = match std::str::from_utf8(header.as_bytes()) {
+        let cookie_headers = headers.clone().filter(|(k, _)| uncased::eq(*k, "Cookie"));
+        for (_, value) in cookie_headers {
+            let raw_str = match std::str::from_utf8(value) {
                 Ok(string) => string,
                 Err(_) => continue
             };
 
             for cookie_str in raw_str.split(';').map(|s| s.trim()) {
                 if let Ok(cookie) = Cookie::parse_encoded(cookie_str) {
-                    request.state.cookies.add_original(cookie.into_owned());
+                    self.state.cookies.add_original(cookie.into_owned());
                 }
             }
         }
 
-        // Set the rest of the headers.
-        for (name, value) in h_headers.iter() {
-            // This is not totally correct since values needn't be UTF8.
-            let value_str = String::from_utf8_lossy(value.as_bytes()).into_owned();
-            let header = Header::new(name.to_string(), value_str);
-            request.add_header(header);
+        // Set the rest of the headers, preserving values that aren't UTF-8.
+        for (name, value) in headers {
+            self.add_header(Header::from_bytes(name.to_string(), value.to_vec()));
         }
+    }
+}
 
-        Ok(request)
+/// The base of URIs generated with `uri!(base = request, ...)`: the
+/// request's [`base_url()`](Request::base_url()).
+impl UriBase for Request<'_> {
+    fn uri_base(&self) -> Absolute<'_> {
+        self.base_url()
     }
 }
 
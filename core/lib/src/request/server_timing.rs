@@ -0,0 +1,132 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// A metric in a `Server-Timing` header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// The name of the metric.
+    pub name: String,
+    /// The duration measured, if any.
+    pub duration: Option<Duration>,
+    /// A description of the metric, if any.
+    pub description: Option<String>,
+}
+
+/// A request-local collector of `Server-Timing` metrics.
+///
+/// Handlers, guards, and fairings append metrics to the request's collector,
+/// retrieved either with the `&ServerTiming` request guard, which never fails,
+/// or with [`ServerTiming::of()`]. If any metrics were added, they are emitted
+/// in a `Server-Timing` header on the response once response fairings have
+/// run, in the order they were added, following the timings recorded by the
+/// `header` [timings mode](crate::request::TimingsMode::Header), if it is on.
+///
+/// Metric names must be HTTP tokens; any other characters are replaced by `_`.
+/// Control characters, such as CR, LF, and NUL, in descriptions are replaced
+/// by spaces, and `"` and `\` are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::{Duration, Instant};
+/// use rocket::request::ServerTiming;
+///
+/// #[get("/")]
+/// fn index(st: &ServerTiming) -> &'static str {
+///     let start = Instant::now();
+///     // query the database...
+///     st.add("db", start.elapsed(), "query");
+///     st.add_metric("cache", None, Some("miss"));
+///     "done"
+/// }
+/// ```
+///
+/// A response to `/` then carries a header like:
+///
+/// ```text
+/// Server-Timing: db;desc="query";dur=12.300, cache;desc="miss"
+/// ```
+#[derive(Debug, Default)]
+pub struct ServerTiming(Mutex<Vec<Metric>>);
+
+impl ServerTiming {
+    /// Returns the collector of `req`.
+    pub fn of<'r>(req: &'r Request<'_>) -> &'r ServerTiming {
+        req.local_cache(ServerTiming::default)
+    }
+
+    /// Adds a metric named `name` that took `duration`, described by
+    /// `description`.
+    pub fn add<N, D>(&self, name: N, duration: Duration, description: D)
+        where N: Into<String>, D: Into<String>
+    {
+        self.add_metric(name, Some(duration), Some(description));
+    }
+
+    /// Adds a metric named `name` with an optional `duration` and
+    /// `description`.
+    pub fn add_metric<N, D>(&self, name: N, duration: Option<Duration>, description: Option<D>)
+        where N: Into<String>, D: Into<String>
+    {
+        let description = description.map(Into::into);
+        let metric = Metric { name: name.into(), duration, description };
+        self.0.lock().expect("server timing lock").push(metric);
+    }
+
+    /// Returns the metrics added so far, in the order they were added.
+    pub fn metrics(&self) -> Vec<Metric> {
+        self.0.lock().expect("server timing lock").clone()
+    }
+
+    /// Returns the value of the `Server-Timing` header for the metrics, or
+    /// `None` if there are none.
+    pub(crate) fn header_value(&self) -> Option<String> {
+        let metrics = self.0.lock().expect("server timing lock");
+        if metrics.is_empty() {
+            return None;
+        }
+
+        let metrics: Vec<_> = metrics.iter()
+            .map(|metric| {
+                let name: String = metric.name.chars()
+                    .map(|c| if is_token_char(c) { c } else { '_' })
+                    .collect();
+
+                let mut value = if name.is_empty() { "_".into() } else { name };
+                if let Some(ref desc) = metric.description {
+                    let desc: String = desc.chars()
+                        .map(|c| if c.is_control() { ' ' } else { c })
+                        .collect();
+
+                    let desc = desc.replace('\\', "\\\\").replace('"', "\\\"");
+                    value.push_str(&format!(";desc=\"{}\"", desc));
+                }
+
+                if let Some(duration) = metric.duration {
+                    value.push_str(&format!(";dur={:.3}", duration.as_secs_f64() * 1000.0));
+                }
+
+                value
+            })
+            .collect();
+
+        Some(metrics.join(", "))
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a ServerTiming {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(ServerTiming::of(req))
+    }
+}
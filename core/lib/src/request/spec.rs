@@ -0,0 +1,310 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::request::{self, FromRequest, FromFormValue, Request};
+use crate::outcome::Outcome;
+use crate::http::{Status, RawStr};
+
+/// The direction of a [`SortKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Ascending order, requested as `field` or `+field`.
+    Asc,
+    /// Descending order, requested as `-field`.
+    Desc,
+}
+
+/// A single field to sort by, and the direction to sort in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey<T> {
+    /// The field to sort by.
+    pub field: T,
+    /// The direction to sort in.
+    pub order: Order,
+}
+
+/// Request guard for the `sort` query parameter.
+///
+/// The parameter is a comma-separated list of fields, each optionally
+/// prefixed with `-` for descending order or `+` for ascending order, the
+/// default: `?sort=-created_at,name`. Fields are parsed into `T` via its
+/// [`FromFormValue`] implementation, which acts as the allow-list of sortable
+/// fields; deriving `FromFormValue` for an enum of fields is the easiest way to
+/// define one. If there is no `sort` parameter, the spec is empty.
+///
+/// If a field is not allowed, is empty, or is repeated, the guard fails with a
+/// status of `400 Bad Request` and a [`SpecError`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{SortSpec, Order};
+///
+/// #[derive(FromFormValue, PartialEq)]
+/// enum Field {
+///     #[form(value = "created_at")]
+///     CreatedAt,
+///     Name,
+/// }
+///
+/// #[get("/users")]
+/// fn users(sort: SortSpec<Field>) -> String {
+///     sort.iter()
+///         .map(|key| match (&key.field, key.order) {
+///             (Field::CreatedAt, Order::Desc) => "created_at DESC",
+///             (Field::CreatedAt, Order::Asc) => "created_at ASC",
+///             (Field::Name, Order::Desc) => "name DESC",
+///             (Field::Name, Order::Asc) => "name ASC",
+///         })
+///         .collect::<Vec<_>>()
+///         .join(", ")
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortSpec<T> {
+    keys: Vec<SortKey<T>>,
+}
+
+/// A filter operator in a [`FilterSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOp {
+    /// `eq`, the default: the field equals the value.
+    Eq,
+    /// `ne`: the field does not equal the value.
+    Ne,
+    /// `lt`: the field is less than the value.
+    Lt,
+    /// `lte`: the field is less than or equal to the value.
+    Lte,
+    /// `gt`: the field is greater than the value.
+    Gt,
+    /// `gte`: the field is greater than or equal to the value.
+    Gte,
+    /// `in`: the field is one of the comma-separated values.
+    In,
+    /// `contains`: the field contains the value.
+    Contains,
+}
+
+/// A single condition in a [`FilterSpec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter<T> {
+    /// The field to filter on.
+    pub field: T,
+    /// The comparison to apply.
+    pub op: FilterOp,
+    /// The percent-decoded value to compare against.
+    pub value: String,
+}
+
+/// Request guard for `filter[field]` and `filter[field][op]` query parameters.
+///
+/// Each query parameter of the form `filter[field][op]=value` becomes a
+/// [`Filter`]. When `[op]` is omitted, the operator is [`FilterOp::Eq`].
+/// Fields are parsed into `T` via its [`FromFormValue`] implementation, which
+/// acts as the allow-list of filterable fields, just as for [`SortSpec`].
+/// Operators are parsed via [`FilterOp`]'s `FromStr` implementation. Values
+/// are left as strings for the query layer to interpret. Parameters that don't
+/// begin with `filter[` are ignored.
+///
+/// If a field or operator is not allowed, or a parameter is malformed, the
+/// guard fails with a status of `400 Bad Request` and a [`SpecError`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{FilterSpec, FilterOp};
+///
+/// #[derive(FromFormValue, PartialEq, Debug)]
+/// enum Field {
+///     Status,
+///     Age,
+/// }
+///
+/// // GET /users?filter[status]=active&filter[age][gte]=21
+/// #[get("/users")]
+/// fn users(filter: FilterSpec<Field>) -> String {
+///     filter.iter()
+///         .map(|f| format!("{:?} {:?} {}", f.field, f.op, f.value))
+///         .collect::<Vec<_>>()
+///         .join(" AND ")
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterSpec<T> {
+    filters: Vec<Filter<T>>,
+}
+
+/// The error returned by the [`SortSpec`] and [`FilterSpec`] request guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecError {
+    /// A field is not in the allow-list.
+    UnknownField(String),
+    /// A filter operator is not recognized.
+    UnknownOp(String),
+    /// A field is empty.
+    EmptyField,
+    /// A sort field appears more than once.
+    Duplicate(String),
+    /// A `filter` parameter is malformed.
+    Malformed(String),
+}
+
+impl<T> SortSpec<T> {
+    /// Returns an iterator over the sort keys, in order of precedence.
+    pub fn iter(&self) -> impl Iterator<Item = &SortKey<T>> {
+        self.keys.iter()
+    }
+
+    /// Returns `true` if no sort keys were requested.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the sort keys, in order of precedence.
+    pub fn into_inner(self) -> Vec<SortKey<T>> {
+        self.keys
+    }
+}
+
+impl<T: for<'v> FromFormValue<'v> + PartialEq> SortSpec<T> {
+    fn parse(value: &str) -> Result<Self, SpecError> {
+        let mut keys: Vec<SortKey<T>> = vec![];
+        for item in value.split(',').map(|s| s.trim()) {
+            let (order, name) = match item.as_bytes().first() {
+                Some(b'-') => (Order::Desc, &item[1..]),
+                Some(b'+') => (Order::Asc, &item[1..]),
+                _ => (Order::Asc, item),
+            };
+
+            let field = parse_field::<T>(name)?;
+            if keys.iter().any(|k| k.field == field) {
+                return Err(SpecError::Duplicate(name.into()));
+            }
+
+            keys.push(SortKey { field, order });
+        }
+
+        Ok(SortSpec { keys })
+    }
+}
+
+impl<T> FilterSpec<T> {
+    /// Returns an iterator over the filters, in the order they were given.
+    pub fn iter(&self) -> impl Iterator<Item = &Filter<T>> {
+        self.filters.iter()
+    }
+
+    /// Returns `true` if no filters were requested.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Returns the filters, in the order they were given.
+    pub fn into_inner(self) -> Vec<Filter<T>> {
+        self.filters
+    }
+}
+
+impl<T: for<'v> FromFormValue<'v>> FilterSpec<T> {
+    /// Parses a `filter[field]` or `filter[field][op]` key and its value.
+    fn parse_item(key: &str, value: String) -> Result<Filter<T>, SpecError> {
+        let malformed = || SpecError::Malformed(key.into());
+        let rest = key.strip_prefix("filter[").ok_or_else(malformed)?;
+        let (name, rest) = rest.split_at(rest.find(']').ok_or_else(malformed)?);
+        let op = match &rest[1..] {
+            "" => FilterOp::Eq,
+            op if op.starts_with('[') && op.ends_with(']') => op[1..op.len() - 1].parse()?,
+            _ => return Err(malformed()),
+        };
+
+        Ok(Filter { field: parse_field::<T>(name)?, op, value })
+    }
+}
+
+fn parse_field<T: for<'v> FromFormValue<'v>>(name: &str) -> Result<T, SpecError> {
+    if name.is_empty() {
+        return Err(SpecError::EmptyField);
+    }
+
+    T::from_form_value(RawStr::from_str(name)).map_err(|_| SpecError::UnknownField(name.into()))
+}
+
+impl FromStr for FilterOp {
+    type Err = SpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "eq" => FilterOp::Eq,
+            "ne" => FilterOp::Ne,
+            "lt" => FilterOp::Lt,
+            "lte" => FilterOp::Lte,
+            "gt" => FilterOp::Gt,
+            "gte" => FilterOp::Gte,
+            "in" => FilterOp::In,
+            "contains" => FilterOp::Contains,
+            _ => return Err(SpecError::UnknownOp(s.into())),
+        })
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r, T> FromRequest<'a, 'r> for SortSpec<T>
+    where T: for<'v> FromFormValue<'v> + PartialEq + Send + 'static
+{
+    type Error = SpecError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let value = request.raw_query_items()
+            .and_then(|mut items| items.rfind(|item| item.key.as_str() == "sort"))
+            .map(|item| item.value.url_decode_lossy());
+
+        match value.filter(|v| !v.is_empty()).map(|v| SortSpec::parse(&v)) {
+            Some(Ok(spec)) => Outcome::Success(spec),
+            Some(Err(e)) => Outcome::Failure((Status::BadRequest, e)),
+            None => Outcome::Success(SortSpec { keys: vec![] }),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r, T> FromRequest<'a, 'r> for FilterSpec<T>
+    where T: for<'v> FromFormValue<'v> + Send + 'static
+{
+    type Error = SpecError;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let mut filters = vec![];
+        for item in request.raw_query_items().into_iter().flatten() {
+            let (key, value) = item.key_value_decoded();
+            if !key.starts_with("filter[") {
+                continue;
+            }
+
+            match FilterSpec::parse_item(&key, value) {
+                Ok(filter) => filters.push(filter),
+                Err(e) => return Outcome::Failure((Status::BadRequest, e)),
+            }
+        }
+
+        Outcome::Success(FilterSpec { filters })
+    }
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::UnknownField(name) => write!(f, "unknown field `{}`", name),
+            SpecError::UnknownOp(op) => write!(f, "unknown filter operator `{}`", op),
+            SpecError::EmptyField => write!(f, "empty field name"),
+            SpecError::Duplicate(name) => write!(f, "field `{}` appears more than once", name),
+            SpecError::Malformed(key) => write!(f, "malformed filter parameter `{}`", key),
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
+
+use crate::request::Request;
+
+/// Whether, and how, the phases of routed requests are timed.
+///
+/// The mode is configured application-wide via the `timings` configuration
+/// parameter, which defaults to `"off"`. See [`Timings`].
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TimingsMode {
+    /// Phases are not timed: `"off"`.
+    Off,
+    /// Phases are timed and recorded in [`Timings`]: `"record"`.
+    Record,
+    /// Phases are timed, recorded in [`Timings`], and emitted in a
+    /// `Server-Timing` response header: `"header"`.
+    Header,
+}
+
+/// The phase of handling a request measured by a [`Timing`].
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Phase {
+    /// A request guard, named by its type.
+    Guard,
+    /// The data guard, named by its type.
+    DataGuard,
+    /// The handler, named by its function.
+    Handler,
+    /// The conversion of the handler's return value into a response, named
+    /// by the handler's function.
+    Responder,
+}
+
+/// The duration of one phase of handling a request.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    /// The phase that was timed.
+    pub phase: Phase,
+    /// The name of the guard type or handler function that was timed.
+    pub name: &'static str,
+    /// How long the phase took.
+    pub duration: Duration,
+}
+
+/// The per-request record of how long each guard, the data guard, the
+/// handler, and the responder of a routed request took.
+///
+/// When the `timings` configuration parameter is `"record"` or `"header"`,
+/// code generated for routes times every request guard and the data guard,
+/// whatever their outcome, the handler, and the conversion of its return
+/// value into a response. The timings are recorded, in the order the phases
+/// ran, in the request's `Timings`, retrieved with [`Timings::of()`]. They are
+/// complete once the handler has responded and so can be inspected by
+/// response and completion fairings. With `"header"`, they are additionally
+/// emitted, after response fairings run, in a `Server-Timing` header, ahead of
+/// any metrics added to the request's [`ServerTiming`](crate::request::ServerTiming):
+///
+/// ```text
+/// Server-Timing: guard;desc="User";dur=0.052, handler;desc="index";dur=1.207, ...
+/// ```
+///
+/// Requests that are forwarded through several routes record the phases of
+/// each route tried. Error catchers are not timed.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::AdHoc;
+/// use rocket::request::Timings;
+///
+/// let fairing = AdHoc::on_response("Slow Guard Logger", |req, _| Box::pin(async move {
+///     if let Some(timings) = Timings::of(req) {
+///         for timing in timings.all() {
+///             if timing.duration.as_millis() > 100 {
+///                 println!("{:?} {} was slow", timing.phase, timing.name);
+///             }
+///         }
+///     }
+/// }));
+/// ```
+#[derive(Debug, Default)]
+pub struct Timings(Mutex<Vec<Timing>>);
+
+impl Timings {
+    /// Returns the timings of `req`, or `None` if timing is off.
+    pub fn of<'r>(req: &'r Request<'_>) -> Option<&'r Timings> {
+        match req.state.config.timings {
+            TimingsMode::Off => None,
+            _ => Some(req.local_cache(Timings::default)),
+        }
+    }
+
+    /// Returns the timings recorded so far, in the order the phases ran.
+    pub fn all(&self) -> Vec<Timing> {
+        self.0.lock().expect("timings lock").clone()
+    }
+
+    /// Returns the total duration of the phases of kind `phase`.
+    pub fn total(&self, phase: Phase) -> Duration {
+        self.0.lock().expect("timings lock").iter()
+            .filter(|timing| timing.phase == phase)
+            .map(|timing| timing.duration)
+            .sum()
+    }
+
+    /// Starts timing a phase of `req`, if timing is on.
+    #[doc(hidden)]
+    pub fn start(req: &Request<'_>) -> Option<Instant> {
+        match req.state.config.timings {
+            TimingsMode::Off => None,
+            _ => Some(Instant::now()),
+        }
+    }
+
+    /// Records the phase of `req` started at `start`, if timing is on.
+    #[doc(hidden)]
+    pub fn stop(req: &Request<'_>, start: Option<Instant>, phase: Phase, name: &'static str) {
+        if let (Some(start), Some(timings)) = (start, Timings::of(req)) {
+            let timing = Timing { phase, name, duration: start.elapsed() };
+            timings.0.lock().expect("timings lock").push(timing);
+        }
+    }
+
+    /// Returns the value of the `Server-Timing` header for the timings, or
+    /// `None` if there are none.
+    pub(crate) fn header_value(&self) -> Option<String> {
+        let timings = self.0.lock().expect("timings lock");
+        if timings.is_empty() {
+            return None;
+        }
+
+        let metrics: Vec<_> = timings.iter()
+            .map(|timing| {
+                let desc = timing.name.replace('\\', "\\\\").replace('"', "\\\"");
+                let millis = timing.duration.as_secs_f64() * 1000.0;
+                format!("{};desc=\"{}\";dur={:.3}", timing.phase, desc, millis)
+            })
+            .collect();
+
+        Some(metrics.join(", "))
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Guard => write!(f, "guard"),
+            Phase::DataGuard => write!(f, "data"),
+            Phase::Handler => write!(f, "handler"),
+            Phase::Responder => write!(f, "responder"),
+        }
+    }
+}
+
+impl TimingsMode {
+    fn as_str(&self) -> &str {
+        match self {
+            TimingsMode::Off => "off",
+            TimingsMode::Record => "record",
+            TimingsMode::Header => "header",
+        }
+    }
+}
+
+impl Default for TimingsMode {
+    fn default() -> Self {
+        TimingsMode::Off
+    }
+}
+
+impl FromStr for TimingsMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mode = match &*s.to_ascii_lowercase() {
+            "off" => TimingsMode::Off,
+            "record" => TimingsMode::Record,
+            "header" => TimingsMode::Header,
+            _ => return Err("a timings mode (off, record, header)")
+        };
+
+        Ok(mode)
+    }
+}
+
+impl fmt::Display for TimingsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for TimingsMode {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TimingsMode {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        TimingsMode::from_str(&string).map_err(|_| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &figment::error::OneOf( &["off", "record", "header"])
+        ))
+    }
+}
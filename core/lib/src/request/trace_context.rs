@@ -0,0 +1,308 @@
+use std::fmt::{self, Write};
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+use crate::http::{Header, hyper};
+
+/// Request guard for the [W3C Trace Context] of a request.
+///
+/// A `TraceContext` identifies the span of work done to handle a request
+/// within a distributed trace. When a request carries a valid `traceparent`
+/// header, the request's span is a child of the span identified by the
+/// header: it has the same trace ID and sampling decision, a new span ID, and
+/// the header's span ID as its parent ID. The `tracestate` header, if any, is
+/// carried along unchanged. Otherwise, the request's span begins a new,
+/// sampled trace. The span is created once per request, so every retrieval of
+/// the guard for a request observes the same span. As a request guard,
+/// `TraceContext` never fails.
+///
+/// To propagate the trace to other services, add the context's headers to
+/// outbound requests with [`TraceContext::inject()`] or
+/// [`TraceContext::headers()`]. The receiving service then observes its span
+/// as a child of the request's span. When the `client` feature is enabled,
+/// [`Outbound`](crate::client::Outbound) does so automatically.
+///
+/// [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::TraceContext;
+///
+/// #[get("/")]
+/// fn index(trace: &TraceContext) -> String {
+///     format!("trace {} span {}", trace.trace_id(), trace.span_id())
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_id: Option<[u8; 8]>,
+    flags: u8,
+    state: Option<String>,
+}
+
+/// The `sampled` bit of the trace flags.
+const SAMPLED: u8 = 0x01;
+
+fn hex(bytes: &[u8]) -> String {
+    let mut string = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(string, "{:02x}", byte);
+    }
+
+    string
+}
+
+/// Parses `N` bytes from exactly `2 * N` lowercase hex characters.
+fn unhex<T: Default + AsMut<[u8]>>(string: &str) -> Option<T> {
+    let mut bytes = T::default();
+    let buffer = bytes.as_mut();
+    if string.len() != buffer.len() * 2 {
+        return None;
+    }
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        let digits = string.get(2 * i..2 * i + 2)?;
+        if digits.bytes().any(|b| !matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return None;
+        }
+
+        *byte = u8::from_str_radix(digits, 16).ok()?;
+    }
+
+    Some(bytes)
+}
+
+impl TraceContext {
+    /// Returns a context for the root span of a new trace with random IDs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    ///
+    /// let trace = TraceContext::new(true);
+    /// assert!(trace.is_sampled());
+    /// assert!(trace.parent_id().is_none());
+    /// ```
+    pub fn new(sampled: bool) -> TraceContext {
+        TraceContext {
+            trace_id: random_id(),
+            span_id: random_id(),
+            parent_id: None,
+            flags: if sampled { SAMPLED } else { 0 },
+            state: None,
+        }
+    }
+
+    /// Parses the values of a `traceparent` and, optionally, a `tracestate`
+    /// header into the context of the span they identify. Returns `None` if
+    /// `traceparent` is invalid.
+    ///
+    /// Versions other than `00` are parsed as version `00` as long as the
+    /// first four fields are valid, as the specification requires. A
+    /// `tracestate` that is empty is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    ///
+    /// let header = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    /// let trace = TraceContext::parse(header, Some("vendor=value")).unwrap();
+    /// assert_eq!(trace.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+    /// assert_eq!(trace.span_id(), "b7ad6b7169203331");
+    /// assert_eq!(trace.tracestate(), Some("vendor=value"));
+    /// assert!(trace.is_sampled());
+    ///
+    /// assert!(TraceContext::parse("00-abc-def-01", None).is_none());
+    /// ```
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<TraceContext> {
+        let mut fields = traceparent.trim().split('-');
+        let version: [u8; 1] = unhex(fields.next()?)?;
+        let trace_id: [u8; 16] = unhex(fields.next()?)?;
+        let span_id: [u8; 8] = unhex(fields.next()?)?;
+        let flags: [u8; 1] = unhex(fields.next()?)?;
+
+        // Version `ff` is invalid; version `00` has exactly four fields.
+        let extra = fields.next();
+        if version[0] == 0xff || (version[0] == 0 && extra.is_some()) {
+            return None;
+        }
+
+        // All-zero trace and span IDs are invalid.
+        if trace_id.iter().all(|&b| b == 0) || span_id.iter().all(|&b| b == 0) {
+            return None;
+        }
+
+        let state = tracestate.map(|s| s.trim()).filter(|s| !s.is_empty());
+        Some(TraceContext {
+            trace_id,
+            span_id,
+            parent_id: None,
+            flags: flags[0],
+            state: state.map(|s| s.to_string()),
+        })
+    }
+
+    /// Returns a context for a new child span of this span: the child has the
+    /// same trace ID, flags, and trace state, a new random span ID, and this
+    /// span's ID as its parent ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    ///
+    /// let parent = TraceContext::new(true);
+    /// let child = parent.child();
+    /// assert_eq!(child.trace_id(), parent.trace_id());
+    /// assert_eq!(child.parent_id(), Some(parent.span_id()));
+    /// assert_ne!(child.span_id(), parent.span_id());
+    /// ```
+    pub fn child(&self) -> TraceContext {
+        TraceContext {
+            trace_id: self.trace_id,
+            span_id: random_id(),
+            parent_id: Some(self.span_id),
+            flags: self.flags,
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns the trace ID as 32 lowercase hex characters.
+    pub fn trace_id(&self) -> String {
+        hex(&self.trace_id)
+    }
+
+    /// Returns the span ID as 16 lowercase hex characters.
+    pub fn span_id(&self) -> String {
+        hex(&self.span_id)
+    }
+
+    /// Returns the span ID of the parent span as 16 lowercase hex characters,
+    /// if this span has a parent.
+    pub fn parent_id(&self) -> Option<String> {
+        self.parent_id.map(|id| hex(&id))
+    }
+
+    /// Returns `true` if the trace is sampled, that is, if the caller may
+    /// have recorded the trace.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED != 0
+    }
+
+    /// Returns the trace flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns the vendor-specific `tracestate`, if any.
+    pub fn tracestate(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// Returns the `traceparent` header value identifying this span.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    ///
+    /// let header = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    /// let trace = TraceContext::parse(header, None).unwrap();
+    /// assert_eq!(trace.traceparent(), header);
+    /// ```
+    pub fn traceparent(&self) -> String {
+        format!("00-{}-{}-{:02x}", self.trace_id(), self.span_id(), self.flags)
+    }
+
+    /// Returns the `traceparent` and, if there is one, `tracestate` headers
+    /// that propagate this span to another service.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    ///
+    /// let trace = TraceContext::new(true);
+    /// let headers = trace.headers();
+    /// assert_eq!(headers.len(), 1);
+    /// assert_eq!(headers[0].name(), "traceparent");
+    /// assert_eq!(headers[0].value(), trace.traceparent());
+    /// ```
+    pub fn headers(&self) -> Vec<Header<'static>> {
+        let mut headers = vec![Header::new("traceparent", self.traceparent())];
+        if let Some(ref state) = self.state {
+            headers.push(Header::new("tracestate", state.clone()));
+        }
+
+        headers
+    }
+
+    /// Sets the `traceparent` and `tracestate` headers of the outbound
+    /// request `request` to propagate this span, replacing any existing
+    /// values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::TraceContext;
+    /// # use rocket::http::hyper as hyper;
+    ///
+    /// let trace = TraceContext::new(true);
+    /// let mut request = hyper::Request::new(hyper::Body::empty());
+    /// trace.inject(&mut request);
+    ///
+    /// let traceparent = request.headers().get("traceparent").unwrap();
+    /// assert_eq!(traceparent.to_str().unwrap(), trace.traceparent());
+    /// ```
+    pub fn inject<B>(&self, request: &mut hyper::Request<B>) {
+        let headers = request.headers_mut();
+        headers.remove("tracestate");
+        if let Ok(value) = hyper::HeaderValue::from_str(&self.traceparent()) {
+            headers.insert("traceparent", value);
+        }
+
+        if let Some(Ok(value)) = self.state.as_deref().map(hyper::HeaderValue::from_str) {
+            headers.insert("tracestate", value);
+        }
+    }
+}
+
+fn random_id<T: Default + AsMut<[u8]>>() -> T {
+    use rand::RngCore;
+
+    // All-zero IDs are invalid, if astronomically unlikely.
+    let mut id = T::default();
+    while id.as_mut().iter().all(|&b| b == 0) {
+        rand::thread_rng().fill_bytes(id.as_mut());
+    }
+
+    id
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.traceparent())
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for &'a TraceContext {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(req.local_cache(|| {
+            let traceparent = req.headers().get_one("traceparent");
+            let tracestate = req.headers().get("tracestate").collect::<Vec<_>>().join(",");
+            traceparent.and_then(|parent| TraceContext::parse(parent, Some(&tracestate)))
+                .map(|parent| parent.child())
+                .unwrap_or_else(|| TraceContext::new(true))
+        }))
+    }
+}
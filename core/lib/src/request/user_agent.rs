@@ -0,0 +1,362 @@
+use std::fmt;
+
+use state::Storage;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// The category of device a [`UserAgent`] runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Device {
+    /// A desktop or laptop computer.
+    Desktop,
+    /// A phone.
+    Mobile,
+    /// A tablet.
+    Tablet,
+    /// A crawler, spider, or other automated agent.
+    Bot,
+    /// Anything else, including command-line tools and unrecognized agents.
+    Other,
+}
+
+/// The parsed components of a user agent string.
+#[derive(Debug)]
+struct Parsed {
+    family: &'static str,
+    version: Option<String>,
+    os: &'static str,
+    device: Device,
+}
+
+/// The per-request value cached by the [`UserAgent`] guard.
+struct RequestUserAgent(Storage<Parsed>);
+
+/// Browser families, checked in order, and the token preceding their version.
+///
+/// Order matters: many browsers include the tokens of the browsers they are
+/// derived from. Chromium-based Edge, for instance, also claims to be Chrome
+/// and Safari.
+const BROWSERS: &[(&str, &str)] = &[
+    ("Edge", "Edg/"),
+    ("Edge", "EdgA/"),
+    ("Edge", "EdgiOS/"),
+    ("Edge", "Edge/"),
+    ("Opera", "OPR/"),
+    ("Samsung Internet", "SamsungBrowser/"),
+    ("Firefox", "FxiOS/"),
+    ("Firefox", "Firefox/"),
+    ("Chrome", "CriOS/"),
+    ("Chromium", "Chromium/"),
+    ("Chrome", "Chrome/"),
+    ("Safari", "Version/"),
+    ("Internet Explorer", "MSIE "),
+    ("Internet Explorer", "rv:"),
+];
+
+/// Non-browser agents identified by their leading product token.
+const TOOLS: &[&str] = &[
+    "curl", "Wget", "HTTPie", "python-requests", "Python-urllib", "Go-http-client",
+    "okhttp", "PostmanRuntime", "axios", "node-fetch", "reqwest",
+];
+
+/// Well-known bots, identified by a token anywhere in the string.
+const BOTS: &[&str] = &[
+    "Googlebot", "bingbot", "Slurp", "DuckDuckBot", "Baiduspider", "YandexBot",
+    "facebookexternalhit", "Twitterbot", "LinkedInBot", "Applebot",
+];
+
+/// Operating systems, checked in order, and a token identifying them.
+const SYSTEMS: &[(&str, &str)] = &[
+    ("Windows", "Windows"),
+    ("iOS", "iPhone"),
+    ("iOS", "iPad"),
+    ("iOS", "iPod"),
+    ("ChromeOS", "CrOS"),
+    ("Android", "Android"),
+    ("macOS", "Macintosh"),
+    ("macOS", "Mac OS X"),
+    ("Linux", "Linux"),
+];
+
+/// Returns the version following `token` in `ua`, if any.
+fn version_after(ua: &str, token: &str) -> Option<String> {
+    let start = ua.find(token)? + token.len();
+    let version: String = ua[start..].chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+
+    match version.trim_end_matches('.') {
+        "" => None,
+        version => Some(version.to_string())
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_ascii_lowercase().contains(&needle.to_ascii_lowercase())
+}
+
+impl Parsed {
+    fn parse(ua: &str) -> Parsed {
+        let os = SYSTEMS.iter()
+            .find(|(_, token)| ua.contains(token))
+            .map_or("Other", |(os, _)| *os);
+
+        if let Some(bot) = BOTS.iter().find(|bot| ua.contains(*bot)) {
+            let version = version_after(ua, &format!("{}/", bot));
+            return Parsed { family: *bot, version, os, device: Device::Bot };
+        }
+
+        let is_bot = ["bot", "crawler", "spider"].iter().any(|t| contains_ignore_case(ua, t));
+        if is_bot {
+            return Parsed { family: "Bot", version: None, os, device: Device::Bot };
+        }
+
+        if let Some(tool) = TOOLS.iter().find(|tool| ua.starts_with(&format!("{}/", tool))) {
+            let version = version_after(ua, &format!("{}/", tool));
+            return Parsed { family: *tool, version, os, device: Device::Other };
+        }
+
+        let (family, version) = BROWSERS.iter()
+            .filter(|(family, _)| *family != "Safari" || ua.contains("Safari/"))
+            .filter(|(_, token)| *token != "rv:" || ua.contains("Trident/"))
+            .find(|(_, token)| ua.contains(token))
+            .map_or(("Other", None), |(family, token)| (*family, version_after(ua, token)));
+
+        let device = if ua.contains("iPad") || ua.contains("Tablet")
+            || (os == "Android" && !ua.contains("Mobile"))
+        {
+            Device::Tablet
+        } else if ua.contains("Mobi") || ua.contains("iPhone") || ua.contains("iPod") {
+            Device::Mobile
+        } else if ["Windows", "macOS", "Linux", "ChromeOS"].contains(&os) {
+            Device::Desktop
+        } else {
+            Device::Other
+        };
+
+        Parsed { family, version, os, device }
+    }
+}
+
+/// Request guard for the request's parsed `User-Agent` header.
+///
+/// The user agent string is parsed by a small built-in parser into a browser
+/// or agent [family](UserAgent::family()), [version](UserAgent::version()),
+/// [operating system](UserAgent::os()), and [device](UserAgent::device())
+/// category. The parser recognizes major browsers, common bots and crawlers,
+/// and common command-line tools and HTTP libraries; anything else is
+/// reported as `"Other"`. It is intended for analytics and conditional
+/// responses, not for security decisions: user agents are trivially spoofed.
+///
+/// Parsing is lazy: it happens the first time a parsed component is
+/// requested, at most once per request, and is shared by all `UserAgent`s for
+/// the request. Applications that need a more thorough parser can pass
+/// [`UserAgent::as_str()`] to one.
+///
+/// As a request guard, `UserAgent` forwards when the request has no
+/// `User-Agent` header. Use `Option<UserAgent>` to accept such requests.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{UserAgent, Device};
+///
+/// #[get("/")]
+/// fn index(ua: UserAgent<'_>) -> &'static str {
+///     match ua.device() {
+///         Device::Mobile => "mobile site",
+///         Device::Bot => "crawlable site",
+///         _ => "full site",
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct UserAgent<'r> {
+    raw: &'r str,
+    parsed: &'r Storage<Parsed>,
+}
+
+impl<'r> UserAgent<'r> {
+    /// Returns the user agent of `request`, if it has a `User-Agent` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::UserAgent;
+    /// use rocket::http::Header;
+    ///
+    /// # let client = rocket::local::blocking::Client::tracked(rocket::ignite()).unwrap();
+    /// let request = client.get("/").header(Header::new("User-Agent", "curl/7.68.0"));
+    /// let ua = UserAgent::of(request.inner()).unwrap();
+    /// assert_eq!(ua.family(), "curl");
+    /// assert_eq!(ua.version(), Some("7.68.0"));
+    /// ```
+    pub fn of(request: &'r Request<'_>) -> Option<UserAgent<'r>> {
+        let raw = request.headers().get_one("User-Agent")?;
+        let cached = request.local_cache(|| RequestUserAgent(Storage::new()));
+        Some(UserAgent { raw, parsed: &cached.0 })
+    }
+
+    fn parsed(&self) -> &'r Parsed {
+        let raw = self.raw;
+        if self.parsed.try_get().is_none() {
+            self.parsed.set(Parsed::parse(raw));
+        }
+
+        self.parsed.get()
+    }
+
+    /// Returns the raw `User-Agent` header value.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'r str {
+        self.raw
+    }
+
+    /// Returns the browser or agent family, such as `"Firefox"`, `"Chrome"`,
+    /// `"Googlebot"`, or `"curl"`, or `"Other"` if it is unrecognized.
+    pub fn family(&self) -> &'static str {
+        self.parsed().family
+    }
+
+    /// Returns the version of the browser or agent, if it is known.
+    pub fn version(&self) -> Option<&'r str> {
+        self.parsed().version.as_deref()
+    }
+
+    /// Returns the major version of the browser or agent, if it is known.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::UserAgent;
+    /// use rocket::http::Header;
+    ///
+    /// # let client = rocket::local::blocking::Client::tracked(rocket::ignite()).unwrap();
+    /// let ua = "Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/115.0";
+    /// let request = client.get("/").header(Header::new("User-Agent", ua));
+    /// let ua = UserAgent::of(request.inner()).unwrap();
+    /// assert_eq!(ua.major_version(), Some(115));
+    /// ```
+    pub fn major_version(&self) -> Option<u32> {
+        self.version()?.split('.').next()?.parse().ok()
+    }
+
+    /// Returns the operating system, one of `"Windows"`, `"macOS"`, `"iOS"`,
+    /// `"Android"`, `"ChromeOS"`, `"Linux"`, or `"Other"`.
+    pub fn os(&self) -> &'static str {
+        self.parsed().os
+    }
+
+    /// Returns the category of device.
+    pub fn device(&self) -> Device {
+        self.parsed().device
+    }
+
+    /// Returns `true` if the agent is a recognized bot or crawler.
+    #[inline]
+    pub fn is_bot(&self) -> bool {
+        self.device() == Device::Bot
+    }
+}
+
+impl fmt::Debug for UserAgent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserAgent")
+            .field("raw", &self.raw)
+            .field("parsed", &self.parsed.try_get())
+            .finish()
+    }
+}
+
+impl fmt::Display for UserAgent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw.fmt(f)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for UserAgent<'a> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match UserAgent::of(request) {
+            Some(ua) => Outcome::Success(ua),
+            None => Outcome::Forward(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Parsed, Device};
+
+    fn parse(ua: &str) -> (&'static str, Option<String>, &'static str, Device) {
+        let parsed = Parsed::parse(ua);
+        (parsed.family, parsed.version, parsed.os, parsed.device)
+    }
+
+    macro_rules! assert_ua {
+        ($ua:expr => $family:expr, $version:expr, $os:expr, $device:expr) => {
+            let version: Option<&str> = $version;
+            assert_eq!(parse($ua), ($family, version.map(String::from), $os, $device));
+        };
+    }
+
+    #[test]
+    fn test_browsers() {
+        assert_ua!("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, \
+            like Gecko) Chrome/120.0.0.0 Safari/537.36"
+            => "Chrome", Some("120.0.0.0"), "Windows", Device::Desktop);
+
+        assert_ua!("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, \
+            like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91"
+            => "Edge", Some("120.0.2210.91"), "Windows", Device::Desktop);
+
+        assert_ua!("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 \
+            (KHTML, like Gecko) Version/17.1 Safari/605.1.15"
+            => "Safari", Some("17.1"), "macOS", Device::Desktop);
+
+        assert_ua!("Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/115.0"
+            => "Firefox", Some("115.0"), "Linux", Device::Desktop);
+
+        assert_ua!("Mozilla/5.0 (Windows NT 10.0; Trident/7.0; rv:11.0) like Gecko"
+            => "Internet Explorer", Some("11.0"), "Windows", Device::Desktop);
+    }
+
+    #[test]
+    fn test_mobile_and_tablets() {
+        assert_ua!("Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 \
+            (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1"
+            => "Safari", Some("17.1"), "iOS", Device::Mobile);
+
+        assert_ua!("Mozilla/5.0 (iPad; CPU OS 17_1 like Mac OS X) AppleWebKit/605.1.15 \
+            (KHTML, like Gecko) CriOS/120.0.6099.119 Mobile/15E148 Safari/604.1"
+            => "Chrome", Some("120.0.6099.119"), "iOS", Device::Tablet);
+
+        assert_ua!("Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like \
+            Gecko) Chrome/120.0.6099.144 Mobile Safari/537.36"
+            => "Chrome", Some("120.0.6099.144"), "Android", Device::Mobile);
+
+        assert_ua!("Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like \
+            Gecko) SamsungBrowser/23.0 Chrome/115.0.0.0 Safari/537.36"
+            => "Samsung Internet", Some("23.0"), "Android", Device::Tablet);
+    }
+
+    #[test]
+    fn test_bots_and_tools() {
+        assert_ua!("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            => "Googlebot", Some("2.1"), "Other", Device::Bot);
+
+        assert_ua!("Mozilla/5.0 (compatible; SomeCrawler/1.0)"
+            => "Bot", None, "Other", Device::Bot);
+
+        assert_ua!("curl/7.68.0" => "curl", Some("7.68.0"), "Other", Device::Other);
+        assert_ua!("python-requests/2.31.0"
+            => "python-requests", Some("2.31.0"), "Other", Device::Other);
+
+        assert_ua!("" => "Other", None, "Other", Device::Other);
+        assert_ua!("something/1.0 (unknown)" => "Other", None, "Other", Device::Other);
+    }
+}
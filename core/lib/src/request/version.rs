@@ -0,0 +1,130 @@
+use std::fmt;
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+
+/// The name of the header carrying an explicitly requested API version.
+const VERSION_HEADER: &str = "Api-Version";
+
+/// The per-request value cached by [`ApiVersion::of()`].
+struct RequestVersion(Option<ApiVersion>);
+
+/// Request guard and router helper for the API version a request targets.
+///
+/// The version is extracted from the first of the following that is present:
+///
+///   1. The `Api-Version` header, as in `Api-Version: 2` or `Api-Version: v2`.
+///   2. A vendor media type in the request's preferred `Accept` media type,
+///      as in `Accept: application/vnd.app.v2+json`.
+///   3. A leading path segment, as in `/v2/users`.
+///
+/// Routes declared with a `version` argument, as in `#[get("/users", version
+/// = 2)]`, only match requests for that version, so several versions of an
+/// endpoint can be mounted at the same path without colliding. Routes without
+/// a version match requests for any version, or none at all. Note that the
+/// version is _not_ stripped from the path: a route matching `/v2/users` must
+/// include the `/v2` prefix, typically via its mount point.
+///
+/// As a request guard, `ApiVersion` forwards when the request doesn't specify
+/// a version. Use `Option<ApiVersion>` to accept unversioned requests.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::ApiVersion;
+///
+/// #[get("/users", version = 1)]
+/// fn users_v1() -> &'static str { "[\"bob\"]" }
+///
+/// #[get("/users", version = 2)]
+/// fn users_v2() -> &'static str { "{\"users\": [\"bob\"]}" }
+///
+/// #[get("/version")]
+/// fn version(version: Option<ApiVersion>) -> String {
+///     version.map(|v| v.to_string()).unwrap_or_else(|| "unversioned".into())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion(pub u32);
+
+impl ApiVersion {
+    /// Returns the API version `request` targets, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::ApiVersion;
+    /// use rocket::http::{Accept, Header, MediaType};
+    ///
+    /// # let client = rocket::local::blocking::Client::tracked(rocket::ignite()).unwrap();
+    /// let request = client.get("/users").header(Header::new("Api-Version", "v3"));
+    /// assert_eq!(ApiVersion::of(request.inner()), Some(ApiVersion(3)));
+    ///
+    /// let vendor = MediaType::new("application", "vnd.app.v2+json");
+    /// let request = client.get("/users").header(Accept::from(vendor));
+    /// assert_eq!(ApiVersion::of(request.inner()), Some(ApiVersion(2)));
+    ///
+    /// let request = client.get("/v1/users");
+    /// assert_eq!(ApiVersion::of(request.inner()), Some(ApiVersion(1)));
+    ///
+    /// let request = client.get("/users");
+    /// assert_eq!(ApiVersion::of(request.inner()), None);
+    /// ```
+    pub fn of(request: &Request<'_>) -> Option<ApiVersion> {
+        request.local_cache(|| RequestVersion(Self::extract(request))).0
+    }
+
+    fn extract(request: &Request<'_>) -> Option<ApiVersion> {
+        if let Some(value) = request.headers().get_one(VERSION_HEADER) {
+            let value = value.trim();
+            let number = value.strip_prefix(|c| c == 'v' || c == 'V').unwrap_or(value);
+            return number.parse().ok().map(ApiVersion);
+        }
+
+        let from_accept = request.accept()
+            .and_then(|accept| Self::from_vendor_sub(accept.preferred().media_type().sub()));
+
+        from_accept.or_else(|| {
+            request.uri().segments().next().and_then(Self::from_segment)
+        })
+    }
+
+    /// Parses a vendor subtype such as `vnd.app.v2` or `vnd.app.v2+json`.
+    fn from_vendor_sub(sub: &str) -> Option<ApiVersion> {
+        let sub = sub.split('+').next()?;
+        if !sub.starts_with("vnd.") {
+            return None;
+        }
+
+        sub.rsplit('.').next().and_then(Self::from_segment)
+    }
+
+    /// Parses a segment of the form `v2`.
+    fn from_segment(segment: &str) -> Option<ApiVersion> {
+        let number = segment.strip_prefix(|c| c == 'v' || c == 'V')?;
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        number.parse().ok().map(ApiVersion)
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for ApiVersion {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match ApiVersion::of(request) {
+            Some(version) => Outcome::Success(version),
+            None => Outcome::Forward(())
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::io;
+
+use crate::http::Status;
+
+/// A record of how writing a response to the client ended.
+///
+/// Once a response has been written, or writing it has failed, Rocket passes
+/// a `Completion` to every completion fairing, via
+/// [`Fairing::on_complete()`](crate::fairing::Fairing::on_complete()). Unlike
+/// a response fairing, which sees the response before any of it is sent, a
+/// completion fairing learns how much of the body actually reached the
+/// connection and why writing stopped if it stopped early, for instance,
+/// because the client disconnected mid-body.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::AdHoc;
+///
+/// let fairing = AdHoc::on_complete("Truncation Logger", |req, completion| {
+///     Box::pin(async move {
+///         if let Some(e) = completion.error() {
+///             eprintln!("{} {}: wrote {} bytes, then failed: {}",
+///                 req.method(), req.uri(), completion.bytes_written(), e);
+///         }
+///     })
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Completion {
+    status: Status,
+    bytes_written: u64,
+    error: Option<io::Error>,
+}
+
+impl Completion {
+    pub(crate) fn new(status: Status, bytes_written: u64, error: Option<io::Error>) -> Self {
+        Completion { status, bytes_written, error }
+    }
+
+    /// Returns the status of the response that was written.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns the number of body bytes handed to the connection before
+    /// writing finished or failed. Headers are not counted.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns the error that ended writing the response early, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns `true` if the entire response was written without error.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+}
@@ -65,3 +65,60 @@ impl<'r, E: std::fmt::Debug> Responder<'r, 'static> for Debug<E> {
         Response::build().status(Status::InternalServerError).ok()
     }
 }
+
+/// Logs every error in `chain`, outermost first, along with the request's
+/// `X-Request-Id`, then responds with a `500` via the `500` catcher.
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+fn log_error_chain<'a, I>(req: &Request<'_>, mut chain: I) -> response::Result<'static>
+    where I: Iterator<Item = &'a (dyn std::error::Error + 'static)>
+{
+    let request_id = req.headers().get_one("X-Request-Id").unwrap_or("-");
+    if let Some(error) = chain.next() {
+        error_!("Handler error (request id {}): {}", request_id, Paint::default(error));
+    }
+
+    for cause in chain {
+        info_!("caused by: {}", cause);
+    }
+
+    Err(Status::InternalServerError)
+}
+
+/// Logs the error and its chain of causes, along with the request's
+/// `X-Request-Id` header, and responds with a `500` via the `500` catcher.
+///
+/// The error's message is never included in the response. This allows `?` to
+/// be used on any error in handlers returning `Result<T, anyhow::Error>`:
+///
+/// ```rust
+/// # use rocket::get;
+/// use anyhow::Context;
+///
+/// #[get("/config")]
+/// async fn config() -> anyhow::Result<String> {
+///     let config = rocket::tokio::fs::read_to_string("App.toml").await
+///         .context("failed to read application configuration")?;
+///
+///     Ok(config)
+/// }
+/// ```
+#[cfg(feature = "anyhow")]
+#[cfg_attr(nightly, doc(cfg(feature = "anyhow")))]
+impl<'r> Responder<'r, 'static> for anyhow::Error {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        log_error_chain(req, self.chain())
+    }
+}
+
+/// Logs the report and its chain of causes, along with the request's
+/// `X-Request-Id` header, and responds with a `500` via the `500` catcher.
+///
+/// The report's message is never included in the response. This allows `?`
+/// to be used on any error in handlers returning `Result<T, eyre::Report>`.
+#[cfg(feature = "eyre")]
+#[cfg_attr(nightly, doc(cfg(feature = "eyre")))]
+impl<'r> Responder<'r, 'static> for eyre::Report {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        log_error_chain(req, self.chain())
+    }
+}
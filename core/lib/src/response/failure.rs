@@ -0,0 +1,123 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::{Status, StatusClass};
+
+/// A typed application error that renders like Rocket's own errors.
+///
+/// A `Failure` wraps an application error type `E` that converts into a
+/// [`Status`] and is [`Serialize`]. Because `Failure<E>` implements
+/// `From<E>`, a handler returning `Result<T, Failure<E>>` can use `?` on
+/// values of type `Result<_, E>` directly.
+///
+/// # Rendering
+///
+/// A `Failure` renders in the same format as Rocket's built-in default
+/// catcher, so errors look the same whether they originate in a handler or in
+/// routing:
+///
+///   * If the request is under one of the mount points in the `json_errors`
+///     configuration parameter, or prefers JSON, the response is the default
+///     catcher's JSON error body with the serialized error as an additional
+///     `detail` member.
+///
+///   * Otherwise, the error's status is forwarded to the catcher registered
+///     for it, exactly as if a guard had failed. The catcher can retrieve the
+///     serialized error via [`Failure::caught()`].
+///
+/// If the error converts into a status that is not a `4xx` or `5xx`, the
+/// error is logged and a `500` is returned instead.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::http::Status;
+/// use rocket::response::Failure;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// enum AppError {
+///     NoSuchUser(usize),
+///     Database,
+/// }
+///
+/// impl From<AppError> for Status {
+///     fn from(error: AppError) -> Status {
+///         match error {
+///             AppError::NoSuchUser(_) => Status::NotFound,
+///             AppError::Database => Status::InternalServerError,
+///         }
+///     }
+/// }
+///
+/// fn find_user(id: usize) -> Result<String, AppError> {
+///     Err(AppError::NoSuchUser(id))
+/// }
+///
+/// #[get("/user/<id>")]
+/// fn user(id: usize) -> Result<String, Failure<AppError>> {
+///     Ok(find_user(id)?)
+/// }
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> String {
+///     match Failure::caught(req) {
+///         Some(error) => format!("Not found: {}", error),
+///         None => format!("Nothing at {}.", req.uri()),
+///     }
+/// }
+///
+/// #[launch]
+/// fn rocket() -> rocket::Rocket {
+///     rocket::ignite()
+///         .mount("/", routes![user])
+///         .register(catchers![not_found])
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure<E>(pub E);
+
+/// The serialized error of a `Failure` forwarded to a catcher.
+struct Caught(Option<Value>);
+
+impl Failure<()> {
+    /// Returns the serialized error of the `Failure` that caused `req` to be
+    /// forwarded to a catcher, if any.
+    pub fn caught<'r>(req: &'r Request<'_>) -> Option<&'r Value> {
+        req.local_cache(|| Caught(None)).0.as_ref()
+    }
+}
+
+impl<E> From<E> for Failure<E> {
+    fn from(error: E) -> Self {
+        Failure(error)
+    }
+}
+
+impl<'r, E: Into<Status> + Serialize> Responder<'r, 'static> for Failure<E> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let detail = serde_json::to_value(&self.0)
+            .map_err(|e| { warn_!("Failed to serialize application error: {}", e); })
+            .ok();
+
+        let status: Status = self.0.into();
+        match status.class() {
+            StatusClass::ClientError | StatusClass::ServerError => {}
+            _ => {
+                error_!("Invalid status for application error: {}.", status);
+                return Err(Status::InternalServerError);
+            }
+        }
+
+        if crate::catcher::wants_json(req) {
+            return crate::catcher::json_error(status, req, detail);
+        }
+
+        req.local_cache(|| Caught(detail));
+        Err(status)
+    }
+}
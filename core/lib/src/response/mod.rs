@@ -27,8 +27,13 @@ mod responder;
 mod redirect;
 mod named_file;
 mod stream;
+mod multipart;
 mod response;
 mod debug;
+mod problem;
+mod failure;
+mod completion;
+pub(crate) mod transform;
 
 pub(crate) mod flash;
 
@@ -44,7 +49,12 @@ pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::named_file::NamedFile;
 pub use self::stream::Stream;
+pub use self::multipart::{Multipart, Part};
 pub use self::debug::Debug;
+pub use self::problem::Problem;
+pub use self::failure::Failure;
+pub use self::completion::Completion;
+pub use self::transform::ResponseTransform;
 #[doc(inline)] pub use self::content::Content;
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
@@ -0,0 +1,342 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Cursor};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use rand::{distributions::Alphanumeric, Rng};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder};
+use crate::http::{ContentType, Header};
+
+/// A multipart response: a sequence of [`Part`]s, each with its own headers
+/// and streamed body.
+///
+/// The response is sent with a `Content-Type` of `multipart/{subtype}` and a
+/// randomly generated `boundary` parameter, which delimits the parts. Part
+/// bodies are streamed one after the other; none are buffered in memory.
+///
+/// The generated boundary is 40 random alphanumeric characters, making a
+/// collision with part contents vanishingly unlikely. A fixed boundary can be
+/// set with [`Multipart::boundary()`], in which case the caller is responsible
+/// for ensuring that it does not appear in any part. CR and LF in part headers
+/// are written as spaces so that a header can't end the part's head early.
+///
+/// # Example
+///
+/// Respond with a JSON document and a plain-text attachment:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::io::Cursor;
+/// use rocket::http::{ContentType, Header};
+/// use rocket::response::{Multipart, Part};
+///
+/// #[get("/bundle")]
+/// fn bundle() -> Multipart<'static> {
+///     Multipart::mixed()
+///         .part(Part::new(Cursor::new(r#"{"id": 1}"#)).content_type(ContentType::JSON))
+///         .part(Part::new(Cursor::new("Hello!"))
+///             .content_type(ContentType::Plain)
+///             .header(Header::new("Content-Disposition", "attachment; filename=\"hi.txt\"")))
+/// }
+/// # fn main() {}
+/// ```
+pub struct Multipart<'o> {
+    subtype: &'static str,
+    boundary: String,
+    parts: Vec<Part<'o>>,
+}
+
+/// A single part of a [`Multipart`] response.
+pub struct Part<'o> {
+    headers: Vec<Header<'static>>,
+    body: Pin<Box<dyn AsyncRead + Send + 'o>>,
+}
+
+impl<'o> Multipart<'o> {
+    /// Creates an empty `multipart/{subtype}` response with a random boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Multipart;
+    ///
+    /// let ranges = Multipart::new("byteranges");
+    /// ```
+    pub fn new(subtype: &'static str) -> Self {
+        let boundary = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(40)
+            .map(char::from)
+            .collect();
+
+        Multipart { subtype, boundary, parts: vec![] }
+    }
+
+    /// Creates an empty `multipart/mixed` response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Multipart;
+    ///
+    /// let bundle = Multipart::mixed();
+    /// ```
+    pub fn mixed() -> Self {
+        Multipart::new("mixed")
+    }
+
+    /// Creates an empty `multipart/form-data` response. Parts are typically
+    /// created with [`Part::form_field()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Multipart;
+    ///
+    /// let form = Multipart::form_data();
+    /// ```
+    pub fn form_data() -> Self {
+        Multipart::new("form-data")
+    }
+
+    /// Sets the boundary to `boundary`, replacing the generated one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boundary` isn't a valid boundary as defined by [RFC 2046]:
+    /// 1 to 70 letters, digits, spaces, or any of `'()+_,-./:=?`, not ending in
+    /// a space.
+    ///
+    /// [RFC 2046]: https://tools.ietf.org/html/rfc2046#section-5.1.1
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Multipart;
+    ///
+    /// let bundle = Multipart::mixed().boundary("XyZ");
+    /// assert_eq!(bundle.get_boundary(), "XyZ");
+    /// ```
+    pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+        let boundary = boundary.into();
+        assert!(is_valid_boundary(&boundary), "invalid multipart boundary: {:?}", boundary);
+        self.boundary = boundary;
+        self
+    }
+
+    /// Returns the boundary delimiting the parts.
+    pub fn get_boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Appends `part` to the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::response::{Multipart, Part};
+    ///
+    /// let bundle = Multipart::mixed().part(Part::new(Cursor::new("one")));
+    /// ```
+    pub fn part(mut self, part: Part<'o>) -> Self {
+        self.parts.push(part);
+        self
+    }
+}
+
+impl<'o> Part<'o> {
+    /// Creates a new part with no headers whose body is read from `body`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::response::Part;
+    ///
+    /// let part = Part::new(Cursor::new("Hello, world!"));
+    /// ```
+    pub fn new<B: AsyncRead + Send + 'o>(body: B) -> Self {
+        Part { headers: vec![], body: Box::pin(body) }
+    }
+
+    /// Creates a new `multipart/form-data` part for the field `name`. As in
+    /// HTML form submissions, `"`, CR, and LF in `name` are percent-encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::response::Part;
+    ///
+    /// let part = Part::form_field("username", Cursor::new("bob"));
+    /// ```
+    pub fn form_field<B: AsyncRead + Send + 'o>(name: &str, body: B) -> Self {
+        let name = name.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A");
+        let disposition = format!("form-data; name=\"{}\"", name);
+        Part::new(body).header(Header::new("Content-Disposition", disposition))
+    }
+
+    /// Sets the `Content-Type` of the part to `content_type`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::http::ContentType;
+    /// use rocket::response::Part;
+    ///
+    /// let part = Part::new(Cursor::new("{}")).content_type(ContentType::JSON);
+    /// ```
+    pub fn content_type(self, content_type: ContentType) -> Self {
+        self.header(content_type)
+    }
+
+    /// Adds `header` to the part, replacing any existing header with the same
+    /// name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::http::Header;
+    /// use rocket::response::Part;
+    ///
+    /// let part = Part::new(Cursor::new("..."))
+    ///     .header(Header::new("Content-Range", "bytes 0-2/100"));
+    /// ```
+    pub fn header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        let header = header.into();
+        self.headers.retain(|h| h.name() != header.name());
+        self.headers.push(header);
+        self
+    }
+}
+
+/// Returns `true` if `boundary` is 1 to 70 of RFC 2046's `bchars` and doesn't
+/// end in a space.
+fn is_valid_boundary(boundary: &str) -> bool {
+    let is_bchar = |b: u8| b.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&b);
+    (1..=70).contains(&boundary.len())
+        && !boundary.ends_with(' ')
+        && boundary.bytes().all(is_bchar)
+}
+
+/// Replaces CR and LF in `string` with spaces.
+fn strip_newlines(string: &str) -> Cow<'_, str> {
+    match string.contains(&['\r', '\n'][..]) {
+        true => Cow::Owned(string.replace(&['\r', '\n'][..], " ")),
+        false => Cow::Borrowed(string),
+    }
+}
+
+/// Reads each segment of a multipart body in turn.
+struct MultipartReader<'o> {
+    segments: VecDeque<Segment<'o>>,
+}
+
+enum Segment<'o> {
+    Bytes(Cursor<Vec<u8>>),
+    Body(Pin<Box<dyn AsyncRead + Send + 'o>>),
+}
+
+impl<'o> MultipartReader<'o> {
+    fn new(boundary: &str, parts: Vec<Part<'o>>) -> Self {
+        let mut segments = VecDeque::with_capacity(parts.len() * 2 + 1);
+        for (i, part) in parts.into_iter().enumerate() {
+            let mut head = String::new();
+            if i > 0 {
+                head.push_str("\r\n");
+            }
+
+            head.push_str("--");
+            head.push_str(boundary);
+            head.push_str("\r\n");
+            for header in &part.headers {
+                let name = strip_newlines(header.name().as_str());
+                let value = strip_newlines(header.value());
+                head.push_str(&format!("{}: {}\r\n", name, value));
+            }
+
+            head.push_str("\r\n");
+            segments.push_back(Segment::Bytes(Cursor::new(head.into_bytes())));
+            segments.push_back(Segment::Body(part.body));
+        }
+
+        let prefix = if segments.is_empty() { "" } else { "\r\n" };
+        let tail = format!("{}--{}--\r\n", prefix, boundary);
+        segments.push_back(Segment::Bytes(Cursor::new(tail.into_bytes())));
+        MultipartReader { segments }
+    }
+}
+
+impl AsyncRead for MultipartReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let start = buf.filled().len();
+        while let Some(segment) = self.segments.front_mut() {
+            let poll = match segment {
+                Segment::Bytes(cursor) => Pin::new(cursor).poll_read(cx, buf),
+                Segment::Body(body) => body.as_mut().poll_read(cx, buf),
+            };
+
+            match poll {
+                Poll::Ready(Ok(())) if buf.filled().len() == start => {
+                    self.segments.pop_front();
+                }
+                poll => return poll,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl fmt::Debug for Multipart<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multipart")
+            .field("subtype", &self.subtype)
+            .field("boundary", &self.boundary)
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Part<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Part").field("headers", &self.headers).finish()
+    }
+}
+
+/// Sets the `Content-Type` to `multipart/{subtype}` with the boundary as a
+/// parameter, quoted if necessary, and streams the parts as a chunked body.
+impl<'r, 'o: 'r> Responder<'r, 'o> for Multipart<'o> {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let is_token = |b: u8| b.is_ascii_alphanumeric() || b"'+_-.".contains(&b);
+        let boundary = match self.boundary.bytes().all(is_token) {
+            true => self.boundary.clone(),
+            false => format!("\"{}\"", self.boundary),
+        };
+
+        let content_type = ContentType::with_params(
+            "multipart", self.subtype, ("boundary", boundary)
+        );
+
+        Response::build()
+            .header(content_type)
+            .streamed_body(MultipartReader::new(&self.boundary, self.parts))
+            .ok()
+    }
+}
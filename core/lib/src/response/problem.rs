@@ -0,0 +1,297 @@
+use std::fmt;
+use std::io::Cursor;
+
+use serde::ser::{Serialize, Serializer, SerializeMap};
+use serde_json::{Map, Value};
+
+use crate::catcher::{Catcher, ErrorHandlerFuture};
+use crate::request::{Request, FormError, FormDataError, FormParseError};
+use crate::response::{self, Response, Responder};
+use crate::http::{ContentType, Status};
+
+/// The members defined by RFC 7807, which may not be used as extensions.
+const STANDARD_MEMBERS: &[&str] = &["type", "title", "status", "detail", "instance"];
+
+/// An [RFC 7807] "problem details" error response.
+///
+/// A `Problem` responds with its status and a JSON body with a Content-Type
+/// of `application/problem+json`. The body contains the standard members:
+///
+///   * `type`: a URI identifying the kind of problem; omitted if unset, which
+///     is equivalent to `about:blank`
+///   * `title`: a short summary of the kind of problem; defaults to the
+///     status's reason phrase
+///   * `status`: the status code
+///   * `detail`: an explanation of this occurrence of the problem, if set
+///   * `instance`: a URI identifying this occurrence of the problem, if set
+///
+/// along with any extension members added with [`Problem::extension()`].
+///
+/// [RFC 7807]: https://tools.ietf.org/html/rfc7807
+///
+/// # Conversions
+///
+/// A `Problem` can be created from the failure of a request guard, a pair of
+/// `(Status, E)` where `E: Display`, with `E` becoming the `detail`. It can
+/// also be created from a [`FormError`] or [`FormParseError`]; parse errors
+/// have a status of `422 Unprocessable Entity` and a `field` extension naming
+/// the offending field.
+///
+/// # Catchers
+///
+/// [`Problem::catcher()`] returns a default catcher that responds to every
+/// unhandled error status with a `Problem`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::response::Problem;
+///
+/// #[get("/account/<id>")]
+/// fn account(id: usize) -> Result<String, Problem> {
+///     # let balance = 30;
+///     if balance < 50 {
+///         return Err(Problem::new(Status::Forbidden)
+///             .kind("https://example.com/probs/out-of-credit")
+///             .title("You do not have enough credit.")
+///             .detail(format!("Your current balance is {}, but that costs 50.", balance))
+///             .instance(format!("/account/{}", id))
+///             .extension("balance", balance));
+///     }
+///
+///     Ok(format!("Account {}", id))
+/// }
+///
+/// fn main() {
+///     # let _ = {
+///     rocket::ignite()
+///         .mount("/", routes![account])
+///         .register(vec![Problem::catcher()])
+///     # };
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    status: Status,
+    kind: Option<String>,
+    title: Option<String>,
+    detail: Option<String>,
+    instance: Option<String>,
+    extensions: Map<String, Value>,
+}
+
+impl Problem {
+    /// Returns a `Problem` with status `status` and no other members set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::response::Problem;
+    ///
+    /// let problem = Problem::new(Status::NotFound);
+    /// assert_eq!(problem.status(), Status::NotFound);
+    /// assert_eq!(problem.get_title(), "Not Found");
+    /// ```
+    pub fn new(status: Status) -> Problem {
+        Problem {
+            status,
+            kind: None,
+            title: None,
+            detail: None,
+            instance: None,
+            extensions: Map::new(),
+        }
+    }
+
+    /// Sets the `type` member, a URI identifying the kind of problem.
+    pub fn kind<S: Into<String>>(mut self, uri: S) -> Self {
+        self.kind = Some(uri.into());
+        self
+    }
+
+    /// Sets the `title` member, a short summary of the kind of problem.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the `detail` member, an explanation of this occurrence.
+    pub fn detail<S: Into<String>>(mut self, detail: S) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Sets the `instance` member, a URI identifying this occurrence.
+    pub fn instance<S: Into<String>>(mut self, uri: S) -> Self {
+        self.instance = Some(uri.into());
+        self
+    }
+
+    /// Adds an extension member named `name` with value `value`, replacing
+    /// any existing extension of the same name.
+    ///
+    /// If `value` fails to serialize, the error is logged and the member is
+    /// not added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is one of the standard members: `type`, `title`,
+    /// `status`, `detail`, or `instance`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::response::Problem;
+    ///
+    /// let problem = Problem::new(Status::Conflict).extension("ids", vec![1, 2]);
+    /// assert_eq!(problem.get_extension("ids").unwrap().to_string(), "[1,2]");
+    /// ```
+    pub fn extension<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<String>, V: Serialize
+    {
+        let name = name.into();
+        assert!(!STANDARD_MEMBERS.contains(&name.as_str()),
+            "`{}` is a standard problem member, not an extension", name);
+
+        match serde_json::to_value(value) {
+            Ok(value) => { self.extensions.insert(name, value); }
+            Err(e) => error_!("Failed to serialize problem extension `{}`: {}", name, e),
+        }
+
+        self
+    }
+
+    /// Returns the status.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns the `type` member, if it is set.
+    pub fn get_kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    /// Returns the `title` member, or the status's reason phrase if it is not
+    /// set.
+    pub fn get_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(self.status.reason)
+    }
+
+    /// Returns the `detail` member, if it is set.
+    pub fn get_detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// Returns the `instance` member, if it is set.
+    pub fn get_instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// Returns the extension member named `name`, if there is one.
+    pub fn get_extension(&self, name: &str) -> Option<&Value> {
+        self.extensions.get(name)
+    }
+
+    /// Returns a default catcher that responds to every error status without
+    /// a more specific catcher with a `Problem` for that status, with an
+    /// `instance` of the request's URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Problem;
+    ///
+    /// # let _ = {
+    /// rocket::ignite().register(vec![Problem::catcher()])
+    /// # };
+    /// ```
+    pub fn catcher() -> Catcher {
+        fn handler<'r>(status: Status, req: &'r Request<'_>) -> ErrorHandlerFuture<'r> {
+            let problem = Problem::new(status).instance(req.uri().to_string());
+            Box::pin(async move { problem.respond_to(req) })
+        }
+
+        Catcher::new(None, handler)
+    }
+}
+
+impl Serialize for Problem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(kind) = &self.kind {
+            map.serialize_entry("type", kind)?;
+        }
+
+        map.serialize_entry("title", self.get_title())?;
+        map.serialize_entry("status", &self.status.code)?;
+        if let Some(detail) = &self.detail {
+            map.serialize_entry("detail", detail)?;
+        }
+
+        if let Some(instance) = &self.instance {
+            map.serialize_entry("instance", instance)?;
+        }
+
+        for (name, value) in &self.extensions {
+            map.serialize_entry(name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Responds with the problem's status and its JSON representation, with a
+/// Content-Type of `application/problem+json`.
+impl<'r> Responder<'r, 'static> for Problem {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let json = serde_json::to_string(&self).map_err(|e| {
+            error_!("Failed to serialize problem: {}", e);
+            Status::InternalServerError
+        })?;
+
+        Response::build()
+            .status(self.status)
+            .header(ContentType::new("application", "problem+json"))
+            .sized_body(json.len(), Cursor::new(json))
+            .ok()
+    }
+}
+
+impl<E: fmt::Display> From<(Status, E)> for Problem {
+    fn from((status, error): (Status, E)) -> Self {
+        Problem::new(status).detail(error.to_string())
+    }
+}
+
+impl From<FormParseError<'_>> for Problem {
+    fn from(error: FormParseError<'_>) -> Self {
+        let (detail, field) = match error {
+            FormParseError::BadValue(field, _) => ("invalid value for field", field),
+            FormParseError::Unknown(field, _) => ("unexpected field", field),
+            FormParseError::Missing(field) => ("missing field", field),
+            FormParseError::Duplicate(field, _) => ("duplicate field", field),
+            FormParseError::Honeypot(field) => ("invalid honeypot field", field),
+        };
+
+        let field = field.url_decode_lossy();
+        Problem::new(Status::UnprocessableEntity)
+            .detail(format!("{} `{}`", detail, field))
+            .extension("field", field)
+    }
+}
+
+impl From<FormError<'_>> for Problem {
+    fn from(error: FormError<'_>) -> Self {
+        match error {
+            FormDataError::Io(e) => Problem::new(Status::BadRequest)
+                .detail(format!("failed to read form data: {}", e)),
+            FormDataError::Malformed(_) => Problem::new(Status::BadRequest)
+                .detail("malformed form data"),
+            FormDataError::Parse(e, _) => Problem::from(e),
+        }
+    }
+}
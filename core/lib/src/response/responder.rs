@@ -65,15 +65,40 @@ use crate::request::Request;
 ///     streamed. To stream a vector of bytes, use
 ///     `Stream::from(Cursor::new(vec))`.
 ///
+///   * **serde_json::Value**
+///
+///     Sets the `Content-Type` to `application/json`. The value is serialized
+///     into JSON, which is used as the body of the response, which is fixed
+///     size and not streamed.
+///
 ///   * **File**
 ///
 ///     Responds with a streamed body containing the data in the `File`. No
 ///     `Content-Type` is set. To automatically have a `Content-Type` set based
 ///     on the file's extension, use [`NamedFile`](crate::response::NamedFile).
 ///
+///   * **Box&lt;str>**, **Box&lt;\[u8\]>**
+///
+///     Responds as `String` and `Vec<u8>`, respectively.
+///
+///   * **Cow&lt;str>**, **Cow&lt;\[u8\]>**
+///
+///     Responds as the borrowed or owned value.
+///
 ///   * **()**
 ///
-///     Responds with an empty body. No `Content-Type` is set.
+///     Responds with an empty body and a status of `204 No Content`. No
+///     `Content-Type` is set. To respond with an empty body and a different
+///     status, use `(Status, ())`.
+///
+///   * **(Status, T)**
+///
+///     Responds with the response of `T`, with its status set to `Status`.
+///
+///   * **(ContentType, Status, T)**
+///
+///     Responds with the response of `T`, with its `Content-Type` set to
+///     `ContentType` and its status set to `Status`.
 ///
 ///   * **Option&lt;T>**
 ///
@@ -237,6 +262,18 @@ impl<'r> Responder<'r, 'static> for Vec<u8> {
     }
 }
 
+/// Returns a response with Content-Type `application/json` and a fixed-size
+/// body containing the serialized value. Always returns `Ok`.
+impl<'r> Responder<'r, 'static> for serde_json::Value {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let json = self.to_string();
+        Response::build()
+            .header(ContentType::JSON)
+            .sized_body(json.len(), Cursor::new(json))
+            .ok()
+    }
+}
+
 /// Returns a response with a sized body for the file. Always returns `Ok`.
 impl<'r> Responder<'r, 'static> for File {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
@@ -251,10 +288,27 @@ impl<'r> Responder<'r, 'static> for tokio::fs::File {
     }
 }
 
-/// Returns an empty, default `Response`. Always returns `Ok`.
+/// Returns a response with Content-Type `text/plain` and a fixed-size body
+/// containing the string `self`. Always returns `Ok`.
+impl<'r> Responder<'r, 'static> for Box<str> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        String::from(self).respond_to(req)
+    }
+}
+
+/// Returns a response with Content-Type `application/octet-stream` and a
+/// fixed-size body containing the data in `self`. Always returns `Ok`.
+impl<'r> Responder<'r, 'static> for Box<[u8]> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        Vec::from(self).respond_to(req)
+    }
+}
+
+/// Returns an empty response with status `204 No Content`. Always returns
+/// `Ok`.
 impl<'r> Responder<'r, 'static> for () {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
-        Ok(Response::new())
+        Response::build().status(Status::NoContent).ok()
     }
 }
 
@@ -270,6 +324,13 @@ impl<'r, 'o: 'r, R: ?Sized + ToOwned> Responder<'r, 'o> for std::borrow::Cow<'o,
     }
 }
 
+/// Responds with the inner `Responder` in `Box`.
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Box<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        (*self).respond_to(req)
+    }
+}
+
 /// If `self` is `Some`, responds with the wrapped `Responder`. Otherwise prints
 /// a warning message and returns an `Err` of `Status::NotFound`.
 impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Option<R> {
@@ -297,6 +358,36 @@ impl<'r, 'o: 'r, 't: 'o, 'e: 'o, T, E> Responder<'r, 'o> for Result<T, E>
     }
 }
 
+/// Responds with the wrapped `Responder` in `self`, whether it is `Left` or
+/// `Right`.
+///
+/// This allows a handler to return one of two response types without defining
+/// an `enum` for the purpose:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Either;
+/// use rocket::response::Redirect;
+///
+/// #[get("/page/<n>")]
+/// fn page(n: usize) -> Either<Redirect, String> {
+///     match n {
+///         0 => Either::Left(Redirect::to("/page/1")),
+///         n => Either::Right(format!("page {}", n)),
+///     }
+/// }
+/// ```
+impl<'r, 'o: 'r, 'a: 'o, 'b: 'o, A, B> Responder<'r, 'o> for either::Either<A, B>
+    where A: Responder<'r, 'a>, B: Responder<'r, 'b>
+{
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            either::Either::Left(responder) => responder.respond_to(req),
+            either::Either::Right(responder) => responder.respond_to(req),
+        }
+    }
+}
+
 /// The response generated by `Status` depends on the status code itself. The
 /// table below summarizes the functionality:
 ///
@@ -304,13 +395,14 @@ impl<'r, 'o: 'r, 't: 'o, 'e: 'o, T, E> Responder<'r, 'o> for Result<T, E>
 /// |-------------------|---------------------------------------|
 /// | [400, 599]        | Forwards to catcher for given status. |
 /// | 100, [200, 205]   | Empty with status of `self`.          |
+/// | 304               | Empty with status of `self`.          |
 /// | All others.       | Invalid. Errors to `500` catcher.     |
 ///
 /// In short, a client or server error status codes will forward to the
-/// corresponding error catcher, a successful status code less than `206` or
-/// `100` responds with any empty body and the given status code, and all other
-/// status code emit an error message and forward to the `500` (internal server
-/// error) catcher.
+/// corresponding error catcher, a successful status code less than `206`,
+/// `100`, or `304` responds with any empty body and the given status code, and
+/// all other status code emit an error message and forward to the `500`
+/// (internal server error) catcher.
 impl<'r> Responder<'r, 'static> for Status {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
         match self.class() {
@@ -321,6 +413,9 @@ impl<'r> Responder<'r, 'static> for Status {
             StatusClass::Informational if self.code == 100 => {
                 Response::build().status(self).ok()
             }
+            StatusClass::Redirection if self.code == 304 => {
+                Response::build().status(self).ok()
+            }
             _ => {
                 error_!("Invalid status used as responder: {}.", self);
                 Err(Status::InternalServerError)
@@ -328,3 +423,25 @@ impl<'r> Responder<'r, 'static> for Status {
         }
     }
 }
+
+/// Responds with the response of `R`, overriding its status with `Status`.
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for (Status, R) {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let (status, responder) = self;
+        Response::build_from(responder.respond_to(req)?)
+            .status(status)
+            .ok()
+    }
+}
+
+/// Responds with the response of `R`, overriding its `Content-Type` with
+/// `ContentType` and its status with `Status`.
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for (ContentType, Status, R) {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let (content_type, status, responder) = self;
+        Response::build_from(responder.respond_to(req)?)
+            .header(content_type)
+            .status(status)
+            .ok()
+    }
+}
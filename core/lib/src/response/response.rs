@@ -1,10 +1,14 @@
 use std::{io, fmt, str};
 use std::borrow::Cow;
 use std::pin::Pin;
+use std::future::Future;
 
+use futures::future::BoxFuture;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
 use crate::response::{self, Responder};
+use crate::data::{ByteUnit, Capture};
+use crate::data::capture::CaptureReader;
 use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
 
 /// The default size, in bytes, of a chunk for streamed responses.
@@ -462,6 +466,26 @@ impl<'r> ResponseBuilder<'r> {
         self
     }
 
+    /// Runs `cleanup` once the response's body has been written out. See
+    /// [`Response::after_sent()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let response = Response::build()
+    ///     .after_sent(async { /* release a lock */ })
+    ///     .finalize();
+    /// ```
+    #[inline(always)]
+    pub fn after_sent<F>(&mut self, cleanup: F) -> &mut ResponseBuilder<'r>
+        where F: Future<Output = ()> + Send + 'static
+    {
+        self.response.after_sent(cleanup);
+        self
+    }
+
     /// Merges the `other` `Response` into `self` by setting any fields in
     /// `self` to the corresponding value in `other` if they are set in `other`.
     /// Fields in `self` are unchanged if they are not set in `other`. If a
@@ -598,6 +622,7 @@ pub struct Response<'r> {
     status: Option<Status>,
     headers: HeaderMap<'r>,
     body: Option<ResponseBody<'r>>,
+    after_sent: Vec<BoxFuture<'static, ()>>,
 }
 
 impl<'r> Response<'r> {
@@ -624,6 +649,7 @@ impl<'r> Response<'r> {
             status: None,
             headers: HeaderMap::new(),
             body: None,
+            after_sent: vec![],
         }
     }
 
@@ -751,6 +777,41 @@ impl<'r> Response<'r> {
             .filter_map(|header| Cookie::parse_encoded(header).ok())
     }
 
+    /// Merges the cookies in `delta` into the `Set-Cookie` headers of `self`
+    /// so that at most one cookie with a given name, path, and domain is set.
+    /// Headers already in `self` are ordered before `delta`, and the last such
+    /// cookie wins. Malformed headers are kept as-is.
+    pub(crate) fn merge_cookies(&mut self, delta: Vec<Cookie<'static>>) {
+        use crate::http::private::cookie::identity;
+
+        if !self.headers.contains("Set-Cookie") {
+            delta.into_iter().for_each(|cookie| self.adjoin_header(cookie));
+            return;
+        }
+
+        let existing = self.headers.get("Set-Cookie").map(|raw| {
+            let id = Cookie::parse_encoded(raw).ok().map(|c| identity(&c));
+            (id, raw.to_string())
+        });
+
+        let delta = delta.into_iter()
+            .map(|c| (Some(identity(&c)), c.encoded().to_string()));
+
+        let mut merged: Vec<(Option<_>, String)> = vec![];
+        for (id, value) in existing.collect::<Vec<_>>().into_iter().chain(delta) {
+            if id.is_some() {
+                merged.retain(|(other, _)| *other != id);
+            }
+
+            merged.push((id, value));
+        }
+
+        self.remove_header("Set-Cookie");
+        for (_, value) in merged {
+            self.adjoin_raw_header("Set-Cookie", value);
+        }
+    }
+
     /// Returns a [`HeaderMap`] of all of the headers in `self`.
     ///
     /// # Example
@@ -1040,6 +1101,74 @@ impl<'r> Response<'r> {
         self.body.take()
     }
 
+    /// Begins recording the first `limit` bytes of the body as it is written
+    /// out, returning a handle to the recording, or `None` if `self` has no
+    /// body. The capture is finished once the body has been written or
+    /// dropped. See [`Capture`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # rocket::async_test(async {
+    /// let mut response = Response::new();
+    /// response.set_sized_body(13, Cursor::new("Hello, world!"));
+    ///
+    /// let capture = response.capture_body(5.bytes()).unwrap();
+    /// assert_eq!(response.body_string().await.unwrap(), "Hello, world!");
+    /// assert_eq!(capture.bytes(), b"Hello");
+    /// assert_eq!(capture.total(), 13);
+    /// assert!(capture.is_finished());
+    /// # })
+    /// ```
+    pub fn capture_body(&mut self, limit: ByteUnit) -> Option<Capture> {
+        let body = self.take_body()?;
+        let capture = Capture::new(limit);
+        let body: ResponseBody<'r> = match body {
+            Body::Sized(b, n) => Body::Sized(Box::pin(CaptureReader::new(b, capture.clone())), n),
+            Body::Chunked(b, n) => Body::Chunked(Box::pin(CaptureReader::new(b, capture.clone())), n),
+        };
+
+        self.body = Some(body);
+        Some(capture)
+    }
+
+    /// Replaces the body of `self`, if any, with the body returned by `f` when
+    /// passed a reader over the current body. The new body is streamed with
+    /// the chunk size of the current body, if it was streamed, or the
+    /// [`DEFAULT_CHUNK_SIZE`] otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::Response;
+    /// use rocket::tokio::io::AsyncReadExt;
+    ///
+    /// # rocket::async_test(async {
+    /// let mut response = Response::new();
+    /// response.set_sized_body(5, Cursor::new("Hello"));
+    /// response.map_body(|body| body.chain(&b", world!"[..]));
+    /// assert_eq!(response.body_string().await.unwrap(), "Hello, world!");
+    /// # })
+    /// ```
+    pub fn map_body<F, B>(&mut self, f: F)
+        where F: FnOnce(Pin<Box<dyn AsyncRead + Send + 'r>>) -> B,
+              B: AsyncRead + Send + 'r
+    {
+        if let Some(body) = self.take_body() {
+            let (reader, chunk_size): (Pin<Box<dyn AsyncRead + Send + 'r>>, _) = match body {
+                Body::Sized(b, _) => (Box::pin(b), DEFAULT_CHUNK_SIZE),
+                Body::Chunked(b, n) => (b, n),
+            };
+
+            self.set_chunked_body(f(reader), chunk_size);
+        }
+    }
+
     // Makes the `AsyncRead`er in the body empty but leaves the size of the body if
     // it exists. Only meant to be used to handle HEAD requests automatically.
     #[inline(always)]
@@ -1153,6 +1282,46 @@ impl<'r> Response<'r> {
         });
     }
 
+    /// Runs `cleanup` once the response's body has been written out to the
+    /// client, or once writing it has failed, and the body has been dropped.
+    /// Use this for asynchronous cleanup that `Drop` can't perform, such as
+    /// removing a file that the body streams from. Cleanup futures run in the
+    /// order they were added, and, like deferred work, are awaited before the
+    /// server finishes shutting down. With a local client, they run once the
+    /// response has been dispatched.
+    ///
+    /// When responses are merged or joined, the cleanup futures of both run.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::tokio::fs::File;
+    ///
+    /// async fn export() -> std::io::Result<Response<'static>> {
+    ///     let path = std::env::temp_dir().join("export.csv");
+    ///     rocket::tokio::fs::write(&path, "id,name\n").await?;
+    ///
+    ///     let mut response = Response::new();
+    ///     response.set_sized_body(None, File::open(&path).await?);
+    ///     response.after_sent(async move {
+    ///         let _ = rocket::tokio::fs::remove_file(path).await;
+    ///     });
+    ///
+    ///     Ok(response)
+    /// }
+    /// ```
+    pub fn after_sent<F>(&mut self, cleanup: F)
+        where F: Future<Output = ()> + Send + 'static
+    {
+        self.after_sent.push(Box::pin(cleanup));
+    }
+
+    /// Removes and returns the cleanup futures added via `after_sent()`.
+    pub(crate) fn take_after_sent(&mut self) -> Vec<BoxFuture<'static, ()>> {
+        std::mem::take(&mut self.after_sent)
+    }
+
     /// Replaces this response's status and body with that of `other`, if they
     /// exist in `other`. Any headers that exist in `other` replace the ones in
     /// `self`. Any in `self` that aren't in `other` remain in `self`.
@@ -1196,6 +1365,8 @@ impl<'r> Response<'r> {
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
+
+        self.after_sent.extend(other.after_sent);
     }
 
     /// Sets `self`'s status and body to that of `other` if they are not already
@@ -1241,6 +1412,8 @@ impl<'r> Response<'r> {
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
+
+        self.after_sent.extend(other.after_sent);
     }
 }
 
@@ -0,0 +1,85 @@
+use crate::request::Request;
+use crate::response::Response;
+
+/// Trait implemented by transforms of outgoing responses.
+///
+/// A `ResponseTransform` is registered under a mount point via
+/// [`Rocket::transform()`](crate::Rocket::transform()) and is applied to every
+/// response to a request whose path is at or below that mount point, whether
+/// the response was generated by a route or by a catcher. Transforms typically
+/// wrap the body stream via [`Response::map_body()`] to rewrite HTML, inject
+/// content, or compress the body on the fly.
+///
+/// # Ordering
+///
+/// Transforms are applied in the order in which they were registered,
+/// regardless of their mount point, after the response has been produced and
+/// the default `Server` header added but _before_ any response fairings run.
+/// As such, response fairings always observe the transformed response.
+/// Cookies and the stripping of bodies for `HEAD` requests are applied after
+/// both transforms and fairings.
+///
+/// # Implementing
+///
+/// `ResponseTransform` is implemented for all `Fn(&Request, &mut Response)`
+/// closures that are `Send + Sync + 'static`, so most transforms need not
+/// implement the trait directly.
+///
+/// # Example
+///
+/// Append a footer to all HTML responses under `/docs`:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::ContentType;
+/// use rocket::tokio::io::AsyncReadExt;
+///
+/// #[launch]
+/// fn rocket() -> rocket::Rocket {
+///     rocket::ignite()
+///         .transform("/docs", |_: &rocket::Request<'_>, res: &mut rocket::Response<'_>| {
+///             if res.content_type() == Some(ContentType::HTML) {
+///                 res.map_body(|body| body.chain(&b"<footer>Docs</footer>"[..]));
+///             }
+///         })
+/// }
+/// ```
+pub trait ResponseTransform: Send + Sync + 'static {
+    /// Transforms `response`, the response to `request`.
+    fn transform<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>);
+}
+
+impl<F> ResponseTransform for F
+    where F: for<'r, 'a> Fn(&'r Request<'a>, &mut Response<'r>) + Send + Sync + 'static
+{
+    #[inline(always)]
+    fn transform<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        self(request, response)
+    }
+}
+
+/// The transforms registered with an instance of Rocket, in order.
+#[derive(Default)]
+pub(crate) struct Transforms(Vec<(String, Box<dyn ResponseTransform>)>);
+
+impl Transforms {
+    pub(crate) fn add(&mut self, base: &str, transform: Box<dyn ResponseTransform>) {
+        self.0.push((base.trim_end_matches('/').to_string(), transform));
+    }
+
+    /// Applies every transform mounted at or above the request's path to
+    /// `response`, in registration order.
+    pub(crate) fn handle<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let path = request.uri().path();
+        for (base, transform) in &self.0 {
+            let applies = match path.strip_prefix(base.as_str()) {
+                Some(rest) => rest.is_empty() || rest.starts_with('/'),
+                None => false,
+            };
+
+            if applies {
+                transform.transform(request, response);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use yansi::Paint;
 use state::Container;
@@ -8,11 +9,17 @@ use futures::future::FutureExt;
 
 use crate::logger;
 use crate::config::Config;
-use crate::catcher::Catcher;
+use crate::catcher::{Catcher, ErrorPageProvider};
 use crate::router::{Router, Route};
+use crate::request::Request;
 use crate::fairing::{Fairing, Fairings};
+use crate::report::{Reporter, DefaultReporter};
+use crate::load::Load;
+use crate::event::Events;
+use crate::request::defer::DeferredTasks;
+use crate::response::{ResponseTransform, transform::Transforms};
 use crate::logger::PaintExt;
-use crate::shutdown::Shutdown;
+use crate::shutdown::{Shutdown, ShutdownSignal};
 use crate::http::uri::Origin;
 use crate::error::{Error, ErrorKind};
 
@@ -28,6 +35,14 @@ pub struct Rocket {
     pub(crate) fairings: Fairings,
     pub(crate) shutdown_receiver: Option<mpsc::Receiver<()>>,
     pub(crate) shutdown_handle: Shutdown,
+    pub(crate) shutdown_trigger: Option<futures::channel::oneshot::Sender<()>>,
+    pub(crate) shutdown_signal: ShutdownSignal,
+    pub(crate) reporter: Box<dyn Reporter>,
+    pub(crate) load: Arc<Load>,
+    pub(crate) events: Events,
+    pub(crate) deferred: Arc<DeferredTasks>,
+    pub(crate) transforms: Transforms,
+    pub(crate) error_pages: Vec<Box<dyn ErrorPageProvider>>,
 }
 
 impl Rocket {
@@ -82,11 +97,13 @@ impl Rocket {
     #[inline]
     pub fn custom<T: figment::Provider>(provider: T) -> Rocket {
         let (config, figment) = (Config::from(&provider), Figment::from(provider));
-        logger::try_init(config.log_level, config.cli_colors, false);
-        config.pretty_print(&figment);
+        logger::try_init_with(config.log_level, config.log_format, &config.log_destination,
+            config.cli_colors, false);
+        config.print_warnings(&figment);
 
         let managed_state = Container::new();
         let (shutdown_sender, shutdown_receiver) = mpsc::channel(1);
+        let (shutdown_trigger, shutdown_signal) = ShutdownSignal::new();
         Rocket {
             config, figment,
             managed_state,
@@ -96,9 +113,54 @@ impl Rocket {
             catchers: HashMap::new(),
             fairings: Fairings::new(),
             shutdown_receiver: Some(shutdown_receiver),
+            shutdown_trigger: Some(shutdown_trigger),
+            shutdown_signal,
+            reporter: Box::new(DefaultReporter),
+            load: Arc::new(Load::default()),
+            events: Events::default(),
+            deferred: Arc::new(DeferredTasks::default()),
+            transforms: Transforms::default(),
+            error_pages: vec![],
         }
     }
 
+    /// Merges the configuration provider `provider` into `self`'s current
+    /// configuration, with values from `provider` taking precedence over all
+    /// existing sources, and re-extracts [`Config`] from the merged figment.
+    ///
+    /// This allows applications to layer additional configuration sources,
+    /// such as a JSON file or command-line arguments, on top of the default
+    /// sources without rebuilding the figment from scratch. As with
+    /// [`Rocket::custom()`], the `workers` value is not reconfigurable.
+    ///
+    /// # Panics
+    ///
+    /// If the merged configuration fails to extract, this function prints a
+    /// nice error message and then exits the process.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use figment::providers::{Format, Json};
+    ///
+    /// let rocket = rocket::ignite()
+    ///     .reconfigure(Json::file("App.json").nested())
+    ///     .reconfigure(("port", 7777));
+    ///
+    /// assert_eq!(rocket.config().port, 7777);
+    /// ```
+    pub fn reconfigure<T: figment::Provider>(mut self, provider: T) -> Self {
+        let figment = Figment::from(&self.figment).merge(provider);
+        let config = Config::from(&figment);
+        if config != self.config {
+            info!("{}Reconfigured for {}.", Paint::emoji("🔧 "), figment.profile());
+        }
+
+        self.config = config;
+        self.figment = figment;
+        self
+    }
+
     /// Mounts all of the routes in the supplied vector at the given `base`
     /// path. Mounting a route with path `path` at path `base` makes the route
     /// available at `base/path`.
@@ -163,12 +225,6 @@ impl Rocket {
             panic!("Invalid mount point.");
         }
 
-        info!("{}{} {}{}",
-              Paint::emoji("🛰  "),
-              Paint::magenta("Mounting"),
-              Paint::blue(&base_uri),
-              Paint::magenta(":"));
-
         for route in routes.into() {
             let old_route = route.clone();
             let route = route.map_base(|old| format!("{}{}", base, old))
@@ -178,13 +234,55 @@ impl Rocket {
                     panic!("Invalid route URI.");
                 });
 
-            info_!("{}", route);
             self.router.add(route);
         }
 
         self
     }
 
+    /// Registers the response transform `transform` for all requests whose
+    /// path is at or below the mount point `base`.
+    ///
+    /// Transforms are applied in registration order to every matching
+    /// response, including those produced by catchers, before any response
+    /// fairings run. See [`ResponseTransform`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI or contains a query string.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::{Request, Response};
+    /// use rocket::http::Header;
+    ///
+    /// fn no_store(_: &Request<'_>, res: &mut Response<'_>) {
+    ///     res.set_header(Header::new("Cache-Control", "no-store"));
+    /// }
+    ///
+    /// #[launch]
+    /// fn rocket() -> rocket::Rocket {
+    ///     rocket::ignite().transform("/account", no_store)
+    /// }
+    /// ```
+    pub fn transform<T: ResponseTransform>(mut self, base: &str, transform: T) -> Self {
+        let base_uri = Origin::parse(base)
+            .unwrap_or_else(|e| {
+                error!("Invalid transform mount point URI: {}.", Paint::white(base));
+                panic!("Error: {}", e);
+            });
+
+        if base_uri.query().is_some() {
+            error!("Transform mount point '{}' contains query string.", base);
+            panic!("Invalid mount point.");
+        }
+
+        self.transforms.add(base_uri.path(), Box::new(transform));
+        self
+    }
+
     /// Registers all of the catchers in the supplied vector.
     ///
     /// # Examples
@@ -210,11 +308,7 @@ impl Rocket {
     /// ```
     #[inline]
     pub fn register(mut self, catchers: Vec<Catcher>) -> Self {
-        info!("{}{}", Paint::emoji("👾 "), Paint::magenta("Catchers:"));
-
         for catcher in catchers {
-            info_!("{}", catcher);
-
             let existing = match catcher.code {
                 Some(code) => self.catchers.insert(code, catcher),
                 None => self.default_catcher.replace(catcher)
@@ -228,6 +322,33 @@ impl Rocket {
         self
     }
 
+    /// Registers `provider` to render the HTML pages of Rocket's built-in
+    /// default catcher. Providers are consulted in the order they were
+    /// registered, falling back to the built-in pages. See
+    /// [`ErrorPageProvider`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    /// use rocket::http::Status;
+    ///
+    /// #[launch]
+    /// fn rocket() -> rocket::Rocket {
+    ///     rocket::ignite()
+    ///         .error_pages(|status: Status, _: &Request<'_>| match status.code {
+    ///             404 => Some("<h1>Lost?</h1>".into()),
+    ///             _ => None,
+    ///         })
+    /// }
+    /// ```
+    #[inline]
+    pub fn error_pages<P: ErrorPageProvider>(mut self, provider: P) -> Self {
+        self.error_pages.push(Box::new(provider));
+        self
+    }
+
     /// Add `state` to the state managed by this instance of Rocket.
     ///
     /// This method can be called any number of times as long as each call
@@ -324,6 +445,33 @@ impl Rocket {
         self
     }
 
+    /// Sets the [`Reporter`] that emits the [launch report] of this instance
+    /// when it launches, replacing the [`DefaultReporter`].
+    ///
+    /// [launch report]: crate::report::LaunchReport
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::report::LaunchReport;
+    ///
+    /// #[launch]
+    /// fn rocket() -> rocket::Rocket {
+    ///     rocket::ignite()
+    ///         .reporter(|report: &LaunchReport| {
+    ///             for route in &report.routes {
+    ///                 println!("{} {} [{}]", route.method, route.uri, route.rank);
+    ///             }
+    ///         })
+    /// }
+    /// ```
+    #[inline]
+    pub fn reporter<R: Reporter>(mut self, reporter: R) -> Self {
+        self.reporter = Box::new(reporter);
+        self
+    }
+
     /// Returns the active configuration.
     ///
     /// # Example
@@ -348,6 +496,12 @@ impl Rocket {
 
     /// Returns the figment for configured provider.
     ///
+    /// This is the merged view of all configuration sources, including any
+    /// added via [`Rocket::reconfigure()`]. Libraries should read their own
+    /// sections from this figment, for instance via [`Figment::focus()`] or
+    /// [`Figment::extract_inner()`], so that they observe the same sources and
+    /// precedence as Rocket itself.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -356,6 +510,9 @@ impl Rocket {
     ///
     /// let port: u16 = figment.extract_inner("port").unwrap();
     /// assert_eq!(port, rocket.config().port);
+    ///
+    /// // Read a library-specific section, if any.
+    /// let databases = figment.focus("databases");
     /// ```
     #[inline(always)]
     pub fn figment(&self) -> &Figment {
@@ -398,6 +555,41 @@ impl Rocket {
         self.router.routes()
     }
 
+    /// Returns an iterator over the routes that match `request`, in the order
+    /// Rocket would try them when dispatching `request`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[get("/<name>")]
+    /// fn hello(name: String) -> String { name }
+    ///
+    /// #[get("/", rank = 2)]
+    /// fn index() { }
+    ///
+    /// fn main() {
+    ///     let rocket = rocket::ignite().mount("/", routes![hello, index]);
+    ///     let none: [(&str, &[u8]); 0] = [];
+    ///
+    ///     let request = Request::from_raw_parts(&rocket, "GET", b"/bob", none).unwrap();
+    ///     let names: Vec<_> = rocket.matching_routes(&request)
+    ///         .map(|r| r.uri.path())
+    ///         .collect();
+    ///
+    ///     assert_eq!(names, vec!["/<name>"]);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn matching_routes<'a>(
+        &'a self,
+        request: &Request<'_>
+    ) -> impl Iterator<Item = &'a Route> + 'a {
+        self.router.route(request).into_iter()
+    }
+
     /// Returns an iterator over all of the catchers registered on this instance
     /// of Rocket. The order is unspecified.
     ///
@@ -481,9 +673,59 @@ impl Rocket {
             return Err(Error::new(ErrorKind::FailedFairings(failures.to_vec())))
         }
 
+        #[cfg(feature = "uri-checks")]
+        if self.config.check_uris {
+            let dangling = self.dangling_uris();
+            if !dangling.is_empty() {
+                return Err(Error::new(ErrorKind::DanglingUris(dangling)));
+            }
+        }
+
+        #[cfg(not(feature = "uri-checks"))]
+        if self.config.check_uris {
+            warn!("`check_uris` is enabled but the `uri-checks` feature is not.");
+            info_!("`uri!` invocations will not be checked.");
+        }
+
         Ok(())
     }
 
+    /// Returns every `uri!` invocation targeting a route mounted by this
+    /// instance that isn't mounted at the invocation's mount point, as the
+    /// route it expected, and the invocation's location.
+    ///
+    /// The invocations are collected from the entire binary, which may contain
+    /// other applications, so invocations whose route this instance doesn't
+    /// mount at all are ignored.
+    #[cfg(feature = "uri-checks")]
+    fn dangling_uris(&self) -> Vec<(Route, String)> {
+        let mut dangling = vec![];
+        for target in inventory::iter::<crate::UriTarget> {
+            let mount_point = target.mount_point;
+            let mut expected = Route::new(target.method, target.path, crate::handler::dummy)
+                .map_base(|old| format!("{}{}", mount_point, old))
+                .expect("`uri!` mount points and route URIs are checked at compile-time");
+
+            expected.name = Some(target.name);
+            let mut candidates = self.router.routes()
+                .filter(|route| route.name == expected.name)
+                .filter(|route| route.method == expected.method)
+                .filter(|route| route.path.path() == expected.path.path())
+                .peekable();
+
+            if candidates.peek().is_none() {
+                continue;
+            }
+
+            let mounted = candidates.any(|route| route.uri.path() == expected.uri.path());
+            if !mounted {
+                dangling.push((expected, format!("{}:{}", target.file, target.line)));
+            }
+        }
+
+        dangling
+    }
+
     /// Returns a `Future` that drives the server, listening for and dispatching
     /// requests to mounted routes and catchers. The `Future` completes when the
     /// server is shut down via [`Shutdown`], encounters a fatal error, or if
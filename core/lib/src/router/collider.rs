@@ -2,7 +2,7 @@ use super::Route;
 
 use crate::http::MediaType;
 use crate::http::route::Kind;
-use crate::request::Request;
+use crate::request::{Request, ApiVersion};
 
 impl Route {
     /// Determines if two routes can match against some request. That is, if two
@@ -16,13 +16,16 @@ impl Route {
     ///   * If route doesn't specify a format, it gets requests for any format.
     ///
     /// Because query parsing is lenient, and dynamic query parameters can be
-    /// missing, queries do not impact whether two routes collide.
+    /// missing, queries do not impact whether two routes collide. Routes for
+    /// different API versions never collide, while a route without a version
+    /// collides with routes for any version.
     #[doc(hidden)]
     pub fn collides_with(&self, other: &Route) -> bool {
         self.method == other.method
             && self.rank == other.rank
             && paths_collide(self, other)
             && formats_collide(self, other)
+            && versions_collide(self, other)
     }
 
     /// Determines if this route matches against the given request. This means
@@ -37,12 +40,14 @@ impl Route {
     ///   * All static components in the route's query string are also in the
     ///     request query string, though in any position.
     ///     - If no query in route, requests with/without queries match.
+    ///   * The route's API version (if any) is the request's API version.
     #[doc(hidden)]
     pub fn matches(&self, req: &Request<'_>) -> bool {
         self.method == req.method()
             && paths_match(self, req)
             && queries_match(self, req)
             && formats_match(self, req)
+            && versions_match(self, req)
     }
 }
 
@@ -127,22 +132,42 @@ fn formats_collide(route: &Route, other: &Route) -> bool {
 }
 
 fn formats_match(route: &Route, request: &Request<'_>) -> bool {
+    // Use the matcher computed at mount time. If the route's format was set
+    // without refreshing its metadata, fall back to comparing directly.
+    let matches = |b: &MediaType| match (&route.metadata.format, &route.format) {
+        (Some(matcher), _) => matcher.matches(b),
+        (None, Some(a)) => media_types_collide(a, b),
+        (None, None) => true,
+    };
+
+    if route.format.is_none() {
+        return true;
+    }
+
     if !route.method.supports_payload() {
-        route.format.as_ref()
-            .and_then(|a| request.format().map(|b| (a, b)))
-            .map(|(a, b)| media_types_collide(a, b))
-            .unwrap_or(true)
+        request.format().map(matches).unwrap_or(true)
     } else {
-        match route.format.as_ref() {
-            Some(a) => match request.format() {
-                Some(b) if b.specificity() == 2 => media_types_collide(a, b),
-                _ => false
-            }
-            None => true
+        match request.format() {
+            Some(b) if b.specificity() == 2 => matches(b),
+            _ => false
         }
     }
 }
 
+fn versions_collide(route: &Route, other: &Route) -> bool {
+    match (route.version, other.version) {
+        (Some(a), Some(b)) => a == b,
+        _ => true
+    }
+}
+
+fn versions_match(route: &Route, request: &Request<'_>) -> bool {
+    match route.version {
+        Some(version) => ApiVersion::of(request).map_or(false, |v| v.0 == version),
+        None => true
+    }
+}
+
 fn media_types_collide(first: &MediaType, other: &MediaType) -> bool {
     let collide = |a, b| a == "*" || b == "*" || a == b;
     collide(first.top(), other.top()) && collide(first.sub(), other.sub())
@@ -356,6 +381,27 @@ mod tests {
         route_a.collides_with(&route_b)
     }
 
+    fn v_v_collide(v1: Option<u32>, v2: Option<u32>) -> bool {
+        let mut route_a = Route::new(Get, "/", dummy);
+        route_a.version = v1;
+
+        let mut route_b = Route::new(Get, "/", dummy);
+        route_b.version = v2;
+
+        route_a.collides_with(&route_b)
+    }
+
+    #[test]
+    fn test_route_version_collisions() {
+        assert!(v_v_collide(None, None));
+        assert!(v_v_collide(Some(1), None));
+        assert!(v_v_collide(None, Some(2)));
+        assert!(v_v_collide(Some(2), Some(2)));
+
+        assert!(!v_v_collide(Some(1), Some(2)));
+        assert!(!v_v_collide(Some(3), Some(1)));
+    }
+
     #[test]
     fn test_route_content_type_colliions() {
         // non-payload bearing routes always collide
@@ -416,7 +462,12 @@ mod tests {
             route.format = Some(mt_str.parse::<MediaType>().unwrap());
         }
 
-        route.matches(&req)
+        // Check both the direct comparison and the precomputed matcher.
+        let direct = route.matches(&req);
+        route.update_metadata().unwrap();
+        let precomputed = route.matches(&req);
+        assert_eq!(direct, precomputed, "precomputed format matcher disagrees");
+        precomputed
     }
 
     #[test]
@@ -448,6 +499,12 @@ mod tests {
 
         assert!(req_route_mt_collide(Get, "text/html, text/plain", "text/html"));
         assert!(req_route_mt_collide(Get, "text/html; q=0.5, text/xml", "text/xml"));
+        assert!(req_route_mt_collide(Get, "text/xml; q=0.1, text/html; q=0.9", "text/html"));
+        assert!(req_route_mt_collide(Get, "TEXT/HTML", "text/html"));
+        assert!(req_route_mt_collide(Get, "text/html", "text/html; charset=utf-8"));
+        assert!(req_route_mt_collide(Get, "*/*", "application/json"));
+        assert!(req_route_mt_collide(Post, "Application/JSON", "application/json"));
+        assert!(req_route_mt_collide(Post, "application/json; charset=utf-8", "application/json"));
 
         assert!(!req_route_mt_collide(Post, None, "text/html"));
         assert!(!req_route_mt_collide(Post, None, "text/*"));
@@ -465,6 +522,10 @@ mod tests {
 
         assert!(!req_route_mt_collide(Post, None, "text/html"));
         assert!(!req_route_mt_collide(Post, None, "application/json"));
+
+        assert!(!req_route_mt_collide(Get, "text/xml; q=0.1, text/html; q=0.9", "text/xml"));
+        assert!(!req_route_mt_collide(Post, "application/*", "application/json"));
+        assert!(!req_route_mt_collide(Post, "*/*", "application/json"));
     }
 
     fn req_route_path_match(a: &'static str, b: &'static str) -> bool {
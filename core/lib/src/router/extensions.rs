@@ -0,0 +1,185 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A typed map of per-route configuration.
+///
+/// `Extensions` lets middleware attach and read configuration for a specific
+/// route, such as the scopes an authorization layer requires, the TTL a cache
+/// should use, or the tags an OpenAPI generator emits, without maintaining a
+/// global registry keyed by route. Each value is keyed by its type, so at most
+/// one value of any given type is stored. Libraries should define newtypes for
+/// their values to avoid clashing with one another.
+///
+/// Extensions are set either with the `extensions` route attribute argument,
+/// which names a function that populates them, or directly on a [`Route`] via
+/// [`Route::extensions`] or [`Route::with_extension()`]. They are read through
+/// [`Request::route()`] by request guards, handlers, and response fairings.
+///
+/// Values are reference counted, so cloning a `Route`, as happens when it is
+/// mounted, is cheap regardless of what its extensions contain.
+///
+/// [`Route`]: crate::Route
+/// [`Route::extensions`]: crate::Route#structfield.extensions
+/// [`Route::with_extension()`]: crate::Route::with_extension()
+/// [`Request::route()`]: crate::Request::route()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{Request, Extensions};
+/// use rocket::request::{self, FromRequest};
+///
+/// struct Scopes(&'static [&'static str]);
+///
+/// fn admin(extensions: &mut Extensions) {
+///     extensions.insert(Scopes(&["admin"]));
+/// }
+///
+/// struct Authorized;
+///
+/// #[rocket::async_trait]
+/// impl<'a, 'r> FromRequest<'a, 'r> for Authorized {
+///     type Error = ();
+///
+///     async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+///         let required = req.route()
+///             .and_then(|route| route.extensions.get::<Scopes>())
+///             .map_or(&[][..], |scopes| scopes.0);
+///
+///         let granted: Vec<_> = req.headers().get("X-Scope").collect();
+///         match required.iter().all(|scope| granted.contains(scope)) {
+///             true => request::Outcome::Success(Authorized),
+///             false => request::Outcome::Forward(()),
+///         }
+///     }
+/// }
+///
+/// #[get("/admin", extensions = "admin")]
+/// fn admin_panel(_auth: Authorized) -> &'static str {
+///     "welcome, admin"
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty set of extensions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Extensions;
+    ///
+    /// let extensions = Extensions::new();
+    /// assert!(extensions.is_empty());
+    /// ```
+    #[inline(always)]
+    pub fn new() -> Extensions {
+        Extensions::default()
+    }
+
+    /// Sets the extension of type `T` to `value`, replacing any existing value
+    /// of the same type. Returns `self` for chaining.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Extensions;
+    ///
+    /// struct CacheTtl(u32);
+    /// struct Tags(Vec<&'static str>);
+    ///
+    /// let mut extensions = Extensions::new();
+    /// extensions.insert(CacheTtl(30)).insert(Tags(vec!["users"]));
+    /// extensions.insert(CacheTtl(60));
+    ///
+    /// assert_eq!(extensions.len(), 2);
+    /// assert_eq!(extensions.get::<CacheTtl>().map(|ttl| ttl.0), Some(60));
+    /// ```
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> &mut Self {
+        self.map.insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Returns the extension of type `T`, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Extensions;
+    ///
+    /// struct CacheTtl(u32);
+    ///
+    /// let mut extensions = Extensions::new();
+    /// assert!(extensions.get::<CacheTtl>().is_none());
+    ///
+    /// extensions.insert(CacheTtl(30));
+    /// assert_eq!(extensions.get::<CacheTtl>().map(|ttl| ttl.0), Some(30));
+    /// ```
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns `true` if there is an extension of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Extensions;
+    ///
+    /// struct Public;
+    ///
+    /// let mut extensions = Extensions::new();
+    /// assert!(!extensions.contains::<Public>());
+    ///
+    /// extensions.insert(Public);
+    /// assert!(extensions.contains::<Public>());
+    /// ```
+    #[inline]
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Removes the extension of type `T`. Returns `true` if there was one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Extensions;
+    ///
+    /// struct Public;
+    ///
+    /// let mut extensions = Extensions::new();
+    /// extensions.insert(Public);
+    /// assert!(extensions.remove::<Public>());
+    /// assert!(!extensions.remove::<Public>());
+    /// ```
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> bool {
+        self.map.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Returns the number of extensions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if there are no extensions.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.len())
+            .finish()
+    }
+}
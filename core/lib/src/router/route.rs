@@ -4,8 +4,10 @@ use std::convert::From;
 use yansi::Paint;
 
 use crate::codegen::StaticRouteInfo;
+use crate::router::Extensions;
 use crate::handler::Handler;
 use crate::http::{Method, MediaType};
+use crate::http::uncased::{Uncased, UncasedStr};
 use crate::http::route::{RouteSegment, Kind};
 use crate::error::RouteUriError;
 use crate::http::ext::IntoOwned;
@@ -30,7 +32,17 @@ pub struct Route {
     /// The rank of this route. Lower ranks have higher priorities.
     pub rank: isize,
     /// The media type this route matches against, if any.
+    ///
+    /// The matcher used during routing is computed from this field when the
+    /// route is mounted; changes made afterwards are not reflected.
     pub format: Option<MediaType>,
+    /// The API version this route serves, if any. A route with a version only
+    /// matches requests whose [`ApiVersion`](crate::request::ApiVersion) is
+    /// that version, while a route without one matches requests for any or no
+    /// version.
+    pub version: Option<u32>,
+    /// Per-route configuration for use by guards, handlers, and fairings.
+    pub extensions: Extensions,
     /// Cached metadata that aids in routing later.
     pub(crate) metadata: Metadata,
 }
@@ -40,6 +52,40 @@ pub(crate) struct Metadata {
     pub path_segments: Vec<RouteSegment<'static, Path>>,
     pub query_segments: Option<Vec<RouteSegment<'static, Query>>>,
     pub fully_dynamic_query: bool,
+    pub format: Option<FormatMatcher>,
+}
+
+/// A pre-computed matcher for a route's `format`. Parameters are discarded, and
+/// wildcard (`*`) components are resolved ahead of time so that matching
+/// against a request's media type is at most two case-insensitive comparisons.
+#[derive(Debug, Clone)]
+pub(crate) struct FormatMatcher {
+    /// The top-level type, or `None` if it is `*`.
+    top: Option<Uncased<'static>>,
+    /// The sub-level type, or `None` if it is `*`.
+    sub: Option<Uncased<'static>>,
+}
+
+impl FormatMatcher {
+    pub fn new(media_type: &MediaType) -> FormatMatcher {
+        let component = |c: &UncasedStr| match c == "*" {
+            true => None,
+            false => Some(Uncased::from(c.as_str().to_string())),
+        };
+
+        FormatMatcher { top: component(media_type.top()), sub: component(media_type.sub()) }
+    }
+
+    /// Returns `true` if a request with media type `other` can match.
+    #[inline]
+    pub fn matches(&self, other: &MediaType) -> bool {
+        let collide = |a: &Option<Uncased<'static>>, b: &UncasedStr| match a {
+            Some(a) => b == "*" || a.as_uncased_str() == b,
+            None => true
+        };
+
+        collide(&self.top, other.top()) && collide(&self.sub, other.sub())
+    }
 }
 
 impl Metadata {
@@ -60,7 +106,11 @@ impl Metadata {
             None => (None, true)
         };
 
-        Ok(Metadata { path_segments, query_segments, fully_dynamic_query: is_dyn })
+        Ok(Metadata {
+            path_segments, query_segments,
+            fully_dynamic_query: is_dyn,
+            format: route.format.as_ref().map(FormatMatcher::new),
+        })
     }
 }
 
@@ -180,6 +230,8 @@ impl Route {
             uri: route_path,
             name: None,
             format: None,
+            version: None,
+            extensions: Extensions::new(),
             base: Origin::dummy(),
             handler: Box::new(handler),
             metadata: Metadata::default(),
@@ -191,8 +243,8 @@ impl Route {
     }
 
     /// Updates the cached routing metadata. MUST be called whenver the route's
-    /// URI is set or changes.
-    fn update_metadata(&mut self) -> Result<(), RouteUriError> {
+    /// URI or format is set or changes.
+    pub(crate) fn update_metadata(&mut self) -> Result<(), RouteUriError> {
         let new_metadata = Metadata::from(&*self)?;
         self.metadata = new_metadata;
         Ok(())
@@ -274,6 +326,27 @@ impl Route {
         self.update_metadata()?;
         Ok(self)
     }
+
+    /// Sets the extension of type `T` on this route to `value`, replacing any
+    /// existing value of the same type, and returns the route. See
+    /// [`Extensions`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// # use rocket::handler::dummy as handler;
+    ///
+    /// struct CacheTtl(u32);
+    ///
+    /// let route = Route::new(Method::Get, "/", handler).with_extension(CacheTtl(60));
+    /// assert_eq!(route.extensions.get::<CacheTtl>().map(|ttl| ttl.0), Some(60));
+    /// ```
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
 }
 
 impl fmt::Display for Route {
@@ -293,6 +366,10 @@ impl fmt::Display for Route {
             write!(f, " {}", Paint::yellow(format))?;
         }
 
+        if let Some(version) = self.version {
+            write!(f, " {}", Paint::yellow(format!("v{}", version)))?;
+        }
+
         if let Some(name) = self.name {
             write!(f, " {}{}{}",
                    Paint::cyan("("), Paint::magenta(name), Paint::cyan(")"))?;
@@ -311,6 +388,8 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("version", &self.version)
+            .field("extensions", &self.extensions)
             .field("metadata", &self.metadata)
             .finish()
     }
@@ -322,11 +401,18 @@ impl From<StaticRouteInfo> for Route {
         // This should never panic since `info.path` is statically checked.
         let mut route = Route::new(info.method, info.path, info.handler);
         route.format = info.format;
+        route.version = info.version;
+        if let Some(extend) = info.extensions {
+            extend(&mut route.extensions);
+        }
+
         route.name = Some(info.name);
         if let Some(rank) = info.rank {
             route.rank = rank;
         }
 
+        // This can't fail: the URI was already validated by `Route::new()`.
+        let _ = route.update_metadata();
         route
     }
 }
@@ -0,0 +1,408 @@
+//! Serving a Rocket application over SCGI.
+//!
+//! [`Rocket::launch_scgi()`] is an alternative to [`Rocket::launch()`] that
+//! serves the application's routes over the [SCGI] protocol instead of HTTP.
+//! This allows a Rocket application to run behind a front-end server that
+//! speaks SCGI, such as nginx (via `scgi_pass`), Apache (via `mod_proxy_scgi`),
+//! or lighttpd, as is common in shared-hosting environments.
+//!
+//! The front-end server forwards each request as a set of CGI variables. The
+//! request's method is read from `REQUEST_METHOD`, its URI from `REQUEST_URI`
+//! or, if it is not set, from `SCRIPT_NAME`, `PATH_INFO`, and `QUERY_STRING`,
+//! and the client's address from `REMOTE_ADDR` and `REMOTE_PORT`. Headers are
+//! read from the `HTTP_` variables and `CONTENT_TYPE`. Request bodies are
+//! streamed to the application.
+//!
+//! [SCGI]: https://python.ca/scgi/protocol.txt
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! #[get("/")]
+//! fn hello() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! #[rocket::main]
+//! async fn main() {
+//!     let rocket = rocket::ignite().mount("/", routes![hello]);
+//!     if let Err(e) = rocket.launch_scgi().await {
+//!         println!("Rocket failed to launch: {}", e);
+//!     }
+//! }
+//! ```
+//!
+//! With the configured `address` and `port` set to `127.0.0.1` and `9000`, an
+//! nginx configuration forwarding all requests to the application is:
+//!
+//! ```text
+//! location / {
+//!     include scgi_params;
+//!     scgi_pass 127.0.0.1:9000;
+//! }
+//! ```
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use std::net::{IpAddr, SocketAddr};
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt as _, AsyncWrite, AsyncWriteExt};
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::{Rocket, Request, Data};
+use crate::response::{Response, Completion};
+use crate::error::{Error, ErrorKind};
+use crate::ext::AsyncReadExt;
+use crate::server::ConnectionTasks;
+use crate::http::{Method, Status, hyper};
+use crate::http::uri::Origin;
+
+/// The maximum size of the header netstring accepted from the front-end.
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+
+/// The size of the chunks request bodies are streamed in.
+const BODY_CHUNK_SIZE: usize = 4096;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// The CGI variables sent by the front-end server for a request.
+struct Vars(Vec<(String, Vec<u8>)>);
+
+impl Vars {
+    /// Reads the header netstring, `<len>:<name>\0<value>\0...,`, from `reader`.
+    async fn read<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Vars> {
+        let mut len = vec![];
+        (&mut *reader).take(16).read_until(b':', &mut len).await?;
+        if len.pop() != Some(b':') {
+            return Err(invalid("malformed SCGI header length"));
+        }
+
+        let len = std::str::from_utf8(&len).ok()
+            .and_then(|len| len.parse::<usize>().ok())
+            .filter(|&len| len <= MAX_HEADER_SIZE)
+            .ok_or_else(|| invalid("invalid SCGI header length"))?;
+
+        let mut header = vec![0; len + 1];
+        reader.read_exact(&mut header).await?;
+        if header.pop() != Some(b',') {
+            return Err(invalid("missing SCGI header terminator"));
+        }
+
+        let mut vars = vec![];
+        let mut fields = header.split(|&b| b == 0);
+        while let Some(name) = fields.next() {
+            // The header ends with a `\0`, so the last field is empty.
+            if name.is_empty() {
+                break;
+            }
+
+            let name = std::str::from_utf8(name).map_err(|_| invalid("invalid SCGI variable"))?;
+            let value = fields.next().ok_or_else(|| invalid("missing SCGI variable value"))?;
+            vars.push((name.to_string(), value.to_vec()));
+        }
+
+        Ok(Vars(vars))
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0.iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| std::str::from_utf8(v).ok())
+    }
+
+    /// The origin-form URI of the request.
+    fn uri(&self) -> String {
+        if let Some(uri) = self.get("REQUEST_URI") {
+            return uri.to_string();
+        }
+
+        let script = self.get("SCRIPT_NAME").unwrap_or("");
+        let mut uri = format!("{}{}", script, self.get("PATH_INFO").unwrap_or(""));
+        if uri.is_empty() {
+            uri.push('/');
+        }
+
+        match self.get("QUERY_STRING") {
+            Some(query) if !query.is_empty() => format!("{}?{}", uri, query),
+            _ => uri,
+        }
+    }
+
+    fn remote(&self) -> Option<SocketAddr> {
+        let ip: IpAddr = self.get("REMOTE_ADDR")?.parse().ok()?;
+        let port = self.get("REMOTE_PORT").and_then(|p| p.parse().ok()).unwrap_or(0);
+        Some(SocketAddr::new(ip, port))
+    }
+
+    /// The request headers as name-value pairs.
+    fn headers(&self) -> Vec<(String, &[u8])> {
+        self.0.iter()
+            .filter_map(|(name, value)| {
+                let name = match name.as_str() {
+                    "CONTENT_TYPE" => "CONTENT_TYPE",
+                    "CONTENT_LENGTH" => "CONTENT_LENGTH",
+                    name => name.strip_prefix("HTTP_")?,
+                };
+
+                Some((name.replace('_', "-"), value.as_slice()))
+            })
+            .collect()
+    }
+}
+
+/// Converts the CGI variables `vars` into a request to `rocket` for `uri`.
+fn to_request<'r>(
+    rocket: &'r Rocket,
+    vars: &Vars,
+    uri: &'r str
+) -> Result<Request<'r>, String> {
+    let method = vars.get("REQUEST_METHOD").ok_or("missing REQUEST_METHOD")?;
+    let method = method.parse::<Method>()
+        .map_err(|_| format!("Unknown or invalid method: {}", method))?;
+
+    let uri = Origin::parse(uri).map_err(|e| e.to_string())?;
+    let mut request = Request::new(rocket, method, uri);
+    if let Some(remote) = vars.remote() {
+        request.set_remote(remote);
+    }
+
+    let headers = vars.headers();
+    request.add_raw_headers(headers.iter().map(|(k, v)| (k.as_str(), *v)));
+    Ok(request)
+}
+
+/// Writes `response` to `writer`, then runs the response's cleanup. Returns
+/// a record of how writing the response ended.
+async fn send_response<W>(
+    rocket: &Rocket,
+    mut response: Response<'_>,
+    writer: &mut W
+) -> Completion
+    where W: AsyncWrite + Unpin
+{
+    let status = response.status();
+    let after_sent = response.take_after_sent();
+    let mut written = 0;
+    let error = match write_response(response, writer, &mut written).await {
+        Ok(()) => None,
+        Err(e) => {
+            error_!("Failed to write response: {:?}.", e);
+            Some(e)
+        }
+    };
+
+    rocket.run_after_sent(after_sent);
+    Completion::new(status, written, error)
+}
+
+/// Writes `response` to `writer` as a CGI response, adding the number of body
+/// bytes written to `written`.
+async fn write_response<W>(
+    mut response: Response<'_>,
+    writer: &mut W,
+    written: &mut u64
+) -> io::Result<()>
+    where W: AsyncWrite + Unpin
+{
+    let mut head = format!("Status: {}\r\n", response.status()).into_bytes();
+    for header in response.headers().iter() {
+        head.extend_from_slice(header.name().as_str().as_bytes());
+        head.extend_from_slice(b": ");
+        head.extend_from_slice(header.value_bytes());
+        head.extend_from_slice(b"\r\n");
+    }
+
+    let size = match response.body_mut() {
+        Some(body) => body.size().await,
+        None => Some(0),
+    };
+
+    if let Some(size) = size {
+        head.extend_from_slice(format!("Content-Length: {}\r\n", size).as_bytes());
+    }
+
+    head.extend_from_slice(b"\r\n");
+    writer.write_all(&head).await?;
+    if let Some(body) = response.body_mut() {
+        let reader = body.as_reader();
+        let mut buf = vec![0; BODY_CHUNK_SIZE];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+
+            writer.write_all(&buf[..n]).await?;
+            *written += n as u64;
+        }
+    }
+
+    writer.shutdown().await
+}
+
+/// Reads a single request from `stream`, dispatches it, and writes back the
+/// response.
+async fn handle_connection(rocket: Arc<Rocket>, stream: TcpStream) -> io::Result<()> {
+    let mut ticket = rocket.load.enqueue();
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let vars = Vars::read(&mut reader).await?;
+    let content_length = vars.get("CONTENT_LENGTH")
+        .and_then(|len| len.parse::<u64>().ok())
+        .ok_or_else(|| invalid("missing or invalid CONTENT_LENGTH"))?;
+
+    let uri = vars.uri();
+    let mut req = match to_request(&rocket, &vars, &uri) {
+        Ok(req) => req,
+        Err(e) => {
+            error!("Bad incoming SCGI request: {}", e);
+            let dummy = Request::new(&rocket, Method::Get, Origin::dummy());
+            let response = rocket.handle_error(Status::BadRequest, &dummy).await;
+            let completion = send_response(&rocket, response, &mut writer).await;
+            rocket.fairings.handle_complete(&dummy, &completion).await;
+            return Ok(());
+        }
+    };
+
+    if rocket.should_shed(&req) {
+        let response = rocket.handle_error(Status::ServiceUnavailable, &req).await;
+        let completion = send_response(&rocket, response, &mut writer).await;
+        rocket.fairings.handle_complete(&req, &completion).await;
+        return Ok(());
+    }
+
+    ticket.start();
+    let body = reader.take(content_length).into_bytes_stream(BODY_CHUNK_SIZE);
+    let mut data = Data::from_hyp(hyper::Body::wrap_stream(body)).await;
+    let token = rocket.preprocess_request(&mut req, &mut data).await;
+    let response = rocket.dispatch(token, &req, data).await;
+    let completion = send_response(&rocket, response, &mut writer).await;
+    rocket.fairings.handle_complete(&req, &completion).await;
+    rocket.run_deferred(&req.state.deferred);
+    Ok(())
+}
+
+impl Rocket {
+    /// Returns a `Future` that drives the server over SCGI, listening on the
+    /// configured address and port for connections from a front-end server
+    /// and dispatching requests to mounted routes and catchers. The `Future`
+    /// completes when the server is shut down via
+    /// [`Shutdown`](crate::Shutdown) or, if the `ctrlc` configuration option
+    /// is set, when `Ctrl+C` is pressed.
+    ///
+    /// See the [`scgi`](crate::scgi) module documentation for details.
+    ///
+    /// # Error
+    ///
+    /// If there is a problem starting the application, an [`Error`] is
+    /// returned. Note that a value of type `Error` panics if dropped without
+    /// first being inspected. See the [`Error`] documentation for more
+    /// information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[rocket::main]
+    /// async fn main() {
+    /// # if false {
+    ///     let result = rocket::ignite().launch_scgi().await;
+    ///     assert!(result.is_ok());
+    /// # }
+    /// }
+    /// ```
+    pub async fn launch_scgi(mut self) -> Result<(), Error> {
+        use std::net::ToSocketAddrs;
+
+        self.prelaunch_check().await?;
+
+        let full_addr = format!("{}:{}", self.config.address, self.config.port);
+        let addr = full_addr.to_socket_addrs()
+            .map(|mut addrs| addrs.next().expect(">= 1 socket addr"))
+            .map_err(|e| Error::new(ErrorKind::Io(e)))?;
+
+        let listener = TcpListener::bind(addr).await.map_err(ErrorKind::Bind)?;
+
+        // Freeze managed state for synchronization-free accesses later.
+        self.managed_state.freeze();
+
+        // Determine the port we actually bound to.
+        self.config.port = listener.local_addr().map(|a| a.port()).unwrap_or(0);
+        let full_addr = format!("{}:{}", self.config.address, self.config.port);
+
+        // Run the launch fairings, then report the launch.
+        self.fairings.handle_launch(&self);
+        self.reporter.report(&self.launch_report(format!("scgi://{}", full_addr)));
+
+        // If `ctrl-c` shutdown is enabled, also stop when it is pressed.
+        let ctrlc_enabled = self.config.ctrlc;
+        let ctrlc = async move {
+            if ctrlc_enabled {
+                match tokio::signal::ctrl_c().await {
+                    Ok(()) => return,
+                    Err(e) => {
+                        warn!("Failed to enable `ctrl-c` graceful signal shutdown.");
+                        info_!("Error: {}", e);
+                    }
+                }
+            }
+
+            futures::future::pending::<()>().await
+        };
+
+        tokio::pin!(ctrlc);
+        let mut shutdown_receiver = self.shutdown_receiver.take()
+            .expect("shutdown receiver has already been used");
+
+        let shutdown_trigger = self.shutdown_trigger.take();
+
+        let rocket = Arc::new(self);
+        let connections = ConnectionTasks::default();
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        error_!("Failed to accept SCGI connection: {}", e);
+                        continue;
+                    }
+                },
+                _ = &mut ctrlc => break,
+                _ = shutdown_receiver.recv() => break,
+            };
+
+            let rocket = rocket.clone();
+            connections.spawn(async move {
+                if let Err(e) = handle_connection(rocket, stream).await {
+                    error_!("Failed to handle SCGI request: {}", e);
+                }
+            });
+        }
+
+        // Signal any long-lived responses still being written to finish.
+        if let Some(trigger) = shutdown_trigger {
+            let _ = trigger.send(());
+        }
+
+        // Then give connections and deferred work `shutdown_grace` seconds to
+        // finish, closing any connections that are still open afterwards.
+        let idle = async { tokio::join!(connections.idle(), rocket.deferred.idle()); };
+        match rocket.config.shutdown_grace {
+            0 => idle.await,
+            n => if tokio::time::timeout(Duration::from_secs(n as u64), idle).await.is_err() {
+                warn!("Shutdown grace period expired. Abandoning remaining work.");
+            }
+        }
+
+        let closed = connections.abort_all();
+        if closed > 0 {
+            warn_!("Closed {} remaining connection(s).", closed);
+        }
+
+        Ok(())
+    }
+}
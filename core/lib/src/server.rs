@@ -1,24 +1,32 @@
 use std::io;
 use std::sync::Arc;
-
-use futures::stream::StreamExt;
-use futures::future::{Future, BoxFuture};
-use tokio::sync::oneshot;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{Stream, StreamExt};
+use futures::channel::mpsc;
+use futures::sink::SinkExt;
+use futures::future::{Future, BoxFuture, AbortHandle, Abortable};
+use parking_lot::Mutex;
+use tokio::sync::{oneshot, Notify};
 use yansi::Paint;
 
 use crate::Rocket;
 use crate::handler;
-use crate::request::{Request, FormItems};
+use crate::request::{Request, FormItems, Disconnect, Timings, TimingsMode, ServerTiming};
+use crate::request::disconnect::Connection;
 use crate::data::Data;
-use crate::response::{Body, Response};
+use crate::response::{Body, Response, Completion};
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
-use crate::logger::PaintExt;
 use crate::ext::AsyncReadExt;
 
-use crate::http::{Method, Status, Header, hyper};
+use crate::http::{Method, Status, Header, hyper, hyper::Bytes};
 use crate::http::private::{Listener, Connection, Incoming};
-use crate::http::uri::Origin;
+use crate::http::uri::{Origin, Segments};
 
 // A token returned to force the execution of one method before another.
 pub(crate) struct Token;
@@ -35,7 +43,10 @@ async fn hyper_service_fn(
     // This future must return a hyper::Response, but the response body might
     // borrow from the request. Instead, write the body in another future that
     // sends the response metadata (and a body channel) prior.
-    let (tx, rx) = oneshot::channel();
+    let (mut tx, rx) = oneshot::channel();
+    let connection = Arc::new(Connection::default());
+    let mut guard = DisconnectGuard(Some(connection.clone()));
+    let mut ticket = rocket.load.enqueue();
 
     tokio::spawn(async move {
         // Get all of the information from Hyper.
@@ -56,50 +67,134 @@ async fn hyper_service_fn(
                 // handler) instead of doing this.
                 let dummy = Request::new(&rocket, Method::Get, Origin::dummy());
                 let r = rocket.handle_error(Status::BadRequest, &dummy).await;
-                return rocket.send_response(r, tx).await;
+                let completion = rocket.send_response(r, &connection, tx).await;
+                return rocket.fairings.handle_complete(&dummy, &completion).await;
             }
         };
 
+        // Shed the request before doing any work if the policy says so.
+        if rocket.should_shed(&req) {
+            let r = rocket.handle_error(Status::ServiceUnavailable, &req).await;
+            let completion = rocket.send_response(r, &connection, tx).await;
+            return rocket.fairings.handle_complete(&req, &completion).await;
+        }
+
         // Retrieve the data from the hyper body.
+        ticket.start();
         let mut data = Data::from_hyp(h_body).await;
 
-        // Dispatch the request to get a response, then write that response out.
+        // Dispatch the request to get a response, then write that response
+        // out. If the client disconnects first, hyper drops the receiving end
+        // of `tx`: cancel the handler by dropping the dispatch future.
+        req.state.connection = connection.clone();
         let token = rocket.preprocess_request(&mut req, &mut data).await;
-        let r = rocket.dispatch(token, &mut req, data).await;
-        rocket.send_response(r, tx).await;
+        let r = tokio::select! {
+            r = rocket.dispatch(token, &req, data) => r,
+            _ = tx.closed() => {
+                connection.disconnect();
+                info_!("{}", Paint::yellow("Client disconnected. Request cancelled."));
+                return rocket.run_deferred(&req.state.deferred);
+            }
+        };
+
+        let completion = rocket.send_response(r, &connection, tx).await;
+        rocket.fairings.handle_complete(&req, &completion).await;
+        rocket.run_deferred(&req.state.deferred);
     });
 
-    // Receive the response written to `tx` by the task above.
-    rx.await.map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    // Receive the response written to `tx` by the task above. If this future
+    // is dropped by hyper because the client went away, `guard` marks the
+    // request as disconnected immediately, even if the handler isn't yielding.
+    let response = rx.await.map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+    guard.0 = None;
+    response
+}
+
+/// Marks a request as disconnected when dropped while armed.
+struct DisconnectGuard(Option<Arc<Connection>>);
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if let Some(connection) = self.0.take() {
+            connection.disconnect();
+        }
+    }
+}
+
+/// The response body handed to hyper. Hyper drops the body when the client
+/// disconnects, so if it is dropped before the end of the stream is reached,
+/// the connection is marked as disconnected.
+struct BodyStream {
+    rx: mpsc::Receiver<io::Result<Bytes>>,
+    guard: DisconnectGuard,
+}
+
+impl Stream for BodyStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = Pin::new(&mut self.rx).poll_next(cx);
+        if let Poll::Ready(None) = next {
+            self.guard.0 = None;
+        }
+
+        next
+    }
 }
 
 impl Rocket {
-    /// Wrapper around `make_response` to log a success or failure.
+    /// Wrapper around `make_response` to log a success or failure and record
+    /// how writing the response ended.
     #[inline]
     async fn send_response(
         &self,
-        response: Response<'_>,
+        mut response: Response<'_>,
+        connection: &Arc<Connection>,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
-    ) {
-        match self.make_response(response, tx).await {
-            Ok(()) => info_!("{}", Paint::green("Response succeeded.")),
-            Err(e) => error_!("Failed to write response: {:?}.", e),
-        }
+    ) -> Completion {
+        let status = response.status();
+        let after_sent = response.take_after_sent();
+        let mut written = 0;
+        let error = match self.make_response(response, connection, tx, &mut written).await {
+            Ok(()) => {
+                info_!("{}", Paint::green("Response succeeded."));
+                None
+            }
+            Err(e) => {
+                error_!("Failed to write response: {:?}.", e);
+                Some(e)
+            }
+        };
+
+        // The body has been written and dropped: run the response's cleanup.
+        self.run_after_sent(after_sent);
+        Completion::new(status, written, error)
     }
 
-    /// Attempts to create a hyper response from `response` and send it to `tx`.
+    /// Attempts to create a hyper response from `response` and send it to `tx`,
+    /// adding the number of body bytes handed to hyper to `written`.
     #[inline]
     async fn make_response(
         &self,
         mut response: Response<'_>,
+        connection: &Arc<Connection>,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
+        written: &mut u64,
     ) -> io::Result<()> {
-        let mut hyp_res = hyper::Response::builder()
-            .status(response.status().code);
+        let status = response.status();
+        let mut hyp_res = hyper::Response::builder().status(status.code);
+        if status.has_custom_reason() {
+            match hyper::ReasonPhrase::try_from(status.reason.as_bytes()) {
+                Ok(reason) => hyp_res = hyp_res.extension(reason),
+                Err(_) => {
+                    warn_!("Invalid reason phrase in status {} was not sent.", status);
+                }
+            }
+        }
 
         for header in response.headers().iter() {
             let name = header.name.as_str();
-            let value = header.value.as_bytes();
+            let value = header.value_bytes();
             hyp_res = hyp_res.header(name, value);
         }
 
@@ -128,13 +223,28 @@ impl Rocket {
                     Body::Sized(_, _) => crate::response::DEFAULT_CHUNK_SIZE,
                 };
 
-                let (mut sender, hyp_body) = hyper::Body::channel();
-                send_response(hyp_res, hyp_body)?;
+                let (mut sender, rx) = mpsc::channel(0);
+                let guard = DisconnectGuard(Some(connection.clone()));
+                send_response(hyp_res, hyper::Body::wrap_stream(BodyStream { rx, guard }))?;
+
+                // Stop reading from the body as soon as the client goes away.
+                let disconnect = Disconnect(connection.clone());
+                let mut stream = body.as_reader()
+                    .into_bytes_stream(chunk_size)
+                    .take_until(disconnect);
 
-                let mut stream = body.as_reader().into_bytes_stream(chunk_size);
                 while let Some(next) = stream.next().await {
-                    sender.send_data(next?).await
+                    let chunk = next?;
+                    let len = chunk.len() as u64;
+                    sender.send(Ok(chunk)).await
                         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+                    *written += len;
+                }
+
+                if connection.is_disconnected() {
+                    let msg = "client disconnected while the response was being written";
+                    return Err(io::Error::new(io::ErrorKind::BrokenPipe, msg));
                 }
             }
         };
@@ -190,18 +300,65 @@ impl Rocket {
         // Remember if the request is `HEAD` for later body stripping.
         let was_head_request = request.method() == Method::Head;
 
-        // Route the request and run the user's handlers.
-        let mut response = self.route_and_process(request, data).await;
+        // The request's time budget starts now, as it's dispatched.
+        let timeout = request.state.config.request_timeout;
+        if timeout > 0 {
+            let budget = std::time::Duration::from_secs(timeout as u64);
+            request.state.deadline.set(std::time::Instant::now() + budget);
+        }
+
+        // Unless the challenge policy answers the request itself, route the
+        // request and run the user's handlers, within the deadline.
+        let challenged = self.challenge(request).await;
+        let deadline = request.state.deadline.try_get().copied();
+        let mut response = match (challenged, deadline) {
+            (Some(response), _) => response,
+            (None, Some(deadline)) => {
+                let deadline = tokio::time::Instant::from_std(deadline);
+                let handle = self.route_and_process(request, data);
+                match tokio::time::timeout_at(deadline, handle).await {
+                    Ok(response) => response,
+                    Err(_) => {
+                        warn_!("Request exceeded its deadline. Handler cancelled.");
+                        self.handle_error(Status::ServiceUnavailable, request).await
+                    }
+                }
+            }
+            (None, None) => self.route_and_process(request, data).await,
+        };
 
-        // Add a default 'Server' header if it isn't already there.
+        // Add the configured default headers, then a 'Server' header with the
+        // configured identity, if they aren't already there.
         // TODO: If removing Hyper, write out `Date` header too.
-        if !response.headers().contains("Server") {
-            response.set_header(Header::new("Server", "Rocket"));
+        self.add_default_headers(request, &mut response);
+        if let Some(ident) = request.state.config.ident.as_str() {
+            if !response.headers().contains("Server") {
+                response.set_header(Header::new("Server", ident.to_string()));
+            }
         }
 
-        // Run the response fairings.
+        // Apply the response transforms, then run the response fairings.
+        self.transforms.handle(request, &mut response);
         self.fairings.handle_response(request, &mut response).await;
 
+        // Emit the recorded timings, if configured to, and any metrics added
+        // to the request's `ServerTiming`, including by response fairings.
+        let mut metrics = vec![];
+        if request.state.config.timings == TimingsMode::Header {
+            metrics.extend(Timings::of(request).and_then(Timings::header_value));
+        }
+
+        let server_timing = request.state.cache.try_get::<ServerTiming>();
+        metrics.extend(server_timing.and_then(ServerTiming::header_value));
+        if !metrics.is_empty() {
+            response.adjoin_header(Header::new("Server-Timing", metrics.join(", ")));
+        }
+
+        // Set the cookies, including those set by response fairings. Note that
+        // error responses only include cookies set by the error handler. See
+        // `handle_error` for more.
+        response.merge_cookies(request.cookies().take_delta());
+
         // Strip the body if this is a `HEAD` request.
         if was_head_request {
             response.strip_body();
@@ -210,6 +367,26 @@ impl Rocket {
         response
     }
 
+    /// Adds the headers in the `default_headers` configuration parameter that
+    /// apply to `request` to `response` unless it already has them. Since any
+    /// two mount points containing a path are prefixes of one another, visiting
+    /// mount points in reverse order visits the more specific ones first. Like
+    /// the router, matching is by segment, skipping empty segments.
+    fn add_default_headers<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        for (base, headers) in request.state.config.default_headers.iter().rev() {
+            let mut segments = request.uri().segments();
+            if !Segments(base).all(|base| segments.next() == Some(base)) {
+                continue;
+            }
+
+            for (name, value) in headers {
+                if !response.headers().contains(name) {
+                    response.set_header(Header::new(name.clone(), value.clone()));
+                }
+            }
+        }
+    }
+
     /// Route the request and process the outcome to eventually get a response.
     fn route_and_process<'s, 'r: 's>(
         &'s self,
@@ -217,7 +394,7 @@ impl Rocket {
         data: Data
     ) -> impl Future<Output = Response<'r>> + Send + 's {
         async move {
-            let mut response = match self.route(request, data).await {
+            match self.route(request, data).await {
                 Outcome::Success(response) => response,
                 Outcome::Forward(data) => {
                     // There was no matching route. Autohandle `HEAD` requests.
@@ -227,26 +404,16 @@ impl Rocket {
                         // Dispatch the request again with Method `GET`.
                         request._set_method(Method::Get);
 
-                        // Return early so we don't set cookies twice.
                         let try_next: BoxFuture<'_, _> =
                             Box::pin(self.route_and_process(request, data));
-                        return try_next.await;
+                        try_next.await
                     } else {
                         // No match was found and it can't be autohandled. 404.
                         self.handle_error(Status::NotFound, request).await
                     }
                 }
                 Outcome::Failure(status) => self.handle_error(status, request).await,
-            };
-
-            // Set the cookies. Note that error responses will only include
-            // cookies set by the error handler. See `handle_error` for more.
-            let delta_jar = request.cookies().take_delta_jar();
-            for cookie in delta_jar.delta() {
-                response.adjoin_header(cookie);
             }
-
-            response
         }
     }
 
@@ -347,15 +514,9 @@ impl Rocket {
         let proto = self.config.tls.as_ref().map_or("http://", |_| "https://");
         let full_addr = format!("{}:{}", self.config.address, self.config.port);
 
-        // Run the launch fairings.
-        self.fairings.pretty_print_counts();
+        // Run the launch fairings, then report the launch.
         self.fairings.handle_launch(&self);
-
-        launch_info!("{}{} {}{}",
-                     Paint::emoji("🚀 "),
-                     Paint::default("Rocket has launched from").bold(),
-                     Paint::default(proto).bold().underline(),
-                     Paint::default(&full_addr).bold().underline());
+        self.reporter.report(&self.launch_report(format!("{}{}", proto, full_addr)));
 
         // Determine keep-alives.
         let http1_keepalive = self.config.keep_alive != 0;
@@ -364,10 +525,16 @@ impl Rocket {
             n => Some(std::time::Duration::from_secs(n as u64))
         };
 
-        // We need to get this before moving `self` into an `Arc`.
+        // We need to get these before moving `self` into an `Arc`.
         let mut shutdown_receiver = self.shutdown_receiver.take()
             .expect("shutdown receiver has already been used");
 
+        let shutdown_trigger = self.shutdown_trigger.take();
+        let shutdown_signal = self.shutdown_signal.clone();
+        let shutdown_grace = self.config.shutdown_grace;
+        let deferred = self.deferred.clone();
+        let connections = ConnectionTasks::default();
+
         let rocket = Arc::new(self);
         let service = hyper::make_service_fn(move |conn: &<L as Listener>::Connection| {
             let rocket = rocket.clone();
@@ -379,13 +546,110 @@ impl Rocket {
             }
         });
 
-        // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
-        hyper::Server::builder(Incoming::from_listener(listener))
+        // Spawn connections via `connections` so that they can be closed.
+        let server = hyper::Server::builder(Incoming::from_listener(listener))
+            .executor(connections.clone())
             .http1_keepalive(http1_keepalive)
             .http2_keep_alive_interval(http2_keep_alive)
             .serve(service)
-            .with_graceful_shutdown(async move { shutdown_receiver.recv().await; })
-            .await
-            .map_err(|e| Error::new(ErrorKind::Runtime(Box::new(e))))
+            .with_graceful_shutdown(async move {
+                shutdown_receiver.recv().await;
+                if let Some(trigger) = shutdown_trigger {
+                    let _ = trigger.send(());
+                }
+            });
+
+        // Once the server has stopped, wait for any deferred work to finish.
+        let server = async move {
+            let result = server.await;
+            deferred.idle().await;
+            result
+        };
+
+        // Once signalled, long-lived connections and deferred work get
+        // `shutdown_grace` seconds to finish before we stop waiting for them
+        // and close the remaining connections.
+        let grace_period = async move {
+            shutdown_signal.await;
+            match shutdown_grace {
+                0 => futures::future::pending().await,
+                n => tokio::time::sleep(std::time::Duration::from_secs(n as u64)).await,
+            }
+        };
+
+        let result = tokio::select! {
+            result = server => result.map_err(|e| Error::new(ErrorKind::Runtime(Box::new(e)))),
+            _ = grace_period => {
+                warn!("Shutdown grace period expired. Abandoning remaining work.");
+                Ok(())
+            }
+        };
+
+        // Close any connections that are still open.
+        let closed = connections.abort_all();
+        if closed > 0 {
+            warn_!("Closed {} remaining connection(s).", closed);
+        }
+
+        result
+    }
+}
+
+/// The tasks serving connections, tracked so that connections still open when
+/// the shutdown grace period expires can be closed.
+#[derive(Clone, Default)]
+pub(crate) struct ConnectionTasks(Arc<ConnectionTasksInner>);
+
+#[derive(Default)]
+struct ConnectionTasksInner {
+    next_id: AtomicUsize,
+    tasks: Mutex<HashMap<usize, AbortHandle>>,
+    idle: Notify,
+}
+
+impl ConnectionTasks {
+    /// Spawns `task`, tracked until it completes or is aborted.
+    pub(crate) fn spawn<F: Future<Output = ()> + Send + 'static>(&self, task: F) {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        let (handle, registration) = AbortHandle::new_pair();
+        self.0.tasks.lock().insert(id, handle);
+
+        let tasks = self.clone();
+        tokio::spawn(async move {
+            let _ = Abortable::new(task, registration).await;
+            let mut running = tasks.0.tasks.lock();
+            running.remove(&id);
+            if running.is_empty() {
+                tasks.0.idle.notify_waiters();
+            }
+        });
+    }
+
+    /// Waits until no tracked task is running.
+    pub(crate) async fn idle(&self) {
+        loop {
+            let idle = self.0.idle.notified();
+            if self.0.tasks.lock().is_empty() {
+                return;
+            }
+
+            idle.await;
+        }
+    }
+
+    /// Aborts every tracked task, closing its connection. Returns the number
+    /// of tasks aborted.
+    pub(crate) fn abort_all(&self) -> usize {
+        let tasks = std::mem::take(&mut *self.0.tasks.lock());
+        tasks.values().for_each(AbortHandle::abort);
+        tasks.len()
+    }
+}
+
+impl<F> hyper::Executor<F> for ConnectionTasks
+    where F: Future + Send + 'static, F::Output: Send + 'static
+{
+    fn execute(&self, task: F) {
+        self.spawn(async move { task.await; });
     }
 }
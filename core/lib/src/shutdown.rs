@@ -1,6 +1,13 @@
-use crate::request::{FromRequest, Outcome, Request};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::oneshot;
+use futures::future::{FutureExt, Shared};
 use tokio::sync::mpsc;
 
+use crate::request::{FromRequest, Outcome, Request};
+
 /// A request guard to gracefully shutdown a Rocket server.
 ///
 /// A server shutdown is manually requested by calling [`Shutdown::shutdown()`]
@@ -58,3 +65,90 @@ impl<'a, 'r> FromRequest<'a, 'r> for Shutdown {
         Outcome::Success(request.state.shutdown.clone())
     }
 }
+
+/// A request guard and future that resolves when the server begins to shut
+/// down.
+///
+/// Once a shutdown is requested, Rocket stops accepting connections and waits
+/// for pending requests to finish. Long-lived responses, such as event streams
+/// and upgraded WebSocket connections, never finish on their own. They should
+/// instead select on a `ShutdownSignal` and end gracefully when it resolves,
+/// for instance by sending a final event or a close frame.
+///
+/// When the [`shutdown_grace`](crate::Config::shutdown_grace) configuration
+/// parameter is non-zero, Rocket closes any connections still open that many
+/// seconds after the signal resolves. Otherwise, it waits for all connections
+/// to close. The signal also resolves when the `Rocket` instance is dropped.
+///
+/// # Example
+///
+/// An event stream that ends with a final event when the server shuts down:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{State, ShutdownSignal};
+/// use rocket::channel::Channel;
+/// use rocket::response::Stream;
+/// use rocket::tokio::io::AsyncRead;
+///
+/// #[get("/events")]
+/// fn events(
+///     events: State<'_, Channel<String>>,
+///     signal: ShutdownSignal
+/// ) -> Stream<impl AsyncRead + Send> {
+///     let reader = events.subscribe("events")
+///         .until(signal)
+///         .followed_by("bye".into())
+///         .reader(|msg| format!("data: {}\n\n", msg).into());
+///
+///     Stream::from(reader)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ShutdownSignal(pub(crate) Shared<oneshot::Receiver<()>>);
+
+impl ShutdownSignal {
+    pub(crate) fn new() -> (oneshot::Sender<()>, ShutdownSignal) {
+        let (trigger, receiver) = oneshot::channel();
+        (trigger, ShutdownSignal(receiver.shared()))
+    }
+
+    /// Returns `true` if the server has begun to shut down.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::ShutdownSignal;
+    ///
+    /// fn keep_going(signal: &ShutdownSignal) -> bool {
+    ///     !signal.is_triggered()
+    /// }
+    /// ```
+    pub fn is_triggered(&self) -> bool {
+        self.clone().now_or_never().is_some()
+    }
+}
+
+impl std::fmt::Debug for ShutdownSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownSignal").field("triggered", &self.is_triggered()).finish()
+    }
+}
+
+impl Future for ShutdownSignal {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut self.0).poll(cx).map(|_| ())
+    }
+}
+
+#[crate::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for ShutdownSignal {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(request.state.rocket.shutdown_signal.clone())
+    }
+}
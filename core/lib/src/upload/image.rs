@@ -0,0 +1,282 @@
+use std::fmt;
+use std::io;
+use std::convert::TryInto;
+
+use tokio::io::{AsyncReadExt, BufReader};
+
+use crate::request::Request;
+use crate::outcome::Outcome::*;
+use crate::data::{self, Data, FromData};
+use crate::http::{ContentType, Status};
+use crate::upload::{FileTypeError, TempFile};
+
+/// Data guard for an uploaded image: a [`TempFile`] whose contents are a PNG,
+/// JPEG, GIF, WebP, or BMP image.
+///
+/// The image's format and dimensions are read from its header without
+/// decoding it. The guard fails with a status of `415 Unsupported Media Type`
+/// if the body isn't an image in one of the supported formats or doesn't
+/// match the request's declared Content-Type, and with `422 Unprocessable
+/// Entity` if its header is malformed. Otherwise, it fails as [`TempFile`]
+/// does.
+///
+/// Validators such as [`Image::max_dimensions()`] check the image further.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::upload::Image;
+///
+/// #[post("/avatar", data = "<image>")]
+/// async fn avatar(image: Image) -> Result<String, Status> {
+///     image.max_dimensions(4000, 4000).map_err(|_| Status::UnprocessableEntity)?;
+///     image.min_dimensions(64, 64).map_err(|_| Status::UnprocessableEntity)?;
+///
+///     let mut file = image.into_file();
+///     file.persist_to("/var/www/avatars/latest").await
+///         .map_err(|_| Status::InternalServerError)?;
+///
+///     Ok("saved".into())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Image {
+    file: TempFile,
+    format: ContentType,
+    width: u32,
+    height: u32,
+}
+
+/// An error reading or validating an [`Image`].
+#[derive(Debug)]
+pub enum ImageError {
+    /// The file isn't an image in a supported format, or isn't of its
+    /// declared type.
+    Unsupported,
+    /// The image's header is malformed.
+    Malformed,
+    /// The image's `(width, height)` are outside of the validated bounds.
+    Dimensions(u32, u32),
+    /// Receiving or reading the file failed.
+    Io(io::Error),
+}
+
+const FORMATS: &[ContentType] = &[
+    ContentType::PNG, ContentType::JPEG, ContentType::GIF, ContentType::WEBP, ContentType::BMP,
+];
+
+impl Image {
+    /// Reads the format and dimensions of the image in `file`.
+    pub async fn new(file: TempFile) -> Result<Image, ImageError> {
+        let format = file.validate_type(FORMATS).await.map_err(|e| match e {
+            FileTypeError::Io(e) => ImageError::Io(e),
+            _ => ImageError::Unsupported,
+        })?;
+
+        let head = file.head().await.map_err(ImageError::Io)?;
+        let dimensions = match format {
+            ref f if *f == ContentType::JPEG => jpeg_dimensions(&file).await?,
+            ref f if *f == ContentType::PNG => png_dimensions(&head),
+            ref f if *f == ContentType::GIF => gif_dimensions(&head),
+            ref f if *f == ContentType::WEBP => webp_dimensions(&head),
+            _ => bmp_dimensions(&head),
+        };
+
+        let (width, height) = dimensions.ok_or(ImageError::Malformed)?;
+        Ok(Image { file, format, width, height })
+    }
+
+    /// Returns the image's format.
+    pub fn format(&self) -> &ContentType {
+        &self.format
+    }
+
+    /// Returns the image's width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the image's height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the image's `(width, height)` in pixels.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the file containing the image.
+    pub fn file(&self) -> &TempFile {
+        &self.file
+    }
+
+    /// Returns the file containing the image, to persist it.
+    pub fn into_file(self) -> TempFile {
+        self.file
+    }
+
+    /// Succeeds if the image is at most `width` wide and `height` high.
+    pub fn max_dimensions(&self, width: u32, height: u32) -> Result<(), ImageError> {
+        match self.width <= width && self.height <= height {
+            true => Ok(()),
+            false => Err(ImageError::Dimensions(self.width, self.height)),
+        }
+    }
+
+    /// Succeeds if the image is at least `width` wide and `height` high.
+    pub fn min_dimensions(&self, width: u32, height: u32) -> Result<(), ImageError> {
+        match self.width >= width && self.height >= height {
+            true => Ok(()),
+            false => Err(ImageError::Dimensions(self.width, self.height)),
+        }
+    }
+
+    /// Succeeds if the image has at most `pixels` pixels.
+    pub fn max_pixels(&self, pixels: u64) -> Result<(), ImageError> {
+        match self.width as u64 * self.height as u64 <= pixels {
+            true => Ok(()),
+            false => Err(ImageError::Dimensions(self.width, self.height)),
+        }
+    }
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u24_le(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16)
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn png_dimensions(head: &[u8]) -> Option<(u32, u32)> {
+    if head.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    Some((u32_be(head, 16)?, u32_be(head, 20)?))
+}
+
+fn gif_dimensions(head: &[u8]) -> Option<(u32, u32)> {
+    Some((u16_le(head, 6)?, u16_le(head, 8)?))
+}
+
+fn bmp_dimensions(head: &[u8]) -> Option<(u32, u32)> {
+    let width = i32::from_le_bytes(head.get(18..22)?.try_into().ok()?);
+    let height = i32::from_le_bytes(head.get(22..26)?.try_into().ok()?);
+    Some((width.checked_abs()? as u32, height.checked_abs()? as u32))
+}
+
+fn webp_dimensions(head: &[u8]) -> Option<(u32, u32)> {
+    match head.get(12..16)? {
+        // Lossy: 14-bit dimensions after the frame tag and start code.
+        b"VP8 " => Some((u16_le(head, 26)? & 0x3FFF, u16_le(head, 28)? & 0x3FFF)),
+        // Lossless: 14-bit dimensions, minus one, after the signature byte.
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(head.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        // Extended: 24-bit dimensions, minus one, after the flags.
+        b"VP8X" => Some((u24_le(head, 24)? + 1, u24_le(head, 27)? + 1)),
+        _ => None,
+    }
+}
+
+/// Scans the JPEG's segments for a start-of-frame segment, which may follow
+/// arbitrarily large metadata segments.
+async fn jpeg_dimensions(file: &TempFile) -> Result<Option<(u32, u32)>, ImageError> {
+    let file = tokio::fs::File::open(file.path()).await.map_err(ImageError::Io)?;
+    let mut reader = BufReader::new(file);
+    match read_jpeg_dimensions(&mut reader).await {
+        Ok(dimensions) => Ok(dimensions),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(ImageError::Io(e)),
+    }
+}
+
+async fn read_jpeg_dimensions<R>(reader: &mut R) -> io::Result<Option<(u32, u32)>>
+    where R: tokio::io::AsyncRead + Unpin
+{
+    // Skip the start-of-image marker.
+    reader.read_u16().await?;
+    loop {
+        if reader.read_u8().await? != 0xFF {
+            return Ok(None);
+        }
+
+        let mut marker = reader.read_u8().await?;
+        while marker == 0xFF {
+            marker = reader.read_u8().await?;
+        }
+
+        match marker {
+            // Markers without a segment.
+            0x01 | 0xD0..=0xD8 => continue,
+            // End of image or start of scan before any frame.
+            0xD9 | 0xDA => return Ok(None),
+            _ => {}
+        }
+
+        let len = reader.read_u16().await?;
+        if len < 2 {
+            return Ok(None);
+        }
+
+        // Start-of-frame markers, excluding DHT, JPG, and DAC.
+        if let 0xC0..=0xCF = marker {
+            if marker != 0xC4 && marker != 0xC8 && marker != 0xCC {
+                let _precision = reader.read_u8().await?;
+                let height = reader.read_u16().await? as u32;
+                let width = reader.read_u16().await? as u32;
+                return Ok(Some((width, height)));
+            }
+        }
+
+        let skip = (len - 2) as u64;
+        let mut segment = (&mut *reader).take(skip);
+        let skipped = tokio::io::copy(&mut segment, &mut tokio::io::sink()).await?;
+        if skipped != skip {
+            return Ok(None);
+        }
+    }
+}
+
+#[crate::async_trait]
+impl FromData for Image {
+    type Error = ImageError;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        let file = match TempFile::from_data(req, data).await {
+            Success(file) => file,
+            Failure((status, e)) => return Failure((status, ImageError::Io(e))),
+            Forward(data) => return Forward(data),
+        };
+
+        match Image::new(file).await {
+            Ok(image) => Success(image),
+            Err(e @ ImageError::Unsupported) => Failure((Status::UnsupportedMediaType, e)),
+            Err(e @ ImageError::Io(_)) => Failure((Status::InternalServerError, e)),
+            Err(e) => Failure((Status::UnprocessableEntity, e)),
+        }
+    }
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Unsupported => write!(f, "file is not a supported image"),
+            ImageError::Malformed => write!(f, "image header is malformed"),
+            ImageError::Dimensions(w, h) => write!(f, "image dimensions {}x{} are invalid", w, h),
+            ImageError::Io(e) => write!(f, "failed to read image: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
@@ -0,0 +1,28 @@
+//! Types and handlers for receiving file uploads.
+//!
+//! * [`TempFile`]: a data guard streaming the request body to a temporary
+//!   file, which can then be persisted locally or in any [`Storage`].
+//! * [`sniff()`]: content type detection from magic bytes, used by
+//!   [`TempFile::validate_type()`] to reject disguised uploads.
+//! * `Image`: a data guard for image uploads that reads their format and
+//!   dimensions. Requires the `images` feature.
+//! * [`resumable`]: uploads that survive connection drops via the [tus]
+//!   resumable upload protocol.
+//!
+//! [tus]: https://tus.io/protocols/resumable-upload.html
+
+mod temp_file;
+mod storage;
+mod sniff;
+#[cfg(feature = "images")]
+mod image;
+
+pub mod resumable;
+
+pub use self::temp_file::TempFile;
+pub use self::storage::{Storage, LocalStorage};
+pub use self::sniff::{sniff, is_executable, FileTypeError};
+
+#[cfg(feature = "images")]
+#[cfg_attr(nightly, doc(cfg(feature = "images")))]
+pub use self::image::{Image, ImageError};
@@ -0,0 +1,430 @@
+//! Resumable uploads via the [tus] protocol.
+//!
+//! A [`Resumable`] handler, mounted at a base path, implements version `1.0.0`
+//! of the tus resumable upload protocol with the `creation` and `termination`
+//! extensions. Clients create an upload with a `POST` to the base, then send
+//! the upload's bytes in one or more `PATCH` requests to the returned
+//! `Location`. If a connection drops, the client asks for the upload's offset
+//! with a `HEAD` request and resumes from there. Uploads are kept in an
+//! [`UploadStorage`]; [`FileStorage`], which keeps uploads in a directory, is
+//! provided.
+//!
+//! | Method    | Path     | Action                                           |
+//! |-----------|----------|--------------------------------------------------|
+//! | `OPTIONS` | `/`      | Reports the supported versions and extensions.   |
+//! | `POST`    | `/`      | Creates an upload of `Upload-Length` bytes.      |
+//! | `HEAD`    | `/<id>`  | Reports the upload's `Upload-Offset`.            |
+//! | `PATCH`   | `/<id>`  | Appends the body at `Upload-Offset`.             |
+//! | `DELETE`  | `/<id>`  | Removes the upload.                              |
+//!
+//! Only one `PATCH` per upload is processed at a time; concurrent `PATCH`
+//! requests for the same upload fail with `409 Conflict`, as do those whose
+//! `Upload-Offset` isn't the upload's current offset.
+//!
+//! [tus]: https://tus.io/protocols/resumable-upload.html
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::data::ToByteUnit;
+//! use rocket::upload::resumable::{Resumable, FileStorage};
+//!
+//! #[launch]
+//! fn rocket() -> rocket::Rocket {
+//!     let uploads = Resumable::new(FileStorage::new("/tmp/uploads"))
+//!         .max_size(4.gibibytes());
+//!
+//!     rocket::ignite().mount("/uploads", uploads)
+//! }
+//! ```
+//!
+//! Once an upload's offset reaches its length, the upload is complete. With
+//! `FileStorage`, its contents are then in the file at
+//! [`FileStorage::path()`].
+
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+
+use crate::{Request, Response, Route};
+use crate::data::{ByteUnit, Data};
+use crate::handler::{Handler, Outcome};
+use crate::http::{Method, RawStr, Status};
+
+/// The supported version of the tus protocol.
+const TUS_VERSION: &str = "1.0.0";
+
+/// The supported tus protocol extensions.
+const TUS_EXTENSIONS: &str = "creation,termination";
+
+/// The length of generated upload IDs.
+const ID_LEN: usize = 32;
+
+/// The state of an upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadInfo {
+    /// The number of bytes received so far.
+    pub offset: u64,
+    /// The total number of bytes in the upload, from `Upload-Length`.
+    pub length: u64,
+    /// The raw value of the `Upload-Metadata` header at creation, if any.
+    pub metadata: Option<String>,
+}
+
+impl UploadInfo {
+    /// Returns `true` if every byte of the upload has been received.
+    pub fn is_complete(&self) -> bool {
+        self.offset >= self.length
+    }
+}
+
+/// Storage for resumable uploads.
+///
+/// Uploads are identified by IDs generated by the storage. IDs appear in
+/// URIs, so they should be URI-safe and unguessable; storages must treat IDs
+/// they did not generate as unknown. [`Resumable`] validates offsets and
+/// lengths and serializes appends to an upload before calling into storage.
+#[crate::async_trait]
+pub trait UploadStorage: Send + Sync + 'static {
+    /// Creates an empty upload of `length` bytes with the given raw
+    /// `metadata` and returns its ID.
+    async fn create(&self, length: u64, metadata: Option<&str>) -> io::Result<String>;
+
+    /// Returns the state of the upload `id` or `None` if it doesn't exist.
+    async fn info(&self, id: &str) -> io::Result<Option<UploadInfo>>;
+
+    /// Appends the bytes in `data` to the upload `id`, whose current offset
+    /// is `offset`, and returns the new offset. Bytes read before an error
+    /// occurs should be kept so that the client can resume after them.
+    async fn append(
+        &self,
+        id: &str,
+        offset: u64,
+        data: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<u64>;
+
+    /// Removes the upload `id`. Returns `false` if it doesn't exist.
+    async fn remove(&self, id: &str) -> io::Result<bool>;
+}
+
+/// An [`UploadStorage`] keeping uploads in a directory.
+///
+/// The bytes of an upload with ID `id` are kept in the file `id` in the
+/// directory, and its length and metadata in the file `id.info`. The
+/// directory is created when the first upload is.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+/// The contents of an upload's `.info` file.
+#[derive(Serialize, Deserialize)]
+struct StoredInfo {
+    length: u64,
+    metadata: Option<String>,
+}
+
+impl FileStorage {
+    /// Returns a storage keeping uploads in the directory `root`.
+    pub fn new<P: AsRef<Path>>(root: P) -> FileStorage {
+        FileStorage { root: root.as_ref().into() }
+    }
+
+    /// Returns the path to the file containing the bytes of the upload `id`,
+    /// or `None` if `id` is not a valid upload ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::upload::resumable::FileStorage;
+    ///
+    /// let storage = FileStorage::new("/tmp/uploads");
+    /// assert!(storage.path("Hd9sJ20x").is_some());
+    /// assert!(storage.path("../etc/passwd").is_none());
+    /// ```
+    pub fn path(&self, id: &str) -> Option<PathBuf> {
+        match !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            true => Some(self.root.join(id)),
+            false => None,
+        }
+    }
+
+    fn info_path(&self, id: &str) -> Option<PathBuf> {
+        self.path(id).map(|path| path.with_extension("info"))
+    }
+}
+
+/// Maps a `NotFound` error to `None`.
+fn found<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[crate::async_trait]
+impl UploadStorage for FileStorage {
+    async fn create(&self, length: u64, metadata: Option<&str>) -> io::Result<String> {
+        fs::create_dir_all(&self.root).await?;
+        let id: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(ID_LEN)
+            .map(char::from)
+            .collect();
+
+        let info = StoredInfo { length, metadata: metadata.map(String::from) };
+        let info = serde_json::to_vec(&info)?;
+        fs::write(self.info_path(&id).expect("generated ID"), info).await?;
+        fs::File::create(self.root.join(&id)).await?;
+        Ok(id)
+    }
+
+    async fn info(&self, id: &str) -> io::Result<Option<UploadInfo>> {
+        let (path, info_path) = match (self.path(id), self.info_path(id)) {
+            (Some(path), Some(info_path)) => (path, info_path),
+            _ => return Ok(None),
+        };
+
+        let info = match found(fs::read(info_path).await)? {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+
+        let info: StoredInfo = serde_json::from_slice(&info)?;
+        let offset = match found(fs::metadata(path).await)? {
+            Some(metadata) => metadata.len(),
+            None => return Ok(None),
+        };
+
+        Ok(Some(UploadInfo { offset, length: info.length, metadata: info.metadata }))
+    }
+
+    async fn append(
+        &self,
+        id: &str,
+        offset: u64,
+        data: &mut (dyn AsyncRead + Send + Unpin),
+    ) -> io::Result<u64> {
+        let path = self.path(id).ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        let mut file = fs::OpenOptions::new().append(true).open(path).await?;
+        let result = tokio::io::copy(data, &mut file).await;
+        file.flush().await?;
+        Ok(offset + result?)
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<bool> {
+        let (path, info_path) = match (self.path(id), self.info_path(id)) {
+            (Some(path), Some(info_path)) => (path, info_path),
+            _ => return Ok(false),
+        };
+
+        let removed = found(fs::remove_file(info_path).await)?.is_some();
+        found(fs::remove_file(path).await)?;
+        Ok(removed)
+    }
+}
+
+/// A handler implementing the tus resumable upload protocol.
+///
+/// See the [module level documentation](crate::upload::resumable) for
+/// details.
+#[derive(Clone)]
+pub struct Resumable {
+    storage: Arc<dyn UploadStorage>,
+    max_size: Option<ByteUnit>,
+    patching: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Marks an upload as being appended to until dropped.
+struct Patching<'a> {
+    patching: &'a Mutex<HashSet<String>>,
+    id: &'a str,
+}
+
+impl<'a> Patching<'a> {
+    fn start(patching: &'a Mutex<HashSet<String>>, id: &'a str) -> Option<Patching<'a>> {
+        match patching.lock().insert(id.to_string()) {
+            true => Some(Patching { patching, id }),
+            false => None,
+        }
+    }
+}
+
+impl Drop for Patching<'_> {
+    fn drop(&mut self) {
+        self.patching.lock().remove(self.id);
+    }
+}
+
+impl Resumable {
+    /// Returns a handler keeping uploads in `storage`, without a maximum
+    /// upload size.
+    pub fn new<S: UploadStorage>(storage: S) -> Resumable {
+        Resumable {
+            storage: Arc::new(storage),
+            max_size: None,
+            patching: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Sets the maximum size of an upload, advertised as `Tus-Max-Size`.
+    /// Creating a larger upload fails with `413 Payload Too Large`.
+    pub fn max_size(mut self, max_size: ByteUnit) -> Resumable {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    fn response<'r>(status: Status) -> Response<'r> {
+        Response::build()
+            .status(status)
+            .raw_header("Tus-Resumable", TUS_VERSION)
+            .finalize()
+    }
+
+    fn options<'r>(&self) -> Response<'r> {
+        let mut response = Self::response(Status::NoContent);
+        response.set_raw_header("Tus-Version", TUS_VERSION);
+        response.set_raw_header("Tus-Extension", TUS_EXTENSIONS);
+        if let Some(max_size) = self.max_size {
+            response.set_raw_header("Tus-Max-Size", max_size.as_u64().to_string());
+        }
+
+        response
+    }
+
+    async fn create<'r>(&self, req: &'r Request<'_>) -> io::Result<Response<'r>> {
+        let length = req.headers().get_one("Upload-Length").and_then(|l| l.parse::<u64>().ok());
+        let length = match length {
+            Some(length) => length,
+            None => return Ok(Self::response(Status::BadRequest)),
+        };
+
+        if self.max_size.map_or(false, |max| length > max.as_u64()) {
+            return Ok(Self::response(Status::PayloadTooLarge));
+        }
+
+        let metadata = req.headers().get_one("Upload-Metadata");
+        let id = self.storage.create(length, metadata).await?;
+        let base = req.route().map_or("", |route| route.base()).trim_end_matches('/');
+        let mut response = Self::response(Status::Created);
+        response.set_raw_header("Location", format!("{}/{}", base, id));
+        Ok(response)
+    }
+
+    async fn head<'r>(&self, id: &str) -> io::Result<Response<'r>> {
+        let info = match self.storage.info(id).await? {
+            Some(info) => info,
+            None => return Ok(Self::response(Status::NotFound)),
+        };
+
+        let mut response = Self::response(Status::Ok);
+        response.set_raw_header("Upload-Offset", info.offset.to_string());
+        response.set_raw_header("Upload-Length", info.length.to_string());
+        response.set_raw_header("Cache-Control", "no-store");
+        if let Some(metadata) = info.metadata {
+            response.set_raw_header("Upload-Metadata", metadata);
+        }
+
+        Ok(response)
+    }
+
+    async fn patch<'r>(
+        &self,
+        req: &'r Request<'_>,
+        id: &str,
+        data: Data
+    ) -> io::Result<Response<'r>> {
+        let is_offset_stream = req.content_type()
+            .map_or(false, |ct| ct.top() == "application" && ct.sub() == "offset+octet-stream");
+
+        if !is_offset_stream {
+            return Ok(Self::response(Status::UnsupportedMediaType));
+        }
+
+        let offset = req.headers().get_one("Upload-Offset").and_then(|o| o.parse::<u64>().ok());
+        let offset = match offset {
+            Some(offset) => offset,
+            None => return Ok(Self::response(Status::BadRequest)),
+        };
+
+        let _patching = match Patching::start(&self.patching, id) {
+            Some(patching) => patching,
+            None => return Ok(Self::response(Status::Conflict)),
+        };
+
+        let info = match self.storage.info(id).await? {
+            Some(info) => info,
+            None => return Ok(Self::response(Status::NotFound)),
+        };
+
+        if info.offset != offset {
+            return Ok(Self::response(Status::Conflict));
+        }
+
+        let remaining = ByteUnit::from(info.length - info.offset);
+        let new_offset = self.storage.append(id, offset, &mut data.open(remaining)).await?;
+        let mut response = Self::response(Status::NoContent);
+        response.set_raw_header("Upload-Offset", new_offset.to_string());
+        Ok(response)
+    }
+
+    async fn delete<'r>(&self, id: &str) -> io::Result<Response<'r>> {
+        match self.storage.remove(id).await? {
+            true => Ok(Self::response(Status::NoContent)),
+            false => Ok(Self::response(Status::NotFound)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Handler for Resumable {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        if req.method() == Method::Options {
+            return Outcome::Success(self.options());
+        }
+
+        if req.headers().get_one("Tus-Resumable") != Some(TUS_VERSION) {
+            let mut response = Self::response(Status::PreconditionFailed);
+            response.set_raw_header("Tus-Version", TUS_VERSION);
+            return Outcome::Success(response);
+        }
+
+        let id = req.get_param::<&RawStr>(0).and_then(|id| id.ok()).map(|id| id.as_str());
+        let result = match (req.method(), id) {
+            (Method::Post, _) => self.create(req).await,
+            (Method::Head, Some(id)) => self.head(id).await,
+            (Method::Patch, Some(id)) => self.patch(req, id, data).await,
+            (Method::Delete, Some(id)) => self.delete(id).await,
+            _ => return Outcome::Forward(data),
+        };
+
+        match result {
+            Ok(response) => Outcome::Success(response),
+            Err(e) => {
+                error_!("Resumable upload storage failed: {}.", e);
+                Outcome::Failure(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+impl Into<Vec<Route>> for Resumable {
+    fn into(self) -> Vec<Route> {
+        vec![
+            Route::new(Method::Options, "/", self.clone()),
+            Route::new(Method::Post, "/", self.clone()),
+            Route::new(Method::Head, "/<id>", self.clone()),
+            Route::new(Method::Patch, "/<id>", self.clone()),
+            Route::new(Method::Delete, "/<id>", self),
+        ]
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+use std::io;
+
+use crate::http::ContentType;
+
+/// The number of leading bytes needed to recognize every known format.
+pub(crate) const SNIFF_LEN: u64 = 264;
+
+/// Returns the content type of a file beginning with `bytes`, determined by
+/// its magic bytes, or `None` if it isn't recognized.
+///
+/// `bytes` should contain at least the first 264 bytes of the file, or all of
+/// it if it's shorter. Executables are never recognized; use
+/// [`is_executable()`] to detect them.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket::upload::sniff;
+///
+/// assert_eq!(sniff(b"\x89PNG\r\n\x1a\n..."), Some(ContentType::PNG));
+/// assert_eq!(sniff(b"%PDF-1.7"), Some(ContentType::PDF));
+/// assert_eq!(sniff(b"MZ\x90\x00"), None);
+/// ```
+pub fn sniff(bytes: &[u8]) -> Option<ContentType> {
+    let at = |offset: usize, magic: &[u8]| bytes.get(offset..offset + magic.len()) == Some(magic);
+    let content_type = match bytes {
+        _ if at(0, b"\x89PNG\r\n\x1a\n") => ContentType::PNG,
+        _ if at(0, b"\xFF\xD8\xFF") => ContentType::JPEG,
+        _ if at(0, b"GIF87a") || at(0, b"GIF89a") => ContentType::GIF,
+        _ if at(0, b"RIFF") && at(8, b"WEBP") => ContentType::WEBP,
+        _ if at(0, b"RIFF") && at(8, b"WAVE") => ContentType::WAV,
+        _ if at(4, b"ftypavif") || at(4, b"ftypavis") => ContentType::AVIF,
+        _ if at(4, b"ftypqt  ") => ContentType::MOV,
+        _ if at(4, b"ftyp") => ContentType::MP4,
+        _ if at(0, b"BM") => ContentType::BMP,
+        _ if at(0, b"\x00\x00\x01\x00") => ContentType::Icon,
+        _ if at(0, b"II*\x00") || at(0, b"MM\x00*") => ContentType::TIFF,
+        _ if at(0, b"%PDF-") => ContentType::PDF,
+        _ if at(0, b"PK\x03\x04") || at(0, b"PK\x05\x06") => ContentType::ZIP,
+        _ if at(0, b"\x1F\x8B") => ContentType::GZIP,
+        _ if at(257, b"ustar") => ContentType::TAR,
+        _ if at(0, b"\x00asm") => ContentType::WASM,
+        _ if at(0, b"fLaC") => ContentType::FLAC,
+        _ if at(0, b"OggS") => ContentType::OGG,
+        _ if at(0, b"\x1A\x45\xDF\xA3") => ContentType::WEBM,
+        _ if at(0, b"wOFF") => ContentType::WOFF,
+        _ if at(0, b"wOF2") => ContentType::WOFF2,
+        _ if at(0, b"\x00\x01\x00\x00") => ContentType::TTF,
+        _ if at(0, b"OTTO") => ContentType::OTF,
+        _ => return None,
+    };
+
+    Some(content_type)
+}
+
+/// Returns `true` if a file beginning with `bytes` is a native executable or
+/// shared library: a Windows PE, ELF, or Mach-O binary.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::upload::is_executable;
+///
+/// assert!(is_executable(b"\x7fELF\x02\x01\x01"));
+/// assert!(is_executable(b"MZ\x90\x00"));
+/// assert!(!is_executable(b"GIF89a"));
+/// ```
+pub fn is_executable(bytes: &[u8]) -> bool {
+    const MAGIC: &[&[u8]] = &[
+        b"MZ",
+        b"\x7fELF",
+        b"\xFE\xED\xFA\xCE", b"\xFE\xED\xFA\xCF",
+        b"\xCE\xFA\xED\xFE", b"\xCF\xFA\xED\xFE",
+        b"\xCA\xFE\xBA\xBE",
+    ];
+
+    MAGIC.iter().any(|magic| bytes.starts_with(magic))
+}
+
+/// The error returned by
+/// [`TempFile::validate_type()`](crate::upload::TempFile::validate_type()).
+#[derive(Debug)]
+pub enum FileTypeError {
+    /// The file is an executable.
+    Executable,
+    /// The file's type couldn't be determined from its contents.
+    Unrecognized,
+    /// The file's detected type isn't one of the allowed types.
+    NotAllowed(ContentType),
+    /// The file's contents don't match its declared type.
+    Mismatch {
+        /// The content type the upload was declared as.
+        declared: ContentType,
+        /// The content type determined from the file's contents.
+        detected: ContentType,
+    },
+    /// Reading the file failed.
+    Io(io::Error),
+}
+
+/// Checks that `bytes`, the beginning of a file declared as `declared`, is
+/// of one of the `allowed` types, and returns that type. A missing or
+/// `application/octet-stream` declaration matches any detected type.
+pub(crate) fn validate(
+    bytes: &[u8],
+    declared: Option<&ContentType>,
+    allowed: &[ContentType]
+) -> Result<ContentType, FileTypeError> {
+    if is_executable(bytes) {
+        return Err(FileTypeError::Executable);
+    }
+
+    let detected = sniff(bytes).ok_or(FileTypeError::Unrecognized)?;
+    if let Some(declared) = declared.filter(|d| **d != ContentType::Binary) {
+        if *declared != detected {
+            let declared = declared.clone();
+            return Err(FileTypeError::Mismatch { declared, detected });
+        }
+    }
+
+    match allowed.contains(&detected) {
+        true => Ok(detected),
+        false => Err(FileTypeError::NotAllowed(detected)),
+    }
+}
+
+impl fmt::Display for FileTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileTypeError::Executable => write!(f, "file is an executable"),
+            FileTypeError::Unrecognized => write!(f, "file type is unrecognized"),
+            FileTypeError::NotAllowed(ct) => write!(f, "file type {} is not allowed", ct),
+            FileTypeError::Mismatch { declared, detected } => {
+                write!(f, "file declared as {} is {}", declared, detected)
+            }
+            FileTypeError::Io(e) => write!(f, "failed to read file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FileTypeError {}
@@ -0,0 +1,130 @@
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use tokio::fs;
+
+/// A persistence target for uploaded files.
+///
+/// [`TempFile::persist_in()`](crate::upload::TempFile::persist_in()) hands a
+/// storage the path to a complete temporary file, its length, and the key to
+/// store it under. [`LocalStorage`], which stores files in a directory, is the
+/// default; adapters for object stores such as S3 or GCS implement `Storage`
+/// by streaming the file at the temporary path to the store. The temporary
+/// file is removed once `persist()` succeeds, if the storage hasn't moved it.
+///
+/// # Example
+///
+/// A storage that writes files to a blob store through an async client:
+///
+/// ```rust
+/// use std::io;
+/// use std::path::Path;
+///
+/// use rocket::tokio::fs::File;
+/// use rocket::tokio::io::AsyncRead;
+/// use rocket::upload::Storage;
+///
+/// # struct Client;
+/// # impl Client {
+/// #     async fn put<R: AsyncRead>(&self, _: &str, _: &str, _: u64, _: R) -> io::Result<()> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// struct BlobStorage {
+///     client: Client,
+///     bucket: String,
+/// }
+///
+/// #[rocket::async_trait]
+/// impl Storage for BlobStorage {
+///     async fn persist(&self, temp: &Path, len: u64, key: &str) -> io::Result<()> {
+///         let file = File::open(temp).await?;
+///         self.client.put(&self.bucket, key, len, file).await
+///     }
+/// }
+/// ```
+#[crate::async_trait]
+pub trait Storage: Send + Sync {
+    /// Stores the `len` bytes in the file at `temp` under `key`.
+    async fn persist(&self, temp: &Path, len: u64, key: &str) -> io::Result<()>;
+}
+
+/// A [`Storage`] keeping files in a directory on the local filesystem.
+///
+/// Keys are relative paths within the directory; keys containing `..` or
+/// absolute paths are rejected with an error of kind `InvalidInput`. Parent
+/// directories are created as needed. Files are moved into place, falling back
+/// to a copy when the temporary file is on another filesystem.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::upload::{LocalStorage, TempFile};
+///
+/// #[post("/avatar", data = "<file>")]
+/// async fn upload(mut file: TempFile) -> std::io::Result<()> {
+///     let storage = LocalStorage::new("/var/www/avatars");
+///     file.persist_in(&storage, "user-1.png").await
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Returns a storage keeping files in the directory `root`.
+    pub fn new<P: AsRef<Path>>(root: P) -> LocalStorage {
+        LocalStorage { root: root.as_ref().into() }
+    }
+
+    /// Returns the path the file with key `key` is stored at, or `None` if
+    /// `key` is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use rocket::upload::LocalStorage;
+    ///
+    /// let storage = LocalStorage::new("/uploads");
+    /// assert_eq!(storage.path("a/b.png").unwrap(), Path::new("/uploads/a/b.png"));
+    /// assert!(storage.path("../b.png").is_none());
+    /// assert!(storage.path("/b.png").is_none());
+    /// ```
+    pub fn path(&self, key: &str) -> Option<PathBuf> {
+        let key = Path::new(key);
+        let valid = key.components().all(|c| matches!(c, Component::Normal(_)));
+        match valid && key.components().next().is_some() {
+            true => Some(self.root.join(key)),
+            false => None,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Storage for LocalStorage {
+    async fn persist(&self, temp: &Path, _: u64, key: &str) -> io::Result<()> {
+        let path = self.path(key).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("invalid key `{}`", key))
+        })?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        move_file(temp, &path).await
+    }
+}
+
+/// Moves the file at `from` to `to`, copying it if renaming fails, as it does
+/// across filesystems.
+pub(crate) async fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+
+    fs::copy(from, to).await?;
+    fs::remove_file(from).await
+}
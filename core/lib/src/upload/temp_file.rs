@@ -0,0 +1,243 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rand::{distributions::Alphanumeric, Rng};
+
+use crate::request::Request;
+use crate::outcome::Outcome::*;
+use crate::data::{self, Data, FromData, ByteUnit, ToByteUnit};
+#[cfg(feature = "checksums")]
+use crate::data::{DataStream, Checksums, Digests, checksum};
+use crate::http::{ContentType, Status};
+use crate::upload::storage::{self, Storage};
+use crate::upload::sniff::{self, FileTypeError};
+
+/// The default limit for [`TempFile`] bodies.
+const DEFAULT_LIMIT: ByteUnit = ByteUnit::Mebibyte(1);
+
+/// The length of the random part of temporary file names.
+const NAME_LEN: usize = 16;
+
+/// Data guard that streams the body to a temporary file.
+///
+/// The body is written to a new file in the system's temporary directory as
+/// it is received, so that large uploads aren't held in memory. The file is
+/// removed when the `TempFile` is dropped unless it was persisted with
+/// [`TempFile::persist_to()`] or [`TempFile::persist_in()`].
+///
+/// The body is limited by the limit named `file`, which defaults to 1MiB. A
+/// longer body fails with a status of `413 Payload Too Large`; failing to write
+/// the file fails with a status of `500 Internal Server Error`.
+///
+/// With the `checksums` feature enabled, digests of the body are computed as
+/// it is written: those of the algorithms in the managed
+/// [`Checksums`](crate::data::Checksums), if any, and those specified by the
+/// request's `Content-MD5` and `Digest` headers. The latter are verified; on
+/// a mismatch, the guard fails with a status of `422 Unprocessable Entity`.
+/// The digests are available via `TempFile::digests()`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::upload::TempFile;
+///
+/// #[post("/upload", data = "<file>")]
+/// async fn upload(mut file: TempFile) -> std::io::Result<String> {
+///     file.persist_to("/var/www/uploads/latest").await?;
+///     Ok(format!("saved {} bytes", file.len()))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TempFile {
+    path: PathBuf,
+    len: u64,
+    content_type: Option<ContentType>,
+    persisted: bool,
+    #[cfg(feature = "checksums")]
+    digests: Digests,
+}
+
+impl TempFile {
+    /// Returns the number of bytes in the file.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the path to the file. After [`TempFile::persist_to()`], this is
+    /// the path the file was persisted to. After [`TempFile::persist_in()`],
+    /// the file at the path may no longer exist.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the Content-Type of the request the file was received in, if
+    /// any.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// Returns the digests computed while the file was written.
+    #[cfg(feature = "checksums")]
+    #[cfg_attr(nightly, doc(cfg(feature = "checksums")))]
+    pub fn digests(&self) -> &Digests {
+        &self.digests
+    }
+
+    /// Returns the content type of the file determined from its first bytes,
+    /// or `None` if it isn't recognized. See [`sniff()`](crate::upload::sniff()).
+    pub async fn sniff(&self) -> io::Result<Option<ContentType>> {
+        Ok(sniff::sniff(&self.head().await?))
+    }
+
+    /// Validates the file's contents against its declared content type and
+    /// the `allowed` types, returning the detected type.
+    ///
+    /// Validation fails if the file is an executable, if its type can't be
+    /// determined from its contents, if the determined type differs from the
+    /// declared Content-Type, or if it isn't one of `allowed`. A missing or
+    /// `application/octet-stream` declared type matches any detected type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{ContentType, Status};
+    /// use rocket::upload::TempFile;
+    ///
+    /// const IMAGES: &[ContentType] = &[ContentType::PNG, ContentType::JPEG];
+    ///
+    /// #[post("/avatar", data = "<file>")]
+    /// async fn avatar(mut file: TempFile) -> Result<&'static str, Status> {
+    ///     file.validate_type(IMAGES).await.map_err(|_| Status::UnsupportedMediaType)?;
+    ///     file.persist_to("/var/www/avatars/latest").await
+    ///         .map_err(|_| Status::InternalServerError)?;
+    ///
+    ///     Ok("saved")
+    /// }
+    /// ```
+    pub async fn validate_type(
+        &self,
+        allowed: &[ContentType]
+    ) -> Result<ContentType, FileTypeError> {
+        let head = self.head().await.map_err(FileTypeError::Io)?;
+        sniff::validate(&head, self.content_type(), allowed)
+    }
+
+    /// Reads the first bytes of the file, enough to determine its type.
+    pub(crate) async fn head(&self) -> io::Result<Vec<u8>> {
+        use tokio::io::AsyncReadExt;
+
+        let mut head = Vec::new();
+        let file = tokio::fs::File::open(&self.path).await?;
+        file.take(sniff::SNIFF_LEN).read_to_end(&mut head).await?;
+        Ok(head)
+    }
+
+    /// Moves the file to `path` on the local filesystem. The file is no
+    /// longer removed when `self` is dropped.
+    pub async fn persist_to<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        storage::move_file(&self.path, path).await?;
+        self.path = path.into();
+        self.persisted = true;
+        Ok(())
+    }
+
+    /// Persists the file in `storage` under `key`, then removes the temporary
+    /// file unless `storage` moved it.
+    pub async fn persist_in<S>(&mut self, storage: &S, key: &str) -> io::Result<()>
+        where S: Storage + ?Sized
+    {
+        storage.persist(&self.path, self.len, key).await?;
+        self.persisted = true;
+        match tokio::fs::remove_file(&self.path).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "checksums")]
+impl TempFile {
+    /// Writes `stream` to the file, computing the digests the request's
+    /// headers and the managed `Checksums` ask for.
+    async fn write_digesting(&mut self, req: &Request<'_>, stream: DataStream) -> io::Result<u64> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut algorithms = checksum::requested_algorithms(req);
+        if let Some(checksums) = req.managed_state::<Checksums>() {
+            algorithms.extend(&checksums.0);
+        }
+
+        let mut stream = stream.digesting(&algorithms);
+        let mut file = tokio::fs::File::create(&self.path).await?;
+        let len = tokio::io::copy(&mut stream, &mut file).await?;
+        file.flush().await?;
+        self.digests = stream.digests();
+        Ok(len)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[crate::async_trait]
+impl FromData for TempFile {
+    type Error = io::Error;
+
+    async fn from_data(req: &Request<'_>, data: Data) -> data::Outcome<Self, Self::Error> {
+        let name: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(NAME_LEN)
+            .map(char::from)
+            .collect();
+
+        let mut file = TempFile {
+            path: std::env::temp_dir().join(format!("rocket-upload-{}", name)),
+            len: 0,
+            content_type: req.content_type().cloned(),
+            persisted: false,
+            #[cfg(feature = "checksums")]
+            digests: Digests::default(),
+        };
+
+        // Read one byte past the limit to determine whether there's more.
+        let limit = req.limits().get("file").unwrap_or(DEFAULT_LIMIT);
+        let stream = data.open(limit + 1.bytes());
+
+        #[cfg(not(feature = "checksums"))]
+        let result = stream.stream_to_file(&file.path).await;
+
+        #[cfg(feature = "checksums")]
+        let result = file.write_digesting(req, stream).await;
+
+        file.len = match result {
+            Ok(len) => len,
+            Err(e) => return Failure((Status::InternalServerError, e)),
+        };
+
+        if file.len > limit.as_u64() {
+            let error = io::Error::new(io::ErrorKind::InvalidData, "body exceeds the limit");
+            return Failure((Status::PayloadTooLarge, error));
+        }
+
+        #[cfg(feature = "checksums")]
+        if let Err(mismatch) = file.digests.verify(req) {
+            let error = io::Error::new(io::ErrorKind::InvalidData, mismatch);
+            return Failure((Status::UnprocessableEntity, error));
+        }
+
+        Success(file)
+    }
+}
@@ -0,0 +1,80 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::ApiVersion;
+use rocket::local::blocking::Client;
+use rocket::http::{Accept, Header, MediaType, Status};
+
+#[get("/users", version = 1)]
+fn users_v1() -> &'static str { "v1 users" }
+
+#[get("/users", version = 2)]
+fn users_v2() -> &'static str { "v2 users" }
+
+#[get("/status")]
+fn status(version: Option<ApiVersion>) -> String {
+    version.map(|v| v.to_string()).unwrap_or_else(|| "none".into())
+}
+
+#[get("/users", rank = 2)]
+fn users_fallback() -> &'static str { "latest users" }
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![users_v1, users_v2, status, users_fallback])
+        .mount("/v1", routes![users_v1])
+        .mount("/v2", routes![users_v2]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn versions_are_selected_by_header() {
+    let client = client();
+    let response = client.get("/users").header(Header::new("Api-Version", "1")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "v1 users");
+
+    let response = client.get("/users").header(Header::new("Api-Version", "v2")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "v2 users");
+}
+
+#[test]
+fn versions_are_selected_by_vendor_media_type() {
+    let client = client();
+    let vendor = MediaType::new("application", "vnd.app.v2+json");
+    let response = client.get("/users").header(Accept::from(vendor)).dispatch();
+    assert_eq!(response.into_string().unwrap(), "v2 users");
+
+    let vendor = MediaType::new("application", "vnd.app.v1");
+    let response = client.get("/users").header(Accept::from(vendor)).dispatch();
+    assert_eq!(response.into_string().unwrap(), "v1 users");
+}
+
+#[test]
+fn versions_are_selected_by_path_prefix() {
+    let client = client();
+    let response = client.get("/v1/users").dispatch();
+    assert_eq!(response.into_string().unwrap(), "v1 users");
+
+    let response = client.get("/v2/users").dispatch();
+    assert_eq!(response.into_string().unwrap(), "v2 users");
+
+    // The header asks for v2, but only a v1 route is mounted at `/v1`.
+    let response = client.get("/v1/users").header(Header::new("Api-Version", "2")).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn unversioned_routes_match_any_version() {
+    let client = client();
+    let response = client.get("/users").dispatch();
+    assert_eq!(response.into_string().unwrap(), "latest users");
+
+    let response = client.get("/users").header(Header::new("Api-Version", "7")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "latest users");
+
+    let response = client.get("/status").header(Header::new("Api-Version", "3")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "v3");
+
+    let response = client.get("/status").dispatch();
+    assert_eq!(response.into_string().unwrap(), "none");
+}
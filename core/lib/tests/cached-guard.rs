@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::request::{self, Request, FromRequest, CachedGuard};
+use rocket::outcome::Outcome::*;
+use rocket::local::blocking::Client;
+
+#[derive(Default)]
+struct Lookups(AtomicUsize);
+
+struct User { admin: bool }
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for User {
+    type Error = &'static str;
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let lookups = try_outcome!(req.guard::<State<'_, Lookups>>().await);
+        lookups.0.fetch_add(1, Ordering::SeqCst);
+        match req.headers().get_one("X-User") {
+            Some("admin") => Success(User { admin: true }),
+            Some("bad") => Failure((Status::Unauthorized, "bad user")),
+            Some(_) => Success(User { admin: false }),
+            None => Forward(()),
+        }
+    }
+}
+
+struct Admin;
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Admin {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match req.guard::<CachedGuard<'_, User>>().await {
+            Success(user) if user.admin => Success(Admin),
+            Failure((status, _)) => Failure((status, ())),
+            _ => Forward(()),
+        }
+    }
+}
+
+#[get("/")]
+fn admin(_admin: Admin) -> &'static str {
+    "admin"
+}
+
+#[get("/", rank = 2)]
+fn user(user: CachedGuard<'_, User>, again: CachedGuard<'_, User>) -> &'static str {
+    assert_eq!(user.admin, again.admin);
+    "user"
+}
+
+#[get("/", rank = 3)]
+fn anonymous() -> &'static str {
+    "anonymous"
+}
+
+#[get("/uncached")]
+fn uncached(_a: User, _b: User) { }
+
+fn lookups(client: &Client) -> usize {
+    client.rocket().state::<Lookups>().unwrap().0.swap(0, Ordering::SeqCst)
+}
+
+fn get(client: &Client, uri: &'static str, user: Option<&'static str>) -> (Status, Option<String>) {
+    let mut request = client.get(uri);
+    if let Some(user) = user {
+        request.add_header(Header::new("X-User", user));
+    }
+
+    let response = request.dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn cached_guard_runs_once() {
+    let rocket = rocket::ignite()
+        .mount("/", routes![admin, user, anonymous, uncached])
+        .manage(Lookups::default());
+
+    let client = Client::tracked(rocket).unwrap();
+
+    assert_eq!(get(&client, "/", Some("admin")), (Status::Ok, Some("admin".into())));
+    assert_eq!(lookups(&client), 1);
+
+    assert_eq!(get(&client, "/", Some("bob")), (Status::Ok, Some("user".into())));
+    assert_eq!(lookups(&client), 1);
+
+    assert_eq!(get(&client, "/", None), (Status::Ok, Some("anonymous".into())));
+    assert_eq!(lookups(&client), 1);
+
+    assert_eq!(get(&client, "/", Some("bad")).0, Status::Unauthorized);
+    assert_eq!(lookups(&client), 1);
+
+    assert_eq!(get(&client, "/uncached", Some("bob")).0, Status::NoContent);
+    assert_eq!(lookups(&client), 2);
+}
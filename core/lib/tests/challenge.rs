@@ -0,0 +1,128 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use rocket::{Clock, Request, Response, Rocket};
+use rocket::challenge::{Challenges, Signals, Verdict};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[catch(403)]
+fn forbidden() -> &'static str {
+    "forbidden"
+}
+
+fn rocket(challenges: Challenges) -> Rocket {
+    rocket::ignite()
+        .mount("/", routes![index])
+        .register(catchers![forbidden])
+        .manage(challenges)
+}
+
+fn by_header(req: &Request<'_>, _: &Signals<'_>) -> Verdict {
+    match req.headers().get_one("X-Verdict") {
+        Some("challenge") => Verdict::Challenge,
+        Some("tarpit") => Verdict::Tarpit(Duration::from_millis(200)),
+        _ => Verdict::Pass,
+    }
+}
+
+fn by_rate(_: &Request<'_>, signals: &Signals<'_>) -> Verdict {
+    match signals.requests() > 2 {
+        true => Verdict::Challenge,
+        false => Verdict::Pass,
+    }
+}
+
+#[test]
+fn passed_requests_are_routed() {
+    let client = Client::tracked(rocket(Challenges::new(by_header))).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+}
+
+#[test]
+fn challenged_requests_use_catcher_by_default() {
+    let client = Client::tracked(rocket(Challenges::new(by_header))).unwrap();
+    let response = client.get("/").header(Header::new("X-Verdict", "challenge")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().unwrap(), "forbidden");
+}
+
+#[test]
+fn challenged_requests_use_configured_response() {
+    fn challenge<'r>(_: &'r Request<'_>) -> rocket::response::Result<'r> {
+        Response::build().status(Status::Unauthorized).ok()
+    }
+
+    let challenges = Challenges::new(by_header).respond_with(challenge);
+    let client = Client::tracked(rocket(challenges)).unwrap();
+    let response = client.get("/").header(Header::new("X-Verdict", "challenge")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Requests that pass are unaffected.
+    assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+}
+
+#[test]
+fn tarpitted_requests_are_delayed_then_routed() {
+    let client = Client::tracked(rocket(Challenges::new(by_header))).unwrap();
+    let start = Instant::now();
+    let response = client.get("/").header(Header::new("X-Verdict", "tarpit")).dispatch();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(response.into_string().unwrap(), "index");
+}
+
+#[test]
+fn requests_are_counted_per_client_and_window() {
+    let clock = Clock::fake();
+    let challenges = Challenges::new(by_rate).window(Duration::from_secs(10));
+    let client = Client::tracked(rocket(challenges).manage(clock.clone())).unwrap();
+
+    let alice: SocketAddr = "10.0.0.1:8000".parse().unwrap();
+    let bob: SocketAddr = "10.0.0.2:8000".parse().unwrap();
+    let get = |remote| client.get("/").remote(remote).dispatch().status();
+
+    assert_eq!(get(alice), Status::Ok);
+    assert_eq!(get(alice), Status::Ok);
+    assert_eq!(get(alice), Status::Forbidden);
+    assert_eq!(get(bob), Status::Ok);
+
+    // A new window starts once the current one has elapsed.
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(get(alice), Status::Ok);
+}
+
+#[test]
+fn ipv6_clients_are_counted_per_network() {
+    let challenges = Challenges::new(by_rate);
+    let client = Client::tracked(rocket(challenges)).unwrap();
+    let get = |remote: &str| client.get("/").remote(remote.parse().unwrap()).dispatch().status();
+
+    assert_eq!(get("[2001:db8:0:1::1]:8000"), Status::Ok);
+    assert_eq!(get("[2001:db8:0:1::2]:8000"), Status::Ok);
+    assert_eq!(get("[2001:db8:0:1::3]:8000"), Status::Forbidden);
+    assert_eq!(get("[2001:db8:0:2::1]:8000"), Status::Ok);
+}
+
+#[test]
+fn oldest_clients_are_forgotten_at_capacity() {
+    let challenges = Challenges::new(by_rate).capacity(2);
+    let client = Client::tracked(rocket(challenges)).unwrap();
+    let get = |remote: &str| client.get("/").remote(remote.parse().unwrap()).dispatch().status();
+
+    assert_eq!(get("10.0.0.1:8000"), Status::Ok);
+    assert_eq!(get("10.0.0.1:8000"), Status::Ok);
+    assert_eq!(get("10.0.0.2:8000"), Status::Ok);
+
+    // A third client evicts the first, whose count starts over.
+    assert_eq!(get("10.0.0.3:8000"), Status::Ok);
+    assert_eq!(get("10.0.0.1:8000"), Status::Ok);
+}
@@ -0,0 +1,75 @@
+use rocket::channel::{Channel, Lag};
+use rocket::futures::StreamExt;
+use rocket::tokio::io::AsyncReadExt;
+
+#[rocket::async_test]
+async fn messages_are_routed_by_topic() {
+    let channel = Channel::new(8);
+    let mut a1 = channel.subscribe("a");
+    let mut a2 = channel.subscribe("a");
+    let mut b = channel.subscribe("b");
+    assert_eq!(channel.subscribers("a"), 2);
+    assert_eq!(channel.subscribers("b"), 1);
+
+    assert_eq!(channel.send("a", 1), 2);
+    assert_eq!(channel.send("b", 2), 1);
+    assert_eq!(channel.send("c", 3), 0);
+
+    assert_eq!(a1.recv().await, Some(1));
+    assert_eq!(a2.recv().await, Some(1));
+    assert_eq!(b.recv().await, Some(2));
+
+    drop(b);
+    assert_eq!(channel.send("b", 4), 0);
+    assert_eq!(channel.subscribers("b"), 0);
+}
+
+#[rocket::async_test]
+async fn lagging_subscribers_skip_by_default() {
+    let channel = Channel::new(2);
+    let mut subscription = channel.subscribe("topic");
+    for i in 0..5 {
+        channel.send("topic", i);
+    }
+
+    assert_eq!(subscription.recv().await, Some(3));
+    assert_eq!(subscription.recv().await, Some(4));
+}
+
+#[rocket::async_test]
+async fn lagging_subscribers_close_on_lag_close() {
+    let channel = Channel::new(2).on_lag(Lag::Close);
+    let mut subscription = channel.subscribe("topic");
+    for i in 0..5 {
+        channel.send("topic", i);
+    }
+
+    assert_eq!(subscription.recv().await, None);
+}
+
+#[rocket::async_test]
+async fn subscriptions_end_when_channel_is_dropped() {
+    let channel = Channel::new(4);
+    let subscription = channel.subscribe("topic");
+    channel.send("topic", "one");
+    channel.send("topic", "two");
+    drop(channel);
+
+    let messages: Vec<_> = subscription.collect().await;
+    assert_eq!(messages, vec!["one", "two"]);
+}
+
+#[rocket::async_test]
+async fn reader_encodes_messages() {
+    let channel = Channel::new(4);
+    let mut reader = channel.subscribe("topic")
+        .reader(|msg: &str| format!("data: {}\n\n", msg).into_bytes());
+
+    channel.send("topic", "hello");
+    channel.send("topic", "world");
+    drop(channel);
+
+    let mut string = String::new();
+    reader.read_to_string(&mut string).await.unwrap();
+    assert_eq!(string, "data: hello\n\ndata: world\n\n");
+}
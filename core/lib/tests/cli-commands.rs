@@ -0,0 +1,36 @@
+#[macro_use] extern crate rocket;
+
+use rocket::cli::Command;
+use rocket::error::ErrorKind;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/")]
+fn other_index() -> &'static str { "other" }
+
+fn rocket() -> rocket::Rocket {
+    rocket::custom(rocket::Config::figment().merge(("log_level", "off")))
+}
+
+#[rocket::async_test]
+async fn check_passes_without_binding() {
+    let rocket = rocket().mount("/", routes![index]);
+    assert!(rocket.execute_command(Command::Check).await.is_ok());
+}
+
+#[rocket::async_test]
+async fn check_detects_collisions() {
+    let rocket = rocket().mount("/", routes![index, other_index]);
+    let error = rocket.execute_command(Command::Check).await.unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Collision(_)));
+}
+
+#[rocket::async_test]
+async fn informational_commands_succeed() {
+    let rocket = rocket().mount("/", routes![index]);
+    assert!(rocket.execute_command(Command::Routes).await.is_ok());
+
+    let rocket = rocket().mount("/", routes![index]);
+    assert!(rocket.execute_command(Command::Config).await.is_ok());
+}
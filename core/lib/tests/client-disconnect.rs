@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use rocket::{Config, State};
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::request::Disconnect;
+use rocket::response::Stream;
+use rocket::tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+use rocket::tokio::time::{sleep, timeout};
+
+#[derive(Default, Clone)]
+struct Observed(Arc<AtomicBool>);
+
+#[get("/events")]
+fn events(disconnect: Disconnect, observed: State<'_, Observed>) -> Stream<DuplexStream> {
+    let observed = observed.inner().clone();
+    let (mut tx, rx) = duplex(64);
+    rocket::tokio::spawn(async move {
+        let _ = tx.write_all(b"hello\n").await;
+        disconnect.await;
+        observed.0.store(true, Ordering::SeqCst);
+    });
+
+    Stream::from(rx)
+}
+
+#[get("/check")]
+fn check(disconnect: Disconnect) -> String {
+    disconnect.is_disconnected().to_string()
+}
+
+#[test]
+fn local_requests_are_not_disconnected() {
+    let client = rocket::local::blocking::Client::tracked(rocket::ignite()
+        .mount("/", routes![check]))
+        .unwrap();
+
+    assert_eq!(client.get("/check").dispatch().into_string().unwrap(), "false");
+}
+
+#[rocket::async_test]
+async fn disconnect_resolves_when_client_goes_away() {
+    let observed = Observed::default();
+    let (port_tx, port_rx) = oneshot::channel();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .manage(observed.clone())
+        .mount("/", routes![events])
+        .attach(AdHoc::on_launch("Send Port", move |rocket| {
+            port_tx.send(rocket.config().port).unwrap();
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    let port = port_rx.await.unwrap();
+
+    rocket::tokio::task::spawn_blocking(move || {
+        let mut socket = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        socket.write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut buf = [0u8; 512];
+        let n = socket.read(&mut buf).unwrap();
+        assert!(String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 200"));
+    }).await.unwrap();
+
+    let wait = async {
+        while !observed.0.load(Ordering::SeqCst) {
+            sleep(Duration::from_millis(10)).await;
+        }
+    };
+
+    assert!(timeout(Duration::from_secs(5), wait).await.is_ok());
+}
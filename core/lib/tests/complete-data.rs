@@ -0,0 +1,50 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Complete, CompleteError, Limits, ToByteUnit};
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[post("/string", data = "<body>")]
+fn string(body: Result<Complete<String>, CompleteError>) -> String {
+    match body {
+        Ok(body) => body.into_inner(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[post("/bytes", data = "<body>")]
+fn bytes(body: Complete<Vec<u8>>) -> String {
+    body.len().to_string()
+}
+
+fn client() -> Client {
+    let limits = Limits::default().limit("string", 5.bytes()).limit("bytes", 3.bytes());
+    let config = rocket::Config::figment().merge(("limits", limits));
+    Client::tracked(rocket::custom(config).mount("/", routes![string, bytes])).unwrap()
+}
+
+#[test]
+fn accepts_bodies_within_limit() {
+    let client = client();
+    let response = client.post("/string").body("hello").dispatch();
+    assert_eq!(response.into_string().unwrap(), "hello");
+
+    let response = client.post("/string").body("").dispatch();
+    assert_eq!(response.into_string().unwrap(), "");
+
+    let response = client.post("/bytes").body(&[1, 2, 3]).dispatch();
+    assert_eq!(response.into_string().unwrap(), "3");
+}
+
+#[test]
+fn rejects_truncated_bodies() {
+    let client = client();
+    let response = client.post("/string").body("hello!").dispatch();
+    assert!(response.into_string().unwrap().starts_with("body exceeds the limit of 5"));
+
+    let response = client.post("/bytes").body(&[1, 2, 3, 4]).dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let response = client.post("/string").body(&[0xff]).dispatch();
+    assert!(response.into_string().unwrap().starts_with("failed to read body"));
+}
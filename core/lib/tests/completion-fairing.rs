@@ -0,0 +1,85 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use rocket::{Config, Rocket};
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::futures::channel::oneshot;
+use rocket::request::Disconnect;
+use rocket::response::Stream;
+use rocket::tokio::io::{duplex, AsyncWriteExt, DuplexStream};
+use rocket::tokio::sync::mpsc;
+use rocket::tokio::time::timeout;
+
+#[get("/hello")]
+fn hello() -> &'static str {
+    "Hello, world!"
+}
+
+#[get("/stalled")]
+fn stalled(disconnect: Disconnect) -> Stream<DuplexStream> {
+    let (mut tx, rx) = duplex(64);
+    rocket::tokio::spawn(async move {
+        let _ = tx.write_all(b"hello\n").await;
+        disconnect.await;
+    });
+
+    Stream::from(rx)
+}
+
+type Record = (Status, u64, bool);
+
+async fn launch() -> (u16, mpsc::UnboundedReceiver<Record>) {
+    let (port_tx, port_rx) = oneshot::channel();
+    let (tx, rx) = mpsc::unbounded_channel();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![hello, stalled])
+        .attach(AdHoc::on_launch("Send Port", move |rocket: &Rocket| {
+            port_tx.send(rocket.config().port).unwrap();
+        }))
+        .attach(AdHoc::on_complete("Record", move |_, completion| {
+            let (status, bytes) = (completion.status(), completion.bytes_written());
+            let _ = tx.send((status, bytes, completion.is_complete()));
+            Box::pin(async move {})
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    (port_rx.await.unwrap(), rx)
+}
+
+#[rocket::async_test]
+async fn complete_response_records_all_bytes() {
+    let (port, mut records) = launch().await;
+    rocket::tokio::task::spawn_blocking(move || {
+        let mut socket = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let request = b"GET /hello HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        socket.write_all(request).unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).unwrap();
+        assert!(response.ends_with("Hello, world!"));
+    }).await.unwrap();
+
+    let record = timeout(Duration::from_secs(5), records.recv()).await.unwrap();
+    assert_eq!(record, Some((Status::Ok, 13, true)));
+}
+
+#[rocket::async_test]
+async fn disconnect_mid_body_records_error() {
+    let (port, mut records) = launch().await;
+    rocket::tokio::task::spawn_blocking(move || {
+        let mut socket = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        socket.write_all(b"GET /stalled HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = Vec::new();
+        let mut buf = [0u8; 512];
+        while !String::from_utf8_lossy(&response).contains("hello") {
+            let n = socket.read(&mut buf).unwrap();
+            assert!(n > 0);
+            response.extend_from_slice(&buf[..n]);
+        }
+    }).await.unwrap();
+
+    let record = timeout(Duration::from_secs(5), records.recv()).await.unwrap();
+    assert_eq!(record, Some((Status::Ok, 6, false)));
+}
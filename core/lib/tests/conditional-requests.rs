@@ -0,0 +1,135 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{ETag, Header, Status};
+use rocket::outcome::Outcome;
+use rocket::request::{IfMatch, IfNoneMatch, Preconditions};
+use rocket::local::blocking::Client;
+
+const CURRENT: &str = "v2";
+
+#[derive(Responder)]
+struct Tagged {
+    body: &'static str,
+    etag: ETag,
+}
+
+#[get("/doc")]
+fn get_doc(pre: Preconditions) -> Result<Tagged, Status> {
+    pre.check(Some(&ETag::strong(CURRENT)))?;
+    Ok(Tagged { body: "document", etag: ETag::strong(CURRENT) })
+}
+
+#[put("/doc")]
+fn put_doc(pre: Preconditions) -> Result<&'static str, Status> {
+    pre.check(Some(&ETag::strong(CURRENT)))?;
+    Ok("updated")
+}
+
+#[put("/missing")]
+fn put_missing(pre: Preconditions) -> Result<&'static str, Status> {
+    pre.check(None)?;
+    Ok("created")
+}
+
+#[get("/if-match")]
+fn if_match(if_match: IfMatch) -> String {
+    format!("{:?}", if_match)
+}
+
+#[get("/if-match", rank = 2)]
+fn no_if_match() -> &'static str {
+    "none"
+}
+
+#[get("/if-none-match")]
+fn if_none_match(if_none_match: Option<IfNoneMatch>) -> String {
+    format!("{:?}", if_none_match)
+}
+
+#[catch(400)]
+async fn bad_request(request: &Request<'_>) -> String {
+    match request.guard::<Preconditions>().await {
+        Outcome::Failure((_, e)) => e.to_string(),
+        _ => "ok".into(),
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![get_doc, put_doc, put_missing, if_match, no_if_match, if_none_match])
+        .register(catchers![bad_request]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn get_with_if_none_match() {
+    let client = client();
+    let get = |value: Option<&'static str>| {
+        let mut request = client.get("/doc");
+        if let Some(value) = value {
+            request.add_header(Header::new("If-None-Match", value));
+        }
+
+        request.dispatch().status()
+    };
+
+    assert_eq!(get(None), Status::Ok);
+    assert_eq!(get(Some("\"v1\"")), Status::Ok);
+    assert_eq!(get(Some("\"v2\"")), Status::NotModified);
+    assert_eq!(get(Some("W/\"v2\"")), Status::NotModified);
+    assert_eq!(get(Some("\"v1\", \"v2\"")), Status::NotModified);
+    assert_eq!(get(Some("*")), Status::NotModified);
+
+    let response = client.get("/doc").dispatch();
+    assert_eq!(response.headers().get_one("ETag"), Some("\"v2\""));
+}
+
+#[test]
+fn put_with_if_match() {
+    let client = client();
+    let put = |uri: &'static str, name: &'static str, value: &'static str| {
+        client.put(uri).header(Header::new(name, value)).dispatch().status()
+    };
+
+    assert_eq!(client.put("/doc").dispatch().status(), Status::Ok);
+    assert_eq!(put("/doc", "If-Match", "\"v2\""), Status::Ok);
+    assert_eq!(put("/doc", "If-Match", "\"v1\", \"v2\""), Status::Ok);
+    assert_eq!(put("/doc", "If-Match", "*"), Status::Ok);
+    assert_eq!(put("/doc", "If-Match", "\"v1\""), Status::PreconditionFailed);
+    assert_eq!(put("/doc", "If-Match", "W/\"v2\""), Status::PreconditionFailed);
+    assert_eq!(put("/doc", "If-None-Match", "\"v2\""), Status::PreconditionFailed);
+    assert_eq!(put("/doc", "If-None-Match", "*"), Status::PreconditionFailed);
+
+    assert_eq!(put("/missing", "If-Match", "*"), Status::PreconditionFailed);
+    assert_eq!(put("/missing", "If-None-Match", "*"), Status::Ok);
+}
+
+#[test]
+fn typed_guards() {
+    let client = client();
+    let get = |uri: &'static str, header: Option<(&'static str, &'static str)>| {
+        let mut request = client.get(uri);
+        if let Some((name, value)) = header {
+            request.add_header(Header::new(name, value));
+        }
+
+        let response = request.dispatch();
+        (response.status(), response.into_string().unwrap())
+    };
+
+    assert_eq!(get("/if-match", None).1, "none");
+    assert_eq!(get("/if-match", Some(("If-Match", "*"))).1, "Any");
+    assert_eq!(get("/if-match", Some(("If-Match", "W/\"a\""))).1,
+        "Tags([ETag { tag: \"a\", weak: true }])");
+
+    assert_eq!(get("/if-none-match", None).1, "None");
+    assert_eq!(get("/if-none-match", Some(("If-None-Match", "\"a\""))).1,
+        "Some(Tags([ETag { tag: \"a\", weak: false }]))");
+
+    assert_eq!(get("/doc", Some(("If-Match", "v2"))),
+        (Status::BadRequest, "malformed `If-Match` header: \"v2\"".into()));
+    assert_eq!(get("/doc", Some(("If-None-Match", "\"a\" \"b\""))),
+        (Status::BadRequest, "malformed `If-None-Match` header: \"\\\"a\\\" \\\"b\\\"\"".into()));
+}
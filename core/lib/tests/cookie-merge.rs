@@ -0,0 +1,97 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::http::{Cookie, CookieJar};
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+
+#[derive(Responder)]
+struct WithCookie(&'static str, Cookie<'static>);
+
+#[get("/")]
+fn index(jar: &CookieJar<'_>) -> WithCookie {
+    jar.add(Cookie::new("a", "jar"));
+    jar.add(Cookie::new("b", "first"));
+    jar.add(Cookie::new("b", "second"));
+    WithCookie("hi", Cookie::new("a", "header"))
+}
+
+#[test]
+fn cookie_changes_merge_last_write_wins() {
+    let seen = Arc::new(Mutex::new(vec![]));
+    let log = seen.clone();
+    let rocket = rocket::ignite()
+        .mount("/", routes![index])
+        .attach(AdHoc::on_response("Cookies", move |req, _| {
+            let log = log.clone();
+            Box::pin(async move {
+                let delta = req.cookies().delta();
+                log.lock().unwrap().extend(delta.iter().map(|c| c.to_string()));
+                req.cookies().add(Cookie::new("c", "fairing"));
+                req.cookies().add(Cookie::new("b", "fairing"));
+            })
+        }));
+
+    let client = Client::untracked(rocket).unwrap();
+    for _ in 0..3 {
+        let response = client.get("/").dispatch();
+        let cookies: Vec<_> = response.headers().get("Set-Cookie")
+            .map(|v| Cookie::parse_encoded(v.to_string()).unwrap())
+            .map(|c| (c.name().to_string(), c.value().to_string()))
+            .collect();
+
+        assert_eq!(cookies, vec![
+            ("a".to_string(), "jar".to_string()),
+            ("c".to_string(), "fairing".to_string()),
+            ("b".to_string(), "fairing".to_string()),
+        ]);
+    }
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 6);
+    assert!(seen[0].starts_with("a=jar"));
+    assert!(seen[1].starts_with("b=second"));
+}
+
+#[get("/paths")]
+fn paths(jar: &CookieJar<'_>) -> WithCookie {
+    jar.add(Cookie::build("a", "root").finish());
+    jar.add(Cookie::build("a", "admin").path("/admin").finish());
+    jar.add(Cookie::build("a", "example").domain("example.com").finish());
+    jar.add(Cookie::build("a", "root again").finish());
+    WithCookie("hi", Cookie::build("a", "header").path("/admin").finish())
+}
+
+#[test]
+fn cookies_are_keyed_by_name_path_and_domain() {
+    let client = Client::untracked(rocket::ignite().mount("/", routes![paths])).unwrap();
+    let response = client.get("/paths").dispatch();
+    let cookies: Vec<_> = response.headers().get("Set-Cookie")
+        .map(|v| Cookie::parse_encoded(v.to_string()).unwrap())
+        .map(|c| (c.value().to_string(), c.path().map(|p| p.to_string())))
+        .collect();
+
+    assert_eq!(cookies, vec![
+        ("admin".to_string(), Some("/admin".to_string())),
+        ("example".to_string(), Some("/".to_string())),
+        ("root again".to_string(), Some("/".to_string())),
+    ]);
+}
+
+#[get("/remove")]
+fn remove(jar: &CookieJar<'_>) {
+    jar.add(Cookie::new("pending", "1"));
+    jar.remove(Cookie::named("pending"));
+    jar.remove(Cookie::named("sent"));
+    assert_eq!(jar.delta().len(), 1);
+}
+
+#[test]
+fn removals_in_delta() {
+    let client = Client::untracked(rocket::ignite().mount("/", routes![remove])).unwrap();
+    let response = client.get("/remove").cookie(Cookie::new("sent", "v")).dispatch();
+    let cookies: Vec<_> = response.headers().get("Set-Cookie").collect();
+    assert_eq!(cookies.len(), 1);
+    assert!(cookies[0].starts_with("sent=;"));
+}
@@ -0,0 +1,77 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Cookie, CookieJar, SameSite};
+use rocket::local::blocking::Client;
+
+#[get("/plain")]
+fn plain(jar: &CookieJar<'_>) {
+    jar.add(Cookie::new("plain", "1"));
+    jar.add(Cookie::build("explicit", "1").same_site(SameSite::Strict).secure(false).finish());
+}
+
+#[get("/prefixed")]
+fn prefixed(jar: &CookieJar<'_>) -> String {
+    let results = vec![
+        jar.try_add(Cookie::new("__Secure-a", "1")),
+        jar.try_add(Cookie::build("__Host-b", "1").path("/app").finish()),
+        jar.try_add(Cookie::build("__Host-c", "1").domain("rocket.rs").finish()),
+        jar.try_add(Cookie::new("__Host-d", "1")),
+    ];
+
+    // Rejected by `add()` as well; only logged.
+    jar.add(Cookie::build("__Host-e", "1").path("/app").finish());
+
+    results.iter()
+        .map(|r| r.as_ref().map_err(|e| e.to_string()).err().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn client(same_site: &str, secure: bool) -> Client {
+    let figment = rocket::Config::figment()
+        .merge(("cookies.same_site", same_site))
+        .merge(("cookies.secure", secure));
+
+    Client::tracked(rocket::custom(figment).mount("/", routes![plain, prefixed])).unwrap()
+}
+
+#[test]
+fn policy_sets_defaults() {
+    let client = client("lax", true);
+    let response = client.get("/plain").dispatch();
+    let plain = response.cookies().get("plain").unwrap();
+    assert_eq!(plain.same_site(), Some(SameSite::Lax));
+    assert_eq!(plain.secure(), Some(true));
+
+    let explicit = response.cookies().get("explicit").unwrap();
+    assert_eq!(explicit.same_site(), Some(SameSite::Strict));
+    assert_eq!(explicit.secure(), Some(false));
+
+    let client = client("strict", false);
+    let response = client.get("/plain").dispatch();
+    let plain = response.cookies().get("plain").unwrap();
+    assert_eq!(plain.same_site(), Some(SameSite::Strict));
+    assert_eq!(plain.secure(), None);
+}
+
+#[test]
+fn prefixes_are_enforced() {
+    let client = client("strict", false);
+    let response = client.get("/prefixed").dispatch();
+    assert!(response.cookies().iter().next().is_none());
+    assert_eq!(response.into_string().unwrap(),
+        "cookie `__Secure-a` has a secure name prefix but is not `Secure`\n\
+         cookie `__Host-b` has a secure name prefix but is not `Secure`\n\
+         cookie `__Host-c` has a secure name prefix but is not `Secure`\n\
+         cookie `__Host-d` has a secure name prefix but is not `Secure`");
+
+    let client = client("strict", true);
+    let response = client.get("/prefixed").dispatch();
+    let mut names: Vec<_> = response.cookies().iter().map(|c| c.name().to_string()).collect();
+    names.sort();
+    assert_eq!(names, vec!["__Host-d", "__Secure-a"]);
+    assert_eq!(response.into_string().unwrap(),
+        "\n\
+         `__Host-` cookie `__Host-b` must have a path of `/`\n\
+         `__Host-` cookie `__Host-c` must not have a `Domain`\n");
+}
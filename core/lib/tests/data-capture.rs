@@ -0,0 +1,132 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::{Request, Data};
+use rocket::data::ToByteUnit;
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::local::blocking::Client;
+
+type Log = Arc<Mutex<Vec<(String, String, u64, bool)>>>;
+
+struct CaptureRequest(Log);
+
+#[rocket::async_trait]
+impl Fairing for CaptureRequest {
+    fn info(&self) -> Info {
+        Info { name: "Capture Request", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+        let (log, uri) = (self.0.clone(), req.uri().to_string());
+        let capture = data.capture(5.bytes()).unwrap();
+        assert!(data.capture(5.bytes()).is_none());
+        capture.on_finish(move |c| {
+            let body = String::from_utf8(c.bytes()).unwrap();
+            log.lock().unwrap().push((uri, body, c.total(), c.is_truncated()));
+        });
+    }
+}
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[post("/ignore")]
+fn ignore() -> &'static str {
+    "ignored"
+}
+
+#[test]
+fn request_capture() {
+    let captured = Log::default();
+    let rocket = rocket::ignite()
+        .mount("/", routes![echo, ignore])
+        .attach(CaptureRequest(captured.clone()));
+
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.post("/").body("hello, world").dispatch();
+    assert_eq!(response.into_string().unwrap(), "hello, world");
+
+    let response = client.post("/").body("hi").dispatch();
+    assert_eq!(response.into_string().unwrap(), "hi");
+
+    // The body is never read, but the capture still finishes.
+    let response = client.post("/ignore").body("unread").dispatch();
+    assert_eq!(response.into_string().unwrap(), "ignored");
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(&*captured, &[
+        ("/".to_string(), "hello".to_string(), 12, true),
+        ("/".to_string(), "hi".to_string(), 2, false),
+        ("/ignore".to_string(), "".to_string(), 0, false),
+    ]);
+}
+
+#[test]
+fn response_capture_finishes_after_write() {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let log = captured.clone();
+    let rocket = rocket::ignite()
+        .mount("/", routes![echo])
+        .attach(AdHoc::on_response("Capture Response", move |_, res| {
+            let log = log.clone();
+            Box::pin(async move {
+                let capture = res.capture_body(1.kibibytes()).unwrap();
+                assert!(!capture.is_finished());
+                capture.on_finish(move |c| log.lock().unwrap().push(c.bytes()));
+            })
+        }));
+
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.post("/").body("response body").dispatch();
+    assert_eq!(response.into_string().unwrap(), "response body");
+    assert_eq!(&*captured.lock().unwrap(), &[b"response body".to_vec()]);
+}
+
+#[derive(Clone, Default)]
+struct Sink(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+struct TeeRequest(Sink);
+
+#[rocket::async_trait]
+impl Fairing for TeeRequest {
+    fn info(&self) -> Info {
+        Info { name: "Tee Request", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, _: &mut Request<'_>, data: &mut Data) {
+        // Peeking doesn't consume: the tee still sees the full body once.
+        assert_eq!(data.peek(3).await, b"the");
+        assert!(data.tee(self.0.clone()).is_ok());
+        assert!(data.is_teed());
+        assert!(data.tee(Sink::default()).is_err());
+        assert!(data.capture(10.bytes()).is_none());
+    }
+}
+
+#[test]
+fn tee_observes_full_body_once() {
+    let sink = Sink::default();
+    let rocket = rocket::ignite()
+        .mount("/", routes![echo])
+        .attach(TeeRequest(sink.clone()));
+
+    let client = Client::tracked(rocket).unwrap();
+    let body = "the body is observed exactly once".repeat(100);
+    let response = client.post("/").body(&body).dispatch();
+    assert_eq!(response.into_string().unwrap(), body);
+    assert_eq!(&*sink.0.lock().unwrap(), body.as_bytes());
+}
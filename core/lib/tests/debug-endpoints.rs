@@ -0,0 +1,92 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Config;
+use rocket::figment::Profile;
+use rocket::debug::DebugEndpoints;
+use rocket::fairing::AdHoc;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use serde_json::Value;
+
+#[get("/hello/<name>", rank = 3)]
+fn hello(name: &str) -> String {
+    name.to_string()
+}
+
+const SECRET_KEY: &str = "hPRYyVRiMyxpw5sBB1XeCMN1kFsDCqKvBi2QJxBVHQk=";
+
+fn client(profile: Profile) -> Client {
+    let figment = Config::figment().select(profile).merge(("secret_key", SECRET_KEY));
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![hello])
+        .attach(AdHoc::on_response("Noop", |_, _| Box::pin(async {})))
+        .attach(DebugEndpoints::fairing().capacity(2));
+
+    Client::untracked(rocket).unwrap()
+}
+
+fn json(client: &Client, path: &str) -> Value {
+    let response = client.get(path).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    serde_json::from_str(&response.into_string().unwrap()).unwrap()
+}
+
+#[test]
+fn debug_endpoints_in_debug_profile() {
+    let client = client(Config::DEBUG_PROFILE);
+
+    let routes = json(&client, "/_rocket/routes");
+    let hello = routes.as_array().unwrap().iter()
+        .find(|r| r["uri"] == "/hello/<name>")
+        .unwrap();
+
+    assert_eq!(hello["method"], "GET");
+    assert_eq!(hello["rank"], 3);
+    assert_eq!(hello["name"], "hello");
+
+    let config = json(&client, "/_rocket/config");
+    assert_eq!(config["profile"], "debug");
+    assert_eq!(config["secret_key"], "[redacted]");
+
+    let fairings = json(&client, "/_rocket/fairings");
+    assert!(fairings.as_array().unwrap().iter().any(|f| f["name"] == "Noop"));
+
+    let limits = json(&client, "/_rocket/limits");
+    assert!(limits.is_object());
+
+    client.get("/hello/a").dispatch();
+    client.get("/hello/b").dispatch();
+    client.get("/missing").dispatch();
+    let requests = json(&client, "/_rocket/requests");
+    let uris: Vec<_> = requests.as_array().unwrap().iter().map(|r| &r["uri"]).collect();
+    assert_eq!(uris, vec!["/missing", "/hello/b"]);
+    assert_eq!(requests[0]["status"], 404);
+    assert_eq!(requests[1]["route"], "/hello/<name>");
+
+    let response = client.get("/_rocket").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let page = response.into_string().unwrap();
+    assert!(page.contains("/hello/&lt;name&gt;"));
+}
+
+#[test]
+fn debug_endpoints_unavailable_in_release_profile() {
+    let client = client(Config::RELEASE_PROFILE);
+    assert_eq!(client.get("/_rocket").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/_rocket/config").dispatch().status(), Status::NotFound);
+    assert!(client.rocket().routes().all(|r| !r.uri.path().starts_with("/_rocket")));
+}
+
+#[test]
+fn debug_endpoints_forward_after_reconfigure() {
+    let figment = Config::figment().select(Config::DEBUG_PROFILE);
+    let rocket = rocket::custom(figment)
+        .attach(DebugEndpoints::fairing())
+        .reconfigure(Config::figment()
+            .select(Config::RELEASE_PROFILE)
+            .merge(("secret_key", SECRET_KEY)));
+
+    let client = Client::untracked(rocket).unwrap();
+    assert_eq!(client.get("/_rocket/config").dispatch().status(), Status::NotFound);
+}
@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Response;
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use serde_json::json;
+
+#[get("/")]
+fn plain() -> &'static str {
+    "plain"
+}
+
+#[get("/")]
+fn cached() -> Response<'static> {
+    Response::build().raw_header("Cache-Control", "no-store").finalize()
+}
+
+fn client() -> Client {
+    let headers = json!({
+        "/": { "X-Org": "Acme", "Cache-Control": "no-cache", "Server": "Acme" },
+        "/static": { "Cache-Control": "max-age=3600" },
+        "/static/fresh": { "X-Fresh": "yes" },
+    });
+
+    let rocket = rocket::custom(rocket::Config::figment().merge(("default_headers", headers)))
+        .mount("/", routes![plain])
+        .mount("/static", routes![plain])
+        .mount("/static/fresh", routes![cached])
+        .mount("/staticky", routes![plain])
+        .attach(AdHoc::on_response("Override", |req, res| Box::pin(async move {
+            if req.uri().path() == "/" {
+                res.set_header(Header::new("X-Org", "Override"));
+            }
+        })));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn global_defaults_apply_everywhere() {
+    let client = client();
+    let response = client.get("/staticky").dispatch();
+    assert_eq!(response.headers().get_one("X-Org"), Some("Acme"));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+    assert_eq!(response.headers().get_one("Server"), Some("Acme"));
+    assert!(response.headers().get_one("X-Fresh").is_none());
+}
+
+#[test]
+fn more_specific_mounts_take_precedence() {
+    let client = client();
+    let response = client.get("/static").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=3600"));
+    assert_eq!(response.headers().get_one("X-Org"), Some("Acme"));
+
+    let response = client.get("/static/missing").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=3600"));
+
+    // Empty segments are skipped when matching, as they are when routing.
+    let response = client.get("//static//fresh").dispatch();
+    assert_eq!(response.headers().get_one("X-Fresh"), Some("yes"));
+}
+
+#[test]
+fn handler_and_fairing_headers_win() {
+    let client = client();
+    let response = client.get("/static/fresh").dispatch();
+    let cache_control: Vec<_> = response.headers().get("Cache-Control").collect();
+    assert_eq!(cache_control, vec!["no-store"]);
+    assert_eq!(response.headers().get_one("X-Fresh"), Some("yes"));
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("X-Org"), Some("Override"));
+}
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rocket::State;
+use rocket::fairing::AdHoc;
+use rocket::local::asynchronous::Client;
+use rocket::request::Defer;
+use rocket::tokio::sync::mpsc::{self, UnboundedSender};
+
+type Log = UnboundedSender<String>;
+
+#[get("/publish/<post>")]
+fn publish(post: usize, defer: Defer, log: State<'_, Log>, sent: State<'_, Arc<AtomicBool>>) {
+    let (log, sent) = (log.inner().clone(), sent.inner().clone());
+    defer.after_response(async move {
+        let after = sent.load(Ordering::Acquire);
+        log.send(format!("webhook {} after response: {}", post, after)).unwrap();
+    });
+}
+
+#[get("/fail")]
+fn fail(defer: Defer, log: State<'_, Log>) -> Option<()> {
+    let log = log.inner().clone();
+    defer.after_response(async move { log.send("cleanup".into()).unwrap() });
+    None
+}
+
+#[rocket::async_test]
+async fn deferred_work_runs_after_the_response() {
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let sent = Arc::new(AtomicBool::new(false));
+    let rocket = rocket::ignite()
+        .mount("/", routes![publish, fail])
+        .manage(tx)
+        .manage(sent.clone())
+        .attach(AdHoc::on_response("Mark Sent", |req, _| Box::pin(async move {
+            req.managed_state::<Arc<AtomicBool>>().unwrap().store(true, Ordering::Release);
+        })))
+        .attach(AdHoc::on_request("Analytics", |req, _| Box::pin(async move {
+            let log = req.managed_state::<Log>().unwrap().clone();
+            let path = req.uri().path().to_string();
+            req.defer(async move { log.send(format!("visit {}", path)).unwrap() });
+        })));
+
+    let client = Client::tracked(rocket).await.unwrap();
+    client.get("/publish/7").dispatch().await;
+
+    let mut messages = vec![rx.recv().await.unwrap(), rx.recv().await.unwrap()];
+    messages.sort();
+    assert_eq!(messages, vec!["visit /publish/7", "webhook 7 after response: true"]);
+
+    // Deferred work runs even when the request fails.
+    client.get("/fail").dispatch().await;
+    let mut messages = vec![rx.recv().await.unwrap(), rx.recv().await.unwrap()];
+    messages.sort();
+    assert_eq!(messages, vec!["cleanup", "visit /fail"]);
+}
+
+#[get("/export")]
+fn export(log: State<'_, Log>) -> (rocket::http::Status, rocket::Response<'static>) {
+    let (first, second) = (log.inner().clone(), log.inner().clone());
+    let response = rocket::Response::build()
+        .sized_body(4, std::io::Cursor::new("data"))
+        .after_sent(async move { first.send("remove export".into()).unwrap() })
+        .after_sent(async move { second.send("release lock".into()).unwrap() })
+        .finalize();
+
+    (rocket::http::Status::Accepted, response)
+}
+
+#[rocket::async_test]
+async fn response_cleanup_runs_in_order() {
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let rocket = rocket::ignite().mount("/", routes![export]).manage(tx);
+    let client = Client::tracked(rocket).await.unwrap();
+
+    let response = client.get("/export").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "data");
+    assert_eq!(rx.recv().await.unwrap(), "remove export");
+    assert_eq!(rx.recv().await.unwrap(), "release lock");
+}
@@ -0,0 +1,51 @@
+#![cfg(any(feature = "anyhow", feature = "eyre"))]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+#[cfg(feature = "anyhow")]
+#[get("/anyhow/<n>")]
+fn anyhow_parse(n: &str) -> anyhow::Result<String> {
+    use anyhow::Context;
+
+    let n: usize = n.parse().context("secret detail")?;
+    Ok(n.to_string())
+}
+
+#[cfg(feature = "eyre")]
+#[get("/eyre/<n>")]
+fn eyre_parse(n: &str) -> eyre::Result<String> {
+    use eyre::WrapErr;
+
+    let n: usize = n.parse().wrap_err("secret detail")?;
+    Ok(n.to_string())
+}
+
+fn check(client: &Client, base: &str) {
+    let response = client.get(format!("/{}/42", base)).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "42");
+
+    let response = client.get(format!("/{}/nope", base))
+        .header(Header::new("X-Request-Id", "abc123"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::InternalServerError);
+    assert!(!response.into_string().unwrap().contains("secret detail"));
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn anyhow_errors_are_internal_errors() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![anyhow_parse])).unwrap();
+    check(&client, "anyhow");
+}
+
+#[cfg(feature = "eyre")]
+#[test]
+fn eyre_reports_are_internal_errors() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![eyre_parse])).unwrap();
+    check(&client, "eyre");
+}
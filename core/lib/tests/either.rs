@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Either;
+use rocket::request::{self, FromRequest, Request};
+use rocket::outcome::Outcome;
+use rocket::http::{ContentType, Status};
+
+struct Header(&'static str);
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Header {
+    type Error = &'static str;
+
+    async fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        match request.headers().get_one("X-Pick") {
+            Some("left") => Outcome::Success(Header("left")),
+            Some("fail") => Outcome::Failure((Status::BadRequest, "bad pick")),
+            _ => Outcome::Forward(()),
+        }
+    }
+}
+
+#[get("/")]
+fn pick(guard: Either<Header, &ContentType>) -> Either<&'static str, String> {
+    match guard {
+        Either::Left(header) => Either::Left(header.0),
+        Either::Right(content_type) => Either::Right(content_type.to_string()),
+    }
+}
+
+mod either_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::Header as HttpHeader;
+
+    #[test]
+    fn test_either() {
+        let client = Client::tracked(rocket::ignite().mount("/", routes![pick])).unwrap();
+
+        let response = client.get("/").header(HttpHeader::new("X-Pick", "left")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "left");
+
+        let response = client.get("/").header(ContentType::JSON).dispatch();
+        assert_eq!(response.into_string().unwrap(), "application/json");
+
+        // `B` is tried when `A` fails; `A`'s failure is returned if `B` forwards.
+        let response = client.get("/")
+            .header(HttpHeader::new("X-Pick", "fail"))
+            .header(ContentType::JSON)
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "application/json");
+
+        let response = client.get("/").header(HttpHeader::new("X-Pick", "fail")).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
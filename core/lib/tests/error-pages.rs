@@ -0,0 +1,76 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::catcher::description;
+use rocket::http::{Accept, ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::Conflict
+}
+
+#[get("/unavailable")]
+fn unavailable() -> Status {
+    Status::ServiceUnavailable
+}
+
+#[catch(418)]
+fn teapot() -> &'static str {
+    "custom teapot"
+}
+
+#[get("/teapot")]
+fn brew() -> Status {
+    Status::ImATeapot
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![fail, brew, unavailable])
+        .register(catchers![teapot])
+        .error_pages(|status: Status, req: &Request<'_>| match status.code {
+            404 => Some(format!("<h1>Lost at {}</h1>", req.uri().path())),
+            _ => None,
+        })
+        .error_pages(|status: Status, _: &Request<'_>| match status.code {
+            404 | 409 => Some(format!("{}: {}", status.code, description(status))),
+            _ => None,
+        });
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn providers_are_consulted_in_order() {
+    let client = client();
+    let response = client.get("/nowhere").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.into_string().unwrap(), "<h1>Lost at /nowhere</h1>");
+
+    let response = client.get("/fail").dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert_eq!(response.into_string().unwrap(),
+        "409: The request could not be processed because of a conflict in the request.");
+}
+
+#[test]
+fn built_in_page_is_the_fallback() {
+    let client = client();
+    let response = client.get("/unavailable").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert!(response.into_string().unwrap().contains("503: Service Unavailable"));
+
+    let response = client.get("/nowhere").header(Accept::JSON).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+}
+
+#[test]
+fn registered_catchers_take_precedence() {
+    let client = client();
+    let response = client.get("/teapot").dispatch();
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert_eq!(response.into_string().unwrap(), "custom teapot");
+}
@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::event::Events;
+use rocket::local::asynchronous::Client;
+use rocket::tokio::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq)]
+struct UserRegistered(String);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Unheard;
+
+#[post("/register/<name>")]
+fn register(events: &Events, name: String) -> String {
+    events.emit(UserRegistered(name)).to_string()
+}
+
+#[post("/unheard")]
+fn unheard(events: &Events) -> String {
+    events.emit(Unheard).to_string()
+}
+
+#[rocket::async_test]
+async fn events_reach_listeners_and_subscribers() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let rocket = rocket::ignite()
+        .mount("/", routes![register, unheard])
+        .attach(Events::fairing("Audit Log", move |event: UserRegistered| {
+            let tx = tx.clone();
+            async move { tx.send(event).unwrap() }
+        }));
+
+    let client = Client::tracked(rocket).await.unwrap();
+    let mut subscriber = client.rocket().events().subscribe::<UserRegistered>();
+
+    let response = client.post("/register/bob").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "2");
+    assert_eq!(rx.recv().await, Some(UserRegistered("bob".into())));
+    assert_eq!(subscriber.recv().await, Some(UserRegistered("bob".into())));
+
+    // Events are routed by type.
+    let response = client.post("/unheard").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "0");
+
+    // Events emitted outside of handlers are delivered too.
+    assert_eq!(client.rocket().emit(UserRegistered("alice".into())), 2);
+    assert_eq!(rx.recv().await, Some(UserRegistered("alice".into())));
+    assert_eq!(subscriber.recv().await, Some(UserRegistered("alice".into())));
+}
+
+#[rocket::async_test]
+async fn subscribers_end_with_rocket() {
+    let rocket = rocket::ignite();
+    let mut subscriber = rocket.events().subscribe::<u8>();
+    rocket.emit(7u8);
+    drop(rocket);
+
+    assert_eq!(subscriber.recv().await, Some(7));
+    assert_eq!(subscriber.recv().await, None);
+}
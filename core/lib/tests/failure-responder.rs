@@ -0,0 +1,111 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{Accept, Status};
+use rocket::response::Failure;
+use rocket::local::blocking::Client;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum AppError {
+    NoSuchUser { id: usize },
+    Redirected,
+}
+
+impl From<AppError> for Status {
+    fn from(error: AppError) -> Status {
+        match error {
+            AppError::NoSuchUser { .. } => Status::NotFound,
+            AppError::Redirected => Status::SeeOther,
+        }
+    }
+}
+
+fn find_user(id: usize) -> Result<String, AppError> {
+    match id {
+        0 => Ok("root".into()),
+        _ => Err(AppError::NoSuchUser { id }),
+    }
+}
+
+#[get("/user/<id>")]
+fn user(id: usize) -> Result<String, Failure<AppError>> {
+    Ok(find_user(id)?)
+}
+
+#[get("/api/user/<id>")]
+fn api_user(id: usize) -> Result<String, Failure<AppError>> {
+    Ok(find_user(id)?)
+}
+
+#[get("/redirected")]
+fn redirected() -> Result<String, Failure<AppError>> {
+    Err(AppError::Redirected.into())
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    match Failure::caught(req) {
+        Some(error) => format!("caught: {}", error),
+        None => "nothing".into(),
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::custom(rocket::Config::figment().merge(("json_errors", ["/api"])))
+        .mount("/", routes![user, api_user, redirected])
+        .register(catchers![not_found]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn successes_respond_normally() {
+    let client = client();
+    let response = client.get("/user/0").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "root");
+}
+
+#[test]
+fn failures_are_forwarded_to_catchers() {
+    let client = client();
+    let response = client.get("/user/7").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().unwrap(), r#"caught: {"id":7,"kind":"NoSuchUser"}"#);
+
+    // Routing failures reach the same catcher without an error.
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.into_string().unwrap(), "nothing");
+}
+
+#[test]
+fn failures_render_as_json_under_json_mounts() {
+    let client = client();
+    let response = client.get("/api/user/7").header(Accept::HTML).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let body: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(body, json!({
+        "error": {
+            "code": 404,
+            "reason": "Not Found",
+            "description": "The requested resource could not be found.",
+            "detail": { "kind": "NoSuchUser", "id": 7 }
+        }
+    }));
+
+    // Requests preferring JSON get JSON anywhere.
+    let response = client.get("/user/7").header(Accept::JSON).dispatch();
+    let body: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(body["error"]["detail"], json!({ "kind": "NoSuchUser", "id": 7 }));
+}
+
+#[test]
+fn non_error_statuses_are_internal_errors() {
+    let client = client();
+    let response = client.get("/redirected").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
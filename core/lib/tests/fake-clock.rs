@@ -0,0 +1,65 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Clock;
+use rocket::http::{Cookie, CookieJar};
+use rocket::local::blocking::Client;
+
+#[get("/now")]
+fn now(clock: &Clock) -> String {
+    clock.now_utc().timestamp().to_string()
+}
+
+#[get("/set")]
+fn set(clock: &Clock, jar: &CookieJar<'_>) {
+    let expires = clock.now_utc() + Duration::from_secs(10);
+    jar.add(Cookie::build("session", "hi").expires(expires).finish());
+}
+
+#[get("/get")]
+fn get(jar: &CookieJar<'_>) -> String {
+    jar.get("session").map(|c| c.value().to_string()).unwrap_or_default()
+}
+
+fn client(clock: &Clock) -> Client {
+    let rocket = rocket::ignite()
+        .manage(clock.clone())
+        .mount("/", routes![now, set, get]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn fake_clock_only_advances_manually() {
+    let clock = Clock::fake_at(time::OffsetDateTime::unix_epoch());
+    let client = client(&clock);
+    assert_eq!(client.get("/now").dispatch().into_string().unwrap(), "0");
+    assert_eq!(client.get("/now").dispatch().into_string().unwrap(), "0");
+
+    clock.advance(Duration::from_secs(90));
+    assert_eq!(client.get("/now").dispatch().into_string().unwrap(), "90");
+}
+
+#[test]
+fn tracked_cookies_expire_with_fake_clock() {
+    let clock = Clock::fake();
+    let client = client(&clock);
+    client.get("/set").dispatch();
+    assert_eq!(client.get("/get").dispatch().into_string().unwrap(), "hi");
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(client.get("/get").dispatch().into_string().unwrap(), "hi");
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(client.get("/get").dispatch().into_string().unwrap(), "");
+}
+
+#[test]
+fn system_clock_is_the_default() {
+    let rocket = rocket::ignite().mount("/", routes![now]);
+    let client = Client::tracked(rocket).unwrap();
+    let before = time::OffsetDateTime::now_utc().timestamp();
+    let now: i64 = client.get("/now").dispatch().into_string().unwrap().parse().unwrap();
+    assert!(now >= before);
+}
@@ -0,0 +1,120 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Config, Request, State};
+use rocket::flags::{Flag, FlagProvider, FeatureFlag, Flags};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+struct NewCheckout;
+
+impl Flag for NewCheckout {
+    const NAME: &'static str = "new_checkout";
+}
+
+struct BetaSearch;
+
+impl Flag for BetaSearch {
+    const NAME: &'static str = "beta_search";
+}
+
+#[get("/checkout")]
+fn new_checkout(_flag: FeatureFlag<NewCheckout>) -> &'static str {
+    "new"
+}
+
+#[get("/checkout", rank = 2)]
+fn old_checkout() -> &'static str {
+    "old"
+}
+
+#[get("/search")]
+fn search(flag: FeatureFlag<BetaSearch>) -> &'static str {
+    flag.name()
+}
+
+#[post("/flags/<name>/<enabled>")]
+fn set(flags: State<'_, Flags>, name: String, enabled: bool) {
+    flags.set(name, enabled);
+}
+
+#[delete("/flags/<name>")]
+fn clear(flags: State<'_, Flags>, name: String) {
+    flags.clear(&name);
+}
+
+fn rocket() -> rocket::Rocket {
+    let figment = Config::figment()
+        .merge(("flags.new_checkout", false))
+        .merge(("flags.beta_search", true));
+
+    rocket::custom(figment).mount("/", routes![new_checkout, old_checkout, search, set, clear])
+}
+
+#[test]
+fn flags_from_config() {
+    let client = Client::untracked(rocket().attach(Flags::fairing())).unwrap();
+    assert_eq!(client.get("/checkout").dispatch().into_string().unwrap(), "old");
+    assert_eq!(client.get("/search").dispatch().into_string().unwrap(), "beta_search");
+}
+
+#[test]
+fn flags_flip_at_runtime() {
+    let client = Client::untracked(rocket().attach(Flags::fairing())).unwrap();
+
+    client.post("/flags/new_checkout/true").dispatch();
+    assert_eq!(client.get("/checkout").dispatch().into_string().unwrap(), "new");
+
+    client.post("/flags/beta_search/false").dispatch();
+    assert_eq!(client.get("/search").dispatch().status(), Status::NotFound);
+
+    client.delete("/flags/beta_search").dispatch();
+    assert_eq!(client.get("/search").dispatch().status(), Status::Ok);
+
+    let overrides = client.rocket().state::<Flags>().unwrap().overrides();
+    assert_eq!(overrides.get("new_checkout"), Some(&true));
+    assert_eq!(overrides.get("beta_search"), None);
+}
+
+struct HeaderProvider;
+
+#[rocket::async_trait]
+impl FlagProvider for HeaderProvider {
+    async fn is_enabled(&self, name: &str, req: &Request<'_>) -> Option<bool> {
+        req.headers().get_one("X-Flags")
+            .map(|flags| flags.split(',').any(|flag| flag == name))
+    }
+}
+
+#[test]
+fn flags_from_provider() {
+    let client = Client::untracked(rocket().attach(Flags::fairing_with(HeaderProvider))).unwrap();
+
+    // Without the header, the provider defers to configuration.
+    assert_eq!(client.get("/checkout").dispatch().into_string().unwrap(), "old");
+    assert_eq!(client.get("/search").dispatch().status(), Status::Ok);
+
+    let beta = Header::new("X-Flags", "new_checkout");
+    let response = client.get("/checkout").header(beta).dispatch();
+    assert_eq!(response.into_string().unwrap(), "new");
+
+    let response = client.get("/search").header(Header::new("X-Flags", "other")).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Runtime overrides take precedence over the provider.
+    client.post("/flags/beta_search/true").dispatch();
+    let response = client.get("/search").header(Header::new("X-Flags", "other")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn unmanaged_flags_fail() {
+    let client = Client::untracked(rocket()).unwrap();
+    assert_eq!(client.get("/search").dispatch().status(), Status::InternalServerError);
+}
+
+#[test]
+fn invalid_flags_config_fails_launch() {
+    let figment = Config::figment().merge(("flags.new_checkout", "maybe"));
+    let rocket = rocket::custom(figment).attach(Flags::fairing());
+    assert!(Client::untracked(rocket).is_err());
+}
@@ -39,11 +39,11 @@ mod flash_lazy_remove_tests {
 
         // Try once.
         let response = client.get("/unused").dispatch();
-        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.status(), Status::NoContent);
 
         // Try again; should still be there.
         let response = client.get("/unused").dispatch();
-        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.status(), Status::NoContent);
 
         // Now use it.
         let response = client.get("/use").dispatch();
@@ -0,0 +1,88 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::Contextual;
+
+#[derive(FromForm)]
+struct Signup {
+    name: String,
+    age: u8,
+    agree: bool,
+}
+
+#[derive(FromForm)]
+struct Comment {
+    text: String,
+    #[form(honeypot)]
+    website: String,
+}
+
+#[post("/comment", data = "<form>")]
+fn comment(form: Contextual<Comment>) -> String {
+    match form.value {
+        Some(comment) => comment.text,
+        None => serde_json::to_string(&form.context).unwrap(),
+    }
+}
+
+#[post("/", data = "<form>")]
+fn signup(form: Contextual<Signup>) -> String {
+    match form.value {
+        Some(signup) => format!("{} {} {}", signup.name, signup.age, signup.agree),
+        None => serde_json::to_string(&form.context).unwrap(),
+    }
+}
+
+mod form_context_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::{Status, ContentType};
+
+    fn post(client: &Client, body: &str) -> (Status, String) {
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch();
+
+        (response.status(), response.into_string().unwrap())
+    }
+
+    #[test]
+    fn test_form_context() {
+        let client = Client::tracked(rocket::ignite().mount("/", routes![signup])).unwrap();
+
+        assert_eq!(post(&client, "name=Bob%20Smith&age=30&agree=on"),
+            (Status::Ok, "Bob Smith 30 true".into()));
+
+        // Every invalid field is reported, and the submitted values are kept.
+        assert_eq!(post(&client, "name=Bob%20Smith&age=old&agree=maybe").1,
+            r#"{"values":{"age":["old"],"agree":["maybe"],"name":["Bob Smith"]},"errors":{"age":["invalid value"],"agree":["invalid value"]},"form_errors":[],"honeypots":[]}"#);
+
+        assert_eq!(post(&client, "age=30&color=red").1,
+            r#"{"values":{"age":["30"],"color":["red"]},"errors":{"color":["unexpected field"],"name":["missing field"]},"form_errors":[],"honeypots":[]}"#);
+
+        assert_eq!(post(&client, "name=a&age=30&=d=").1,
+            r#"{"values":{"age":["30"],"name":["a"]},"errors":{},"form_errors":["malformed form"],"honeypots":[]}"#);
+    }
+
+    #[test]
+    fn test_honeypot_context() {
+        let client = Client::tracked(rocket::ignite().mount("/", routes![comment])).unwrap();
+        let post = |body: &str| {
+            let response = client.post("/comment").header(ContentType::Form).body(body).dispatch();
+            response.into_string().unwrap()
+        };
+
+        assert_eq!(post("text=hello&website="), "hello");
+
+        // A filled in honeypot's value isn't redisplayed.
+        assert_eq!(post("text=hello&website=spam.example"),
+            r#"{"values":{"text":["hello"]},"errors":{},"form_errors":["rejected submission"],"honeypots":["website"]}"#);
+    }
+
+    #[test]
+    fn test_non_form_forwards() {
+        let client = Client::tracked(rocket::ignite().mount("/", routes![signup])).unwrap();
+        let response = client.post("/").header(ContentType::JSON).body("{}").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
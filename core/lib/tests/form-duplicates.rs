@@ -0,0 +1,91 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Form, Request};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Filter {
+    tag: String,
+}
+
+#[get("/single?<n>")]
+fn single(n: usize) -> String {
+    n.to_string()
+}
+
+#[get("/multi?<filter..>")]
+fn multi(filter: Form<Filter>) -> String {
+    filter.into_inner().tag
+}
+
+#[post("/form", data = "<filter>")]
+fn form(filter: Form<Filter>) -> String {
+    filter.into_inner().tag
+}
+
+#[get("/manual")]
+fn manual(request: &Request<'_>) -> String {
+    match request.get_query_value::<usize>("n") {
+        Some(Ok(n)) => n.to_string(),
+        _ => "none".into(),
+    }
+}
+
+fn client(policy: &str) -> Client {
+    let figment = rocket::Config::figment().merge(("form_duplicates", policy));
+    let rocket = rocket::custom(figment).mount("/", routes![single, multi, form, manual]);
+    Client::tracked(rocket).unwrap()
+}
+
+fn get(client: &Client, uri: &str) -> Option<String> {
+    let response = client.get(uri.to_string()).dispatch();
+    if response.status().code != 200 {
+        return None;
+    }
+
+    response.into_string()
+}
+
+fn post(client: &Client, body: &str) -> Option<String> {
+    let response = client.post("/form")
+        .header(rocket::http::ContentType::Form)
+        .body(body)
+        .dispatch();
+
+    if response.status().code != 200 {
+        return None;
+    }
+
+    response.into_string()
+}
+
+#[test]
+fn last_wins_by_default() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![single, multi, form, manual]))
+        .unwrap();
+
+    assert_eq!(get(&client, "/single?n=1&n=2").as_deref(), Some("2"));
+    assert_eq!(get(&client, "/multi?tag=a&tag=b").as_deref(), Some("b"));
+    assert_eq!(get(&client, "/manual?n=1&n=2").as_deref(), Some("2"));
+    assert_eq!(post(&client, "tag=a&tag=b").as_deref(), Some("b"));
+}
+
+#[test]
+fn first_wins() {
+    let client = client("first");
+    assert_eq!(get(&client, "/single?n=1&n=2").as_deref(), Some("1"));
+    assert_eq!(get(&client, "/multi?tag=a&tag=b").as_deref(), Some("a"));
+    assert_eq!(get(&client, "/manual?n=1&n=2").as_deref(), Some("1"));
+    assert_eq!(post(&client, "tag=a&tag=b").as_deref(), Some("a"));
+}
+
+#[test]
+fn duplicates_rejected() {
+    let client = client("reject");
+    assert_eq!(get(&client, "/single?n=1").as_deref(), Some("1"));
+    assert_eq!(get(&client, "/single?n=1&n=2"), None);
+    assert_eq!(get(&client, "/multi?tag=a&tag=b"), None);
+    assert_eq!(get(&client, "/manual?n=1&n=2").as_deref(), Some("none"));
+    assert_eq!(post(&client, "tag=a"), Some("a".into()));
+    assert_eq!(post(&client, "tag=a&tag=b"), None);
+}
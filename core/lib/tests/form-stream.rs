@@ -0,0 +1,124 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Form, FormStream};
+
+#[post("/", data = "<form>")]
+async fn index(mut form: FormStream) -> String {
+    let mut fields = vec![];
+    loop {
+        match form.next().await {
+            Ok(Some(field)) => fields.push(format!("{}:{}", field.key, field.value)),
+            Ok(None) => break,
+            Err(_) => return "error".into(),
+        }
+    }
+
+    fields.join(",")
+}
+
+#[derive(FromForm)]
+struct Pair {
+    a: String,
+    c: String,
+}
+
+#[post("/form", data = "<form>")]
+fn form(form: Form<Pair>) -> String {
+    format!("{}:{}", form.a, form.c)
+}
+
+mod form_stream_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{Status, ContentType};
+    use rocket::data::Limits;
+
+    fn client(forms: u64, field: u64) -> Client {
+        let limits = Limits::default()
+            .limit("forms", forms.into())
+            .limit("form-field", field.into());
+
+        let config = rocket::Config::figment().merge(("limits", limits));
+        let rocket = rocket::custom(config).mount("/", routes![super::index, super::form]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    fn post(client: &Client, body: &str) -> String {
+        client.post("/")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch()
+            .into_string()
+            .unwrap()
+    }
+
+    #[test]
+    fn emits_fields_in_order() {
+        let client = client(1024, 1024);
+        assert_eq!(post(&client, "a=b&c=d"), "a:b,c:d");
+        assert_eq!(post(&client, "a=Hello%2C+world%21&&b&=c&"), "a:Hello, world!,b:,:c");
+        assert_eq!(post(&client, "&&=&"), "");
+        assert_eq!(post(&client, "a=b&c=d=e"), "error");
+    }
+
+    #[test]
+    fn fields_larger_than_chunks() {
+        let client = client(64 * 1024, 64 * 1024);
+        let value = "x".repeat(10_000);
+        let body = format!("a={}&b={}", value, value);
+        assert_eq!(post(&client, &body), format!("a:{},b:{}", value, value));
+    }
+
+    #[test]
+    fn field_limit_enforced() {
+        let client = client(64 * 1024, 16);
+        assert_eq!(post(&client, "a=short&b=alsoshort"), "a:short,b:alsoshort");
+
+        let body = format!("a=short&b={}", "x".repeat(8 * 1024));
+        assert_eq!(post(&client, &body), "error");
+    }
+
+    #[test]
+    fn field_limit_is_exact() {
+        let client = client(64 * 1024, 16);
+        let at_limit = format!("b={}", "x".repeat(14));
+        let over_limit = format!("b={}", "x".repeat(15));
+
+        // A field followed by a `&` in the same read.
+        let body = format!("{}&c=d", at_limit);
+        assert_eq!(post(&client, &body), format!("b:{},c:d", &at_limit[2..]));
+        assert_eq!(post(&client, &format!("{}&c=d", over_limit)), "error");
+
+        // The final field, terminated by the end of the stream.
+        let body = format!("c=d&{}", at_limit);
+        assert_eq!(post(&client, &body), format!("c:d,b:{}", &at_limit[2..]));
+        assert_eq!(post(&client, &format!("c=d&{}", over_limit)), "error");
+    }
+
+    #[test]
+    fn total_limit_truncates() {
+        let client = client(9, 1024);
+        assert_eq!(post(&client, "a=b&c=defghi"), "a:b,c:def");
+    }
+
+    #[test]
+    fn requires_form_content_type() {
+        let client = client(1024, 1024);
+        let response = client.post("/").body("a=b").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn form_reads_through_stream() {
+        let client = client(1024, 16);
+        let post = |body: &str| client.post("/form")
+            .header(ContentType::Form)
+            .body(body.to_string())
+            .dispatch();
+
+        let response = post("a=b&&c=d%21&");
+        assert_eq!(response.into_string().unwrap(), "b:d!");
+
+        assert_eq!(post("a=b=c&c=d").status(), Status::BadRequest);
+        assert_eq!(post(&format!("a=b&c={}", "x".repeat(64))).status(), Status::PayloadTooLarge);
+    }
+}
@@ -0,0 +1,64 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn base(request: &Request<'_>) -> String {
+    request.base_url().to_string()
+}
+
+fn client() -> Client {
+    let figment = rocket::Config::figment().merge(("trusted_proxies", ["10.0.0.1"]));
+    Client::tracked(rocket::custom(figment).mount("/", routes![base])).unwrap()
+}
+
+#[test]
+fn host_header_is_used() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "http://127.0.0.1:8000");
+
+    let response = client.get("/").header(Header::new("Host", "rocket.rs:8080")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "http://rocket.rs:8080");
+
+    let response = client.get("/").header(Header::new("Host", "not a host")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "http://127.0.0.1:8000");
+}
+
+#[test]
+fn forwarded_headers_from_untrusted_peer_are_ignored() {
+    let client = client();
+    let response = client.get("/")
+        .remote("10.0.0.2:5000".parse().unwrap())
+        .header(Header::new("Host", "internal"))
+        .header(Header::new("X-Forwarded-Proto", "https"))
+        .header(Header::new("X-Forwarded-Host", "evil.com"))
+        .header(Header::new("X-Forwarded-Port", "8443"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "http://internal");
+}
+
+#[test]
+fn forwarded_headers_from_trusted_proxy_are_used() {
+    let client = client();
+    let forwarded = |proto: &'static str, host: &'static str, port: &'static str| {
+        client.get("/")
+            .remote("10.0.0.1:5000".parse().unwrap())
+            .header(Header::new("Host", "internal"))
+            .header(Header::new("X-Forwarded-Proto", proto))
+            .header(Header::new("X-Forwarded-Host", host))
+            .header(Header::new("X-Forwarded-Port", port))
+            .dispatch()
+            .into_string()
+            .unwrap()
+    };
+
+    assert_eq!(forwarded("https", "rocket.rs", "443"), "https://rocket.rs");
+    assert_eq!(forwarded("HTTPS", "rocket.rs", "8443"), "https://rocket.rs:8443");
+    assert_eq!(forwarded("http", "rocket.rs, proxy.local", "80"), "http://rocket.rs");
+    assert_eq!(forwarded("https", "rocket.rs:9000", "8443"), "https://rocket.rs:9000");
+    assert_eq!(forwarded("gopher", "rocket.rs", "bad"), "http://rocket.rs");
+}
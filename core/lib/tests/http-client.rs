@@ -0,0 +1,140 @@
+#![cfg(feature = "client")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Config, State};
+use rocket::fairing::AdHoc;
+use rocket::figment::Figment;
+use rocket::futures::channel::oneshot;
+use rocket::client::{ClientConfig, HttpClient, Outbound};
+use rocket::local::asynchronous::Client;
+use rocket::http::{Header, Status};
+use rocket::http::hyper::{self, Body, Request};
+
+struct Upstream(u16);
+
+#[get("/headers")]
+fn headers(req: &rocket::Request<'_>) -> String {
+    ["traceparent", "authorization", "x-private"].iter()
+        .map(|name| format!("{}={}", name, req.headers().get_one(name).unwrap_or("-")))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+#[get("/call")]
+async fn call(outbound: Outbound<'_>, upstream: State<'_, Upstream>) -> String {
+    let uri = format!("http://127.0.0.1:{}/headers", upstream.0);
+    let request = Request::get(uri)
+        .header("authorization", "Bearer outbound")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = outbound.send(request).await.unwrap();
+    let body = hyper::to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[get("/call/bare")]
+async fn call_bare(outbound: Outbound<'_>, upstream: State<'_, Upstream>) -> String {
+    let uri = format!("http://127.0.0.1:{}/headers", upstream.0);
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = outbound.send(request).await.unwrap();
+    let body = hyper::to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+async fn upstream() -> u16 {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![headers])
+        .attach(AdHoc::on_launch("Send Port", move |rocket| {
+            tx.send(rocket.config().port).unwrap();
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    rx.await.unwrap()
+}
+
+#[rocket::async_test]
+async fn outbound_forwards_configured_headers() {
+    let port = upstream().await;
+    let rocket = rocket::ignite()
+        .manage(Upstream(port))
+        .attach(HttpClient::fairing())
+        .mount("/", routes![call]);
+
+    let trace = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+    let client = Client::untracked(rocket).await.unwrap();
+    let response = client.get("/call")
+        .header(Header::new("traceparent", trace))
+        .header(Header::new("authorization", "Bearer incoming"))
+        .header(Header::new("x-private", "secret"))
+        .dispatch().await;
+
+    // The trace is propagated with the request's span as the parent.
+    let body = response.into_string().await.unwrap();
+    assert!(body.starts_with("traceparent=00-0af7651916cd43dd8448eb211c80319c-"));
+    assert!(!body.contains(trace));
+    assert!(body.ends_with(";authorization=Bearer outbound;x-private=-"));
+}
+
+async fn forwarded_authorization(credential_hosts: &[&str]) -> String {
+    let port = upstream().await;
+    let figment = Figment::from(Config::debug_default())
+        .merge(("http_client.forward_headers", vec!["authorization"]))
+        .merge(("http_client.credential_hosts", credential_hosts));
+
+    let rocket = rocket::custom(figment)
+        .manage(Upstream(port))
+        .attach(HttpClient::fairing())
+        .mount("/", routes![call_bare]);
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let response = client.get("/call/bare")
+        .header(Header::new("authorization", "Bearer incoming"))
+        .dispatch().await;
+
+    response.into_string().await.unwrap()
+}
+
+#[rocket::async_test]
+async fn credentials_are_forwarded_only_to_credential_hosts() {
+    let body = forwarded_authorization(&[]).await;
+    assert!(body.contains(";authorization=-;"));
+
+    let body = forwarded_authorization(&["upstream.local"]).await;
+    assert!(body.contains(";authorization=-;"));
+
+    let body = forwarded_authorization(&["127.0.0.1"]).await;
+    assert!(body.contains(";authorization=Bearer incoming;"));
+}
+
+#[rocket::async_test]
+async fn outbound_requires_fairing() {
+    let rocket = rocket::ignite().manage(Upstream(0)).mount("/", routes![call]);
+    let client = Client::untracked(rocket).await.unwrap();
+    let response = client.get("/call").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[rocket::async_test]
+async fn client_is_configured_from_figment() {
+    let figment = Figment::from(Config::debug_default())
+        .merge(("http_client.pool_idle_timeout", 5))
+        .merge(("http_client.forward_headers", vec!["x-request-id"]));
+
+    let rocket = rocket::custom(figment).attach(HttpClient::fairing());
+    let client = Client::untracked(rocket).await.unwrap();
+    let config = client.rocket().state::<HttpClient>().unwrap().config();
+    assert_eq!(config, &ClientConfig {
+        pool_idle_timeout: 5,
+        forward_headers: vec!["x-request-id".into()],
+        ..ClientConfig::default()
+    });
+
+    let figment = Figment::from(Config::debug_default())
+        .merge(("http_client.proxy", "ftp://proxy.local"));
+
+    let rocket = rocket::custom(figment).attach(HttpClient::fairing());
+    assert!(Client::untracked(rocket).await.is_err());
+}
@@ -0,0 +1,103 @@
+#[macro_use] extern crate rocket;
+
+use std::convert::Infallible;
+
+use rocket::interop::HttpService;
+use rocket::data::ToByteUnit;
+use rocket::local::blocking::Client;
+use rocket::http::{ContentType, Header, Status};
+use rocket::http::hyper::{self, service_fn, Body, Request, RequestParts, Response};
+
+async fn echo(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let method = req.method().to_string();
+    let uri = req.uri().to_string();
+    let custom = req.headers().get("X-Custom").map(|v| v.as_bytes().to_vec());
+    let body = hyper::to_bytes(req.into_body()).await.unwrap();
+
+    let mut response = Response::builder()
+        .status(201)
+        .header("X-Method", method)
+        .header("X-Uri", uri);
+
+    if let Some(custom) = custom {
+        response = response.header("X-Custom", custom);
+    }
+
+    Ok(response.body(Body::from(body)).unwrap())
+}
+
+async fn fail(_: Request<Body>) -> Result<Response<Body>, String> {
+    Err("service failure".into())
+}
+
+#[get("/native")]
+fn native() -> &'static str {
+    "native"
+}
+
+#[get("/parts")]
+fn parts(parts: RequestParts) -> Response<String> {
+    let custom = parts.headers.get("X-Custom").map(|v| v.to_str().unwrap().to_string());
+    Response::builder()
+        .status(202)
+        .header("Content-Type", "text/html")
+        .header("X-Custom", "a")
+        .header("X-Custom", "b")
+        .body(format!("{} {} {:?}", parts.method, parts.uri, custom))
+        .unwrap()
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/legacy", HttpService::new(service_fn(echo)).limit(16.bytes()))
+        .mount("/legacy", routes![native])
+        .mount("/", routes![parts])
+        .mount("/fail", HttpService::new(service_fn(fail)));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn dispatches_to_service() {
+    let client = client();
+    let response = client.post("/legacy/a/b?c=d")
+        .header(Header::from_bytes("X-Custom", &b"\xffraw"[..]))
+        .body("hello")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Created);
+    assert_eq!(response.headers().get_one("X-Method"), Some("POST"));
+    assert_eq!(response.headers().get_one("X-Uri"), Some("/legacy/a/b?c=d"));
+    assert_eq!(response.headers().get_bytes("X-Custom").next(), Some(&b"\xffraw"[..]));
+    assert_eq!(response.into_string().unwrap(), "hello");
+
+    let response = client.delete("/legacy").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    assert_eq!(response.headers().get_one("X-Uri"), Some("/legacy"));
+}
+
+#[test]
+fn native_routes_take_precedence() {
+    let client = client();
+    assert_eq!(client.get("/legacy/native").dispatch().into_string().unwrap(), "native");
+}
+
+#[test]
+fn service_errors_and_limits() {
+    let client = client();
+    let response = client.post("/legacy").body("this body is too long").dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let response = client.get("/fail/anything").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn http_types_as_guards_and_responders() {
+    let client = client();
+    let response = client.get("/parts?x=1").header(Header::new("X-Custom", "hi")).dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.headers().get("X-Custom").collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(response.into_string().unwrap(), "GET /parts?x=1 Some(\"hi\")");
+}
@@ -0,0 +1,59 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Accept, ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use serde_json::{json, Value};
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::Conflict
+}
+
+fn client() -> Client {
+    let figment = rocket::Config::figment().merge(("json_errors", ["/api/"]));
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![fail])
+        .mount("/api", routes![fail]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn json_under_configured_mounts() {
+    let client = client();
+    for uri in &["/api", "/api/nowhere", "/api/fail"] {
+        let response = client.get(*uri).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+    }
+
+    let response = client.get("/api/fail").header(Header::new("X-Request-Id", "abc")).dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    let body: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(body, json!({
+        "error": {
+            "code": 409,
+            "reason": "Conflict",
+            "description": "The request could not be processed because of a conflict in the request.",
+            "request_id": "abc"
+        }
+    }));
+
+    let response = client.get("/api/nowhere").dispatch();
+    let body: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(body["error"]["code"], 404);
+    assert!(body["error"].get("request_id").is_none());
+}
+
+#[test]
+fn html_elsewhere_unless_preferred() {
+    let client = client();
+    for uri in &["/fail", "/nowhere", "/apiary"] {
+        let response = client.get(*uri).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+    }
+
+    let response = client.get("/fail").header(Accept::JSON).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let body: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(body["error"]["reason"], "Conflict");
+}
@@ -0,0 +1,25 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use serde_json::{json, Value};
+
+#[get("/user/<id>")]
+fn user(id: usize) -> Option<Value> {
+    match id {
+        1 => Some(json!({ "id": 1, "roles": ["admin"] })),
+        _ => None,
+    }
+}
+
+#[test]
+fn responds_with_json() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![user])).unwrap();
+    let response = client.get("/user/1").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_string().unwrap(), r#"{"id":1,"roles":["admin"]}"#);
+
+    let response = client.get("/user/2").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "lambda")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::lambda::Lambda;
+use rocket::http::{Cookie, CookieJar};
+use serde_json::{json, Value};
+
+#[get("/echo/<path>?<q>")]
+fn echo(path: String, q: String, jar: &CookieJar<'_>) -> String {
+    let session = jar.get("session").map(|c| c.value().to_string());
+    jar.add(Cookie::new("seen", "yes"));
+    format!("{}:{}:{}", path, q, session.unwrap_or_default())
+}
+
+#[post("/bytes", data = "<data>")]
+fn bytes(data: Vec<u8>) -> Vec<u8> {
+    data.into_iter().rev().collect()
+}
+
+async fn handle(event: Value) -> Value {
+    let rocket = rocket::ignite().mount("/", routes![echo, bytes]);
+    let lambda = Lambda::new(rocket).await.unwrap();
+    lambda.handle(event).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn http_api_event() {
+    let response = handle(json!({
+        "version": "2.0",
+        "rawPath": "/echo/a%20b",
+        "rawQueryString": "q=x%26y",
+        "cookies": ["session=abc"],
+        "headers": { "host": "example.com" },
+        "requestContext": { "http": { "method": "GET", "sourceIp": "10.0.0.1" } },
+        "isBase64Encoded": false
+    })).await;
+
+    assert_eq!(response["statusCode"], 200);
+    assert_eq!(response["body"], "a b:x&y:abc");
+    assert_eq!(response["isBase64Encoded"], false);
+    assert_eq!(response["cookies"][0].as_str().map(|c| c.starts_with("seen=yes")), Some(true));
+    assert!(response["headers"].get("Set-Cookie").is_none());
+}
+
+#[rocket::async_test]
+async fn rest_api_event() {
+    let response = handle(json!({
+        "httpMethod": "GET",
+        "path": "/echo/a b",
+        "multiValueQueryStringParameters": { "q": ["x&y"] },
+        "multiValueHeaders": { "Cookie": ["session=abc"] },
+        "requestContext": { "identity": { "sourceIp": "10.0.0.1" } },
+        "body": null,
+        "isBase64Encoded": false
+    })).await;
+
+    assert_eq!(response["statusCode"], 200);
+    assert_eq!(response["body"], "a b:x&y:abc");
+    let cookies = &response["multiValueHeaders"]["Set-Cookie"];
+    assert_eq!(cookies[0].as_str().map(|c| c.starts_with("seen=yes")), Some(true));
+}
+
+#[rocket::async_test]
+async fn alb_event() {
+    let response = handle(json!({
+        "httpMethod": "GET",
+        "path": "/echo/a%20b",
+        "queryStringParameters": { "q": "x%26y" },
+        "headers": { "cookie": "session=abc" },
+        "requestContext": { "elb": { "targetGroupArn": "arn" } },
+        "isBase64Encoded": false
+    })).await;
+
+    assert_eq!(response["statusCode"], 200);
+    assert_eq!(response["statusDescription"], "200 OK");
+    assert_eq!(response["body"], "a b:x&y:abc");
+    assert!(response["headers"]["Set-Cookie"].is_string());
+    assert!(response.get("multiValueHeaders").is_none());
+}
+
+#[rocket::async_test]
+async fn base64_bodies_and_errors() {
+    let response = handle(json!({
+        "version": "2.0",
+        "rawPath": "/bytes",
+        "requestContext": { "http": { "method": "POST" } },
+        "body": base64::encode(&[0xff, 0xfe, 0x00]),
+        "isBase64Encoded": true
+    })).await;
+
+    assert_eq!(response["statusCode"], 200);
+    assert_eq!(response["isBase64Encoded"], true);
+    assert_eq!(response["body"], base64::encode(&[0x00, 0xfe, 0xff]));
+
+    let lambda = Lambda::new(rocket::ignite()).await.unwrap();
+    assert!(lambda.handle(json!({ "version": "2.0" })).await.is_err());
+    let event = json!({ "httpMethod": "BREW", "path": "/" });
+    assert!(lambda.handle(event).await.is_err());
+}
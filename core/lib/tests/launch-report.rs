@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+
+use rocket::Config;
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::http::{ContentType, Method};
+use rocket::report::LaunchReport;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[post("/", format = "json", data = "<body>", rank = 5)]
+fn create(body: String) -> String { body }
+
+#[catch(404)]
+fn not_found() -> &'static str { "not found" }
+
+async fn launch_report(rocket: rocket::Rocket) -> LaunchReport {
+    let (tx, rx) = oneshot::channel();
+    let tx = Mutex::new(Some(tx));
+    let rocket = rocket.reporter(move |report: &LaunchReport| {
+        if let Some(tx) = tx.lock().unwrap().take() {
+            let _ = tx.send(report.clone());
+        }
+    });
+
+    let shutdown = rocket.shutdown();
+    rocket::tokio::spawn(rocket.launch());
+    let report = rx.await.unwrap();
+    shutdown.shutdown();
+    report
+}
+
+#[rocket::async_test]
+async fn launch_report_describes_application() {
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![index])
+        .mount("/api", routes![create])
+        .register(catchers![not_found])
+        .attach(AdHoc::on_launch("Launcher", |_| ()))
+        .attach(AdHoc::on_response("Responder", |_, _| Box::pin(async {})));
+
+    let report = launch_report(rocket).await;
+    assert_eq!(report.config.profile, Config::DEBUG_PROFILE);
+    assert!(report.endpoint.starts_with("http://127.0.0.1:"));
+    assert_ne!(report.endpoint, "http://127.0.0.1:0");
+
+    assert_eq!(report.routes.len(), 2);
+    assert_eq!(report.routes[0].method, Method::Get);
+    assert_eq!(report.routes[0].uri, "/");
+    assert_eq!(report.routes[0].name, Some("index"));
+
+    let create = &report.routes[1];
+    assert_eq!(create.method, Method::Post);
+    assert_eq!(create.base, "/api");
+    assert_eq!(create.uri, "/api");
+    assert_eq!(create.rank, 5);
+    assert_eq!(create.format.as_ref(), Some(ContentType::JSON.media_type()));
+
+    assert_eq!(report.catchers, vec![Some(404)]);
+
+    let fairings: Vec<_> = report.fairings.iter().map(|info| info.name).collect();
+    assert_eq!(fairings, vec!["Launcher", "Responder"]);
+}
+
+#[test]
+fn route_report_display() {
+    let rocket = rocket::ignite().mount("/api", routes![create]);
+    let route = rocket.routes().next().unwrap();
+    let report = rocket::report::RouteReport::from(route);
+
+    assert_eq!(report.to_string(), route.to_string());
+}
@@ -0,0 +1,33 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Config;
+use rocket::http::{RawStr, Status};
+use rocket::http::uri::Origin;
+use rocket::local::blocking::Client;
+
+#[get("/<name>?<q>")]
+fn echo(name: &RawStr, q: &RawStr, uri: &Origin<'_>) -> String {
+    format!("{} {} {}", uri, name.percent_decode_lossy(), q.percent_decode_lossy())
+}
+
+fn client(lenient_uris: bool) -> Client {
+    let config = Config { lenient_uris, ..Config::debug_default() };
+    Client::tracked(rocket::custom(config).mount("/", routes![echo])).unwrap()
+}
+
+#[test]
+fn malformed_uris_are_rejected_by_default() {
+    let client = client(false);
+    assert_eq!(client.get("/a b?q=\"x\"").dispatch().status(), Status::BadRequest);
+    assert_eq!(client.get("/ab?q=x").dispatch().into_string().unwrap(), "/ab?q=x ab x");
+}
+
+#[test]
+fn malformed_uris_are_encoded_when_lenient() {
+    let client = client(true);
+    let response = client.get("/a b?q=\"x\"").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "/a%20b?q=%22x%22 a b \"x\"");
+
+    assert_eq!(client.get("a b").dispatch().status(), Status::BadRequest);
+}
@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+
+use rocket::{Config, Rocket, State};
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::load::{Load, LoadShed, MaxInFlight};
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::sync::Notify;
+
+#[derive(Default)]
+struct Gate {
+    entered: Notify,
+    release: Notify,
+}
+
+#[get("/slow")]
+async fn slow(gate: State<'_, Arc<Gate>>) -> &'static str {
+    gate.entered.notify_one();
+    gate.release.notified().await;
+    "slow"
+}
+
+#[get("/load")]
+fn load(load: &Load) -> String {
+    format!("{}/{}", load.in_flight(), load.queued())
+}
+
+fn rocket(gate: Arc<Gate>) -> Rocket {
+    rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![slow, load])
+        .manage(gate)
+}
+
+async fn launch(rocket: Rocket) -> u16 {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket.attach(AdHoc::on_launch("Send Port", move |rocket| {
+        tx.send(rocket.config().port).unwrap();
+    }));
+
+    rocket::tokio::spawn(rocket.launch());
+    rx.await.unwrap()
+}
+
+async fn get(port: u16, path: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response.split("\r\n\r\n").nth(1).unwrap_or("").to_string();
+    (status, body)
+}
+
+#[rocket::async_test]
+async fn load_is_tracked() {
+    let rocket = rocket(Arc::default());
+    assert_eq!(rocket.load().in_flight(), 0);
+    assert_eq!(rocket.load().queued(), 0);
+
+    // The request being handled is in flight.
+    let port = launch(rocket).await;
+    assert_eq!(get(port, "/load").await, (200, "1/0".into()));
+}
+
+#[rocket::async_test]
+async fn requests_are_shed_over_threshold() {
+    let gate = Arc::new(Gate::default());
+    let port = launch(rocket(gate.clone()).manage(LoadShed::new(MaxInFlight(1)))).await;
+
+    let slow = rocket::tokio::spawn(get(port, "/slow"));
+    gate.entered.notified().await;
+
+    // With one request in flight, the next is shed.
+    assert_eq!(get(port, "/load").await.0, 503);
+
+    gate.release.notify_one();
+    assert_eq!(slow.await.unwrap(), (200, "slow".into()));
+}
+
+#[rocket::async_test]
+async fn closure_policies_see_the_request() {
+    let policy = |_: &Load, req: &rocket::Request<'_>| req.uri().path() == "/load";
+    let port = launch(rocket(Arc::default()).manage(LoadShed::new(policy))).await;
+    assert_eq!(get(port, "/load").await.0, 503);
+}
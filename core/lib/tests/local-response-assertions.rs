@@ -0,0 +1,79 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[get("/json")]
+fn json() -> serde_json::Value {
+    serde_json::json!({ "name": "Bob", "ids": [1, 2] })
+}
+
+#[get("/text")]
+fn text() -> &'static str {
+    "plain text"
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::ignite().mount("/", routes![json, text])).unwrap()
+}
+
+#[test]
+fn assertions_and_json() {
+    let client = client();
+    let response = client.get("/json").dispatch();
+    response.assert_status(Status::Ok).assert_header("Content-Type", "application/json");
+
+    let value: serde_json::Value = response.into_json().unwrap();
+    assert_eq!(value["ids"][1], 2);
+
+    let pretty = client.get("/json").dispatch().into_string_pretty().unwrap();
+    assert_eq!(pretty, "{\n  \"ids\": [\n    1,\n    2\n  ],\n  \"name\": \"Bob\"\n}");
+
+    let response = client.get("/text").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_json::<serde_json::Value>(), None);
+    assert_eq!(client.get("/text").dispatch().into_string_pretty().unwrap(), "plain text");
+}
+
+#[test]
+#[should_panic(expected = "unexpected response status")]
+fn assert_status_panics() {
+    client().get("/nope").dispatch().assert_status(Status::Ok);
+}
+
+#[test]
+#[should_panic(expected = "expected header `Content-Type: text/html`")]
+fn assert_header_panics() {
+    client().get("/text").dispatch().assert_header("Content-Type", "text/html");
+}
+
+#[test]
+fn snapshots() {
+    let dir = std::env::temp_dir().join(format!("rocket-snapshots-{}", std::process::id()));
+    let path = dir.join("nested").join("json.txt");
+
+    // A missing snapshot fails unless snapshots are being updated.
+    let missing = std::panic::catch_unwind(|| {
+        let client = self::client();
+        client.get("/json").dispatch().assert_snapshot(&path);
+    });
+
+    assert!(missing.is_err());
+    assert!(!path.exists());
+
+    // Updating writes the snapshot; subsequent runs compare against it.
+    let client = client();
+    std::env::set_var("ROCKET_UPDATE_SNAPSHOTS", "1");
+    client.get("/json").dispatch().assert_snapshot(&path);
+    std::env::remove_var("ROCKET_UPDATE_SNAPSHOTS");
+    assert!(std::fs::read_to_string(&path).unwrap().contains("\"name\": \"Bob\""));
+    client.get("/json").dispatch().assert_snapshot(&path);
+
+    let mismatch = std::panic::catch_unwind(|| {
+        let client = self::client();
+        client.get("/text").dispatch().assert_snapshot(&path);
+    });
+
+    assert!(mismatch.is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,116 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::http::{ContentType, Header};
+use rocket::local::blocking::Client;
+use rocket::response::{Multipart, Part};
+
+#[get("/mixed")]
+fn mixed() -> Multipart<'static> {
+    Multipart::mixed()
+        .boundary("BOUNDARY")
+        .part(Part::new(Cursor::new("{\"a\":1}")).content_type(ContentType::JSON))
+        .part(Part::new(Cursor::new("hi"))
+            .header(Header::new("X-Part", "1"))
+            .header(Header::new("x-part", "2")))
+}
+
+#[get("/form")]
+fn form() -> Multipart<'static> {
+    Multipart::form_data()
+        .boundary("b")
+        .part(Part::form_field("name", Cursor::new("bob")))
+        .part(Part::form_field("a \"b\"\r\nc", Cursor::new("x")))
+}
+
+#[get("/quoted")]
+fn quoted() -> Multipart<'static> {
+    Multipart::mixed()
+        .boundary("a=b c")
+        .part(Part::new(Cursor::new("x"))
+            .header(Header::new("X-A", "1\r\n--a=b c\r\nX-B: 2")))
+}
+
+#[get("/empty")]
+fn empty() -> Multipart<'static> {
+    Multipart::new("byteranges").boundary("b")
+}
+
+#[get("/random")]
+fn random() -> Multipart<'static> {
+    Multipart::mixed().part(Part::new(Cursor::new("x")))
+}
+
+fn client() -> Client {
+    let routes = routes![mixed, form, quoted, empty, random];
+    Client::tracked(rocket::ignite().mount("/", routes)).unwrap()
+}
+
+#[test]
+fn multipart_mixed() {
+    let client = client();
+    let response = client.get("/mixed").dispatch();
+    let content_type = response.content_type().unwrap().to_string();
+    assert_eq!(content_type, "multipart/mixed; boundary=BOUNDARY");
+    assert_eq!(response.into_string().unwrap(),
+        "--BOUNDARY\r\nContent-Type: application/json\r\n\r\n{\"a\":1}\r\n\
+         --BOUNDARY\r\nx-part: 2\r\n\r\nhi\r\n\
+         --BOUNDARY--\r\n");
+}
+
+#[test]
+fn multipart_form_data() {
+    let client = client();
+    let response = client.get("/form").dispatch();
+    let content_type = response.content_type().unwrap().to_string();
+    assert_eq!(content_type, "multipart/form-data; boundary=b");
+    assert_eq!(response.into_string().unwrap(),
+        "--b\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nbob\r\n\
+         --b\r\nContent-Disposition: form-data; name=\"a %22b%22%0D%0Ac\"\r\n\r\nx\r\n\
+         --b--\r\n");
+}
+
+#[test]
+fn multipart_quotes_boundary_and_strips_newlines() {
+    let client = client();
+    let response = client.get("/quoted").dispatch();
+    let content_type = response.headers().get_one("Content-Type");
+    assert_eq!(content_type, Some("multipart/mixed; boundary=\"a=b c\""));
+    assert_eq!(response.into_string().unwrap(),
+        "--a=b c\r\nX-A: 1  --a=b c  X-B: 2\r\n\r\nx\r\n--a=b c--\r\n");
+}
+
+#[test]
+#[should_panic(expected = "invalid multipart boundary")]
+fn multipart_rejects_invalid_boundary() {
+    let _ = Multipart::mixed().boundary("a\r\nb");
+}
+
+#[test]
+#[should_panic(expected = "invalid multipart boundary")]
+fn multipart_rejects_long_boundary() {
+    let _ = Multipart::mixed().boundary("b".repeat(71));
+}
+
+#[test]
+fn multipart_empty() {
+    let response = client().get("/empty").dispatch();
+    assert_eq!(response.into_string().unwrap(), "--b--\r\n");
+}
+
+#[test]
+fn multipart_random_boundary() {
+    let client = client();
+    let a = client.get("/random").dispatch();
+    let b = client.get("/random").dispatch();
+    let boundary = |ct: ContentType| {
+        ct.params().find(|(k, _)| *k == "boundary").unwrap().1.to_string()
+    };
+
+    let boundary_a = boundary(a.content_type().unwrap());
+    let boundary_b = boundary(b.content_type().unwrap());
+    assert_eq!(boundary_a.len(), 40);
+    assert_ne!(boundary_a, boundary_b);
+    assert!(a.into_string().unwrap().starts_with(&format!("--{}\r\n", boundary_a)));
+}
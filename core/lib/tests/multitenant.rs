@@ -0,0 +1,136 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Config, Request, State};
+use rocket::multitenant::{Tenant, Tenants, TenantState, Subdomain, FromHeader, PathPrefix};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+#[derive(serde::Deserialize)]
+struct Plan {
+    plan: String,
+    seats: usize,
+}
+
+#[get("/")]
+fn index(tenant: Tenant<'_>, hits: State<'_, TenantState<AtomicUsize>>) -> String {
+    let hits = hits.get(&tenant).fetch_add(1, Ordering::Relaxed) + 1;
+    format!("{}:{}", tenant, hits)
+}
+
+#[get("/", rank = 2)]
+fn landing() -> &'static str {
+    "landing"
+}
+
+#[get("/plan")]
+fn plan(tenant: Tenant<'_>) -> Option<String> {
+    let plan = tenant.config::<Plan>()?;
+    Some(format!("{} {}", plan.plan, plan.seats))
+}
+
+#[get("/<_>/scope/<key>")]
+fn scope(tenant: Tenant<'_>, key: String) -> String {
+    tenant.scoped(&key)
+}
+
+fn rocket() -> rocket::Rocket {
+    rocket::ignite()
+        .manage(TenantState::new(|_| AtomicUsize::new(0)))
+        .mount("/", routes![index, landing, plan, scope])
+}
+
+#[test]
+fn subdomain_tenants_are_isolated() {
+    let client = Client::tracked(rocket().attach(Tenants::fairing(Subdomain::of("example.com"))))
+        .unwrap();
+
+    let get = |host: &'static str| {
+        client.get("/").header(Header::new("Host", host)).dispatch().into_string().unwrap()
+    };
+
+    assert_eq!(get("acme.example.com"), "acme:1");
+    assert_eq!(get("acme.example.com:8000"), "acme:2");
+    assert_eq!(get("globex.example.com"), "globex:1");
+    assert_eq!(get("example.com"), "landing");
+    assert_eq!(get("a.b.example.com"), "landing");
+    assert_eq!(get("acme.example.org"), "landing");
+}
+
+#[test]
+fn header_and_closure_resolvers() {
+    let client = Client::tracked(rocket().attach(Tenants::fairing(FromHeader("X-Tenant"))))
+        .unwrap();
+
+    let response = client.get("/").header(Header::new("X-Tenant", "acme")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "acme:1");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "landing");
+
+    let resolver = |req: &Request<'_>| req.headers().get_one("X-Org").map(|o| o.to_lowercase());
+    let client = Client::tracked(rocket().attach(Tenants::fairing(resolver))).unwrap();
+    let response = client.get("/").header(Header::new("X-Org", "Initech")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "initech:1");
+}
+
+#[test]
+fn path_prefix_tenants_scope_keys() {
+    let client = Client::tracked(rocket().attach(Tenants::fairing(PathPrefix))).unwrap();
+    let response = client.get("/acme/scope/session").dispatch();
+    assert_eq!(response.into_string().unwrap(), "acme_session");
+
+    // `acme_b` would otherwise scope `x` to `acme_b_x`, as `acme` scopes `b_x`.
+    assert_eq!(client.get("/acme_b/scope/x").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn invalid_tenant_keys_are_ignored() {
+    let client = Client::tracked(rocket().attach(Tenants::fairing(FromHeader("X-Tenant"))))
+        .unwrap();
+
+    for key in &["a_b", "ACME", "a.b", "a b"] {
+        let response = client.get("/").header(Header::new("X-Tenant", *key)).dispatch();
+        assert_eq!(response.into_string().unwrap(), "landing");
+    }
+}
+
+#[test]
+fn tenant_state_is_bounded() {
+    let state = TenantState::new(|_| AtomicUsize::new(0)).capacity(2);
+    state.get_key("a").fetch_add(1, Ordering::Relaxed);
+    state.get_key("b").fetch_add(1, Ordering::Relaxed);
+    assert_eq!(state.get_key("a").load(Ordering::Relaxed), 1);
+
+    state.get_key("c");
+    assert_eq!(state.get_key("b").load(Ordering::Relaxed), 1);
+    assert_eq!(state.get_key("a").load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn configured_tenants_are_an_allow_list() {
+    let figment = Config::figment()
+        .merge(("tenants.acme.plan", "enterprise"))
+        .merge(("tenants.acme.seats", 250))
+        .merge(("tenants.globex.plan", "free"))
+        .merge(("tenants.globex.seats", 5));
+
+    let rocket = rocket::custom(figment)
+        .attach(Tenants::fairing(FromHeader("X-Tenant")))
+        .mount("/", routes![plan]);
+
+    let client = Client::tracked(rocket).unwrap();
+    let get = |tenant: &'static str| {
+        client.get("/plan").header(Header::new("X-Tenant", tenant)).dispatch()
+    };
+
+    assert_eq!(get("acme").into_string().unwrap(), "enterprise 250");
+    assert_eq!(get("globex").into_string().unwrap(), "free 5");
+    assert_eq!(get("initech").status(), Status::NotFound);
+}
+
+#[test]
+fn tenant_guard_requires_fairing() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.get("/plan").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
@@ -0,0 +1,97 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::outcome::Outcome;
+use rocket::request::{Page, PageLimits};
+use rocket::local::blocking::Client;
+
+#[get("/items")]
+fn items(page: Page) -> String {
+    format!("{} {} {} {:?}", page.page(), page.per_page(), page.offset(), page.cursor())
+}
+
+#[get("/links?<total>")]
+fn links(page: Page, total: Option<u64>, request: &Request<'_>) -> String {
+    page.links(request.uri(), total).value().to_string()
+}
+
+#[catch(400)]
+async fn bad_request(request: &Request<'_>) -> String {
+    match request.guard::<Page>().await {
+        Outcome::Failure((_, e)) => e.to_string(),
+        _ => "ok".into(),
+    }
+}
+
+fn client(limits: Option<PageLimits>) -> Client {
+    let mut rocket = rocket::ignite()
+        .mount("/", routes![items, links])
+        .register(catchers![bad_request]);
+
+    if let Some(limits) = limits {
+        rocket = rocket.manage(limits);
+    }
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn parses_parameters() {
+    let client = client(None);
+    let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+    assert_eq!(get("/items"), "1 20 0 None");
+    assert_eq!(get("/items?page=3"), "3 20 40 None");
+    assert_eq!(get("/items?page=2&per_page=5"), "2 5 5 None");
+    assert_eq!(get("/items?offset=7&limit=5"), "2 5 7 None");
+    assert_eq!(get("/items?cursor=a%20b&limit=10"), "1 10 0 Some(\"a b\")");
+}
+
+#[test]
+fn rejects_invalid_parameters() {
+    let client = client(Some(PageLimits { default_per_page: 10, max_per_page: 50 }));
+    let get = |uri: &str| {
+        let response = client.get(uri.to_string()).dispatch();
+        (response.status(), response.into_string().unwrap())
+    };
+
+    assert_eq!(get("/items"), (Status::Ok, "1 10 0 None".into()));
+    assert_eq!(get("/items?per_page=50"), (Status::Ok, "1 50 0 None".into()));
+
+    let bad = |uri: &str| {
+        let (status, body) = get(uri);
+        assert_eq!(status, Status::BadRequest);
+        body
+    };
+
+    assert_eq!(bad("/items?page=two"), "invalid `page`: \"two\"");
+    assert_eq!(bad("/items?page=0"), "`page` must be greater than 0");
+    assert_eq!(bad("/items?limit=0"), "`limit` must be greater than 0");
+    assert_eq!(bad("/items?per_page=51"), "page size 51 exceeds the maximum of 50");
+    assert_eq!(bad("/items?per_page=5&limit=5"), "`per_page` and `limit` cannot both be used");
+    assert_eq!(bad("/items?page=1&offset=5"), "`page` and `offset` cannot both be used");
+    assert_eq!(bad("/items?offset=1&cursor=x"), "`offset` and `cursor` cannot both be used");
+    assert_eq!(bad("/items?offset=-1"), "invalid `offset`: \"-1\"");
+}
+
+#[test]
+fn generates_link_headers() {
+    let client = client(None);
+    let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+    assert_eq!(get("/links?per_page=10"),
+        "</links?page=1&per_page=10>; rel=\"first\", \
+         </links?page=2&per_page=10>; rel=\"next\"");
+
+    assert_eq!(get("/links?total=25&page=3&per_page=10"),
+        "</links?total=25&page=1&per_page=10>; rel=\"first\", \
+         </links?total=25&page=2&per_page=10>; rel=\"prev\", \
+         </links?total=25&page=3&per_page=10>; rel=\"last\"");
+
+    assert_eq!(get("/links?offset=15&limit=10&total=40"),
+        "</links?total=40&offset=0&limit=10>; rel=\"first\", \
+         </links?total=40&offset=5&limit=10>; rel=\"prev\", \
+         </links?total=40&offset=25&limit=10>; rel=\"next\", \
+         </links?total=40&offset=30&limit=10>; rel=\"last\"");
+}
@@ -0,0 +1,52 @@
+#[macro_use] extern crate rocket;
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use rocket::request::{Form, State};
+use rocket::http::Method;
+
+#[derive(FromForm)]
+struct Note {
+    title: Cow<'static, str>,
+    pinned: Box<bool>,
+}
+
+#[post("/", data = "<note>")]
+fn boxed(note: Form<Arc<Note>>) -> Box<String> {
+    Box::new(format!("{} {}", note.title, note.pinned))
+}
+
+#[get("/")]
+fn method(method: Box<Method>, state: Arc<State<'_, u8>>) -> Box<&'static str> {
+    assert_eq!(**state, 7);
+    Box::new(method.as_str())
+}
+
+mod pointer_guards_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::{ContentType, Status};
+
+    #[test]
+    fn test_pointer_guards() {
+        let rocket = rocket::ignite().manage(7u8).mount("/", routes![boxed, method]);
+        let client = Client::tracked(rocket).unwrap();
+
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body("title=Hello%20there&pinned=on")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "Hello there true");
+
+        // `Box<bool>` inherits `bool`'s default when missing.
+        let response = client.post("/").header(ContentType::Form).body("title=a").dispatch();
+        assert_eq!(response.into_string().unwrap(), "a false");
+
+        let response = client.post("/").header(ContentType::Form).body("pinned=on").dispatch();
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+
+        assert_eq!(client.get("/").dispatch().into_string().unwrap(), "GET");
+    }
+}
@@ -0,0 +1,114 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::request::{Form, FormError, Page};
+use rocket::response::Problem;
+use rocket::local::blocking::Client;
+use serde_json::{json, Value};
+
+#[derive(FromForm)]
+struct Input {
+    count: usize,
+}
+
+#[get("/credit")]
+fn credit() -> Problem {
+    Problem::new(Status::Forbidden)
+        .kind("https://example.com/probs/out-of-credit")
+        .title("You do not have enough credit.")
+        .detail("Your current balance is 30, but that costs 50.")
+        .instance("/account/12345/msgs/abc")
+        .extension("balance", 30)
+        .extension("accounts", vec!["/account/12345", "/account/67890"])
+}
+
+#[post("/form", data = "<form>")]
+fn form(form: Result<Form<Input>, FormError<'_>>) -> Result<String, Problem> {
+    Ok(form.map_err(Problem::from)?.count.to_string())
+}
+
+#[get("/page")]
+fn page(page: Result<Page, rocket::request::PageError>) -> Result<String, Problem> {
+    let page = page.map_err(|e| Problem::from((Status::BadRequest, e)))?;
+    Ok(page.page().to_string())
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![credit, form, page])
+        .register(vec![Problem::catcher()]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+fn problem(response: rocket::local::blocking::LocalResponse<'_>) -> (Status, Value) {
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "problem+json")));
+    let status = response.status();
+    (status, serde_json::from_str(&response.into_string().unwrap()).unwrap())
+}
+
+#[test]
+fn responds_with_members() {
+    let client = client();
+    let (status, body) = problem(client.get("/credit").dispatch());
+    assert_eq!(status, Status::Forbidden);
+    assert_eq!(body, json!({
+        "type": "https://example.com/probs/out-of-credit",
+        "title": "You do not have enough credit.",
+        "status": 403,
+        "detail": "Your current balance is 30, but that costs 50.",
+        "instance": "/account/12345/msgs/abc",
+        "balance": 30,
+        "accounts": ["/account/12345", "/account/67890"]
+    }));
+}
+
+#[test]
+fn converts_form_errors() {
+    let client = client();
+    let post = |body: &str| {
+        let response = client.post("/form")
+            .header(ContentType::Form)
+            .body(body)
+            .dispatch();
+
+        problem(response)
+    };
+
+    assert_eq!(post("count=ten"), (Status::UnprocessableEntity, json!({
+        "title": "Unprocessable Entity",
+        "status": 422,
+        "detail": "invalid value for field `count`",
+        "field": "count"
+    })));
+
+    assert_eq!(post(""), (Status::UnprocessableEntity, json!({
+        "title": "Unprocessable Entity",
+        "status": 422,
+        "detail": "missing field `count`",
+        "field": "count"
+    })));
+
+    let response = client.post("/form").header(ContentType::Form).body("count=7").dispatch();
+    assert_eq!(response.into_string().unwrap(), "7");
+}
+
+#[test]
+fn converts_guard_failures() {
+    let client = client();
+    assert_eq!(problem(client.get("/page?page=0").dispatch()), (Status::BadRequest, json!({
+        "title": "Bad Request",
+        "status": 400,
+        "detail": "`page` must be greater than 0"
+    })));
+}
+
+#[test]
+fn catches_unhandled_statuses() {
+    let client = client();
+    assert_eq!(problem(client.get("/nowhere?a=b").dispatch()), (Status::NotFound, json!({
+        "title": "Not Found",
+        "status": 404,
+        "instance": "/nowhere?a=b"
+    })));
+}
@@ -0,0 +1,41 @@
+use rocket::config::Config;
+use rocket::figment::providers::{Format, Toml};
+
+#[test]
+fn reconfigure_takes_precedence() {
+    rocket::figment::Jail::expect_with(|jail| {
+        jail.create_file("Rocket.toml", r#"
+            [default]
+            port = 1234
+            workers = 3
+        "#)?;
+
+        jail.create_file("App.toml", r#"
+            [default]
+            port = 4321
+
+            [default.databases.my_db]
+            url = "db.sqlite"
+        "#)?;
+
+        jail.set_env("ROCKET_KEEP_ALIVE", 17);
+
+        let rocket = rocket::custom(Config::figment().merge(("log_level", "off")));
+        assert_eq!(rocket.config().port, 1234);
+        assert_eq!(rocket.config().keep_alive, 17);
+
+        let rocket = rocket.reconfigure(Toml::file("App.toml").nested());
+        assert_eq!(rocket.config().port, 4321);
+        assert_eq!(rocket.config().workers, 3);
+        assert_eq!(rocket.config().keep_alive, 17);
+
+        let url: String = rocket.figment().focus("databases.my_db").extract_inner("url")?;
+        assert_eq!(url, "db.sqlite");
+
+        let rocket = rocket.reconfigure(("keep_alive", 0));
+        assert_eq!(rocket.config().keep_alive, 0);
+        assert_eq!(rocket.config().port, 4321);
+
+        Ok(())
+    });
+}
@@ -0,0 +1,61 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::Deadline;
+use rocket::local::blocking::Client;
+
+#[get("/remaining")]
+fn remaining(deadline: Deadline) -> String {
+    match deadline.remaining() {
+        Some(left) => (left <= Duration::from_secs(1) && !deadline.is_expired()).to_string(),
+        None => "none".into(),
+    }
+}
+
+#[get("/slow")]
+async fn slow() -> &'static str {
+    rocket::tokio::time::sleep(Duration::from_secs(5)).await;
+    "finished"
+}
+
+#[get("/connected")]
+fn connected(request: &Request<'_>) -> String {
+    request.is_client_disconnected().to_string()
+}
+
+fn client(timeout: u32) -> Client {
+    let figment = rocket::Config::figment().merge(("request_timeout", timeout));
+    Client::tracked(rocket::custom(figment).mount("/", routes![remaining, slow, connected]))
+        .unwrap()
+}
+
+#[test]
+fn no_deadline_by_default() {
+    let client = client(0);
+    assert_eq!(client.get("/remaining").dispatch().into_string().unwrap(), "none");
+    assert_eq!(client.get("/connected").dispatch().into_string().unwrap(), "false");
+}
+
+#[test]
+fn deadline_is_exposed() {
+    let client = client(1);
+    assert_eq!(client.get("/remaining").dispatch().into_string().unwrap(), "true");
+}
+
+#[test]
+fn deadline_starts_at_dispatch() {
+    let client = client(1);
+    let request = client.get("/remaining");
+    std::thread::sleep(Duration::from_millis(1200));
+    assert_eq!(request.dispatch().into_string().unwrap(), "true");
+}
+
+#[test]
+fn expired_deadline_cancels_handler() {
+    let client = client(1);
+    let response = client.get("/slow").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+}
@@ -0,0 +1,66 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Rocket};
+use rocket::request::{FromForm, FormItems};
+
+#[derive(FromForm)]
+struct Query {
+    a: usize,
+}
+
+#[get("/item/<id>")]
+fn item(id: usize) -> String { id.to_string() }
+
+#[post("/item")]
+fn new_item() { }
+
+fn rocket() -> Rocket {
+    rocket::ignite().mount("/", routes![item, new_item])
+}
+
+fn parts<'r>(rocket: &'r Rocket, method: &str, uri: &'r [u8]) -> Option<Request<'r>> {
+    let headers: Vec<(&str, Vec<u8>)> = vec![("X-Raw", vec![0xff, b'a'])];
+    Request::from_raw_parts(rocket, method, uri, headers).ok()
+}
+
+#[test]
+fn raw_parts_route() {
+    let rocket = rocket();
+    let request = parts(&rocket, "get", b"/item/10").unwrap();
+    assert_eq!(rocket.matching_routes(&request).count(), 1);
+    assert_eq!(request.headers().get_one("X-Raw"), Some("\u{fffd}a"));
+    assert_eq!(request.headers().get_bytes("X-Raw").next(), Some(&[0xff, b'a'][..]));
+
+    let request = parts(&rocket, "POST", b"/item/10").unwrap();
+    assert_eq!(rocket.matching_routes(&request).count(), 0);
+
+    let request = parts(&rocket, "POST", b"/item").unwrap();
+    assert_eq!(rocket.matching_routes(&request).count(), 1);
+}
+
+#[test]
+fn raw_parts_query_forms() {
+    let rocket = rocket();
+    let request = parts(&rocket, "GET", b"/?a=10&b=2").unwrap();
+    let query = request.uri().query().unwrap();
+    let form = Query::from_form(&mut FormItems::from(query), false).unwrap();
+    assert_eq!(form.a, 10);
+}
+
+#[test]
+fn raw_parts_never_panic() {
+    let rocket = rocket();
+    let inputs: &[&[u8]] = &[
+        b"", b"/", b"//", b"/%", b"/%ff", b"/\xff\xfe", b"?", b"/?&&==",
+        b"/a b", b"http://x/", b"*", b"/\0", b"/<x>", b"/item/-1",
+    ];
+
+    for method in &["GET", "get", "", "\u{0}", "PATCH", "CONNECT"] {
+        for uri in inputs {
+            if let Some(request) = parts(&rocket, method, uri) {
+                let _ = rocket.matching_routes(&request).count();
+                let _ = request.raw_query_items().map(|items| items.count());
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+#[macro_use] extern crate rocket;
+
+use std::path::{Path, PathBuf};
+
+use rocket::{Config, Rocket};
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::record::{Recorded, Recorder};
+
+#[post("/echo?<n>", data = "<body>")]
+fn echo(n: usize, body: String, content_type: &ContentType) -> String {
+    format!("{} {}: {}", n, content_type, body)
+}
+
+#[post("/bytes", data = "<data>")]
+async fn bytes(data: Data) -> String {
+    let bytes = data.open(1.kibibytes()).stream_to_vec().await.unwrap();
+    bytes.len().to_string()
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+fn rocket(profile: rocket::figment::Profile) -> Rocket {
+    let figment = Config::figment()
+        .select(profile)
+        .merge(("secret_key", "hPRYyVRiMyxpw5sBB1XeCMN1kFsDCqKvBi2QJxBVHQk="));
+
+    rocket::custom(figment).mount("/", routes![echo, bytes, index])
+}
+
+fn recording(name: &str) -> PathBuf {
+    let name = format!("rocket-replay-{}-{}.jsonl", name, std::process::id());
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn record(path: &Path, limit: u64) {
+    let recorder = Recorder::fairing(path).limit(limit.bytes());
+    let client = Client::untracked(rocket(Config::DEBUG_PROFILE).attach(recorder)).unwrap();
+
+    client.get("/").header(Header::new("X-Trace", "abc")).dispatch();
+    client.post("/echo?n=7")
+        .header(ContentType::Plain)
+        .remote("10.0.0.1:1234".parse().unwrap())
+        .body("Hello, world!")
+        .dispatch();
+
+    client.post("/bytes").body(vec![0xffu8, 0xfe, 0x00, 0x01]).dispatch();
+
+    // Records are written by a background thread; wait for all three.
+    for _ in 0..500 {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        if contents.lines().count() >= 3 {
+            return;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn records_requests_in_debug_profile() {
+    let path = recording("records");
+    record(&path, 1024);
+
+    let records = Recorded::load(&path).unwrap();
+    assert_eq!(records.len(), 3);
+
+    assert_eq!(records[0].method(), rocket::http::Method::Get);
+    assert_eq!(records[0].uri(), "/");
+    assert!(records[0].headers().any(|h| h == ("X-Trace", "abc")));
+    assert_eq!(records[0].body(), None);
+
+    assert_eq!(records[1].uri(), "/echo?n=7");
+    assert_eq!(records[1].remote(), Some("10.0.0.1:1234".parse().unwrap()));
+    assert!(records[1].headers().any(|h| h == ("Content-Type", "text/plain; charset=utf-8")));
+    assert_eq!(records[1].body(), Some(&b"Hello, world!"[..]));
+    assert!(!records[1].is_truncated());
+
+    assert_eq!(records[2].body(), Some(&[0xff, 0xfe, 0x00, 0x01][..]));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn truncates_bodies_to_limit() {
+    let path = recording("truncates");
+    record(&path, 5);
+
+    let records = Recorded::load(&path).unwrap();
+    assert_eq!(records[1].body(), Some(&b"Hello"[..]));
+    assert!(records[1].is_truncated());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn replays_recorded_requests() {
+    let path = recording("replays");
+    record(&path, 1024);
+
+    let client = Client::untracked(rocket(Config::DEBUG_PROFILE)).unwrap();
+    let responses: Vec<_> = client.replay(&path).unwrap()
+        .into_iter()
+        .map(|request| request.dispatch().into_string().unwrap())
+        .collect();
+
+    assert_eq!(responses, vec!["index", "7 text/plain; charset=utf-8: Hello, world!", "4"]);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn does_not_record_outside_debug_profile() {
+    let path = recording("release");
+    let recorder = Recorder::fairing(&path);
+    let client = Client::untracked(rocket(Config::RELEASE_PROFILE).attach(recorder)).unwrap();
+    assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+    assert!(!path.exists());
+}
+
+#[test]
+fn rejects_invalid_recordings() {
+    let path = recording("invalid");
+    std::fs::write(&path, "{\"method\":\"FOO\",\"uri\":\"/\",\"remote\":null,\
+        \"headers\":[],\"body\":null,\"truncated\":false}\n").unwrap();
+
+    let client = Client::untracked(rocket(Config::DEBUG_PROFILE)).unwrap();
+    let error = client.replay(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    let _ = std::fs::remove_file(&path);
+}
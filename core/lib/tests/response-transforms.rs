@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::fairing::AdHoc;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::content::Html;
+use rocket::tokio::io::AsyncReadExt;
+
+#[get("/page")]
+fn page() -> Html<&'static str> {
+    Html("<p>Hello</p>")
+}
+
+#[get("/plain")]
+fn plain() -> &'static str {
+    "plain"
+}
+
+fn footer(_: &Request<'_>, res: &mut Response<'_>) {
+    if res.content_type() == Some(ContentType::HTML) {
+        res.map_body(|body| body.chain(&b"<footer/>"[..]));
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![plain])
+        .mount("/docs", routes![page, plain])
+        .transform("/docs", footer)
+        .transform("/", |_: &Request<'_>, res: &mut Response<'_>| {
+            let order = match res.headers().get_one("X-Order") {
+                Some(order) => format!("{},global", order),
+                None => "global".into(),
+            };
+
+            res.set_header(Header::new("X-Order", order));
+        })
+        .transform("/docs/", |_: &Request<'_>, res: &mut Response<'_>| {
+            let order = res.headers().get_one("X-Order").unwrap_or("").to_string();
+            res.set_header(Header::new("X-Order", format!("{},docs", order)));
+        })
+        .attach(AdHoc::on_response("Observer", |_, res| Box::pin(async move {
+            let order = res.headers().get_one("X-Order").unwrap_or("").to_string();
+            res.set_header(Header::new("X-Order", format!("{},fairing", order)));
+        })));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn transforms_apply_under_their_mount_point() {
+    let client = client();
+    let response = client.get("/docs/page").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "<p>Hello</p><footer/>");
+
+    let response = client.get("/docs/plain").dispatch();
+    assert_eq!(response.into_string().unwrap(), "plain");
+
+    let response = client.get("/plain").dispatch();
+    assert_eq!(response.headers().get_one("X-Order"), Some("global,fairing"));
+}
+
+#[test]
+fn transforms_run_in_order_before_fairings() {
+    let client = client();
+    let response = client.get("/docs/plain").dispatch();
+    assert_eq!(response.headers().get_one("X-Order"), Some("global,docs,fairing"));
+}
+
+#[test]
+fn transforms_apply_to_catcher_responses() {
+    let client = client();
+    let response = client.get("/docs/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.headers().get_one("X-Order"), Some("global,docs,fairing"));
+    assert!(response.into_string().unwrap().ends_with("<footer/>"));
+
+    // A prefix that isn't a path segment doesn't match.
+    let response = client.get("/docsmissing").dispatch();
+    assert_eq!(response.headers().get_one("X-Order"), Some("global,fairing"));
+}
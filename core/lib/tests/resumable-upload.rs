@@ -0,0 +1,140 @@
+use std::path::PathBuf;
+
+use rocket::data::ToByteUnit;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::upload::resumable::{FileStorage, Resumable};
+
+fn uploads(name: &str) -> PathBuf {
+    let name = format!("rocket-uploads-{}-{}", name, std::process::id());
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&path);
+    path
+}
+
+fn client(root: &PathBuf) -> Client {
+    let uploads = Resumable::new(FileStorage::new(root)).max_size(1.kibibytes());
+    Client::tracked(rocket::ignite().mount("/files", uploads)).unwrap()
+}
+
+fn tus() -> Header<'static> {
+    Header::new("Tus-Resumable", "1.0.0")
+}
+
+fn offset_stream() -> ContentType {
+    ContentType::new("application", "offset+octet-stream")
+}
+
+fn header<'a>(response: &'a LocalResponse<'_>, name: &str) -> Option<&'a str> {
+    response.headers().get_one(name)
+}
+
+fn create(client: &Client, length: usize) -> String {
+    let response = client.post("/files")
+        .header(tus())
+        .header(Header::new("Upload-Length", length.to_string()))
+        .header(Header::new("Upload-Metadata", "filename d29ybGQudHh0"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Created);
+    assert_eq!(header(&response, "Tus-Resumable"), Some("1.0.0"));
+    header(&response, "Location").unwrap().to_string()
+}
+
+fn patch<'c>(client: &'c Client, location: &str, offset: u64, body: &str) -> LocalResponse<'c> {
+    client.patch(location.to_string())
+        .header(tus())
+        .header(offset_stream())
+        .header(Header::new("Upload-Offset", offset.to_string()))
+        .body(body)
+        .dispatch()
+}
+
+#[test]
+fn uploads_resume_at_reported_offset() {
+    let root = uploads("resume");
+    let client = client(&root);
+    let location = create(&client, 11);
+    assert!(location.starts_with("/files/"));
+
+    let response = client.head(&location).header(tus()).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(header(&response, "Upload-Offset"), Some("0"));
+    assert_eq!(header(&response, "Upload-Length"), Some("11"));
+    assert_eq!(header(&response, "Upload-Metadata"), Some("filename d29ybGQudHh0"));
+
+    let response = patch(&client, &location, 0, "hello");
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Upload-Offset"), Some("5"));
+
+    // Resume from the offset reported by `HEAD`.
+    let response = client.head(&location).header(tus()).dispatch();
+    assert_eq!(header(&response, "Upload-Offset"), Some("5"));
+
+    // Bytes past the upload's length are ignored.
+    let response = patch(&client, &location, 5, " world!!!");
+    assert_eq!(header(&response, "Upload-Offset"), Some("11"));
+
+    let id = location.rsplit('/').next().unwrap();
+    let path = FileStorage::new(&root).path(id).unwrap();
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "hello world");
+}
+
+#[test]
+fn mismatched_offsets_conflict() {
+    let root = uploads("conflict");
+    let client = client(&root);
+    let location = create(&client, 10);
+
+    assert_eq!(patch(&client, &location, 3, "abc").status(), Status::Conflict);
+    assert_eq!(patch(&client, &location, 0, "abc").status(), Status::NoContent);
+    assert_eq!(patch(&client, &location, 0, "abc").status(), Status::Conflict);
+}
+
+#[test]
+fn invalid_requests_are_rejected() {
+    let root = uploads("invalid");
+    let client = client(&root);
+
+    let response = client.post("/files").dispatch();
+    assert_eq!(response.status(), Status::PreconditionFailed);
+    assert_eq!(header(&response, "Tus-Version"), Some("1.0.0"));
+
+    let response = client.post("/files").header(tus()).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/files")
+        .header(tus())
+        .header(Header::new("Upload-Length", "2048"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let location = create(&client, 3);
+    let response = client.patch(location.clone())
+        .header(tus())
+        .header(ContentType::Binary)
+        .header(Header::new("Upload-Offset", "0"))
+        .body("abc")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::UnsupportedMediaType);
+    assert_eq!(patch(&client, "/files/unknown", 0, "abc").status(), Status::NotFound);
+    assert_eq!(patch(&client, "/files/..%2F..%2Fetc", 0, "abc").status(), Status::NotFound);
+}
+
+#[test]
+fn options_and_termination() {
+    let root = uploads("termination");
+    let client = client(&root);
+
+    let response = client.options("/files").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(header(&response, "Tus-Extension"), Some("creation,termination"));
+    assert_eq!(header(&response, "Tus-Max-Size"), Some("1024"));
+
+    let location = create(&client, 3);
+    assert_eq!(client.delete(&location).header(tus()).dispatch().status(), Status::NoContent);
+    assert_eq!(client.delete(&location).header(tus()).dispatch().status(), Status::NotFound);
+    assert_eq!(client.head(&location).header(tus()).dispatch().status(), Status::NotFound);
+}
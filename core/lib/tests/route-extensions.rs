@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Route, Extensions};
+use rocket::fairing::AdHoc;
+use rocket::handler::{Handler, Outcome};
+use rocket::http::{Header, Method, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+use rocket::data::Data;
+
+struct Scopes(&'static [&'static str]);
+
+struct CacheTtl(u32);
+
+fn admin(extensions: &mut Extensions) {
+    extensions.insert(Scopes(&["admin"])).insert(CacheTtl(60));
+}
+
+struct Authorized;
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Authorized {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let required = req.route()
+            .and_then(|route| route.extensions.get::<Scopes>())
+            .map_or(&[][..], |scopes| scopes.0);
+
+        let granted: Vec<_> = req.headers().get("X-Scope").collect();
+        match required.iter().all(|scope| granted.contains(scope)) {
+            true => request::Outcome::Success(Authorized),
+            false => request::Outcome::Failure((Status::Forbidden, ())),
+        }
+    }
+}
+
+#[get("/admin", extensions = "admin")]
+fn admin_panel(_auth: Authorized) -> &'static str { "admin" }
+
+#[get("/open")]
+fn open(_auth: Authorized) -> &'static str { "open" }
+
+#[derive(Clone)]
+struct Static(&'static str);
+
+#[rocket::async_trait]
+impl Handler for Static {
+    async fn handle<'r, 's: 'r>(&'s self, req: &'r Request<'_>, _: Data) -> Outcome<'r> {
+        Outcome::from(req, self.0)
+    }
+}
+
+fn client() -> Client {
+    let manual = Route::new(Method::Get, "/manual", Static("manual"))
+        .with_extension(CacheTtl(5));
+
+    let rocket = rocket::ignite()
+        .mount("/", routes![admin_panel, open])
+        .mount("/", vec![manual])
+        .attach(AdHoc::on_response("Cache Control", |req, res| Box::pin(async move {
+            let ttl = req.route().and_then(|route| route.extensions.get::<CacheTtl>());
+            if let Some(ttl) = ttl {
+                res.set_header(Header::new("Cache-Control", format!("max-age={}", ttl.0)));
+            }
+        })));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn attribute_extensions_are_visible_to_guards() {
+    let client = client();
+    let response = client.get("/admin").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.get("/admin").header(Header::new("X-Scope", "admin")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "admin");
+
+    let response = client.get("/open").dispatch();
+    assert_eq!(response.into_string().unwrap(), "open");
+}
+
+#[test]
+fn extensions_are_visible_to_fairings() {
+    let client = client();
+    let response = client.get("/admin").header(Header::new("X-Scope", "admin")).dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=60"));
+
+    let response = client.get("/manual").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=5"));
+
+    let response = client.get("/open").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), None);
+}
@@ -0,0 +1,146 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use rocket::{Config, Response, State};
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::http::CookieJar;
+use rocket::tokio::sync::mpsc;
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[post("/echo/<name>?<q>", data = "<body>")]
+fn echo(name: String, q: String, body: String, jar: &CookieJar<'_>) -> String {
+    let cookie = jar.get("c").map(|c| c.value().to_string()).unwrap_or_default();
+    format!("{}:{}:{}:{}", name, q, body, cookie)
+}
+
+#[get("/remote")]
+fn remote(remote: SocketAddr) -> String {
+    remote.to_string()
+}
+
+type Log = mpsc::UnboundedSender<&'static str>;
+
+#[get("/export")]
+fn export(log: State<'_, Log>) -> Response<'static> {
+    let log = log.inner().clone();
+    Response::build()
+        .sized_body(4, std::io::Cursor::new("data"))
+        .after_sent(async move { log.send("cleaned up").unwrap() })
+        .finalize()
+}
+
+async fn launch() -> (u16, mpsc::UnboundedReceiver<&'static str>) {
+    let (tx, rx) = oneshot::channel();
+    let (log, events) = mpsc::unbounded_channel();
+    let completions: Log = log.clone();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![echo, remote, export])
+        .manage(log)
+        .attach(AdHoc::on_complete("Log Completion", move |req, completion| {
+            let log = completions.clone();
+            let export = req.uri().path() == "/export";
+            let written = completion.is_complete() && completion.bytes_written() == 4;
+            Box::pin(async move {
+                if export && written {
+                    log.send("completed").unwrap();
+                }
+            })
+        }))
+        .attach(AdHoc::on_launch("Send Port", move |rocket| {
+            tx.send(rocket.config().port).unwrap();
+        }));
+
+    rocket::tokio::spawn(rocket.launch_scgi());
+    (rx.await.unwrap(), events)
+}
+
+async fn request(port: u16, vars: &[(&str, &str)], body: &str) -> String {
+    let mut header = vec![];
+    for (name, value) in vars {
+        header.extend_from_slice(name.as_bytes());
+        header.push(0);
+        header.extend_from_slice(value.as_bytes());
+        header.push(0);
+    }
+
+    let mut message = format!("{}:", header.len()).into_bytes();
+    message.extend_from_slice(&header);
+    message.push(b',');
+    message.extend_from_slice(body.as_bytes());
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    stream.write_all(&message).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[rocket::async_test]
+async fn serves_routes_over_scgi() {
+    let (port, _) = launch().await;
+    let response = request(port, &[
+        ("CONTENT_LENGTH", "5"),
+        ("SCGI", "1"),
+        ("REQUEST_METHOD", "POST"),
+        ("REQUEST_URI", "/echo/a%20b?q=x%26y"),
+        ("CONTENT_TYPE", "text/plain"),
+        ("HTTP_COOKIE", "c=yum"),
+        ("REMOTE_ADDR", "10.0.0.1"),
+        ("REMOTE_PORT", "4321"),
+    ], "hello").await;
+
+    assert!(response.starts_with("Status: 200 OK\r\n"), "{}", response);
+    assert!(response.contains("Content-Length: 15\r\n"));
+    assert!(response.ends_with("\r\n\r\na b:x&y:hello:yum"));
+
+    let response = request(port, &[
+        ("CONTENT_LENGTH", "0"),
+        ("REQUEST_METHOD", "GET"),
+        ("SCRIPT_NAME", ""),
+        ("PATH_INFO", "/remote"),
+        ("REMOTE_ADDR", "10.0.0.1"),
+        ("REMOTE_PORT", "4321"),
+    ], "").await;
+
+    assert!(response.ends_with("\r\n\r\n10.0.0.1:4321"));
+}
+
+#[rocket::async_test]
+async fn errors_over_scgi() {
+    let (port, _) = launch().await;
+    let response = request(port, &[
+        ("CONTENT_LENGTH", "0"),
+        ("REQUEST_METHOD", "GET"),
+        ("REQUEST_URI", "/missing"),
+    ], "").await;
+
+    assert!(response.starts_with("Status: 404 Not Found\r\n"));
+
+    let response = request(port, &[
+        ("CONTENT_LENGTH", "0"),
+        ("REQUEST_METHOD", "BREW"),
+        ("REQUEST_URI", "/"),
+    ], "").await;
+
+    assert!(response.starts_with("Status: 400 Bad Request\r\n"));
+}
+
+#[rocket::async_test]
+async fn response_cleanup_and_completion_run_over_scgi() {
+    let (port, mut log) = launch().await;
+    let response = request(port, &[
+        ("CONTENT_LENGTH", "0"),
+        ("REQUEST_METHOD", "GET"),
+        ("REQUEST_URI", "/export"),
+    ], "").await;
+
+    assert!(response.ends_with("\r\n\r\ndata"));
+
+    // Cleanup runs in a separate task, so the two may arrive in any order.
+    let mut events = vec![log.recv().await.unwrap(), log.recv().await.unwrap()];
+    events.sort();
+    assert_eq!(events, vec!["cleaned up", "completed"]);
+}
@@ -0,0 +1,52 @@
+use rocket::figment::{Figment, Jail};
+use rocket::config::SecretKey;
+
+const KEY: &str = "hPRYyVRiMyxpw5sBB1XeCMN1kFsDCqKvBi2QJxBVHQk=";
+
+#[test]
+fn secret_key_from_file_and_env() {
+    Jail::expect_with(|jail| {
+        let expected: SecretKey = Figment::from(("secret_key", KEY)).extract_inner("secret_key")?;
+
+        jail.create_file("key.b64", &format!("{}\n", KEY))?;
+        let figment = Figment::from(("secret_key", rocket::figment::util::map!["file" => "key.b64"]));
+        let key: SecretKey = figment.extract_inner("secret_key")?;
+        assert!(key.is_provided());
+        assert_eq!(key, expected);
+
+        jail.set_env("MY_SECRET_KEY", KEY);
+        let figment = Figment::from(("secret_key", rocket::figment::util::map!["env" => "MY_SECRET_KEY"]));
+        let key: SecretKey = figment.extract_inner("secret_key")?;
+        assert_eq!(key, expected);
+
+        let figment = Figment::from(("secret_key", rocket::figment::util::map!["env" => "NOT_SET"]));
+        assert!(figment.extract_inner::<SecretKey>("secret_key").is_err());
+
+        let figment = Figment::from(("secret_key", rocket::figment::util::map!["url" => "x"]));
+        assert!(figment.extract_inner::<SecretKey>("secret_key").is_err());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn weak_secret_keys_detected() {
+    let key: SecretKey = Figment::from(("secret_key", KEY)).extract_inner("secret_key").unwrap();
+    assert!(!key.is_weak());
+
+    let key: SecretKey = Figment::from(("secret_key", vec![7u8; 32]))
+        .extract_inner("secret_key")
+        .unwrap();
+
+    assert!(key.is_weak());
+
+    let key: SecretKey = Figment::from(("secret_key", (0..64).map(|i| i % 4).collect::<Vec<u8>>()))
+        .extract_inner("secret_key")
+        .unwrap();
+
+    assert!(key.is_weak());
+
+    let generated = SecretKey::keygen().unwrap();
+    let key: SecretKey = Figment::from(("secret_key", generated)).extract_inner("secret_key").unwrap();
+    assert!(key.is_provided() && !key.is_weak());
+}
@@ -0,0 +1,59 @@
+#[macro_use] extern crate rocket;
+
+use rocket::config::{Config, Ident};
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+fn client(ident: Ident) -> Client {
+    let config = Config { ident, ..Config::debug_default() };
+    Client::tracked(rocket::custom(config).mount("/", routes![index])).unwrap()
+}
+
+#[test]
+fn default_ident_is_rocket() {
+    let client = client(Ident::default());
+    assert_eq!(client.get("/").dispatch().headers().get_one("Server"), Some("Rocket"));
+
+    let response = client.get("/missing").dispatch();
+    assert!(response.into_string().unwrap().contains("<small>Rocket</small>"));
+}
+
+#[test]
+fn custom_ident_is_sent_and_shown() {
+    let client = client(Ident::try_new("Acme <Edge>").unwrap());
+    assert_eq!(client.get("/").dispatch().headers().get_one("Server"), Some("Acme <Edge>"));
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.headers().get_one("Server"), Some("Acme <Edge>"));
+
+    let body = response.into_string().unwrap();
+    assert!(body.contains("<small>Acme &lt;Edge&gt;</small>"));
+    assert!(body.contains("404: Not Found"));
+    assert!(!body.contains("Rocket"));
+}
+
+#[test]
+fn hidden_ident_is_never_revealed() {
+    let client = client(Ident::none());
+    assert!(client.get("/").dispatch().headers().get_one("Server").is_none());
+
+    let response = client.get("/missing").dispatch();
+    assert!(response.headers().get_one("Server").is_none());
+
+    let body = response.into_string().unwrap();
+    assert!(body.contains("404: Not Found"));
+    assert!(!body.contains("Rocket"));
+    assert!(!body.contains("contentinfo"));
+}
+
+#[test]
+fn invalid_idents_are_rejected() {
+    assert!(Ident::try_new("").is_err());
+    assert!(Ident::try_new("Acme\r\nX-Injected: yes").is_err());
+}
@@ -0,0 +1,72 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Config;
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+use rocket::request::{ServerTiming, TimingsMode};
+
+#[get("/")]
+fn index(st: &ServerTiming) -> &'static str {
+    st.add("db", Duration::from_micros(12300), "query");
+    st.add_metric("cache", None, Some("a \"hot\" miss"));
+    st.add_metric("bad name", None, None::<String>);
+    st.add_metric("split", None, Some("a\r\nb\0c"));
+    "index"
+}
+
+#[get("/quiet")]
+fn quiet() -> &'static str {
+    "quiet"
+}
+
+fn client(timings: TimingsMode) -> Client {
+    let config = Config { timings, ..Config::debug_default() };
+    let rocket = rocket::custom(config)
+        .mount("/", routes![index, quiet])
+        .attach(AdHoc::on_response("Metric", |req, _| Box::pin(async move {
+            if req.uri().path() == "/" {
+                ServerTiming::of(req).add_metric("fairing", None, None::<String>);
+            }
+        })));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn metrics_are_emitted_in_order() {
+    let client = client(TimingsMode::Off);
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("Server-Timing"), Some(concat!(
+        r#"db;desc="query";dur=12.300, cache;desc="a \"hot\" miss", bad_name, "#,
+        r#"split;desc="a  b c", fairing"#
+    )));
+}
+
+#[test]
+fn no_header_without_metrics() {
+    let client = client(TimingsMode::Off);
+    let response = client.get("/quiet").dispatch();
+    assert!(response.headers().get_one("Server-Timing").is_none());
+}
+
+#[test]
+fn metrics_follow_recorded_timings() {
+    let client = client(TimingsMode::Header);
+    let response = client.get("/").dispatch();
+    let values: Vec<_> = response.headers().get("Server-Timing").collect();
+    assert_eq!(values.len(), 1);
+
+    let metrics: Vec<_> = values[0].split(", ").collect();
+    assert!(metrics[0].starts_with("guard;desc=\"&ServerTiming\";dur="));
+    assert!(metrics[1].starts_with("handler;desc=\"index\";dur="));
+    assert!(metrics[2].starts_with("responder;desc=\"index\";dur="));
+    assert_eq!(&metrics[3..], &[
+        "db;desc=\"query\";dur=12.300",
+        "cache;desc=\"a \\\"hot\\\" miss\"",
+        "bad_name",
+        "split;desc=\"a  b c\"",
+        "fairing",
+    ]);
+}
@@ -0,0 +1,91 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocket::{Config, State, Shutdown, ShutdownSignal};
+use rocket::channel::Channel;
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::response::Stream;
+use rocket::tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::task::JoinHandle;
+use rocket::tokio::time::{sleep, timeout};
+
+type Events = Arc<Channel<String>>;
+
+#[get("/events")]
+fn events(events: State<'_, Events>, signal: ShutdownSignal) -> Stream<impl AsyncRead + Send> {
+    let reader = events.subscribe("events")
+        .until(signal)
+        .followed_by("bye".into())
+        .reader(|msg| format!("data: {}\n\n", msg).into());
+
+    Stream::from(reader)
+}
+
+#[get("/forever")]
+fn forever(events: State<'_, Events>) -> Stream<impl AsyncRead + Send> {
+    Stream::from(events.subscribe("forever").reader(|msg| msg.into()))
+}
+
+async fn launch(shutdown_grace: u32, events: Events) -> (u16, Shutdown, JoinHandle<bool>) {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket::custom(Config { port: 0, shutdown_grace, ..Config::debug_default() })
+        .mount("/", routes![events, forever])
+        .manage(events)
+        .attach(AdHoc::on_launch("Send Port", move |rocket| {
+            tx.send(rocket.config().port).unwrap();
+        }));
+
+    let shutdown = rocket.shutdown();
+    let server = rocket::tokio::spawn(async move { rocket.launch().await.is_ok() });
+    (rx.await.unwrap(), shutdown, server)
+}
+
+async fn subscribe(port: u16, path: &str, events: &Events, topic: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    while events.subscribers(topic) == 0 {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    stream
+}
+
+#[rocket::async_test]
+async fn streams_end_with_final_event_on_shutdown() {
+    let events = Events::new(Channel::new(8));
+    let (port, shutdown, server) = launch(0, events.clone()).await;
+    let mut stream = subscribe(port, "/events", &events, "events").await;
+
+    events.send("events", "hello".into());
+    shutdown.shutdown();
+
+    let mut response = String::new();
+    timeout(Duration::from_secs(5), stream.read_to_string(&mut response)).await
+        .expect("stream ended")
+        .unwrap();
+
+    assert!(response.contains("data: hello\n\n"));
+    assert!(response.contains("data: bye\n\n"));
+    assert!(timeout(Duration::from_secs(5), server).await.expect("server stopped").unwrap());
+}
+
+#[rocket::async_test]
+async fn remaining_connections_close_after_grace_period() {
+    let events = Events::new(Channel::new(8));
+    let (port, shutdown, server) = launch(1, events.clone()).await;
+    let mut stream = subscribe(port, "/forever", &events, "forever").await;
+
+    shutdown.shutdown();
+    assert!(timeout(Duration::from_secs(5), server).await.expect("server stopped").unwrap());
+
+    let mut response = Vec::new();
+    timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await
+        .expect("connection closed")
+        .unwrap();
+}
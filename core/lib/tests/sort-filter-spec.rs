@@ -0,0 +1,94 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::Status;
+use rocket::request::{SortSpec, FilterSpec, Order, FilterOp};
+use rocket::local::blocking::Client;
+
+#[derive(Debug, PartialEq, FromFormValue)]
+enum Field {
+    #[form(value = "created_at")]
+    CreatedAt,
+    Name,
+}
+
+#[get("/sort")]
+fn sort(spec: SortSpec<Field>) -> String {
+    spec.iter()
+        .map(|key| format!("{:?}:{:?}", key.field, key.order))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[get("/filter")]
+fn filter(spec: FilterSpec<Field>) -> String {
+    spec.iter()
+        .map(|f| format!("{:?}:{:?}:{}", f.field, f.op, f.value))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[catch(400)]
+async fn bad_request(request: &Request<'_>) -> String {
+    let error = match request.uri().path() {
+        "/sort" => request.guard::<SortSpec<Field>>().await.failed(),
+        _ => request.guard::<FilterSpec<Field>>().await.failed(),
+    };
+
+    error.map(|(_, e)| e.to_string()).unwrap_or_default()
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![sort, filter])
+        .register(catchers![bad_request]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+fn get(client: &Client, uri: &str) -> (Status, String) {
+    let response = client.get(uri.to_string()).dispatch();
+    (response.status(), response.into_string().unwrap_or_default())
+}
+
+#[test]
+fn parses_sort_spec() {
+    let client = client();
+    assert_eq!(get(&client, "/sort"), (Status::Ok, "".into()));
+    assert_eq!(get(&client, "/sort?sort=name"), (Status::Ok, "Name:Asc".into()));
+    assert_eq!(get(&client, "/sort?sort=-created_at,%2Bname"),
+        (Status::Ok, "CreatedAt:Desc,Name:Asc".into()));
+    assert_eq!(get(&client, "/sort?sort=NAME,-Created_At"),
+        (Status::Ok, "Name:Asc,CreatedAt:Desc".into()));
+
+    assert_eq!(get(&client, "/sort?sort=email"),
+        (Status::BadRequest, "unknown field `email`".into()));
+    assert_eq!(get(&client, "/sort?sort=name,,created_at"),
+        (Status::BadRequest, "empty field name".into()));
+    assert_eq!(get(&client, "/sort?sort=name,-name"),
+        (Status::BadRequest, "field `name` appears more than once".into()));
+}
+
+#[test]
+fn parses_filter_spec() {
+    let client = client();
+    assert_eq!(get(&client, "/filter?page=2"), (Status::Ok, "".into()));
+    assert_eq!(get(&client, "/filter?filter%5Bname%5D=Bob%20B&filter%5Bcreated_at%5D%5Bgte%5D=2020"),
+        (Status::Ok, "Name:Eq:Bob B,CreatedAt:Gte:2020".into()));
+    assert_eq!(get(&client, "/filter?filter%5Bname%5D%5Bin%5D=a,b"),
+        (Status::Ok, "Name:In:a,b".into()));
+
+    assert_eq!(get(&client, "/filter?filter%5Bemail%5D=x"),
+        (Status::BadRequest, "unknown field `email`".into()));
+    assert_eq!(get(&client, "/filter?filter%5Bname%5D%5Blike%5D=x"),
+        (Status::BadRequest, "unknown filter operator `like`".into()));
+    assert_eq!(get(&client, "/filter?filter%5Bname%5Dx=1"),
+        (Status::BadRequest, "malformed filter parameter `filter[name]x`".into()));
+}
+
+#[test]
+fn order_and_op_are_exposed() {
+    assert_ne!(Order::Asc, Order::Desc);
+    assert_eq!("gte".parse::<FilterOp>(), Ok(FilterOp::Gte));
+    assert!("ge".parse::<FilterOp>().is_err());
+}
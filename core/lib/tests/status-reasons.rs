@@ -0,0 +1,59 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[get("/tuple")]
+fn tuple() -> (Status, &'static str) {
+    (Status::Accepted, "queued")
+}
+
+#[get("/triple")]
+fn triple() -> (ContentType, Status, &'static str) {
+    (ContentType::JSON, Status::Created, "{}")
+}
+
+#[get("/custom")]
+fn custom() -> Status {
+    Status::raw(499)
+}
+
+#[catch(404)]
+fn not_found(_: &Request<'_>) -> (Status, &'static str) {
+    (Status::new(404, "Gone Fishing"), "nothing here")
+}
+
+fn client() -> Client {
+    Status::register_reason(499, "Client Closed Request");
+    let rocket = rocket::ignite()
+        .mount("/", routes![tuple, triple, custom])
+        .register(catchers![not_found]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn tuple_responders_set_status_and_content_type() {
+    let client = client();
+    let response = client.get("/tuple").dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().unwrap(), "queued");
+
+    let response = client.get("/triple").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+}
+
+#[test]
+fn registered_reasons_and_catcher_reasons() {
+    let client = client();
+    let response = client.get("/custom").dispatch();
+    assert_eq!(response.status().reason, "Client Closed Request");
+    assert!(response.into_string().unwrap().contains("499: Client Closed Request"));
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::new(404, "Gone Fishing"));
+    assert_eq!(response.into_string().unwrap(), "nothing here");
+}
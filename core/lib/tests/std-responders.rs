@@ -0,0 +1,56 @@
+#[macro_use] extern crate rocket;
+
+use std::borrow::Cow;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[get("/cow/<owned>")]
+fn cow(owned: bool) -> Cow<'static, str> {
+    match owned {
+        true => Cow::Owned("owned".to_string()),
+        false => Cow::Borrowed("borrowed"),
+    }
+}
+
+#[get("/boxed")]
+fn boxed() -> Box<str> {
+    "boxed".into()
+}
+
+#[get("/bytes")]
+fn bytes() -> Box<[u8]> {
+    vec![1, 2, 3].into_boxed_slice()
+}
+
+#[delete("/unit")]
+fn unit() { }
+
+#[put("/unit")]
+fn unit_with_status() -> (Status, ()) {
+    (Status::Accepted, ())
+}
+
+#[test]
+fn std_types_respond() {
+    let rocket = rocket::ignite().mount("/", routes![cow, boxed, bytes, unit, unit_with_status]);
+    let client = Client::tracked(rocket).unwrap();
+
+    assert_eq!(client.get("/cow/true").dispatch().into_string().unwrap(), "owned");
+    assert_eq!(client.get("/cow/false").dispatch().into_string().unwrap(), "borrowed");
+
+    let response = client.get("/boxed").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().unwrap(), "boxed");
+
+    let response = client.get("/bytes").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Binary));
+    assert_eq!(response.into_bytes().unwrap(), vec![1, 2, 3]);
+
+    let response = client.delete("/unit").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert!(response.into_bytes().unwrap_or_default().is_empty());
+
+    let response = client.put("/unit").dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+}
@@ -0,0 +1,100 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rocket::{Config, State};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::tokio::sync::Mutex;
+use rocket::upload::{LocalStorage, Storage, TempFile};
+
+#[derive(Default)]
+struct MemoryStorage(Mutex<Vec<(String, u64, Vec<u8>)>>);
+
+#[rocket::async_trait]
+impl Storage for MemoryStorage {
+    async fn persist(&self, temp: &Path, len: u64, key: &str) -> io::Result<()> {
+        let bytes = rocket::tokio::fs::read(temp).await?;
+        self.0.lock().await.push((key.into(), len, bytes));
+        Ok(())
+    }
+}
+
+#[post("/local/<key>", data = "<file>")]
+async fn local(key: String, mut file: TempFile, root: State<'_, PathBuf>) -> io::Result<String> {
+    let temp = file.path().to_path_buf();
+    file.persist_in(&LocalStorage::new(root.inner()), &key).await?;
+    Ok(format!("{}", temp.exists()))
+}
+
+#[post("/memory/<key>", data = "<file>")]
+async fn memory(
+    key: String,
+    mut file: TempFile,
+    storage: State<'_, MemoryStorage>
+) -> io::Result<String> {
+    let temp = file.path().to_path_buf();
+    file.persist_in(storage.inner(), &key).await?;
+    Ok(format!("{}", temp.exists()))
+}
+
+#[post("/discard", data = "<file>")]
+fn discard(file: TempFile) -> String {
+    file.path().display().to_string()
+}
+
+fn root() -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rocket-temp-file-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    path
+}
+
+fn client(root: &Path) -> Client {
+    let config = Config {
+        limits: Limits::default().limit("file", 8.bytes()),
+        ..Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config)
+        .mount("/", routes![local, memory, discard])
+        .manage(root.to_path_buf())
+        .manage(MemoryStorage::default());
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn temp_files_persist_in_local_storage() {
+    let root = root();
+    let client = client(&root);
+
+    let response = client.post("/local/avatar.png").body("hello").dispatch();
+    assert_eq!(response.into_string().unwrap(), "false");
+    assert_eq!(std::fs::read_to_string(root.join("avatar.png")).unwrap(), "hello");
+
+    let response = client.post("/local/%2E%2E").body("hello").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn temp_files_persist_in_custom_storage() {
+    let client = client(&root());
+    let response = client.post("/memory/blob.bin").body("bytes").dispatch();
+    assert_eq!(response.into_string().unwrap(), "false");
+
+    let storage = client.rocket().state::<MemoryStorage>().unwrap();
+    let stored = storage.0.try_lock().unwrap();
+    assert_eq!(&*stored, &[("blob.bin".to_string(), 5, b"bytes".to_vec())]);
+}
+
+#[test]
+fn temp_files_are_limited_and_removed_on_drop() {
+    let client = client(&root());
+    let path = client.post("/discard").body("data").dispatch().into_string().unwrap();
+    assert!(!Path::new(&path).exists());
+
+    let response = client.post("/discard").body("too much data").dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+}
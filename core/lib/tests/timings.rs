@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::Config;
+use rocket::data::Data;
+use rocket::fairing::AdHoc;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request, Timings, Timing, Phase, TimingsMode};
+
+struct Admin;
+
+#[rocket::async_trait]
+impl<'a, 'r> FromRequest<'a, 'r> for Admin {
+    type Error = ();
+
+    async fn from_request(req: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        match req.headers().contains("X-Admin") {
+            true => request::Outcome::Success(Admin),
+            false => request::Outcome::Failure((Status::Forbidden, ())),
+        }
+    }
+}
+
+#[post("/", data = "<_data>")]
+fn index(_admin: Admin, _data: Data) -> &'static str {
+    "ok"
+}
+
+#[derive(Default, Clone)]
+struct Recorded(Arc<Mutex<Vec<Timing>>>);
+
+fn client(mode: TimingsMode, recorded: Recorded) -> Client {
+    let config = Config { timings: mode, ..Config::debug_default() };
+    let rocket = rocket::custom(config)
+        .mount("/", routes![index])
+        .attach(AdHoc::on_response("Record", move |req, _| {
+            let recorded = recorded.clone();
+            Box::pin(async move {
+                *recorded.0.lock().unwrap() = Timings::of(req).map(|t| t.all()).unwrap_or_default();
+            })
+        }));
+
+    Client::tracked(rocket).unwrap()
+}
+
+fn phases(recorded: &Recorded) -> Vec<(Phase, &'static str)> {
+    recorded.0.lock().unwrap().iter().map(|t| (t.phase, t.name)).collect()
+}
+
+#[test]
+fn every_phase_is_recorded() {
+    let recorded = Recorded::default();
+    let client = client(TimingsMode::Record, recorded.clone());
+    let response = client.post("/").header(rocket::http::Header::new("X-Admin", "1")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("Server-Timing").is_none());
+    assert_eq!(phases(&recorded), vec![
+        (Phase::Guard, "Admin"),
+        (Phase::DataGuard, "Data"),
+        (Phase::Handler, "index"),
+        (Phase::Responder, "index"),
+    ]);
+}
+
+#[test]
+fn failing_guards_are_recorded() {
+    let recorded = Recorded::default();
+    let client = client(TimingsMode::Record, recorded.clone());
+    let response = client.post("/").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(phases(&recorded), vec![(Phase::Guard, "Admin")]);
+}
+
+#[test]
+fn header_mode_emits_server_timing() {
+    let client = client(TimingsMode::Header, Recorded::default());
+    let response = client.post("/").header(rocket::http::Header::new("X-Admin", "1")).dispatch();
+    let header = response.headers().get_one("Server-Timing").unwrap();
+    let metrics: Vec<_> = header.split(", ").collect();
+    assert_eq!(metrics.len(), 4);
+    assert!(metrics[0].starts_with("guard;desc=\"Admin\";dur="));
+    assert!(metrics[1].starts_with("data;desc=\"Data\";dur="));
+    assert!(metrics[2].starts_with("handler;desc=\"index\";dur="));
+    assert!(metrics[3].starts_with("responder;desc=\"index\";dur="));
+}
+
+#[test]
+fn nothing_is_recorded_when_off() {
+    let recorded = Recorded::default();
+    let client = client(TimingsMode::Off, recorded.clone());
+    let response = client.post("/").header(rocket::http::Header::new("X-Admin", "1")).dispatch();
+    assert!(response.headers().get_one("Server-Timing").is_none());
+    assert!(phases(&recorded).is_empty());
+}
@@ -0,0 +1,58 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::TraceContext;
+use rocket::local::blocking::Client;
+use rocket::http::Header;
+
+#[get("/")]
+fn index(trace: &TraceContext, again: &TraceContext) -> String {
+    assert_eq!(trace, again);
+    format!("{} {} {}", trace.traceparent(), trace.parent_id().unwrap_or_default(),
+        trace.tracestate().unwrap_or_default())
+}
+
+const PARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00";
+
+#[test]
+fn request_span_is_child_of_incoming_span() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![index])).unwrap();
+    let response = client.get("/")
+        .header(Header::new("traceparent", PARENT))
+        .header(Header::new("tracestate", "a=1"))
+        .header(Header::new("tracestate", "b=2"))
+        .dispatch();
+
+    let body = response.into_string().unwrap();
+    let fields: Vec<_> = body.split(' ').collect();
+    let child = TraceContext::parse(fields[0], None).unwrap();
+    assert_eq!(child.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+    assert_ne!(child.span_id(), "b7ad6b7169203331");
+    assert!(!child.is_sampled());
+    assert_eq!(fields[1], "b7ad6b7169203331");
+    assert_eq!(fields[2], "a=1,b=2");
+}
+
+#[test]
+fn invalid_or_missing_traceparent_starts_new_trace() {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![index])).unwrap();
+    let invalid = [
+        "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+        "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+        "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+        "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+        "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+        "garbage",
+    ];
+
+    for traceparent in invalid.iter() {
+        let response = client.get("/").header(Header::new("traceparent", *traceparent)).dispatch();
+        let body = response.into_string().unwrap();
+        let trace = TraceContext::parse(body.split(' ').next().unwrap(), None).unwrap();
+        assert_ne!(trace.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+        assert!(trace.is_sampled());
+        assert!(body.ends_with("  "), "no parent or state: {:?}", body);
+    }
+
+    let future = "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra";
+    assert!(TraceContext::parse(future, None).is_some());
+}
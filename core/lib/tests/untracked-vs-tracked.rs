@@ -27,7 +27,7 @@ mod many_cookie_jars_tests {
         let client = Client::tracked(rocket()).unwrap();
 
         assert_eq!(client.get("/").dispatch().status(), Status::NotFound);
-        assert_eq!(client.post("/").dispatch().status(), Status::Ok);
+        assert_eq!(client.post("/").dispatch().status(), Status::NoContent);
 
         let response = client.get("/").dispatch();
         assert_eq!(response.status(), Status::Ok);
@@ -39,7 +39,7 @@ mod many_cookie_jars_tests {
         let client = Client::untracked(rocket()).unwrap();
 
         assert_eq!(client.get("/").dispatch().status(), Status::NotFound);
-        assert_eq!(client.post("/").dispatch().status(), Status::Ok);
+        assert_eq!(client.post("/").dispatch().status(), Status::NoContent);
         assert_eq!(client.get("/").dispatch().status(), Status::NotFound);
     }
 }
@@ -0,0 +1,58 @@
+#![cfg(feature = "checksums")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Algorithm, Checksums};
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::upload::TempFile;
+
+const MD5: &str = "XUFAKrxLKna5cZ2REBfFkg==";
+const SHA256: &str = "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+
+#[post("/", data = "<file>")]
+fn upload(file: TempFile) -> String {
+    let digests = file.digests();
+    format!("{} {} {:?}", digests.md5().is_some(), digests.sha256().is_some(), digests.crc32())
+}
+
+fn client(checksums: Vec<Algorithm>) -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![upload])
+        .manage(Checksums(checksums));
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn configured_digests_are_computed() {
+    let client = client(vec![Algorithm::Crc32]);
+    let response = client.post("/").body("hello").dispatch();
+    assert_eq!(response.into_string().unwrap(), "false false Some(907060870)");
+}
+
+#[test]
+fn header_digests_are_verified() {
+    let client = client(vec![]);
+    let response = client.post("/")
+        .header(Header::new("Content-MD5", MD5))
+        .header(Header::new("Digest", format!("SHA-256={}", SHA256)))
+        .body("hello")
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "true true None");
+
+    let response = client.post("/")
+        .header(Header::new("Content-MD5", MD5))
+        .body("hello!")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = client.post("/")
+        .header(Header::new("Digest", format!("unknown=abc, sha-256={}", SHA256)))
+        .body("goodbye")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
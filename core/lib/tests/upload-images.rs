@@ -0,0 +1,64 @@
+#![cfg(feature = "images")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::upload::Image;
+
+#[post("/", data = "<image>")]
+fn upload(image: Image) -> String {
+    let valid = image.max_dimensions(4000, 4000).is_ok();
+    format!("{} {}x{} {}", image.format(), image.width(), image.height(), valid)
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+    png.extend(&width.to_be_bytes());
+    png.extend(&height.to_be_bytes());
+    png.extend(b"\x08\x06\x00\x00\x00");
+    png
+}
+
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    let mut jpeg = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00".to_vec();
+    jpeg.extend(b"\xFF\xC0\x00\x11\x08");
+    jpeg.extend(&height.to_be_bytes());
+    jpeg.extend(&width.to_be_bytes());
+    jpeg.extend(b"\x03\x01\x22\x00\x02\x11\x01\x03\x11\x01");
+    jpeg
+}
+
+fn post(content_type: ContentType, body: Vec<u8>) -> (Status, Option<String>) {
+    let client = Client::tracked(rocket::ignite().mount("/", routes![upload])).unwrap();
+    let response = client.post("/").header(content_type).body(body).dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn image_dimensions_are_read() {
+    let (status, body) = post(ContentType::PNG, png(640, 480));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), "image/png 640x480 true");
+
+    let (_, body) = post(ContentType::JPEG, jpeg(5000, 20));
+    assert_eq!(body.unwrap(), "image/jpeg 5000x20 false");
+
+    let (_, body) = post(ContentType::GIF, b"GIF89a\x20\x00\x10\x00\x00".to_vec());
+    assert_eq!(body.unwrap(), "image/gif 32x16 true");
+}
+
+#[test]
+fn non_images_are_rejected() {
+    let (status, _) = post(ContentType::PDF, b"%PDF-1.7".to_vec());
+    assert_eq!(status, Status::UnsupportedMediaType);
+
+    let (status, _) = post(ContentType::JPEG, png(640, 480));
+    assert_eq!(status, Status::UnsupportedMediaType);
+
+    let (status, _) = post(ContentType::PNG, png(640, 480)[..18].to_vec());
+    assert_eq!(status, Status::UnprocessableEntity);
+
+    let (status, _) = post(ContentType::JPEG, jpeg(640, 480)[..24].to_vec());
+    assert_eq!(status, Status::UnprocessableEntity);
+}
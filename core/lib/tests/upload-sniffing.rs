@@ -0,0 +1,55 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::upload::{FileTypeError, TempFile};
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+const ELF: &[u8] = b"\x7fELF\x02\x01\x01\x00";
+
+#[post("/", data = "<file>")]
+async fn upload(file: TempFile) -> Result<String, String> {
+    let sniffed = file.sniff().await.unwrap();
+    match file.validate_type(&[ContentType::PNG, ContentType::GIF]).await {
+        Ok(ct) => Ok(format!("{:?} {}", sniffed.map(|ct| ct.to_string()), ct)),
+        Err(FileTypeError::Mismatch { declared, detected }) => {
+            Err(format!("mismatch {} {}", declared, detected))
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::ignite().mount("/", routes![upload])).unwrap()
+}
+
+fn post(client: &Client, content_type: Option<ContentType>, body: &[u8]) -> String {
+    let mut request = client.post("/").body(body);
+    if let Some(content_type) = content_type {
+        request.add_header(content_type);
+    }
+
+    let response = request.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    response.into_string().unwrap()
+}
+
+#[test]
+fn matching_uploads_are_accepted() {
+    let client = client();
+    assert_eq!(post(&client, Some(ContentType::PNG), PNG), "Some(\"image/png\") image/png");
+    assert_eq!(post(&client, Some(ContentType::Binary), PNG), "Some(\"image/png\") image/png");
+    assert_eq!(post(&client, None, b"GIF89a\x01\x00"), "Some(\"image/gif\") image/gif");
+}
+
+#[test]
+fn disguised_uploads_are_rejected() {
+    let client = client();
+    assert_eq!(post(&client, Some(ContentType::PNG), ELF), "file is an executable");
+    assert_eq!(post(&client, Some(ContentType::PNG), b"MZ\x90\x00"), "file is an executable");
+    let pdf = b"%PDF-1.7";
+    assert_eq!(post(&client, Some(ContentType::PNG), pdf), "mismatch image/png application/pdf");
+    let response = post(&client, Some(ContentType::PDF), pdf);
+    assert_eq!(response, "file type application/pdf is not allowed");
+    assert_eq!(post(&client, Some(ContentType::PNG), b"plain text"), "file type is unrecognized");
+}
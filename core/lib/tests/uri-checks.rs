@@ -0,0 +1,60 @@
+#![cfg(feature = "uri-checks")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Rocket;
+use rocket::http::uri::Origin;
+use rocket::error::ErrorKind;
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/item/<id>")]
+fn item(id: usize) -> String { id.to_string() }
+
+#[allow(dead_code)]
+fn links() -> Vec<Origin<'static>> {
+    vec![uri!(index), uri!("/api", item: 7)]
+}
+
+fn rocket(check: bool) -> Rocket {
+    rocket::custom(rocket::Config::figment().merge(("check_uris", check)))
+}
+
+#[test]
+fn mounted_uris_pass() {
+    let rocket = rocket(true)
+        .mount("/", routes![index])
+        .mount("/api", routes![item]);
+
+    assert!(Client::tracked(rocket).is_ok());
+}
+
+#[test]
+fn unmounted_uris_fail_launch() {
+    let rocket = rocket(true).mount("/", routes![index, item]);
+    let error = Client::tracked(rocket).unwrap_err();
+    match error.kind() {
+        ErrorKind::DanglingUris(dangling) => {
+            assert_eq!(dangling.len(), 1);
+            let (route, location) = &dangling[0];
+            assert_eq!(route.name, Some("item"));
+            assert_eq!(route.uri.path(), "/api/item/<id>");
+            assert!(location.starts_with(file!()));
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn uris_of_unmounted_routes_are_ignored() {
+    let rocket = rocket(true).mount("/", routes![index]);
+    assert!(Client::tracked(rocket).is_ok());
+}
+
+#[test]
+fn checks_are_opt_in() {
+    let rocket = rocket(false).mount("/", routes![index]);
+    assert!(Client::tracked(rocket).is_ok());
+}
@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::{ContentType, Header, Status};
+use rocket::outcome::Outcome;
+use rocket::dav::{Depth, Destination, Overwrite, MultiStatus, DavResponse};
+use rocket::local::blocking::Client;
+
+#[put("/copy")]
+fn copy(destination: Destination, overwrite: Overwrite, depth: Option<Depth>) -> String {
+    let depth = depth.map(|d| d.to_string()).unwrap_or_else(|| "none".into());
+    format!("{} {:?} {} {}", destination.origin(), destination.authority(), overwrite.0, depth)
+}
+
+#[delete("/tree")]
+fn delete() -> MultiStatus {
+    MultiStatus::new()
+        .response(DavResponse::new("/tree/a&b").status(Status::Locked))
+        .response(DavResponse::new("/tree/c")
+            .prop(Status::Ok, "getcontentlength", "42")
+            .empty_prop(Status::NotFound, "getetag")
+            .prop(Status::Ok, "displayname", "<c>"))
+        .description("partial failure")
+}
+
+#[catch(400)]
+async fn bad_request(request: &Request<'_>) -> String {
+    let depth = request.guard::<Depth>().await;
+    let overwrite = request.guard::<Overwrite>().await;
+    match (depth, overwrite) {
+        (Outcome::Failure((_, e)), _) | (_, Outcome::Failure((_, e))) => e.to_string(),
+        _ => "ok".into(),
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::ignite()
+        .mount("/", routes![copy, delete])
+        .register(catchers![bad_request]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn header_guards() {
+    let client = client();
+    let put = |headers: &[(&'static str, &'static str)]| {
+        let mut request = client.put("/copy");
+        for (name, value) in headers {
+            request.add_header(Header::new(*name, *value));
+        }
+
+        let response = request.dispatch();
+        (response.status(), response.into_string().unwrap())
+    };
+
+    assert_eq!(put(&[("Destination", "http://example.com/a/b?c")]),
+        (Status::Ok, "/a/b?c Some(\"example.com\") true none".into()));
+    assert_eq!(put(&[("Destination", "/a"), ("Overwrite", "F"), ("Depth", "0")]),
+        (Status::Ok, "/a None false 0".into()));
+    assert_eq!(put(&[("Destination", "/a"), ("Overwrite", "T"), ("Depth", "Infinity")]),
+        (Status::Ok, "/a None true infinity".into()));
+
+    assert_eq!(put(&[]).0, Status::NotFound);
+    assert_eq!(put(&[("Destination", "/a"), ("Depth", "2")]),
+        (Status::BadRequest, "malformed `Depth` header: \"2\"".into()));
+    assert_eq!(put(&[("Destination", "/a"), ("Overwrite", "yes")]),
+        (Status::BadRequest, "malformed `Overwrite` header: \"yes\"".into()));
+    assert_eq!(put(&[("Destination", "a b")]).0, Status::BadRequest);
+}
+
+#[test]
+fn multi_status() {
+    let client = client();
+    let response = client.delete("/tree").dispatch();
+    assert_eq!(response.status(), Status::MultiStatus);
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "xml")));
+    assert_eq!(response.into_string().unwrap(), "\
+<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<D:multistatus xmlns:D=\"DAV:\">
+  <D:response>
+    <D:href>/tree/a&amp;b</D:href>
+    <D:status>HTTP/1.1 423 Locked</D:status>
+  </D:response>
+  <D:response>
+    <D:href>/tree/c</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:getcontentlength>42</D:getcontentlength>
+        <D:displayname>&lt;c&gt;</D:displayname>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+    <D:propstat>
+      <D:prop>
+        <D:getetag/>
+      </D:prop>
+      <D:status>HTTP/1.1 404 Not Found</D:status>
+    </D:propstat>
+  </D:response>
+  <D:responsedescription>partial failure</D:responsedescription>
+</D:multistatus>
+");
+}
@@ -6,7 +6,7 @@ fn test_push_pop() {
     let client = Client::tracked(super::rocket()).unwrap();
 
     let response = client.put("/push?event=test1").dispatch();
-    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.status(), Status::NoContent);
 
     let response = client.get("/pop").dispatch();
     assert_eq!(response.into_string(), Some("test1".to_string()));
@@ -82,10 +82,14 @@ if [ "$1" = "--contrib" ]; then
   FEATURES=(
     json
     msgpack
+    csv
     tera_templates
     handlebars_templates
     serve
     helmet
+    ip_filter
+    geoip_maxmind
+    otel
     diesel_postgres_pool
     diesel_sqlite_pool
     diesel_mysql_pool
@@ -112,6 +116,12 @@ elif [ "$1" = "--core" ]; then
   FEATURES=(
     secrets
     tls
+    client
+    checksums
+    images
+    uri-checks
+    anyhow
+    eyre
   )
 
   pushd "${CORE_LIB_ROOT}" > /dev/null 2>&1